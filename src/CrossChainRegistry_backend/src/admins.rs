@@ -0,0 +1,362 @@
+use crate::audit::AuditManager;
+use crate::community::CommunityValidationManager;
+use crate::monitoring::MonitoringSystem;
+use crate::reputation::{ReputationInputs, ReputationJournal};
+use crate::sanitize;
+use crate::security::SecurityLog;
+use crate::settings::SettingsManager;
+use crate::storage::StorageManager;
+use crate::timelock::TimelockManager;
+use crate::types::{
+    Admin, AlertSeverity, AlertType, Company, CompanyStatus, EmergencySuspension, RegistryResult,
+    ReputationEventKind, SecurityEventType, VerificationStatus, VerificationType,
+};
+use crate::verification::VerificationManager;
+use candid::Principal;
+use ic_cdk::api::time;
+use std::time::Duration;
+
+// Registry admins: principals the controllers appoint to run the registry without
+// handing out control of the canister itself. Controllers count as admins; admins count
+// as moderators. Every privileged call lands in the security log under the admin's
+// principal.
+//
+// The split: registry admins run the registry (actions on companies, partners, peers,
+// moderators and API keys, and triggering jobs), while canister-level configuration
+// (topology, the admin roster, integrations and registry-wide policy) stays with
+// SettingsManager::ensure_admin, controllers only.
+
+pub struct AdminManager;
+
+impl AdminManager {
+    const MAX_REASON_LENGTH: usize = 500;
+    const CONFIRMATION_WINDOW_NS: u64 = 24 * 60 * 60 * 1_000_000_000; // 24 hours
+    const LAPSE_CHECK_INTERVAL_SECS: u64 = 15 * 60;
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::LAPSE_CHECK_INTERVAL_SECS), || {
+            Self::lift_unconfirmed_suspensions();
+        });
+    }
+
+    pub fn is_admin(principal: Principal) -> bool {
+        SettingsManager::ensure_admin(principal).is_ok() || StorageManager::get_admin(&principal).is_some()
+    }
+
+    pub fn ensure_admin(principal: Principal) -> Result<(), String> {
+        if Self::is_admin(principal) {
+            Ok(())
+        } else {
+            Err("Unauthorized: Registry admin access required".to_string())
+        }
+    }
+
+    // Suspended companies are frozen: no new community actions and no verifications
+    // until an admin reinstates them
+    pub fn ensure_not_suspended(company: &Company) -> Result<(), String> {
+        if matches!(company.status, CompanyStatus::Suspended) {
+            Err(format!("Company {} is suspended", company.id))
+        } else {
+            Ok(())
+        }
+    }
+
+    // Roster (controllers only)
+
+    pub fn add_admin(principal: Principal, caller_principal: Principal) -> RegistryResult<Admin> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if principal == Principal::anonymous() {
+            return RegistryResult::Err("The anonymous principal cannot be an admin".to_string());
+        }
+        if StorageManager::get_admin(&principal).is_some() {
+            return RegistryResult::Err("Principal is already an admin".to_string());
+        }
+
+        let admin = Admin {
+            principal,
+            added_by: caller_principal,
+            added_at: time(),
+        };
+        StorageManager::insert_admin(admin.clone());
+        SecurityLog::record(principal, SecurityEventType::AdminAdded, None, format!("added by {}", caller_principal));
+        RegistryResult::Ok(admin)
+    }
+
+    pub fn remove_admin(principal: Principal, caller_principal: Principal) -> RegistryResult<()> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if StorageManager::remove_admin(&principal).is_none() {
+            return RegistryResult::Err("Admin not found".to_string());
+        }
+        SecurityLog::record(principal, SecurityEventType::AdminRemoved, None, format!("removed by {}", caller_principal));
+        RegistryResult::Ok(())
+    }
+
+    pub fn list_admins(caller_principal: Principal) -> RegistryResult<Vec<Admin>> {
+        if let Err(err) = Self::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let mut admins = StorageManager::get_all_admins();
+        admins.sort_by_key(|a| a.added_at);
+        RegistryResult::Ok(admins)
+    }
+
+    // Privileged moderation
+
+    // The one status change that skips the timelock: it takes effect at once, for abuse in
+    // progress, but a second admin must confirm it within the window or it lapses and the
+    // company is reinstated. Planned status changes go through the timelock.
+    pub fn suspend_company(company_id: String, reason: String, caller_principal: Principal) -> RegistryResult<()> {
+        if let Err(err) = Self::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let reason = match Self::validate_reason(&reason) {
+            Ok(reason) => reason,
+            Err(err) => return RegistryResult::Err(err),
+        };
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+        if matches!(company.status, CompanyStatus::Suspended) {
+            return RegistryResult::Err("Company is already suspended".to_string());
+        }
+
+        let now = time();
+        StorageManager::update_company(&company_id, |company| {
+            company.status = CompanyStatus::Suspended;
+        });
+        StorageManager::insert_emergency_suspension(EmergencySuspension {
+            company_id: company_id.clone(),
+            reason: reason.clone(),
+            suspended_by: caller_principal,
+            suspended_at: now,
+            confirm_by: now + Self::CONFIRMATION_WINDOW_NS,
+            confirmed_by: None,
+            confirmed_at: None,
+        });
+        Self::log_action(caller_principal, "suspend_company", &company_id, &reason);
+        MonitoringSystem::raise_alert(
+            &company_id,
+            AlertType::CompanySuspended,
+            AlertSeverity::Critical,
+            format!("Company suspended by a registry admin: {}", reason),
+            None,
+            &company_id,
+            &format!("suspended@{}", now),
+        );
+        RegistryResult::Ok(())
+    }
+
+    // Second admin's sign-off on an emergency suspension; it then stands until reinstated
+    pub fn confirm_suspension(company_id: String, caller_principal: Principal) -> RegistryResult<EmergencySuspension> {
+        if let Err(err) = Self::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let mut suspension = match StorageManager::get_emergency_suspension(&company_id) {
+            Some(suspension) => suspension,
+            None => return RegistryResult::Err("No emergency suspension found for this company".to_string()),
+        };
+        if suspension.confirmed_by.is_some() {
+            return RegistryResult::Err("Suspension is already confirmed".to_string());
+        }
+        if suspension.suspended_by == caller_principal {
+            return RegistryResult::Err("A different admin must confirm this suspension".to_string());
+        }
+        let now = time();
+        if now > suspension.confirm_by {
+            return RegistryResult::Err("The confirmation window has passed".to_string());
+        }
+
+        suspension.confirmed_by = Some(caller_principal);
+        suspension.confirmed_at = Some(now);
+        StorageManager::insert_emergency_suspension(suspension.clone());
+        Self::log_action(caller_principal, "confirm_suspension", &company_id, &suspension.reason);
+        RegistryResult::Ok(suspension)
+    }
+
+    pub fn get_emergency_suspensions(caller_principal: Principal) -> RegistryResult<Vec<EmergencySuspension>> {
+        if let Err(err) = Self::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let mut suspensions = StorageManager::get_all_emergency_suspensions();
+        suspensions.sort_by_key(|s| std::cmp::Reverse(s.suspended_at));
+        RegistryResult::Ok(suspensions)
+    }
+
+    // Reinstates companies whose emergency suspension nobody confirmed in time; returns
+    // how many were lifted
+    pub fn lift_unconfirmed_suspensions() -> u32 {
+        let now = time();
+        let mut lifted = 0;
+        for suspension in StorageManager::get_all_emergency_suspensions() {
+            if suspension.confirmed_by.is_some() || now <= suspension.confirm_by {
+                continue;
+            }
+            StorageManager::remove_emergency_suspension(&suspension.company_id);
+
+            // A permanent or timelocked suspension applied in the meantime stands on its own
+            let still_lapsable = StorageManager::get_company(&suspension.company_id).is_some_and(|company| {
+                matches!(company.status, CompanyStatus::Suspended) && company.permanently_suspended != Some(true)
+            }) && !TimelockManager::suspended_by_timelock(&suspension.company_id);
+            if !still_lapsable {
+                continue;
+            }
+            Self::lift_suspension(&suspension.company_id);
+            Self::log_action(
+                suspension.suspended_by,
+                "suspension_lapsed",
+                &suspension.company_id,
+                "no second admin confirmed it",
+            );
+            MonitoringSystem::raise_alert(
+                &suspension.company_id,
+                AlertType::CompanyReinstated,
+                AlertSeverity::Info,
+                "Emergency suspension lapsed without a second admin's confirmation".to_string(),
+                None,
+                &suspension.company_id,
+                &format!("lapsed@{}", now),
+            );
+            lifted += 1;
+        }
+        lifted
+    }
+
+    // Lifts a suspension; the status goes back to what the company's reputation supports.
+    // Permanent suspensions went through critical-action approval and can't be undone here,
+    // and a timelocked suspension is lifted by a timelocked action, with the same delay.
+    pub fn reinstate_company(company_id: String, caller_principal: Principal) -> RegistryResult<CompanyStatus> {
+        if let Err(err) = Self::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+        if !matches!(company.status, CompanyStatus::Suspended) {
+            return RegistryResult::Err("Company is not suspended".to_string());
+        }
+        if company.permanently_suspended == Some(true) {
+            return RegistryResult::Err("Company is permanently suspended".to_string());
+        }
+        if TimelockManager::suspended_by_timelock(&company_id) {
+            return RegistryResult::Err(
+                "This suspension went through the admin timelock; schedule a Pending status change to lift it".to_string(),
+            );
+        }
+
+        let status = Self::lift_suspension(&company_id);
+        Self::log_action(caller_principal, "reinstate_company", &company_id, "");
+        MonitoringSystem::raise_alert(
+            &company_id,
+            AlertType::CompanyReinstated,
+            AlertSeverity::Info,
+            "Company reinstated by a registry admin".to_string(),
+            None,
+            &company_id,
+            &format!("reinstated@{}", time()),
+        );
+        RegistryResult::Ok(status)
+    }
+
+    pub fn lift_suspension(company_id: &str) -> CompanyStatus {
+        StorageManager::remove_emergency_suspension(company_id);
+        let mut status = CompanyStatus::Pending;
+        StorageManager::update_company(company_id, |company| {
+            company.status = CompanyStatus::Pending;
+            let score = ReputationInputs::from_company(company).score();
+            CommunityValidationManager::set_reputation_score(company, score);
+            status = company.status.clone();
+        });
+        status
+    }
+
+    // Drops every proof of the type and the verification it backed
+    pub fn force_unverify(
+        company_id: String,
+        verification_type: VerificationType,
+        reason: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<u32> {
+        if let Err(err) = Self::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let reason = match Self::validate_reason(&reason) {
+            Ok(reason) => reason,
+            Err(err) => return RegistryResult::Err(err),
+        };
+
+        let same_type = |t: &VerificationType| std::mem::discriminant(t) == std::mem::discriminant(&verification_type);
+        let mut removed = Vec::new();
+        let success = StorageManager::update_company(&company_id, |company| {
+            let identity = &mut company.web3_identity;
+            identity.verification_proofs.retain(|proof| {
+                if same_type(&proof.verification_type) {
+                    removed.push((proof.proof_url.clone(), proof.id.clone()));
+                    false
+                } else {
+                    true
+                }
+            });
+            match verification_type {
+                VerificationType::Domain => identity.domain_verified = false,
+                VerificationType::GitHub => identity.github_org = None,
+                VerificationType::Twitter | VerificationType::Discord | VerificationType::Telegram => {
+                    let social_left = identity.verification_proofs.iter().any(|proof| {
+                        matches!(
+                            proof.verification_type,
+                            VerificationType::Twitter | VerificationType::Discord | VerificationType::Telegram
+                        )
+                    });
+                    if !social_left {
+                        identity.social_verification_status = VerificationStatus::Pending;
+                    }
+                }
+            }
+            company.verification_score = VerificationManager::calculate_verification_score(company);
+        });
+        if !success {
+            return RegistryResult::Err("Company not found".to_string());
+        }
+
+        for (proof_url, proof_id) in &removed {
+            ReputationJournal::record(
+                &company_id,
+                ReputationEventKind::ProofRemoved {
+                    proof_url: proof_url.clone(),
+                    proof_id: proof_id.clone(),
+                },
+                Some(caller_principal),
+            );
+        }
+        Self::log_action(
+            caller_principal,
+            "force_unverify",
+            &company_id,
+            &format!("{:?}: {}", verification_type, reason),
+        );
+        RegistryResult::Ok(removed.len() as u32)
+    }
+
+    fn validate_reason(reason: &str) -> Result<String, String> {
+        let reason = reason.trim();
+        if reason.is_empty() || sanitize::grapheme_len(reason) > Self::MAX_REASON_LENGTH {
+            return Err(format!("Reason must be 1-{} characters", Self::MAX_REASON_LENGTH));
+        }
+        Ok(reason.to_string())
+    }
+
+    fn log_action(caller_principal: Principal, action: &str, company_id: &str, details: &str) {
+        SecurityLog::record(
+            caller_principal,
+            SecurityEventType::AdminAction,
+            Some(company_id.to_string()),
+            format!("{}: {}", action, details),
+        );
+        AuditManager::record(action, company_id, vec![caller_principal], details.to_string());
+    }
+}
@@ -0,0 +1,98 @@
+use crate::admins::AdminManager;
+use crate::storage::StorageManager;
+use crate::types::{
+    AlertRoutingRule, AlertSeverity, AlertType, AlertVisibility, NotificationChannel,
+    RegistryResult,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Admin-managed policy deciding who sees an alert and who gets notified about it,
+// keyed by (alert type, severity). Combinations without a rule use the defaults below.
+
+pub struct AlertRoutingManager;
+
+impl AlertRoutingManager {
+    fn rule_key(alert_type: &AlertType, severity: &AlertSeverity) -> String {
+        format!("{:?}_{:?}", alert_type, severity)
+    }
+
+    // Public alerts; owners hear about errors, moderators about critical ones
+    pub fn default_route(severity: &AlertSeverity) -> (AlertVisibility, Vec<NotificationChannel>) {
+        let channels = match severity {
+            AlertSeverity::Info | AlertSeverity::Warning => vec![],
+            AlertSeverity::Error => vec![NotificationChannel::OwnerInbox],
+            AlertSeverity::Critical => vec![
+                NotificationChannel::OwnerInbox,
+                NotificationChannel::ModeratorQueue,
+            ],
+        };
+        (AlertVisibility::Public, channels)
+    }
+
+    pub fn resolve_route(
+        alert_type: &AlertType,
+        severity: &AlertSeverity,
+    ) -> (AlertVisibility, Vec<NotificationChannel>) {
+        match StorageManager::get_alert_routing_rule(&Self::rule_key(alert_type, severity)) {
+            Some(rule) => (rule.visibility, rule.channels),
+            None => Self::default_route(severity),
+        }
+    }
+
+    pub fn set_alert_routing_rule(
+        alert_type: AlertType,
+        severity: AlertSeverity,
+        visibility: AlertVisibility,
+        channels: Vec<NotificationChannel>,
+        caller_principal: Principal,
+    ) -> RegistryResult<AlertRoutingRule> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        let mut deduped: Vec<NotificationChannel> = Vec::new();
+        for channel in channels {
+            if !deduped.contains(&channel) {
+                deduped.push(channel);
+            }
+        }
+
+        let rule = AlertRoutingRule {
+            alert_type,
+            severity,
+            visibility,
+            channels: deduped,
+            updated_by: caller_principal,
+            updated_at: time(),
+        };
+
+        StorageManager::insert_alert_routing_rule(
+            Self::rule_key(&rule.alert_type, &rule.severity),
+            rule.clone(),
+        );
+        RegistryResult::Ok(rule)
+    }
+
+    pub fn remove_alert_routing_rule(
+        alert_type: AlertType,
+        severity: AlertSeverity,
+        caller_principal: Principal,
+    ) -> RegistryResult<()> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        match StorageManager::remove_alert_routing_rule(&Self::rule_key(&alert_type, &severity)) {
+            Some(_) => RegistryResult::Ok(()),
+            None => RegistryResult::Err("Alert routing rule not found".to_string()),
+        }
+    }
+
+    pub fn list_alert_routing_rules(caller_principal: Principal) -> RegistryResult<Vec<AlertRoutingRule>> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        RegistryResult::Ok(StorageManager::get_all_alert_routing_rules())
+    }
+}
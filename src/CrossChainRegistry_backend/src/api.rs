@@ -1,7 +1,23 @@
+use crate::admins::AdminManager;
+use crate::archive::ArchiveManager;
+use crate::banners::BannerManager;
+use crate::blocklist::BlocklistManager;
+use crate::canary::CanaryGuard;
+use crate::community::CommunityValidationManager;
+use crate::funding::FundingManager;
+use crate::hiring::HiringManager;
+use crate::inactivity::InactivityManager;
+use crate::ownership::OwnershipManager;
+use crate::reserved::ReservedNameManager;
+use crate::rotation::RotationManager;
+use crate::sanitize::{self, Handle, SafeText, SafeUrl};
+use crate::search_index::SearchIndex;
+use crate::sharding::ShardManager;
 use crate::storage::StorageManager;
 use crate::types::{
-    Company, CompanyStatus, CommunityValidation, CreateCompanyRequest, RegistryResult,
-    SearchFilters, UpdateCompanyRequest,
+    Company, CompanyBasicInfo, CompanyRole, CompanySection, CompanyStatus, CommunityPage, CommunityValidation, CreateCompanyRequest,
+    CrossChainPresence, EntityType, RegistryResult, SearchFilters, SearchField, SearchResult, TeamMember,
+    UpdateCompanyRequest, VerificationStatus, Web3Identity,
 };
 use crate::verification::VerificationManager;
 use candid::Principal;
@@ -13,101 +29,172 @@ use std::collections::HashMap;
 pub struct RegistryAPI;
 
 impl RegistryAPI {
-    // Security constants for input validation
-    const MAX_NAME_LENGTH: usize = 100;
-    const MAX_DESCRIPTION_LENGTH: usize = 2000;
-    const MAX_URL_LENGTH: usize = 500;
-    const MAX_MESSAGE_LENGTH: usize = 1000;
-    const MAX_SOCIAL_HANDLE_LENGTH: usize = 100;
-    const MAX_ADDRESS_LENGTH: usize = 100;
+    // Collection limits; string limits live in the sanitize policy
+    const MAX_FOCUS_AREAS: usize = 10;
     const MAX_TEAM_MEMBERS: usize = 50;
     const MAX_ADDRESSES_PER_CHAIN: usize = 20;
 
-    // Input validation functions
-    fn validate_string_length(value: &str, max_length: usize, field_name: &str) -> Result<(), String> {
-        if value.len() > max_length {
-            return Err(format!("{} exceeds maximum length of {} characters", field_name, max_length));
-        }
-        Ok(())
-    }
-
-    fn validate_company_request(request: &CreateCompanyRequest) -> Result<(), String> {
-        // Validate basic info
-        Self::validate_string_length(&request.basic_info.name, Self::MAX_NAME_LENGTH, "Company name")?;
-        Self::validate_string_length(&request.basic_info.description, Self::MAX_DESCRIPTION_LENGTH, "Description")?;
-        Self::validate_string_length(&request.basic_info.website, Self::MAX_URL_LENGTH, "Website URL")?;
-        Self::validate_string_length(&request.basic_info.founding_date, 20, "Founding date")?;
+    // Search ranking weights
+    const NAME_WEIGHT: u32 = 60;
+    const EXACT_NAME_BONUS: u32 = 40;
+    const PREFIX_NAME_BONUS: u32 = 20;
+    const FOCUS_AREA_WEIGHT: u32 = 30;
+    const DESCRIPTION_WEIGHT: u32 = 15;
+    const SNIPPET_CONTEXT_CHARS: usize = 40;
+
+    // Field validation shared by create and update; each section is optional on update
+    fn validate_sections(
+        basic_info: Option<&CompanyBasicInfo>,
+        web3_identity: Option<&Web3Identity>,
+        cross_chain_presence: Option<&CrossChainPresence>,
+        team_members: Option<&[TeamMember]>,
+    ) -> Result<(), String> {
+        let policy = sanitize::policy();
+
+        if let Some(basic_info) = basic_info {
+            SafeText::parse_required(&basic_info.name, "Company name", policy.max_name)?;
+            SafeText::parse(&basic_info.description, "Description", policy.max_description)?;
+            if !basic_info.website.trim().is_empty() {
+                SafeUrl::parse_web(&basic_info.website, "Website URL")?;
+            }
+            SafeText::parse(&basic_info.founding_date, "Founding date", 20)?;
 
-        if request.basic_info.name.trim().is_empty() {
-            return Err("Company name cannot be empty".to_string());
+            if basic_info.team_size > 10000 {
+                return Err("Team size cannot exceed 10,000 members".to_string());
+            }
+            if basic_info.focus_areas.len() > Self::MAX_FOCUS_AREAS {
+                return Err("Cannot have more than 10 focus areas".to_string());
+            }
+            for area in &basic_info.focus_areas {
+                SafeText::parse_required(area, "Focus area", policy.max_label)?;
+            }
         }
 
-        if request.basic_info.team_size > 10000 {
-            return Err("Team size cannot exceed 10,000 members".to_string());
+        if let Some(web3_identity) = web3_identity {
+            // Forms submit empty strings for unset handles
+            if let Some(github_org) = web3_identity.github_org.as_deref().filter(|h| !h.trim().is_empty()) {
+                Handle::parse(github_org, "GitHub organization")?;
+            }
+            if let Some(twitter) = web3_identity.twitter_handle.as_deref().filter(|h| !h.trim().is_empty()) {
+                Handle::parse(twitter, "Twitter handle")?;
+            }
+            if let Some(discord) = &web3_identity.discord_server {
+                SafeText::parse(discord, "Discord server", policy.max_url)?;
+            }
+            if let Some(telegram) = &web3_identity.telegram_channel {
+                SafeText::parse(telegram, "Telegram channel", policy.max_url)?;
+            }
         }
 
-        if request.basic_info.focus_areas.len() > 10 {
-            return Err("Cannot have more than 10 focus areas".to_string());
+        if let Some(presence) = cross_chain_presence {
+            let chains: [(&[String], &str, &str); 5] = [
+                (&presence.ethereum_contracts, "Ethereum contract", "Ethereum contracts"),
+                (&presence.bitcoin_addresses, "Bitcoin address", "Bitcoin addresses"),
+                (&presence.solana_addresses, "Solana address", "Solana addresses"),
+                (&presence.sui_addresses, "Sui address", "Sui addresses"),
+                (&presence.ton_addresses, "TON address", "TON addresses"),
+            ];
+            for (addresses, field, plural) in chains {
+                if addresses.len() > Self::MAX_ADDRESSES_PER_CHAIN {
+                    return Err(format!("Too many {}", plural));
+                }
+                for address in addresses {
+                    SafeText::parse(address, field, policy.max_address)?;
+                }
+            }
         }
 
-        // Validate web3 identity
-        if let Some(github_org) = &request.web3_identity.github_org {
-            Self::validate_string_length(github_org, Self::MAX_SOCIAL_HANDLE_LENGTH, "GitHub organization")?;
-        }
-        if let Some(twitter) = &request.web3_identity.twitter_handle {
-            Self::validate_string_length(twitter, Self::MAX_SOCIAL_HANDLE_LENGTH, "Twitter handle")?;
-        }
-        if let Some(discord) = &request.web3_identity.discord_server {
-            Self::validate_string_length(discord, Self::MAX_URL_LENGTH, "Discord server")?;
-        }
-        if let Some(telegram) = &request.web3_identity.telegram_channel {
-            Self::validate_string_length(telegram, Self::MAX_URL_LENGTH, "Telegram channel")?;
+        if let Some(team_members) = team_members {
+            if team_members.len() > Self::MAX_TEAM_MEMBERS {
+                return Err("Too many team members".to_string());
+            }
+            for member in team_members {
+                SafeText::parse(&member.name, "Team member name", policy.max_name)?;
+                SafeText::parse(&member.role, "Team member role", policy.max_name)?;
+                if let Some(github) = &member.github_profile {
+                    SafeText::parse(github, "GitHub profile", policy.max_url)?;
+                }
+                if let Some(linkedin) = &member.linkedin_profile {
+                    SafeText::parse(linkedin, "LinkedIn profile", policy.max_url)?;
+                }
+            }
         }
 
-        // Validate cross-chain addresses
-        if request.cross_chain_presence.ethereum_contracts.len() > Self::MAX_ADDRESSES_PER_CHAIN {
-            return Err("Too many Ethereum contracts".to_string());
-        }
-        if request.cross_chain_presence.bitcoin_addresses.len() > Self::MAX_ADDRESSES_PER_CHAIN {
-            return Err("Too many Bitcoin addresses".to_string());
-        }
-        if request.cross_chain_presence.solana_addresses.len() > Self::MAX_ADDRESSES_PER_CHAIN {
-            return Err("Too many Solana addresses".to_string());
-        }
-        if request.cross_chain_presence.sui_addresses.len() > Self::MAX_ADDRESSES_PER_CHAIN {
-            return Err("Too many Sui addresses".to_string());
-        }
-        if request.cross_chain_presence.ton_addresses.len() > Self::MAX_ADDRESSES_PER_CHAIN {
-            return Err("Too many TON addresses".to_string());
-        }
+        Ok(())
+    }
 
-        for address in &request.cross_chain_presence.ethereum_contracts {
-            Self::validate_string_length(address, Self::MAX_ADDRESS_LENGTH, "Ethereum contract")?;
-        }
-        for address in &request.cross_chain_presence.bitcoin_addresses {
-            Self::validate_string_length(address, Self::MAX_ADDRESS_LENGTH, "Bitcoin address")?;
-        }
-        for address in &request.cross_chain_presence.solana_addresses {
-            Self::validate_string_length(address, Self::MAX_ADDRESS_LENGTH, "Solana address")?;
+    // Type-specific requirements on top of the common field validation
+    fn validate_entity_requirements(
+        entity_type: &EntityType,
+        basic_info: &CompanyBasicInfo,
+        web3_identity: &Web3Identity,
+        cross_chain_presence: &CrossChainPresence,
+        team_members: &[TeamMember],
+    ) -> Result<(), String> {
+        match entity_type {
+            EntityType::Company => Ok(()),
+            EntityType::Dao => {
+                let presence = cross_chain_presence;
+                let has_on_chain_presence = !presence.ethereum_contracts.is_empty()
+                    || !presence.bitcoin_addresses.is_empty()
+                    || !presence.icp_canisters.is_empty()
+                    || !presence.polygon_contracts.is_empty()
+                    || !presence.solana_addresses.is_empty()
+                    || !presence.sui_addresses.is_empty()
+                    || !presence.ton_addresses.is_empty()
+                    || !presence.treasury_wallets.is_empty()
+                    || !presence.token_contracts.is_empty();
+                if !has_on_chain_presence {
+                    return Err(
+                        "A DAO must list at least one governance contract, treasury wallet or token".to_string(),
+                    );
+                }
+                Ok(())
+            }
+            EntityType::OpenSourceProject => {
+                if web3_identity.github_org.as_deref().is_none_or(|org| org.trim().is_empty()) {
+                    return Err("An open-source project must provide its GitHub organization".to_string());
+                }
+                Ok(())
+            }
+            EntityType::IndividualBuilder => {
+                if basic_info.team_size > 1 || team_members.len() > 1 {
+                    return Err("An individual builder cannot list more than one team member".to_string());
+                }
+                if web3_identity.github_org.is_none() && web3_identity.twitter_handle.is_none() {
+                    return Err(
+                        "An individual builder must provide a GitHub or Twitter account".to_string(),
+                    );
+                }
+                Ok(())
+            }
         }
+    }
 
-        // Validate team members
-        if request.team_members.len() > Self::MAX_TEAM_MEMBERS {
-            return Err("Too many team members".to_string());
+    // Websites are stored in SafeUrl's normalized form; call after validate_sections
+    fn normalize_website(website: String) -> String {
+        match SafeUrl::parse_web(&website, "Website URL") {
+            Ok(url) => url.into_string(),
+            Err(_) => website.trim().to_string(),
         }
+    }
 
-        for member in &request.team_members {
-            Self::validate_string_length(&member.name, Self::MAX_NAME_LENGTH, "Team member name")?;
-            Self::validate_string_length(&member.role, Self::MAX_NAME_LENGTH, "Team member role")?;
-            if let Some(github) = &member.github_profile {
-                Self::validate_string_length(github, Self::MAX_URL_LENGTH, "GitHub profile")?;
+    // Verification results are only ever set by the verification flows. A new profile
+    // starts unverified; an edit keeps whatever the current profile has earned.
+    fn keep_verification_results(mut submitted: Web3Identity, current: Option<&mut Web3Identity>) -> Web3Identity {
+        match current {
+            Some(current) => {
+                submitted.domain_verified = current.domain_verified;
+                submitted.social_verification_status = current.social_verification_status.clone();
+                submitted.verification_proofs = std::mem::take(&mut current.verification_proofs);
             }
-            if let Some(linkedin) = &member.linkedin_profile {
-                Self::validate_string_length(linkedin, Self::MAX_URL_LENGTH, "LinkedIn profile")?;
+            None => {
+                submitted.domain_verified = false;
+                submitted.social_verification_status = VerificationStatus::Pending;
+                submitted.verification_proofs = Vec::new();
             }
         }
-
-        Ok(())
+        submitted
     }
 
     // Core CRUD operations
@@ -116,19 +203,50 @@ impl RegistryAPI {
         caller_principal: Principal,
     ) -> RegistryResult<String> {
         // Validate input first
-        if let Err(validation_error) = Self::validate_company_request(&request) {
+        if let Err(validation_error) = Self::validate_sections(
+            Some(&request.basic_info),
+            Some(&request.web3_identity),
+            Some(&request.cross_chain_presence),
+            Some(&request.team_members),
+        ) {
             return RegistryResult::Err(validation_error);
         }
+        let entity_type = request.entity_type.clone().unwrap_or_default();
+        if let Err(entity_error) = Self::validate_entity_requirements(
+            &entity_type,
+            &request.basic_info,
+            &request.web3_identity,
+            &request.cross_chain_presence,
+            &request.team_members,
+        ) {
+            return RegistryResult::Err(entity_error);
+        }
+        if let Err(reserved_error) = ReservedNameManager::check_registration(
+            &request.basic_info.name,
+            &request.basic_info.website,
+            None,
+        ) {
+            return RegistryResult::Err(reserved_error);
+        }
+        if let Err(canary_error) = CanaryGuard::check_name(&request.basic_info.name, None, caller_principal) {
+            return RegistryResult::Err(canary_error);
+        }
+        if let Err(blocked_error) =
+            BlocklistManager::check_registration(&caller_principal, &request.basic_info.website)
+        {
+            return RegistryResult::Err(blocked_error);
+        }
         let now = time();
         let company_id = StorageManager::generate_company_id();
 
         // Initialize company with default values
-        let mut web3_identity = request.web3_identity;
-        web3_identity.verification_proofs = Vec::new(); // Initialize empty verification proofs
+        let mut basic_info = request.basic_info;
+        basic_info.website = Self::normalize_website(basic_info.website);
+        let web3_identity = Self::keep_verification_results(request.web3_identity, None);
 
         let company = Company {
             id: company_id.clone(),
-            basic_info: request.basic_info,
+            basic_info,
             web3_identity,
             cross_chain_presence: request.cross_chain_presence,
             team_members: request.team_members,
@@ -138,12 +256,26 @@ impl RegistryAPI {
                 community_vouches: Vec::new(),
                 reputation_score: 0,
                 reputation_staked: 0,
+                reputation_escrow: None,
             },
             status: CompanyStatus::Pending,
             created_at: now,
             updated_at: now,
             created_by: caller_principal,
             verification_score: 0,
+            entity_type: Some(entity_type),
+            funding: None,
+            hiring: None,
+            contact_channels: None,
+            impersonation_warnings: None,
+            warning_banner: None,
+            address_rotations: None,
+            paused_score_sources: None,
+            permanently_suspended: None,
+            tags: None,
+            last_owner_activity_at: Some(now),
+            inactivity_warned_at: None,
+            managers: None,
         };
 
         // Calculate initial verification score
@@ -151,15 +283,84 @@ impl RegistryAPI {
         updated_company.verification_score =
             VerificationManager::calculate_verification_score(&updated_company);
 
-        StorageManager::insert_company(company_id.clone(), updated_company);
+        if let Err(err) = StorageManager::insert_new_company(updated_company) {
+            return RegistryResult::Err(err);
+        }
+        CanaryGuard::inspect_company(&company_id, caller_principal);
 
         RegistryResult::Ok(company_id)
     }
 
-    pub fn get_company(company_id: String) -> RegistryResult<Company> {
+    // Shape a stored company for API responses
+    pub fn prepare_response(company: &mut Company) {
+        CommunityValidationManager::cap_embedded_lists(company);
+        BannerManager::strip_inactive_banner(company);
+    }
+
+    // Blank out every optional section the caller did not ask for; None keeps them all
+    pub fn select_sections(company: &mut Company, sections: &Option<Vec<CompanySection>>) {
+        let sections = match sections {
+            Some(sections) => sections,
+            None => return,
+        };
+        let wants = |section: CompanySection| sections.contains(&section);
+
+        if !wants(CompanySection::Team) {
+            company.team_members = Vec::new();
+        }
+        if !wants(CompanySection::Proofs) {
+            company.web3_identity.verification_proofs = Vec::new();
+        }
+        if !wants(CompanySection::Community) {
+            let community = &mut company.community_validation;
+            community.peer_endorsements = Vec::new();
+            community.employee_testimonials = Vec::new();
+            community.community_vouches = Vec::new();
+        }
+        if !wants(CompanySection::Presence) {
+            company.cross_chain_presence = CrossChainPresence {
+                ethereum_contracts: Vec::new(),
+                bitcoin_addresses: Vec::new(),
+                icp_canisters: Vec::new(),
+                polygon_contracts: Vec::new(),
+                solana_addresses: Vec::new(),
+                sui_addresses: Vec::new(),
+                ton_addresses: Vec::new(),
+                treasury_wallets: Vec::new(),
+                token_contracts: Vec::new(),
+            };
+        }
+        if !wants(CompanySection::Funding) {
+            company.funding = None;
+        }
+        if !wants(CompanySection::Hiring) {
+            company.hiring = None;
+        }
+        if !wants(CompanySection::Contacts) {
+            company.contact_channels = None;
+            company.impersonation_warnings = None;
+        }
+        if !wants(CompanySection::History) {
+            company.address_rotations = None;
+            company.paused_score_sources = None;
+        }
+    }
+
+    pub fn get_company(company_id: String, sections: Option<Vec<CompanySection>>) -> RegistryResult<Company> {
         match StorageManager::get_company(&company_id) {
-            Some(company) => RegistryResult::Ok(company),
-            None => RegistryResult::Err("Company not found".to_string()),
+            Some(mut company) => {
+                Self::prepare_response(&mut company);
+                Self::select_sections(&mut company, &sections);
+                RegistryResult::Ok(company)
+            }
+            None => match ArchiveManager::get_archived_profile(&company_id) {
+                Some(mut company) => {
+                    Self::prepare_response(&mut company);
+                    Self::select_sections(&mut company, &sections);
+                    RegistryResult::Ok(company)
+                }
+                None => RegistryResult::Err(ShardManager::missing_company_error(&company_id)),
+            },
         }
     }
 
@@ -173,20 +374,64 @@ impl RegistryAPI {
             None => return RegistryResult::Err("Company not found".to_string()),
         };
 
-        if company.created_by != caller_principal {
+        if !OwnershipManager::has_role(&company, caller_principal, CompanyRole::Editor) {
             return RegistryResult::Err(
-                "Unauthorized: Only company creator can update".to_string(),
+                "Unauthorized: Only company owners or editors can update".to_string(),
             );
         }
 
+        if let Err(validation_error) = Self::validate_sections(
+            request.basic_info.as_ref(),
+            request.web3_identity.as_ref(),
+            request.cross_chain_presence.as_ref(),
+            request.team_members.as_deref(),
+        ) {
+            return RegistryResult::Err(validation_error);
+        }
+
+        let entity_type = request
+            .entity_type
+            .clone()
+            .or(company.entity_type.clone())
+            .unwrap_or_default();
+        if let Err(entity_error) = Self::validate_entity_requirements(
+            &entity_type,
+            request.basic_info.as_ref().unwrap_or(&company.basic_info),
+            request.web3_identity.as_ref().unwrap_or(&company.web3_identity),
+            request.cross_chain_presence.as_ref().unwrap_or(&company.cross_chain_presence),
+            request.team_members.as_deref().unwrap_or(&company.team_members),
+        ) {
+            return RegistryResult::Err(entity_error);
+        }
+
+        if let Some(basic_info) = &request.basic_info {
+            if let Err(reserved_error) = ReservedNameManager::check_registration(
+                &basic_info.name,
+                &basic_info.website,
+                Some(&company),
+            ) {
+                return RegistryResult::Err(reserved_error);
+            }
+            if let Err(canary_error) =
+                CanaryGuard::check_name(&basic_info.name, Some(&request.company_id), caller_principal)
+            {
+                return RegistryResult::Err(canary_error);
+            }
+            if let Err(blocked_error) = BlocklistManager::check_registration(&caller_principal, &basic_info.website) {
+                return RegistryResult::Err(blocked_error);
+            }
+        }
+
         // Update company fields
         let success = StorageManager::update_company(&request.company_id, |company| {
             // Update fields if provided
-            if let Some(basic_info) = request.basic_info {
+            if let Some(mut basic_info) = request.basic_info {
+                basic_info.website = Self::normalize_website(basic_info.website);
                 company.basic_info = basic_info;
             }
             if let Some(web3_identity) = request.web3_identity {
-                company.web3_identity = web3_identity;
+                company.web3_identity =
+                    Self::keep_verification_results(web3_identity, Some(&mut company.web3_identity));
             }
             if let Some(cross_chain_presence) = request.cross_chain_presence {
                 company.cross_chain_presence = cross_chain_presence;
@@ -194,12 +439,17 @@ impl RegistryAPI {
             if let Some(team_members) = request.team_members {
                 company.team_members = team_members;
             }
+            if request.entity_type.is_some() {
+                company.entity_type = request.entity_type;
+            }
 
             // Recalculate verification score
             company.verification_score = VerificationManager::calculate_verification_score(company);
+            InactivityManager::mark_active(company);
         });
 
         if success {
+            CanaryGuard::inspect_company(&request.company_id, caller_principal);
             RegistryResult::Ok(())
         } else {
             RegistryResult::Err("Company not found".to_string())
@@ -210,45 +460,19 @@ impl RegistryAPI {
         offset: Option<u32>,
         limit: Option<u32>,
         filters: Option<SearchFilters>,
+        sections: Option<Vec<CompanySection>>,
     ) -> Vec<Company> {
         let offset = offset.unwrap_or(0) as usize;
         let limit = limit.unwrap_or(50) as usize;
 
         let mut all_companies = StorageManager::get_all_companies();
+        if filters.as_ref().is_some_and(|filters| filters.include_archived == Some(true)) {
+            all_companies.extend(ArchiveManager::get_archived_profiles());
+        }
 
         // Apply filters if provided
         if let Some(filters) = filters {
-            all_companies.retain(|company| {
-                let mut matches = true;
-
-                if let Some(ref status_filter) = filters.status {
-                    matches &= std::mem::discriminant(&company.status)
-                        == std::mem::discriminant(status_filter);
-                }
-
-                if let Some(ref focus_areas) = filters.focus_areas {
-                    matches &= focus_areas.iter().any(|area| {
-                        company.basic_info.focus_areas.contains(area)
-                    });
-                }
-
-                if let Some(min_score) = filters.min_verification_score {
-                    matches &= company.verification_score >= min_score;
-                }
-
-                if let Some(has_github) = filters.has_github {
-                    matches &= has_github == company.web3_identity.github_org.is_some();
-                }
-
-                if let Some(has_contracts) = filters.has_contracts {
-                    let has_any_contracts = !company.cross_chain_presence.ethereum_contracts.is_empty()
-                        || !company.cross_chain_presence.icp_canisters.is_empty()
-                        || !company.cross_chain_presence.polygon_contracts.is_empty();
-                    matches &= has_contracts == has_any_contracts;
-                }
-
-                matches
-            });
+            all_companies.retain(|company| Self::matches_filters(company, &filters));
         }
 
         // Sort by verification score (highest first), then by creation date
@@ -263,28 +487,231 @@ impl RegistryAPI {
             .into_iter()
             .skip(offset)
             .take(limit)
+            .map(|mut company| {
+                Self::prepare_response(&mut company);
+                Self::select_sections(&mut company, &sections);
+                company
+            })
             .collect()
     }
 
-    pub fn search_companies(query: String) -> Vec<Company> {
-        let query_lower = query.to_lowercase();
+    pub fn matches_filters(company: &Company, filters: &SearchFilters) -> bool {
+        let mut matches = true;
+
+        if let Some(ref status_filter) = filters.status {
+            matches &= std::mem::discriminant(&company.status)
+                == std::mem::discriminant(status_filter);
+        }
+
+        if let Some(ref focus_areas) = filters.focus_areas {
+            matches &= focus_areas.iter().any(|area| {
+                company.basic_info.focus_areas.contains(area)
+            });
+        }
+
+        if let Some(min_score) = filters.min_verification_score {
+            matches &= company.verification_score >= min_score;
+        }
+
+        if let Some(has_github) = filters.has_github {
+            matches &= has_github == company.web3_identity.github_org.is_some();
+        }
+
+        if let Some(has_contracts) = filters.has_contracts {
+            let has_any_contracts = !company.cross_chain_presence.ethereum_contracts.is_empty()
+                || !company.cross_chain_presence.icp_canisters.is_empty()
+                || !company.cross_chain_presence.polygon_contracts.is_empty();
+            matches &= has_contracts == has_any_contracts;
+        }
+
+        if let Some(ref entity_type) = filters.entity_type {
+            matches &= company.entity_type.clone().unwrap_or_default() == *entity_type;
+        }
+
+        if let Some(ref min_stage) = filters.min_funding_stage {
+            matches &= FundingManager::matches_min_stage(company, min_stage);
+        }
 
-        StorageManager::get_all_companies()
+        if let Some(actively_hiring) = filters.actively_hiring {
+            matches &= actively_hiring == HiringManager::is_actively_hiring(company);
+        }
+
+        if let Some(ref tags) = filters.tags {
+            let company_tags = company.tags.as_deref().unwrap_or(&[]);
+            matches &= tags.iter().all(|tag| company_tags.contains(&tag.trim().to_lowercase()));
+        }
+
+        if let Some(ref chain_type) = filters.on_chain {
+            matches &= !RotationManager::active_addresses(&company.cross_chain_presence, chain_type).is_empty();
+        }
+
+        matches
+    }
+
+    // Ranked by relevance plus half the verification score, so a well-verified partial
+    // match can overtake a weak exact one only within the same field tier
+    pub fn search_companies(query: String) -> Vec<SearchResult> {
+        let query: Vec<char> = query.trim().chars().map(Self::fold_char).collect();
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let mut results: Vec<SearchResult> = StorageManager::get_all_companies()
             .into_iter()
-            .filter(|company| {
-                company.basic_info.name.to_lowercase().contains(&query_lower)
-                    || company
-                        .basic_info
-                        .description
-                        .to_lowercase()
-                        .contains(&query_lower)
-                    || company
-                        .basic_info
+            .filter_map(|company| {
+                let info = &company.basic_info;
+                let mut relevance = 0;
+                let mut best: Option<(SearchField, String, usize)> = None;
+
+                if let Some(position) = Self::find_folded(&info.name, &query) {
+                    relevance += Self::NAME_WEIGHT;
+                    if info.name.chars().count() == query.len() {
+                        relevance += Self::EXACT_NAME_BONUS;
+                    } else if position == 0 {
+                        relevance += Self::PREFIX_NAME_BONUS;
+                    }
+                    best = Some((SearchField::Name, info.name.clone(), position));
+                }
+
+                if let Some((area, position)) = info
+                    .focus_areas
+                    .iter()
+                    .find_map(|area| Self::find_folded(area, &query).map(|p| (area, p)))
+                {
+                    relevance += Self::FOCUS_AREA_WEIGHT;
+                    best.get_or_insert((SearchField::FocusArea, area.clone(), position));
+                }
+
+                if let Some(position) = Self::find_folded(&info.description, &query) {
+                    relevance += Self::DESCRIPTION_WEIGHT;
+                    best.get_or_insert((SearchField::Description, info.description.clone(), position));
+                }
+
+                let (matched_field, text, position) = best?;
+                let (snippet, highlight_start) = Self::snippet_around(&text, position, query.len());
+                Some(SearchResult {
+                    company,
+                    relevance,
+                    matched_field,
+                    snippet,
+                    highlight_start,
+                    highlight_length: query.len() as u32,
+                })
+            })
+            .collect();
+
+        results.sort_by_key(|r| {
+            std::cmp::Reverse((r.relevance + r.company.verification_score / 2, r.company.created_at))
+        });
+        for result in results.iter_mut() {
+            Self::prepare_response(&mut result.company);
+        }
+        results
+    }
+
+    // Token-based counterpart of search_companies served from the search index: every word
+    // of the query must start some word of the company's name, focus areas or description
+    pub fn search_companies_ranked(
+        query: String,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> CommunityPage<SearchResult> {
+        let ranked = SearchIndex::rank(&query);
+        let first_token: Vec<char> = SearchIndex::tokenize(&query)
+            .first()
+            .map(|token| token.chars().collect())
+            .unwrap_or_default();
+
+        let mut results: Vec<SearchResult> = ranked
+            .into_iter()
+            .filter_map(|(company_id, relevance, matched_field)| {
+                let company = StorageManager::get_company(&company_id)?;
+                let info = &company.basic_info;
+                let text = match matched_field {
+                    SearchField::Name => info.name.clone(),
+                    SearchField::FocusArea => info
                         .focus_areas
                         .iter()
-                        .any(|area| area.to_lowercase().contains(&query_lower))
+                        .find(|area| Self::find_folded(area, &first_token).is_some())
+                        .or(info.focus_areas.first())
+                        .cloned()
+                        .unwrap_or_default(),
+                    SearchField::Description => info.description.clone(),
+                };
+                let position = Self::find_folded(&text, &first_token).unwrap_or(0);
+                let (snippet, highlight_start) = Self::snippet_around(&text, position, first_token.len());
+                Some(SearchResult {
+                    company,
+                    relevance,
+                    matched_field,
+                    snippet,
+                    highlight_start,
+                    highlight_length: first_token.len() as u32,
+                })
             })
-            .collect()
+            .collect();
+
+        results.sort_by_key(|r| {
+            std::cmp::Reverse((r.relevance + r.company.verification_score / 2, r.company.created_at))
+        });
+        let mut page = CommunityValidationManager::paginate(results, offset, limit);
+        for result in page.items.iter_mut() {
+            Self::prepare_response(&mut result.company);
+        }
+        page
+    }
+
+    pub fn rebuild_search_index(caller_principal: Principal) -> RegistryResult<u64> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        RegistryResult::Ok(StorageManager::rebuild_search_index())
+    }
+
+    // Name-only relevance on the search_companies scale, for results without a full profile
+    pub fn name_relevance(name: &str, query: &str) -> Option<u32> {
+        let query: Vec<char> = query.trim().chars().map(Self::fold_char).collect();
+        if query.is_empty() {
+            return None;
+        }
+        let position = Self::find_folded(name, &query)?;
+        let bonus = if name.chars().count() == query.len() {
+            Self::EXACT_NAME_BONUS
+        } else if position == 0 {
+            Self::PREFIX_NAME_BONUS
+        } else {
+            0
+        };
+        Some(Self::NAME_WEIGHT + bonus)
+    }
+
+    // One char in, one char out, so match offsets stay valid in the original text
+    fn fold_char(c: char) -> char {
+        c.to_lowercase().next().unwrap_or(c)
+    }
+
+    // Char offset of the first case-insensitive occurrence of `query` in `text`
+    fn find_folded(text: &str, query: &[char]) -> Option<usize> {
+        let text: Vec<char> = text.chars().map(Self::fold_char).collect();
+        text.windows(query.len()).position(|window| window == query)
+    }
+
+    fn snippet_around(text: &str, position: usize, length: usize) -> (String, u32) {
+        let chars: Vec<char> = text.chars().collect();
+        let start = position.saturating_sub(Self::SNIPPET_CONTEXT_CHARS);
+        let end = (position + length + Self::SNIPPET_CONTEXT_CHARS).min(chars.len());
+
+        let mut snippet = String::new();
+        let mut highlight_start = position - start;
+        if start > 0 {
+            snippet.push('…');
+            highlight_start += 1;
+        }
+        snippet.extend(&chars[start..end]);
+        if end < chars.len() {
+            snippet.push('…');
+        }
+        (snippet, highlight_start as u32)
     }
 
     pub fn get_company_count() -> u64 {
@@ -302,6 +729,7 @@ impl RegistryAPI {
         let mut verified_count = 0u64;
         let mut trusted_count = 0u64;
         let mut flagged_count = 0u64;
+        let mut stale_count = 0u64;
 
         for company in all_companies {
             match company.status {
@@ -309,6 +737,7 @@ impl RegistryAPI {
                 CompanyStatus::Verified => verified_count += 1,
                 CompanyStatus::Trusted => trusted_count += 1,
                 CompanyStatus::Flagged => flagged_count += 1,
+                CompanyStatus::Stale => stale_count += 1,
                 CompanyStatus::Suspended => {}
             }
         }
@@ -317,6 +746,7 @@ impl RegistryAPI {
         stats.insert("verified_companies".to_string(), verified_count);
         stats.insert("trusted_companies".to_string(), trusted_count);
         stats.insert("flagged_companies".to_string(), flagged_count);
+        stats.insert("stale_companies".to_string(), stale_count);
 
         stats
     }
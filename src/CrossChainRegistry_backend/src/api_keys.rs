@@ -0,0 +1,321 @@
+use crate::admins::AdminManager;
+use crate::api::RegistryAPI;
+use crate::sanitize;
+use crate::security::SecurityLog;
+use crate::storage::StorageManager;
+use crate::types::{
+    ApiKey, ApiKeyScope, HttpRequest, HttpResponse, IssuedApiKey, RegistryResult, SecurityEventType,
+};
+use candid::Principal;
+use ic_cdk::api::management_canister::main::raw_rand;
+use ic_cdk::api::time;
+use sha2::{Digest, Sha256};
+
+// API keys for the REST routes under /api/v1/ on the HTTP gateway, so websites embedding
+// registry data can be throttled and cut off independently of IC principals. The query
+// path only routes; keyed requests are upgraded to http_request_update so the rate limit
+// and usage counters actually stick.
+
+pub struct ApiKeyManager;
+
+impl ApiKeyManager {
+    pub const API_PREFIX: &'static str = "/api/v1/";
+    const KEY_HEADER: &'static str = "x-api-key";
+    const KEY_PREFIX: &'static str = "ccr_";
+    const MAX_LABEL_LENGTH: usize = 64;
+    const MAX_ACTIVE_KEYS_PER_OWNER: usize = 5;
+    const DEFAULT_RATE_LIMIT_PER_MINUTE: u32 = 60;
+    const MAX_RATE_LIMIT_PER_MINUTE: u32 = 6_000;
+    const MAX_SEARCH_RESULTS: usize = 50;
+
+    pub async fn issue_api_key(
+        label: String,
+        scope: ApiKeyScope,
+        caller_principal: Principal,
+    ) -> RegistryResult<IssuedApiKey> {
+        if caller_principal == Principal::anonymous() {
+            return RegistryResult::Err("Anonymous principals cannot hold API keys".to_string());
+        }
+        let label = label.trim().to_string();
+        if label.is_empty() || sanitize::grapheme_len(&label) > Self::MAX_LABEL_LENGTH {
+            return RegistryResult::Err(format!(
+                "Label must be 1-{} characters",
+                Self::MAX_LABEL_LENGTH
+            ));
+        }
+        if Self::active_keys_of(caller_principal) >= Self::MAX_ACTIVE_KEYS_PER_OWNER {
+            return RegistryResult::Err(format!(
+                "At most {} active API keys per principal; revoke one first",
+                Self::MAX_ACTIVE_KEYS_PER_OWNER
+            ));
+        }
+
+        let random_bytes = match raw_rand().await {
+            Ok((bytes,)) => bytes,
+            Err((code, message)) => {
+                return RegistryResult::Err(format!("raw_rand failed ({:?}): {}", code, message))
+            }
+        };
+        // Re-check after the await, another issuance may have landed in between
+        if Self::active_keys_of(caller_principal) >= Self::MAX_ACTIVE_KEYS_PER_OWNER {
+            return RegistryResult::Err("Too many active API keys".to_string());
+        }
+
+        let key = format!("{}{}", Self::KEY_PREFIX, Self::to_hex(&random_bytes));
+        let key_hash = Self::hash_key(&key);
+        let api_key = ApiKey {
+            id: format!("key_{}", &key_hash[..16]),
+            key_hash,
+            owner: caller_principal,
+            label,
+            scope,
+            rate_limit_per_minute: Self::DEFAULT_RATE_LIMIT_PER_MINUTE,
+            created_at: time(),
+            revoked_at: None,
+            last_used_at: None,
+            request_count: 0,
+        };
+        StorageManager::insert_api_key(api_key.clone());
+        SecurityLog::record(caller_principal, SecurityEventType::ApiKeyIssued, None, api_key.id.clone());
+        RegistryResult::Ok(IssuedApiKey { key, info: api_key })
+    }
+
+    pub fn revoke_api_key(key_id: String, caller_principal: Principal) -> RegistryResult<ApiKey> {
+        let mut api_key = match Self::find_by_id(&key_id) {
+            Some(api_key) => api_key,
+            None => return RegistryResult::Err("API key not found".to_string()),
+        };
+        if api_key.owner != caller_principal && !AdminManager::is_admin(caller_principal) {
+            return RegistryResult::Err("Only the key owner or an admin can revoke this key".to_string());
+        }
+        if api_key.revoked_at.is_some() {
+            return RegistryResult::Err("API key is already revoked".to_string());
+        }
+        api_key.revoked_at = Some(time());
+        StorageManager::insert_api_key(api_key.clone());
+        SecurityLog::record(api_key.owner, SecurityEventType::ApiKeyRevoked, None, api_key.id.clone());
+        RegistryResult::Ok(api_key)
+    }
+
+    pub fn set_api_key_rate_limit(
+        key_id: String,
+        rate_limit_per_minute: u32,
+        caller_principal: Principal,
+    ) -> RegistryResult<ApiKey> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if rate_limit_per_minute == 0 || rate_limit_per_minute > Self::MAX_RATE_LIMIT_PER_MINUTE {
+            return RegistryResult::Err(format!(
+                "Rate limit must be between 1 and {} requests per minute",
+                Self::MAX_RATE_LIMIT_PER_MINUTE
+            ));
+        }
+        let mut api_key = match Self::find_by_id(&key_id) {
+            Some(api_key) => api_key,
+            None => return RegistryResult::Err("API key not found".to_string()),
+        };
+        api_key.rate_limit_per_minute = rate_limit_per_minute;
+        StorageManager::insert_api_key(api_key.clone());
+        RegistryResult::Ok(api_key)
+    }
+
+    pub fn get_my_api_keys(caller_principal: Principal) -> Vec<ApiKey> {
+        let mut keys: Vec<ApiKey> = StorageManager::get_all_api_keys()
+            .into_iter()
+            .filter(|k| k.owner == caller_principal)
+            .collect();
+        keys.sort_by_key(|k| std::cmp::Reverse(k.created_at));
+        keys
+    }
+
+    pub fn list_api_keys(caller_principal: Principal) -> RegistryResult<Vec<ApiKey>> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let mut keys = StorageManager::get_all_api_keys();
+        keys.sort_by_key(|k| std::cmp::Reverse(k.created_at));
+        RegistryResult::Ok(keys)
+    }
+
+    pub fn is_api_path(url: &str) -> bool {
+        url.split('?').next().unwrap_or("").starts_with(Self::API_PREFIX)
+    }
+
+    // Query-side handling: nothing can be recorded here, so hand the request to the update path
+    pub fn http_request(request: HttpRequest) -> HttpResponse {
+        // Browsers preflight the custom key header from embedding sites
+        if request.method == "OPTIONS" {
+            let mut response = Self::json_response(204, Vec::new());
+            response.headers.push(("Access-Control-Allow-Methods".to_string(), "GET".to_string()));
+            response.headers.push(("Access-Control-Allow-Headers".to_string(), "X-API-Key".to_string()));
+            return response;
+        }
+        if request.method != "GET" {
+            return Self::json_error(405, "Method not allowed");
+        }
+        HttpResponse {
+            status_code: 200,
+            headers: Vec::new(),
+            body: Vec::new(),
+            upgrade: Some(true),
+        }
+    }
+
+    pub fn http_request_update(request: HttpRequest) -> HttpResponse {
+        if request.method != "GET" {
+            return Self::json_error(405, "Method not allowed");
+        }
+
+        let scope = match Self::authenticate(&request) {
+            Ok(api_key) => api_key.scope,
+            Err(response) => return response,
+        };
+
+        let (path, query) = match request.url.split_once('?') {
+            Some((path, query)) => (path, query),
+            None => (request.url.as_str(), ""),
+        };
+        let route = &path[Self::API_PREFIX.len().min(path.len())..];
+        match route.split('/').collect::<Vec<_>>().as_slice() {
+            ["search"] => Self::search(query),
+            ["companies", company_id] if !company_id.is_empty() => {
+                if scope != ApiKeyScope::ReadOnly {
+                    return Self::json_error(403, "This key is limited to search");
+                }
+                match RegistryAPI::get_company(company_id.to_string(), None) {
+                    RegistryResult::Ok(company) => {
+                        Self::json_response(200, serde_json::to_vec(&company).unwrap_or_default())
+                    }
+                    RegistryResult::Err(err) => Self::json_error(404, &err),
+                }
+            }
+            _ => Self::json_error(404, "Not found"),
+        }
+    }
+
+    pub fn has_key(request: &HttpRequest) -> bool {
+        request.headers.iter().any(|(name, _)| name.eq_ignore_ascii_case(Self::KEY_HEADER))
+    }
+
+    // Resolves the request's key and counts the request against its rate limit. Update
+    // path only: a query can't keep the counters.
+    pub fn authenticate(request: &HttpRequest) -> Result<ApiKey, HttpResponse> {
+        let key = request
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(Self::KEY_HEADER))
+            .map(|(_, value)| value.trim().to_string());
+        let key = match key {
+            Some(key) if !key.is_empty() => key,
+            _ => return Err(Self::json_error(401, "Missing X-API-Key header")),
+        };
+        let mut api_key = match StorageManager::get_api_key_by_hash(&Self::hash_key(&key)) {
+            Some(api_key) if api_key.revoked_at.is_none() => api_key,
+            Some(_) => return Err(Self::json_error(401, "API key has been revoked")),
+            None => return Err(Self::json_error(401, "Invalid API key")),
+        };
+        if let Err(retry_after) = StorageManager::check_api_key_rate_limit(&api_key.id, api_key.rate_limit_per_minute) {
+            let mut response = Self::json_error(429, &format!("Rate limit exceeded. Retry after {}s.", retry_after));
+            response.headers.push(("Retry-After".to_string(), retry_after.to_string()));
+            return Err(response);
+        }
+
+        api_key.last_used_at = Some(time());
+        api_key.request_count += 1;
+        StorageManager::insert_api_key(api_key.clone());
+        Ok(api_key)
+    }
+
+    fn search(query: &str) -> HttpResponse {
+        let term = query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(name, _)| *name == "q")
+            .map(|(_, value)| Self::decode_query_value(value))
+            .unwrap_or_default();
+        if term.trim().is_empty() {
+            return Self::json_error(400, "Missing q parameter");
+        }
+
+        let results: Vec<serde_json::Value> = RegistryAPI::search_companies(term)
+            .into_iter()
+            .take(Self::MAX_SEARCH_RESULTS)
+            .map(|result| {
+                serde_json::json!({
+                    "id": result.company.id,
+                    "name": result.company.basic_info.name,
+                    "website": result.company.basic_info.website,
+                    "status": result.company.status,
+                    "verification_score": result.company.verification_score,
+                    "relevance": result.relevance,
+                    "snippet": result.snippet,
+                    "highlight_start": result.highlight_start,
+                    "highlight_length": result.highlight_length,
+                })
+            })
+            .collect();
+        Self::json_response(200, serde_json::json!({ "results": results }).to_string().into_bytes())
+    }
+
+    // application/x-www-form-urlencoded: '+' is a space, %XX a byte
+    fn decode_query_value(value: &str) -> String {
+        let bytes = value.as_bytes();
+        let mut decoded = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'+' => decoded.push(b' '),
+                b'%' if i + 2 < bytes.len() => {
+                    let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                    match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                        Some(byte) => {
+                            decoded.push(byte);
+                            i += 2;
+                        }
+                        None => decoded.push(b'%'),
+                    }
+                }
+                byte => decoded.push(byte),
+            }
+            i += 1;
+        }
+        String::from_utf8_lossy(&decoded).into_owned()
+    }
+
+    fn active_keys_of(owner: Principal) -> usize {
+        StorageManager::get_all_api_keys()
+            .iter()
+            .filter(|k| k.owner == owner && k.revoked_at.is_none())
+            .count()
+    }
+
+    fn find_by_id(key_id: &str) -> Option<ApiKey> {
+        StorageManager::get_all_api_keys().into_iter().find(|k| k.id == key_id)
+    }
+
+    fn hash_key(key: &str) -> String {
+        Self::to_hex(&Sha256::digest(key.as_bytes()))
+    }
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn json_response(status_code: u16, body: Vec<u8>) -> HttpResponse {
+        HttpResponse {
+            status_code,
+            headers: vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("Cache-Control".to_string(), "no-store".to_string()),
+                ("Access-Control-Allow-Origin".to_string(), "*".to_string()),
+            ],
+            body,
+            upgrade: None,
+        }
+    }
+
+    fn json_error(status_code: u16, message: &str) -> HttpResponse {
+        Self::json_response(status_code, serde_json::json!({ "error": message }).to_string().into_bytes())
+    }
+}
@@ -0,0 +1,154 @@
+use crate::admins::AdminManager;
+use crate::audit::AuditManager;
+use crate::ownership::OwnershipManager;
+use crate::storage::StorageManager;
+use crate::types::{ArchiveReason, ArchivedCompany, Company, RegistryResult};
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Taking companies off the registry. Both archiving and deleting move the record out of
+// the live company map (so listings, search, rankings and monitoring stop seeing it) into
+// the archive, and drop its pending challenges and monitoring state. An archived profile
+// stays readable by id; a deleted one is only kept for admins, e.g. for abuse reports.
+
+pub struct ArchiveManager;
+
+impl ArchiveManager {
+    pub fn archive_company(company_id: String, caller_principal: Principal) -> RegistryResult<ArchivedCompany> {
+        Self::take_down(&company_id, ArchiveReason::Archived, caller_principal)
+    }
+
+    pub fn delete_company(company_id: String, caller_principal: Principal) -> RegistryResult<ArchivedCompany> {
+        Self::take_down(&company_id, ArchiveReason::Deleted, caller_principal)
+    }
+
+    // Creators may bring back what they archived; deleted companies need an admin
+    pub fn restore_company(company_id: String, caller_principal: Principal) -> RegistryResult<Company> {
+        let archived = match StorageManager::get_archived_company(&company_id) {
+            Some(archived) => archived,
+            None => return RegistryResult::Err("Archived company not found".to_string()),
+        };
+        let is_admin = AdminManager::is_admin(caller_principal);
+        let allowed = match archived.reason {
+            ArchiveReason::Archived => is_admin || OwnershipManager::is_owner(&archived.company, caller_principal),
+            ArchiveReason::Deleted => is_admin,
+        };
+        if !allowed {
+            return RegistryResult::Err("Unauthorized: Only the company creator or an admin can restore this company".to_string());
+        }
+        if StorageManager::get_company(&company_id).is_some() {
+            return RegistryResult::Err("A live company with this id already exists".to_string());
+        }
+
+        StorageManager::remove_archived_company(&company_id);
+        let company = archived.company;
+        StorageManager::insert_company(company_id.clone(), company.clone());
+        AuditManager::record(
+            "company_restored",
+            &company_id,
+            vec![caller_principal],
+            format!("{:?}", archived.reason),
+        );
+        RegistryResult::Ok(company)
+    }
+
+    // Archived (not deleted) profiles keep resolving by id
+    pub fn get_archived_profile(company_id: &str) -> Option<Company> {
+        StorageManager::get_archived_company(company_id)
+            .filter(|archived| archived.reason == ArchiveReason::Archived)
+            .map(|archived| archived.company)
+    }
+
+    pub fn get_archived_profiles() -> Vec<Company> {
+        StorageManager::get_all_archived_companies()
+            .into_iter()
+            .filter(|archived| archived.reason == ArchiveReason::Archived)
+            .map(|archived| archived.company)
+            .collect()
+    }
+
+    pub fn get_my_archived_companies(caller_principal: Principal) -> Vec<ArchivedCompany> {
+        let mut archived: Vec<ArchivedCompany> = StorageManager::get_all_archived_companies()
+            .into_iter()
+            .filter(|archived| OwnershipManager::is_owner(&archived.company, caller_principal))
+            .collect();
+        archived.sort_by_key(|archived| std::cmp::Reverse(archived.archived_at));
+        archived
+    }
+
+    pub fn list_archived_companies(caller_principal: Principal) -> RegistryResult<Vec<ArchivedCompany>> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let mut archived = StorageManager::get_all_archived_companies();
+        archived.sort_by_key(|archived| std::cmp::Reverse(archived.archived_at));
+        RegistryResult::Ok(archived)
+    }
+
+    fn take_down(
+        company_id: &str,
+        reason: ArchiveReason,
+        caller_principal: Principal,
+    ) -> RegistryResult<ArchivedCompany> {
+        match StorageManager::get_company(company_id) {
+            Some(company) => {
+                if !OwnershipManager::is_owner(&company, caller_principal) && !AdminManager::is_admin(caller_principal) {
+                    return RegistryResult::Err(
+                        "Unauthorized: Only the company creator or an admin can do this".to_string(),
+                    );
+                }
+            }
+            None => {
+                // Deleting an archived company just changes how it is kept
+                return match StorageManager::get_archived_company(company_id) {
+                    Some(mut archived) if reason == ArchiveReason::Deleted && archived.reason == ArchiveReason::Archived => {
+                        if !OwnershipManager::is_owner(&archived.company, caller_principal)
+                            && !AdminManager::is_admin(caller_principal)
+                        {
+                            return RegistryResult::Err(
+                                "Unauthorized: Only the company creator or an admin can do this".to_string(),
+                            );
+                        }
+                        archived.reason = ArchiveReason::Deleted;
+                        archived.archived_by = caller_principal;
+                        archived.archived_at = time();
+                        StorageManager::insert_archived_company(archived.clone());
+                        AuditManager::record("company_deleted", company_id, vec![caller_principal], String::new());
+                        RegistryResult::Ok(archived)
+                    }
+                    Some(_) => RegistryResult::Err("Company is already archived".to_string()),
+                    None => RegistryResult::Err("Company not found".to_string()),
+                };
+            }
+        }
+        match Self::move_to_archive(company_id, reason, caller_principal) {
+            Ok(archived) => RegistryResult::Ok(archived),
+            Err(err) => RegistryResult::Err(err),
+        }
+    }
+
+    // Shared with the multi-admin CompanyDeletion critical action
+    pub fn move_to_archive(
+        company_id: &str,
+        reason: ArchiveReason,
+        actor: Principal,
+    ) -> Result<ArchivedCompany, String> {
+        let company = StorageManager::remove_company(company_id).ok_or("Company not found")?;
+        StorageManager::remove_company_dependents(company_id);
+
+        let archived = ArchivedCompany {
+            company,
+            reason,
+            archived_by: actor,
+            archived_at: time(),
+        };
+        StorageManager::insert_archived_company(archived.clone());
+        let action = match archived.reason {
+            ArchiveReason::Archived => "company_archived",
+            ArchiveReason::Deleted => "company_deleted",
+        };
+        AuditManager::record(action, company_id, vec![actor], String::new());
+        Ok(archived)
+    }
+
+}
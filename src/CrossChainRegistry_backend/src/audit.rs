@@ -0,0 +1,47 @@
+use crate::moderation::ModerationManager;
+use crate::storage::StorageManager;
+use crate::types::{AuditLogEntry, RegistryResult};
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Append-only trail of privileged actions, readable by moderators
+
+pub struct AuditManager;
+
+impl AuditManager {
+    const MAX_PAGE_SIZE: u32 = 200;
+
+    pub fn record(action: &str, target: &str, actors: Vec<Principal>, details: String) {
+        // Zero-padded sequence keeps the stable map in chronological order
+        let entry = AuditLogEntry {
+            id: format!("audit_{:012}", StorageManager::audit_log_count()),
+            action: action.to_string(),
+            target: target.to_string(),
+            actors,
+            details,
+            timestamp: time(),
+        };
+        StorageManager::insert_audit_log_entry(entry);
+    }
+
+    // Newest first
+    pub fn get_admin_audit_log(
+        offset: Option<u32>,
+        limit: Option<u32>,
+        caller_principal: Principal,
+    ) -> RegistryResult<Vec<AuditLogEntry>> {
+        if let Err(err) = ModerationManager::ensure_moderator(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        let offset = offset.unwrap_or(0) as usize;
+        let limit = limit.unwrap_or(50).min(Self::MAX_PAGE_SIZE) as usize;
+        let entries = StorageManager::get_all_audit_log_entries()
+            .into_iter()
+            .rev()
+            .skip(offset)
+            .take(limit)
+            .collect();
+        RegistryResult::Ok(entries)
+    }
+}
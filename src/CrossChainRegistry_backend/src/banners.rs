@@ -0,0 +1,137 @@
+use crate::moderation::ModerationManager;
+use crate::ownership::OwnershipManager;
+use crate::sanitize::SafeText;
+use crate::storage::StorageManager;
+use crate::types::{BannerSeverity, BannerSource, Company, RegistryResult, WarningBanner};
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Time-boxed warning banners for compromised companies (e.g. a hacked Discord).
+// The banner is stored on the company and returned with every company query while active.
+
+pub struct BannerManager;
+
+impl BannerManager {
+    const MAX_MESSAGE_LENGTH: usize = 500;
+    const MAX_DURATION_NS: u64 = 30 * 24 * 60 * 60 * 1_000_000_000; // 30 days
+
+    pub fn is_active(banner: &WarningBanner, now: u64) -> bool {
+        banner.starts_at <= now && now < banner.ends_at
+    }
+
+    // Expired and scheduled banners are not shown in responses
+    pub fn strip_inactive_banner(company: &mut Company) {
+        let now = time();
+        if company
+            .warning_banner
+            .as_ref()
+            .is_some_and(|banner| !Self::is_active(banner, now))
+        {
+            company.warning_banner = None;
+        }
+    }
+
+    pub fn publish_warning_banner(
+        company_id: String,
+        message: String,
+        severity: BannerSeverity,
+        starts_at: Option<u64>,
+        ends_at: u64,
+        caller_principal: Principal,
+    ) -> RegistryResult<WarningBanner> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        let source = if ModerationManager::is_moderator(caller_principal) {
+            BannerSource::Moderator
+        } else if OwnershipManager::is_owner(&company, caller_principal) {
+            BannerSource::Owner
+        } else {
+            return RegistryResult::Err(
+                "Unauthorized: Only moderators or the company owner can publish a warning banner".to_string(),
+            );
+        };
+
+        // Owners cannot replace a banner moderators put up
+        let now = time();
+        if let Some(existing) = &company.warning_banner {
+            if existing.source == BannerSource::Moderator
+                && source == BannerSource::Owner
+                && existing.ends_at > now
+            {
+                return RegistryResult::Err(
+                    "A moderator banner is in place and can only be changed by moderators".to_string(),
+                );
+            }
+        }
+
+        let message = match SafeText::parse_required(&message, "Banner message", Self::MAX_MESSAGE_LENGTH) {
+            Ok(text) => text.into_inner(),
+            Err(err) => return RegistryResult::Err(err),
+        };
+
+        let starts_at = starts_at.unwrap_or(now).max(now);
+        if ends_at <= starts_at {
+            return RegistryResult::Err("Banner must end after it starts".to_string());
+        }
+        if ends_at - starts_at > Self::MAX_DURATION_NS {
+            return RegistryResult::Err("Banner cannot be shown for more than 30 days".to_string());
+        }
+
+        let banner = WarningBanner {
+            message,
+            severity,
+            starts_at,
+            ends_at,
+            source,
+            published_by: caller_principal,
+            published_at: now,
+        };
+
+        let stored = banner.clone();
+        StorageManager::update_company(&company_id, |company| {
+            company.warning_banner = Some(stored);
+        });
+
+        RegistryResult::Ok(banner)
+    }
+
+    pub fn remove_warning_banner(company_id: String, caller_principal: Principal) -> RegistryResult<()> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        let banner = match &company.warning_banner {
+            Some(banner) => banner,
+            None => return RegistryResult::Err("Company has no warning banner".to_string()),
+        };
+
+        let is_moderator = ModerationManager::is_moderator(caller_principal);
+        let owner_can_remove =
+            OwnershipManager::is_owner(&company, caller_principal) && banner.source == BannerSource::Owner;
+        if !(is_moderator || owner_can_remove) {
+            return RegistryResult::Err(
+                "Unauthorized: Only moderators can remove a moderator banner".to_string(),
+            );
+        }
+
+        StorageManager::update_company(&company_id, |company| {
+            company.warning_banner = None;
+        });
+
+        RegistryResult::Ok(())
+    }
+
+    pub fn get_warning_banner(company_id: String) -> RegistryResult<Option<WarningBanner>> {
+        match StorageManager::get_company(&company_id) {
+            Some(company) => {
+                let now = time();
+                RegistryResult::Ok(company.warning_banner.filter(|banner| Self::is_active(banner, now)))
+            }
+            None => RegistryResult::Err("Company not found".to_string()),
+        }
+    }
+}
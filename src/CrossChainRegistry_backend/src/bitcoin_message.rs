@@ -0,0 +1,345 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use k256::ecdsa::signature::hazmat::PrehashVerifier;
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
+// Checks that a message was signed by the key behind a Bitcoin address. Two encodings
+// are accepted, both base64 as wallets export them:
+//
+// - BIP-137 "Sign Message" (65 bytes: header + compact signature), for P2PKH, P2SH-P2WPKH
+//   and P2WPKH addresses; the public key is recovered and hashed back to the address.
+// - BIP-322 simple (a serialized witness stack), for P2WPKH addresses; the witness must
+//   validly spend the address's virtual to_spend output committing to the message.
+//
+// Taproot and other script addresses aren't supported yet.
+
+enum AddressKind {
+    P2pkh([u8; 20]),
+    P2sh([u8; 20]),
+    P2wpkh([u8; 20]),
+}
+
+pub struct BitcoinMessageVerifier;
+
+impl BitcoinMessageVerifier {
+    const MESSAGE_MAGIC: &'static [u8] = b"\x18Bitcoin Signed Message:\n";
+    const BIP322_TAG: &'static [u8] = b"BIP0322-signed-message";
+    const SIGHASH_ALL: u8 = 0x01;
+
+    pub fn verify(address: &str, message: &str, signature: &str) -> Result<(), String> {
+        let kind = Self::address_kind(address)?;
+        let bytes = STANDARD
+            .decode(signature.trim())
+            .map_err(|_| "Signature must be base64".to_string())?;
+
+        if bytes.len() == 65 && (27..=42).contains(&bytes[0]) {
+            Self::verify_bip137(&kind, message, &bytes)
+        } else {
+            Self::verify_bip322_simple(&kind, message, &bytes)
+        }
+    }
+
+    fn address_kind(address: &str) -> Result<AddressKind, String> {
+        if address.to_lowercase().starts_with("bc1") {
+            let (hrp, version, program) =
+                bech32::segwit::decode(address).map_err(|_| "Invalid bech32 address".to_string())?;
+            if hrp != bech32::hrp::BC {
+                return Err("Only mainnet addresses can be verified".to_string());
+            }
+            return match (version.to_u8(), <[u8; 20]>::try_from(program.as_slice())) {
+                (0, Ok(hash)) => Ok(AddressKind::P2wpkh(hash)),
+                _ => Err("Only P2PKH, P2SH-P2WPKH and P2WPKH addresses support signed messages".to_string()),
+            };
+        }
+
+        let payload = bs58::decode(address)
+            .with_check(None)
+            .into_vec()
+            .map_err(|_| "Invalid base58 address".to_string())?;
+        match (payload.first(), <[u8; 20]>::try_from(&payload[1.min(payload.len())..])) {
+            (Some(0x00), Ok(hash)) => Ok(AddressKind::P2pkh(hash)),
+            (Some(0x05), Ok(hash)) => Ok(AddressKind::P2sh(hash)),
+            _ => Err("Only mainnet P2PKH and P2SH addresses can be verified".to_string()),
+        }
+    }
+
+    fn verify_bip137(kind: &AddressKind, message: &str, bytes: &[u8]) -> Result<(), String> {
+        let header = bytes[0];
+        // 27-30 uncompressed P2PKH; 31-42 compressed keys (P2PKH, P2SH-P2WPKH, P2WPKH)
+        let compressed = header >= 31;
+        let recovery_id = RecoveryId::from_byte((header - 27) & 3).ok_or("Invalid signature header")?;
+        let signature = Signature::from_slice(&bytes[1..]).map_err(|_| "Invalid signature".to_string())?;
+        // Wallets only emit low-S; a high-S copy is a malleated signature, not a new one
+        if signature.normalize_s().is_some() {
+            return Err("Signature must use a low S value".to_string());
+        }
+
+        let mut preimage = Self::MESSAGE_MAGIC.to_vec();
+        Self::push_var_bytes(&mut preimage, message.as_bytes());
+        let digest = Self::sha256d(&preimage);
+        let key = VerifyingKey::recover_from_prehash(&digest, &signature, recovery_id)
+            .map_err(|_| "Signature does not match the message".to_string())?;
+        let public_key = key.to_encoded_point(compressed);
+        let key_hash = Self::hash160(public_key.as_bytes());
+
+        let matches = match kind {
+            AddressKind::P2pkh(hash) => key_hash == *hash,
+            AddressKind::P2sh(hash) => compressed && Self::hash160(&Self::p2wpkh_script(&key_hash)) == *hash,
+            AddressKind::P2wpkh(hash) => compressed && key_hash == *hash,
+        };
+        if matches {
+            Ok(())
+        } else {
+            Err("Message was signed by a different address".to_string())
+        }
+    }
+
+    fn verify_bip322_simple(kind: &AddressKind, message: &str, witness: &[u8]) -> Result<(), String> {
+        let key_hash = match kind {
+            AddressKind::P2wpkh(hash) => *hash,
+            _ => return Err("BIP-322 signatures are only supported for P2WPKH (bc1q) addresses".to_string()),
+        };
+        let items = Self::parse_witness(witness).ok_or("Signature is neither BIP-137 nor a BIP-322 witness")?;
+        let (signature, public_key) = match items.as_slice() {
+            [signature, public_key] if public_key.len() == 33 => (signature, public_key),
+            _ => return Err("A P2WPKH witness must hold a signature and a compressed public key".to_string()),
+        };
+        if Self::hash160(public_key) != key_hash {
+            return Err("Message was signed by a different address".to_string());
+        }
+        let (sighash_type, der) = signature.split_last().ok_or("Empty witness signature")?;
+        if *sighash_type != Self::SIGHASH_ALL {
+            return Err("Only SIGHASH_ALL BIP-322 signatures are supported".to_string());
+        }
+
+        let sighash = Self::bip322_sighash(&key_hash, message);
+        let key = VerifyingKey::from_sec1_bytes(public_key).map_err(|_| "Invalid public key".to_string())?;
+        let signature = Signature::from_der(der).map_err(|_| "Invalid DER signature".to_string())?;
+        if signature.normalize_s().is_some() {
+            return Err("Signature must use a low S value".to_string());
+        }
+        key.verify_prehash(&sighash, &signature)
+            .map_err(|_| "Signature does not match the message".to_string())
+    }
+
+    // BIP-143 digest of the virtual to_sign transaction spending to_spend's only output
+    fn bip322_sighash(key_hash: &[u8; 20], message: &str) -> [u8; 32] {
+        let tag_hash = Sha256::digest(Self::BIP322_TAG);
+        let message_hash = Sha256::new()
+            .chain_update(tag_hash)
+            .chain_update(tag_hash)
+            .chain_update(message.as_bytes())
+            .finalize();
+
+        // to_spend: version 0, one input from the null outpoint whose scriptSig is
+        // OP_0 PUSH32 <message hash>, one 0-value output to the address, locktime 0
+        let mut to_spend = Vec::new();
+        to_spend.extend_from_slice(&0u32.to_le_bytes());
+        to_spend.push(1);
+        to_spend.extend_from_slice(&[0u8; 32]);
+        to_spend.extend_from_slice(&u32::MAX.to_le_bytes());
+        let mut script_sig = vec![0x00, 0x20];
+        script_sig.extend_from_slice(&message_hash);
+        Self::push_var_bytes(&mut to_spend, &script_sig);
+        to_spend.extend_from_slice(&0u32.to_le_bytes());
+        to_spend.push(1);
+        to_spend.extend_from_slice(&0u64.to_le_bytes());
+        Self::push_var_bytes(&mut to_spend, &Self::p2wpkh_script(key_hash));
+        to_spend.extend_from_slice(&0u32.to_le_bytes());
+        let to_spend_txid = Self::sha256d(&to_spend);
+
+        // to_sign spends to_spend:0 with sequence 0 into a single OP_RETURN output
+        let mut outpoint = to_spend_txid.to_vec();
+        outpoint.extend_from_slice(&0u32.to_le_bytes());
+        let mut output = 0u64.to_le_bytes().to_vec();
+        Self::push_var_bytes(&mut output, &[0x6a]);
+        let mut script_code = vec![0x76, 0xa9, 0x14];
+        script_code.extend_from_slice(key_hash);
+        script_code.extend_from_slice(&[0x88, 0xac]);
+
+        let mut preimage = Vec::new();
+        preimage.extend_from_slice(&0u32.to_le_bytes());
+        preimage.extend_from_slice(&Self::sha256d(&outpoint));
+        preimage.extend_from_slice(&Self::sha256d(&0u32.to_le_bytes()));
+        preimage.extend_from_slice(&outpoint);
+        Self::push_var_bytes(&mut preimage, &script_code);
+        preimage.extend_from_slice(&0u64.to_le_bytes());
+        preimage.extend_from_slice(&0u32.to_le_bytes());
+        preimage.extend_from_slice(&Self::sha256d(&output));
+        preimage.extend_from_slice(&0u32.to_le_bytes());
+        preimage.extend_from_slice(&u32::from(Self::SIGHASH_ALL).to_le_bytes());
+        Self::sha256d(&preimage)
+    }
+
+    // Consensus-serialized witness stack: item count, then length-prefixed items
+    fn parse_witness(bytes: &[u8]) -> Option<Vec<Vec<u8>>> {
+        let mut position = 0;
+        let count = Self::read_var_int(bytes, &mut position)?;
+        let mut items = Vec::new();
+        for _ in 0..count.min(16) {
+            let length = Self::read_var_int(bytes, &mut position)? as usize;
+            let item = bytes.get(position..position.checked_add(length)?)?;
+            items.push(item.to_vec());
+            position += length;
+        }
+        (items.len() as u64 == count && position == bytes.len()).then_some(items)
+    }
+
+    fn read_var_int(bytes: &[u8], position: &mut usize) -> Option<u64> {
+        let prefix = *bytes.get(*position)?;
+        *position += 1;
+        let width = match prefix {
+            0xfd => 2,
+            0xfe => 4,
+            0xff => 8,
+            _ => return Some(u64::from(prefix)),
+        };
+        let raw = bytes.get(*position..*position + width)?;
+        *position += width;
+        Some(raw.iter().rev().fold(0u64, |value, byte| (value << 8) | u64::from(*byte)))
+    }
+
+    fn push_var_bytes(buffer: &mut Vec<u8>, data: &[u8]) {
+        match data.len() {
+            length @ 0..=0xfc => buffer.push(length as u8),
+            length @ 0xfd..=0xffff => {
+                buffer.push(0xfd);
+                buffer.extend_from_slice(&(length as u16).to_le_bytes());
+            }
+            length => {
+                buffer.push(0xfe);
+                buffer.extend_from_slice(&(length as u32).to_le_bytes());
+            }
+        }
+        buffer.extend_from_slice(data);
+    }
+
+    fn p2wpkh_script(key_hash: &[u8; 20]) -> Vec<u8> {
+        let mut script = vec![0x00, 0x14];
+        script.extend_from_slice(key_hash);
+        script
+    }
+
+    fn sha256d(data: &[u8]) -> [u8; 32] {
+        Sha256::digest(Sha256::digest(data)).into()
+    }
+
+    fn hash160(data: &[u8]) -> [u8; 20] {
+        Ripemd160::digest(Sha256::digest(data)).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // BIP-322 test vectors: key L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k
+    const BIP322_ADDRESS: &str = "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l";
+    const BIP322_EMPTY: &str = "AkcwRAIgM2gBAQqvZX15ZiysmKmQpDrG83avLIT492QBzLnQIxYCIBaTpOaD20qRlEylyxFSeEA2ba9YOixpX8z46TSDtS40ASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=";
+    const BIP322_HELLO: &str = "AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=";
+
+    // bitcoinjs-message README: key L4rK1yDtCWekvXuE6oXD9jCYfFNV2cWRpVuPLBcCU2z8TrisoyY1.
+    // Signing is RFC 6979, so every address type shares R and S and only the header differs.
+    const BIP137_MESSAGE: &str = "This is an example of a signed message.";
+    const BIP137_UNCOMPRESSED: (&str, &str) =
+        ("1HZwkjkeaoZfTSaJxDw6aKkxp45agDiEzN", "G9L5yLFjti0QTHhPyFrZCT1V/MMnBtXKmoiKDZ78NDBjERki6ZTQZdSMCtkgoNmp17By9ItJr8o7ChX0XxY91nk=");
+    const BIP137_P2PKH: (&str, &str) =
+        ("1F3sAm6ZtwLAUnj7d38pGFxtP3RVEvtsbV", "H9L5yLFjti0QTHhPyFrZCT1V/MMnBtXKmoiKDZ78NDBjERki6ZTQZdSMCtkgoNmp17By9ItJr8o7ChX0XxY91nk=");
+    const BIP137_P2SH_P2WPKH: (&str, &str) =
+        ("3DnW8JGpPViEZdpqat8qky1zc26EKbXnmM", "I9L5yLFjti0QTHhPyFrZCT1V/MMnBtXKmoiKDZ78NDBjERki6ZTQZdSMCtkgoNmp17By9ItJr8o7ChX0XxY91nk=");
+    const BIP137_P2WPKH: (&str, &str) =
+        ("bc1qngw83fg8dz0k749cg7k3emc7v98wy0c74dlrkd", "J9L5yLFjti0QTHhPyFrZCT1V/MMnBtXKmoiKDZ78NDBjERki6ZTQZdSMCtkgoNmp17By9ItJr8o7ChX0XxY91nk=");
+
+    #[test]
+    fn bip322_message_hash_matches_the_spec() {
+        let tag_hash = Sha256::digest(BitcoinMessageVerifier::BIP322_TAG);
+        let hash = |message: &str| {
+            hex::encode(Sha256::new().chain_update(tag_hash).chain_update(tag_hash).chain_update(message).finalize())
+        };
+        assert_eq!(hash(""), "c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1");
+        assert_eq!(hash("Hello World"), "f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a");
+    }
+
+    #[test]
+    fn bip322_simple_vectors_verify() {
+        assert_eq!(BitcoinMessageVerifier::verify(BIP322_ADDRESS, "", BIP322_EMPTY), Ok(()));
+        assert_eq!(BitcoinMessageVerifier::verify(BIP322_ADDRESS, "Hello World", BIP322_HELLO), Ok(()));
+    }
+
+    #[test]
+    fn bip322_rejects_swapped_messages_and_other_addresses() {
+        assert_eq!(
+            BitcoinMessageVerifier::verify(BIP322_ADDRESS, "Hello World", BIP322_EMPTY),
+            Err("Signature does not match the message".to_string())
+        );
+        assert_eq!(
+            BitcoinMessageVerifier::verify(BIP137_P2WPKH.0, "Hello World", BIP322_HELLO),
+            Err("Message was signed by a different address".to_string())
+        );
+        assert_eq!(
+            BitcoinMessageVerifier::verify(BIP137_P2PKH.0, "Hello World", BIP322_HELLO),
+            Err("BIP-322 signatures are only supported for P2WPKH (bc1q) addresses".to_string())
+        );
+    }
+
+    #[test]
+    fn bip137_vectors_verify_for_each_header_range() {
+        for (address, signature) in [BIP137_UNCOMPRESSED, BIP137_P2PKH, BIP137_P2SH_P2WPKH, BIP137_P2WPKH] {
+            assert_eq!(BitcoinMessageVerifier::verify(address, BIP137_MESSAGE, signature), Ok(()), "{}", address);
+        }
+    }
+
+    #[test]
+    fn bip137_verifies_bitcoin_core_signmessage_vector() {
+        // rpc_signmessage.py signs with cUeKHd5orzT3mz8P9pxyREHfsWtVfgsfDjiZZBcjUBAaGk1BTj7N
+        // for testnet mpLQjfK79b7CCV4VMJWEWAj5Mpx8Up5zxB; this is the same key hash on mainnet
+        assert_eq!(
+            BitcoinMessageVerifier::verify(
+                "19pTScE8LZfwRNasdjXrgFWkVqMRcU99GK",
+                "This is just a test message",
+                "INbVnW4e6PeRmsv2Qgu8NuopvrVjkcxob+sX8OcZG0SALhWybUjzMLPdAsXI46YZGb0KQTRii+wWIQzRpG/U+S0=",
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn bip137_rejects_a_header_for_the_wrong_key_encoding() {
+        // Compressed and uncompressed keys hash to different P2PKH addresses
+        assert_eq!(
+            BitcoinMessageVerifier::verify(BIP137_UNCOMPRESSED.0, BIP137_MESSAGE, BIP137_P2PKH.1),
+            Err("Message was signed by a different address".to_string())
+        );
+        assert_eq!(
+            BitcoinMessageVerifier::verify(BIP137_P2PKH.0, BIP137_MESSAGE, BIP137_UNCOMPRESSED.1),
+            Err("Message was signed by a different address".to_string())
+        );
+        // Segwit addresses never use uncompressed keys
+        assert_eq!(
+            BitcoinMessageVerifier::verify(BIP137_P2WPKH.0, BIP137_MESSAGE, BIP137_UNCOMPRESSED.1),
+            Err("Message was signed by a different address".to_string())
+        );
+    }
+
+    #[test]
+    fn bip137_rejects_a_different_message() {
+        let (address, signature) = BIP137_P2PKH;
+        assert!(BitcoinMessageVerifier::verify(address, "This is a different message.", signature).is_err());
+    }
+
+    #[test]
+    fn bip137_rejects_high_s() {
+        let (address, signature) = BIP137_P2PKH;
+        let mut bytes = STANDARD.decode(signature).unwrap();
+        let low = Signature::from_slice(&bytes[1..]).unwrap();
+        let high = Signature::from_scalars(low.r().to_bytes(), (-*low.s()).to_bytes()).unwrap();
+        // Negating S mirrors R, so the recovery parity in the header flips with it
+        bytes[0] ^= 1;
+        bytes[1..].copy_from_slice(&high.to_bytes());
+        assert_eq!(
+            BitcoinMessageVerifier::verify(address, BIP137_MESSAGE, &STANDARD.encode(&bytes)),
+            Err("Signature must use a low S value".to_string())
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use crate::moderation::ModerationManager;
+use crate::sanitize::{self, SafeUrl};
+use crate::storage::StorageManager;
+use crate::types::{BlocklistEntry, BlocklistKind, RegistryResult};
+use candid::Principal;
+
+// Principals and domains barred from registering. Entries are only added through an
+// approved CriticalAction::BlocklistAddition, never directly.
+
+pub struct BlocklistManager;
+
+impl BlocklistManager {
+    pub fn normalize(kind: &BlocklistKind, value: &str) -> Result<String, String> {
+        match kind {
+            BlocklistKind::Principal => Principal::from_text(value.trim())
+                .map(|p| p.to_text())
+                .map_err(|_| "Invalid principal".to_string()),
+            BlocklistKind::Domain => {
+                // Same punycode form SafeUrl gives website hosts
+                let domain = sanitize::domain_to_ascii(value).map_err(|_| "Invalid domain".to_string())?;
+                let domain = domain.strip_prefix("www.").unwrap_or(&domain);
+                if domain.is_empty() || !domain.contains('.') || domain.contains('/') {
+                    return Err("Invalid domain".to_string());
+                }
+                Ok(domain.to_string())
+            }
+        }
+    }
+
+    pub fn key(kind: &BlocklistKind, normalized_value: &str) -> String {
+        format!("{:?}:{}", kind, normalized_value)
+    }
+
+    pub fn is_principal_blocked(principal: &Principal) -> bool {
+        StorageManager::get_blocklist_entry(&Self::key(&BlocklistKind::Principal, &principal.to_text())).is_some()
+    }
+
+    // A blocked domain also covers its subdomains
+    pub fn is_website_blocked(website: &str) -> bool {
+        let url = match SafeUrl::parse_web(website, "Website") {
+            Ok(url) => url,
+            Err(_) => return false,
+        };
+        let domain = url.site_host();
+
+        let mut candidate = domain;
+        loop {
+            if StorageManager::get_blocklist_entry(&Self::key(&BlocklistKind::Domain, candidate)).is_some() {
+                return true;
+            }
+            match candidate.split_once('.') {
+                Some((_, parent)) if parent.contains('.') => candidate = parent,
+                _ => return false,
+            }
+        }
+    }
+
+    pub fn check_registration(caller_principal: &Principal, website: &str) -> Result<(), String> {
+        if Self::is_principal_blocked(caller_principal) {
+            return Err("This principal is blocked from the registry".to_string());
+        }
+        if Self::is_website_blocked(website) {
+            return Err("This website's domain is blocked from the registry".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn get_blocklist(caller_principal: Principal) -> RegistryResult<Vec<BlocklistEntry>> {
+        if let Err(err) = ModerationManager::ensure_moderator(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        RegistryResult::Ok(StorageManager::get_all_blocklist_entries())
+    }
+}
@@ -0,0 +1,143 @@
+use crate::settings::SettingsManager;
+use crate::storage::StorageManager;
+use crate::types::{CacheInvalidationHook, CompanyEvent, RegistryResult};
+use candid::Principal;
+use ic_cdk::api::time;
+use std::time::Duration;
+
+// Pushes company changes to a configured canister (normally the frontend asset canister)
+// so it can invalidate or re-render cached pages. Changes are read from the company event
+// journal in batches; the cursor only moves once the target accepts a batch, so a failed
+// call is retried with exponential backoff and nothing is lost in between.
+
+pub struct CacheHookManager;
+
+impl CacheHookManager {
+    const JOURNAL_CONSUMER: &'static str = "cache_hook";
+    const DELIVERY_INTERVAL_SECS: u64 = 30;
+    const MAX_BATCH_EVENTS: usize = 200;
+    const MAX_METHOD_LENGTH: usize = 64;
+    const MAX_ERROR_LENGTH: usize = 200;
+    const BASE_BACKOFF_NS: u64 = 30_000_000_000; // 30 seconds
+    const MAX_BACKOFF_NS: u64 = 60 * 60 * 1_000_000_000; // 1 hour
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::DELIVERY_INTERVAL_SECS), || {
+            ic_cdk::spawn(async {
+                Self::deliver_pending().await;
+            })
+        });
+    }
+
+    pub fn set_cache_invalidation_hook(
+        target: Principal,
+        method: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<CacheInvalidationHook> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let method = method.trim().to_string();
+        let valid_method = !method.is_empty()
+            && method.len() <= Self::MAX_METHOD_LENGTH
+            && method.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_method {
+            return RegistryResult::Err("Method must be a plain Candid method name".to_string());
+        }
+        if target == Principal::anonymous() || target == ic_cdk::id() {
+            return RegistryResult::Err("Target must be another canister".to_string());
+        }
+
+        let hook = CacheInvalidationHook {
+            target,
+            method,
+            configured_at: time(),
+            consecutive_failures: 0,
+            last_error: None,
+            next_attempt_at: 0,
+            last_delivered_at: None,
+        };
+        StorageManager::set_cache_invalidation_hook(hook.clone());
+        // Start from now; past changes are already reflected in whatever the target serves
+        StorageManager::set_journal_cursor(Self::JOURNAL_CONSUMER, StorageManager::latest_company_event_seq());
+        RegistryResult::Ok(hook)
+    }
+
+    pub fn remove_cache_invalidation_hook(caller_principal: Principal) -> RegistryResult<()> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        StorageManager::remove_cache_invalidation_hook();
+        // Otherwise the stale cursor would hold back journal pruning forever
+        StorageManager::remove_journal_cursor(Self::JOURNAL_CONSUMER);
+        RegistryResult::Ok(())
+    }
+
+    pub fn get_cache_invalidation_hook(caller_principal: Principal) -> RegistryResult<Option<CacheInvalidationHook>> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        RegistryResult::Ok(StorageManager::get_cache_invalidation_hook())
+    }
+
+    // Sends at most one batch; returns the number of changes the target accepted
+    pub async fn deliver_pending() -> u32 {
+        let hook = match StorageManager::get_cache_invalidation_hook() {
+            Some(hook) if time() >= hook.next_attempt_at => hook,
+            _ => return 0,
+        };
+        let cursor = StorageManager::get_journal_cursor(Self::JOURNAL_CONSUMER);
+        let events = StorageManager::get_company_events_after(cursor, Self::MAX_BATCH_EVENTS);
+        let last_seq = match events.last() {
+            Some(event) => event.seq,
+            None => return 0,
+        };
+        let changes = Self::latest_per_company(events);
+        let change_count = changes.len() as u32;
+        if !StorageManager::try_begin_cache_hook_delivery() {
+            return 0;
+        }
+
+        let result: Result<(), _> = ic_cdk::call(hook.target, &hook.method, (changes,)).await;
+        StorageManager::end_cache_hook_delivery();
+
+        // The hook may have been replaced or removed while the call was out
+        let mut current = match StorageManager::get_cache_invalidation_hook() {
+            Some(current) if current.configured_at == hook.configured_at => current,
+            _ => return 0,
+        };
+        let now = time();
+        match result {
+            Ok(()) => {
+                StorageManager::set_journal_cursor(Self::JOURNAL_CONSUMER, last_seq);
+                current.consecutive_failures = 0;
+                current.last_error = None;
+                current.next_attempt_at = 0;
+                current.last_delivered_at = Some(now);
+                StorageManager::set_cache_invalidation_hook(current);
+                change_count
+            }
+            Err((code, message)) => {
+                current.consecutive_failures += 1;
+                let error = format!("{:?}: {}", code, message);
+                current.last_error = Some(error.chars().take(Self::MAX_ERROR_LENGTH).collect());
+                let backoff = Self::BASE_BACKOFF_NS
+                    .saturating_mul(1u64 << current.consecutive_failures.min(16))
+                    .min(Self::MAX_BACKOFF_NS);
+                current.next_attempt_at = now.saturating_add(backoff);
+                StorageManager::set_cache_invalidation_hook(current);
+                0
+            }
+        }
+    }
+
+    // A company written several times in one batch only needs invalidating once
+    fn latest_per_company(events: Vec<CompanyEvent>) -> Vec<CompanyEvent> {
+        let mut changes: Vec<CompanyEvent> = Vec::new();
+        for event in events {
+            changes.retain(|change| change.company_id != event.company_id);
+            changes.push(event);
+        }
+        changes
+    }
+}
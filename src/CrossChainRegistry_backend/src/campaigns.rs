@@ -0,0 +1,282 @@
+use crate::admins::AdminManager;
+use crate::api::RegistryAPI;
+use crate::audit::AuditManager;
+use crate::notifications::NotificationManager;
+use crate::ownership::OwnershipManager;
+use crate::sanitize::{self, SafeText};
+use crate::storage::StorageManager;
+use crate::types::{
+    CampaignAction, CampaignConsequence, CampaignProgress, CampaignStatus, CampaignTask, Company, CompanyStatus,
+    CreateCampaignRequest, ProofStatus, RegistryResult, ReverificationCampaign,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+use std::time::Duration;
+
+// Re-verification campaigns: when scoring rules or proof requirements change, admins ask
+// a cohort of companies (selected with the usual search filters) to redo some steps by a
+// deadline. Completion is judged from the companies' own records, so nothing has to be
+// reported back; whoever hasn't finished by the deadline gets the campaign's consequence.
+
+pub struct CampaignManager;
+
+impl CampaignManager {
+    const MAX_DEADLINE_DAYS: u32 = 180;
+    const REMINDER_BEFORE_NS: u64 = 3 * 24 * 60 * 60 * 1_000_000_000; // 3 days
+    const ENFORCEMENT_INTERVAL_SECS: u64 = 60 * 60;
+    const DAY_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::ENFORCEMENT_INTERVAL_SECS), || {
+            Self::run_campaign_enforcement();
+        });
+    }
+
+    pub fn create_reverification_campaign(
+        request: CreateCampaignRequest,
+        caller_principal: Principal,
+    ) -> RegistryResult<ReverificationCampaign> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let policy = sanitize::policy();
+        let title = match SafeText::parse_required(&request.title, "Title", policy.max_label) {
+            Ok(text) => text.into_inner(),
+            Err(err) => return RegistryResult::Err(err),
+        };
+        let reason = match SafeText::parse_required(&request.reason, "Reason", policy.max_message) {
+            Ok(text) => text.into_inner(),
+            Err(err) => return RegistryResult::Err(err),
+        };
+        if request.required_actions.is_empty() {
+            return RegistryResult::Err("A campaign needs at least one required action".to_string());
+        }
+        if request.deadline_days == 0 || request.deadline_days > Self::MAX_DEADLINE_DAYS {
+            return RegistryResult::Err(format!("Deadline must be 1-{} days", Self::MAX_DEADLINE_DAYS));
+        }
+
+        let enrolled_company_ids: Vec<String> = StorageManager::get_all_companies()
+            .into_iter()
+            .filter(|company| RegistryAPI::matches_filters(company, &request.target))
+            .map(|company| company.id)
+            .collect();
+        if enrolled_company_ids.is_empty() {
+            return RegistryResult::Err("No companies match the campaign target".to_string());
+        }
+
+        let now = time();
+        let campaign = ReverificationCampaign {
+            id: format!("campaign_{}_{}", now, StorageManager::reverification_campaign_count()),
+            title,
+            reason,
+            target: request.target,
+            required_actions: request.required_actions,
+            consequence: request.consequence,
+            enrolled_company_ids,
+            created_by: caller_principal,
+            created_at: now,
+            deadline: now + request.deadline_days as u64 * Self::DAY_NS,
+            status: CampaignStatus::Active,
+            reminder_sent_at: None,
+            closed_at: None,
+            consequences_applied: 0,
+        };
+        StorageManager::insert_reverification_campaign(campaign.clone());
+        AuditManager::record(
+            "reverification_campaign_created",
+            &campaign.id,
+            vec![caller_principal],
+            format!("{} companies enrolled", campaign.enrolled_company_ids.len()),
+        );
+
+        Self::notify_pending(
+            &campaign,
+            &format!(
+                "Re-verification requested: {}. {} Complete it within {} days.",
+                campaign.title, campaign.reason, request.deadline_days
+            ),
+        );
+        RegistryResult::Ok(campaign)
+    }
+
+    pub fn cancel_reverification_campaign(
+        campaign_id: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<ReverificationCampaign> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let mut campaign = match StorageManager::get_reverification_campaign(&campaign_id) {
+            Some(campaign) => campaign,
+            None => return RegistryResult::Err("Campaign not found".to_string()),
+        };
+        if campaign.status != CampaignStatus::Active {
+            return RegistryResult::Err("Only active campaigns can be cancelled".to_string());
+        }
+        campaign.status = CampaignStatus::Cancelled;
+        campaign.closed_at = Some(time());
+        StorageManager::insert_reverification_campaign(campaign.clone());
+        AuditManager::record("reverification_campaign_cancelled", &campaign_id, vec![caller_principal], String::new());
+        RegistryResult::Ok(campaign)
+    }
+
+    pub fn list_reverification_campaigns() -> Vec<ReverificationCampaign> {
+        let mut campaigns = StorageManager::get_all_reverification_campaigns();
+        campaigns.sort_by_key(|c| std::cmp::Reverse(c.created_at));
+        campaigns
+    }
+
+    pub fn get_campaign_progress(campaign_id: String) -> RegistryResult<CampaignProgress> {
+        let campaign = match StorageManager::get_reverification_campaign(&campaign_id) {
+            Some(campaign) => campaign,
+            None => return RegistryResult::Err("Campaign not found".to_string()),
+        };
+        let pending_company_ids: Vec<String> = Self::pending_companies(&campaign)
+            .into_iter()
+            .map(|(company, _)| company.id)
+            .collect();
+        RegistryResult::Ok(CampaignProgress {
+            campaign_id,
+            status: campaign.status,
+            deadline: campaign.deadline,
+            enrolled: campaign.enrolled_company_ids.len() as u32,
+            completed: (campaign.enrolled_company_ids.len() - pending_company_ids.len()) as u32,
+            pending_company_ids,
+        })
+    }
+
+    // Open campaign work on companies the caller manages
+    pub fn get_my_campaign_tasks(caller_principal: Principal) -> Vec<CampaignTask> {
+        let mut tasks = Vec::new();
+        for campaign in StorageManager::get_all_reverification_campaigns() {
+            if campaign.status != CampaignStatus::Active {
+                continue;
+            }
+            for (company, missing_actions) in Self::pending_companies(&campaign) {
+                if OwnershipManager::is_manager(&company, caller_principal) {
+                    tasks.push(CampaignTask {
+                        campaign_id: campaign.id.clone(),
+                        title: campaign.title.clone(),
+                        reason: campaign.reason.clone(),
+                        company_id: company.id,
+                        deadline: campaign.deadline,
+                        missing_actions,
+                        consequence: campaign.consequence.clone(),
+                    });
+                }
+            }
+        }
+        tasks.sort_by_key(|t| t.deadline);
+        tasks
+    }
+
+    // Sends reminders and closes campaigns past their deadline; returns the number of
+    // consequences applied
+    pub fn run_campaign_enforcement() -> u32 {
+        let now = time();
+        let mut applied = 0;
+        for mut campaign in StorageManager::get_all_reverification_campaigns() {
+            if campaign.status != CampaignStatus::Active {
+                continue;
+            }
+
+            if now >= campaign.deadline {
+                let pending = Self::pending_companies(&campaign);
+                for (company, _) in &pending {
+                    Self::apply_consequence(&campaign, company);
+                }
+                campaign.status = CampaignStatus::Closed;
+                campaign.closed_at = Some(now);
+                campaign.consequences_applied = match campaign.consequence {
+                    CampaignConsequence::None => 0,
+                    _ => pending.len() as u32,
+                };
+                applied += campaign.consequences_applied;
+                AuditManager::record(
+                    "reverification_campaign_closed",
+                    &campaign.id,
+                    Vec::new(),
+                    format!("{} of {} companies incomplete", pending.len(), campaign.enrolled_company_ids.len()),
+                );
+                StorageManager::insert_reverification_campaign(campaign);
+            } else if campaign.reminder_sent_at.is_none()
+                && now.saturating_add(Self::REMINDER_BEFORE_NS) >= campaign.deadline
+            {
+                Self::notify_pending(
+                    &campaign,
+                    &format!("Reminder: the re-verification \"{}\" is due in less than 3 days.", campaign.title),
+                );
+                campaign.reminder_sent_at = Some(now);
+                StorageManager::insert_reverification_campaign(campaign);
+            }
+        }
+        applied
+    }
+
+    pub fn trigger_campaign_enforcement(caller_principal: Principal) -> RegistryResult<u32> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        RegistryResult::Ok(Self::run_campaign_enforcement())
+    }
+
+    fn apply_consequence(campaign: &ReverificationCampaign, company: &Company) {
+        let status = match campaign.consequence {
+            CampaignConsequence::None => return,
+            CampaignConsequence::MarkStale => CompanyStatus::Stale,
+            CampaignConsequence::Flag => CompanyStatus::Flagged,
+        };
+        StorageManager::update_company(&company.id, |company| {
+            // Never soften a Suspended company
+            if !matches!(company.status, CompanyStatus::Suspended) {
+                company.status = status;
+            }
+        });
+        NotificationManager::notify(
+            company.created_by,
+            &company.id,
+            None,
+            format!(
+                "The re-verification \"{}\" was not completed by its deadline; the company status changed.",
+                campaign.title
+            ),
+        );
+    }
+
+    // Enrolled companies that still exist and haven't done everything, with what is missing
+    fn pending_companies(campaign: &ReverificationCampaign) -> Vec<(Company, Vec<CampaignAction>)> {
+        campaign
+            .enrolled_company_ids
+            .iter()
+            .filter_map(|id| StorageManager::get_company(id))
+            .filter_map(|company| {
+                let missing: Vec<CampaignAction> = campaign
+                    .required_actions
+                    .iter()
+                    .filter(|action| !Self::is_done(&company, action, campaign.created_at))
+                    .cloned()
+                    .collect();
+                (!missing.is_empty()).then_some((company, missing))
+            })
+            .collect()
+    }
+
+    fn is_done(company: &Company, action: &CampaignAction, since: u64) -> bool {
+        match action {
+            CampaignAction::Reverify(verification_type) => {
+                company.web3_identity.verification_proofs.iter().any(|proof| {
+                    std::mem::discriminant(&proof.verification_type) == std::mem::discriminant(verification_type)
+                        && proof.verified_at >= since
+                        && proof.status == ProofStatus::Active
+                })
+            }
+            CampaignAction::ConfirmActive => company.last_owner_activity_at.is_some_and(|at| at >= since),
+        }
+    }
+
+    fn notify_pending(campaign: &ReverificationCampaign, message: &str) {
+        for (company, _) in Self::pending_companies(campaign) {
+            NotificationManager::notify(company.created_by, &company.id, None, message.to_string());
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use crate::crosschain::CrossChainVerifier;
+use crate::monitoring::MonitoringSystem;
+use crate::sanitize::{self, SafeUrl};
+use crate::security::SecurityLog;
+use crate::settings::SettingsManager;
+use crate::storage::StorageManager;
+use crate::types::{
+    AlertSeverity, AlertType, ChainType, Company, CompanyStatus, RegistryIdentity, RegistryResult, SecurityEventType,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Honeypot checks against profiles posing as the registry itself. Names only the registry
+// should use are refused outright. A profile claiming one of the registry's own canisters
+// or domains is still stored, so the attempt stays on record, but it is flagged on the
+// spot and reported as a Critical alert. Controllers are exempt, so the registry can list
+// itself.
+
+pub struct CanaryGuard;
+
+impl CanaryGuard {
+    // Matched on confusable keys, so spacing, case and lookalike characters don't help
+    const REGISTRY_NAMES: [&'static str; 5] = [
+        "CrossChain Registry",
+        "Registry Admin",
+        "Registry Moderator",
+        "Registry Support",
+        "Registry Official",
+    ];
+    const MAX_IDENTITIES: usize = 20;
+    const ALERT_SUBJECT: &'static str = "registry_identity";
+
+    // Called on registration and on profile updates that change the name
+    pub fn check_name(name: &str, company_id: Option<&str>, caller_principal: Principal) -> Result<(), String> {
+        if ic_cdk::api::is_controller(&caller_principal) {
+            return Ok(());
+        }
+        let key = sanitize::confusable_key(name);
+        let reserved = match Self::REGISTRY_NAMES
+            .iter()
+            .find(|reserved| key.contains(&sanitize::confusable_key(reserved)))
+        {
+            Some(reserved) => reserved,
+            None => return Ok(()),
+        };
+        SecurityLog::record(
+            caller_principal,
+            SecurityEventType::RegistryImpersonation,
+            company_id.map(|id| id.to_string()),
+            format!("refused name '{}'", name),
+        );
+        Err(format!("Name '{}' is reserved for the registry itself", reserved))
+    }
+
+    // Called after a company is written; flags it if it claims a registry identity
+    pub fn inspect_company(company_id: &str, caller_principal: Principal) {
+        if ic_cdk::api::is_controller(&caller_principal) {
+            return;
+        }
+        let company = match StorageManager::get_company(company_id) {
+            Some(company) => company,
+            None => return,
+        };
+        let claims = Self::registry_claims(&company);
+        if claims.is_empty() {
+            return;
+        }
+
+        StorageManager::update_company(company_id, |company| {
+            // Never soften a Suspended company
+            if !matches!(company.status, CompanyStatus::Suspended) {
+                company.status = CompanyStatus::Flagged;
+            }
+        });
+        let evidence = claims.join(", ");
+        SecurityLog::record(
+            caller_principal,
+            SecurityEventType::RegistryImpersonation,
+            Some(company_id.to_string()),
+            format!("claims {}", evidence),
+        );
+        MonitoringSystem::raise_alert(
+            company_id,
+            AlertType::RegistryImpersonation,
+            AlertSeverity::Critical,
+            format!("Profile claims the registry's own {}; it has been flagged", evidence),
+            None,
+            Self::ALERT_SUBJECT,
+            &evidence,
+        );
+    }
+
+    fn protected_canister_ids() -> Vec<String> {
+        let mut canister_ids = vec![ic_cdk::id().to_text()];
+        if let Some(identity) = StorageManager::get_registry_identity() {
+            canister_ids.extend(identity.canister_ids);
+        }
+        canister_ids
+    }
+
+    // Human-readable list of the registry identities the company claims, e.g. "canister
+    // rrkah-fqaaa-aaaaa-aaaaq-cai"
+    fn registry_claims(company: &Company) -> Vec<String> {
+        let canister_ids = Self::protected_canister_ids();
+        let domains = StorageManager::get_registry_identity()
+            .map(|identity| identity.domains)
+            .unwrap_or_default();
+        let presence = &company.cross_chain_presence;
+        let mut claims = Vec::new();
+
+        let addresses = presence
+            .icp_canisters
+            .iter()
+            .chain(presence.treasury_wallets.iter().map(|wallet| &wallet.address))
+            .chain(presence.token_contracts.iter().map(|token| &token.contract_address));
+        for address in addresses {
+            let address = address.trim().to_lowercase();
+            if canister_ids.contains(&address) {
+                claims.push(format!("canister {}", address));
+            }
+        }
+
+        // Canister URLs such as https://<canister-id>.icp0.io count as claiming the canister
+        if let Ok(url) = SafeUrl::parse_web(&company.basic_info.website, "Website") {
+            if let Some(canister_id) = url.host().split('.').find(|label| canister_ids.iter().any(|id| id == label)) {
+                claims.push(format!("canister {}", canister_id));
+            }
+            let host = url.site_host();
+            if let Some(domain) = domains
+                .iter()
+                .find(|domain| host == domain.as_str() || host.ends_with(&format!(".{}", domain)))
+            {
+                claims.push(format!("domain {}", domain));
+            }
+        }
+
+        claims.sort();
+        claims.dedup();
+        claims
+    }
+
+    pub fn set_registry_identity(
+        canister_ids: Vec<String>,
+        domains: Vec<String>,
+        caller_principal: Principal,
+    ) -> RegistryResult<RegistryIdentity> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if canister_ids.len() > Self::MAX_IDENTITIES || domains.len() > Self::MAX_IDENTITIES {
+            return RegistryResult::Err(format!(
+                "At most {} canister IDs and {} domains can be protected",
+                Self::MAX_IDENTITIES,
+                Self::MAX_IDENTITIES
+            ));
+        }
+
+        let mut normalized_canister_ids = Vec::new();
+        for canister_id in &canister_ids {
+            let canister_id = canister_id.trim().to_lowercase();
+            if let Err(err) = CrossChainVerifier::validate_address_format(&ChainType::ICP, &canister_id) {
+                return RegistryResult::Err(err);
+            }
+            normalized_canister_ids.push(canister_id);
+        }
+        let mut normalized_domains = Vec::new();
+        for domain in &domains {
+            let domain = match sanitize::domain_to_ascii(domain) {
+                Ok(domain) => domain,
+                Err(err) => return RegistryResult::Err(err),
+            };
+            let domain = domain.strip_prefix("www.").unwrap_or(&domain).to_string();
+            if !domain.contains('.') || domain.contains('/') {
+                return RegistryResult::Err(format!("Invalid domain '{}'", domain));
+            }
+            normalized_domains.push(domain);
+        }
+        normalized_canister_ids.sort();
+        normalized_canister_ids.dedup();
+        normalized_domains.sort();
+        normalized_domains.dedup();
+
+        let identity = RegistryIdentity {
+            canister_ids: normalized_canister_ids,
+            domains: normalized_domains,
+            updated_by: caller_principal,
+            updated_at: time(),
+        };
+        StorageManager::set_registry_identity(identity.clone());
+        RegistryResult::Ok(identity)
+    }
+
+    pub fn get_registry_identity() -> Option<RegistryIdentity> {
+        StorageManager::get_registry_identity()
+    }
+}
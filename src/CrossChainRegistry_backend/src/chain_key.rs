@@ -0,0 +1,92 @@
+use crate::storage::StorageManager;
+use crate::types::EvmSigner;
+use ic_cdk::api::management_canister::ecdsa::{
+    ecdsa_public_key, sign_with_ecdsa, EcdsaCurve, EcdsaKeyId, EcdsaPublicKeyArgument, SignWithEcdsaArgument,
+};
+use ic_cdk::api::time;
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use sha3::{Digest, Keccak256};
+
+// EVM accounts backed by the canister's threshold ECDSA key. Each purpose (attestations,
+// the root bridge) derives its own key, so its address can be funded or trusted on its
+// own. Public keys are fetched once and kept; signatures come back low-S with the
+// recovery id ecrecover needs.
+
+pub struct ChainKeySigner;
+
+impl ChainKeySigner {
+    // Local replicas only provision the test key
+    #[cfg(feature = "simulation")]
+    const KEY_NAME: &'static str = "dfx_test_key";
+    #[cfg(not(feature = "simulation"))]
+    const KEY_NAME: &'static str = "key_1";
+
+    fn key_id() -> EcdsaKeyId {
+        EcdsaKeyId {
+            curve: EcdsaCurve::Secp256k1,
+            name: Self::KEY_NAME.to_string(),
+        }
+    }
+
+    // None until the key for `purpose` was first used
+    pub fn cached_evm_signer(purpose: &str) -> Option<EvmSigner> {
+        StorageManager::get_evm_signer(Self::KEY_NAME, purpose)
+    }
+
+    pub async fn evm_signer(purpose: &str) -> Result<EvmSigner, String> {
+        if let Some(signer) = Self::cached_evm_signer(purpose) {
+            return Ok(signer);
+        }
+        let args = EcdsaPublicKeyArgument {
+            canister_id: None,
+            derivation_path: vec![purpose.as_bytes().to_vec()],
+            key_id: Self::key_id(),
+        };
+        let public_key = match ecdsa_public_key(args).await {
+            Ok((response,)) => response.public_key,
+            Err((code, message)) => return Err(format!("ecdsa_public_key failed ({:?}): {}", code, message)),
+        };
+        let key = VerifyingKey::from_sec1_bytes(&public_key).map_err(|_| "Invalid threshold ECDSA public key".to_string())?;
+        let signer = EvmSigner {
+            key_name: Self::KEY_NAME.to_string(),
+            purpose: purpose.to_string(),
+            public_key,
+            evm_address: Self::evm_address(&key),
+            fetched_at: time(),
+        };
+        StorageManager::set_evm_signer(signer.clone());
+        Ok(signer)
+    }
+
+    // r || s and the recovery id (0 or 1) for a 32-byte digest
+    pub async fn sign_evm_digest(purpose: &str, digest: &[u8; 32]) -> Result<([u8; 64], u8), String> {
+        let signer = Self::evm_signer(purpose).await?;
+        let args = SignWithEcdsaArgument {
+            message_hash: digest.to_vec(),
+            derivation_path: vec![purpose.as_bytes().to_vec()],
+            key_id: Self::key_id(),
+        };
+        let raw_signature = match sign_with_ecdsa(args).await {
+            Ok((response,)) => response.signature,
+            Err((code, message)) => return Err(format!("sign_with_ecdsa failed ({:?}): {}", code, message)),
+        };
+
+        // Contracts (and OpenZeppelin's ECDSA) reject high-S signatures
+        let signature = Signature::from_slice(&raw_signature).map_err(|_| "Invalid threshold signature".to_string())?;
+        let signature = signature.normalize_s().unwrap_or(signature);
+        let key = VerifyingKey::from_sec1_bytes(&signer.public_key)
+            .map_err(|_| "Invalid threshold ECDSA public key".to_string())?;
+        let recovery_id = (0..2u8)
+            .filter_map(RecoveryId::from_byte)
+            .find(|id| VerifyingKey::recover_from_prehash(digest, &signature, *id).is_ok_and(|recovered| recovered == key))
+            .ok_or("Threshold signature does not match the canister key")?;
+        Ok((signature.to_bytes().into(), recovery_id.to_byte()))
+    }
+
+    // Lowercase 0x address, as ecrecover returns it
+    pub fn evm_address(key: &VerifyingKey) -> String {
+        let public_key = key.to_encoded_point(false);
+        let hash = Keccak256::digest(&public_key.as_bytes()[1..]);
+        format!("0x{}", hex::encode(&hash[12..]))
+    }
+}
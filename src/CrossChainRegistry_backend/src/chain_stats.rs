@@ -0,0 +1,170 @@
+use crate::crosschain::CrossChainVerifier;
+use crate::rotation::RotationManager;
+use crate::storage::StorageManager;
+use crate::types::{
+    ChainContribution, ChainContributionEntry, ChainCounters, ChainStatistics, Company, CompanyEventKind,
+};
+use crate::verification_guard::VerificationGuard;
+use std::time::Duration;
+
+// Per-chain adoption numbers for ecosystem teams. Reads come from counters rather than a
+// scan: presence and address totals are kept in step with the company event journal (each
+// company's share is stored so a change applies as a delta), and verification attempts
+// are counted as they finish. Only addresses verified since these counters were added
+// show up as verified.
+
+pub struct ChainStatsManager;
+
+impl ChainStatsManager {
+    const JOURNAL_CONSUMER: &'static str = "chain_stats";
+    const EVENTS_PER_RUN: usize = 500;
+    const UPDATE_INTERVAL_SECS: u64 = 5 * 60;
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer(Duration::ZERO, || {
+            Self::process_journal();
+        });
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::UPDATE_INTERVAL_SECS), || {
+            Self::process_journal();
+        });
+    }
+
+    // Called by VerificationGuard with the outcome of every guarded verification; targets
+    // that aren't a chain (GitHub, domain, proof migration) are ignored
+    pub fn record_verification(target: &str, success: bool) {
+        let chain = match target.split(':').next().and_then(CrossChainVerifier::chain_from_name) {
+            Some(chain_type) => CrossChainVerifier::chain_name(&chain_type),
+            None => return,
+        };
+        if success {
+            StorageManager::mark_chain_address_verified(target);
+        }
+        let mut counters = StorageManager::get_chain_counters(chain);
+        counters.verification_attempts += 1;
+        if success {
+            counters.verification_successes += 1;
+        }
+        StorageManager::set_chain_counters(chain, counters);
+    }
+
+    fn contribution(company: &Company) -> ChainContribution {
+        let chains = CrossChainVerifier::ALL_CHAINS
+            .iter()
+            .filter_map(|chain_type| {
+                let addresses = RotationManager::active_addresses(&company.cross_chain_presence, chain_type);
+                if addresses.is_empty() {
+                    return None;
+                }
+                let chain = CrossChainVerifier::chain_name(chain_type);
+                let verified_addresses = addresses
+                    .iter()
+                    .filter(|address| {
+                        StorageManager::is_chain_address_verified(&VerificationGuard::target(chain, &company.id, address))
+                    })
+                    .count() as u64;
+                Some(ChainContributionEntry {
+                    chain: chain.to_string(),
+                    listed_addresses: addresses.len() as u64,
+                    verified_addresses,
+                })
+            })
+            .collect();
+        ChainContribution { chains }
+    }
+
+    fn apply(contribution: &ChainContribution, add: bool) {
+        for entry in &contribution.chains {
+            let mut counters = StorageManager::get_chain_counters(&entry.chain);
+            if add {
+                counters.companies_with_presence += 1;
+                counters.listed_addresses += entry.listed_addresses;
+                counters.verified_addresses += entry.verified_addresses;
+            } else {
+                counters.companies_with_presence = counters.companies_with_presence.saturating_sub(1);
+                counters.listed_addresses = counters.listed_addresses.saturating_sub(entry.listed_addresses);
+                counters.verified_addresses = counters.verified_addresses.saturating_sub(entry.verified_addresses);
+            }
+            StorageManager::set_chain_counters(&entry.chain, counters);
+        }
+    }
+
+    fn update_company(company_id: &str, company: Option<&Company>) {
+        if let Some(previous) = StorageManager::remove_chain_contribution(company_id) {
+            Self::apply(&previous, false);
+        }
+        if let Some(company) = company {
+            let contribution = Self::contribution(company);
+            if !contribution.chains.is_empty() {
+                Self::apply(&contribution, true);
+                StorageManager::set_chain_contribution(company_id, contribution);
+            }
+        }
+    }
+
+    // Recounts presence and addresses from scratch; attempt counters are kept
+    fn rebuild() {
+        let cursor = StorageManager::latest_company_event_seq();
+        StorageManager::clear_chain_contributions();
+        for chain_type in CrossChainVerifier::ALL_CHAINS.iter() {
+            let chain = CrossChainVerifier::chain_name(chain_type);
+            let counters = StorageManager::get_chain_counters(chain);
+            StorageManager::set_chain_counters(
+                chain,
+                ChainCounters {
+                    verification_attempts: counters.verification_attempts,
+                    verification_successes: counters.verification_successes,
+                    ..ChainCounters::default()
+                },
+            );
+        }
+        for company in StorageManager::get_all_companies() {
+            Self::update_company(&company.id, Some(&company));
+        }
+        StorageManager::set_journal_cursor(Self::JOURNAL_CONSUMER, cursor);
+    }
+
+    // Apply journal events since the last run; returns the number of events consumed
+    pub fn process_journal() -> u32 {
+        let cursor = StorageManager::get_journal_cursor(Self::JOURNAL_CONSUMER);
+        if cursor == 0 && !StorageManager::has_chain_contributions() {
+            Self::rebuild();
+            return 0;
+        }
+
+        let events = StorageManager::get_company_events_after(cursor, Self::EVENTS_PER_RUN);
+        let last_seq = match events.last() {
+            Some(event) => event.seq,
+            None => return 0,
+        };
+        for event in &events {
+            let company = match event.kind {
+                CompanyEventKind::Deleted => None,
+                _ => StorageManager::get_company(&event.company_id),
+            };
+            Self::update_company(&event.company_id, company.as_ref());
+        }
+        StorageManager::set_journal_cursor(Self::JOURNAL_CONSUMER, last_seq);
+        events.len() as u32
+    }
+
+    pub fn get_chain_statistics() -> Vec<ChainStatistics> {
+        CrossChainVerifier::ALL_CHAINS
+            .into_iter()
+            .map(|chain_type| {
+                let counters = StorageManager::get_chain_counters(CrossChainVerifier::chain_name(&chain_type));
+                let success_rate_percent = (counters.verification_attempts > 0).then(|| {
+                    (counters.verification_successes * 100 / counters.verification_attempts) as u32
+                });
+                ChainStatistics {
+                    chain: chain_type,
+                    companies_with_presence: counters.companies_with_presence,
+                    listed_addresses: counters.listed_addresses,
+                    verified_addresses: counters.verified_addresses,
+                    verification_attempts: counters.verification_attempts,
+                    verification_successes: counters.verification_successes,
+                    success_rate_percent,
+                }
+            })
+            .collect()
+    }
+}
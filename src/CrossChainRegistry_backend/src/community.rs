@@ -1,15 +1,149 @@
+use crate::admins::AdminManager;
+use crate::api::RegistryAPI;
+use crate::audit::AuditManager;
+use crate::escrow::ReputationEscrowManager;
+use crate::moderation::ModerationManager;
+use crate::ownership::OwnershipManager;
+use crate::policy::PolicyLog;
+use crate::reputation::{ReputationInputs, ReputationJournal};
+use crate::sanitize::{self, SafeText};
+use crate::settings::SettingsManager;
 use crate::storage::StorageManager;
+use crate::tiers::TierEngine;
 use crate::types::{
-    Company, CompanyStatus, CommunityValidation, CommunityValidationStats, Endorsement, 
-    RegistryResult, ReputationLeaderboard, Testimonial, Vouch,
+    Company, CompanyRole, CompanyStatus, CommunityCooldownPolicy, CommunityPage, CommunitySortOrder, CommunityValidation,
+    CommunityValidationStats, Endorsement, EndorsementWeight, RegistryResult, ReputationEventKind, ReputationLeaderboard,
+    Testimonial, Vouch,
 };
 use candid::Principal;
 use ic_cdk::api::time;
+use std::cmp::Reverse;
+use std::time::Duration;
 
 // Community validation business logic
 pub struct CommunityValidationManager;
 
 impl CommunityValidationManager {
+    const DEFAULT_MAX_VOUCHES_PER_DAY: u32 = 10;
+    const DEFAULT_ENDORSEMENT_READD_DAYS: u32 = 7;
+    const MAX_VOUCHES_PER_DAY_LIMIT: u32 = 100;
+    const MAX_ENDORSEMENT_READD_DAYS: u32 = 365;
+    const COOLDOWN_PRUNE_INTERVAL_SECS: u64 = 60 * 60;
+    const DAY_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+    const MIN_ENDORSEMENT_POINTS: u32 = 2;
+    const MAX_ENDORSEMENT_POINTS: u32 = 15;
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::COOLDOWN_PRUNE_INTERVAL_SECS), || {
+            Self::prune_cooldowns();
+        });
+    }
+
+    // Cool-downs. Rate limits cap calls per minute; these cap how fast vouches and
+    // endorsements can be farmed and withdrawn. Marks outlive the vouch or endorsement
+    // they record, so removing one doesn't free up the slot.
+    pub fn get_community_cooldown_policy() -> CommunityCooldownPolicy {
+        StorageManager::get_community_cooldown_policy().unwrap_or(CommunityCooldownPolicy {
+            max_vouches_per_day: Self::DEFAULT_MAX_VOUCHES_PER_DAY,
+            endorsement_readd_days: Self::DEFAULT_ENDORSEMENT_READD_DAYS,
+        })
+    }
+
+    pub fn set_community_cooldown_policy(
+        policy: CommunityCooldownPolicy,
+        caller_principal: Principal,
+    ) -> RegistryResult<()> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if policy.max_vouches_per_day == 0 || policy.max_vouches_per_day > Self::MAX_VOUCHES_PER_DAY_LIMIT {
+            return RegistryResult::Err(format!(
+                "Vouches per day must be 1-{}",
+                Self::MAX_VOUCHES_PER_DAY_LIMIT
+            ));
+        }
+        if policy.endorsement_readd_days > Self::MAX_ENDORSEMENT_READD_DAYS {
+            return RegistryResult::Err(format!(
+                "Endorsement cool-down must be at most {} days",
+                Self::MAX_ENDORSEMENT_READD_DAYS
+            ));
+        }
+
+        AuditManager::record(
+            "community_cooldown_policy_set",
+            "community_cooldowns",
+            vec![caller_principal],
+            format!(
+                "{} vouches per day, endorsements re-addable after {} days",
+                policy.max_vouches_per_day, policy.endorsement_readd_days
+            ),
+        );
+        PolicyLog::record(
+            "community_cooldown_policy",
+            Some(format!("{:?}", Self::get_community_cooldown_policy())),
+            format!("{:?}", policy),
+            vec![caller_principal],
+            "set_community_cooldown_policy",
+        );
+        StorageManager::set_community_cooldown_policy(policy);
+        RegistryResult::Ok(())
+    }
+
+    fn vouch_mark_prefix(voucher_principal: Principal) -> String {
+        format!("vouch:{}:", voucher_principal.to_text())
+    }
+
+    fn unendorse_mark(company_id: &str, endorser_company_id: &str) -> String {
+        format!("unendorse:{}:{}", company_id, endorser_company_id)
+    }
+
+    fn check_vouch_cooldown(voucher_principal: Principal) -> Result<(), String> {
+        let policy = Self::get_community_cooldown_policy();
+        let now = time();
+        let window_start = now.saturating_sub(Self::DAY_NS);
+        let recent: Vec<u64> = StorageManager::get_cooldown_marks_with_prefix(&Self::vouch_mark_prefix(voucher_principal))
+            .into_iter()
+            .map(|(_, vouched_at)| vouched_at)
+            .filter(|vouched_at| *vouched_at >= window_start)
+            .collect();
+        if recent.len() < policy.max_vouches_per_day as usize {
+            return Ok(());
+        }
+        let frees_at = recent.iter().min().copied().unwrap_or(now).saturating_add(Self::DAY_NS);
+        Err(format!(
+            "Vouch limit reached: at most {} companies per day. Try again in {} minutes.",
+            policy.max_vouches_per_day,
+            frees_at.saturating_sub(now) / 60_000_000_000 + 1
+        ))
+    }
+
+    fn check_endorsement_cooldown(company_id: &str, endorser_company_id: &str) -> Result<(), String> {
+        let removed_at = match StorageManager::get_cooldown_mark(&Self::unendorse_mark(company_id, endorser_company_id)) {
+            Some(removed_at) => removed_at,
+            None => return Ok(()),
+        };
+        let policy = Self::get_community_cooldown_policy();
+        let readd_at = removed_at.saturating_add(policy.endorsement_readd_days as u64 * Self::DAY_NS);
+        let now = time();
+        if now >= readd_at {
+            return Ok(());
+        }
+        Err(format!(
+            "This endorsement was removed recently and can be re-added in {} days",
+            (readd_at - now).div_ceil(Self::DAY_NS)
+        ))
+    }
+
+    fn prune_cooldowns() {
+        let policy = Self::get_community_cooldown_policy();
+        let now = time();
+        StorageManager::prune_cooldown_marks("vouch:", now.saturating_sub(Self::DAY_NS));
+        StorageManager::prune_cooldown_marks(
+            "unendorse:",
+            now.saturating_sub(policy.endorsement_readd_days as u64 * Self::DAY_NS),
+        );
+    }
+
     // Endorsement operations
     pub fn add_endorsement(
         company_id: String,
@@ -23,15 +157,21 @@ impl CommunityValidationManager {
             None => return RegistryResult::Err("Endorser company not found".to_string()),
         };
 
-        if endorser_company.created_by != caller_principal {
+        if !OwnershipManager::has_role(&endorser_company, caller_principal, CompanyRole::Editor) {
             return RegistryResult::Err(
-                "Unauthorized: Only company owner can create endorsements".to_string(),
+                "Unauthorized: Only company owners or editors can create endorsements".to_string(),
             );
         }
 
         // Validate that target company exists
-        if StorageManager::get_company(&company_id).is_none() {
-            return RegistryResult::Err("Target company not found".to_string());
+        let target_company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Target company not found".to_string()),
+        };
+        if let Err(err) = AdminManager::ensure_not_suspended(&endorser_company)
+            .and_then(|_| AdminManager::ensure_not_suspended(&target_company))
+        {
+            return RegistryResult::Err(err);
         }
 
         // Prevent self-endorsement
@@ -50,12 +190,17 @@ impl CommunityValidationManager {
                 return RegistryResult::Err("Endorsement already exists".to_string());
             }
         }
+        if let Err(err) = Self::check_endorsement_cooldown(&company_id, &endorser_company_id) {
+            return RegistryResult::Err(err);
+        }
 
+        let weight = Self::endorsement_weight(&endorser_company).points;
         let endorsement = Endorsement {
-            endorser_company_id,
+            endorser_company_id: endorser_company_id.clone(),
             message,
             timestamp: time(),
             endorser_principal: caller_principal,
+            weight: Some(weight),
         };
 
         let success = StorageManager::update_company(&company_id, |company| {
@@ -67,6 +212,12 @@ impl CommunityValidationManager {
         });
 
         if success {
+            StorageManager::remove_cooldown_mark(&Self::unendorse_mark(&company_id, &endorser_company_id));
+            ReputationJournal::record(
+                &company_id,
+                ReputationEventKind::EndorsementAdded { endorser_company_id, weight: Some(weight) },
+                Some(caller_principal),
+            );
             RegistryResult::Ok(())
         } else {
             RegistryResult::Err("Failed to add endorsement".to_string())
@@ -84,21 +235,32 @@ impl CommunityValidationManager {
             None => return RegistryResult::Err("Endorser company not found".to_string()),
         };
 
-        if endorser_company.created_by != caller_principal {
+        if !OwnershipManager::has_role(&endorser_company, caller_principal, CompanyRole::Editor) {
             return RegistryResult::Err(
-                "Unauthorized: Only company owner can remove endorsements".to_string(),
+                "Unauthorized: Only company owners or editors can remove endorsements".to_string(),
             );
         }
 
+        let mut removed = false;
         let success = StorageManager::update_company(&company_id, |company| {
+            let before = company.community_validation.peer_endorsements.len();
             company
                 .community_validation
                 .peer_endorsements
                 .retain(|e| e.endorser_company_id != endorser_company_id);
+            removed = company.community_validation.peer_endorsements.len() < before;
             Self::update_reputation_score(company);
         });
 
         if success {
+            if removed {
+                StorageManager::set_cooldown_mark(&Self::unendorse_mark(&company_id, &endorser_company_id), time());
+            }
+            ReputationJournal::record(
+                &company_id,
+                ReputationEventKind::EndorsementRemoved { endorser_company_id },
+                Some(caller_principal),
+            );
             RegistryResult::Ok(())
         } else {
             RegistryResult::Err("Failed to remove endorsement".to_string())
@@ -111,35 +273,31 @@ impl CommunityValidationManager {
         author_name: String,
         role: String,
         message: String,
-        _caller_principal: Principal,
+        caller_principal: Principal,
     ) -> RegistryResult<()> {
         // Validate that target company exists
-        if StorageManager::get_company(&company_id).is_none() {
-            return RegistryResult::Err("Company not found".to_string());
+        match StorageManager::get_company(&company_id) {
+            Some(company) => {
+                if let Err(err) = AdminManager::ensure_not_suspended(&company) {
+                    return RegistryResult::Err(err);
+                }
+            }
+            None => return RegistryResult::Err("Company not found".to_string()),
         }
 
-        // Input validation with length limits
-        if author_name.trim().is_empty() {
-            return RegistryResult::Err("Author name cannot be empty".to_string());
-        }
-        if author_name.len() > 100 {
-            return RegistryResult::Err("Author name exceeds 100 characters".to_string());
-        }
-        if role.trim().is_empty() {
-            return RegistryResult::Err("Role cannot be empty".to_string());
-        }
-        if role.len() > 100 {
-            return RegistryResult::Err("Role exceeds 100 characters".to_string());
-        }
-        if message.trim().is_empty() {
-            return RegistryResult::Err("Message cannot be empty".to_string());
-        }
-        if message.len() > 1000 {
-            return RegistryResult::Err("Message exceeds 1000 characters".to_string());
-        }
-        if message.len() > 1000 {
-            return RegistryResult::Err("Message exceeds 1000 characters".to_string());
-        }
+        let policy = sanitize::policy();
+        let author_name = match SafeText::parse_required(&author_name, "Author name", policy.max_name) {
+            Ok(text) => text.into_inner(),
+            Err(err) => return RegistryResult::Err(err),
+        };
+        let role = match SafeText::parse_required(&role, "Role", policy.max_name) {
+            Ok(text) => text.into_inner(),
+            Err(err) => return RegistryResult::Err(err),
+        };
+        let message = match SafeText::parse_required(&message, "Message", policy.max_message) {
+            Ok(text) => text.into_inner(),
+            Err(err) => return RegistryResult::Err(err),
+        };
 
         // Check if testimonial from this principal already exists
         if let Some(company) = StorageManager::get_company(&company_id) {
@@ -154,7 +312,7 @@ impl CommunityValidationManager {
         }
 
         let testimonial = Testimonial {
-            author_name,
+            author_name: author_name.clone(),
             role,
             message,
             timestamp: time(),
@@ -170,6 +328,11 @@ impl CommunityValidationManager {
         });
 
         if success {
+            ReputationJournal::record(
+                &company_id,
+                ReputationEventKind::TestimonialAdded { author_name, verified: false },
+                Some(caller_principal),
+            );
             RegistryResult::Ok(())
         } else {
             RegistryResult::Err("Failed to add testimonial".to_string())
@@ -187,8 +350,8 @@ impl CommunityValidationManager {
             None => return RegistryResult::Err("Company not found".to_string()),
         };
 
-        // Allow company owner or testimonial author to remove testimonial
-        let is_company_owner = company.created_by == caller_principal;
+        // Allow company owners and editors to remove testimonials
+        let is_company_editor = OwnershipManager::has_role(&company, caller_principal, CompanyRole::Editor);
         let testimonial_exists = company
             .community_validation
             .employee_testimonials
@@ -199,9 +362,9 @@ impl CommunityValidationManager {
             return RegistryResult::Err("Testimonial not found".to_string());
         }
 
-        if !is_company_owner {
+        if !is_company_editor {
             return RegistryResult::Err(
-                "Unauthorized: Only company owner can remove testimonials".to_string(),
+                "Unauthorized: Only company owners or editors can remove testimonials".to_string(),
             );
         }
 
@@ -214,6 +377,11 @@ impl CommunityValidationManager {
         });
 
         if success {
+            ReputationJournal::record(
+                &company_id,
+                ReputationEventKind::TestimonialRemoved { author_name },
+                Some(caller_principal),
+            );
             RegistryResult::Ok(())
         } else {
             RegistryResult::Err("Failed to remove testimonial".to_string())
@@ -232,12 +400,13 @@ impl CommunityValidationManager {
             None => return RegistryResult::Err("Company not found".to_string()),
         };
 
-        if company.created_by != caller_principal {
+        if !OwnershipManager::has_role(&company, caller_principal, CompanyRole::Editor) {
             return RegistryResult::Err(
-                "Unauthorized: Only company owner can verify testimonials".to_string(),
+                "Unauthorized: Only company owners or editors can verify testimonials".to_string(),
             );
         }
 
+        let mut found = false;
         let success = StorageManager::update_company(&company_id, |company| {
             if let Some(testimonial) = company
                 .community_validation
@@ -246,11 +415,19 @@ impl CommunityValidationManager {
                 .find(|t| t.author_name == author_name)
             {
                 testimonial.verified = true;
+                found = true;
             }
             Self::update_reputation_score(company);
         });
 
         if success {
+            if found {
+                ReputationJournal::record(
+                    &company_id,
+                    ReputationEventKind::TestimonialVerificationChanged { author_name, verified: true },
+                    Some(caller_principal),
+                );
+            }
             RegistryResult::Ok(())
         } else {
             RegistryResult::Err("Testimonial not found".to_string())
@@ -264,16 +441,19 @@ impl CommunityValidationManager {
         caller_principal: Principal,
     ) -> RegistryResult<()> {
         // Validate that target company exists
-        if StorageManager::get_company(&company_id).is_none() {
-            return RegistryResult::Err("Company not found".to_string());
+        match StorageManager::get_company(&company_id) {
+            Some(company) => {
+                if let Err(err) = AdminManager::ensure_not_suspended(&company) {
+                    return RegistryResult::Err(err);
+                }
+            }
+            None => return RegistryResult::Err("Company not found".to_string()),
         }
 
-        if message.trim().is_empty() {
-            return RegistryResult::Err("Message cannot be empty".to_string());
-        }
-        if message.len() > 1000 {
-            return RegistryResult::Err("Message exceeds 1000 characters".to_string());
-        }
+        let message = match SafeText::parse_required(&message, "Message", sanitize::policy().max_message) {
+            Ok(text) => text.into_inner(),
+            Err(err) => return RegistryResult::Err(err),
+        };
 
         // Check if vouch from this principal already exists
         if let Some(company) = StorageManager::get_company(&company_id) {
@@ -286,6 +466,9 @@ impl CommunityValidationManager {
                 return RegistryResult::Err("Vouch from this principal already exists".to_string());
             }
         }
+        if let Err(err) = Self::check_vouch_cooldown(caller_principal) {
+            return RegistryResult::Err(err);
+        }
 
         // Calculate voucher weight based on their activity/reputation
         let weight = Self::calculate_voucher_weight(caller_principal);
@@ -303,6 +486,15 @@ impl CommunityValidationManager {
         });
 
         if success {
+            StorageManager::set_cooldown_mark(
+                &format!("{}{}", Self::vouch_mark_prefix(caller_principal), company_id),
+                time(),
+            );
+            ReputationJournal::record(
+                &company_id,
+                ReputationEventKind::VouchAdded { voucher_principal: caller_principal, weight },
+                Some(caller_principal),
+            );
             RegistryResult::Ok(())
         } else {
             RegistryResult::Err("Failed to add vouch".to_string())
@@ -322,47 +514,43 @@ impl CommunityValidationManager {
         });
 
         if success {
+            ReputationJournal::record(
+                &company_id,
+                ReputationEventKind::VouchRemoved { voucher_principal: caller_principal },
+                Some(caller_principal),
+            );
             RegistryResult::Ok(())
         } else {
             RegistryResult::Err("Failed to remove vouch".to_string())
         }
     }
 
-    // Reputation management
-    pub fn stake_reputation(
-        company_id: String,
-        amount: u64,
-        caller_principal: Principal,
-    ) -> RegistryResult<()> {
-        // Validate that company exists and caller is authorized
-        let company = match StorageManager::get_company(&company_id) {
-            Some(company) => company,
-            None => return RegistryResult::Err("Company not found".to_string()),
-        };
-
-        if company.created_by != caller_principal {
-            return RegistryResult::Err(
-                "Unauthorized: Only company owner can stake reputation".to_string(),
-            );
-        }
+    // Utility functions
 
-        if amount == 0 {
-            return RegistryResult::Err("Stake amount must be greater than 0".to_string());
+    // An endorsement is worth more from a company with a good reputation and strong
+    // verification. Like vouch weights, the points are fixed when the endorsement is made,
+    // so a change in the endorser's score doesn't ripple through everyone it endorsed.
+    fn endorsement_weight(endorser: &Company) -> EndorsementWeight {
+        let reputation_score = endorser.community_validation.reputation_score;
+        let verification_score = endorser.verification_score;
+        let points = (Self::MIN_ENDORSEMENT_POINTS + reputation_score.min(100) / 10 + verification_score.min(100) / 20)
+            .min(Self::MAX_ENDORSEMENT_POINTS);
+        EndorsementWeight {
+            endorser_company_id: endorser.id.clone(),
+            reputation_score,
+            verification_score,
+            points,
+            max_points: Self::MAX_ENDORSEMENT_POINTS,
         }
+    }
 
-        let success = StorageManager::update_company(&company_id, |company| {
-            company.community_validation.reputation_staked += amount;
-            Self::update_reputation_score(company);
-        });
-
-        if success {
-            RegistryResult::Ok(())
-        } else {
-            RegistryResult::Err("Failed to stake reputation".to_string())
+    pub fn get_endorsement_weight(endorser_company_id: String) -> RegistryResult<EndorsementWeight> {
+        match StorageManager::get_company(&endorser_company_id) {
+            Some(company) => RegistryResult::Ok(Self::endorsement_weight(&company)),
+            None => RegistryResult::Err("Endorser company not found".to_string()),
         }
     }
 
-    // Utility functions
     fn calculate_voucher_weight(voucher_principal: Principal) -> u32 {
         // Calculate weight based on voucher's activity in the system
         // For now, use a simple heuristic based on how many companies they've vouched for
@@ -388,63 +576,28 @@ impl CommunityValidationManager {
         }
     }
 
-    fn update_reputation_score(company: &mut Company) {
-        let mut score = 0u32;
-
-        // Base score from verification
-        score += company.verification_score / 4;
-
-        // Endorsements (high weight)
-        let endorsement_score = company
-            .community_validation
-            .peer_endorsements
-            .len() as u32 * 10;
-        score += endorsement_score;
-
-        // Verified testimonials (medium weight)
-        let verified_testimonial_score = company
-            .community_validation
-            .employee_testimonials
-            .iter()
-            .filter(|t| t.verified)
-            .count() as u32 * 5;
-        score += verified_testimonial_score;
-
-        // Unverified testimonials (low weight)
-        let unverified_testimonial_score = company
-            .community_validation
-            .employee_testimonials
-            .iter()
-            .filter(|t| !t.verified)
-            .count() as u32 * 2;
-        score += unverified_testimonial_score;
-
-        // Community vouches (weighted by voucher reputation)
-        let vouch_score: u32 = company
-            .community_validation
-            .community_vouches
-            .iter()
-            .map(|v| v.weight * 3)
-            .sum();
-        score += vouch_score;
+    // Recompute after a change that lives outside the company record
+    pub fn recalculate_reputation(company_id: &str) {
+        StorageManager::update_company(company_id, |company| {
+            Self::update_reputation_score(company);
+        });
+    }
 
-        // Reputation staking bonus (logarithmic scale)
-        let staking_bonus = if company.community_validation.reputation_staked > 0 {
-            (company.community_validation.reputation_staked as f64).log10().ceil() as u32 * 2
-        } else {
-            0
-        };
-        score += staking_bonus;
+    pub fn update_reputation_score(company: &mut Company) {
+        let inputs = ReputationInputs::from_company(company);
+        company.community_validation.reputation_escrow = ReputationEscrowManager::pending_escrow(company, &inputs);
+        Self::set_reputation_score(company, inputs.score());
+    }
 
+    // A Stale status stays until the owner re-confirms (see InactivityManager::mark_active)
+    pub fn set_reputation_score(company: &mut Company, score: u32) {
         company.community_validation.reputation_score = score;
+        if matches!(company.status, CompanyStatus::Stale | CompanyStatus::Suspended) {
+            return;
+        }
 
-        // Update company status based on reputation score
-        company.status = match score {
-            0..=20 => CompanyStatus::Pending,
-            21..=50 => CompanyStatus::Verified,
-            51..=100 => CompanyStatus::Trusted,
-            _ => CompanyStatus::Trusted,
-        };
+        // Highest tier whose requirements the company meets
+        company.status = TierEngine::tier_status(company);
     }
 
     // Query functions
@@ -457,16 +610,14 @@ impl CommunityValidationManager {
 
     pub fn get_companies_by_reputation(min_score: u32, limit: Option<u32>) -> Vec<Company> {
         let limit = limit.unwrap_or(50) as usize;
-        
-        let mut companies = StorageManager::get_all_companies();
-        companies.retain(|company| company.community_validation.reputation_score >= min_score);
-        companies.sort_by(|a, b| {
-            b.community_validation
-                .reputation_score
-                .cmp(&a.community_validation.reputation_score)
-        });
-        
-        companies.into_iter().take(limit).collect()
+
+        Self::top_by_reputation(min_score, limit)
+            .into_iter()
+            .map(|mut company| {
+                RegistryAPI::prepare_response(&mut company);
+                company
+            })
+            .collect()
     }
 
     pub fn get_endorsements_for_company(company_id: String) -> RegistryResult<Vec<Endorsement>> {
@@ -490,6 +641,123 @@ impl CommunityValidationManager {
         }
     }
 
+    // Paginated community queries
+    const DEFAULT_PAGE_SIZE: u32 = 20;
+    const MAX_PAGE_SIZE: u32 = 100;
+    pub const MAX_EMBEDDED_ITEMS: usize = 20;
+
+    pub fn get_endorsements_page(
+        company_id: String,
+        offset: Option<u32>,
+        limit: Option<u32>,
+        sort: Option<CommunitySortOrder>,
+    ) -> RegistryResult<CommunityPage<Endorsement>> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        let mut endorsements = company.community_validation.peer_endorsements;
+        match sort.unwrap_or(CommunitySortOrder::Newest) {
+            CommunitySortOrder::Newest => endorsements.sort_by_key(|item| Reverse(item.timestamp)),
+            CommunitySortOrder::Oldest => endorsements.sort_by_key(|item| item.timestamp),
+            CommunitySortOrder::Weight => {
+                // Weight endorsements by the endorser's current reputation
+                let mut weighted: Vec<(u32, Endorsement)> = endorsements
+                    .into_iter()
+                    .map(|e| {
+                        let weight = StorageManager::get_company(&e.endorser_company_id)
+                            .map(|c| c.community_validation.reputation_score)
+                            .unwrap_or(0);
+                        (weight, e)
+                    })
+                    .collect();
+                weighted.sort_by_key(|(weight, e)| Reverse((*weight, e.timestamp)));
+                endorsements = weighted.into_iter().map(|(_, e)| e).collect();
+            }
+        }
+
+        RegistryResult::Ok(Self::paginate(endorsements, offset, limit))
+    }
+
+    pub fn get_testimonials_page(
+        company_id: String,
+        offset: Option<u32>,
+        limit: Option<u32>,
+        sort: Option<CommunitySortOrder>,
+    ) -> RegistryResult<CommunityPage<Testimonial>> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        let mut testimonials = company.community_validation.employee_testimonials;
+        match sort.unwrap_or(CommunitySortOrder::Newest) {
+            CommunitySortOrder::Newest => testimonials.sort_by_key(|item| Reverse(item.timestamp)),
+            CommunitySortOrder::Oldest => testimonials.sort_by_key(|item| item.timestamp),
+            // Verified testimonials carry more weight in the reputation score
+            CommunitySortOrder::Weight => {
+                testimonials.sort_by_key(|t| Reverse((t.verified, t.timestamp)))
+            }
+        }
+
+        RegistryResult::Ok(Self::paginate(testimonials, offset, limit))
+    }
+
+    pub fn get_vouches_page(
+        company_id: String,
+        offset: Option<u32>,
+        limit: Option<u32>,
+        sort: Option<CommunitySortOrder>,
+    ) -> RegistryResult<CommunityPage<Vouch>> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        let mut vouches = company.community_validation.community_vouches;
+        match sort.unwrap_or(CommunitySortOrder::Newest) {
+            CommunitySortOrder::Newest => vouches.sort_by_key(|item| Reverse(item.timestamp)),
+            CommunitySortOrder::Oldest => vouches.sort_by_key(|item| item.timestamp),
+            CommunitySortOrder::Weight => {
+                vouches.sort_by_key(|v| Reverse((v.weight, v.timestamp)))
+            }
+        }
+
+        RegistryResult::Ok(Self::paginate(vouches, offset, limit))
+    }
+
+    pub fn paginate<T>(items: Vec<T>, offset: Option<u32>, limit: Option<u32>) -> CommunityPage<T> {
+        let offset = offset.unwrap_or(0);
+        let limit = limit
+            .unwrap_or(Self::DEFAULT_PAGE_SIZE)
+            .clamp(1, Self::MAX_PAGE_SIZE);
+        let total = items.len() as u32;
+
+        CommunityPage {
+            items: items
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .collect(),
+            total,
+            offset,
+            limit,
+            has_more: offset.saturating_add(limit) < total,
+        }
+    }
+
+    // Company responses embed only the newest community entries; full lists are paginated
+    pub fn cap_embedded_lists(company: &mut Company) {
+        let cv = &mut company.community_validation;
+        cv.peer_endorsements.sort_by_key(|item| Reverse(item.timestamp));
+        cv.peer_endorsements.truncate(Self::MAX_EMBEDDED_ITEMS);
+        cv.employee_testimonials.sort_by_key(|item| Reverse(item.timestamp));
+        cv.employee_testimonials.truncate(Self::MAX_EMBEDDED_ITEMS);
+        cv.community_vouches.sort_by_key(|item| Reverse(item.timestamp));
+        cv.community_vouches.truncate(Self::MAX_EMBEDDED_ITEMS);
+    }
+
     // Statistics and analytics functions
     pub fn get_community_validation_stats(company_id: String) -> RegistryResult<CommunityValidationStats> {
         match StorageManager::get_company(&company_id) {
@@ -513,17 +781,9 @@ impl CommunityValidationManager {
 
     pub fn get_reputation_leaderboard(limit: Option<u32>) -> Vec<ReputationLeaderboard> {
         let limit = limit.unwrap_or(20) as usize;
-        
-        let mut companies = StorageManager::get_all_companies();
-        companies.sort_by(|a, b| {
-            b.community_validation
-                .reputation_score
-                .cmp(&a.community_validation.reputation_score)
-        });
-        
-        companies
+
+        Self::top_by_reputation(0, limit)
             .into_iter()
-            .take(limit)
             .map(|company| ReputationLeaderboard {
                 company_id: company.id,
                 company_name: company.basic_info.name,
@@ -533,6 +793,36 @@ impl CommunityValidationManager {
             .collect()
     }
 
+    // Served from the reputation index; the full scan only runs until
+    // rebuild_reputation_index has covered companies stored before the index existed
+    fn top_by_reputation(min_score: u32, limit: usize) -> Vec<Company> {
+        match StorageManager::get_company_ids_by_reputation(min_score, limit) {
+            Some(company_ids) => company_ids
+                .iter()
+                .filter_map(|company_id| StorageManager::get_company(company_id))
+                .collect(),
+            None => {
+                let mut companies = StorageManager::get_all_companies();
+                companies.retain(|company| company.community_validation.reputation_score >= min_score);
+                companies.sort_by(|a, b| {
+                    b.community_validation
+                        .reputation_score
+                        .cmp(&a.community_validation.reputation_score)
+                        .then_with(|| a.id.cmp(&b.id))
+                });
+                companies.truncate(limit);
+                companies
+            }
+        }
+    }
+
+    pub fn rebuild_reputation_index(caller_principal: Principal) -> RegistryResult<u64> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        RegistryResult::Ok(StorageManager::rebuild_reputation_index())
+    }
+
     pub fn get_endorsements_by_company(endorser_company_id: String) -> RegistryResult<Vec<(String, Endorsement)>> {
         // Get all endorsements made by a specific company
         let all_companies = StorageManager::get_all_companies();
@@ -584,14 +874,18 @@ impl CommunityValidationManager {
         testimonials
     }
 
-    // Moderation functions (for future admin features)
+    // Moderation functions
     pub fn flag_testimonial(
         company_id: String,
         author_name: String,
-        _admin_principal: Principal,
+        admin_principal: Principal,
     ) -> RegistryResult<()> {
-        // This could be used by moderators to flag inappropriate testimonials
-        // For now, we'll just mark them as unverified
+        if let Err(err) = ModerationManager::ensure_moderator(admin_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        // Flagged testimonials lose their verified mark
+        let mut found = false;
         let success = StorageManager::update_company(&company_id, |company| {
             if let Some(testimonial) = company
                 .community_validation
@@ -600,11 +894,19 @@ impl CommunityValidationManager {
                 .find(|t| t.author_name == author_name)
             {
                 testimonial.verified = false;
+                found = true;
             }
             Self::update_reputation_score(company);
         });
 
         if success {
+            if found {
+                ReputationJournal::record(
+                    &company_id,
+                    ReputationEventKind::TestimonialVerificationChanged { author_name, verified: false },
+                    Some(admin_principal),
+                );
+            }
             RegistryResult::Ok(())
         } else {
             RegistryResult::Err("Company or testimonial not found".to_string())
@@ -0,0 +1,171 @@
+use crate::admins::AdminManager;
+use crate::community::CommunityValidationManager;
+use crate::notifications::NotificationManager;
+use crate::ownership::OwnershipManager;
+use crate::sanitize::{self, SafeText};
+use crate::storage::StorageManager;
+use crate::types::{CompanyStatus, Concern, ConcernSummary, RegistryResult};
+use candid::Principal;
+use ic_cdk::api::time;
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+// Concerns are the soft counterpart to a report: a principal with a role in a Verified or
+// Trusted company says something looks wrong, without asking a moderator to act. One
+// concern moves nothing. Once enough independent sources agree, the company loses a few
+// reputation points, capped so concerns can never outweigh a real track record. Sources
+// are counted by the company a principal speaks for, so one team can't corroborate itself.
+// Everything is public, including who raised each concern, and concerns expire on their
+// own.
+
+pub struct ConcernManager;
+
+impl ConcernManager {
+    const CONCERN_LIFETIME_NS: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+    const MIN_CORROBORATING_SOURCES: usize = 3;
+    const POINTS_PER_WEIGHT: u32 = 2;
+    pub const MAX_CONCERN_PENALTY: u32 = 15;
+    const EXPIRY_SWEEP_INTERVAL_SECS: u64 = 60 * 60;
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::EXPIRY_SWEEP_INTERVAL_SECS), || {
+            Self::expire_concerns();
+        });
+    }
+
+    // The company the principal speaks for: the highest-standing Verified or Trusted company
+    // it holds a role in, other than the target. Trusted counts double.
+    fn source_for(caller_principal: Principal, company_id: &str) -> Option<(String, u32)> {
+        StorageManager::get_all_companies()
+            .into_iter()
+            .filter(|company| company.id != company_id)
+            .filter(|company| OwnershipManager::role_of(company, caller_principal).is_some())
+            .filter_map(|company| match company.status {
+                CompanyStatus::Trusted => Some((company.id, 2)),
+                CompanyStatus::Verified => Some((company.id, 1)),
+                _ => None,
+            })
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+    }
+
+    pub fn raise_concern(company_id: String, reason: String, caller_principal: Principal) -> RegistryResult<Concern> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+        if let Err(err) = AdminManager::ensure_not_suspended(&company) {
+            return RegistryResult::Err(err);
+        }
+        if OwnershipManager::role_of(&company, caller_principal).is_some() {
+            return RegistryResult::Err("Cannot raise a concern about your own company".to_string());
+        }
+        let (source_company_id, weight) = match Self::source_for(caller_principal, &company_id) {
+            Some(source) => source,
+            None => {
+                return RegistryResult::Err(
+                    "Only members of a Verified or Trusted company can raise concerns".to_string(),
+                )
+            }
+        };
+        let reason = match SafeText::parse_required(&reason, "Reason", sanitize::policy().max_message) {
+            Ok(text) => text.into_inner(),
+            Err(err) => return RegistryResult::Err(err),
+        };
+
+        let now = time();
+        let concern = Concern {
+            company_id: company_id.clone(),
+            raised_by: caller_principal,
+            source_company_id,
+            reason,
+            weight,
+            raised_at: now,
+            expires_at: now.saturating_add(Self::CONCERN_LIFETIME_NS),
+        };
+        let renewed = StorageManager::get_concern(&company_id, caller_principal).is_some();
+        StorageManager::insert_concern(concern.clone());
+        CommunityValidationManager::recalculate_reputation(&company_id);
+
+        if !renewed {
+            NotificationManager::notify(
+                company.created_by,
+                &company_id,
+                None,
+                format!(
+                    "A concern was raised about your company on behalf of {}: {}",
+                    concern.source_company_id, concern.reason
+                ),
+            );
+        }
+        RegistryResult::Ok(concern)
+    }
+
+    pub fn withdraw_concern(company_id: String, caller_principal: Principal) -> RegistryResult<()> {
+        if StorageManager::remove_concern(&company_id, caller_principal).is_none() {
+            return RegistryResult::Err("No concern from this principal".to_string());
+        }
+        CommunityValidationManager::recalculate_reputation(&company_id);
+        RegistryResult::Ok(())
+    }
+
+    fn active_concerns(company_id: &str) -> Vec<Concern> {
+        let now = time();
+        StorageManager::get_concerns_for_company(company_id)
+            .into_iter()
+            .filter(|concern| concern.expires_at > now)
+            .collect()
+    }
+
+    // Points taken off the reputation score; zero until concerns are corroborated
+    pub fn reputation_penalty(company_id: &str) -> u32 {
+        Self::penalty_for(&Self::active_concerns(company_id))
+    }
+
+    fn penalty_for(concerns: &[Concern]) -> u32 {
+        if Self::distinct_sources(concerns) < Self::MIN_CORROBORATING_SOURCES {
+            return 0;
+        }
+        concerns
+            .iter()
+            .fold(0u32, |total, concern| {
+                total.saturating_add(concern.weight.saturating_mul(Self::POINTS_PER_WEIGHT))
+            })
+            .min(Self::MAX_CONCERN_PENALTY)
+    }
+
+    fn distinct_sources(concerns: &[Concern]) -> usize {
+        concerns
+            .iter()
+            .map(|concern| concern.source_company_id.as_str())
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+
+    pub fn get_concerns(company_id: String) -> ConcernSummary {
+        let mut concerns = Self::active_concerns(&company_id);
+        concerns.sort_by_key(|concern| std::cmp::Reverse(concern.raised_at));
+        let distinct_sources = Self::distinct_sources(&concerns);
+        ConcernSummary {
+            company_id,
+            reputation_penalty: Self::penalty_for(&concerns),
+            corroborated: distinct_sources >= Self::MIN_CORROBORATING_SOURCES,
+            distinct_sources: distinct_sources as u32,
+            concerns,
+        }
+    }
+
+    // Drops expired concerns and gives the affected companies their points back
+    fn expire_concerns() {
+        let now = time();
+        let mut affected = BTreeSet::new();
+        for concern in StorageManager::get_all_concerns() {
+            if concern.expires_at <= now {
+                StorageManager::remove_concern(&concern.company_id, concern.raised_by);
+                affected.insert(concern.company_id);
+            }
+        }
+        for company_id in affected {
+            CommunityValidationManager::recalculate_reputation(&company_id);
+        }
+    }
+}
@@ -0,0 +1,350 @@
+use crate::http_client;
+use crate::ownership::OwnershipManager;
+use crate::sanitize::{self, SafeUrl};
+use crate::storage::StorageManager;
+use crate::types::{
+    Company, CompanyRole, ContactChannel, ContactChannelMatch, ContactChannelType, RegistryResult,
+};
+use crate::verification::VerificationManager;
+use candid::Principal;
+use ic_cdk::api::management_canister::http_request::{
+    CanisterHttpRequestArgument, HttpHeader, HttpMethod, HttpResponse, TransformArgs,
+    TransformContext,
+};
+use ic_cdk::api::time;
+
+// Support and contact channels, verified separately from marketing socials so users
+// can check whether someone claiming to be "support" really speaks for the company.
+//
+// - Email: the address must be on the company's verified domain.
+// - Telegram: the challenge token must appear in the public channel/group description.
+// - Discord: the token must appear in the name of the channel the invite points to,
+//   or in the server description.
+
+pub struct ContactChannelManager;
+
+impl ContactChannelManager {
+    const MAX_CHANNELS: usize = 10;
+    const MAX_VALUE_LENGTH: usize = 100;
+    const MAX_LABEL_LENGTH: usize = 50;
+
+    // Canonical form used for storage and lookups
+    pub fn normalize_value(channel_type: &ContactChannelType, value: &str) -> String {
+        let value = value.trim().to_lowercase();
+        match channel_type {
+            ContactChannelType::Email => value.trim_start_matches("mailto:").to_string(),
+            ContactChannelType::Telegram => value
+                .trim_start_matches("https://")
+                .trim_start_matches("t.me/")
+                .trim_start_matches("telegram.me/")
+                .trim_start_matches('@')
+                .trim_end_matches('/')
+                .to_string(),
+            ContactChannelType::Discord => value
+                .trim_start_matches("https://")
+                .trim_start_matches("discord.gg/")
+                .trim_start_matches("discord.com/invite/")
+                .trim_end_matches('/')
+                .to_string(),
+        }
+    }
+
+    fn validate_value(channel_type: &ContactChannelType, value: &str) -> Result<(), String> {
+        if value.is_empty() || value.len() > Self::MAX_VALUE_LENGTH {
+            return Err(format!(
+                "Contact channel must be 1-{} characters",
+                Self::MAX_VALUE_LENGTH
+            ));
+        }
+
+        let valid = match channel_type {
+            ContactChannelType::Email => {
+                let mut parts = value.split('@');
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some(local), Some(domain), None) => {
+                        !local.is_empty()
+                            && domain.contains('.')
+                            && !value.chars().any(|c| c.is_whitespace() || c.is_control())
+                    }
+                    _ => false,
+                }
+            }
+            ContactChannelType::Telegram => {
+                value.len() >= 5 && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            }
+            ContactChannelType::Discord => {
+                value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            }
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err("Invalid contact channel format".to_string())
+        }
+    }
+
+    pub fn add_contact_channel(
+        company_id: String,
+        channel_type: ContactChannelType,
+        value: String,
+        label: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<ContactChannel> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        if !OwnershipManager::has_role(&company, caller_principal, CompanyRole::Editor) {
+            return RegistryResult::Err(
+                "Unauthorized: Only company owners or editors can manage contact channels".to_string(),
+            );
+        }
+
+        let value = Self::normalize_value(&channel_type, &value);
+        if let Err(err) = Self::validate_value(&channel_type, &value) {
+            return RegistryResult::Err(err);
+        }
+        let label = label.trim().to_string();
+        if label.is_empty() || sanitize::grapheme_len(&label) > Self::MAX_LABEL_LENGTH {
+            return RegistryResult::Err(format!(
+                "Label must be 1-{} characters",
+                Self::MAX_LABEL_LENGTH
+            ));
+        }
+
+        let channels = company.contact_channels.unwrap_or_default();
+        if channels.len() >= Self::MAX_CHANNELS {
+            return RegistryResult::Err(format!(
+                "Cannot register more than {} contact channels",
+                Self::MAX_CHANNELS
+            ));
+        }
+        if channels.iter().any(|c| c.channel_type == channel_type && c.value == value) {
+            return RegistryResult::Err("Contact channel already registered".to_string());
+        }
+
+        // Channels can be removed, so the count alone may repeat an id minted earlier this round
+        let now = time();
+        let mut suffix = channels.len();
+        let mut id = format!("contact_{}_{}", now, suffix);
+        while channels.iter().any(|c| c.id == id) {
+            suffix += 1;
+            id = format!("contact_{}_{}", now, suffix);
+        }
+
+        let channel = ContactChannel {
+            id,
+            channel_type,
+            value,
+            label,
+            verified: false,
+            challenge_token: VerificationManager::generate_challenge_token(),
+            added_at: now,
+            verified_at: None,
+        };
+
+        let stored = channel.clone();
+        StorageManager::update_company(&company_id, |company| {
+            company.contact_channels.get_or_insert_with(Vec::new).push(stored);
+        });
+
+        RegistryResult::Ok(channel)
+    }
+
+    pub fn remove_contact_channel(
+        company_id: String,
+        channel_id: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<()> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        if !OwnershipManager::has_role(&company, caller_principal, CompanyRole::Editor) {
+            return RegistryResult::Err(
+                "Unauthorized: Only company owners or editors can manage contact channels".to_string(),
+            );
+        }
+
+        let exists = company
+            .contact_channels
+            .as_ref()
+            .is_some_and(|channels| channels.iter().any(|c| c.id == channel_id));
+        if !exists {
+            return RegistryResult::Err("Contact channel not found".to_string());
+        }
+
+        StorageManager::update_company(&company_id, |company| {
+            if let Some(channels) = company.contact_channels.as_mut() {
+                channels.retain(|c| c.id != channel_id);
+            }
+        });
+
+        RegistryResult::Ok(())
+    }
+
+    pub async fn verify_contact_channel(
+        company_id: String,
+        channel_id: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<ContactChannel> {
+        if let Err(err) = StorageManager::check_verification_rate_limit(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        if !OwnershipManager::has_role(&company, caller_principal, CompanyRole::Editor) {
+            return RegistryResult::Err(
+                "Unauthorized: Only company owners or editors can verify contact channels".to_string(),
+            );
+        }
+
+        let channel = match company
+            .contact_channels
+            .as_ref()
+            .and_then(|channels| channels.iter().find(|c| c.id == channel_id))
+        {
+            Some(channel) => channel.clone(),
+            None => return RegistryResult::Err("Contact channel not found".to_string()),
+        };
+
+        if channel.verified {
+            return RegistryResult::Ok(channel);
+        }
+
+        let check = match channel.channel_type {
+            ContactChannelType::Email => Self::check_email_domain(&channel.value, &company),
+            ContactChannelType::Telegram => {
+                let url = format!("https://t.me/{}", channel.value);
+                Self::check_token_at(&url, &channel.challenge_token).await
+            }
+            ContactChannelType::Discord => {
+                let url = format!("https://discord.com/api/v10/invites/{}", channel.value);
+                Self::check_token_at(&url, &channel.challenge_token).await
+            }
+        };
+
+        if let Err(err) = check {
+            return RegistryResult::Err(err);
+        }
+
+        let mut verified_channel = None;
+        StorageManager::update_company(&company_id, |company| {
+            if let Some(channels) = company.contact_channels.as_mut() {
+                if let Some(channel) = channels.iter_mut().find(|c| c.id == channel_id) {
+                    channel.verified = true;
+                    channel.verified_at = Some(time());
+                    verified_channel = Some(channel.clone());
+                }
+            }
+        });
+
+        match verified_channel {
+            Some(channel) => RegistryResult::Ok(channel),
+            None => RegistryResult::Err("Contact channel not found".to_string()),
+        }
+    }
+
+    pub fn get_contact_channels(company_id: String) -> RegistryResult<Vec<ContactChannel>> {
+        match StorageManager::get_company(&company_id) {
+            Some(company) => RegistryResult::Ok(company.contact_channels.unwrap_or_default()),
+            None => RegistryResult::Err("Company not found".to_string()),
+        }
+    }
+
+    // "Is this support account real?" lookup across all companies
+    pub fn check_contact_channel(channel_type: ContactChannelType, value: String) -> ContactChannelMatch {
+        let value = Self::normalize_value(&channel_type, &value);
+
+        for company in StorageManager::get_all_companies() {
+            let channels = company.contact_channels.unwrap_or_default();
+            if let Some(channel) = channels
+                .into_iter()
+                .find(|c| c.verified && c.channel_type == channel_type && c.value == value)
+            {
+                return ContactChannelMatch {
+                    matched: true,
+                    company_id: Some(company.id),
+                    company_name: Some(company.basic_info.name),
+                    label: Some(channel.label),
+                };
+            }
+        }
+
+        ContactChannelMatch {
+            matched: false,
+            company_id: None,
+            company_name: None,
+            label: None,
+        }
+    }
+
+    fn check_email_domain(email: &str, company: &Company) -> Result<(), String> {
+        if !company.web3_identity.domain_verified {
+            return Err("Verify the company domain before verifying email contacts".to_string());
+        }
+
+        let website = SafeUrl::parse_web(&company.basic_info.website, "Company website")?;
+        let company_domain = website.site_host();
+        let email_domain = email.rsplit('@').next().unwrap_or("");
+
+        if email_domain == company_domain || email_domain.ends_with(&format!(".{}", company_domain)) {
+            Ok(())
+        } else {
+            Err(format!(
+                "Email must be on the verified domain {}",
+                company_domain
+            ))
+        }
+    }
+
+    async fn check_token_at(url: &str, token: &str) -> Result<(), String> {
+        let request = CanisterHttpRequestArgument {
+            url: url.to_string(),
+            method: HttpMethod::GET,
+            body: None,
+            max_response_bytes: Some(65_536),
+            transform: Some(TransformContext::from_name(
+                "transform_contact_check".to_string(),
+                token.as_bytes().to_vec(),
+            )),
+            headers: vec![HttpHeader {
+                name: "User-Agent".to_string(),
+                value: "ICP-CrossChainRegistry-ContactChecker/1.0".to_string(),
+            }],
+        };
+
+        match http_client::send(request, 20_000_000_000).await {
+            Ok(response) => {
+                if response.status != 200u32 {
+                    return Err(format!("Channel lookup failed with HTTP status {}", response.status));
+                }
+                if response.body == b"token_found" {
+                    Ok(())
+                } else {
+                    Err("Challenge token not found in the channel description".to_string())
+                }
+            }
+            Err(err) => Err(format!("Channel lookup failed: {}", err)),
+        }
+    }
+}
+
+// Reduce the page to whether it contains the challenge token passed as context
+pub fn transform_contact_check(raw: TransformArgs) -> HttpResponse {
+    let token = raw.context;
+    let found = !token.is_empty() && raw.response.body.windows(token.len()).any(|w| w == token.as_slice());
+
+    HttpResponse {
+        status: raw.response.status.clone(),
+        body: if found { b"token_found".to_vec() } else { b"token_not_found".to_vec() },
+        headers: vec![],
+    }
+}
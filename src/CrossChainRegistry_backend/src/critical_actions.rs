@@ -0,0 +1,229 @@
+use crate::archive::ArchiveManager;
+use crate::audit::AuditManager;
+use crate::blocklist::BlocklistManager;
+use crate::moderation::ModerationManager;
+use crate::sanitize;
+use crate::storage::StorageManager;
+use crate::types::{
+    ArchiveReason, BlocklistEntry, CompanyStatus, CriticalAction, CriticalActionProposal, ProposalStatus,
+    RegistryResult,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Two-person rule: irreversible actions are proposed by one moderator and only run
+// once a different moderator approves before the proposal expires.
+
+pub struct CriticalActionManager;
+
+impl CriticalActionManager {
+    const PROPOSAL_TTL_NS: u64 = 72 * 60 * 60 * 1_000_000_000; // 72 hours
+    const MAX_REASON_LENGTH: usize = 500;
+
+    pub fn propose_critical_action(
+        action: CriticalAction,
+        reason: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<CriticalActionProposal> {
+        if let Err(err) = ModerationManager::ensure_moderator(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if reason.trim().is_empty() || sanitize::grapheme_len(&reason) > Self::MAX_REASON_LENGTH {
+            return RegistryResult::Err("Reason must be 1-500 characters".to_string());
+        }
+        let action = match Self::validate(action) {
+            Ok(action) => action,
+            Err(err) => return RegistryResult::Err(err),
+        };
+
+        let now = time();
+        let proposal = CriticalActionProposal {
+            id: format!("proposal_{}_{}", now, StorageManager::critical_action_proposal_count()),
+            action,
+            reason: reason.trim().to_string(),
+            proposed_by: caller_principal,
+            proposed_at: now,
+            expires_at: now + Self::PROPOSAL_TTL_NS,
+            status: ProposalStatus::Pending,
+            approved_by: None,
+            resolved_at: None,
+            error: None,
+        };
+
+        StorageManager::insert_critical_action_proposal(proposal.clone());
+        RegistryResult::Ok(proposal)
+    }
+
+    // The second approval executes the action immediately
+    pub fn approve_critical_action(
+        proposal_id: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<CriticalActionProposal> {
+        if let Err(err) = ModerationManager::ensure_moderator(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        let mut proposal = match Self::load_pending(&proposal_id) {
+            Ok(proposal) => proposal,
+            Err(err) => return RegistryResult::Err(err),
+        };
+        if proposal.proposed_by == caller_principal {
+            return RegistryResult::Err("A different moderator must approve this action".to_string());
+        }
+
+        let now = time();
+        proposal.approved_by = Some(caller_principal);
+        proposal.resolved_at = Some(now);
+
+        let result = Self::validate(proposal.action.clone())
+            .and_then(|action| Self::execute(&action, &proposal, caller_principal));
+        match result {
+            Ok(()) => {
+                proposal.status = ProposalStatus::Executed;
+                let (action_name, target) = Self::describe(&proposal.action);
+                AuditManager::record(
+                    action_name,
+                    &target,
+                    vec![proposal.proposed_by, caller_principal],
+                    format!("{} (proposal {})", proposal.reason, proposal.id),
+                );
+            }
+            Err(err) => {
+                proposal.status = ProposalStatus::Failed;
+                proposal.error = Some(err);
+            }
+        }
+
+        StorageManager::insert_critical_action_proposal(proposal.clone());
+        RegistryResult::Ok(proposal)
+    }
+
+    pub fn cancel_critical_action(
+        proposal_id: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<CriticalActionProposal> {
+        let mut proposal = match Self::load_pending(&proposal_id) {
+            Ok(proposal) => proposal,
+            Err(err) => return RegistryResult::Err(err),
+        };
+        if proposal.proposed_by != caller_principal {
+            return RegistryResult::Err("Unauthorized: Only the proposer can cancel this action".to_string());
+        }
+
+        proposal.status = ProposalStatus::Cancelled;
+        proposal.resolved_at = Some(time());
+        StorageManager::insert_critical_action_proposal(proposal.clone());
+        RegistryResult::Ok(proposal)
+    }
+
+    pub fn get_critical_action_proposals(
+        status: Option<ProposalStatus>,
+        caller_principal: Principal,
+    ) -> RegistryResult<Vec<CriticalActionProposal>> {
+        if let Err(err) = ModerationManager::ensure_moderator(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        let now = time();
+        let mut proposals: Vec<CriticalActionProposal> = StorageManager::get_all_critical_action_proposals()
+            .into_iter()
+            .map(|mut p| {
+                if p.status == ProposalStatus::Pending && now >= p.expires_at {
+                    p.status = ProposalStatus::Expired;
+                }
+                p
+            })
+            .filter(|p| status.as_ref().is_none_or(|s| p.status == *s))
+            .collect();
+        proposals.sort_by_key(|p| std::cmp::Reverse(p.proposed_at));
+        RegistryResult::Ok(proposals)
+    }
+
+    // Expiry is applied lazily when a proposal is next touched
+    fn load_pending(proposal_id: &str) -> Result<CriticalActionProposal, String> {
+        let mut proposal = StorageManager::get_critical_action_proposal(proposal_id)
+            .ok_or_else(|| "Proposal not found".to_string())?;
+        if proposal.status == ProposalStatus::Pending && time() >= proposal.expires_at {
+            proposal.status = ProposalStatus::Expired;
+            proposal.resolved_at = Some(proposal.expires_at);
+            StorageManager::insert_critical_action_proposal(proposal);
+            return Err("Proposal has expired".to_string());
+        }
+        if proposal.status != ProposalStatus::Pending {
+            return Err("Proposal is no longer pending".to_string());
+        }
+        Ok(proposal)
+    }
+
+    // Returns the action with blocklist values normalized
+    fn validate(action: CriticalAction) -> Result<CriticalAction, String> {
+        match action {
+            CriticalAction::PermanentSuspension { company_id } => {
+                let company = StorageManager::get_company(&company_id).ok_or("Company not found")?;
+                if company.permanently_suspended.unwrap_or(false) {
+                    return Err("Company is already permanently suspended".to_string());
+                }
+                Ok(CriticalAction::PermanentSuspension { company_id })
+            }
+            CriticalAction::CompanyDeletion { company_id } => {
+                if StorageManager::get_company(&company_id).is_none() {
+                    return Err("Company not found".to_string());
+                }
+                Ok(CriticalAction::CompanyDeletion { company_id })
+            }
+            CriticalAction::BlocklistAddition { kind, value } => {
+                let value = BlocklistManager::normalize(&kind, &value)?;
+                if StorageManager::get_blocklist_entry(&BlocklistManager::key(&kind, &value)).is_some() {
+                    return Err("Entry is already on the blocklist".to_string());
+                }
+                Ok(CriticalAction::BlocklistAddition { kind, value })
+            }
+        }
+    }
+
+    fn execute(
+        action: &CriticalAction,
+        proposal: &CriticalActionProposal,
+        approver: Principal,
+    ) -> Result<(), String> {
+        match action {
+            CriticalAction::PermanentSuspension { company_id } => {
+                let updated = StorageManager::update_company(company_id, |company| {
+                    company.status = CompanyStatus::Suspended;
+                    company.permanently_suspended = Some(true);
+                    company.updated_at = time();
+                });
+                if !updated {
+                    return Err("Company not found".to_string());
+                }
+            }
+            CriticalAction::CompanyDeletion { company_id } => {
+                ArchiveManager::move_to_archive(company_id, ArchiveReason::Deleted, approver)?;
+            }
+            CriticalAction::BlocklistAddition { kind, value } => {
+                StorageManager::insert_blocklist_entry(
+                    BlocklistManager::key(kind, value),
+                    BlocklistEntry {
+                        kind: kind.clone(),
+                        value: value.clone(),
+                        reason: proposal.reason.clone(),
+                        proposed_by: proposal.proposed_by,
+                        approved_by: approver,
+                        added_at: time(),
+                    },
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn describe(action: &CriticalAction) -> (&'static str, String) {
+        match action {
+            CriticalAction::PermanentSuspension { company_id } => ("permanent_suspension", company_id.clone()),
+            CriticalAction::CompanyDeletion { company_id } => ("company_deletion", company_id.clone()),
+            CriticalAction::BlocklistAddition { kind, value } => {
+                ("blocklist_addition", BlocklistManager::key(kind, value))
+            }
+        }
+    }
+}
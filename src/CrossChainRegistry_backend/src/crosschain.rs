@@ -1,24 +1,43 @@
+use crate::admins::AdminManager;
+use crate::bitcoin_message::BitcoinMessageVerifier;
+use crate::evm_signature::EvmSignatureVerifier;
+use crate::feature_flags::{self, FeatureFlagManager};
+use crate::ownership::OwnershipManager;
+use crate::rotation::RotationManager;
+use crate::settings::SettingsManager;
 use crate::storage::StorageManager;
 use crate::types::{
-    ChainType, CrossChainChallenge, CrossChainVerificationMethod, CrossChainVerificationRequest,
-    EtherscanContractResponse, RegistryResult, VerificationResult, BlockchainInfoResponse,
+    ApiProvider, ApiStyle, ChainType, CompanyRole, CrossChainChallenge,
+    CrossChainVerificationRequest, EsploraAddressResponse, EvmTransaction,
+    ProviderEndpoint, RegistryResult, SolanaSignatureInfo, VerificationResult, BlockchainInfoResponse,
 };
+use crate::verification::VerificationManager;
 use candid::Principal;
 use ic_cdk::api::management_canister::http_request::{
-    http_request, CanisterHttpRequestArgument, HttpHeader, HttpMethod, HttpResponse, TransformArgs,
+    CanisterHttpRequestArgument, HttpHeader, HttpMethod, HttpResponse, TransformArgs,
     TransformContext,
 };
+use ic_cdk::api::management_canister::main::{canister_info, raw_rand, CanisterInfoRequest};
 use ic_cdk::api::time;
 use regex::Regex;
-use serde_json;
+
+// Ownable owner() function selector
+const OWNER_SELECTOR: &str = "0x8da5cb5b";
 
 // Cross-chain verification implementation
 
 pub struct CrossChainVerifier;
 
 impl CrossChainVerifier {
-    // Create cross-chain verification challenge
-    pub fn create_crosschain_challenge(
+    const SOLANA_SIGNATURE_LIMIT: u32 = 25;
+    const MAX_SOLANA_MEMO_CANDIDATES: usize = 3;
+    const NONCE_PREFIX: &'static str = "ccr-";
+
+    // Create cross-chain verification challenge. The text to sign or publish is always
+    // built by the registry around a random nonce; caller-supplied text in the
+    // verification method is ignored, so a message the owner signed elsewhere can't be
+    // replayed as a proof.
+    pub async fn create_crosschain_challenge(
         request: CrossChainVerificationRequest,
         caller_principal: Principal,
     ) -> RegistryResult<CrossChainChallenge> {
@@ -28,22 +47,49 @@ impl CrossChainVerifier {
             None => return RegistryResult::Err("Company not found".to_string()),
         };
 
-        if company.created_by != caller_principal {
+        if !OwnershipManager::has_role(&company, caller_principal, CompanyRole::Verifier) {
             return RegistryResult::Err(
-                "Unauthorized: Only company creator can create challenges".to_string(),
+                "Unauthorized: Only company owners or verifiers can create challenges".to_string(),
             );
         }
+        if let Err(err) = AdminManager::ensure_not_suspended(&company) {
+            return RegistryResult::Err(err);
+        }
+
+        // Newer chains roll out behind feature flags
+        let chain_flag = match request.chain_type {
+            ChainType::Solana => Some(feature_flags::CHAIN_SOLANA),
+            ChainType::TON => Some(feature_flags::CHAIN_TON),
+            _ => None,
+        };
+        if let Some(flag) = chain_flag {
+            if let Err(err) = FeatureFlagManager::ensure_enabled(flag, caller_principal) {
+                return RegistryResult::Err(err);
+            }
+        }
 
         // Validate address/contract format
         if let Err(err) = Self::validate_address_format(&request.chain_type, &request.address_or_contract) {
             return RegistryResult::Err(err);
         }
 
+        let random_bytes = match raw_rand().await {
+            Ok((bytes,)) => bytes,
+            Err((code, message)) => {
+                return RegistryResult::Err(format!("raw_rand failed ({:?}): {}", code, message))
+            }
+        };
+        let nonce = format!("{}{}", Self::NONCE_PREFIX, hex::encode(&random_bytes[..16]));
+
         let now = time();
         let expires_at = now + (48 * 60 * 60 * 1_000_000_000); // 48 hours for cross-chain verification
-
-        // Generate challenge message based on verification method
-        let challenge_message = Self::generate_challenge_message(&request.verification_method, &request.company_id);
+        let challenge_message = Self::generate_challenge_message(
+            &request.company_id,
+            &request.chain_type,
+            &request.address_or_contract,
+            &nonce,
+            expires_at,
+        );
 
         let challenge = CrossChainChallenge {
             company_id: request.company_id.clone(),
@@ -53,22 +99,13 @@ impl CrossChainVerifier {
             verification_method: request.verification_method,
             created_at: now,
             expires_at,
+            nonce: Some(nonce),
         };
 
         // Generate unique challenge key
-        let chain_name = match request.chain_type {
-            ChainType::Ethereum => "ethereum",
-            ChainType::Bitcoin => "bitcoin", 
-            ChainType::ICP => "icp",
-            ChainType::Polygon => "polygon",
-            ChainType::Solana => "solana",
-            ChainType::Sui => "sui",
-            ChainType::TON => "ton",
-        };
-
         let challenge_key = StorageManager::generate_crosschain_challenge_key(
             &request.company_id,
-            chain_name,
+            Self::chain_name(&request.chain_type),
             &request.address_or_contract,
         );
 
@@ -77,10 +114,17 @@ impl CrossChainVerifier {
         RegistryResult::Ok(challenge)
     }
 
-    // Verify Ethereum contract ownership
+    // Verify Ethereum address or contract ownership with either proof:
+    // - an EIP-191 signature of the challenge, recovered locally, or
+    // - the hash of a transaction whose input data is exactly the challenge nonce.
+    // The signer or sender must be the address itself or, for a contract, the account its
+    // owner() returns. Reads go to several JSON-RPC endpoints, which must agree, so no
+    // single API (or API key) decides the outcome.
     pub async fn verify_ethereum_contract(
         company_id: String,
         contract_address: String,
+        transaction_hash: Option<String>,
+        signature: Option<String>,
     ) -> RegistryResult<VerificationResult> {
         // Find the corresponding challenge
         let challenge_key = match Self::find_challenge_key(&company_id, "ethereum", &contract_address) {
@@ -98,20 +142,196 @@ impl CrossChainVerifier {
             return RegistryResult::Err("Cross-chain verification challenge expired".to_string());
         }
 
-        // Query Etherscan API for recent transactions
-        let etherscan_url = format!(
-            "https://api.etherscan.io/api?module=account&action=txlist&address={}&startblock=0&endblock=99999999&sort=desc&apikey=YourApiKeyToken",
-            contract_address
-        );
+        let address = contract_address.to_lowercase();
+        let checked = match (signature, transaction_hash) {
+            (Some(signature), _) => Self::check_ethereum_signature(&address, &challenge, &signature).await,
+            (None, Some(hash)) => Self::check_ethereum_transaction(&address, &challenge, hash).await,
+            (None, None) => {
+                return RegistryResult::Ok(Self::failed_result(
+                    ChainType::Ethereum,
+                    "A signature or transaction hash is required".to_string(),
+                    "Sign the challenge message with the wallet (personal_sign) or pass the hash of a transaction whose input data is the challenge nonce to verify_ethereum_contract",
+                ))
+            }
+        };
+        let proof = match checked {
+            Ok(proof) => proof,
+            Err(result) => return result,
+        };
+
+        let mut score_delta = 0;
+        let success = StorageManager::update_company(&company_id, |company| {
+            let previous_score = company.verification_score;
+            // Add to verified contracts if not already present
+            if !company.cross_chain_presence.ethereum_contracts.contains(&contract_address) {
+                company.cross_chain_presence.ethereum_contracts.push(contract_address.clone());
+            }
+            // Mark contract as verified in WalletInfo or TokenInfo if exists
+            for wallet in &mut company.cross_chain_presence.treasury_wallets {
+                if wallet.address == contract_address && wallet.chain == "ethereum" {
+                    wallet.verified = true;
+                }
+            }
+            for token in &mut company.cross_chain_presence.token_contracts {
+                if token.contract_address == contract_address && token.chain == "ethereum" {
+                    token.verified = true;
+                }
+            }
+            RotationManager::complete_verified_successor(company, &ChainType::Ethereum, &contract_address);
+            company.verification_score = VerificationManager::calculate_verification_score(company);
+            score_delta = company.verification_score as i32 - previous_score as i32;
+        });
+        if !success {
+            return RegistryResult::Err("Failed to update company".to_string());
+        }
+
+        // Remove challenge after successful verification
+        StorageManager::remove_crosschain_challenge(&challenge_key);
+        RegistryResult::Ok(Self::verified_result(
+            &company_id,
+            ChainType::Ethereum,
+            &contract_address,
+            format!("Ethereum contract {} verified successfully ({})", contract_address, proof),
+            score_delta,
+        ))
+    }
+
+    // Describes the proof on success; the early return on failure
+    async fn check_ethereum_signature(
+        address: &str,
+        challenge: &CrossChainChallenge,
+        signature: &str,
+    ) -> Result<String, RegistryResult<VerificationResult>> {
+        if let Err(err) = Self::issued_nonce(challenge) {
+            return Err(RegistryResult::Err(err));
+        }
+        let signer = match EvmSignatureVerifier::recover_personal_sign(&challenge.challenge_message, signature) {
+            Ok(signer) => signer,
+            Err(err) => {
+                return Err(RegistryResult::Ok(Self::failed_result(
+                    ChainType::Ethereum,
+                    format!("Signature check failed: {}", err),
+                    "Sign exactly the challenge message with personal_sign and pass the 0x-prefixed signature",
+                )))
+            }
+        };
+        if signer != address {
+            Self::ensure_contract_owner(address, &signer, "signed by").await?;
+        }
+        Ok(format!("EIP-191 signature by {}", signer))
+    }
+
+    async fn check_ethereum_transaction(
+        address: &str,
+        challenge: &CrossChainChallenge,
+        transaction_hash: String,
+    ) -> Result<String, RegistryResult<VerificationResult>> {
+        let nonce = Self::issued_nonce(challenge).map_err(RegistryResult::Err)?;
+        let transaction_hash = transaction_hash.trim().to_lowercase();
+        if !Self::is_transaction_hash(&transaction_hash) {
+            return Err(RegistryResult::Err(
+                "Transaction hash must be 0x followed by 64 hex characters".to_string(),
+            ));
+        }
+
+        let transaction = match Self::fetch_ethereum_transaction(&transaction_hash).await {
+            Ok(Some(transaction)) => transaction,
+            Ok(None) => {
+                return Err(RegistryResult::Ok(Self::failed_result(
+                    ChainType::Ethereum,
+                    format!("Transaction {} was not found or is not mined yet", transaction_hash),
+                    "Wait for the transaction to be mined and retry verify_ethereum_contract",
+                )))
+            }
+            Err(err) => return Err(RegistryResult::Err(err)),
+        };
+
+        // The input must be exactly the nonce, so no older transaction can match
+        if transaction.input != format!("0x{}", hex::encode(nonce)) {
+            return Err(RegistryResult::Ok(Self::failed_result(
+                ChainType::Ethereum,
+                "The transaction input data is not the challenge nonce".to_string(),
+                "Send a transaction whose input data is exactly the UTF-8 challenge nonce and retry verify_ethereum_contract",
+            )));
+        }
+        // Anyone can send to an address; only the address itself or, for a contract, its
+        // owner proves control of it
+        if transaction.from != address {
+            Self::ensure_contract_owner(address, &transaction.from, "sent by").await?;
+        }
+        Ok(format!("transaction {} in block {}", transaction_hash, transaction.block_number))
+    }
+
+    async fn ensure_contract_owner(
+        address: &str,
+        account: &str,
+        action: &str,
+    ) -> Result<(), RegistryResult<VerificationResult>> {
+        match Self::fetch_contract_owner(address).await {
+            Ok(Some(owner)) if owner == account => Ok(()),
+            Ok(_) => Err(RegistryResult::Ok(Self::failed_result(
+                ChainType::Ethereum,
+                format!("The proof was {} {}, which is neither {} nor its owner()", action, account, address),
+                "Use the address being verified or, for a contract, the account its owner() returns",
+            ))),
+            Err(err) => Err(RegistryResult::Err(err)),
+        }
+    }
+
+    // Verify Bitcoin address ownership: the registry-issued challenge message, nonce
+    // included, must be signed with the address's key (BIP-137 or BIP-322), and a quorum
+    // of explorers must agree the address has on-chain history
+    pub async fn verify_bitcoin_address(
+        company_id: String,
+        bitcoin_address: String,
+        signature: Option<String>,
+    ) -> RegistryResult<VerificationResult> {
+        // Find the corresponding challenge
+        let challenge_key = match Self::find_challenge_key(&company_id, "bitcoin", &bitcoin_address) {
+            Ok(key) => key,
+            Err(err) => return RegistryResult::Err(err),
+        };
+        let challenge = match StorageManager::get_crosschain_challenge(&challenge_key) {
+            Some(challenge) => challenge,
+            None => return RegistryResult::Err("No verification challenge found".to_string()),
+        };
+
+        // Check if challenge expired
+        if time() > challenge.expires_at {
+            StorageManager::remove_crosschain_challenge(&challenge_key);
+            return RegistryResult::Err("Cross-chain verification challenge expired".to_string());
+        }
+
+        if let Err(err) = Self::issued_nonce(&challenge) {
+            return RegistryResult::Err(err);
+        }
+        let signature = match signature {
+            Some(signature) => signature,
+            None => {
+                return RegistryResult::Ok(Self::failed_result(
+                    ChainType::Bitcoin,
+                    "A signed challenge message is required".to_string(),
+                    "Sign the challenge message with the address's key (Sign Message in your wallet) and pass the signature to verify_bitcoin_address",
+                ))
+            }
+        };
+        if let Err(err) = BitcoinMessageVerifier::verify(&bitcoin_address, &challenge.challenge_message, &signature) {
+            return RegistryResult::Ok(Self::failed_result(
+                ChainType::Bitcoin,
+                format!("Signature check failed: {}", err),
+                "Sign exactly the challenge message with the key of the address being verified",
+            ));
+        }
 
-        let request = CanisterHttpRequestArgument {
-            url: etherscan_url,
+        // Ask the explorers until a quorum agrees on whether the address has history
+        let build_request = |endpoint: &ProviderEndpoint| CanisterHttpRequestArgument {
+            url: endpoint.url_for(&bitcoin_address),
             method: HttpMethod::GET,
             body: None,
-            max_response_bytes: Some(8192),
+            max_response_bytes: Some(4096),
             transform: Some(TransformContext::from_name(
-                "transform_etherscan_response".to_string(),
-                vec![],
+                "transform_blockchain_response".to_string(),
+                serde_json::to_vec(&endpoint.api_style).unwrap_or_default(),
             )),
             headers: vec![
                 HttpHeader {
@@ -121,70 +341,76 @@ impl CrossChainVerifier {
             ],
         };
 
-        match http_request(request, 15_000_000_000).await {
-            Ok((response,)) => {
-                if response.status == 200u32 {
-                    // Parse Etherscan response
-                    match serde_json::from_slice::<EtherscanContractResponse>(&response.body) {
-                        Ok(etherscan_data) => {
-                            // Look for the challenge message in recent transactions
-                            if Self::verify_ethereum_challenge(&etherscan_data, &challenge.challenge_message) {
-                                // Verification successful - update company
-                                let success = StorageManager::update_company(&company_id, |company| {
-                                    // Add to verified contracts if not already present
-                                    if !company.cross_chain_presence.ethereum_contracts.contains(&contract_address) {
-                                        company.cross_chain_presence.ethereum_contracts.push(contract_address.clone());
-                                    }
-                                    // Mark contract as verified in WalletInfo or TokenInfo if exists
-                                    for wallet in &mut company.cross_chain_presence.treasury_wallets {
-                                        if wallet.address == contract_address && wallet.chain == "ethereum" {
-                                            wallet.verified = true;
-                                        }
-                                    }
-                                    for token in &mut company.cross_chain_presence.token_contracts {
-                                        if token.contract_address == contract_address && token.chain == "ethereum" {
-                                            token.verified = true;
-                                        }
-                                    }
-                                });
-
-                                if success {
-                                    // Remove challenge after successful verification
-                                    StorageManager::remove_crosschain_challenge(&challenge_key);
-
-                                    RegistryResult::Ok(VerificationResult {
-                                        success: true,
-                                        message: format!("Ethereum contract {} verified successfully", contract_address),
-                                        verified_at: Some(time()),
-                                    })
-                                } else {
-                                    RegistryResult::Err("Failed to update company".to_string())
-                                }
-                            } else {
-                                RegistryResult::Ok(VerificationResult {
-                                    success: false,
-                                    message: "Challenge message not found in recent transactions".to_string(),
-                                    verified_at: None,
-                                })
-                            }
-                        }
-                        Err(_) => RegistryResult::Err("Failed to parse Etherscan API response".to_string()),
-                    }
-                } else {
-                    RegistryResult::Err(format!("Etherscan API error: {}", response.status))
+        let (providers, response) =
+            match SettingsManager::http_request_with_quorum(ApiProvider::Bitcoin, build_request, 15_000_000_000).await {
+                Ok(answer) => answer,
+                Err(err) => return RegistryResult::Err(format!("Bitcoin address lookup failed: {}", err)),
+            };
+        let has_transactions = match serde_json::from_slice::<bool>(&response.body) {
+            Ok(has_transactions) => has_transactions,
+            Err(_) => {
+                return RegistryResult::Err(format!("Failed to parse {} API response", providers.join(", ")))
+            }
+        };
+
+        // For Bitcoin, we verify the address exists and has activity
+        if !has_transactions {
+            let mut result = Self::failed_result(
+                ChainType::Bitcoin,
+                "Bitcoin address has no transaction history".to_string(),
+                "Make at least one transaction from the address and retry verify_bitcoin_address",
+            );
+            result.providers = Some(providers);
+            return RegistryResult::Ok(result);
+        }
+
+        // Update company with verified Bitcoin address
+        let mut score_delta = 0;
+        let success = StorageManager::update_company(&company_id, |company| {
+            let previous_score = company.verification_score;
+            if !company.cross_chain_presence.bitcoin_addresses.contains(&bitcoin_address) {
+                company.cross_chain_presence.bitcoin_addresses.push(bitcoin_address.clone());
+            }
+            // Mark wallet as verified if exists
+            for wallet in &mut company.cross_chain_presence.treasury_wallets {
+                if wallet.address == bitcoin_address && wallet.chain == "bitcoin" {
+                    wallet.verified = true;
                 }
             }
-            Err(err) => RegistryResult::Err(format!("HTTP request failed: {:?}", err)),
+            RotationManager::complete_verified_successor(company, &ChainType::Bitcoin, &bitcoin_address);
+            company.verification_score = VerificationManager::calculate_verification_score(company);
+            score_delta = company.verification_score as i32 - previous_score as i32;
+        });
+        if !success {
+            return RegistryResult::Err("Failed to update company".to_string());
         }
+
+        // Remove challenge after successful verification
+        StorageManager::remove_crosschain_challenge(&challenge_key);
+        let mut result = Self::verified_result(
+            &company_id,
+            ChainType::Bitcoin,
+            &bitcoin_address,
+            format!(
+                "Bitcoin address {} verified successfully (confirmed by {})",
+                bitcoin_address,
+                providers.join(", ")
+            ),
+            score_delta,
+        );
+        result.providers = Some(providers);
+        RegistryResult::Ok(result)
     }
 
-    // Verify Bitcoin address ownership
-    pub async fn verify_bitcoin_address(
+    // Verify Solana address ownership: the challenge nonce must be a memo in a successful
+    // transaction the address signed. getSignaturesForAddress finds memo candidates, and
+    // getTransaction confirms the address was a signer rather than just a recipient.
+    pub async fn verify_solana_address(
         company_id: String,
-        bitcoin_address: String,
+        solana_address: String,
     ) -> RegistryResult<VerificationResult> {
         // Find the corresponding challenge
-        let challenge_key = match Self::find_challenge_key(&company_id, "bitcoin", &bitcoin_address) {
+        let challenge_key = match Self::find_challenge_key(&company_id, "solana", &solana_address) {
             Ok(key) => key,
             Err(err) => return RegistryResult::Err(err),
         };
@@ -199,19 +425,122 @@ impl CrossChainVerifier {
             return RegistryResult::Err("Cross-chain verification challenge expired".to_string());
         }
 
-        // Query Blockchain.info API for address information
-        let blockchain_url = format!(
-            "https://blockchain.info/rawaddr/{}?limit=50",
-            bitcoin_address
-        );
+        let nonce = match Self::issued_nonce(&challenge) {
+            Ok(nonce) => nonce,
+            Err(err) => return RegistryResult::Err(err),
+        };
+        let signatures = match Self::fetch_solana_signatures(&solana_address).await {
+            Ok(signatures) => signatures,
+            Err(err) => return RegistryResult::Err(err),
+        };
+        let candidates: Vec<&SolanaSignatureInfo> = signatures
+            .iter()
+            .filter(|info| {
+                info.succeeded
+                    && info.memo.as_deref().is_some_and(|memo| Self::memo_matches(memo, nonce))
+            })
+            .take(Self::MAX_SOLANA_MEMO_CANDIDATES)
+            .collect();
+        if candidates.is_empty() {
+            return RegistryResult::Ok(Self::failed_result(
+                ChainType::Solana,
+                "Challenge nonce not found in recent transaction memos".to_string(),
+                "Send a transaction from the address with exactly the challenge nonce as its memo and retry verify_solana_address",
+            ));
+        }
 
-        let request = CanisterHttpRequestArgument {
-            url: blockchain_url,
+        let mut signed_by_address = false;
+        for candidate in candidates {
+            match Self::fetch_solana_signers(&candidate.signature).await {
+                Ok(signers) if signers.contains(&solana_address) => {
+                    signed_by_address = true;
+                    break;
+                }
+                Ok(_) => {}
+                Err(err) => return RegistryResult::Err(err),
+            }
+        }
+        if !signed_by_address {
+            return RegistryResult::Ok(Self::failed_result(
+                ChainType::Solana,
+                "The memo transaction was not signed by this address".to_string(),
+                "Send the memo transaction from the address being verified and retry verify_solana_address",
+            ));
+        }
+
+        let mut score_delta = 0;
+        let success = StorageManager::update_company(&company_id, |company| {
+            let previous_score = company.verification_score;
+            if !company.cross_chain_presence.solana_addresses.contains(&solana_address) {
+                company.cross_chain_presence.solana_addresses.push(solana_address.clone());
+            }
+            for wallet in &mut company.cross_chain_presence.treasury_wallets {
+                if wallet.address == solana_address && wallet.chain == "solana" {
+                    wallet.verified = true;
+                }
+            }
+            for token in &mut company.cross_chain_presence.token_contracts {
+                if token.contract_address == solana_address && token.chain == "solana" {
+                    token.verified = true;
+                }
+            }
+            RotationManager::complete_verified_successor(company, &ChainType::Solana, &solana_address);
+            company.verification_score = VerificationManager::calculate_verification_score(company);
+            score_delta = company.verification_score as i32 - previous_score as i32;
+        });
+
+        if success {
+            // Remove challenge after successful verification
+            StorageManager::remove_crosschain_challenge(&challenge_key);
+
+            RegistryResult::Ok(Self::verified_result(
+                &company_id,
+                ChainType::Solana,
+                &solana_address,
+                format!("Solana address {} verified successfully", solana_address),
+                score_delta,
+            ))
+        } else {
+            RegistryResult::Err("Failed to update company".to_string())
+        }
+    }
+
+    // Verify TON address ownership: the challenge nonce must be the text comment of a
+    // recent outgoing message. Outgoing messages are only emitted when the wallet's owner
+    // signs, so unlike an incoming transfer this cannot be forged by a third party.
+    pub async fn verify_ton_address(
+        company_id: String,
+        ton_address: String,
+    ) -> RegistryResult<VerificationResult> {
+        // Find the corresponding challenge
+        let challenge_key = match Self::find_challenge_key(&company_id, "ton", &ton_address) {
+            Ok(key) => key,
+            Err(err) => return RegistryResult::Err(err),
+        };
+        let challenge = match StorageManager::get_crosschain_challenge(&challenge_key) {
+            Some(challenge) => challenge,
+            None => return RegistryResult::Err("No verification challenge found".to_string()),
+        };
+
+        // Check if challenge expired
+        if time() > challenge.expires_at {
+            StorageManager::remove_crosschain_challenge(&challenge_key);
+            return RegistryResult::Err("Cross-chain verification challenge expired".to_string());
+        }
+
+        let nonce = match Self::issued_nonce(&challenge) {
+            Ok(nonce) => nonce,
+            Err(err) => return RegistryResult::Err(err),
+        };
+
+        // Query TON explorer APIs for recent transactions
+        let build_request = |endpoint: &ProviderEndpoint| CanisterHttpRequestArgument {
+            url: endpoint.url_for(&ton_address),
             method: HttpMethod::GET,
             body: None,
-            max_response_bytes: Some(4096),
+            max_response_bytes: Some(200_000),
             transform: Some(TransformContext::from_name(
-                "transform_blockchain_response".to_string(),
+                "transform_ton_transactions".to_string(),
                 vec![],
             )),
             headers: vec![
@@ -222,65 +551,67 @@ impl CrossChainVerifier {
             ],
         };
 
-        match http_request(request, 15_000_000_000).await {
-            Ok((response,)) => {
-                if response.status == 200u32 {
-                    // Parse blockchain.info response
-                    match serde_json::from_slice::<BlockchainInfoResponse>(&response.body) {
-                        Ok(blockchain_data) => {
-                            // For Bitcoin, we verify the address exists and has activity
-                            if blockchain_data.n_tx > 0 {
-                                // Update company with verified Bitcoin address
-                                let success = StorageManager::update_company(&company_id, |company| {
-                                    if !company.cross_chain_presence.bitcoin_addresses.contains(&bitcoin_address) {
-                                        company.cross_chain_presence.bitcoin_addresses.push(bitcoin_address.clone());
-                                    }
-                                    // Mark wallet as verified if exists
-                                    for wallet in &mut company.cross_chain_presence.treasury_wallets {
-                                        if wallet.address == bitcoin_address && wallet.chain == "bitcoin" {
-                                            wallet.verified = true;
-                                        }
-                                    }
-                                });
-
-                                if success {
-                                    // Remove challenge after successful verification
-                                    StorageManager::remove_crosschain_challenge(&challenge_key);
-
-                                    RegistryResult::Ok(VerificationResult {
-                                        success: true,
-                                        message: format!("Bitcoin address {} verified successfully", bitcoin_address),
-                                        verified_at: Some(time()),
-                                    })
-                                } else {
-                                    RegistryResult::Err("Failed to update company".to_string())
-                                }
-                            } else {
-                                RegistryResult::Ok(VerificationResult {
-                                    success: false,
-                                    message: "Bitcoin address has no transaction history".to_string(),
-                                    verified_at: None,
-                                })
-                            }
-                        }
-                        Err(_) => RegistryResult::Err("Failed to parse Blockchain.info API response".to_string()),
-                    }
-                } else {
-                    RegistryResult::Err(format!("Blockchain.info API error: {}", response.status))
+        let comments = match SettingsManager::http_request_with_failover(ApiProvider::Ton, build_request, 15_000_000_000).await {
+            Ok((endpoint, response)) if response.status == 200u32 => {
+                match serde_json::from_slice::<Vec<String>>(&response.body) {
+                    Ok(comments) => comments,
+                    Err(_) => return RegistryResult::Err(format!("Failed to parse {} API response", endpoint.name)),
                 }
             }
-            Err(err) => RegistryResult::Err(format!("HTTP request failed: {:?}", err)),
+            Ok((endpoint, response)) => {
+                return RegistryResult::Err(format!("{} API error: {}", endpoint.name, response.status))
+            }
+            Err(err) => return RegistryResult::Err(format!("HTTP request failed: {}", err)),
+        };
+
+        if !comments.iter().any(|comment| comment.trim() == nonce) {
+            return RegistryResult::Ok(Self::failed_result(
+                ChainType::TON,
+                "Challenge nonce not found in recent outgoing messages".to_string(),
+                "Send a transfer from the address with exactly the challenge nonce as its comment and retry verify_ton_address",
+            ));
+        }
+
+        let mut score_delta = 0;
+        let success = StorageManager::update_company(&company_id, |company| {
+            let previous_score = company.verification_score;
+            if !company.cross_chain_presence.ton_addresses.contains(&ton_address) {
+                company.cross_chain_presence.ton_addresses.push(ton_address.clone());
+            }
+            // Verified treasury wallets are picked up by treasury monitoring
+            for wallet in &mut company.cross_chain_presence.treasury_wallets {
+                if wallet.address == ton_address && wallet.chain == "ton" {
+                    wallet.verified = true;
+                }
+            }
+            RotationManager::complete_verified_successor(company, &ChainType::TON, &ton_address);
+            company.verification_score = VerificationManager::calculate_verification_score(company);
+            score_delta = company.verification_score as i32 - previous_score as i32;
+        });
+
+        if success {
+            // Remove challenge after successful verification
+            StorageManager::remove_crosschain_challenge(&challenge_key);
+
+            RegistryResult::Ok(Self::verified_result(
+                &company_id,
+                ChainType::TON,
+                &ton_address,
+                format!("TON address {} verified successfully", ton_address),
+                score_delta,
+            ))
+        } else {
+            RegistryResult::Err("Failed to update company".to_string())
         }
     }
 
-    // Verify ICP canister ownership
+    // Verify ICP canister ownership: one of the company's owners must be among the
+    // canister's controllers, read with canister_info. The canister itself is never asked,
+    // since its answer would only show what its code says, not who controls it.
     pub async fn verify_icp_canister(
         company_id: String,
         canister_id: String,
     ) -> RegistryResult<VerificationResult> {
-        // For ICP canisters, we use the management canister to get canister info
-        // This is a simplified version - in production you'd want to verify controller ownership
-        
         // Find the corresponding challenge
         let challenge_key = match Self::find_challenge_key(&company_id, "icp", &canister_id) {
             Ok(key) => key,
@@ -297,71 +628,188 @@ impl CrossChainVerifier {
             return RegistryResult::Err("Cross-chain verification challenge expired".to_string());
         }
 
-        // For now, we'll do basic validation - in production, you'd call the management canister
-        if Self::is_valid_canister_id(&canister_id) {
-            // Update company with verified ICP canister
-            let success = StorageManager::update_company(&company_id, |company| {
-                if !company.cross_chain_presence.icp_canisters.contains(&canister_id) {
-                    company.cross_chain_presence.icp_canisters.push(canister_id.clone());
-                }
-            });
-
-            if success {
-                // Remove challenge after successful verification
-                StorageManager::remove_crosschain_challenge(&challenge_key);
+        let canister = match Principal::from_text(&canister_id) {
+            Ok(canister) if Self::is_valid_canister_id(&canister_id) => canister,
+            _ => {
+                return RegistryResult::Ok(Self::failed_result(
+                    ChainType::ICP,
+                    "Invalid ICP canister ID format".to_string(),
+                    "Check the canister ID and create a new challenge",
+                ))
+            }
+        };
+        let evidence = match Self::check_canister_control(&company_id, canister, &challenge).await {
+            Ok(evidence) => evidence,
+            Err(result) => return result,
+        };
 
-                RegistryResult::Ok(VerificationResult {
-                    success: true,
-                    message: format!("ICP canister {} verified successfully", canister_id),
-                    verified_at: Some(time()),
-                })
-            } else {
-                RegistryResult::Err("Failed to update company".to_string())
+        // Update company with verified ICP canister
+        let mut score_delta = 0;
+        let success = StorageManager::update_company(&company_id, |company| {
+            let previous_score = company.verification_score;
+            if !company.cross_chain_presence.icp_canisters.contains(&canister_id) {
+                company.cross_chain_presence.icp_canisters.push(canister_id.clone());
             }
+            RotationManager::complete_verified_successor(company, &ChainType::ICP, &canister_id);
+            company.verification_score = VerificationManager::calculate_verification_score(company);
+            score_delta = company.verification_score as i32 - previous_score as i32;
+        });
+
+        if success {
+            // Remove challenge after successful verification
+            StorageManager::remove_crosschain_challenge(&challenge_key);
+
+            RegistryResult::Ok(Self::verified_result(
+                &company_id,
+                ChainType::ICP,
+                &canister_id,
+                format!("ICP canister {} verified successfully ({})", canister_id, evidence),
+                score_delta,
+            ))
         } else {
-            RegistryResult::Ok(VerificationResult {
-                success: false,
-                message: "Invalid ICP canister ID format".to_string(),
-                verified_at: None,
-            })
+            RegistryResult::Err("Failed to update company".to_string())
+        }
+    }
+
+    // How control was shown, or the result to return
+    async fn check_canister_control(
+        company_id: &str,
+        canister: Principal,
+        challenge: &CrossChainChallenge,
+    ) -> Result<String, RegistryResult<VerificationResult>> {
+        let company = StorageManager::get_company(company_id)
+            .ok_or_else(|| RegistryResult::Err("Company not found".to_string()))?;
+        Self::issued_nonce(challenge).map_err(RegistryResult::Err)?;
+
+        let request = CanisterInfoRequest {
+            canister_id: canister,
+            num_requested_changes: None,
+        };
+        let info = match canister_info(request).await {
+            Ok((info,)) => info,
+            Err((code, message)) => {
+                return Err(RegistryResult::Ok(Self::failed_result(
+                    ChainType::ICP,
+                    format!("Could not read canister {} ({:?}): {}", canister, code, message),
+                    "Check that the canister exists on the network this registry runs on",
+                )))
+            }
+        };
+        match info.controllers.iter().find(|c| OwnershipManager::is_owner(&company, **c)) {
+            Some(controller) => Ok(format!("controlled by owner {}", controller)),
+            None => Err(RegistryResult::Ok(Self::failed_result(
+                ChainType::ICP,
+                format!("No company owner controls canister {}", canister),
+                "Add a company owner as a controller of the canister",
+            ))),
         }
     }
 
     // Helper functions
-    fn validate_address_format(chain_type: &ChainType, address: &str) -> Result<(), String> {
+    pub fn chain_name(chain_type: &ChainType) -> &'static str {
         match chain_type {
-            ChainType::Ethereum | ChainType::Polygon => {
-                if !address.starts_with("0x") || address.len() != 42 {
-                    return Err("Invalid Ethereum/Polygon address format".to_string());
-                }
-            }
-            ChainType::Bitcoin => {
-                if address.len() < 26 || address.len() > 35 {
-                    return Err("Invalid Bitcoin address format".to_string());
-                }
-            }
-            ChainType::ICP => {
-                if !Self::is_valid_canister_id(address) {
-                    return Err("Invalid ICP canister ID format".to_string());
-                }
-            }
-            ChainType::Solana => {
-                if address.len() < 32 || address.len() > 44 {
-                    return Err("Invalid Solana address format".to_string());
-                }
-            }
-            ChainType::Sui => {
-                if !address.starts_with("0x") || address.len() != 66 {
-                    return Err("Invalid Sui address format".to_string());
-                }
-            }
-            ChainType::TON => {
-                if !(address.starts_with("0:") || address.starts_with("EQ") || address.starts_with("UQ") || address.starts_with("kQ")) {
-                    return Err("Invalid TON address format".to_string());
-                }
-            }
+            ChainType::Ethereum => "ethereum",
+            ChainType::Bitcoin => "bitcoin",
+            ChainType::ICP => "icp",
+            ChainType::Polygon => "polygon",
+            ChainType::Solana => "solana",
+            ChainType::Sui => "sui",
+            ChainType::TON => "ton",
+        }
+    }
+
+    // Cross-chain proofs are referenced by chain and address
+    fn verified_result(
+        company_id: &str,
+        chain_type: ChainType,
+        address: &str,
+        message: String,
+        score_delta: i32,
+    ) -> VerificationResult {
+        VerificationResult {
+            success: true,
+            message,
+            verified_at: Some(time()),
+            proof_id: Some(format!("{}_{}", Self::chain_name(&chain_type), address)),
+            verification_type: None,
+            chain_type: Some(chain_type),
+            score_delta,
+            retry_after_secs: None,
+            next_action: VerificationManager::recommend_next_action(company_id),
+            providers: None,
+        }
+    }
+
+    fn failed_result(chain_type: ChainType, message: String, next_action: &str) -> VerificationResult {
+        VerificationResult {
+            success: false,
+            message,
+            verified_at: None,
+            proof_id: None,
+            verification_type: None,
+            chain_type: Some(chain_type),
+            score_delta: 0,
+            retry_after_secs: None,
+            next_action: Some(next_action.to_string()),
+            providers: None,
+        }
+    }
+
+    // Every chain the registry accepts challenges for
+    pub const ALL_CHAINS: [ChainType; 7] = [
+        ChainType::Ethereum,
+        ChainType::Bitcoin,
+        ChainType::ICP,
+        ChainType::Polygon,
+        ChainType::Solana,
+        ChainType::Sui,
+        ChainType::TON,
+    ];
+
+    // Accepts the storage key from chain_name plus the usual ticker aliases
+    pub fn chain_from_name(name: &str) -> Option<ChainType> {
+        match name.trim().to_lowercase().as_str() {
+            "btc" => Some(ChainType::Bitcoin),
+            "eth" => Some(ChainType::Ethereum),
+            "sol" => Some(ChainType::Solana),
+            "matic" => Some(ChainType::Polygon),
+            "internet_computer" => Some(ChainType::ICP),
+            name => Self::ALL_CHAINS
+                .into_iter()
+                .find(|chain_type| Self::chain_name(chain_type) == name),
+        }
+    }
+
+    // Single source of truth for address formats; the public validate_address endpoint
+    // goes through here as well
+    pub fn validate_address_format(chain_type: &ChainType, address: &str) -> Result<(), String> {
+        let valid = match chain_type {
+            ChainType::Ethereum => VerificationManager::validate_ethereum_address(address),
+            ChainType::Polygon => VerificationManager::validate_polygon_address(address),
+            ChainType::Bitcoin => VerificationManager::validate_bitcoin_address(address),
+            // Canister IDs are the short 5-5-5-5-3 principal form
+            ChainType::ICP => VerificationManager::validate_icp_principal(address),
+            ChainType::Solana => VerificationManager::validate_solana_address(address),
+            ChainType::Sui => VerificationManager::validate_sui_address(address),
+            ChainType::TON => VerificationManager::validate_ton_address(address),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("Invalid {} address format", Self::display_name(chain_type)))
+        }
+    }
+
+    fn display_name(chain_type: &ChainType) -> &'static str {
+        match chain_type {
+            ChainType::Ethereum => "Ethereum",
+            ChainType::Bitcoin => "Bitcoin",
+            ChainType::ICP => "ICP canister",
+            ChainType::Polygon => "Polygon",
+            ChainType::Solana => "Solana",
+            ChainType::Sui => "Sui",
+            ChainType::TON => "TON",
         }
-        Ok(())
     }
 
     fn is_valid_canister_id(canister_id: &str) -> bool {
@@ -372,36 +820,189 @@ impl CrossChainVerifier {
         canister_regex.is_match(canister_id)
     }
 
-    fn generate_challenge_message(method: &CrossChainVerificationMethod, company_id: &str) -> String {
-        match method {
-            CrossChainVerificationMethod::SignMessage { message } => message.clone(),
-            CrossChainVerificationMethod::DeploySpecialContract { verification_code } => {
-                format!("Deploy contract with code: {} for company: {}", verification_code, company_id)
-            }
-            CrossChainVerificationMethod::SetPublicVariable { variable_name, value } => {
-                format!("Set {} = {} for company: {}", variable_name, value, company_id)
-            }
-            CrossChainVerificationMethod::SpecialTransaction { transaction_data } => {
-                format!("Execute transaction: {} for company: {}", transaction_data, company_id)
+    fn generate_challenge_message(
+        company_id: &str,
+        chain_type: &ChainType,
+        address: &str,
+        nonce: &str,
+        expires_at: u64,
+    ) -> String {
+        format!(
+            "CrossChainRegistry ownership proof\nCompany: {}\nChain: {}\nAddress: {}\nNonce: {}\nExpires: {}",
+            company_id,
+            Self::chain_name(chain_type),
+            address,
+            nonce,
+            expires_at / 1_000_000_000
+        )
+    }
+
+    // The challenge's nonce, if the registry issued it. Challenges from before nonces
+    // carry caller-chosen text and can't be redeemed.
+    fn issued_nonce(challenge: &CrossChainChallenge) -> Result<&str, String> {
+        match challenge.nonce.as_deref() {
+            Some(nonce)
+                if nonce.len() > Self::NONCE_PREFIX.len()
+                    && nonce.starts_with(Self::NONCE_PREFIX)
+                    && challenge.challenge_message.contains(nonce) =>
+            {
+                Ok(nonce)
             }
+            _ => Err("This challenge has no registry-issued nonce; create a new challenge".to_string()),
         }
     }
 
     fn find_challenge_key(company_id: &str, chain: &str, address: &str) -> Result<String, String> {
+        // A challenge issued before a suspension can't be redeemed during it
+        if let Some(company) = StorageManager::get_company(company_id) {
+            AdminManager::ensure_not_suspended(&company)?;
+        }
         match StorageManager::find_crosschain_challenge_key(company_id, chain, address) {
             Some(key) => Ok(key),
             None => Err("Challenge not found".to_string()),
         }
     }
 
-    fn verify_ethereum_challenge(etherscan_data: &EtherscanContractResponse, challenge_message: &str) -> bool {
-        // Look for the challenge message in transaction input data
-        for transaction in &etherscan_data.result {
-            if transaction.input.contains(challenge_message) {
-                return true;
-            }
+    fn parse_bitcoin_tx_count(api_style: &ApiStyle, body: &[u8]) -> Result<u32, String> {
+        match api_style {
+            ApiStyle::Esplora => serde_json::from_slice::<EsploraAddressResponse>(body)
+                .map(|data| data.chain_stats.tx_count + data.mempool_stats.tx_count)
+                .map_err(|e| e.to_string()),
+            _ => serde_json::from_slice::<BlockchainInfoResponse>(body)
+                .map(|data| data.n_tx)
+                .map_err(|e| e.to_string()),
+        }
+    }
+
+    // getSignaturesForAddress joins a transaction's memos with "; " and prefixes each with
+    // its length, e.g. "[36] ccr-..."; one of them must be exactly the nonce
+    fn memo_matches(memo: &str, nonce: &str) -> bool {
+        memo.split("; ").any(|entry| {
+            let text = match entry.strip_prefix('[').and_then(|rest| rest.split_once("] ")) {
+                Some((length, text)) if length.chars().all(|c| c.is_ascii_digit()) => text,
+                _ => entry,
+            };
+            text.trim() == nonce
+        })
+    }
+
+    async fn fetch_solana_signatures(address: &str) -> Result<Vec<SolanaSignatureInfo>, String> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getSignaturesForAddress",
+            "params": [address, { "limit": Self::SOLANA_SIGNATURE_LIMIT, "commitment": "finalized" }],
+        });
+        let response = Self::solana_rpc_call(body, "transform_solana_signatures", 32_768).await?;
+        serde_json::from_slice::<Vec<SolanaSignatureInfo>>(&response.body)
+            .map_err(|_| "Failed to parse Solana getSignaturesForAddress response".to_string())
+    }
+
+    async fn fetch_solana_signers(signature: &str) -> Result<Vec<String>, String> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getTransaction",
+            "params": [signature, {
+                "encoding": "json",
+                "commitment": "finalized",
+                "maxSupportedTransactionVersion": 0,
+            }],
+        });
+        let response = Self::solana_rpc_call(body, "transform_solana_transaction", 65_536).await?;
+        serde_json::from_slice::<Vec<String>>(&response.body)
+            .map_err(|_| "Failed to parse Solana getTransaction response".to_string())
+    }
+
+    async fn solana_rpc_call(
+        body: serde_json::Value,
+        transform: &str,
+        max_response_bytes: u64,
+    ) -> Result<HttpResponse, String> {
+        let body = body.to_string().into_bytes();
+        let build_request = |endpoint: &ProviderEndpoint| CanisterHttpRequestArgument {
+            url: endpoint.url_template.clone(),
+            method: HttpMethod::POST,
+            body: Some(body.clone()),
+            max_response_bytes: Some(max_response_bytes),
+            transform: Some(TransformContext::from_name(transform.to_string(), vec![])),
+            headers: vec![
+                HttpHeader {
+                    name: "Content-Type".to_string(),
+                    value: "application/json".to_string(),
+                },
+                HttpHeader {
+                    name: "User-Agent".to_string(),
+                    value: "ICP-CrossChainRegistry/1.0".to_string(),
+                },
+            ],
+        };
+
+        match SettingsManager::http_request_with_failover(ApiProvider::SolanaRpc, build_request, 15_000_000_000).await {
+            Ok((_, response)) if response.status == 200u32 => Ok(response),
+            Ok((endpoint, response)) => Err(format!("{} RPC error: {}", endpoint.name, response.status)),
+            Err(err) => Err(format!("HTTP request failed: {}", err)),
         }
-        false
+    }
+
+    fn is_transaction_hash(hash: &str) -> bool {
+        hash.len() == 66 && hash.starts_with("0x") && hash[2..].chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    // None until the transaction is mined
+    async fn fetch_ethereum_transaction(hash: &str) -> Result<Option<EvmTransaction>, String> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_getTransactionByHash",
+            "params": [hash],
+        });
+        let response = Self::ethereum_rpc_call(body, "transform_evm_transaction", 65_536).await?;
+        serde_json::from_slice::<Option<EvmTransaction>>(&response.body)
+            .map_err(|_| "Failed to parse Ethereum eth_getTransactionByHash response".to_string())
+    }
+
+    // The Ownable owner() of a contract; None if it has no such function
+    async fn fetch_contract_owner(address: &str) -> Result<Option<String>, String> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_call",
+            "params": [{ "to": address, "data": OWNER_SELECTOR }, "latest"],
+        });
+        let response = Self::ethereum_rpc_call(body, "transform_evm_owner", 4_096).await?;
+        serde_json::from_slice::<Option<String>>(&response.body)
+            .map_err(|_| "Failed to parse Ethereum owner() response".to_string())
+    }
+
+    async fn ethereum_rpc_call(
+        body: serde_json::Value,
+        transform: &str,
+        max_response_bytes: u64,
+    ) -> Result<HttpResponse, String> {
+        let body = body.to_string().into_bytes();
+        let build_request = |endpoint: &ProviderEndpoint| CanisterHttpRequestArgument {
+            url: endpoint.url_template.clone(),
+            method: HttpMethod::POST,
+            body: Some(body.clone()),
+            max_response_bytes: Some(max_response_bytes),
+            transform: Some(TransformContext::from_name(transform.to_string(), vec![])),
+            headers: vec![
+                HttpHeader {
+                    name: "Content-Type".to_string(),
+                    value: "application/json".to_string(),
+                },
+                HttpHeader {
+                    name: "User-Agent".to_string(),
+                    value: "ICP-CrossChainRegistry/1.0".to_string(),
+                },
+            ],
+        };
+
+        let (_, response) =
+            SettingsManager::http_request_with_quorum(ApiProvider::EthereumRpc, build_request, 15_000_000_000)
+                .await?;
+        Ok(response)
     }
 
     // Get verification instructions for cross-chain verification
@@ -409,84 +1010,215 @@ impl CrossChainVerifier {
         match chain_type {
             ChainType::Ethereum => {
                 "To verify Ethereum contract ownership:\n\
-                1. Create a cross-chain verification challenge for your contract address\n\
-                2. Send a transaction to your contract with the challenge message in the input data\n\
-                3. Call verify_ethereum_contract to complete verification\n\
-                4. The system will check recent transactions for the challenge message".to_string()
+                1. Create a cross-chain verification challenge for your contract or address\n\
+                2. Sign the returned challenge message exactly (personal_sign / EIP-191) with the address itself or, for a\n\
+                   contract, the account its owner() returns, and pass the signature to verify_ethereum_contract\n\
+                3. Or send a transaction from that account whose input data is exactly the UTF-8 challenge nonce\n\
+                   and, once it is mined, pass the transaction hash instead\n\
+                4. Transactions and owner() are read from several Ethereum RPC providers, which must agree".to_string()
             }
             ChainType::Bitcoin => {
                 "To verify Bitcoin address ownership:\n\
                 1. Create a cross-chain verification challenge for your Bitcoin address\n\
                 2. Ensure your address has transaction history (at least 1 transaction)\n\
-                3. Call verify_bitcoin_address to complete verification\n\
-                4. The system will verify address activity and ownership".to_string()
+                3. Sign the returned challenge message exactly with the address's key (BIP-137 Sign Message, or BIP-322 for bc1q addresses)\n\
+                4. Call verify_bitcoin_address with the base64 signature to complete verification".to_string()
             }
             ChainType::ICP => {
                 "To verify ICP canister ownership:\n\
                 1. Create a cross-chain verification challenge for your canister ID\n\
-                2. Ensure you are listed as a controller of the canister\n\
-                3. Call verify_icp_canister to complete verification\n\
-                4. The system will verify canister existence and controller status".to_string()
+                2. Add one of the company's owner principals as a controller of the canister\n\
+                3. Call verify_icp_canister; controllers are read from the management canister (canister_info)\n\
+                4. The canister stays under monitoring and loses its verification if its controllers change".to_string()
             }
             ChainType::Polygon => {
                 "To verify Polygon contract ownership:\n\
                 1. Create a cross-chain verification challenge for your contract address\n\
                 2. Send a transaction to your contract with the challenge message\n\
-                3. Call verify_polygon_contract to complete verification\n\
-                4. Similar to Ethereum verification process".to_string()
+                3. Automated Polygon verification is not available yet; the open challenge\n\
+                   records your claim until it is".to_string()
             }
             ChainType::Solana => {
                 "To verify Solana address ownership:\n\
                 1. Create a cross-chain verification challenge for your Solana address\n\
-                2. Send a transaction from your address or create a program interaction\n\
-                3. Call verify_solana_address to complete verification\n\
-                4. The system will verify address activity and ownership".to_string()
+                2. Send a transaction signed by your address with exactly the challenge nonce as its memo (Memo program)\n\
+                3. Wait for the transaction to be finalized, then call verify_solana_address\n\
+                4. The system will check recent memos and confirm your address signed the transaction".to_string()
             }
             ChainType::Sui => {
                 "To verify Sui address ownership:\n\
                 1. Create a cross-chain verification challenge for your Sui address\n\
                 2. Perform a transaction or object interaction from your address\n\
-                3. Call verify_sui_address to complete verification\n\
-                4. The system will verify address activity on Sui network".to_string()
+                3. Automated Sui verification is not available yet; the open challenge\n\
+                   records your claim until it is".to_string()
             }
             ChainType::TON => {
                 "To verify TON address ownership:\n\
                 1. Create a cross-chain verification challenge for your TON address\n\
-                2. Send a transaction or message from your address\n\
+                2. Send a transfer from your address with exactly the challenge nonce as its text comment\n\
                 3. Call verify_ton_address to complete verification\n\
-                4. The system will verify address activity and ownership".to_string()
+                4. The system will look for the comment in the address's recent outgoing messages".to_string()
             }
         }
     }
 }
 
 // HTTP transform functions for cross-chain API responses
-pub fn transform_etherscan_response(raw: TransformArgs) -> HttpResponse {
-    let headers = vec![
-        HttpHeader {
-            name: "Content-Security-Policy".to_string(),
-            value: "default-src 'self'".to_string(),
+
+// Reduce eth_getTransactionByHash to what verification checks; null while unmined
+pub fn transform_evm_transaction(raw: TransformArgs) -> HttpResponse {
+    let transaction = serde_json::from_slice::<serde_json::Value>(&raw.response.body)
+        .ok()
+        .and_then(|json| {
+            let result = &json["result"];
+            if result.is_null() {
+                return Some(serde_json::Value::Null);
+            }
+            let block_number = match result["blockNumber"].as_str() {
+                Some(hex) => u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok()?,
+                None => return Some(serde_json::Value::Null),
+            };
+            Some(serde_json::json!({
+                "from": result["from"].as_str()?.to_lowercase(),
+                "input": result["input"].as_str()?.to_lowercase(),
+                "block_number": block_number,
+            }))
+        });
+
+    HttpResponse {
+        status: raw.response.status.clone(),
+        body: match transaction {
+            Some(transaction) => transaction.to_string().into_bytes(),
+            None => b"invalid_response".to_vec(),
         },
-    ];
+        headers: vec![],
+    }
+}
+
+// Reduce an owner() eth_call to the lowercased address; null if the call reverted or
+// returned something other than one word
+pub fn transform_evm_owner(raw: TransformArgs) -> HttpResponse {
+    let owner = serde_json::from_slice::<serde_json::Value>(&raw.response.body)
+        .ok()
+        .and_then(|json| json["result"].as_str().map(|hex| hex.trim_start_matches("0x").to_lowercase()))
+        .filter(|word| word.len() == 64 && word.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(|word| format!("0x{}", &word[24..]))
+        .filter(|owner| owner != "0x0000000000000000000000000000000000000000");
 
     HttpResponse {
         status: raw.response.status.clone(),
-        body: raw.response.body.clone(),
-        headers,
+        body: serde_json::to_vec(&owner).unwrap_or_default(),
+        headers: vec![],
     }
 }
 
+// Reduce an address lookup to whether the address has any transactions, read in the
+// endpoint's API style (the transform context). Explorers count unconfirmed
+// transactions differently, so the counts themselves would rarely agree.
 pub fn transform_blockchain_response(raw: TransformArgs) -> HttpResponse {
-    let headers = vec![
-        HttpHeader {
-            name: "Content-Security-Policy".to_string(),
-            value: "default-src 'self'".to_string(),
+    let has_transactions = serde_json::from_slice::<ApiStyle>(&raw.context)
+        .ok()
+        .and_then(|api_style| CrossChainVerifier::parse_bitcoin_tx_count(&api_style, &raw.response.body).ok())
+        .map(|tx_count| tx_count > 0);
+
+    HttpResponse {
+        status: raw.response.status.clone(),
+        body: match has_transactions {
+            Some(has_transactions) => serde_json::to_vec(&has_transactions).unwrap_or_default(),
+            None => b"invalid_response".to_vec(),
         },
-    ];
+        headers: vec![],
+    }
+}
+
+// Reduce getSignaturesForAddress to what verification needs. Slot times and confirmation
+// status can differ between the nodes the replicas reach, so they are dropped.
+pub fn transform_solana_signatures(raw: TransformArgs) -> HttpResponse {
+    let signatures = serde_json::from_slice::<serde_json::Value>(&raw.response.body)
+        .ok()
+        .and_then(|json| json["result"].as_array().cloned())
+        .map(|entries| {
+            let mut signatures: Vec<SolanaSignatureInfo> = entries
+                .iter()
+                .filter_map(|entry| {
+                    Some(SolanaSignatureInfo {
+                        signature: entry["signature"].as_str()?.to_string(),
+                        memo: entry["memo"].as_str().map(|memo| memo.to_string()),
+                        succeeded: entry["err"].is_null(),
+                    })
+                })
+                .collect();
+            signatures.sort_by(|a, b| a.signature.cmp(&b.signature));
+            signatures
+        });
+
+    HttpResponse {
+        status: raw.response.status.clone(),
+        body: match signatures {
+            Some(signatures) => serde_json::to_vec(&signatures).unwrap_or_default(),
+            None => b"invalid_response".to_vec(),
+        },
+        headers: vec![],
+    }
+}
+
+// Reduce getTransaction to the accounts that signed it
+pub fn transform_solana_transaction(raw: TransformArgs) -> HttpResponse {
+    let signers = serde_json::from_slice::<serde_json::Value>(&raw.response.body)
+        .ok()
+        .and_then(|json| {
+            let message = &json["result"]["transaction"]["message"];
+            let required = message["header"]["numRequiredSignatures"].as_u64()? as usize;
+            let keys = message["accountKeys"].as_array()?;
+            Some(
+                keys.iter()
+                    .take(required)
+                    .filter_map(|key| key.as_str().map(|key| key.to_string()))
+                    .collect::<Vec<String>>(),
+            )
+        });
 
     HttpResponse {
         status: raw.response.status.clone(),
-        body: raw.response.body.clone(),
-        headers,
+        body: match signers {
+            Some(signers) => serde_json::to_vec(&signers).unwrap_or_default(),
+            None => b"invalid_response".to_vec(),
+        },
+        headers: vec![],
     }
-}
\ No newline at end of file
+}
+
+// Reduce a toncenter or tonapi transaction list to the text comments of outgoing messages,
+// sorted so replicas that saw the list in different orders still agree
+pub fn transform_ton_transactions(raw: TransformArgs) -> HttpResponse {
+    let comments = serde_json::from_slice::<serde_json::Value>(&raw.response.body)
+        .ok()
+        .and_then(|json| {
+            // toncenter keeps the comment in out_msgs[].message, tonapi in decoded_body.text
+            let (transactions, comment_path): (&Vec<serde_json::Value>, &[&str]) = match json["result"].as_array() {
+                Some(transactions) => (transactions, &["message"]),
+                None => (json["transactions"].as_array()?, &["decoded_body", "text"]),
+            };
+            let mut comments: Vec<String> = transactions
+                .iter()
+                .filter(|transaction| transaction["success"].as_bool() != Some(false))
+                .filter_map(|transaction| transaction["out_msgs"].as_array())
+                .flatten()
+                .filter_map(|message| comment_path.iter().fold(message, |value, key| &value[*key]).as_str())
+                .filter(|comment| !comment.is_empty())
+                .map(|comment| comment.to_string())
+                .collect();
+            comments.sort();
+            comments.dedup();
+            Some(comments)
+        });
+
+    HttpResponse {
+        status: raw.response.status.clone(),
+        body: match comments {
+            Some(comments) => serde_json::to_vec(&comments).unwrap_or_default(),
+            None => b"invalid_response".to_vec(),
+        },
+        headers: vec![],
+    }
+}
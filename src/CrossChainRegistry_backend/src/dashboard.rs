@@ -0,0 +1,82 @@
+use crate::api::RegistryAPI;
+use crate::monitoring::MonitoringSystem;
+use crate::ownership::OwnershipManager;
+use crate::storage::StorageManager;
+use crate::types::{
+    Company, CrossChainChallenge, DomainVerificationChallenge, ModerationTaskKind,
+    ModerationTaskStatus, OwnerDashboard, RegistryResult,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+
+// One authorized read of everything that concerns the caller as a company owner, so the
+// frontend doesn't stitch it together from a dozen endpoints.
+
+pub struct DashboardManager;
+
+impl DashboardManager {
+    pub fn get_my_dashboard(caller_principal: Principal) -> RegistryResult<OwnerDashboard> {
+        if caller_principal == Principal::anonymous() {
+            return RegistryResult::Err("Sign in to view your dashboard".to_string());
+        }
+
+        let now = time();
+        let mut companies: Vec<Company> = StorageManager::get_all_companies()
+            .into_iter()
+            .filter(|c| OwnershipManager::is_manager(c, caller_principal))
+            .collect();
+        companies.sort_by_key(|c| c.created_at);
+        let owns = |company_id: &str| companies.iter().any(|c| c.id == company_id);
+
+        let domain_challenges: Vec<DomainVerificationChallenge> = companies
+            .iter()
+            .filter_map(|c| StorageManager::get_domain_challenge(&c.id))
+            .filter(|challenge| challenge.expires_at > now)
+            .collect();
+        let crosschain_challenges: Vec<CrossChainChallenge> = companies
+            .iter()
+            .flat_map(|c| StorageManager::get_crosschain_challenges_for_company(&c.id))
+            .filter(|challenge| challenge.expires_at > now)
+            .collect();
+
+        let mut open_alerts: Vec<_> = StorageManager::get_all_community_alerts()
+            .into_iter()
+            .filter(|a| {
+                owns(&a.company_id)
+                    && a.acknowledged_at.is_none()
+                    && MonitoringSystem::can_view_alert(a, caller_principal)
+            })
+            .collect();
+        open_alerts.sort_by_key(|a| std::cmp::Reverse(a.created_at));
+
+        let mut open_reports: Vec<_> = StorageManager::get_all_moderation_tasks()
+            .into_iter()
+            .filter(|t| {
+                owns(&t.company_id)
+                    && t.kind == ModerationTaskKind::Dispute
+                    && t.status != ModerationTaskStatus::Closed
+            })
+            .collect();
+        open_reports.sort_by_key(|t| std::cmp::Reverse(t.created_at));
+
+        let unread_notifications = StorageManager::get_all_notifications()
+            .iter()
+            .filter(|n| n.recipient == caller_principal && !n.read)
+            .count() as u32;
+
+        for company in companies.iter_mut() {
+            RegistryAPI::prepare_response(company);
+        }
+
+        RegistryResult::Ok(OwnerDashboard {
+            companies,
+            domain_challenges,
+            crosschain_challenges,
+            open_alerts,
+            open_reports,
+            unread_notifications,
+            rate_limits: StorageManager::get_rate_limit_quotas(caller_principal),
+            generated_at: now,
+        })
+    }
+}
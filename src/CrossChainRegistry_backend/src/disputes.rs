@@ -0,0 +1,287 @@
+use crate::audit::AuditManager;
+use crate::moderation::ModerationManager;
+use crate::notifications::NotificationManager;
+use crate::ownership::OwnershipManager;
+use crate::reputation::ReputationJournal;
+use crate::sanitize;
+use crate::storage::StorageManager;
+use crate::types::{
+    CommunityReport, CompanyStatus, Dispute, DisputeOutcome, DisputeStatus, ModerationTask, ModerationTaskKind,
+    ModerationTaskStatus, ProofStatus, RegistryResult, ReportType, ReputationEventKind,
+};
+use crate::verification::VerificationManager;
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Proof disputes: community reports against a verification proof collect on one Dispute
+// moderation task per proof, which moves Open -> UnderReview -> Resolved/Rejected. A
+// moderator claims the task to review it; upholding it updates the proof and, for
+// impersonation or repeat offenders, the company's status.
+
+pub struct DisputeManager;
+
+impl DisputeManager {
+    const MAX_RESOLUTION_LENGTH: usize = 500;
+    // Upheld disputes (including this one) after which a company is flagged
+    const UPHELD_DISPUTES_TO_FLAG: usize = 3;
+
+    // Files a report against the open dispute for the proof, opening one if needed
+    pub fn open_dispute(company_id: &str, proof_id: &str, summary: String) -> ModerationTask {
+        let mut task = ModerationManager::create_task(ModerationTaskKind::Dispute, company_id, proof_id, summary);
+        if task.dispute_status.is_none() {
+            // Also picks up a dispute opened before the lifecycle existed
+            task.dispute_status = Some(Self::dispute_status(&task));
+            StorageManager::insert_moderation_task(task.id.clone(), task.clone());
+        }
+        task
+    }
+
+    // Tasks from before the lifecycle: assigned means under review, and the closing
+    // outcome was prefixed with the verdict
+    fn dispute_status(task: &ModerationTask) -> DisputeStatus {
+        if let Some(status) = &task.dispute_status {
+            return status.clone();
+        }
+        match task.status {
+            ModerationTaskStatus::Open => DisputeStatus::Open,
+            ModerationTaskStatus::Assigned => DisputeStatus::UnderReview,
+            ModerationTaskStatus::Closed if task.outcome.as_deref().is_some_and(|o| o.starts_with("Upheld")) => {
+                DisputeStatus::Resolved
+            }
+            ModerationTaskStatus::Closed => DisputeStatus::Rejected,
+        }
+    }
+
+    // Dispute tasks also carry impersonation reports; only those naming one of the
+    // company's proofs are proof disputes
+    fn get_dispute_task(task_id: &str) -> Option<ModerationTask> {
+        StorageManager::get_moderation_task(task_id).filter(Self::is_proof_dispute)
+    }
+
+    fn is_proof_dispute(task: &ModerationTask) -> bool {
+        if task.kind != ModerationTaskKind::Dispute {
+            return false;
+        }
+        task.dispute_status.is_some()
+            || StorageManager::get_company(&task.company_id).is_some_and(|company| {
+                VerificationManager::find_proof(&company, &task.reference_id).is_some()
+            })
+    }
+
+    // Lifecycle
+
+    // Claims the dispute for the caller (unless a super-admin reviews someone else's)
+    pub fn review_report(task_id: String, caller_principal: Principal) -> RegistryResult<Dispute> {
+        let mut task = match Self::get_dispute_task(&task_id) {
+            Some(task) => task,
+            None => return RegistryResult::Err("Report not found".to_string()),
+        };
+        if let Err(err) = ModerationManager::ensure_can_finalize(&task, caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        match Self::dispute_status(&task) {
+            DisputeStatus::Open => {}
+            DisputeStatus::UnderReview if task.review_started_at.is_none() => {}
+            DisputeStatus::UnderReview => return RegistryResult::Err("Report is already under review".to_string()),
+            DisputeStatus::Resolved | DisputeStatus::Rejected => {
+                return RegistryResult::Err("Report is already resolved".to_string())
+            }
+        }
+
+        let now = time();
+        if task.assignee.is_none() {
+            task.status = ModerationTaskStatus::Assigned;
+            task.assignee = Some(caller_principal);
+            task.assigned_by = Some(caller_principal);
+            task.assigned_at = Some(now);
+        }
+        task.dispute_status = Some(DisputeStatus::UnderReview);
+        task.review_started_at = Some(now);
+        StorageManager::insert_moderation_task(task.id.clone(), task.clone());
+
+        AuditManager::record("review_report", &task.company_id, vec![caller_principal], task.id.clone());
+        RegistryResult::Ok(Self::to_dispute(task))
+    }
+
+    pub fn resolve_report(
+        task_id: String,
+        outcome: DisputeOutcome,
+        resolution: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<Dispute> {
+        let task = match Self::get_dispute_task(&task_id) {
+            Some(task) => task,
+            None => return RegistryResult::Err("Report not found".to_string()),
+        };
+        if let Err(err) = ModerationManager::ensure_can_finalize(&task, caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        match Self::dispute_status(&task) {
+            DisputeStatus::UnderReview => {}
+            DisputeStatus::Open => {
+                return RegistryResult::Err("Report must be taken under review before it is resolved".to_string())
+            }
+            DisputeStatus::Resolved | DisputeStatus::Rejected => {
+                return RegistryResult::Err("Report is already resolved".to_string())
+            }
+        }
+        let resolution = resolution.trim();
+        if resolution.is_empty() || sanitize::grapheme_len(resolution) > Self::MAX_RESOLUTION_LENGTH {
+            return RegistryResult::Err(format!("Resolution must be 1-{} characters", Self::MAX_RESOLUTION_LENGTH));
+        }
+
+        let reports = Self::reports_for(&task);
+        let mut changes = Vec::new();
+        if outcome == DisputeOutcome::Upheld {
+            changes = Self::apply_upheld(&task, &reports, caller_principal);
+        }
+
+        let company_id = task.company_id.clone();
+        let verdict = match outcome {
+            DisputeOutcome::Upheld => "Upheld",
+            DisputeOutcome::Rejected => "Dismissed",
+        };
+        let mut closed = ModerationManager::close_task(task, format!("{}: {}", verdict, resolution), caller_principal);
+        closed.dispute_status = Some(match outcome {
+            DisputeOutcome::Upheld => DisputeStatus::Resolved,
+            DisputeOutcome::Rejected => DisputeStatus::Rejected,
+        });
+        StorageManager::insert_moderation_task(closed.id.clone(), closed.clone());
+
+        let summary = if changes.is_empty() {
+            format!("Report on proof {} {}", closed.reference_id, verdict.to_lowercase())
+        } else {
+            format!("Report on proof {} upheld: {}", closed.reference_id, changes.join(", "))
+        };
+        if let Some(company) = StorageManager::get_company(&company_id) {
+            NotificationManager::notify(company.created_by, &company_id, None, summary.clone());
+        }
+        let mut reporters: Vec<Principal> = reports.iter().map(|r| r.reporter_principal).collect();
+        reporters.sort();
+        reporters.dedup();
+        for reporter in reporters {
+            NotificationManager::notify(reporter, &company_id, None, summary.clone());
+        }
+
+        AuditManager::record(
+            "resolve_report",
+            &company_id,
+            vec![caller_principal],
+            format!("{} {}: {}", closed.id, verdict.to_lowercase(), resolution),
+        );
+        RegistryResult::Ok(Self::to_dispute(closed))
+    }
+
+    // A deleted post removes the proof, anything else marks it disputed. Impersonation
+    // reports, or too many upheld disputes, flag the company.
+    fn apply_upheld(task: &ModerationTask, reports: &[CommunityReport], caller_principal: Principal) -> Vec<String> {
+        let deleted = reports.iter().any(|r| matches!(r.report_type, ReportType::PostDeleted));
+        let fake_profile = reports.iter().any(|r| matches!(r.report_type, ReportType::FakeProfile));
+        let prior_upheld = StorageManager::get_all_moderation_tasks()
+            .iter()
+            .filter(|t| t.company_id == task.company_id && t.id != task.id && Self::is_proof_dispute(t))
+            .filter(|t| Self::dispute_status(t) == DisputeStatus::Resolved)
+            .count();
+        let flag = fake_profile || prior_upheld + 1 >= Self::UPHELD_DISPUTES_TO_FLAG;
+
+        let mut changes = Vec::new();
+        let mut removed = None;
+        StorageManager::update_company(&task.company_id, |company| {
+            for proof in company.web3_identity.verification_proofs.iter_mut() {
+                // Reports filed before stable proof ids reference the proof URL
+                if !VerificationManager::proof_matches(proof, &task.reference_id) {
+                    continue;
+                }
+                if deleted && proof.status != ProofStatus::Removed {
+                    proof.status = ProofStatus::Removed;
+                    removed = Some((proof.proof_url.clone(), proof.id.clone()));
+                    changes.push("proof removed".to_string());
+                } else if proof.status == ProofStatus::Active {
+                    proof.status = ProofStatus::Disputed;
+                    changes.push("proof disputed".to_string());
+                }
+            }
+            if removed.is_some() {
+                company.verification_score = VerificationManager::calculate_verification_score(company);
+            }
+            // Never soften a Suspended company
+            if flag && !matches!(company.status, CompanyStatus::Flagged | CompanyStatus::Suspended) {
+                company.status = CompanyStatus::Flagged;
+                changes.push("company flagged".to_string());
+            }
+        });
+
+        if let Some((proof_url, proof_id)) = removed {
+            ReputationJournal::record(
+                &task.company_id,
+                ReputationEventKind::ProofRemoved { proof_url, proof_id },
+                Some(caller_principal),
+            );
+        }
+        changes
+    }
+
+    // Reports collected while the task was open
+    fn reports_for(task: &ModerationTask) -> Vec<CommunityReport> {
+        let closed_at = task.closed_at.unwrap_or(u64::MAX);
+        StorageManager::get_proof_monitoring(&task.company_id, &task.reference_id)
+            .map(|monitoring| monitoring.community_reports)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|r| r.timestamp >= task.created_at && r.timestamp <= closed_at)
+            .collect()
+    }
+
+    fn to_dispute(task: ModerationTask) -> Dispute {
+        let proof_status = StorageManager::get_company(&task.company_id)
+            .and_then(|company| VerificationManager::find_proof(&company, &task.reference_id).map(|p| p.status.clone()));
+        Dispute {
+            status: Self::dispute_status(&task),
+            reports: Self::reports_for(&task),
+            task_id: task.id,
+            company_id: task.company_id,
+            proof_id: task.reference_id,
+            summary: task.summary,
+            reviewer: task.assignee,
+            opened_at: task.created_at,
+            review_started_at: task.review_started_at.or(task.assigned_at),
+            resolved_by: task.closed_by,
+            resolved_at: task.closed_at,
+            resolution: task.outcome,
+            proof_status,
+        }
+    }
+
+    // Queries
+
+    // Moderators and the company's own managers
+    pub fn get_company_disputes(
+        company_id: String,
+        status: Option<DisputeStatus>,
+        caller_principal: Principal,
+    ) -> RegistryResult<Vec<Dispute>> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+        let is_moderator = ModerationManager::is_moderator(caller_principal);
+        if !is_moderator && !OwnershipManager::is_manager(&company, caller_principal) {
+            return RegistryResult::Err("Unauthorized: Only moderators and company managers can view disputes".to_string());
+        }
+
+        let mut disputes: Vec<Dispute> = StorageManager::get_all_moderation_tasks()
+            .into_iter()
+            .filter(|t| t.company_id == company_id && Self::is_proof_dispute(t))
+            .filter(|t| status.as_ref().is_none_or(|s| Self::dispute_status(t) == *s))
+            .map(Self::to_dispute)
+            .collect();
+        // Companies see what was reported, not who reported it
+        if !is_moderator {
+            for report in disputes.iter_mut().flat_map(|d| d.reports.iter_mut()) {
+                report.reporter_principal = Principal::anonymous();
+            }
+        }
+        disputes.sort_by_key(|d| std::cmp::Reverse(d.opened_at));
+        RegistryResult::Ok(disputes)
+    }
+}
@@ -0,0 +1,123 @@
+use crate::audit::AuditManager;
+use crate::community::CommunityValidationManager;
+use crate::policy::PolicyLog;
+use crate::reputation::ReputationInputs;
+use crate::settings::SettingsManager;
+use crate::storage::StorageManager;
+use crate::types::{Company, RegistryResult, ReputationEscrow, ReputationEscrowPolicy};
+use candid::Principal;
+use std::time::Duration;
+
+// Reputation escrow. A new company's community reputation (endorsements, testimonials,
+// vouches) doesn't count in full straight away: it accrues into escrow and is released
+// linearly over the maturation period from registration, so a burst of vouches can't make
+// a day-old company Trusted. Verification, grants and stake aren't escrowed. Companies
+// older than the period are unaffected. The pending schedule is kept on the company's
+// community validation and refreshed by a timer as points are released.
+
+pub struct ReputationEscrowManager;
+
+impl ReputationEscrowManager {
+    const DEFAULT_MATURATION_DAYS: u32 = 30;
+    const MAX_MATURATION_DAYS: u32 = 365;
+    const RELEASE_INTERVAL_SECS: u64 = 60 * 60;
+    const DAY_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::RELEASE_INTERVAL_SECS), || {
+            Self::release_escrow();
+        });
+    }
+
+    pub fn get_reputation_escrow_policy() -> ReputationEscrowPolicy {
+        StorageManager::get_reputation_escrow_policy().unwrap_or(ReputationEscrowPolicy {
+            maturation_days: Self::DEFAULT_MATURATION_DAYS,
+        })
+    }
+
+    pub fn set_reputation_escrow_policy(
+        policy: ReputationEscrowPolicy,
+        caller_principal: Principal,
+    ) -> RegistryResult<()> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if policy.maturation_days > Self::MAX_MATURATION_DAYS {
+            return RegistryResult::Err(format!(
+                "Maturation period must be at most {} days",
+                Self::MAX_MATURATION_DAYS
+            ));
+        }
+
+        AuditManager::record(
+            "reputation_escrow_policy_set",
+            "reputation_escrow",
+            vec![caller_principal],
+            format!("community reputation matures over {} days", policy.maturation_days),
+        );
+        PolicyLog::record(
+            "reputation_escrow_policy",
+            Some(format!("{:?}", Self::get_reputation_escrow_policy())),
+            format!("{:?}", policy),
+            vec![caller_principal],
+            "set_reputation_escrow_policy",
+        );
+        StorageManager::set_reputation_escrow_policy(policy);
+        // A shorter period can mature companies at once, a longer one re-escrows them
+        Self::release_escrow();
+        RegistryResult::Ok(())
+    }
+
+    // Share of the company's community points (0-1000) still in escrow at `now`
+    pub fn escrowed_permille(company: &Company, now: u64) -> u32 {
+        let period = Self::get_reputation_escrow_policy().maturation_days as u64 * Self::DAY_NS;
+        let age = now.saturating_sub(company.created_at);
+        if age >= period {
+            return 0;
+        }
+        ((period - age) as u128 * 1000 / period as u128) as u32
+    }
+
+    fn escrow(company: &Company, inputs: &ReputationInputs) -> ReputationEscrow {
+        let community_points = inputs.community_points();
+        let escrowed_points = inputs.escrowed_points();
+        let period = Self::get_reputation_escrow_policy().maturation_days as u64 * Self::DAY_NS;
+        ReputationEscrow {
+            community_points,
+            released_points: community_points - escrowed_points,
+            escrowed_points,
+            release_started_at: company.created_at,
+            matures_at: company.created_at.saturating_add(period),
+        }
+    }
+
+    // What goes on the profile: None once nothing is held back
+    pub fn pending_escrow(company: &Company, inputs: &ReputationInputs) -> Option<ReputationEscrow> {
+        let escrow = Self::escrow(company, inputs);
+        (escrow.escrowed_points > 0).then_some(escrow)
+    }
+
+    // Live view, including companies whose reputation has fully matured
+    pub fn get_reputation_escrow(company_id: String) -> RegistryResult<ReputationEscrow> {
+        match StorageManager::get_company(&company_id) {
+            Some(company) => RegistryResult::Ok(Self::escrow(&company, &ReputationInputs::from_company(&company))),
+            None => RegistryResult::Err("Company not found".to_string()),
+        }
+    }
+
+    // Rescores companies whose escrowed points have moved since they were last written;
+    // returns how many were updated
+    pub fn release_escrow() -> u32 {
+        let mut released = 0;
+        for company in StorageManager::get_all_companies() {
+            let pending = Self::pending_escrow(&company, &ReputationInputs::from_company(&company));
+            let stored = company.community_validation.reputation_escrow.as_ref();
+            if pending.as_ref().map(|escrow| escrow.escrowed_points) == stored.map(|escrow| escrow.escrowed_points) {
+                continue;
+            }
+            CommunityValidationManager::recalculate_reputation(&company.id);
+            released += 1;
+        }
+        released
+    }
+}
@@ -0,0 +1,139 @@
+use crate::chain_key::ChainKeySigner;
+use crate::ownership::OwnershipManager;
+use crate::storage::StorageManager;
+use crate::types::{Company, CompanyStatus, EvmAttestation, EvmSigner, RegistryResult};
+use candid::Principal;
+use ic_cdk::api::time;
+use sha3::{Digest, Keccak256};
+
+// Portable attestations for EVM chains. A verified company's owner can have the registry
+// sign its current status and scores with the canister's threshold ECDSA key; the result
+// is checked on-chain without trusting a relayer:
+//
+//   structHash = keccak256(abi.encode(ATTESTATION_TYPEHASH, keccak256(bytes(companyId)),
+//                                     uint8 status, uint32 verificationScore,
+//                                     uint32 reputationScore, uint64 attestedAt, uint64 expiresAt))
+//   ecrecover(toEthSignedMessageHash(structHash), v, r, s) == get_attestation_signer().evm_address
+//
+// Status is 1 for Verified and 2 for Trusted. Attestations expire so a downgraded company
+// can't keep presenting an old one; signing costs cycles, so an unchanged attestation is
+// reused for a day before a new one is signed.
+
+pub struct EvmAttestationManager;
+
+impl EvmAttestationManager {
+    const KEY_PURPOSE: &'static str = "evm_attestations";
+    const ATTESTATION_TYPE: &'static str = "CrossChainRegistryAttestation(string companyId,uint8 status,uint32 verificationScore,uint32 reputationScore,uint64 attestedAt,uint64 expiresAt)";
+    const VALIDITY_SECS: u64 = 30 * 24 * 60 * 60;
+    const REUSE_SECS: u64 = 24 * 60 * 60;
+    const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+    pub async fn get_attestation_signer() -> RegistryResult<EvmSigner> {
+        match ChainKeySigner::evm_signer(Self::KEY_PURPOSE).await {
+            Ok(signer) => RegistryResult::Ok(signer),
+            Err(err) => RegistryResult::Err(err),
+        }
+    }
+
+    pub fn get_evm_attestation(company_id: String) -> RegistryResult<EvmAttestation> {
+        match StorageManager::get_evm_attestation(&company_id) {
+            Some(attestation) => RegistryResult::Ok(attestation),
+            None => RegistryResult::Err("No attestation has been issued for this company".to_string()),
+        }
+    }
+
+    pub async fn request_evm_attestation(
+        company_id: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<EvmAttestation> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+        if !OwnershipManager::is_owner(&company, caller_principal) {
+            return RegistryResult::Err("Unauthorized: Only company owner can request attestations".to_string());
+        }
+        let status_code = match Self::status_code(&company.status) {
+            Some(code) => code,
+            None => return RegistryResult::Err("Only Verified and Trusted companies can be attested".to_string()),
+        };
+
+        let now = time() / Self::NANOS_PER_SEC;
+        if let Some(existing) = StorageManager::get_evm_attestation(&company_id) {
+            if Self::matches_company(&existing, &company) && now < existing.attested_at.saturating_add(Self::REUSE_SECS) {
+                return RegistryResult::Ok(existing);
+            }
+        }
+
+        match Self::sign(&company, status_code, now).await {
+            Ok(attestation) => {
+                StorageManager::insert_evm_attestation(attestation.clone());
+                RegistryResult::Ok(attestation)
+            }
+            Err(err) => RegistryResult::Err(err),
+        }
+    }
+
+    async fn sign(company: &Company, status_code: u8, now: u64) -> Result<EvmAttestation, String> {
+        let signer = ChainKeySigner::evm_signer(Self::KEY_PURPOSE).await?;
+        let expires_at = now.saturating_add(Self::VALIDITY_SECS);
+        let struct_hash = Self::struct_hash(company, status_code, now, expires_at);
+        let digest = Self::eth_signed_message_hash(&struct_hash);
+        let (signature, recovery_id) = ChainKeySigner::sign_evm_digest(Self::KEY_PURPOSE, &digest).await?;
+
+        let mut signature_bytes = signature.to_vec();
+        signature_bytes.push(27 + recovery_id);
+        Ok(EvmAttestation {
+            company_id: company.id.clone(),
+            status: company.status.clone(),
+            verification_score: company.verification_score,
+            reputation_score: company.community_validation.reputation_score,
+            attested_at: now,
+            expires_at,
+            struct_hash: format!("0x{}", hex::encode(struct_hash)),
+            digest: format!("0x{}", hex::encode(digest)),
+            signature: format!("0x{}", hex::encode(signature_bytes)),
+            signer: signer.evm_address,
+        })
+    }
+
+    fn status_code(status: &CompanyStatus) -> Option<u8> {
+        match status {
+            CompanyStatus::Verified => Some(1),
+            CompanyStatus::Trusted => Some(2),
+            _ => None,
+        }
+    }
+
+    fn matches_company(attestation: &EvmAttestation, company: &Company) -> bool {
+        std::mem::discriminant(&attestation.status) == std::mem::discriminant(&company.status)
+            && attestation.verification_score == company.verification_score
+            && attestation.reputation_score == company.community_validation.reputation_score
+    }
+
+    // abi.encode of the typehash and fields, each left-padded to a 32-byte word
+    fn struct_hash(company: &Company, status_code: u8, attested_at: u64, expires_at: u64) -> [u8; 32] {
+        let mut encoded = Vec::with_capacity(7 * 32);
+        encoded.extend_from_slice(&Keccak256::digest(Self::ATTESTATION_TYPE.as_bytes()));
+        encoded.extend_from_slice(&Keccak256::digest(company.id.as_bytes()));
+        for value in [
+            u64::from(status_code),
+            u64::from(company.verification_score),
+            u64::from(company.community_validation.reputation_score),
+            attested_at,
+            expires_at,
+        ] {
+            encoded.extend_from_slice(&[0u8; 24]);
+            encoded.extend_from_slice(&value.to_be_bytes());
+        }
+        Keccak256::digest(&encoded).into()
+    }
+
+    fn eth_signed_message_hash(hash: &[u8; 32]) -> [u8; 32] {
+        Keccak256::new()
+            .chain_update(b"\x19Ethereum Signed Message:\n32")
+            .chain_update(hash)
+            .finalize()
+            .into()
+    }
+}
@@ -0,0 +1,599 @@
+use crate::admins::AdminManager;
+use crate::audit::AuditManager;
+use crate::chain_key::ChainKeySigner;
+use crate::policy::PolicyLog;
+use crate::settings::SettingsManager;
+use crate::storage::StorageManager;
+use crate::types::{
+    ApiProvider, ChainType, CompanyStatus, EvmBridgeConfig, EvmBridgeStatus, EvmMerkleProof, EvmRootLeaf,
+    EvmRootSnapshot, EvmRootSummary, ProviderEndpoint, RegistryResult,
+};
+use candid::Principal;
+use ic_cdk::api::management_canister::http_request::{
+    CanisterHttpRequestArgument, HttpHeader, HttpMethod, HttpResponse, TransformArgs, TransformContext,
+};
+use ic_cdk::api::time;
+use sha3::{Digest, Keccak256};
+use std::time::Duration;
+
+// "Verify once, attest anywhere". Every run snapshots (company id, status, reputation
+// score) for all companies into a Merkle tree that Solidity can check with OpenZeppelin's
+// MerkleProof (keccak256, sorted pairs):
+//
+//   leaf = keccak256(bytes.concat(keccak256(abi.encode(string companyId, uint8 status, uint32 score))))
+//   status: 0 Pending, 1 Verified, 2 Trusted, 3 Flagged, 4 Suspended, 5 Stale
+//
+// When the bridge is enabled, a changed root is sent to the configured contract as
+// publishRoot(bytes32 root, uint64 seq) in an EIP-1559 transaction signed with the
+// canister's threshold ECDSA key, at most once per publish interval. The sending account
+// (see get_evm_bridge_status) has to be funded for gas. A submission is tracked until it
+// is mined; one that is still unmined after an hour is dropped and sent again.
+//
+// Proofs are served against the last root confirmed on-chain, or against the latest
+// snapshot while nothing has been published.
+
+pub struct EvmBridgeManager;
+
+impl EvmBridgeManager {
+    const KEY_PURPOSE: &'static str = "evm_bridge";
+    const PUBLISH_ROOT_SIGNATURE: &'static str = "publishRoot(bytes32,uint64)";
+    const RUN_INTERVAL_SECS: u64 = 15 * 60;
+    const DEFAULT_PUBLISH_INTERVAL_MINS: u32 = 60;
+    const MIN_PUBLISH_INTERVAL_MINS: u32 = 15;
+    const MAX_PUBLISH_INTERVAL_MINS: u32 = 7 * 24 * 60;
+    const DEFAULT_GAS_LIMIT: u64 = 100_000;
+    const MIN_GAS_LIMIT: u64 = 21_000;
+    const MAX_GAS_LIMIT: u64 = 1_000_000;
+    const DEFAULT_MAX_FEE_PER_GAS: u64 = 50_000_000_000; // 50 gwei
+    const DEFAULT_MAX_PRIORITY_FEE_PER_GAS: u64 = 1_000_000_000; // 1 gwei
+    const GAS_PRICE_ROUNDING: u64 = 1_000_000_000; // Replicas must agree on the quote
+    const PENDING_TIMEOUT_NS: u64 = 60 * 60 * 1_000_000_000;
+    const MINUTE_NS: u64 = 60 * 1_000_000_000;
+
+    const LATEST: &'static str = "latest";
+    const PENDING: &'static str = "pending";
+    const PUBLISHED: &'static str = "published";
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::RUN_INTERVAL_SECS), || {
+            ic_cdk::spawn(Self::run_evm_bridge());
+        });
+    }
+
+    // Configuration
+
+    pub fn get_evm_bridge_config() -> EvmBridgeConfig {
+        StorageManager::get_evm_bridge_config().unwrap_or(EvmBridgeConfig {
+            enabled: false,
+            chain_type: ChainType::Ethereum,
+            chain_id: 1,
+            contract_address: None,
+            publish_interval_mins: Self::DEFAULT_PUBLISH_INTERVAL_MINS,
+            gas_limit: Self::DEFAULT_GAS_LIMIT,
+            max_fee_per_gas: Self::DEFAULT_MAX_FEE_PER_GAS,
+            max_priority_fee_per_gas: Self::DEFAULT_MAX_PRIORITY_FEE_PER_GAS,
+        })
+    }
+
+    pub fn set_evm_bridge_config(mut config: EvmBridgeConfig, caller_principal: Principal) -> RegistryResult<()> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        config.contract_address = config.contract_address.map(|address| address.trim().to_lowercase());
+        if let Err(err) = Self::validate_evm_bridge_config(&config) {
+            return RegistryResult::Err(err);
+        }
+
+        AuditManager::record(
+            "evm_bridge_config_set",
+            "evm_bridge",
+            vec![caller_principal],
+            format!(
+                "{} on chain {} to {}",
+                if config.enabled { "enabled" } else { "disabled" },
+                config.chain_id,
+                config.contract_address.as_deref().unwrap_or("no contract")
+            ),
+        );
+        PolicyLog::record(
+            "evm_bridge_config",
+            Some(format!("{:?}", Self::get_evm_bridge_config())),
+            format!("{:?}", config),
+            vec![caller_principal],
+            "set_evm_bridge_config",
+        );
+        StorageManager::set_evm_bridge_config(config);
+        RegistryResult::Ok(())
+    }
+
+    fn validate_evm_bridge_config(config: &EvmBridgeConfig) -> Result<(), String> {
+        Self::rpc_provider(&config.chain_type)?;
+        if config.chain_id == 0 {
+            return Err("Chain id is required".to_string());
+        }
+        match &config.contract_address {
+            Some(address) if !Self::is_evm_address(address) => {
+                return Err("Contract address must be 0x followed by 40 hex characters".to_string());
+            }
+            None if config.enabled => return Err("Set a contract address before enabling the bridge".to_string()),
+            _ => {}
+        }
+        if config.publish_interval_mins < Self::MIN_PUBLISH_INTERVAL_MINS
+            || config.publish_interval_mins > Self::MAX_PUBLISH_INTERVAL_MINS
+        {
+            return Err(format!(
+                "Publish interval must be {}-{} minutes",
+                Self::MIN_PUBLISH_INTERVAL_MINS,
+                Self::MAX_PUBLISH_INTERVAL_MINS
+            ));
+        }
+        if config.gas_limit < Self::MIN_GAS_LIMIT || config.gas_limit > Self::MAX_GAS_LIMIT {
+            return Err(format!("Gas limit must be {}-{}", Self::MIN_GAS_LIMIT, Self::MAX_GAS_LIMIT));
+        }
+        if config.max_priority_fee_per_gas > config.max_fee_per_gas {
+            return Err("Priority fee can't exceed the maximum fee per gas".to_string());
+        }
+        Ok(())
+    }
+
+    fn rpc_provider(chain_type: &ChainType) -> Result<ApiProvider, String> {
+        match chain_type {
+            ChainType::Ethereum => Ok(ApiProvider::EthereumRpc),
+            ChainType::Polygon => Ok(ApiProvider::PolygonRpc),
+            _ => Err("The bridge can only publish to Ethereum or Polygon".to_string()),
+        }
+    }
+
+    fn is_evm_address(address: &str) -> bool {
+        address.len() == 42 && address.starts_with("0x") && address[2..].chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    // Status and proofs
+
+    pub fn get_evm_bridge_status() -> EvmBridgeStatus {
+        let summary = |slot: &str| StorageManager::get_evm_root_snapshot(slot).map(|snapshot| Self::summary(&snapshot));
+        EvmBridgeStatus {
+            config: Self::get_evm_bridge_config(),
+            sender: ChainKeySigner::cached_evm_signer(Self::KEY_PURPOSE).map(|signer| signer.evm_address),
+            latest: summary(Self::LATEST),
+            pending: summary(Self::PENDING),
+            published: summary(Self::PUBLISHED),
+            last_error: StorageManager::get_evm_bridge_last_error(),
+        }
+    }
+
+    fn summary(snapshot: &EvmRootSnapshot) -> EvmRootSummary {
+        EvmRootSummary {
+            seq: snapshot.seq,
+            root: snapshot.root.clone(),
+            built_at: snapshot.built_at,
+            company_count: snapshot.leaves.len() as u32,
+            tx_hash: snapshot.tx_hash.clone(),
+            submitted_at: snapshot.submitted_at,
+            confirmed_at: snapshot.confirmed_at,
+        }
+    }
+
+    pub fn get_evm_merkle_proof(company_id: String) -> RegistryResult<EvmMerkleProof> {
+        let snapshot = match StorageManager::get_evm_root_snapshot(Self::PUBLISHED)
+            .or_else(|| StorageManager::get_evm_root_snapshot(Self::LATEST))
+        {
+            Some(snapshot) => snapshot,
+            None => return RegistryResult::Err("No root snapshot yet; try again shortly".to_string()),
+        };
+        let index = match snapshot
+            .leaves
+            .binary_search_by(|leaf| leaf.company_id.as_str().cmp(company_id.as_str()))
+        {
+            Ok(index) => index,
+            Err(_) => {
+                return RegistryResult::Err(format!(
+                    "Company is not in root {}; it will be included once a newer root is published",
+                    snapshot.seq
+                ))
+            }
+        };
+
+        let levels = Self::tree_levels(&snapshot.leaves);
+        let mut proof = Vec::new();
+        let mut position = index;
+        for level in &levels[..levels.len() - 1] {
+            let sibling = if position.is_multiple_of(2) {
+                level.get(position + 1).unwrap_or(&level[position])
+            } else {
+                &level[position - 1]
+            };
+            proof.push(format!("0x{}", hex::encode(sibling)));
+            position /= 2;
+        }
+
+        let leaf = &snapshot.leaves[index];
+        RegistryResult::Ok(EvmMerkleProof {
+            company_id,
+            status: leaf.status.clone(),
+            status_code: Self::status_code(&leaf.status),
+            reputation_score: leaf.reputation_score,
+            leaf: format!("0x{}", hex::encode(levels[0][index])),
+            proof,
+            root: snapshot.root,
+            seq: snapshot.seq,
+            tx_hash: snapshot.confirmed_at.and(snapshot.tx_hash),
+        })
+    }
+
+    // Merkle tree
+
+    fn status_code(status: &CompanyStatus) -> u8 {
+        match status {
+            CompanyStatus::Pending => 0,
+            CompanyStatus::Verified => 1,
+            CompanyStatus::Trusted => 2,
+            CompanyStatus::Flagged => 3,
+            CompanyStatus::Suspended => 4,
+            CompanyStatus::Stale => 5,
+        }
+    }
+
+    // Double-hashed abi.encode(string, uint8, uint32), as OpenZeppelin's StandardMerkleTree
+    fn leaf_hash(leaf: &EvmRootLeaf) -> [u8; 32] {
+        let id = leaf.company_id.as_bytes();
+        let mut encoded = Vec::with_capacity(4 * 32 + id.len().div_ceil(32) * 32);
+        encoded.extend_from_slice(&Self::word(3 * 32)); // Offset of the string's tail
+        encoded.extend_from_slice(&Self::word(u64::from(Self::status_code(&leaf.status))));
+        encoded.extend_from_slice(&Self::word(u64::from(leaf.reputation_score)));
+        encoded.extend_from_slice(&Self::word(id.len() as u64));
+        encoded.extend_from_slice(id);
+        encoded.resize(encoded.len().div_ceil(32) * 32, 0);
+        Keccak256::digest(Keccak256::digest(&encoded)).into()
+    }
+
+    fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        let (low, high) = if a <= b { (a, b) } else { (b, a) };
+        Keccak256::new().chain_update(low).chain_update(high).finalize().into()
+    }
+
+    // Leaf hashes first, root last; an odd node out is paired with itself
+    fn tree_levels(leaves: &[EvmRootLeaf]) -> Vec<Vec<[u8; 32]>> {
+        let mut levels = vec![leaves.iter().map(Self::leaf_hash).collect::<Vec<[u8; 32]>>()];
+        while levels[levels.len() - 1].len() > 1 {
+            let next = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| Self::node_hash(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+                .collect();
+            levels.push(next);
+        }
+        levels
+    }
+
+    fn word(value: u64) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[24..].copy_from_slice(&value.to_be_bytes());
+        word
+    }
+
+    // Stores a new latest snapshot when the companies' leaves changed; None while the
+    // registry is empty
+    fn refresh_snapshot() -> Option<EvmRootSnapshot> {
+        let mut leaves: Vec<EvmRootLeaf> = StorageManager::get_all_companies()
+            .into_iter()
+            .map(|company| EvmRootLeaf {
+                company_id: company.id,
+                status: company.status,
+                reputation_score: company.community_validation.reputation_score,
+            })
+            .collect();
+        if leaves.is_empty() {
+            return None;
+        }
+        leaves.sort_by(|a, b| a.company_id.cmp(&b.company_id));
+
+        let levels = Self::tree_levels(&leaves);
+        let root = format!("0x{}", hex::encode(levels[levels.len() - 1][0]));
+        let previous = StorageManager::get_evm_root_snapshot(Self::LATEST);
+        if let Some(previous) = previous.as_ref().filter(|previous| previous.root == root) {
+            return Some(previous.clone());
+        }
+        let snapshot = EvmRootSnapshot {
+            seq: previous.map_or(1, |previous| previous.seq + 1),
+            root,
+            built_at: time(),
+            leaves,
+            tx_hash: None,
+            submitted_at: None,
+            confirmed_at: None,
+        };
+        StorageManager::set_evm_root_snapshot(Self::LATEST, snapshot.clone());
+        Some(snapshot)
+    }
+
+    // Publishing
+
+    pub async fn trigger_evm_bridge(caller_principal: Principal) -> RegistryResult<EvmBridgeStatus> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        Self::run_evm_bridge().await;
+        RegistryResult::Ok(Self::get_evm_bridge_status())
+    }
+
+    pub async fn run_evm_bridge() {
+        if !StorageManager::try_begin_evm_bridge_run() {
+            return;
+        }
+        let result = Self::run_once().await;
+        StorageManager::end_evm_bridge_run(result.err());
+    }
+
+    async fn run_once() -> Result<(), String> {
+        let config = Self::get_evm_bridge_config();
+        let latest = Self::refresh_snapshot();
+        if !config.enabled {
+            return Ok(());
+        }
+        let contract = config.contract_address.clone().ok_or("No bridge contract is configured")?;
+        let provider = Self::rpc_provider(&config.chain_type)?;
+
+        if let Some(mut pending) = StorageManager::get_evm_root_snapshot(Self::PENDING) {
+            let tx_hash = pending.tx_hash.clone().unwrap_or_default();
+            match Self::fetch_receipt(&provider, &tx_hash).await? {
+                Some(true) => {
+                    pending.confirmed_at = Some(time());
+                    StorageManager::set_evm_root_snapshot(Self::PUBLISHED, pending);
+                    StorageManager::remove_evm_root_snapshot(Self::PENDING);
+                }
+                Some(false) => {
+                    StorageManager::remove_evm_root_snapshot(Self::PENDING);
+                    return Err(format!("publishRoot transaction {} reverted", tx_hash));
+                }
+                None if time().saturating_sub(pending.submitted_at.unwrap_or(0)) < Self::PENDING_TIMEOUT_NS => {
+                    return Ok(());
+                }
+                // Dropped or priced out; the root goes out again below
+                None => StorageManager::remove_evm_root_snapshot(Self::PENDING),
+            }
+        }
+
+        let latest = match latest {
+            Some(latest) => latest,
+            None => return Ok(()),
+        };
+        let published = StorageManager::get_evm_root_snapshot(Self::PUBLISHED);
+        if published.as_ref().is_some_and(|published| published.root == latest.root) {
+            return Ok(());
+        }
+        let last_submitted = published.and_then(|published| published.submitted_at).unwrap_or(0);
+        let interval = config.publish_interval_mins as u64 * Self::MINUTE_NS;
+        if time() < last_submitted.saturating_add(interval) {
+            return Ok(());
+        }
+
+        let tx_hash = Self::publish_root(&config, &contract, &provider, &latest).await?;
+        let mut pending = latest;
+        pending.tx_hash = Some(tx_hash);
+        pending.submitted_at = Some(time());
+        StorageManager::set_evm_root_snapshot(Self::PENDING, pending);
+        Ok(())
+    }
+
+    // Signs and sends publishRoot; returns the transaction hash
+    async fn publish_root(
+        config: &EvmBridgeConfig,
+        contract: &str,
+        provider: &ApiProvider,
+        snapshot: &EvmRootSnapshot,
+    ) -> Result<String, String> {
+        let signer = ChainKeySigner::evm_signer(Self::KEY_PURPOSE).await?;
+        let nonce_params = serde_json::json!([signer.evm_address, "pending"]);
+        let nonce = Self::fetch_quantity(provider, "eth_getTransactionCount", nonce_params, 1).await?;
+        let gas_price =
+            Self::fetch_quantity(provider, "eth_gasPrice", serde_json::json!([]), Self::GAS_PRICE_ROUNDING).await?;
+        if gas_price > config.max_fee_per_gas {
+            return Err(format!(
+                "Gas price {} wei is above the {} wei cap; publishing later",
+                gas_price, config.max_fee_per_gas
+            ));
+        }
+        // Headroom for the base fee to rise while the transaction waits
+        let max_fee_per_gas = gas_price.saturating_mul(2).min(config.max_fee_per_gas);
+        let max_priority_fee_per_gas = config.max_priority_fee_per_gas.min(max_fee_per_gas);
+
+        let root = hex::decode(&snapshot.root[2..]).map_err(|_| "Invalid snapshot root".to_string())?;
+        let mut data = Keccak256::digest(Self::PUBLISH_ROOT_SIGNATURE.as_bytes())[..4].to_vec();
+        data.extend_from_slice(&root);
+        data.extend_from_slice(&Self::word(snapshot.seq));
+        let to = hex::decode(&contract[2..]).map_err(|_| "Invalid contract address".to_string())?;
+
+        // EIP-1559: 0x02 || rlp([chainId, nonce, maxPriorityFee, maxFee, gas, to, value, data, accessList, ...sig])
+        let mut fields = vec![
+            Self::rlp_uint(config.chain_id),
+            Self::rlp_uint(nonce),
+            Self::rlp_uint(max_priority_fee_per_gas),
+            Self::rlp_uint(max_fee_per_gas),
+            Self::rlp_uint(config.gas_limit),
+            Self::rlp_bytes(&to),
+            Self::rlp_uint(0),
+            Self::rlp_bytes(&data),
+            Self::rlp_list(&[]),
+        ];
+        let mut unsigned = vec![0x02];
+        unsigned.extend(Self::rlp_list(&fields));
+        let digest: [u8; 32] = Keccak256::digest(&unsigned).into();
+        let (signature, recovery_id) = ChainKeySigner::sign_evm_digest(Self::KEY_PURPOSE, &digest).await?;
+
+        fields.push(Self::rlp_uint(u64::from(recovery_id)));
+        fields.push(Self::rlp_bytes(Self::trim_leading_zeros(&signature[..32])));
+        fields.push(Self::rlp_bytes(Self::trim_leading_zeros(&signature[32..])));
+        let mut raw = vec![0x02];
+        raw.extend(Self::rlp_list(&fields));
+        let tx_hash = format!("0x{}", hex::encode(Keccak256::digest(&raw)));
+
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_sendRawTransaction",
+            "params": [format!("0x{}", hex::encode(&raw))],
+        });
+        let response = Self::rpc_call(provider, body, "transform_evm_send", vec![], 4_096).await?;
+        match String::from_utf8_lossy(&response.body).as_ref() {
+            "accepted" => Ok(tx_hash),
+            rejected => Err(format!("eth_sendRawTransaction failed: {}", rejected)),
+        }
+    }
+
+    // Some(success) once mined, None while unknown or pending
+    async fn fetch_receipt(provider: &ApiProvider, tx_hash: &str) -> Result<Option<bool>, String> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_getTransactionReceipt",
+            "params": [tx_hash],
+        });
+        let response = Self::rpc_call(provider, body, "transform_evm_receipt", vec![], 65_536).await?;
+        serde_json::from_slice::<Option<bool>>(&response.body)
+            .map_err(|_| "Failed to parse eth_getTransactionReceipt response".to_string())
+    }
+
+    // A hex quantity, rounded up to `rounding` so every replica sees the same value
+    async fn fetch_quantity(
+        provider: &ApiProvider,
+        method: &str,
+        params: serde_json::Value,
+        rounding: u64,
+    ) -> Result<u64, String> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let context = rounding.to_string().into_bytes();
+        let response = Self::rpc_call(provider, body, "transform_evm_quantity", context, 4_096).await?;
+        String::from_utf8_lossy(&response.body)
+            .parse::<u64>()
+            .map_err(|_| format!("Failed to parse {} response", method))
+    }
+
+    async fn rpc_call(
+        provider: &ApiProvider,
+        body: serde_json::Value,
+        transform: &str,
+        context: Vec<u8>,
+        max_response_bytes: u64,
+    ) -> Result<HttpResponse, String> {
+        let body = body.to_string().into_bytes();
+        let build_request = |endpoint: &ProviderEndpoint| CanisterHttpRequestArgument {
+            url: endpoint.url_template.clone(),
+            method: HttpMethod::POST,
+            body: Some(body.clone()),
+            max_response_bytes: Some(max_response_bytes),
+            transform: Some(TransformContext::from_name(transform.to_string(), context.clone())),
+            headers: vec![
+                HttpHeader {
+                    name: "Content-Type".to_string(),
+                    value: "application/json".to_string(),
+                },
+                HttpHeader {
+                    name: "User-Agent".to_string(),
+                    value: "ICP-CrossChainRegistry-Bridge/1.0".to_string(),
+                },
+            ],
+        };
+
+        let (_, response) =
+            SettingsManager::http_request_with_failover(provider.clone(), build_request, 15_000_000_000).await?;
+        if response.status != 200u32 {
+            return Err(format!("RPC request failed with HTTP status {}", response.status));
+        }
+        Ok(response)
+    }
+
+    // RLP encoding, enough for a transaction
+
+    fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+        if bytes.len() == 1 && bytes[0] < 0x80 {
+            return bytes.to_vec();
+        }
+        let mut encoded = Self::rlp_length(bytes.len(), 0x80);
+        encoded.extend_from_slice(bytes);
+        encoded
+    }
+
+    fn rlp_uint(value: u64) -> Vec<u8> {
+        Self::rlp_bytes(Self::trim_leading_zeros(&value.to_be_bytes()))
+    }
+
+    fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+        let payload = items.concat();
+        let mut encoded = Self::rlp_length(payload.len(), 0xc0);
+        encoded.extend(payload);
+        encoded
+    }
+
+    fn rlp_length(length: usize, offset: u8) -> Vec<u8> {
+        if length <= 55 {
+            return vec![offset + length as u8];
+        }
+        let length_bytes = Self::trim_leading_zeros(&(length as u64).to_be_bytes()).to_vec();
+        let mut encoded = vec![offset + 55 + length_bytes.len() as u8];
+        encoded.extend(length_bytes);
+        encoded
+    }
+
+    fn trim_leading_zeros(bytes: &[u8]) -> &[u8] {
+        let start = bytes.iter().position(|byte| *byte != 0).unwrap_or(bytes.len());
+        &bytes[start..]
+    }
+}
+
+// HTTP transform functions for the bridge's RPC calls
+
+// Reduce a hex quantity result to a decimal, rounded up to the multiple in the context
+pub fn transform_evm_quantity(raw: TransformArgs) -> HttpResponse {
+    let rounding = String::from_utf8_lossy(&raw.context).parse::<u64>().unwrap_or(1).max(1);
+    let value = serde_json::from_slice::<serde_json::Value>(&raw.response.body)
+        .ok()
+        .and_then(|json| json["result"].as_str().map(|hex| hex.trim_start_matches("0x").to_string()))
+        .and_then(|hex| u64::from_str_radix(&hex, 16).ok())
+        .map(|value| value.div_ceil(rounding).saturating_mul(rounding));
+
+    HttpResponse {
+        status: raw.response.status.clone(),
+        body: value.map(|value| value.to_string()).unwrap_or_default().into_bytes(),
+        headers: vec![],
+    }
+}
+
+// Replicas after the first see "already known"; that counts as accepted too
+pub fn transform_evm_send(raw: TransformArgs) -> HttpResponse {
+    let outcome = match serde_json::from_slice::<serde_json::Value>(&raw.response.body) {
+        Ok(json) if json["result"].is_string() => "accepted".to_string(),
+        Ok(json) => {
+            let message = json["error"]["message"].as_str().unwrap_or("invalid response").to_lowercase();
+            if message.contains("already known") || message.contains("known transaction") {
+                "accepted".to_string()
+            } else {
+                message.chars().take(200).collect()
+            }
+        }
+        Err(_) => "invalid response".to_string(),
+    };
+
+    HttpResponse {
+        status: raw.response.status.clone(),
+        body: outcome.into_bytes(),
+        headers: vec![],
+    }
+}
+
+// Reduce a receipt to whether the transaction succeeded; null while it isn't mined
+pub fn transform_evm_receipt(raw: TransformArgs) -> HttpResponse {
+    let success = serde_json::from_slice::<serde_json::Value>(&raw.response.body)
+        .ok()
+        .and_then(|json| json["result"]["status"].as_str().map(|status| status == "0x1"));
+
+    HttpResponse {
+        status: raw.response.status.clone(),
+        body: serde_json::to_vec(&success).unwrap_or_default(),
+        headers: vec![],
+    }
+}
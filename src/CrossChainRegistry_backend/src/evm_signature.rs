@@ -0,0 +1,121 @@
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use sha3::{Digest, Keccak256};
+
+// EIP-191 personal_sign recovery, what wallets produce for "Sign message": the signer's
+// address is recovered from the 65-byte r || s || v signature with ecrecover, so the
+// canister can check it without an outcall.
+
+pub struct EvmSignatureVerifier;
+
+impl EvmSignatureVerifier {
+    // Lowercased 0x address of the account that personal_signed `message`
+    pub fn recover_personal_sign(message: &str, signature: &str) -> Result<String, String> {
+        let bytes = hex::decode(signature.trim().trim_start_matches("0x"))
+            .map_err(|_| "Signature must be hex".to_string())?;
+        if bytes.len() != 65 {
+            return Err("Signature must be 65 bytes (r || s || v)".to_string());
+        }
+
+        // v is 27/28 from most wallets, 0/1 from some hardware wallets and libraries
+        let v = match bytes[64] {
+            27 | 28 => bytes[64] - 27,
+            0 | 1 => bytes[64],
+            _ => return Err("Signature has an invalid recovery byte".to_string()),
+        };
+        let mut recovery_id = RecoveryId::from_byte(v).ok_or("Signature has an invalid recovery byte")?;
+        let mut signature = Signature::from_slice(&bytes[..64]).map_err(|_| "Invalid signature".to_string())?;
+        // k256 only accepts low-S; flipping S mirrors R, so the parity flips too
+        if let Some(normalized) = signature.normalize_s() {
+            signature = normalized;
+            recovery_id = RecoveryId::new(!recovery_id.is_y_odd(), recovery_id.is_x_reduced());
+        }
+
+        let prefixed = format!("\x19Ethereum Signed Message:\n{}{}", message.len(), message);
+        let digest = Keccak256::digest(prefixed.as_bytes());
+        let key = VerifyingKey::recover_from_prehash(&digest, &signature, recovery_id)
+            .map_err(|_| "Signature does not match the message".to_string())?;
+
+        let public_key = key.to_encoded_point(false);
+        let hash = Keccak256::digest(&public_key.as_bytes()[1..]);
+        Ok(format!("0x{}", hex::encode(&hash[12..])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ethers.js Wallet docs key 0x0123...0123 signing "Hello World" (v = 28). Computed outside
+    // k256 with RFC 6979, as Wallet.signMessage does; the same code reproduces the viem vector.
+    const ETHERS_ADDRESS: &str = "0x14791697260e4c9a71f18484c9f997b308e59325";
+    const ETHERS_SIGNATURE: &str = "0xe0ed34fbbe927a58267ce2e8067a611c69869e20e731bc99187a8bc97058664c16de07f7660f06ce0985d1d8e063726783033fda59b307897f26a21392d62b3a1c";
+
+    // viem signMessage docs: the first Anvil account signing "hello world" (v = 27)
+    const VIEM_ADDRESS: &str = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+    const VIEM_SIGNATURE: &str = "0xa461f509887bd19e312c0c58467ce8ff8e300d3c1a90b608a760c5b80318eaf15fe57c96f9175d6cd4daad4663763baa7e78836e067d0163e9a2ccf2ff753f5b1b";
+
+    fn with_v(signature: &str, v: u8) -> String {
+        format!("{}{:02x}", &signature[..signature.len() - 2], v)
+    }
+
+    #[test]
+    fn recovers_known_signers_with_v_27_and_28() {
+        assert_eq!(
+            EvmSignatureVerifier::recover_personal_sign("Hello World", ETHERS_SIGNATURE),
+            Ok(ETHERS_ADDRESS.to_string())
+        );
+        assert_eq!(
+            EvmSignatureVerifier::recover_personal_sign("hello world", VIEM_SIGNATURE),
+            Ok(VIEM_ADDRESS.to_string())
+        );
+    }
+
+    #[test]
+    fn recovers_known_signers_with_v_0_and_1() {
+        assert_eq!(
+            EvmSignatureVerifier::recover_personal_sign("Hello World", &with_v(ETHERS_SIGNATURE, 1)),
+            Ok(ETHERS_ADDRESS.to_string())
+        );
+        assert_eq!(
+            EvmSignatureVerifier::recover_personal_sign("hello world", &with_v(VIEM_SIGNATURE, 0)),
+            Ok(VIEM_ADDRESS.to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_recovery_bytes_outside_0_1_27_28() {
+        for v in [2, 26, 29, 35, 37, 255] {
+            assert_eq!(
+                EvmSignatureVerifier::recover_personal_sign("Hello World", &with_v(ETHERS_SIGNATURE, v)),
+                Err("Signature has an invalid recovery byte".to_string()),
+                "v = {}",
+                v
+            );
+        }
+    }
+
+    #[test]
+    fn wrong_message_or_parity_recovers_a_different_address() {
+        let tampered = EvmSignatureVerifier::recover_personal_sign("Hello World!", ETHERS_SIGNATURE);
+        assert!(tampered.map_or(true, |signer| signer != ETHERS_ADDRESS));
+        let flipped = EvmSignatureVerifier::recover_personal_sign("Hello World", &with_v(ETHERS_SIGNATURE, 27));
+        assert!(flipped.map_or(true, |signer| signer != ETHERS_ADDRESS));
+        // A valid signature from one account never matches another
+        assert_ne!(
+            EvmSignatureVerifier::recover_personal_sign("hello world", VIEM_SIGNATURE),
+            Ok(ETHERS_ADDRESS.to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_signatures() {
+        assert_eq!(
+            EvmSignatureVerifier::recover_personal_sign("Hello World", "0xnothex"),
+            Err("Signature must be hex".to_string())
+        );
+        assert_eq!(
+            EvmSignatureVerifier::recover_personal_sign("Hello World", &ETHERS_SIGNATURE[..130]),
+            Err("Signature must be 65 bytes (r || s || v)".to_string())
+        );
+    }
+}
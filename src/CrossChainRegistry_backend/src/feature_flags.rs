@@ -0,0 +1,124 @@
+use crate::audit::AuditManager;
+use crate::settings::SettingsManager;
+use crate::storage::StorageManager;
+use crate::types::{FeatureFlag, RegistryResult};
+use candid::Principal;
+use ic_cdk::api::time;
+use sha2::{Digest, Sha256};
+
+// Switches for rolling subsystems out gradually. Each flag is on or off, and when on
+// reaches `rollout_percent` of principals. A principal's bucket comes from hashing it
+// together with the flag name, so it is stable across calls and upgrades but independent
+// between flags. Only flags declared in KNOWN_FLAGS exist; their defaults apply until an
+// admin sets them.
+
+pub const CHAIN_SOLANA: &str = "chain_solana";
+pub const CHAIN_TON: &str = "chain_ton";
+pub const REVIEWER_STAKING: &str = "reviewer_staking";
+
+pub struct FeatureFlagManager;
+
+impl FeatureFlagManager {
+    // (name, description, enabled by default)
+    const KNOWN_FLAGS: [(&'static str, &'static str, bool); 3] = [
+        (CHAIN_SOLANA, "Solana address challenges and verification", true),
+        (CHAIN_TON, "TON address challenges and verification", true),
+        (REVIEWER_STAKING, "Joining the staked reviewer pool", true),
+    ];
+
+    pub fn set_feature_flag(
+        name: String,
+        enabled: bool,
+        rollout_percent: u8,
+        caller_principal: Principal,
+    ) -> RegistryResult<FeatureFlag> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if rollout_percent > 100 {
+            return RegistryResult::Err("Rollout percent must be between 0 and 100".to_string());
+        }
+        let mut flag = match Self::flag(&name) {
+            Some(flag) => flag,
+            None => return RegistryResult::Err(format!("Unknown feature flag '{}'", name)),
+        };
+        flag.enabled = enabled;
+        flag.rollout_percent = rollout_percent;
+        flag.updated_at = time();
+        flag.updated_by = Some(caller_principal);
+        StorageManager::insert_feature_flag(flag.clone());
+        AuditManager::record(
+            "feature_flag_set",
+            &flag.name,
+            vec![caller_principal],
+            format!("enabled: {}, rollout: {}%", enabled, rollout_percent),
+        );
+        RegistryResult::Ok(flag)
+    }
+
+    // Drops the admin setting so the built-in default applies again
+    pub fn reset_feature_flag(name: String, caller_principal: Principal) -> RegistryResult<FeatureFlag> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if !StorageManager::remove_feature_flag(&name) {
+            return RegistryResult::Err(format!("Feature flag '{}' is not set", name));
+        }
+        AuditManager::record("feature_flag_reset", &name, vec![caller_principal], String::new());
+        match Self::flag(&name) {
+            Some(flag) => RegistryResult::Ok(flag),
+            None => RegistryResult::Err(format!("Unknown feature flag '{}'", name)),
+        }
+    }
+
+    pub fn list_feature_flags() -> Vec<FeatureFlag> {
+        Self::KNOWN_FLAGS
+            .iter()
+            .filter_map(|(name, _, _)| Self::flag(name))
+            .collect()
+    }
+
+    // Flags that reach at least some principals
+    pub fn list_active_feature_flags() -> Vec<FeatureFlag> {
+        Self::list_feature_flags()
+            .into_iter()
+            .filter(|flag| flag.enabled && flag.rollout_percent > 0)
+            .collect()
+    }
+
+    pub fn is_enabled(name: &str, principal: Principal) -> bool {
+        match Self::flag(name) {
+            Some(flag) => flag.enabled && Self::bucket(name, principal) < flag.rollout_percent,
+            None => false,
+        }
+    }
+
+    pub fn ensure_enabled(name: &str, principal: Principal) -> Result<(), String> {
+        if Self::is_enabled(name, principal) {
+            Ok(())
+        } else {
+            Err(format!("Feature '{}' is not available yet", name))
+        }
+    }
+
+    fn flag(name: &str) -> Option<FeatureFlag> {
+        let (name, description, enabled) = Self::KNOWN_FLAGS.iter().find(|(known, _, _)| *known == name)?;
+        Some(StorageManager::get_feature_flag(name).unwrap_or(FeatureFlag {
+            name: name.to_string(),
+            description: description.to_string(),
+            enabled: *enabled,
+            rollout_percent: 100,
+            updated_at: 0,
+            updated_by: None,
+        }))
+    }
+
+    // 0-99
+    fn bucket(name: &str, principal: Principal) -> u8 {
+        let mut hasher = Sha256::new();
+        hasher.update(name.as_bytes());
+        hasher.update(principal.as_slice());
+        let digest = hasher.finalize();
+        (u16::from_be_bytes([digest[0], digest[1]]) % 100) as u8
+    }
+}
@@ -0,0 +1,434 @@
+use crate::admins::AdminManager;
+use crate::api::RegistryAPI;
+use crate::audit::AuditManager;
+use crate::community::CommunityValidationManager;
+use crate::partners::PartnerManager;
+use crate::sanitize::{self, SafeText};
+use crate::storage::StorageManager;
+use crate::types::{
+    FederatedAttestation, FederatedSearchResult, FederationExport, FederationPeer,
+    ListingAttestation, Provenance, RegistryResult, RegistrySearchPage,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+// Federation with registries run by other ecosystems. Admins register peer registry
+// canisters; each side serves its certified listing attestations (the same leaves partners
+// get from get_listing_attestation) a page at a time through export_attestations, and
+// pulls its peers' pages on a timer. An imported attestation is kept only if it hashes up
+// its Merkle path to the root of the page it came in, and the page came back from the
+// peer canister itself, so what is stored is what the peer certified. Only a registry's
+// own companies are exported, so attestations never travel more than one hop.
+//
+// Federated results are never merged into local profiles: they are shown next to local
+// ones, labelled with the peer they came from, and only when a query asks for them.
+
+pub struct FederationManager;
+
+impl FederationManager {
+    const SYNC_INTERVAL_SECS: u64 = 60 * 60;
+    const MAX_PEERS: usize = 20;
+    const MAX_EXPORT_PAGE: u32 = 200;
+    const MAX_PAGES_PER_SYNC: usize = 10;
+    const MAX_ATTESTATIONS_PER_PEER: usize = 50_000;
+    const MAX_COMPANY_ID_LENGTH: usize = 128;
+    const MAX_ERROR_LENGTH: usize = 200;
+    const MAX_SEARCH_SCAN: usize = 2_000;
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::SYNC_INTERVAL_SECS), || {
+            for peer in StorageManager::get_all_federation_peers() {
+                ic_cdk::spawn(async move {
+                    let _ = Self::sync_peer(peer.canister_id).await;
+                });
+            }
+        });
+    }
+
+    // Peer roster (registry admins)
+
+    pub fn add_federation_peer(
+        canister_id: Principal,
+        name: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<FederationPeer> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if canister_id == Principal::anonymous() || canister_id == ic_cdk::id() {
+            return RegistryResult::Err("Peer must be another registry canister".to_string());
+        }
+        if StorageManager::get_federation_peer(&canister_id).is_some() {
+            return RegistryResult::Err("Canister is already a federation peer".to_string());
+        }
+        if StorageManager::get_all_federation_peers().len() >= Self::MAX_PEERS {
+            return RegistryResult::Err(format!("At most {} federation peers", Self::MAX_PEERS));
+        }
+        let name = match SafeText::parse_required(&name, "Peer name", sanitize::policy().max_name) {
+            Ok(text) => text.into_inner(),
+            Err(err) => return RegistryResult::Err(err),
+        };
+
+        let peer = FederationPeer {
+            canister_id,
+            name,
+            added_by: caller_principal,
+            added_at: time(),
+            sync_cursor: None,
+            pass_started_at: None,
+            last_synced_at: None,
+            imported_count: 0,
+            consecutive_failures: 0,
+            last_error: None,
+        };
+        StorageManager::insert_federation_peer(peer.clone());
+        AuditManager::record(
+            "federation_peer_added",
+            &canister_id.to_text(),
+            vec![caller_principal],
+            peer.name.clone(),
+        );
+        RegistryResult::Ok(peer)
+    }
+
+    // Returns the number of imported attestations dropped with the peer
+    pub fn remove_federation_peer(canister_id: Principal, caller_principal: Principal) -> RegistryResult<u64> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let peer = match StorageManager::remove_federation_peer(&canister_id) {
+            Some(peer) => peer,
+            None => return RegistryResult::Err("Federation peer not found".to_string()),
+        };
+        let removed = StorageManager::remove_federated_attestations_before(&canister_id, u64::MAX);
+        AuditManager::record(
+            "federation_peer_removed",
+            &canister_id.to_text(),
+            vec![caller_principal],
+            format!("{} ({} attestations dropped)", peer.name, removed),
+        );
+        RegistryResult::Ok(removed)
+    }
+
+    // Public, so anyone can see where federated results come from
+    pub fn get_federation_peers() -> Vec<FederationPeer> {
+        let mut peers = StorageManager::get_all_federation_peers();
+        peers.sort_by_key(|peer| peer.added_at);
+        peers
+    }
+
+    // Export
+
+    // Served to registered peers and admins, in company id order after `after`
+    pub fn export_attestations(
+        after: Option<String>,
+        limit: Option<u32>,
+        caller_principal: Principal,
+    ) -> RegistryResult<FederationExport> {
+        if StorageManager::get_federation_peer(&caller_principal).is_none() && !AdminManager::is_admin(caller_principal)
+        {
+            return RegistryResult::Err("Unauthorized: Registered federation peer access required".to_string());
+        }
+        let limit = limit.unwrap_or(Self::MAX_EXPORT_PAGE).clamp(1, Self::MAX_EXPORT_PAGE) as usize;
+        // Only available to query calls; peers calling from a canister rely on the call
+        // itself coming from this canister instead
+        let certificate = ic_cdk::api::data_certificate().unwrap_or_default();
+
+        StorageManager::with_listing_snapshot(|snapshot| {
+            let snapshot = match snapshot {
+                Some(snapshot) => snapshot,
+                None => return RegistryResult::Err("No certified snapshot yet; try again shortly".to_string()),
+            };
+            let start = match &after {
+                Some(after) => snapshot
+                    .attestations
+                    .partition_point(|attestation| attestation.company_id.as_str() <= after.as_str()),
+                None => 0,
+            };
+            let end = (start + limit).min(snapshot.attestations.len());
+            let attestations: Vec<ListingAttestation> = (start..end)
+                .map(|index| {
+                    let mut attestation = snapshot.attestations[index].clone();
+                    attestation.signature = Some(PartnerManager::listing_signature(snapshot, index, Vec::new()));
+                    attestation
+                })
+                .collect();
+            let next_after = if end < snapshot.attestations.len() {
+                attestations.last().map(|attestation| attestation.company_id.clone())
+            } else {
+                None
+            };
+            RegistryResult::Ok(FederationExport {
+                registry: ic_cdk::id(),
+                root: snapshot.levels[snapshot.levels.len() - 1][0].to_vec(),
+                built_at: snapshot.built_at,
+                certificate,
+                attestations,
+                next_after,
+            })
+        })
+    }
+
+    // Import
+
+    pub async fn sync_federation_peer(canister_id: Principal, caller_principal: Principal) -> RegistryResult<u32> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if StorageManager::get_federation_peer(&canister_id).is_none() {
+            return RegistryResult::Err("Federation peer not found".to_string());
+        }
+        match Self::sync_peer(canister_id).await {
+            Ok(imported) => RegistryResult::Ok(imported),
+            Err(err) => RegistryResult::Err(err),
+        }
+    }
+
+    // Pulls up to MAX_PAGES_PER_SYNC pages, continuing the current pass; returns the number
+    // of attestations imported
+    async fn sync_peer(canister_id: Principal) -> Result<u32, String> {
+        if !StorageManager::try_begin_federation_sync(&canister_id) {
+            return Err("A sync with this peer is already running".to_string());
+        }
+        let result = Self::pull_pages(canister_id).await;
+        StorageManager::end_federation_sync(&canister_id);
+
+        // The peer may have been removed while the calls were out
+        if let Some(mut peer) = StorageManager::get_federation_peer(&canister_id) {
+            match &result {
+                Ok(_) => {
+                    peer.consecutive_failures = 0;
+                    peer.last_error = None;
+                }
+                Err(err) => {
+                    peer.consecutive_failures += 1;
+                    peer.last_error = Some(err.chars().take(Self::MAX_ERROR_LENGTH).collect());
+                }
+            }
+            StorageManager::insert_federation_peer(peer);
+        }
+        result
+    }
+
+    async fn pull_pages(canister_id: Principal) -> Result<u32, String> {
+        let mut imported = 0;
+        for _ in 0..Self::MAX_PAGES_PER_SYNC {
+            let cursor = match StorageManager::get_federation_peer(&canister_id) {
+                Some(peer) => peer.sync_cursor,
+                None => return Ok(imported),
+            };
+            let call: Result<(RegistryResult<FederationExport>,), _> = ic_cdk::call(
+                canister_id,
+                "export_attestations",
+                (cursor, Some(Self::MAX_EXPORT_PAGE)),
+            )
+            .await;
+            let export = match call {
+                Ok((RegistryResult::Ok(export),)) => export,
+                Ok((RegistryResult::Err(err),)) => return Err(err),
+                Err((code, message)) => return Err(format!("{:?}: {}", code, message)),
+            };
+            if export.registry != canister_id {
+                return Err("Peer answered for a different registry".to_string());
+            }
+
+            let mut peer = match StorageManager::get_federation_peer(&canister_id) {
+                Some(peer) => peer,
+                None => return Ok(imported),
+            };
+            let now = time();
+            let pass_started_at = *peer.pass_started_at.get_or_insert(now);
+            let mut held = StorageManager::get_federated_attestations_for_peer(&canister_id).len();
+            for attestation in export.attestations {
+                let attestation = match Self::validate_attestation(attestation, &export.root) {
+                    Some(attestation) => attestation,
+                    None => continue,
+                };
+                let known = StorageManager::has_federated_attestation(&canister_id, &attestation.company_id);
+                if !known {
+                    if held >= Self::MAX_ATTESTATIONS_PER_PEER {
+                        continue;
+                    }
+                    held += 1;
+                }
+                StorageManager::insert_federated_attestation(FederatedAttestation {
+                    peer: canister_id,
+                    attestation,
+                    imported_at: now,
+                });
+                imported += 1;
+            }
+
+            match export.next_after {
+                Some(after) => {
+                    peer.sync_cursor = Some(after);
+                    StorageManager::insert_federation_peer(peer);
+                }
+                None => {
+                    // A full pass is done; whatever the peer no longer lists goes
+                    StorageManager::remove_federated_attestations_before(&canister_id, pass_started_at);
+                    peer.sync_cursor = None;
+                    peer.pass_started_at = None;
+                    peer.last_synced_at = Some(now);
+                    peer.imported_count = StorageManager::get_federated_attestations_for_peer(&canister_id).len() as u64;
+                    StorageManager::insert_federation_peer(peer);
+                    return Ok(imported);
+                }
+            }
+        }
+        Ok(imported)
+    }
+
+    // Keeps an attestation only if it is under the page's root and its text is clean
+    fn validate_attestation(attestation: ListingAttestation, root: &[u8]) -> Option<ListingAttestation> {
+        let under_root = attestation
+            .signature
+            .as_ref()
+            .is_some_and(|signature| signature.root == root);
+        if !under_root || !PartnerManager::verify_listing_signature(&attestation) {
+            return None;
+        }
+        let company_id_ok = !attestation.company_id.is_empty()
+            && attestation.company_id.len() <= Self::MAX_COMPANY_ID_LENGTH
+            && !attestation.company_id.contains('|');
+        // Sanitizing would break the signature, so a name that needs it is rejected
+        let name_ok = SafeText::parse_required(&attestation.company_name, "Company name", sanitize::policy().max_name)
+            .is_ok_and(|name| name.into_inner() == attestation.company_name);
+        if company_id_ok && name_ok {
+            Some(attestation)
+        } else {
+            None
+        }
+    }
+
+    // Queries
+
+    // Name search over local companies and, when asked for, attestations imported from
+    // peers; every result says where it came from. Each call scans at most MAX_SEARCH_SCAN
+    // peer attestations after `federated_cursor`; pass the returned cursor to scan the next window
+    pub fn search_registries(
+        query: String,
+        include_federated: Option<bool>,
+        federated_cursor: Option<String>,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> RegistrySearchPage {
+        let mut results: Vec<FederatedSearchResult> = RegistryAPI::search_companies(query.clone())
+            .into_iter()
+            .map(|result| FederatedSearchResult {
+                company_id: result.company.id.clone(),
+                company_name: result.company.basic_info.name.clone(),
+                status: result.company.status.clone(),
+                verification_score: result.company.verification_score,
+                reputation_score: result.company.community_validation.reputation_score,
+                relevance: result.relevance,
+                provenance: Provenance::Local,
+            })
+            .collect();
+
+        let mut next_cursor = None;
+        if include_federated == Some(true) {
+            let peer_names: BTreeMap<Principal, String> = StorageManager::get_all_federation_peers()
+                .into_iter()
+                .map(|peer| (peer.canister_id, peer.name))
+                .collect();
+            let mut window =
+                StorageManager::get_federated_attestations_after(federated_cursor.as_deref(), Self::MAX_SEARCH_SCAN + 1);
+            if window.len() > Self::MAX_SEARCH_SCAN {
+                window.truncate(Self::MAX_SEARCH_SCAN);
+                next_cursor = window.last().map(|(key, _)| key.clone());
+            }
+            for (_, federated) in window {
+                let peer_name = match peer_names.get(&federated.peer) {
+                    Some(name) => name.clone(),
+                    None => continue,
+                };
+                let attestation = federated.attestation;
+                let relevance = match RegistryAPI::name_relevance(&attestation.company_name, &query) {
+                    Some(relevance) => relevance,
+                    None => continue,
+                };
+                results.push(FederatedSearchResult {
+                    company_id: attestation.company_id,
+                    company_name: attestation.company_name,
+                    status: attestation.status,
+                    verification_score: attestation.verification_score,
+                    reputation_score: attestation.reputation_score,
+                    relevance,
+                    provenance: Provenance::Peer {
+                        canister_id: federated.peer,
+                        name: peer_name,
+                        attested_at: attestation.attested_at,
+                        imported_at: federated.imported_at,
+                    },
+                });
+            }
+        }
+
+        // Same ordering as search_companies; local results first on a tie
+        results.sort_by_key(|result| {
+            (
+                std::cmp::Reverse(result.relevance + result.verification_score / 2),
+                result.provenance != Provenance::Local,
+            )
+        });
+        RegistrySearchPage {
+            page: CommunityValidationManager::paginate(results, offset, limit),
+            federated_cursor: next_cursor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CompanyStatus, RiskLevel};
+
+    fn import(peer: Principal, index: usize) {
+        StorageManager::insert_federated_attestation(FederatedAttestation {
+            peer,
+            attestation: ListingAttestation {
+                company_id: format!("company_{:05}", index),
+                company_name: format!("Acme {}", index),
+                status: CompanyStatus::Verified,
+                verification_score: 50,
+                reputation_score: 0,
+                risk_level: RiskLevel::Low,
+                attested_at: 0,
+                signature: None,
+            },
+            imported_at: 0,
+        });
+    }
+
+    #[test]
+    fn federated_search_scans_in_bounded_windows() {
+        let peer = Principal::from_slice(&[5; 29]);
+        StorageManager::insert_federation_peer(FederationPeer {
+            canister_id: peer,
+            name: "Peer".to_string(),
+            added_by: Principal::anonymous(),
+            added_at: 0,
+            sync_cursor: None,
+            pass_started_at: None,
+            last_synced_at: None,
+            imported_count: 0,
+            consecutive_failures: 0,
+            last_error: None,
+        });
+        let imported = FederationManager::MAX_SEARCH_SCAN + 10;
+        for index in 0..imported {
+            import(peer, index);
+        }
+
+        let first = FederationManager::search_registries("acme".to_string(), Some(true), None, None, Some(1));
+        assert_eq!(first.page.total as usize, FederationManager::MAX_SEARCH_SCAN);
+        let cursor = first.federated_cursor.expect("more attestations remain");
+
+        let second = FederationManager::search_registries("acme".to_string(), Some(true), Some(cursor), None, Some(1));
+        assert_eq!(second.page.total, 10);
+        assert_eq!(second.federated_cursor, None);
+    }
+}
@@ -0,0 +1,248 @@
+use crate::links::LinkChecker;
+use crate::ownership::OwnershipManager;
+use crate::reserved::ReservedNameManager;
+use crate::sanitize::SafeText;
+use crate::storage::StorageManager;
+use crate::types::{
+    Company, CompanyRole, FundingInfo, FundingRound, FundingStage, InvestmentRecord, RegistryResult,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Funding and investor disclosure
+
+pub struct FundingManager;
+
+impl FundingManager {
+    const MAX_ROUNDS: usize = 20;
+    const MAX_INVESTORS_PER_ROUND: usize = 30;
+    const MAX_INVESTOR_NAME_LENGTH: usize = 100;
+
+    // Priced equity rounds are ordered; other funding kinds only match themselves
+    pub fn stage_rank(stage: &FundingStage) -> Option<u8> {
+        match stage {
+            FundingStage::PreSeed => Some(0),
+            FundingStage::Seed => Some(1),
+            FundingStage::SeriesA => Some(2),
+            FundingStage::SeriesB => Some(3),
+            FundingStage::SeriesC => Some(4),
+            FundingStage::SeriesDPlus => Some(5),
+            FundingStage::Strategic
+            | FundingStage::PublicSale
+            | FundingStage::Grant
+            | FundingStage::Other => None,
+        }
+    }
+
+    // Search filter: "Series A" matches companies that raised Series A or later
+    pub fn matches_min_stage(company: &Company, min_stage: &FundingStage) -> bool {
+        let rounds = match &company.funding {
+            Some(funding) => &funding.rounds,
+            None => return false,
+        };
+
+        match Self::stage_rank(min_stage) {
+            Some(min_rank) => rounds
+                .iter()
+                .any(|round| Self::stage_rank(&round.stage).is_some_and(|rank| rank >= min_rank)),
+            None => rounds.iter().any(|round| &round.stage == min_stage),
+        }
+    }
+
+    pub fn set_funding(
+        company_id: String,
+        rounds: Vec<FundingRound>,
+        caller_principal: Principal,
+    ) -> RegistryResult<FundingInfo> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        if !OwnershipManager::has_role(&company, caller_principal, CompanyRole::Editor) {
+            return RegistryResult::Err(
+                "Unauthorized: Only company owners or editors can update funding".to_string(),
+            );
+        }
+
+        if rounds.len() > Self::MAX_ROUNDS {
+            return RegistryResult::Err(format!(
+                "Cannot disclose more than {} funding rounds",
+                Self::MAX_ROUNDS
+            ));
+        }
+
+        let previous_rounds = company.funding.map(|f| f.rounds).unwrap_or_default();
+        let mut validated = Vec::with_capacity(rounds.len());
+
+        for mut round in rounds {
+            if round.investors.len() > Self::MAX_INVESTORS_PER_ROUND {
+                return RegistryResult::Err(format!(
+                    "Cannot list more than {} investors per round",
+                    Self::MAX_INVESTORS_PER_ROUND
+                ));
+            }
+            if let Some(date) = &round.date {
+                if date.len() > 20 {
+                    return RegistryResult::Err("Funding date exceeds maximum length of 20 characters".to_string());
+                }
+            }
+            if let Some(url) = round.announcement_url.take() {
+                match LinkChecker::validate_link_url(&url, "Announcement URL") {
+                    Ok(url) => round.announcement_url = Some(url),
+                    Err(err) => return RegistryResult::Err(err),
+                }
+            }
+
+            for investor in round.investors.iter_mut() {
+                investor.name = match SafeText::parse_required(&investor.name, "Investor name", Self::MAX_INVESTOR_NAME_LENGTH) {
+                    Ok(text) => text.into_inner(),
+                    Err(err) => return RegistryResult::Err(err),
+                };
+
+                investor.company_id = match investor.company_id.take() {
+                    Some(investor_company_id) => {
+                        if investor_company_id == company_id {
+                            return RegistryResult::Err("A company cannot list itself as an investor".to_string());
+                        }
+                        if StorageManager::get_company(&investor_company_id).is_none() {
+                            return RegistryResult::Err(format!(
+                                "Investor company '{}' not found",
+                                investor_company_id
+                            ));
+                        }
+                        Some(investor_company_id)
+                    }
+                    None => Self::find_registered_investor(&investor.name, &company_id),
+                };
+            }
+
+            // Keep liveness results for links that did not change
+            round.announcement_link = previous_rounds
+                .iter()
+                .find(|previous| {
+                    previous.announcement_url.is_some() && previous.announcement_url == round.announcement_url
+                })
+                .map(|previous| previous.announcement_link.clone())
+                .unwrap_or_else(LinkChecker::unchecked);
+
+            validated.push(round);
+        }
+
+        let funding = FundingInfo {
+            rounds: validated,
+            updated_at: time(),
+        };
+
+        let stored = funding.clone();
+        StorageManager::update_company(&company_id, |company| {
+            company.funding = if stored.rounds.is_empty() { None } else { Some(stored) };
+        });
+
+        RegistryResult::Ok(funding)
+    }
+
+    pub fn get_funding(company_id: String) -> RegistryResult<Option<FundingInfo>> {
+        match StorageManager::get_company(&company_id) {
+            Some(company) => RegistryResult::Ok(company.funding),
+            None => RegistryResult::Err("Company not found".to_string()),
+        }
+    }
+
+    // Re-check every announcement URL and store the result on the round
+    pub async fn check_funding_links(
+        company_id: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<FundingInfo> {
+        if let Err(err) = StorageManager::check_http_rate_limit(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        let funding = match StorageManager::get_company(&company_id) {
+            Some(company) => match company.funding {
+                Some(funding) => funding,
+                None => return RegistryResult::Err("Company has no funding disclosure".to_string()),
+            },
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        let mut results = Vec::new();
+        for round in &funding.rounds {
+            if let Some(url) = &round.announcement_url {
+                results.push((url.clone(), LinkChecker::check_link(url).await));
+            }
+        }
+
+        // The profile may have changed while the outcalls were in flight
+        let mut updated = None;
+        StorageManager::update_company(&company_id, |company| {
+            if let Some(funding) = company.funding.as_mut() {
+                for round in funding.rounds.iter_mut() {
+                    if let Some((_, check)) = results
+                        .iter()
+                        .find(|(url, _)| round.announcement_url.as_ref() == Some(url))
+                    {
+                        round.announcement_link = check.clone();
+                    }
+                }
+                updated = Some(funding.clone());
+            }
+        });
+
+        match updated {
+            Some(funding) => RegistryResult::Ok(funding),
+            None => RegistryResult::Err("Company has no funding disclosure".to_string()),
+        }
+    }
+
+    // Portfolio view for an investor that is itself a registered company
+    pub fn get_investments_by_investor(investor_company_id: String) -> RegistryResult<Vec<InvestmentRecord>> {
+        if StorageManager::get_company(&investor_company_id).is_none() {
+            return RegistryResult::Err("Investor company not found".to_string());
+        }
+
+        let investments = StorageManager::get_all_companies()
+            .into_iter()
+            .flat_map(|company| {
+                let rounds = company.funding.map(|f| f.rounds).unwrap_or_default();
+                let company_id = company.id;
+                let company_name = company.basic_info.name;
+                rounds
+                    .into_iter()
+                    .filter(|round| {
+                        round
+                            .investors
+                            .iter()
+                            .any(|investor| investor.company_id.as_deref() == Some(investor_company_id.as_str()))
+                    })
+                    .map(move |round| InvestmentRecord {
+                        company_id: company_id.clone(),
+                        company_name: company_name.clone(),
+                        stage: round.stage,
+                        amount_usd: round.amount_usd,
+                        date: round.date,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        RegistryResult::Ok(investments)
+    }
+
+    // Link an investor by name only when exactly one registered company matches
+    fn find_registered_investor(name: &str, company_id: &str) -> Option<String> {
+        let normalized = ReservedNameManager::normalize_name(name);
+        if normalized.is_empty() {
+            return None;
+        }
+
+        let mut matches = StorageManager::get_all_companies()
+            .into_iter()
+            .filter(|c| c.id != company_id && ReservedNameManager::normalize_name(&c.basic_info.name) == normalized);
+
+        match (matches.next(), matches.next()) {
+            (Some(company), None) => Some(company.id),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use crate::community::CommunityValidationManager;
+use crate::ownership::OwnershipManager;
+use crate::sanitize::SafeText;
+use crate::storage::StorageManager;
+use crate::types::{
+    CompanyStatus, GrantAmountRange, GrantAttestation, GrantProgram, RegistryResult,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Grant programs run by verified foundations and the attestations they issue
+
+pub struct GrantManager;
+
+impl GrantManager {
+    const MAX_PROGRAM_NAME_LENGTH: usize = 100;
+    const MAX_DESCRIPTION_LENGTH: usize = 1000;
+    const MAX_PROGRAMS_PER_ISSUER: usize = 20;
+    pub const REPUTATION_POINTS_PER_GRANT: u32 = 8;
+    pub const MAX_GRANT_REPUTATION: u32 = 40;
+
+    fn ensure_verified_issuer(issuer_company_id: &str, caller_principal: Principal) -> Result<(), String> {
+        let issuer = match StorageManager::get_company(issuer_company_id) {
+            Some(company) => company,
+            None => return Err("Issuer company not found".to_string()),
+        };
+
+        if !OwnershipManager::is_owner(&issuer, caller_principal) {
+            return Err("Unauthorized: Only the foundation owner can manage grants".to_string());
+        }
+        if !matches!(issuer.status, CompanyStatus::Verified | CompanyStatus::Trusted) {
+            return Err("Only verified foundations can issue grant attestations".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn register_grant_program(
+        issuer_company_id: String,
+        name: String,
+        description: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<GrantProgram> {
+        if let Err(err) = Self::ensure_verified_issuer(&issuer_company_id, caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        let name = match SafeText::parse_required(&name, "Program name", Self::MAX_PROGRAM_NAME_LENGTH) {
+            Ok(text) => text.into_inner(),
+            Err(err) => return RegistryResult::Err(err),
+        };
+        let description = match SafeText::parse(&description, "Description", Self::MAX_DESCRIPTION_LENGTH) {
+            Ok(text) => text.into_inner(),
+            Err(err) => return RegistryResult::Err(err),
+        };
+
+        let existing = Self::get_grant_programs(issuer_company_id.clone());
+        if existing.len() >= Self::MAX_PROGRAMS_PER_ISSUER {
+            return RegistryResult::Err("Foundation has reached the maximum number of grant programs".to_string());
+        }
+        if existing.iter().any(|p| p.name.eq_ignore_ascii_case(&name)) {
+            return RegistryResult::Err("A grant program with this name already exists".to_string());
+        }
+
+        let program = GrantProgram {
+            id: format!("grant_program_{}_{}", time(), StorageManager::grant_program_count()),
+            issuer_company_id,
+            name,
+            description,
+            created_at: time(),
+            active: true,
+        };
+
+        StorageManager::insert_grant_program(program.id.clone(), program.clone());
+        RegistryResult::Ok(program)
+    }
+
+    pub fn get_grant_programs(issuer_company_id: String) -> Vec<GrantProgram> {
+        StorageManager::get_all_grant_programs()
+            .into_iter()
+            .filter(|p| p.issuer_company_id == issuer_company_id)
+            .collect()
+    }
+
+    pub fn issue_grant_attestation(
+        program_id: String,
+        recipient_company_id: String,
+        amount_range: GrantAmountRange,
+        awarded_date: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<GrantAttestation> {
+        let program = match StorageManager::get_grant_program(&program_id) {
+            Some(program) => program,
+            None => return RegistryResult::Err("Grant program not found".to_string()),
+        };
+
+        if !program.active {
+            return RegistryResult::Err("Grant program is no longer active".to_string());
+        }
+        if let Err(err) = Self::ensure_verified_issuer(&program.issuer_company_id, caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if program.issuer_company_id == recipient_company_id {
+            return RegistryResult::Err("A foundation cannot attest a grant to itself".to_string());
+        }
+        if StorageManager::get_company(&recipient_company_id).is_none() {
+            return RegistryResult::Err("Recipient company not found".to_string());
+        }
+        if awarded_date.trim().is_empty() || awarded_date.len() > 20 {
+            return RegistryResult::Err("Awarded date must be 1-20 characters".to_string());
+        }
+
+        let duplicate = StorageManager::get_all_grant_attestations().into_iter().any(|a| {
+            a.program_id == program_id
+                && a.recipient_company_id == recipient_company_id
+                && a.awarded_date == awarded_date
+                && !a.revoked
+        });
+        if duplicate {
+            return RegistryResult::Err("This grant has already been attested".to_string());
+        }
+
+        let attestation = GrantAttestation {
+            id: format!("grant_{}_{}", time(), StorageManager::grant_attestation_count()),
+            program_id,
+            program_name: program.name,
+            issuer_company_id: program.issuer_company_id,
+            recipient_company_id: recipient_company_id.clone(),
+            amount_range,
+            awarded_date,
+            issued_at: time(),
+            issued_by: caller_principal,
+            revoked: false,
+        };
+
+        StorageManager::insert_grant_attestation(attestation.id.clone(), attestation.clone());
+        CommunityValidationManager::recalculate_reputation(&recipient_company_id);
+
+        RegistryResult::Ok(attestation)
+    }
+
+    pub fn revoke_grant_attestation(attestation_id: String, caller_principal: Principal) -> RegistryResult<()> {
+        let mut attestation = match StorageManager::get_grant_attestation(&attestation_id) {
+            Some(attestation) => attestation,
+            None => return RegistryResult::Err("Grant attestation not found".to_string()),
+        };
+
+        let is_issuer_owner = StorageManager::get_company(&attestation.issuer_company_id)
+            .is_some_and(|c| OwnershipManager::is_owner(&c, caller_principal));
+        if !is_issuer_owner {
+            return RegistryResult::Err(
+                "Unauthorized: Only the issuing foundation can revoke an attestation".to_string(),
+            );
+        }
+
+        attestation.revoked = true;
+        let recipient_company_id = attestation.recipient_company_id.clone();
+        StorageManager::insert_grant_attestation(attestation_id, attestation);
+        CommunityValidationManager::recalculate_reputation(&recipient_company_id);
+
+        RegistryResult::Ok(())
+    }
+
+    pub fn get_grants_for_company(company_id: String) -> Vec<GrantAttestation> {
+        let mut grants: Vec<GrantAttestation> = StorageManager::get_all_grant_attestations()
+            .into_iter()
+            .filter(|a| a.recipient_company_id == company_id && !a.revoked)
+            .collect();
+        grants.sort_by_key(|a| std::cmp::Reverse(a.issued_at));
+        grants
+    }
+
+    pub fn get_grants_issued_by(issuer_company_id: String) -> Vec<GrantAttestation> {
+        StorageManager::get_all_grant_attestations()
+            .into_iter()
+            .filter(|a| a.issuer_company_id == issuer_company_id)
+            .collect()
+    }
+
+    // Reputation contribution; must not read the company map (called during company updates)
+    pub fn reputation_points(company_id: &str) -> u32 {
+        let active_grants = StorageManager::get_all_grant_attestations()
+            .into_iter()
+            .filter(|a| a.recipient_company_id == company_id && !a.revoked)
+            .count() as u32;
+        std::cmp::min(
+            active_grants * Self::REPUTATION_POINTS_PER_GRANT,
+            Self::MAX_GRANT_REPUTATION,
+        )
+    }
+}
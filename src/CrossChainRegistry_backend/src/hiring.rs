@@ -0,0 +1,130 @@
+use crate::links::LinkChecker;
+use crate::ownership::OwnershipManager;
+use crate::sanitize::SafeText;
+use crate::storage::StorageManager;
+use crate::types::{Company, CompanyRole, HiringInfo, JobLink, LinkStatus, RegistryResult};
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Hiring status and job board links
+
+pub struct HiringManager;
+
+impl HiringManager {
+    const MAX_JOB_LINKS: usize = 10;
+    const MAX_LABEL_LENGTH: usize = 100;
+
+    pub fn is_actively_hiring(company: &Company) -> bool {
+        company.hiring.as_ref().is_some_and(|h| h.actively_hiring)
+    }
+
+    pub fn set_hiring_status(
+        company_id: String,
+        actively_hiring: bool,
+        job_links: Vec<JobLink>,
+        caller_principal: Principal,
+    ) -> RegistryResult<HiringInfo> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        if !OwnershipManager::has_role(&company, caller_principal, CompanyRole::Editor) {
+            return RegistryResult::Err(
+                "Unauthorized: Only company owners or editors can update hiring status".to_string(),
+            );
+        }
+
+        if job_links.len() > Self::MAX_JOB_LINKS {
+            return RegistryResult::Err(format!(
+                "Cannot list more than {} job links",
+                Self::MAX_JOB_LINKS
+            ));
+        }
+
+        let previous_links = company.hiring.map(|h| h.job_links).unwrap_or_default();
+        let mut validated = Vec::with_capacity(job_links.len());
+
+        for mut job_link in job_links {
+            job_link.label = match SafeText::parse_required(&job_link.label, "Job link label", Self::MAX_LABEL_LENGTH) {
+                Ok(text) => text.into_inner(),
+                Err(err) => return RegistryResult::Err(err),
+            };
+            job_link.url = match LinkChecker::validate_link_url(&job_link.url, "Job link URL") {
+                Ok(url) => url,
+                Err(err) => return RegistryResult::Err(err),
+            };
+            if validated.iter().any(|l: &JobLink| l.url == job_link.url) {
+                return RegistryResult::Err(format!("Duplicate job link '{}'", job_link.url));
+            }
+
+            // Keep liveness results for links that did not change
+            job_link.link = previous_links
+                .iter()
+                .find(|previous| previous.url == job_link.url)
+                .map(|previous| previous.link.clone())
+                .unwrap_or_else(LinkChecker::unchecked);
+
+            validated.push(job_link);
+        }
+
+        let hiring = HiringInfo {
+            actively_hiring,
+            job_links: validated,
+            updated_at: time(),
+        };
+
+        let stored = hiring.clone();
+        StorageManager::update_company(&company_id, |company| {
+            company.hiring = Some(stored);
+        });
+
+        RegistryResult::Ok(hiring)
+    }
+
+    // Re-check every job link; companies whose links are all dead stop showing as hiring
+    pub async fn check_job_links(
+        company_id: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<HiringInfo> {
+        if let Err(err) = StorageManager::check_http_rate_limit(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        let hiring = match StorageManager::get_company(&company_id) {
+            Some(company) => match company.hiring {
+                Some(hiring) => hiring,
+                None => return RegistryResult::Err("Company has no hiring information".to_string()),
+            },
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        let mut results = Vec::new();
+        for job_link in &hiring.job_links {
+            results.push((job_link.url.clone(), LinkChecker::check_link(&job_link.url).await));
+        }
+
+        let mut updated = None;
+        StorageManager::update_company(&company_id, |company| {
+            if let Some(hiring) = company.hiring.as_mut() {
+                for job_link in hiring.job_links.iter_mut() {
+                    if let Some((_, check)) = results.iter().find(|(url, _)| *url == job_link.url) {
+                        job_link.link = check.clone();
+                    }
+                }
+
+                let all_dead = !hiring.job_links.is_empty()
+                    && hiring.job_links.iter().all(|l| l.link.status == LinkStatus::Dead);
+                if all_dead {
+                    hiring.actively_hiring = false;
+                }
+                updated = Some(hiring.clone());
+            }
+        });
+
+        match updated {
+            Some(hiring) => RegistryResult::Ok(hiring),
+            None => RegistryResult::Err("Company has no hiring information".to_string()),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use ic_cdk::api::management_canister::http_request::{http_request, CanisterHttpRequestArgument, HttpResponse};
+use std::future::Future;
+
+// Every HTTPS outcall goes through `send`, so verifiers never talk to the management
+// canister directly. Builds with the `simulation` feature can swap in canned responses
+// (see MockHttpClient) to drive the verification flows without reaching real providers.
+
+pub trait HttpClient {
+    fn request(
+        &self,
+        argument: CanisterHttpRequestArgument,
+        cycles: u128,
+    ) -> impl Future<Output = Result<HttpResponse, String>>;
+}
+
+// The real thing: an outcall through the management canister
+pub struct IcHttpClient;
+
+impl HttpClient for IcHttpClient {
+    async fn request(&self, argument: CanisterHttpRequestArgument, cycles: u128) -> Result<HttpResponse, String> {
+        http_request(argument, cycles)
+            .await
+            .map(|(response,)| response)
+            .map_err(|(code, message)| format!("{:?}: {}", code, message))
+    }
+}
+
+pub async fn send(argument: CanisterHttpRequestArgument, cycles: u128) -> Result<HttpResponse, String> {
+    #[cfg(feature = "simulation")]
+    if mock::MockHttpClient::is_installed() {
+        return mock::MockHttpClient.request(argument, cycles).await;
+    }
+    IcHttpClient.request(argument, cycles).await
+}
+
+#[cfg(feature = "simulation")]
+pub mod mock {
+    use super::HttpClient;
+    use crate::storage::StorageManager;
+    use crate::types::SimulationFixture;
+    use crate::{contacts, crosschain, evm_bridge, monitoring, proof_migration, verification, webhooks};
+    use ic_cdk::api::management_canister::http_request::{
+        CanisterHttpRequestArgument, HttpHeader, HttpResponse, TransformArgs, TransformContext,
+    };
+
+    // Answers from the simulation fixtures instead of the network. The longest matching
+    // URL prefix wins, and among those a fixture whose `body_contains` matches the request
+    // body beats one without (JSON-RPC providers take every method on the same URL).
+    // Unmatched requests fail like an unreachable host, so failover paths get exercised too.
+    pub struct MockHttpClient;
+
+    impl MockHttpClient {
+        pub fn is_installed() -> bool {
+            StorageManager::is_simulation_enabled()
+        }
+
+        fn find_fixture(argument: &CanisterHttpRequestArgument) -> Option<SimulationFixture> {
+            let body = argument
+                .body
+                .as_deref()
+                .map(String::from_utf8_lossy)
+                .unwrap_or_default();
+            StorageManager::get_simulation_fixtures()
+                .into_iter()
+                .filter(|fixture| argument.url.starts_with(&fixture.url_prefix))
+                .filter(|fixture| fixture.body_contains.as_ref().is_none_or(|needle| body.contains(needle.as_str())))
+                .max_by_key(|fixture| (fixture.url_prefix.len(), fixture.body_contains.is_some()))
+        }
+    }
+
+    impl HttpClient for MockHttpClient {
+        async fn request(&self, argument: CanisterHttpRequestArgument, _cycles: u128) -> Result<HttpResponse, String> {
+            let fixture = Self::find_fixture(&argument)
+                .ok_or_else(|| format!("SysTransient: no simulation fixture for {}", argument.url))?;
+
+            let response = HttpResponse {
+                status: fixture.status.into(),
+                headers: vec![HttpHeader {
+                    name: "Content-Type".to_string(),
+                    value: "application/json".to_string(),
+                }],
+                body: fixture.body.into_bytes(),
+            };
+            Ok(apply_transform(&argument.transform, response))
+        }
+    }
+
+    // Runs the transform an outcall named, in-process. Replicas do this for real outcalls;
+    // responses that never left the canister need it done here.
+    pub fn apply_transform(transform: &Option<TransformContext>, response: HttpResponse) -> HttpResponse {
+        let transform = match transform {
+            Some(transform) => transform,
+            None => return response,
+        };
+        let apply: fn(TransformArgs) -> HttpResponse = match transform.function.0.method.as_str() {
+            "transform_github_response" => verification::transform_github_response,
+            "transform_domain_response" => verification::transform_domain_response,
+            "transform_proof_check" => verification::transform_proof_check,
+            "transform_proof_reference" => proof_migration::transform_proof_reference,
+            "transform_contact_check" => contacts::transform_contact_check,
+            "transform_evm_block_number" => monitoring::transform_evm_block_number,
+            "transform_evm_logs" => monitoring::transform_evm_logs,
+            "transform_evm_balance" => monitoring::transform_evm_balance,
+            "transform_bitcoin_balance" => monitoring::transform_bitcoin_balance,
+            "transform_ton_balance" => monitoring::transform_ton_balance,
+            "transform_evm_transaction" => crosschain::transform_evm_transaction,
+            "transform_evm_owner" => crosschain::transform_evm_owner,
+            "transform_blockchain_response" => crosschain::transform_blockchain_response,
+            "transform_solana_signatures" => crosschain::transform_solana_signatures,
+            "transform_solana_transaction" => crosschain::transform_solana_transaction,
+            "transform_ton_transactions" => crosschain::transform_ton_transactions,
+            "transform_evm_quantity" => evm_bridge::transform_evm_quantity,
+            "transform_evm_send" => evm_bridge::transform_evm_send,
+            "transform_evm_receipt" => evm_bridge::transform_evm_receipt,
+            "transform_webhook_response" => webhooks::transform_webhook_response,
+            _ => return response,
+        };
+        apply(TransformArgs {
+            response,
+            context: transform.context.clone(),
+        })
+    }
+}
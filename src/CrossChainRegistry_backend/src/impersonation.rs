@@ -0,0 +1,210 @@
+use crate::moderation::ModerationManager;
+use crate::ownership::OwnershipManager;
+use crate::sanitize;
+use crate::storage::StorageManager;
+use crate::types::{
+    ImpersonationChannel, ImpersonationReport, ImpersonationReportStatus, ImpersonationWarning,
+    ModerationTaskKind, RegistryResult,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Reports about fake sites and handles impersonating a registered company.
+// The impersonator is not registered, so reports target the victim company; confirmed
+// reports become public warnings on the victim's profile.
+
+pub struct ImpersonationManager;
+
+impl ImpersonationManager {
+    const MAX_IMPERSONATOR_LENGTH: usize = 200;
+    const MAX_EVIDENCE_LENGTH: usize = 1000;
+    const MAX_PENDING_PER_COMPANY: usize = 100;
+
+    // Canonical form so the same site or handle is recognised however it was typed
+    pub fn normalize_impersonator(value: &str) -> String {
+        value
+            .trim()
+            .to_lowercase()
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .trim_start_matches("www.")
+            .trim_start_matches('@')
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    pub fn report_impersonation(
+        company_id: String,
+        channel: ImpersonationChannel,
+        impersonator: String,
+        evidence: String,
+        reporter_principal: Principal,
+    ) -> RegistryResult<ImpersonationReport> {
+        if let Err(err) = StorageManager::check_report_rate_limit(reporter_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        if StorageManager::get_company(&company_id).is_none() {
+            return RegistryResult::Err("Company not found".to_string());
+        }
+
+        let impersonator = Self::normalize_impersonator(&impersonator);
+        if impersonator.is_empty() || impersonator.len() > Self::MAX_IMPERSONATOR_LENGTH {
+            return RegistryResult::Err(format!(
+                "Impersonator must be 1-{} characters",
+                Self::MAX_IMPERSONATOR_LENGTH
+            ));
+        }
+        if impersonator.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return RegistryResult::Err("Impersonator must be a single URL or handle".to_string());
+        }
+        if evidence.trim().is_empty() || sanitize::grapheme_len(&evidence) > Self::MAX_EVIDENCE_LENGTH {
+            return RegistryResult::Err("Evidence must be 1-1000 characters".to_string());
+        }
+
+        let reports = Self::reports_for_company(&company_id);
+        if reports.iter().any(|r| {
+            r.impersonator == impersonator && r.status != ImpersonationReportStatus::Rejected
+        }) {
+            return RegistryResult::Err("This impersonator has already been reported".to_string());
+        }
+        let pending = reports
+            .iter()
+            .filter(|r| r.status == ImpersonationReportStatus::Pending)
+            .count();
+        if pending >= Self::MAX_PENDING_PER_COMPANY {
+            return RegistryResult::Err(
+                "Too many pending impersonation reports for this company".to_string(),
+            );
+        }
+
+        let report = ImpersonationReport {
+            id: format!("impersonation_{}_{}", time(), StorageManager::impersonation_report_count()),
+            company_id,
+            channel,
+            impersonator,
+            evidence,
+            reporter_principal,
+            reported_at: time(),
+            status: ImpersonationReportStatus::Pending,
+            reviewed_by: None,
+            reviewed_at: None,
+        };
+
+        StorageManager::insert_impersonation_report(report.id.clone(), report.clone());
+        ModerationManager::create_task(
+            ModerationTaskKind::Dispute,
+            &report.company_id,
+            &report.id,
+            format!("Impersonation report: {:?} {}", report.channel, report.impersonator),
+        );
+        RegistryResult::Ok(report)
+    }
+
+    // Moderators or the impersonated company's owner confirm or reject a report; once the
+    // report's moderation task is assigned, only the assignee or a super-admin may act on it
+    pub fn review_impersonation_report(
+        report_id: String,
+        confirmed: bool,
+        caller_principal: Principal,
+    ) -> RegistryResult<ImpersonationReport> {
+        let mut report = match StorageManager::get_impersonation_report(&report_id) {
+            Some(report) => report,
+            None => return RegistryResult::Err("Impersonation report not found".to_string()),
+        };
+
+        let company = match StorageManager::get_company(&report.company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        if !OwnershipManager::is_owner(&company, caller_principal) {
+            if let Err(err) = ModerationManager::ensure_can_finalize_reference(
+                &ModerationTaskKind::Dispute,
+                &report_id,
+                caller_principal,
+            ) {
+                return RegistryResult::Err(err);
+            }
+        }
+
+        report.status = if confirmed {
+            ImpersonationReportStatus::Confirmed
+        } else {
+            ImpersonationReportStatus::Rejected
+        };
+        report.reviewed_by = Some(caller_principal);
+        report.reviewed_at = Some(time());
+        StorageManager::insert_impersonation_report(report_id.clone(), report.clone());
+
+        let warning = ImpersonationWarning {
+            report_id: report.id.clone(),
+            channel: report.channel.clone(),
+            impersonator: report.impersonator.clone(),
+            confirmed_at: time(),
+        };
+        StorageManager::update_company(&report.company_id, |company| {
+            let warnings = company.impersonation_warnings.get_or_insert_with(Vec::new);
+            warnings.retain(|w| w.report_id != report_id);
+            if confirmed {
+                warnings.push(warning);
+            }
+        });
+
+        ModerationManager::close_reference(
+            &ModerationTaskKind::Dispute,
+            &report_id,
+            format!("Impersonation report {:?}", report.status),
+            caller_principal,
+        );
+
+        RegistryResult::Ok(report)
+    }
+
+    pub fn get_impersonation_warnings(company_id: String) -> RegistryResult<Vec<ImpersonationWarning>> {
+        match StorageManager::get_company(&company_id) {
+            Some(company) => RegistryResult::Ok(company.impersonation_warnings.unwrap_or_default()),
+            None => RegistryResult::Err("Company not found".to_string()),
+        }
+    }
+
+    pub fn get_impersonation_reports(
+        company_id: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<Vec<ImpersonationReport>> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        if !OwnershipManager::is_owner(&company, caller_principal) && !ModerationManager::is_moderator(caller_principal) {
+            return RegistryResult::Err(
+                "Unauthorized: Only moderators or the company owner can view impersonation reports".to_string(),
+            );
+        }
+
+        RegistryResult::Ok(Self::reports_for_company(&company_id))
+    }
+
+    // "Is this site/handle a known impersonator?" lookup
+    pub fn check_impersonator(value: String) -> Vec<ImpersonationWarning> {
+        let value = Self::normalize_impersonator(&value);
+        StorageManager::get_all_impersonation_reports()
+            .into_iter()
+            .filter(|r| r.status == ImpersonationReportStatus::Confirmed && r.impersonator == value)
+            .map(|r| ImpersonationWarning {
+                report_id: r.id,
+                channel: r.channel,
+                impersonator: r.impersonator,
+                confirmed_at: r.reviewed_at.unwrap_or(r.reported_at),
+            })
+            .collect()
+    }
+
+    fn reports_for_company(company_id: &str) -> Vec<ImpersonationReport> {
+        StorageManager::get_all_impersonation_reports()
+            .into_iter()
+            .filter(|r| r.company_id == company_id)
+            .collect()
+    }
+}
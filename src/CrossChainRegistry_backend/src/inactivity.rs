@@ -0,0 +1,169 @@
+use crate::admins::AdminManager;
+use crate::audit::AuditManager;
+use crate::policy::PolicyLog;
+use crate::community::CommunityValidationManager;
+use crate::notifications::NotificationManager;
+use crate::ownership::OwnershipManager;
+use crate::reputation::ReputationInputs;
+use crate::settings::SettingsManager;
+use crate::storage::StorageManager;
+use crate::types::{Company, CompanyStatus, InactivityPolicy, RegistryResult};
+use candid::Principal;
+use ic_cdk::api::time;
+use std::time::Duration;
+
+// Verified and Trusted companies nobody has looked after for a long time degrade to
+// Stale, so a years-old verification doesn't keep vouching for an abandoned project.
+// Owner edits, an explicit re-confirmation and passing proof checks all count as
+// activity; the owner is notified ahead of time and again when the status drops.
+
+pub struct InactivityManager;
+
+impl InactivityManager {
+    const DEFAULT_STALE_AFTER_DAYS: u32 = 365;
+    const DEFAULT_WARNING_DAYS: u32 = 30;
+    const MAX_STALE_AFTER_DAYS: u32 = 5 * 365;
+    const SWEEP_INTERVAL_SECS: u64 = 24 * 60 * 60;
+    const DAY_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::SWEEP_INTERVAL_SECS), || {
+            Self::run_inactivity_sweep();
+        });
+    }
+
+    pub fn get_inactivity_policy() -> InactivityPolicy {
+        StorageManager::get_inactivity_policy().unwrap_or(InactivityPolicy {
+            stale_after_days: Self::DEFAULT_STALE_AFTER_DAYS,
+            warning_days: Self::DEFAULT_WARNING_DAYS,
+        })
+    }
+
+    pub fn set_inactivity_policy(policy: InactivityPolicy, caller_principal: Principal) -> RegistryResult<()> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if policy.stale_after_days == 0 || policy.stale_after_days > Self::MAX_STALE_AFTER_DAYS {
+            return RegistryResult::Err(format!(
+                "Inactivity period must be 1-{} days",
+                Self::MAX_STALE_AFTER_DAYS
+            ));
+        }
+        if policy.warning_days >= policy.stale_after_days {
+            return RegistryResult::Err("Warning period must be shorter than the inactivity period".to_string());
+        }
+
+        AuditManager::record(
+            "inactivity_policy_set",
+            "inactivity",
+            vec![caller_principal],
+            format!("stale after {} days, warning {} days before", policy.stale_after_days, policy.warning_days),
+        );
+        PolicyLog::record(
+            "inactivity_policy",
+            Some(format!("{:?}", Self::get_inactivity_policy())),
+            format!("{:?}", policy),
+            vec![caller_principal],
+            "set_inactivity_policy",
+        );
+        StorageManager::set_inactivity_policy(policy);
+        RegistryResult::Ok(())
+    }
+
+    // Call from inside an update_company closure. A Stale company gets its score-derived
+    // status back; returns whether it was Stale.
+    pub fn mark_active(company: &mut Company) -> bool {
+        company.last_owner_activity_at = Some(time());
+        company.inactivity_warned_at = None;
+        if !matches!(company.status, CompanyStatus::Stale) {
+            return false;
+        }
+        company.status = CompanyStatus::Pending;
+        let score = ReputationInputs::from_company(company).score();
+        CommunityValidationManager::set_reputation_score(company, score);
+        true
+    }
+
+    // One-click re-confirmation; also resets the clock on companies that aren't Stale yet
+    pub fn confirm_company_active(company_id: String, caller_principal: Principal) -> RegistryResult<CompanyStatus> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+        if !OwnershipManager::is_owner(&company, caller_principal) {
+            return RegistryResult::Err("Unauthorized: Only company owner can confirm activity".to_string());
+        }
+
+        let mut status = company.status;
+        let mut was_stale = false;
+        StorageManager::update_company(&company_id, |company| {
+            was_stale = Self::mark_active(company);
+            status = company.status.clone();
+        });
+        if was_stale {
+            AuditManager::record("company_reconfirmed", &company_id, vec![caller_principal], String::new());
+        }
+        RegistryResult::Ok(status)
+    }
+
+    pub fn trigger_inactivity_sweep(caller_principal: Principal) -> RegistryResult<u32> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        RegistryResult::Ok(Self::run_inactivity_sweep())
+    }
+
+    // Warns owners approaching the deadline and marks overdue companies Stale; returns the
+    // number that became Stale
+    pub fn run_inactivity_sweep() -> u32 {
+        let policy = Self::get_inactivity_policy();
+        let stale_after_ns = policy.stale_after_days as u64 * Self::DAY_NS;
+        let warn_after_ns = (policy.stale_after_days - policy.warning_days) as u64 * Self::DAY_NS;
+        let now = time();
+
+        let mut marked_stale = 0;
+        for company in StorageManager::get_all_companies() {
+            if !matches!(company.status, CompanyStatus::Verified | CompanyStatus::Trusted) {
+                continue;
+            }
+            // Records from before activity tracking start counting from their last update;
+            // the value is written back so this sweep's own writes don't reset it
+            let last_active = company.last_owner_activity_at.unwrap_or(company.updated_at);
+            let inactive_ns = now.saturating_sub(last_active);
+
+            if inactive_ns >= stale_after_ns {
+                StorageManager::update_company(&company.id, |company| {
+                    company.last_owner_activity_at = Some(last_active);
+                    company.status = CompanyStatus::Stale;
+                });
+                NotificationManager::notify(
+                    company.created_by,
+                    &company.id,
+                    None,
+                    format!(
+                        "{} is now marked Stale after {} days without activity. Call confirm_company_active to restore its status.",
+                        company.basic_info.name, policy.stale_after_days
+                    ),
+                );
+                AuditManager::record("company_stale", &company.id, Vec::new(), format!("inactive since {}", last_active));
+                marked_stale += 1;
+            } else if inactive_ns >= warn_after_ns && company.inactivity_warned_at.is_none() {
+                StorageManager::update_company(&company.id, |company| {
+                    company.last_owner_activity_at = Some(last_active);
+                    company.inactivity_warned_at = Some(now);
+                });
+                let days_left = (stale_after_ns - inactive_ns).div_ceil(Self::DAY_NS);
+                NotificationManager::notify(
+                    company.created_by,
+                    &company.id,
+                    None,
+                    format!(
+                        "{} will be marked Stale in {} days unless you confirm it is still active (confirm_company_active).",
+                        company.basic_info.name, days_left
+                    ),
+                );
+            }
+        }
+        marked_stale
+    }
+}
@@ -0,0 +1,432 @@
+use crate::admins::AdminManager;
+use crate::settings::SettingsManager;
+use candid::Principal;
+
+// Cheap checks run by inspect_message before an ingress update is accepted, so obviously
+// invalid calls are dropped before they cost execution cycles. This is a filter, not the
+// authorization layer: inspect_message only runs on a single replica and is skipped for
+// inter-canister calls, so every endpoint keeps its own checks.
+
+// Who an admin-gated update is for, matching the check its manager makes
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Gate {
+    Controller,    // SettingsManager::ensure_admin
+    RegistryAdmin, // AdminManager::ensure_admin (controllers included)
+}
+
+pub struct IngressFilter;
+
+impl IngressFilter {
+    const DEFAULT_MAX_ARG_BYTES: usize = 16 * 1024;
+    const LARGE_MAX_ARG_BYTES: usize = 128 * 1024;
+
+    // Profiles carry descriptions, team lists and proofs
+    const LARGE_PAYLOAD_METHODS: [&'static str; 2] = ["create_company", "update_company"];
+
+    // The HTTP gateway replays upgraded requests anonymously. Anonymous reads go through
+    // query calls, which never reach inspect_message.
+    const ANONYMOUS_METHODS: [&'static str; 1] = ["http_request_update"];
+
+    // Every admin-gated update and its gate. The unit tests call each listed manager as an
+    // outsider (and the controller-only ones as a registry admin) and expect this rejection.
+    const GATED_METHODS: [(&'static str, Gate); 64] = [
+        // Canister-level configuration: topology, roles, integrations and registry-wide policy
+        ("set_provider_endpoints", Gate::Controller),
+        ("set_provider_quorum", Gate::Controller),
+        ("set_api_config", Gate::Controller),
+        ("set_cache_invalidation_hook", Gate::Controller),
+        ("remove_cache_invalidation_hook", Gate::Controller),
+        ("set_sitemap_base_url", Gate::Controller),
+        ("schedule_admin_action", Gate::Controller),
+        ("cancel_admin_action", Gate::Controller),
+        ("set_monitoring_config", Gate::Controller),
+        ("set_feature_flag", Gate::Controller),
+        ("reset_feature_flag", Gate::Controller),
+        ("start_migration", Gate::Controller),
+        ("run_migration_batch", Gate::Controller),
+        ("cancel_migration", Gate::Controller),
+        ("set_inactivity_policy", Gate::Controller),
+        ("set_community_cooldown_policy", Gate::Controller),
+        ("set_reputation_escrow_policy", Gate::Controller),
+        ("set_evm_bridge_config", Gate::Controller),
+        ("add_admin", Gate::Controller),
+        ("remove_admin", Gate::Controller),
+        ("set_registry_identity", Gate::Controller),
+        ("add_replica", Gate::Controller),
+        ("remove_replica", Gate::Controller),
+        ("sync_replication", Gate::Controller),
+        ("add_shard", Gate::Controller),
+        ("remove_shard", Gate::Controller),
+        ("assign_shard_slots", Gate::Controller),
+        ("drain_shards", Gate::Controller),
+        ("set_job_concurrency_limit", Gate::Controller),
+        // Only exported by the simulation build
+        ("set_simulation_fixture", Gate::Controller),
+        ("remove_simulation_fixture", Gate::Controller),
+        ("clear_simulation_fixtures", Gate::Controller),
+        ("set_simulation_mode", Gate::Controller),
+        // Running the registry: companies, partners, peers, moderators, keys and jobs
+        ("suspend_company", Gate::RegistryAdmin),
+        ("confirm_company_suspension", Gate::RegistryAdmin),
+        ("reinstate_company", Gate::RegistryAdmin),
+        ("force_unverify", Gate::RegistryAdmin),
+        ("add_partner", Gate::RegistryAdmin),
+        ("remove_partner", Gate::RegistryAdmin),
+        ("add_federation_peer", Gate::RegistryAdmin),
+        ("remove_federation_peer", Gate::RegistryAdmin),
+        ("sync_federation_peer", Gate::RegistryAdmin),
+        ("add_moderator", Gate::RegistryAdmin),
+        ("remove_moderator", Gate::RegistryAdmin),
+        ("reserve_name", Gate::RegistryAdmin),
+        ("release_reserved_name", Gate::RegistryAdmin),
+        ("set_api_key_rate_limit", Gate::RegistryAdmin),
+        ("set_alert_routing_rule", Gate::RegistryAdmin),
+        ("remove_alert_routing_rule", Gate::RegistryAdmin),
+        ("rebuild_sitemap", Gate::RegistryAdmin),
+        ("run_open_data_snapshot", Gate::RegistryAdmin),
+        ("run_contract_monitoring", Gate::RegistryAdmin),
+        ("run_canister_monitoring", Gate::RegistryAdmin),
+        ("run_treasury_monitoring", Gate::RegistryAdmin),
+        ("run_proof_monitoring", Gate::RegistryAdmin),
+        ("rebuild_reputation_index", Gate::RegistryAdmin),
+        ("rebuild_search_index", Gate::RegistryAdmin),
+        ("recompute_reputation", Gate::RegistryAdmin),
+        ("run_evm_bridge", Gate::RegistryAdmin),
+        ("run_inactivity_sweep", Gate::RegistryAdmin),
+        ("create_reverification_campaign", Gate::RegistryAdmin),
+        ("cancel_reverification_campaign", Gate::RegistryAdmin),
+        ("run_campaign_enforcement", Gate::RegistryAdmin),
+        ("cancel_job", Gate::RegistryAdmin),
+    ];
+
+    fn gate(method: &str) -> Option<Gate> {
+        Self::GATED_METHODS
+            .iter()
+            .find(|(name, _)| *name == method)
+            .map(|(_, gate)| *gate)
+    }
+
+    pub fn accepts(method: &str, caller_principal: Principal, arg_bytes: usize) -> Result<(), String> {
+        if caller_principal == Principal::anonymous() && !Self::ANONYMOUS_METHODS.contains(&method) {
+            return Err("Anonymous principals cannot make update calls".to_string());
+        }
+
+        let max_arg_bytes = if Self::LARGE_PAYLOAD_METHODS.contains(&method) {
+            Self::LARGE_MAX_ARG_BYTES
+        } else {
+            Self::DEFAULT_MAX_ARG_BYTES
+        };
+        if arg_bytes > max_arg_bytes {
+            return Err(format!("Payload of {} bytes exceeds the {} byte limit", arg_bytes, max_arg_bytes));
+        }
+
+        match Self::gate(method) {
+            Some(Gate::Controller) => SettingsManager::ensure_admin(caller_principal),
+            Some(Gate::RegistryAdmin) => AdminManager::ensure_admin(caller_principal),
+            None => Ok(()),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alert_routing::AlertRoutingManager;
+    use crate::api::RegistryAPI;
+    use crate::api_keys::ApiKeyManager;
+    use crate::cache_hook::CacheHookManager;
+    use crate::campaigns::CampaignManager;
+    use crate::canary::CanaryGuard;
+    use crate::community::CommunityValidationManager;
+    use crate::escrow::ReputationEscrowManager;
+    use crate::evm_bridge::EvmBridgeManager;
+    use crate::feature_flags::FeatureFlagManager;
+    use crate::federation::FederationManager;
+    use crate::inactivity::InactivityManager;
+    use crate::jobs::JobRunner;
+    use crate::migrations::MigrationManager;
+    use crate::moderation::ModerationManager;
+    use crate::monitoring::MonitoringSystem;
+    use crate::open_data::OpenDataManager;
+    use crate::partners::PartnerManager;
+    use crate::replication::ReplicationManager;
+    use crate::reputation::ReputationJournal;
+    use crate::reserved::ReservedNameManager;
+    use crate::sharding::ShardManager;
+    #[cfg(feature = "simulation")]
+    use crate::simulation::SimulationManager;
+    use crate::sitemap::SitemapManager;
+    use crate::storage::StorageManager;
+    use crate::timelock::TimelockManager;
+    use crate::types::{
+        Admin, AlertSeverity, AlertType, AlertVisibility, ApiProvider, CampaignConsequence, ChainType,
+        CommunityCooldownPolicy, CreateCampaignRequest, EvmBridgeConfig, InactivityPolicy, MigrationKind,
+        MonitoringConfig, NameMatchRule, RegistryResult, ReputationEscrowPolicy, ReserveNameRequest, SearchFilters,
+        TimelockAction, VerificationType,
+    };
+    use std::future::Future;
+    use std::task::{Context, Poll, Waker};
+
+    const CONTROLLER_ERROR: &str = "Unauthorized: Admin access required";
+    const REGISTRY_ADMIN_ERROR: &str = "Unauthorized: Registry admin access required";
+
+    fn outcome<T>(result: RegistryResult<T>) -> Result<(), String> {
+        match result {
+            RegistryResult::Ok(_) => Ok(()),
+            RegistryResult::Err(err) => Err(err),
+        }
+    }
+
+    // A rejected caller is turned away before the first await, so one poll settles it
+    fn settle<T>(future: impl Future<Output = RegistryResult<T>>) -> Result<(), String> {
+        let mut future = std::pin::pin!(future);
+        match future.as_mut().poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(result) => outcome(result),
+            Poll::Pending => panic!("the call got past its admin check"),
+        }
+    }
+
+    fn principal(byte: u8) -> Principal {
+        Principal::from_slice(&[byte; 29])
+    }
+
+    // Calls the manager behind `method` as `caller`; None for methods this build doesn't export
+    fn call_as(method: &str, caller: Principal) -> Option<Result<(), String>> {
+        let id = "missing".to_string();
+        let other = principal(42);
+        Some(match method {
+            "set_provider_endpoints" => outcome(SettingsManager::set_provider_endpoints(ApiProvider::GitHub, Vec::new(), caller)),
+            "set_provider_quorum" => outcome(SettingsManager::set_provider_quorum(ApiProvider::GitHub, Some(1), caller)),
+            "set_api_config" => outcome(SettingsManager::set_api_config(ApiProvider::GitHub, None, None, caller)),
+            "set_cache_invalidation_hook" => {
+                outcome(CacheHookManager::set_cache_invalidation_hook(other, "invalidate".to_string(), caller))
+            }
+            "remove_cache_invalidation_hook" => outcome(CacheHookManager::remove_cache_invalidation_hook(caller)),
+            "set_sitemap_base_url" => {
+                outcome(SitemapManager::set_sitemap_base_url("https://registry.example".to_string(), caller))
+            }
+            "schedule_admin_action" => outcome(TimelockManager::schedule_admin_action(
+                TimelockAction::TimelockDelay { delay_hours: 48 },
+                caller,
+            )),
+            "cancel_admin_action" => outcome(TimelockManager::cancel_admin_action(id, caller)),
+            "set_monitoring_config" => outcome(MonitoringSystem::set_monitoring_config(
+                MonitoringConfig {
+                    treasury_outflow_threshold_percent: 50,
+                    treasury_outflow_window_hours: 24,
+                    alert_dedup_window_hours: None,
+                    escalation_after_hours: None,
+                    escalation_pauses_score: None,
+                    contract_interval_mins: None,
+                    canister_interval_mins: None,
+                    treasury_interval_mins: None,
+                    escalation_interval_mins: None,
+                    proof_interval_mins: None,
+                    proof_checks_per_run: None,
+                },
+                caller,
+            )),
+            "set_feature_flag" => outcome(FeatureFlagManager::set_feature_flag("flag".to_string(), true, 100, caller)),
+            "reset_feature_flag" => outcome(FeatureFlagManager::reset_feature_flag("flag".to_string(), caller)),
+            "start_migration" => outcome(MigrationManager::start_migration(MigrationKind::AssignProofIds, caller)),
+            "run_migration_batch" => outcome(MigrationManager::run_migration_batch(id, None, caller)),
+            "cancel_migration" => outcome(MigrationManager::cancel_migration(id, caller)),
+            "set_inactivity_policy" => outcome(InactivityManager::set_inactivity_policy(
+                InactivityPolicy {
+                    stale_after_days: 365,
+                    warning_days: 30,
+                },
+                caller,
+            )),
+            "set_community_cooldown_policy" => outcome(CommunityValidationManager::set_community_cooldown_policy(
+                CommunityCooldownPolicy {
+                    max_vouches_per_day: 10,
+                    endorsement_readd_days: 7,
+                },
+                caller,
+            )),
+            "set_reputation_escrow_policy" => outcome(ReputationEscrowManager::set_reputation_escrow_policy(
+                ReputationEscrowPolicy { maturation_days: 30 },
+                caller,
+            )),
+            "set_evm_bridge_config" => outcome(EvmBridgeManager::set_evm_bridge_config(
+                EvmBridgeConfig {
+                    enabled: false,
+                    chain_type: ChainType::Ethereum,
+                    chain_id: 1,
+                    contract_address: None,
+                    publish_interval_mins: 60,
+                    gas_limit: 100_000,
+                    max_fee_per_gas: 1,
+                    max_priority_fee_per_gas: 1,
+                },
+                caller,
+            )),
+            "add_admin" => outcome(AdminManager::add_admin(other, caller)),
+            "remove_admin" => outcome(AdminManager::remove_admin(other, caller)),
+            "set_registry_identity" => outcome(CanaryGuard::set_registry_identity(Vec::new(), Vec::new(), caller)),
+            "add_replica" => outcome(ReplicationManager::add_replica(other, caller)),
+            "remove_replica" => outcome(ReplicationManager::remove_replica(other, caller)),
+            "sync_replication" => settle(ReplicationManager::sync_replication(caller)),
+            "add_shard" => outcome(ShardManager::add_shard(other, caller)),
+            "remove_shard" => outcome(ShardManager::remove_shard(other, caller)),
+            "assign_shard_slots" => outcome(ShardManager::assign_shard_slots(0, 0, other, caller)),
+            "drain_shards" => settle(ShardManager::drain_shards(caller)),
+            "set_job_concurrency_limit" => outcome(JobRunner::set_job_concurrency_limit(None, Some(1), caller)),
+            #[cfg(feature = "simulation")]
+            "set_simulation_fixture" => outcome(SimulationManager::set_simulation_fixture(
+                crate::types::SimulationFixture {
+                    url_prefix: "https://api.github.com/".to_string(),
+                    body_contains: None,
+                    status: 200,
+                    body: String::new(),
+                },
+                caller,
+            )),
+            #[cfg(feature = "simulation")]
+            "remove_simulation_fixture" => {
+                outcome(SimulationManager::remove_simulation_fixture("https://".to_string(), None, caller))
+            }
+            #[cfg(feature = "simulation")]
+            "clear_simulation_fixtures" => outcome(SimulationManager::clear_simulation_fixtures(caller)),
+            #[cfg(feature = "simulation")]
+            "set_simulation_mode" => outcome(SimulationManager::set_simulation_mode(false, caller)),
+            #[cfg(not(feature = "simulation"))]
+            "set_simulation_fixture" | "remove_simulation_fixture" | "clear_simulation_fixtures" | "set_simulation_mode" => {
+                return None
+            }
+            "suspend_company" => outcome(AdminManager::suspend_company(id, "reason".to_string(), caller)),
+            "confirm_company_suspension" => outcome(AdminManager::confirm_suspension(id, caller)),
+            "reinstate_company" => outcome(AdminManager::reinstate_company(id, caller)),
+            "force_unverify" => outcome(AdminManager::force_unverify(
+                id,
+                VerificationType::GitHub,
+                "reason".to_string(),
+                caller,
+            )),
+            "add_partner" => outcome(PartnerManager::add_partner(other, "Partner".to_string(), caller)),
+            "remove_partner" => outcome(PartnerManager::remove_partner(other, caller)),
+            "add_federation_peer" => outcome(FederationManager::add_federation_peer(other, "Peer".to_string(), caller)),
+            "remove_federation_peer" => outcome(FederationManager::remove_federation_peer(other, caller)),
+            "sync_federation_peer" => settle(FederationManager::sync_federation_peer(other, caller)),
+            "add_moderator" => outcome(ModerationManager::add_moderator(other, caller)),
+            "remove_moderator" => outcome(ModerationManager::remove_moderator(other, caller)),
+            "reserve_name" => outcome(ReservedNameManager::reserve_name(
+                ReserveNameRequest {
+                    name: "Example".to_string(),
+                    match_rule: NameMatchRule::Exact,
+                    required_domains: vec!["example.com".to_string()],
+                    reason: None,
+                },
+                caller,
+            )),
+            "release_reserved_name" => outcome(ReservedNameManager::release_reserved_name("Example".to_string(), caller)),
+            "set_api_key_rate_limit" => outcome(ApiKeyManager::set_api_key_rate_limit(id, 60, caller)),
+            "set_alert_routing_rule" => outcome(AlertRoutingManager::set_alert_routing_rule(
+                AlertType::CanisterModuleChanged,
+                AlertSeverity::Warning,
+                AlertVisibility::Moderators,
+                Vec::new(),
+                caller,
+            )),
+            "remove_alert_routing_rule" => outcome(AlertRoutingManager::remove_alert_routing_rule(
+                AlertType::CanisterModuleChanged,
+                AlertSeverity::Warning,
+                caller,
+            )),
+            "rebuild_sitemap" => outcome(SitemapManager::rebuild_sitemap(caller)),
+            "run_open_data_snapshot" => outcome(OpenDataManager::run_open_data_snapshot(caller)),
+            "run_contract_monitoring" => settle(MonitoringSystem::trigger_contract_monitoring(caller)),
+            "run_canister_monitoring" => settle(MonitoringSystem::trigger_canister_monitoring(caller)),
+            "run_treasury_monitoring" => settle(MonitoringSystem::trigger_treasury_monitoring(caller)),
+            "run_proof_monitoring" => settle(MonitoringSystem::trigger_proof_monitoring(caller)),
+            "rebuild_reputation_index" => outcome(CommunityValidationManager::rebuild_reputation_index(caller)),
+            "rebuild_search_index" => outcome(RegistryAPI::rebuild_search_index(caller)),
+            "recompute_reputation" => outcome(ReputationJournal::recompute_reputation(id, caller)),
+            "run_evm_bridge" => settle(EvmBridgeManager::trigger_evm_bridge(caller)),
+            "run_inactivity_sweep" => outcome(InactivityManager::trigger_inactivity_sweep(caller)),
+            "create_reverification_campaign" => outcome(CampaignManager::create_reverification_campaign(
+                CreateCampaignRequest {
+                    title: "Campaign".to_string(),
+                    reason: "reason".to_string(),
+                    target: SearchFilters {
+                        status: None,
+                        focus_areas: None,
+                        min_verification_score: None,
+                        has_github: None,
+                        has_contracts: None,
+                        entity_type: None,
+                        min_funding_stage: None,
+                        actively_hiring: None,
+                        on_chain: None,
+                        tags: None,
+                        include_archived: None,
+                    },
+                    required_actions: Vec::new(),
+                    deadline_days: 30,
+                    consequence: CampaignConsequence::None,
+                },
+                caller,
+            )),
+            "cancel_reverification_campaign" => outcome(CampaignManager::cancel_reverification_campaign(id, caller)),
+            "run_campaign_enforcement" => outcome(CampaignManager::trigger_campaign_enforcement(caller)),
+            "cancel_job" => outcome(JobRunner::cancel_job(id, caller)),
+            _ => panic!("no test call for {}", method),
+        })
+    }
+
+    fn expected_error(gate: Gate) -> String {
+        match gate {
+            Gate::Controller => CONTROLLER_ERROR.to_string(),
+            Gate::RegistryAdmin => REGISTRY_ADMIN_ERROR.to_string(),
+        }
+    }
+
+    #[test]
+    fn gated_managers_reject_outsiders() {
+        let outsider = principal(7);
+        for (method, gate) in IngressFilter::GATED_METHODS {
+            if let Some(result) = call_as(method, outsider) {
+                assert_eq!(result, Err(expected_error(gate)), "{}", method);
+            }
+        }
+    }
+
+    #[test]
+    fn registry_admins_cannot_configure_the_canister() {
+        let admin = principal(8);
+        StorageManager::insert_admin(Admin {
+            principal: admin,
+            added_by: principal(1),
+            added_at: 0,
+        });
+        for (method, gate) in IngressFilter::GATED_METHODS {
+            if gate != Gate::Controller {
+                continue;
+            }
+            if let Some(result) = call_as(method, admin) {
+                assert_eq!(result, Err(CONTROLLER_ERROR.to_string()), "{}", method);
+            }
+        }
+    }
+
+    #[test]
+    fn ingress_filter_applies_the_same_gates() {
+        let outsider = principal(7);
+        for (method, gate) in IngressFilter::GATED_METHODS {
+            assert_eq!(IngressFilter::accepts(method, outsider, 0), Err(expected_error(gate)), "{}", method);
+        }
+        assert_eq!(IngressFilter::accepts("create_company", outsider, 0), Ok(()));
+        assert!(IngressFilter::accepts("create_company", Principal::anonymous(), 0).is_err());
+        assert_eq!(IngressFilter::accepts("http_request_update", Principal::anonymous(), 0), Ok(()));
+    }
+
+    #[test]
+    fn gated_methods_are_listed_once() {
+        let mut methods: Vec<&str> = IngressFilter::GATED_METHODS.iter().map(|(method, _)| *method).collect();
+        methods.sort();
+        methods.dedup();
+        assert_eq!(methods.len(), IngressFilter::GATED_METHODS.len());
+    }
+}
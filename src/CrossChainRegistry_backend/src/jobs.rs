@@ -0,0 +1,359 @@
+use crate::admins::AdminManager;
+use crate::audit::AuditManager;
+use crate::settings::SettingsManager;
+use crate::storage::StorageManager;
+use crate::types::{JobConcurrencyLimits, JobProgress, JobStatus, RegistryResult};
+use candid::Principal;
+use ic_cdk::api::time;
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+// Background job runner. A job is a list of tasks, each tagged with the provider it
+// calls out to; the runner keeps at most max_in_flight tasks running across all jobs and
+// at most the provider's limit against any one provider, starting queued tasks as
+// running ones finish. Jobs are served oldest first. Cancelling a job drops its queued
+// tasks; outcalls already sent cannot be recalled, so those finish and are counted.
+//
+// Jobs live on the heap: an upgrade drops running futures anyway, and whatever started
+// the job (a timer, usually) starts it again.
+
+pub type TaskFuture = Pin<Box<dyn Future<Output = Result<u32, String>>>>;
+
+pub struct JobTask {
+    provider: String,
+    run: Box<dyn FnOnce() -> TaskFuture>,
+}
+
+impl JobTask {
+    // The task reports a count (e.g. alerts raised) that is summed into the job's result
+    pub fn new<F, Fut>(provider: impl Into<String>, run: F) -> JobTask
+    where
+        F: FnOnce() -> Fut + 'static,
+        Fut: Future<Output = Result<u32, String>> + 'static,
+    {
+        JobTask {
+            provider: provider.into(),
+            run: Box::new(move || Box::pin(run()) as TaskFuture),
+        }
+    }
+}
+
+pub struct Job {
+    progress: JobProgress,
+    pending: VecDeque<JobTask>,
+    waiters: Vec<Waker>,
+}
+
+#[derive(Default)]
+pub struct JobRegistry {
+    jobs: BTreeMap<String, Job>,
+    in_flight: BTreeMap<String, u32>,
+    total_in_flight: u32,
+    next_id: u64,
+    dispatching: bool,
+}
+
+pub struct JobRunner;
+
+impl JobRunner {
+    const DEFAULT_MAX_IN_FLIGHT: u32 = 10;
+    const DEFAULT_PER_PROVIDER: u32 = 2;
+    const MAX_LIMIT: u32 = 50;
+    const MAX_FINISHED_JOBS: usize = 50;
+    const MAX_PROVIDER_LENGTH: usize = 64;
+    const MAX_ERROR_LENGTH: usize = 200;
+    const GLOBAL_LIMIT_KEY: &'static str = "*";
+
+    fn max_in_flight() -> u32 {
+        StorageManager::get_job_concurrency_limit(Self::GLOBAL_LIMIT_KEY).unwrap_or(Self::DEFAULT_MAX_IN_FLIGHT)
+    }
+
+    fn provider_limit(provider: &str) -> u32 {
+        StorageManager::get_job_concurrency_limit(provider).unwrap_or(Self::DEFAULT_PER_PROVIDER)
+    }
+
+    // Queues the tasks and starts as many as the limits allow; returns the job id
+    pub fn submit(name: &str, tasks: Vec<JobTask>) -> String {
+        let job_id = StorageManager::with_job_registry(|registry| {
+            registry.next_id += 1;
+            let job_id = format!("job_{}_{}", time(), registry.next_id);
+            let total = tasks.len() as u32;
+            let finished = total == 0;
+            registry.jobs.insert(
+                job_id.clone(),
+                Job {
+                    progress: JobProgress {
+                        id: job_id.clone(),
+                        name: name.to_string(),
+                        status: if finished { JobStatus::Completed } else { JobStatus::Running },
+                        total,
+                        pending: total,
+                        in_flight: 0,
+                        completed: 0,
+                        failed: 0,
+                        result: 0,
+                        started_at: time(),
+                        finished_at: finished.then(time),
+                        last_error: None,
+                        cancelled_by: None,
+                    },
+                    pending: tasks.into(),
+                    waiters: Vec::new(),
+                },
+            );
+            Self::prune_finished(registry);
+            job_id
+        });
+        Self::dispatch();
+        job_id
+    }
+
+    // Resolves once the job is no longer running
+    pub fn wait(job_id: &str) -> JobCompletion {
+        JobCompletion {
+            job_id: job_id.to_string(),
+        }
+    }
+
+    // Submits and waits; returns the summed task results
+    pub async fn run(name: &str, tasks: Vec<JobTask>) -> u32 {
+        let job_id = Self::submit(name, tasks);
+        Self::wait(&job_id).await.map_or(0, |progress| progress.result)
+    }
+
+    fn prune_finished(registry: &mut JobRegistry) {
+        let mut finished: Vec<(u64, String)> = registry
+            .jobs
+            .values()
+            .filter(|job| job.progress.status != JobStatus::Running && job.progress.in_flight == 0)
+            .map(|job| (job.progress.finished_at.unwrap_or(0), job.progress.id.clone()))
+            .collect();
+        if finished.len() > Self::MAX_FINISHED_JOBS {
+            finished.sort();
+            for (_, job_id) in finished.iter().take(finished.len() - Self::MAX_FINISHED_JOBS) {
+                registry.jobs.remove(job_id);
+            }
+        }
+    }
+
+    // Starts queued tasks until a limit is reached. Tasks that finish without awaiting
+    // call back in here; the flag keeps that from recursing, the outer loop picks up
+    // whatever they freed.
+    fn dispatch() {
+        let entered = StorageManager::with_job_registry(|registry| !std::mem::replace(&mut registry.dispatching, true));
+        if !entered {
+            return;
+        }
+        let max_in_flight = Self::max_in_flight();
+        loop {
+            let next = StorageManager::with_job_registry(|registry| {
+                if registry.total_in_flight >= max_in_flight {
+                    return None;
+                }
+                let JobRegistry { jobs, in_flight, .. } = registry;
+                for job in jobs.values_mut().filter(|job| job.progress.status == JobStatus::Running) {
+                    let position = job.pending.iter().position(|task| {
+                        in_flight.get(&task.provider).copied().unwrap_or(0) < Self::provider_limit(&task.provider)
+                    });
+                    if let Some(task) = position.and_then(|position| job.pending.remove(position)) {
+                        *in_flight.entry(task.provider.clone()).or_insert(0) += 1;
+                        job.progress.pending -= 1;
+                        job.progress.in_flight += 1;
+                        return Some((job.progress.id.clone(), task));
+                    }
+                }
+                None
+            });
+            let Some((job_id, task)) = next else {
+                break;
+            };
+            StorageManager::with_job_registry(|registry| registry.total_in_flight += 1);
+            ic_cdk::spawn(Self::run_task(job_id, task));
+        }
+        StorageManager::with_job_registry(|registry| registry.dispatching = false);
+    }
+
+    async fn run_task(job_id: String, task: JobTask) {
+        let mut guard = TaskGuard {
+            job_id,
+            provider: task.provider,
+            finished: false,
+        };
+        let result = (task.run)().await;
+        guard.finished = true;
+        let waiters = Self::finish_task(&guard.job_id, &guard.provider, result);
+        for waiter in waiters {
+            waiter.wake();
+        }
+        Self::dispatch();
+    }
+
+    // Books a finished task; returns the waiters to wake if that finished the job
+    fn finish_task(job_id: &str, provider: &str, result: Result<u32, String>) -> Vec<Waker> {
+        StorageManager::with_job_registry(|registry| {
+            registry.total_in_flight = registry.total_in_flight.saturating_sub(1);
+            if let Some(count) = registry.in_flight.get_mut(provider) {
+                *count = count.saturating_sub(1);
+                if *count == 0 {
+                    registry.in_flight.remove(provider);
+                }
+            }
+            let Some(job) = registry.jobs.get_mut(job_id) else {
+                return Vec::new();
+            };
+            let progress = &mut job.progress;
+            progress.in_flight = progress.in_flight.saturating_sub(1);
+            match result {
+                Ok(count) => {
+                    progress.completed += 1;
+                    progress.result = progress.result.saturating_add(count);
+                }
+                Err(err) => {
+                    progress.failed += 1;
+                    progress.last_error = Some(err.chars().take(Self::MAX_ERROR_LENGTH).collect());
+                }
+            }
+            if progress.status == JobStatus::Running && job.pending.is_empty() && progress.in_flight == 0 {
+                progress.status = JobStatus::Completed;
+                progress.finished_at = Some(time());
+                return std::mem::take(&mut job.waiters);
+            }
+            Vec::new()
+        })
+    }
+
+    // Endpoints (controllers)
+
+    pub fn cancel_job(job_id: String, caller_principal: Principal) -> RegistryResult<JobProgress> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let cancelled = StorageManager::with_job_registry(|registry| {
+            let job = registry.jobs.get_mut(&job_id).ok_or("Job not found".to_string())?;
+            if job.progress.status != JobStatus::Running {
+                return Err("Job is not running".to_string());
+            }
+            job.pending.clear();
+            job.progress.pending = 0;
+            job.progress.status = JobStatus::Cancelled;
+            job.progress.cancelled_by = Some(caller_principal);
+            job.progress.finished_at = Some(time());
+            Ok((job.progress.clone(), std::mem::take(&mut job.waiters)))
+        });
+        match cancelled {
+            Ok((progress, waiters)) => {
+                for waiter in waiters {
+                    waiter.wake();
+                }
+                AuditManager::record("job_cancelled", &job_id, vec![caller_principal], progress.name.clone());
+                RegistryResult::Ok(progress)
+            }
+            Err(err) => RegistryResult::Err(err),
+        }
+    }
+
+    // Newest first
+    pub fn get_jobs(status: Option<JobStatus>, caller_principal: Principal) -> RegistryResult<Vec<JobProgress>> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let mut jobs: Vec<JobProgress> = StorageManager::with_job_registry(|registry| {
+            registry
+                .jobs
+                .values()
+                .filter(|job| status.as_ref().is_none_or(|status| job.progress.status == *status))
+                .map(|job| job.progress.clone())
+                .collect()
+        });
+        jobs.sort_by_key(|job| std::cmp::Reverse(job.started_at));
+        RegistryResult::Ok(jobs)
+    }
+
+    // None as the provider sets the overall cap; None as the limit restores the default
+    pub fn set_job_concurrency_limit(
+        provider: Option<String>,
+        limit: Option<u32>,
+        caller_principal: Principal,
+    ) -> RegistryResult<JobConcurrencyLimits> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let key = match provider {
+            Some(provider) => {
+                let provider = provider.trim().to_string();
+                if provider.is_empty() || provider.len() > Self::MAX_PROVIDER_LENGTH || provider == Self::GLOBAL_LIMIT_KEY {
+                    return RegistryResult::Err(format!(
+                        "Provider must be 1-{} characters",
+                        Self::MAX_PROVIDER_LENGTH
+                    ));
+                }
+                provider
+            }
+            None => Self::GLOBAL_LIMIT_KEY.to_string(),
+        };
+        if limit.is_some_and(|limit| limit == 0 || limit > Self::MAX_LIMIT) {
+            return RegistryResult::Err(format!("Limit must be 1-{}", Self::MAX_LIMIT));
+        }
+
+        StorageManager::set_job_concurrency_limit(&key, limit);
+        AuditManager::record(
+            "job_concurrency_limit_set",
+            &key,
+            vec![caller_principal],
+            limit.map_or("default".to_string(), |limit| limit.to_string()),
+        );
+        // A raised limit can start queued tasks right away
+        Self::dispatch();
+        RegistryResult::Ok(Self::get_job_concurrency_limits())
+    }
+
+    pub fn get_job_concurrency_limits() -> JobConcurrencyLimits {
+        JobConcurrencyLimits {
+            max_in_flight: Self::max_in_flight(),
+            default_per_provider: Self::DEFAULT_PER_PROVIDER,
+            provider_limits: StorageManager::get_all_job_concurrency_limits()
+                .into_iter()
+                .filter(|(key, _)| key != Self::GLOBAL_LIMIT_KEY)
+                .collect(),
+        }
+    }
+}
+
+pub struct JobCompletion {
+    job_id: String,
+}
+
+impl Future for JobCompletion {
+    // None if the job was pruned before it was looked at
+    type Output = Option<JobProgress>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        StorageManager::with_job_registry(|registry| match registry.jobs.get_mut(&self.job_id) {
+            Some(job) if job.progress.status == JobStatus::Running => {
+                job.waiters.push(cx.waker().clone());
+                Poll::Pending
+            }
+            Some(job) => Poll::Ready(Some(job.progress.clone())),
+            None => Poll::Ready(None),
+        })
+    }
+}
+
+// Frees the task's slot if its callback traps. The future is dropped during cleanup,
+// where waking or spawning is off limits, so the next finished task or submission starts
+// the queued work instead.
+struct TaskGuard {
+    job_id: String,
+    provider: String,
+    finished: bool,
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        if !self.finished {
+            JobRunner::finish_task(&self.job_id, &self.provider, Err("Task trapped".to_string()));
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::types::{
+    IcrcAccount, IcrcTransferArg, IcrcTransferError, IcrcTransferFromArgs, IcrcTransferFromError,
+    PendingIntake, PendingPayout,
+};
+use candid::{Nat, Principal};
+use ic_cdk::api::call::RejectionCode;
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+// Minimal ICRC-1/ICRC-2 client for moving tokens between users and this canister's
+// default account. Amounts are in the ledger's base units.
+//
+// Every transfer carries created_at_time and a memo, so the ledger deduplicates a
+// retried transfer (answering Duplicate) instead of executing it twice.
+
+// Only Rejected means the ledger did not move the tokens. Unknown covers calls whose
+// outcome we can't see (the reply was lost, or the ledger no longer deduplicates the
+// timestamp); refunding on those could pay out twice.
+#[derive(Clone, Debug)]
+pub enum LedgerError {
+    Rejected(String),
+    Unknown(String),
+}
+
+impl fmt::Display for LedgerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerError::Rejected(message) | LedgerError::Unknown(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+pub struct LedgerClient;
+
+impl LedgerClient {
+    pub fn canister_account() -> IcrcAccount {
+        IcrcAccount {
+            owner: ic_cdk::id(),
+            subaccount: None,
+        }
+    }
+
+    // 32 bytes, the ICRC-1 default memo limit. Ties a payout to its purpose, recipient
+    // and timestamp so two claims never share a deduplication key.
+    pub fn memo(purpose: &str, principal: Principal, created_at_time: u64) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(purpose.as_bytes());
+        hasher.update(principal.as_slice());
+        hasher.update(created_at_time.to_be_bytes());
+        hasher.finalize().to_vec()
+    }
+
+    // Builds a payout to record before the call, so a retry reuses the same timestamp
+    pub fn pending_payout(purpose: &str, to: Principal, amount: u64, fee: u64, now: u64) -> PendingPayout {
+        PendingPayout {
+            amount,
+            payout: amount.saturating_sub(fee),
+            memo: Self::memo(purpose, to, now),
+            created_at_time: now,
+        }
+    }
+
+    // Same for tokens pulled from a user
+    pub fn pending_intake(purpose: &str, from: Principal, amount: u64, now: u64) -> PendingIntake {
+        PendingIntake {
+            amount,
+            memo: Self::memo(purpose, from, now),
+            created_at_time: now,
+        }
+    }
+
+    pub async fn pull(ledger: Principal, from: Principal, intake: &PendingIntake) -> Result<Nat, LedgerError> {
+        Self::transfer_from(ledger, from, intake.amount, intake.memo.clone(), intake.created_at_time).await
+    }
+
+    pub async fn pay(ledger: Principal, to: Principal, payout: &PendingPayout) -> Result<Nat, LedgerError> {
+        Self::transfer(ledger, to, payout.payout, payout.memo.clone(), payout.created_at_time).await
+    }
+
+    // Pay out from the canister's account; returns the ledger block index
+    pub async fn transfer(
+        ledger: Principal,
+        to: Principal,
+        amount: u64,
+        memo: Vec<u8>,
+        created_at_time: u64,
+    ) -> Result<Nat, LedgerError> {
+        let arg = IcrcTransferArg {
+            from_subaccount: None,
+            to: IcrcAccount {
+                owner: to,
+                subaccount: None,
+            },
+            amount: Nat::from(amount),
+            fee: None,
+            memo: Some(memo),
+            created_at_time: Some(created_at_time),
+        };
+
+        let result: Result<(Result<Nat, IcrcTransferError>,), _> =
+            ic_cdk::call(ledger, "icrc1_transfer", (arg,)).await;
+        match result {
+            Ok((Ok(block_index),)) => Ok(block_index),
+            // An earlier attempt with the same timestamp and memo already went through
+            Ok((Err(IcrcTransferError::Duplicate { duplicate_of }),)) => Ok(duplicate_of),
+            Ok((Err(err @ IcrcTransferError::TooOld),)) => {
+                Err(LedgerError::Unknown(format!("Ledger transfer failed: {:?}", err)))
+            }
+            Ok((Err(err),)) => Err(LedgerError::Rejected(format!("Ledger transfer failed: {:?}", err))),
+            Err((code, message)) => Err(Self::call_error(code, message)),
+        }
+    }
+
+    // Pull tokens the user approved for this canister (icrc2_approve) into its account
+    pub async fn transfer_from(
+        ledger: Principal,
+        from: Principal,
+        amount: u64,
+        memo: Vec<u8>,
+        created_at_time: u64,
+    ) -> Result<Nat, LedgerError> {
+        let args = IcrcTransferFromArgs {
+            spender_subaccount: None,
+            from: IcrcAccount {
+                owner: from,
+                subaccount: None,
+            },
+            to: Self::canister_account(),
+            amount: Nat::from(amount),
+            fee: None,
+            memo: Some(memo),
+            created_at_time: Some(created_at_time),
+        };
+
+        let result: Result<(Result<Nat, IcrcTransferFromError>,), _> =
+            ic_cdk::call(ledger, "icrc2_transfer_from", (args,)).await;
+        match result {
+            Ok((Ok(block_index),)) => Ok(block_index),
+            Ok((Err(IcrcTransferFromError::Duplicate { duplicate_of }),)) => Ok(duplicate_of),
+            Ok((Err(err @ IcrcTransferFromError::TooOld),)) => {
+                Err(LedgerError::Unknown(format!("Ledger transfer_from failed: {:?}", err)))
+            }
+            Ok((Err(err),)) => Err(LedgerError::Rejected(format!("Ledger transfer_from failed: {:?}", err))),
+            Err((code, message)) => Err(Self::call_error(code, message)),
+        }
+    }
+
+    // The ledger never reached or rolled back the call for these codes; for the rest the
+    // transfer may have executed before the reply was lost
+    fn call_error(code: RejectionCode, message: String) -> LedgerError {
+        let text = format!("Ledger call failed ({:?}): {}", code, message);
+        match code {
+            RejectionCode::DestinationInvalid | RejectionCode::CanisterReject | RejectionCode::CanisterError => {
+                LedgerError::Rejected(text)
+            }
+            _ => LedgerError::Unknown(text),
+        }
+    }
+}
@@ -1,182 +1,2067 @@
+mod admins;
+mod alert_routing;
 mod api;
+mod api_keys;
+mod archive;
+mod audit;
+mod banners;
+mod bitcoin_message;
+mod blocklist;
+mod cache_hook;
+mod campaigns;
+mod canary;
+mod chain_key;
+mod chain_stats;
 mod community;
+mod concerns;
+mod contacts;
+mod critical_actions;
 mod crosschain;
+mod dashboard;
+mod disputes;
+mod escrow;
+mod evm_attestations;
+mod evm_bridge;
+mod evm_signature;
+mod feature_flags;
+mod federation;
+mod funding;
+mod grants;
+mod hiring;
+mod http_client;
+mod impersonation;
+mod inactivity;
+mod ingress;
+mod jobs;
+mod ledger;
+mod links;
+mod migrations;
+mod moderation;
+mod moderator_notes;
+mod monitoring;
+mod notifications;
+mod open_data;
+mod ownership;
+mod partners;
+mod policy;
+mod private_messages;
+mod proof_migration;
+mod public_api;
+mod relationships;
+mod replication;
+mod reputation;
+mod reserved;
+mod reviewers;
+mod rotation;
+mod sanitize;
+mod saved_searches;
+mod search_index;
+mod security;
+mod settings;
+mod sharding;
+#[cfg(feature = "simulation")]
+mod simulation;
+mod sitemap;
+mod staking;
+mod timelock;
 mod storage;
+mod tags;
+mod tiers;
 mod types;
+mod usage;
 mod verification;
+mod verification_guard;
+mod vetkd;
+mod webhooks;
+mod widget;
 
+use admins::AdminManager;
+use alert_routing::AlertRoutingManager;
 use api::RegistryAPI;
+use api_keys::ApiKeyManager;
+use archive::ArchiveManager;
+use audit::AuditManager;
+use banners::BannerManager;
+use blocklist::BlocklistManager;
+use cache_hook::CacheHookManager;
+use campaigns::CampaignManager;
+use canary::CanaryGuard;
+use chain_stats::ChainStatsManager;
+use candid::{Nat, Principal};
 use community::CommunityValidationManager;
+use concerns::ConcernManager;
+use contacts::ContactChannelManager;
+use critical_actions::CriticalActionManager;
 use crosschain::CrossChainVerifier;
+use dashboard::DashboardManager;
+use disputes::DisputeManager;
+use escrow::ReputationEscrowManager;
+use evm_attestations::EvmAttestationManager;
+use evm_bridge::EvmBridgeManager;
+use feature_flags::FeatureFlagManager;
+use federation::FederationManager;
+use funding::FundingManager;
+use grants::GrantManager;
+use hiring::HiringManager;
+use impersonation::ImpersonationManager;
+use inactivity::InactivityManager;
+use ingress::IngressFilter;
+use jobs::JobRunner;
+use migrations::MigrationManager;
+use moderation::ModerationManager;
+use moderator_notes::ModeratorNoteManager;
+use monitoring::MonitoringSystem;
+use notifications::NotificationManager;
+use open_data::OpenDataManager;
+use ownership::OwnershipManager;
+use partners::PartnerManager;
+use policy::PolicyLog;
+use private_messages::PrivateMessageManager;
+use proof_migration::ProofMigrationManager;
+use public_api::PublicApiManager;
+use relationships::RelationshipManager;
+use replication::ReplicationManager;
+use reputation::ReputationJournal;
+use reserved::ReservedNameManager;
+use reviewers::ReviewerPoolManager;
+use rotation::RotationManager;
+use saved_searches::SavedSearchManager;
+use security::SecurityLog;
 use ic_cdk::api::management_canister::http_request::TransformArgs;
+use settings::SettingsManager;
+use sharding::ShardManager;
+#[cfg(feature = "simulation")]
+use simulation::SimulationManager;
+use sitemap::SitemapManager;
+use staking::ReputationStakingManager;
 use storage::StorageManager;
+use tags::TagManager;
+use tiers::TierEngine;
+use usage::UsageTracker;
+use timelock::TimelockManager;
 use types::{
-    ChainType, Company, CommunityValidation, CommunityValidationStats, CreateCompanyRequest, 
-    CrossChainChallenge, CrossChainVerificationRequest, DomainVerificationChallenge, Endorsement, 
-    ProofCheckResult, ProofStatus, RegistryResult, ReportType, ReputationLeaderboard, SearchFilters, 
-    Testimonial, UpdateCompanyRequest, VerificationResult, VerificationType, Vouch,
+    AddressLineage, AddressRotation, Admin, AlertRoutingRule, ApiKey, ApiKeyScope, AlertSeverity, AlertType, AlertVisibility,
+    ApiProvider, ArchivedCompany, AuditLogEntry, BannerSeverity, BlocklistEntry, CacheInvalidationHook, CampaignProgress,
+    CampaignTask, CanisterSnapshot, ChainType,
+    ChainStatistics, CommunityAlert, CommunityPage, CommunitySortOrder, CommunityValidation,
+    CommunityValidationStats, Company, CompanyEvent, CompanyManager, CompanyRelationship, CompanyRole, CompanySection,
+    CommunityCooldownPolicy, CompanyStatus, Concern, ConcernSummary, CompanyStatusChange,
+    ContactChannel, ContactChannelMatch, ContactChannelType, ContractWatch, CorporateGraph,
+    CreateCampaignRequest, CreateCompanyRequest, CriticalAction, CriticalActionProposal, CrossChainChallenge,
+    CrossChainVerificationRequest, DataMigration, DomainVerificationChallenge, Endorsement, EntityType, FeatureFlag,
+    FederationExport, FederationPeer,
+    FundingInfo, FundingRound, GrantAmountRange, GrantAttestation, GrantProgram, GroupSignals,
+    HiringInfo, HttpRequest, HttpResponse, IssuedApiKey, ImpersonationChannel, ImpersonationReport,
+    ImpersonationWarning, InactivityPolicy, InvestmentRecord, JobLink, ListingAttestation, MigrationKind, ModerationTask, ModerationTaskKind,
+    ModerationTaskStatus, Moderator, ModeratorWorkload, MonitoringConfig, Notification,
+    NotificationChannel, OpenDataSnapshotInfo, OwnerDashboard, OwnershipTransfer, Partner, PartnerCallback,
+    PartnerSubscription, PolicyChange, ProposalStatus, ProviderEndpoint, ProviderHealth,
+    RegistryResult, RegistrySearchPage, RelationshipKind, ReputationEvent, ReputationLeaderboard, ReputationRecomputation, ReputationStake, ReputationStakingConfig, ReserveNameRequest, ReverificationCampaign, ReservedName,
+    RegistryIdentity, ReviewCase, ReviewCaseStatus, Reviewer, ReviewerPoolConfig, SavedSearch, ScoreWeights,
+    SearchFilters, SearchResult, SecurityEvent, SecurityEventFilter, TagProposal, TagStats, TagStatus, Testimonial, TimelockAction,
+    TimelockConfig, TimelockStatus, TimelockedAction, TreasurySnapshot, UpdateCompanyRequest,
+    RateLimitQuota, UsageMetric, UsageStats, VerificationResult, VerificationSummary, VerificationType, Vouch,
+    WarningBanner, WebhookSubscription, WebhookTarget,
 };
 use verification::VerificationManager;
+use verification_guard::VerificationGuard;
+use webhooks::WebhookManager;
+use widget::WidgetManager;
 use std::collections::HashMap;
 
+// Call guards: every update is counted towards its caller's usage, and a read replica
+// refuses updates. Queries aren't counted: their state changes are discarded.
+fn track_update() -> Result<(), String> {
+    ReplicationManager::ensure_writable(ic_cdk::caller())?;
+    UsageTracker::record_update(ic_cdk::caller());
+    Ok(())
+}
+
+// Upgraded HTTP reads only touch local serving state (cache, rate limits, key usage), so
+// a replica answers them too
+fn track_http_update() -> Result<(), String> {
+    UsageTracker::record_update(ic_cdk::caller());
+    Ok(())
+}
+
+// Canister lifecycle
+// Timers don't survive an upgrade, so init and post_upgrade both register them
+fn start_timers() {
+    UsageTracker::start_timer();
+    ReplicationManager::start_timer();
+    PublicApiManager::start_timer();
+    // A read replica only mirrors its source; the jobs below write to companies
+    if ReplicationManager::is_replica() {
+        return;
+    }
+    MonitoringSystem::start_timers();
+    TimelockManager::start_timer();
+    AdminManager::start_timer();
+    OpenDataManager::start_timer();
+    SitemapManager::start_timer();
+    CacheHookManager::start_timer();
+    SavedSearchManager::start_timer();
+    InactivityManager::start_timer();
+    CampaignManager::start_timer();
+    PartnerManager::start_timer();
+    ChainStatsManager::start_timer();
+    CommunityValidationManager::start_timer();
+    ReputationEscrowManager::start_timer();
+    TierEngine::start_timer();
+    EvmBridgeManager::start_timer();
+    ConcernManager::start_timer();
+    WebhookManager::start_timer();
+    FederationManager::start_timer();
+    ShardManager::start_timer();
+}
+
+#[ic_cdk::init]
+fn init(args: Option<types::RegistryInitArgs>) {
+    ReplicationManager::configure(args);
+    start_timers();
+}
+
+#[ic_cdk::pre_upgrade]
+fn pre_upgrade() {
+    StorageManager::snapshot_provider_health();
+}
+
+#[ic_cdk::post_upgrade]
+fn post_upgrade(args: Option<types::RegistryInitArgs>) {
+    StorageManager::restore_provider_health();
+    ReplicationManager::configure(args);
+    start_timers();
+}
+
+// Ingress filter for update calls; rejected messages are never executed
+#[ic_cdk::inspect_message]
+fn inspect_message() {
+    let method = ic_cdk::api::call::method_name();
+    let arg_bytes = ic_cdk::api::call::arg_data_raw_size();
+    match IngressFilter::accepts(&method, ic_cdk::caller(), arg_bytes) {
+        Ok(()) => ic_cdk::api::call::accept_message(),
+        // Trapping rejects the message and hands the reason back to the caller
+        Err(err) => ic_cdk::trap(&err),
+    }
+}
+
 // Core CRUD API endpoints
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "track_update")]
 pub fn create_company(request: CreateCompanyRequest) -> RegistryResult<String> {
     let caller = ic_cdk::caller();
-    RegistryAPI::create_company(request, caller)
+    RegistryAPI::create_company(request, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_company(company_id: String, sections: Option<Vec<CompanySection>>) -> RegistryResult<Company> {
+    RegistryAPI::get_company(company_id, sections)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn update_company(request: UpdateCompanyRequest) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    RegistryAPI::update_company(request, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn add_company_manager(
+    company_id: String,
+    principal: Principal,
+    role: CompanyRole,
+) -> RegistryResult<Vec<CompanyManager>> {
+    let caller = ic_cdk::caller();
+    OwnershipManager::add_company_manager(company_id, principal, role, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn remove_company_manager(company_id: String, principal: Principal) -> RegistryResult<Vec<CompanyManager>> {
+    let caller = ic_cdk::caller();
+    OwnershipManager::remove_company_manager(company_id, principal, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_company_managers(company_id: String) -> RegistryResult<Vec<CompanyManager>> {
+    OwnershipManager::get_company_managers(company_id)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn transfer_company_ownership(company_id: String, new_principal: Principal) -> RegistryResult<OwnershipTransfer> {
+    let caller = ic_cdk::caller();
+    OwnershipManager::transfer_company_ownership(company_id, new_principal, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn accept_company_ownership(company_id: String) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    OwnershipManager::accept_company_ownership(company_id, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn cancel_ownership_transfer(company_id: String) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    OwnershipManager::cancel_ownership_transfer(company_id, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_my_ownership_transfers() -> Vec<OwnershipTransfer> {
+    let caller = ic_cdk::caller();
+    OwnershipManager::get_my_ownership_transfers(caller)
+}
+
+#[ic_cdk::query]
+pub fn list_companies(
+    offset: Option<u32>,
+    limit: Option<u32>,
+    filters: Option<SearchFilters>,
+    sections: Option<Vec<CompanySection>>,
+) -> Vec<Company> {
+    RegistryAPI::list_companies(offset, limit, filters, sections)
+}
+
+// Archival endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn archive_company(company_id: String) -> RegistryResult<ArchivedCompany> {
+    let caller = ic_cdk::caller();
+    ArchiveManager::archive_company(company_id, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn delete_company(company_id: String) -> RegistryResult<ArchivedCompany> {
+    let caller = ic_cdk::caller();
+    ArchiveManager::delete_company(company_id, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn restore_company(company_id: String) -> RegistryResult<Company> {
+    let caller = ic_cdk::caller();
+    ArchiveManager::restore_company(company_id, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_my_archived_companies() -> Vec<ArchivedCompany> {
+    let caller = ic_cdk::caller();
+    ArchiveManager::get_my_archived_companies(caller)
+}
+
+#[ic_cdk::query]
+pub fn list_archived_companies() -> RegistryResult<Vec<ArchivedCompany>> {
+    let caller = ic_cdk::caller();
+    ArchiveManager::list_archived_companies(caller)
+}
+
+#[ic_cdk::query]
+pub fn search_companies(query: String) -> Vec<SearchResult> {
+    RegistryAPI::search_companies(query)
+}
+
+#[ic_cdk::query]
+pub fn search_companies_ranked(
+    query: String,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> CommunityPage<SearchResult> {
+    RegistryAPI::search_companies_ranked(query, offset, limit)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn rebuild_search_index() -> RegistryResult<u64> {
+    let caller = ic_cdk::caller();
+    RegistryAPI::rebuild_search_index(caller)
+}
+
+#[ic_cdk::query]
+pub fn get_company_count() -> u64 {
+    RegistryAPI::get_company_count()
+}
+
+#[ic_cdk::query]
+pub fn get_statistics() -> HashMap<String, u64> {
+    RegistryAPI::get_statistics()
+}
+
+#[ic_cdk::query]
+pub fn get_chain_statistics() -> Vec<ChainStatistics> {
+    ChainStatsManager::get_chain_statistics()
+}
+
+// Verification API endpoints
+#[ic_cdk::update(guard = "track_update")]
+async fn verify_github_organization(
+    company_id: String,
+    github_org: String,
+) -> RegistryResult<VerificationResult> {
+    let caller = ic_cdk::caller();
+    let target = VerificationGuard::target("github", &company_id, &github_org);
+    VerificationGuard::run(
+        caller,
+        target,
+        VerificationManager::verify_github_organization(company_id, github_org, caller),
+    )
+    .await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+fn create_domain_verification_challenge(
+    company_id: String,
+) -> RegistryResult<DomainVerificationChallenge> {
+    let caller = ic_cdk::caller();
+    VerificationManager::create_domain_verification_challenge(company_id, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+async fn verify_domain_ownership(company_id: String) -> RegistryResult<VerificationResult> {
+    let caller = ic_cdk::caller();
+    let target = VerificationGuard::target("domain", &company_id, "");
+    VerificationGuard::run(caller, target, VerificationManager::verify_domain_ownership(company_id, caller)).await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+async fn preview_domain_check(company_id: String) -> RegistryResult<types::ProofPreview> {
+    let caller = ic_cdk::caller();
+    VerificationManager::preview_domain_check(company_id, caller).await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+async fn preview_social_proof(
+    company_id: String,
+    platform: String,
+    proof_url: String,
+) -> RegistryResult<types::ProofPreview> {
+    let caller = ic_cdk::caller();
+    VerificationManager::preview_social_proof(company_id, platform, proof_url, caller).await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+fn verify_social_media_manual(
+    company_id: String,
+    platform: String,
+    proof_url: String,
+) -> RegistryResult<VerificationResult> {
+    let caller = ic_cdk::caller();
+    VerificationManager::verify_social_media_manual(company_id, platform, proof_url, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+fn verify_social_media_with_proof(
+    company_id: String,
+    platform: String,
+    proof_url: String,
+) -> RegistryResult<VerificationResult> {
+    let caller = ic_cdk::caller();
+    VerificationManager::verify_social_media_with_proof(company_id, platform, proof_url, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+fn request_proof_recheck(company_id: String, proof_id: String) -> RegistryResult<String> {
+    let caller = ic_cdk::caller();
+    VerificationManager::request_proof_recheck(company_id, proof_id, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+async fn migrate_proof(
+    company_id: String,
+    old_proof_url: String,
+    new_proof_url: String,
+) -> RegistryResult<VerificationResult> {
+    let caller = ic_cdk::caller();
+    let target = VerificationGuard::target("migration", &company_id, &old_proof_url);
+    VerificationGuard::run(
+        caller,
+        target,
+        ProofMigrationManager::migrate_proof(company_id, old_proof_url, new_proof_url, caller),
+    )
+    .await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+async fn verify_proof_still_exists(
+    company_id: String,
+    proof_url: String,
+) -> RegistryResult<types::ProofCheckResult> {
+    let caller = ic_cdk::caller();
+    VerificationManager::verify_proof_still_exists(company_id, proof_url, caller).await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+async fn validate_proof_content(
+    company_id: String,
+    proof_url: String,
+) -> RegistryResult<types::ProofCheckResult> {
+    let caller = ic_cdk::caller();
+    VerificationManager::validate_proof_content(company_id, proof_url, caller).await
+}
+
+#[ic_cdk::query]
+fn resolve_proof(proof_id: String) -> Option<types::ResolvedProof> {
+    VerificationManager::resolve_proof(proof_id)
+}
+
+#[ic_cdk::query]
+fn get_proof_monitoring(company_id: String, proof_id: String) -> Option<types::ProofMonitoring> {
+    VerificationManager::get_proof_monitoring(company_id, proof_id)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+fn report_verification_issue(
+    company_id: String,
+    proof_id: String,
+    report_type: types::ReportType,
+    evidence: String,
+) -> RegistryResult<String> {
+    let caller = ic_cdk::caller();
+    VerificationManager::report_verification_issue(company_id, proof_id, report_type, evidence, caller)
+}
+
+// Verification utility endpoints
+#[ic_cdk::query]
+fn get_domain_verification_challenge(company_id: String) -> Option<DomainVerificationChallenge> {
+    StorageManager::get_domain_challenge(&company_id)
+}
+
+#[ic_cdk::query]
+fn get_verification_instructions(verification_type: VerificationType) -> String {
+    VerificationManager::get_verification_instructions(verification_type)
+}
+
+#[ic_cdk::query]
+fn get_score_weights(entity_type: EntityType) -> ScoreWeights {
+    VerificationManager::score_weights(&entity_type)
+}
+
+#[ic_cdk::query]
+fn get_verification_summary(company_id: String) -> RegistryResult<VerificationSummary> {
+    VerificationManager::get_verification_summary(company_id)
+}
+
+// Cross-chain verification API endpoints
+#[ic_cdk::update(guard = "track_update")]
+async fn create_crosschain_challenge(
+    request: CrossChainVerificationRequest,
+) -> RegistryResult<CrossChainChallenge> {
+    let caller = ic_cdk::caller();
+    CrossChainVerifier::create_crosschain_challenge(request, caller).await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+async fn verify_ethereum_contract(
+    company_id: String,
+    contract_address: String,
+    transaction_hash: Option<String>,
+    signature: Option<String>,
+) -> RegistryResult<VerificationResult> {
+    let caller = ic_cdk::caller();
+    let target = VerificationGuard::target("ethereum", &company_id, &contract_address);
+    VerificationGuard::run(
+        caller,
+        target,
+        CrossChainVerifier::verify_ethereum_contract(company_id, contract_address, transaction_hash, signature),
+    )
+    .await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+async fn verify_bitcoin_address(
+    company_id: String,
+    bitcoin_address: String,
+    signature: Option<String>,
+) -> RegistryResult<VerificationResult> {
+    let caller = ic_cdk::caller();
+    let target = VerificationGuard::target("bitcoin", &company_id, &bitcoin_address);
+    VerificationGuard::run(
+        caller,
+        target,
+        CrossChainVerifier::verify_bitcoin_address(company_id, bitcoin_address, signature),
+    )
+    .await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+async fn verify_solana_address(
+    company_id: String,
+    solana_address: String,
+) -> RegistryResult<VerificationResult> {
+    let caller = ic_cdk::caller();
+    let target = VerificationGuard::target("solana", &company_id, &solana_address);
+    VerificationGuard::run(
+        caller,
+        target,
+        CrossChainVerifier::verify_solana_address(company_id, solana_address),
+    )
+    .await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+async fn verify_ton_address(
+    company_id: String,
+    ton_address: String,
+) -> RegistryResult<VerificationResult> {
+    let caller = ic_cdk::caller();
+    let target = VerificationGuard::target("ton", &company_id, &ton_address);
+    VerificationGuard::run(
+        caller,
+        target,
+        CrossChainVerifier::verify_ton_address(company_id, ton_address),
+    )
+    .await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+async fn verify_icp_canister(
+    company_id: String,
+    canister_id: String,
+) -> RegistryResult<VerificationResult> {
+    let caller = ic_cdk::caller();
+    let target = VerificationGuard::target("icp", &company_id, &canister_id);
+    VerificationGuard::run(caller, target, CrossChainVerifier::verify_icp_canister(company_id, canister_id)).await
+}
+
+#[ic_cdk::query]
+fn get_crosschain_verification_instructions(chain_type: ChainType) -> String {
+    CrossChainVerifier::get_crosschain_verification_instructions(chain_type)
+}
+
+#[ic_cdk::query]
+fn get_crosschain_challenges_for_company(company_id: String) -> Vec<CrossChainChallenge> {
+    StorageManager::get_crosschain_challenges_for_company(&company_id)
+}
+
+// Provider settings endpoints (admin only)
+#[ic_cdk::query]
+fn get_provider_health() -> RegistryResult<Vec<ProviderHealth>> {
+    let caller = ic_cdk::caller();
+    SettingsManager::get_provider_health(caller)
+}
+
+#[ic_cdk::query]
+fn get_provider_endpoints(provider: ApiProvider) -> Vec<ProviderEndpoint> {
+    SettingsManager::get_provider_endpoints(&provider)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+fn set_provider_endpoints(
+    provider: ApiProvider,
+    endpoints: Vec<ProviderEndpoint>,
+) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    SettingsManager::set_provider_endpoints(provider, endpoints, caller)
+}
+
+#[ic_cdk::query]
+fn get_provider_quorum(provider: ApiProvider) -> u32 {
+    SettingsManager::get_provider_quorum(&provider)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+fn set_provider_quorum(provider: ApiProvider, quorum: Option<u32>) -> RegistryResult<u32> {
+    let caller = ic_cdk::caller();
+    SettingsManager::set_provider_quorum(provider, quorum, caller)
+}
+
+#[ic_cdk::query]
+fn get_api_configs() -> RegistryResult<Vec<types::ApiConfigInfo>> {
+    let caller = ic_cdk::caller();
+    SettingsManager::get_api_configs(caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+fn set_api_config(
+    provider: ApiProvider,
+    base_url: Option<String>,
+    api_key: Option<String>,
+) -> RegistryResult<Option<types::ApiConfigInfo>> {
+    let caller = ic_cdk::caller();
+    SettingsManager::set_api_config(provider, base_url, api_key, caller)
+}
+
+// Reserved name endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn reserve_name(request: ReserveNameRequest) -> RegistryResult<ReservedName> {
+    let caller = ic_cdk::caller();
+    ReservedNameManager::reserve_name(request, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn release_reserved_name(name: String) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    ReservedNameManager::release_reserved_name(name, caller)
+}
+
+#[ic_cdk::query]
+pub fn list_reserved_names() -> Vec<ReservedName> {
+    ReservedNameManager::list_reserved_names()
+}
+
+#[ic_cdk::query]
+pub fn check_reserved_name(name: String) -> Vec<ReservedName> {
+    ReservedNameManager::find_matching_reservations(&name)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn claim_reserved_name(company_id: String, name: String) -> RegistryResult<ReservedName> {
+    let caller = ic_cdk::caller();
+    ReservedNameManager::claim_reserved_name(company_id, name, caller)
+}
+
+// Registry identity endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn set_registry_identity(canister_ids: Vec<String>, domains: Vec<String>) -> RegistryResult<RegistryIdentity> {
+    let caller = ic_cdk::caller();
+    CanaryGuard::set_registry_identity(canister_ids, domains, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_registry_identity() -> Option<RegistryIdentity> {
+    CanaryGuard::get_registry_identity()
+}
+
+// Funding disclosure endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn set_company_funding(
+    company_id: String,
+    rounds: Vec<FundingRound>,
+) -> RegistryResult<FundingInfo> {
+    let caller = ic_cdk::caller();
+    FundingManager::set_funding(company_id, rounds, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_company_funding(company_id: String) -> RegistryResult<Option<FundingInfo>> {
+    FundingManager::get_funding(company_id)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub async fn check_funding_links(company_id: String) -> RegistryResult<FundingInfo> {
+    let caller = ic_cdk::caller();
+    FundingManager::check_funding_links(company_id, caller).await
+}
+
+#[ic_cdk::query]
+pub fn get_investments_by_investor(investor_company_id: String) -> RegistryResult<Vec<InvestmentRecord>> {
+    FundingManager::get_investments_by_investor(investor_company_id)
+}
+
+// Contact channel endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn add_contact_channel(
+    company_id: String,
+    channel_type: ContactChannelType,
+    value: String,
+    label: String,
+) -> RegistryResult<ContactChannel> {
+    let caller = ic_cdk::caller();
+    ContactChannelManager::add_contact_channel(company_id, channel_type, value, label, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn remove_contact_channel(company_id: String, channel_id: String) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    ContactChannelManager::remove_contact_channel(company_id, channel_id, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub async fn verify_contact_channel(company_id: String, channel_id: String) -> RegistryResult<ContactChannel> {
+    let caller = ic_cdk::caller();
+    ContactChannelManager::verify_contact_channel(company_id, channel_id, caller).await
+}
+
+#[ic_cdk::query]
+pub fn get_contact_channels(company_id: String) -> RegistryResult<Vec<ContactChannel>> {
+    ContactChannelManager::get_contact_channels(company_id)
+}
+
+#[ic_cdk::query]
+pub fn check_contact_channel(channel_type: ContactChannelType, value: String) -> ContactChannelMatch {
+    ContactChannelManager::check_contact_channel(channel_type, value)
+}
+
+// API key endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub async fn issue_api_key(label: String, scope: ApiKeyScope) -> RegistryResult<IssuedApiKey> {
+    let caller = ic_cdk::caller();
+    ApiKeyManager::issue_api_key(label, scope, caller).await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn revoke_api_key(key_id: String) -> RegistryResult<ApiKey> {
+    let caller = ic_cdk::caller();
+    ApiKeyManager::revoke_api_key(key_id, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn set_api_key_rate_limit(key_id: String, rate_limit_per_minute: u32) -> RegistryResult<ApiKey> {
+    let caller = ic_cdk::caller();
+    ApiKeyManager::set_api_key_rate_limit(key_id, rate_limit_per_minute, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_my_api_keys() -> Vec<ApiKey> {
+    let caller = ic_cdk::caller();
+    ApiKeyManager::get_my_api_keys(caller)
+}
+
+#[ic_cdk::query]
+pub fn list_api_keys() -> RegistryResult<Vec<ApiKey>> {
+    let caller = ic_cdk::caller();
+    ApiKeyManager::list_api_keys(caller)
+}
+
+// Usage endpoints
+#[ic_cdk::query]
+pub fn get_my_usage() -> UsageStats {
+    let caller = ic_cdk::caller();
+    UsageTracker::get_my_usage(caller)
+}
+
+#[ic_cdk::query]
+pub fn get_my_rate_limit_status() -> Vec<RateLimitQuota> {
+    let caller = ic_cdk::caller();
+    UsageTracker::get_my_rate_limit_status(caller)
+}
+
+#[ic_cdk::query]
+pub fn get_top_consumers(metric: UsageMetric, limit: Option<u32>) -> RegistryResult<Vec<UsageStats>> {
+    let caller = ic_cdk::caller();
+    UsageTracker::get_top_consumers(metric, limit, caller)
+}
+
+// Community tag endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn propose_tag(company_id: String, tag: String) -> RegistryResult<TagProposal> {
+    let caller = ic_cdk::caller();
+    TagManager::propose_tag(company_id, tag, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn review_tag(company_id: String, tag: String, approve: bool) -> RegistryResult<TagProposal> {
+    let caller = ic_cdk::caller();
+    TagManager::review_tag(company_id, tag, approve, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn remove_tag(company_id: String, tag: String) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    TagManager::remove_tag(company_id, tag, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_tag_proposals(company_id: String, status: Option<TagStatus>) -> Vec<TagProposal> {
+    TagManager::get_tag_proposals(company_id, status)
+}
+
+#[ic_cdk::query]
+pub fn get_tag_stats() -> Vec<TagStats> {
+    TagManager::get_tag_stats()
+}
+
+// Saved search endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn save_search(name: String, filters: SearchFilters) -> RegistryResult<SavedSearch> {
+    let caller = ic_cdk::caller();
+    SavedSearchManager::save_search(name, filters, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn delete_saved_search(search_id: String) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    SavedSearchManager::delete_saved_search(search_id, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_my_saved_searches() -> Vec<SavedSearch> {
+    let caller = ic_cdk::caller();
+    SavedSearchManager::get_my_saved_searches(caller)
+}
+
+// Company writes in sequence order, for incremental consumers
+#[ic_cdk::query]
+pub fn get_company_events(after_seq: Option<u64>, limit: Option<u32>) -> Vec<CompanyEvent> {
+    StorageManager::get_company_events_after(after_seq.unwrap_or(0), limit.unwrap_or(100).min(1000) as usize)
+}
+
+// Data migration endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn start_migration(kind: MigrationKind) -> RegistryResult<DataMigration> {
+    let caller = ic_cdk::caller();
+    MigrationManager::start_migration(kind, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn run_migration_batch(migration_id: String, batch_size: Option<u32>) -> RegistryResult<DataMigration> {
+    let caller = ic_cdk::caller();
+    MigrationManager::run_migration_batch(migration_id, batch_size, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn cancel_migration(migration_id: String) -> RegistryResult<DataMigration> {
+    let caller = ic_cdk::caller();
+    MigrationManager::cancel_migration(migration_id, caller)
+}
+
+#[ic_cdk::query]
+pub fn list_migrations() -> RegistryResult<Vec<DataMigration>> {
+    let caller = ic_cdk::caller();
+    MigrationManager::list_migrations(caller)
+}
+
+// Feature flag endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn set_feature_flag(name: String, enabled: bool, rollout_percent: u8) -> RegistryResult<FeatureFlag> {
+    let caller = ic_cdk::caller();
+    FeatureFlagManager::set_feature_flag(name, enabled, rollout_percent, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn reset_feature_flag(name: String) -> RegistryResult<FeatureFlag> {
+    let caller = ic_cdk::caller();
+    FeatureFlagManager::reset_feature_flag(name, caller)
+}
+
+#[ic_cdk::query]
+pub fn list_feature_flags() -> Vec<FeatureFlag> {
+    FeatureFlagManager::list_feature_flags()
+}
+
+#[ic_cdk::query]
+pub fn list_active_feature_flags() -> Vec<FeatureFlag> {
+    FeatureFlagManager::list_active_feature_flags()
+}
+
+#[ic_cdk::query]
+pub fn is_feature_enabled(name: String) -> bool {
+    FeatureFlagManager::is_enabled(&name, ic_cdk::caller())
+}
+
+// Simulation mode endpoints (builds with the `simulation` feature only)
+#[cfg(feature = "simulation")]
+#[ic_cdk::update(guard = "track_update")]
+pub fn set_simulation_fixture(fixture: types::SimulationFixture) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    SimulationManager::set_simulation_fixture(fixture, caller)
+}
+
+#[cfg(feature = "simulation")]
+#[ic_cdk::update(guard = "track_update")]
+pub fn remove_simulation_fixture(url_prefix: String, body_contains: Option<String>) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    SimulationManager::remove_simulation_fixture(url_prefix, body_contains, caller)
+}
+
+#[cfg(feature = "simulation")]
+#[ic_cdk::update(guard = "track_update")]
+pub fn clear_simulation_fixtures() -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    SimulationManager::clear_simulation_fixtures(caller)
+}
+
+#[cfg(feature = "simulation")]
+#[ic_cdk::update(guard = "track_update")]
+pub fn set_simulation_mode(enabled: bool) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    SimulationManager::set_simulation_mode(enabled, caller)
+}
+
+#[cfg(feature = "simulation")]
+#[ic_cdk::query]
+pub fn get_simulation_status() -> types::SimulationStatus {
+    SimulationManager::get_simulation_status()
+}
+
+// Cache invalidation hook endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn set_cache_invalidation_hook(target: Principal, method: String) -> RegistryResult<CacheInvalidationHook> {
+    let caller = ic_cdk::caller();
+    CacheHookManager::set_cache_invalidation_hook(target, method, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn remove_cache_invalidation_hook() -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    CacheHookManager::remove_cache_invalidation_hook(caller)
+}
+
+#[ic_cdk::query]
+pub fn get_cache_invalidation_hook() -> RegistryResult<Option<CacheInvalidationHook>> {
+    let caller = ic_cdk::caller();
+    CacheHookManager::get_cache_invalidation_hook(caller)
+}
+
+// Sitemap endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn set_sitemap_base_url(base_url: String) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    SitemapManager::set_sitemap_base_url(base_url, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn rebuild_sitemap() -> RegistryResult<u32> {
+    let caller = ic_cdk::caller();
+    SitemapManager::rebuild_sitemap(caller)
+}
+
+// Open data endpoints
+#[ic_cdk::query]
+pub fn http_request(request: HttpRequest) -> HttpResponse {
+    if ApiKeyManager::is_api_path(&request.url) {
+        return ApiKeyManager::http_request(request);
+    }
+    if PublicApiManager::is_public_path(&request.url) {
+        return PublicApiManager::http_request(request);
+    }
+    if request.url.split('?').next() == Some(SitemapManager::SITEMAP_PATH) {
+        return SitemapManager::http_request();
+    }
+    if WidgetManager::is_widget_path(&request.url) {
+        return WidgetManager::http_request(request);
+    }
+    OpenDataManager::http_request(request)
+}
+
+// Keyed REST requests and public cache misses land here after the query path asks for an upgrade
+#[ic_cdk::update(guard = "track_http_update")]
+pub fn http_request_update(request: HttpRequest) -> HttpResponse {
+    if PublicApiManager::is_public_path(&request.url) {
+        return PublicApiManager::http_request_update(request, ic_cdk::caller());
+    }
+    ApiKeyManager::http_request_update(request)
+}
+
+#[ic_cdk::query]
+pub fn get_open_data_snapshot_info() -> Option<OpenDataSnapshotInfo> {
+    OpenDataManager::get_open_data_snapshot_info()
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn run_open_data_snapshot() -> RegistryResult<OpenDataSnapshotInfo> {
+    let caller = ic_cdk::caller();
+    OpenDataManager::run_open_data_snapshot(caller)
+}
+
+// Two-person rule endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn propose_critical_action(action: CriticalAction, reason: String) -> RegistryResult<CriticalActionProposal> {
+    let caller = ic_cdk::caller();
+    CriticalActionManager::propose_critical_action(action, reason, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn approve_critical_action(proposal_id: String) -> RegistryResult<CriticalActionProposal> {
+    let caller = ic_cdk::caller();
+    CriticalActionManager::approve_critical_action(proposal_id, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn cancel_critical_action(proposal_id: String) -> RegistryResult<CriticalActionProposal> {
+    let caller = ic_cdk::caller();
+    CriticalActionManager::cancel_critical_action(proposal_id, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_critical_action_proposals(
+    status: Option<ProposalStatus>,
+) -> RegistryResult<Vec<CriticalActionProposal>> {
+    let caller = ic_cdk::caller();
+    CriticalActionManager::get_critical_action_proposals(status, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_blocklist() -> RegistryResult<Vec<BlocklistEntry>> {
+    let caller = ic_cdk::caller();
+    BlocklistManager::get_blocklist(caller)
+}
+
+#[ic_cdk::query]
+pub fn get_admin_audit_log(offset: Option<u32>, limit: Option<u32>) -> RegistryResult<Vec<AuditLogEntry>> {
+    let caller = ic_cdk::caller();
+    AuditManager::get_admin_audit_log(offset, limit, caller)
+}
+
+// Timelock endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn schedule_admin_action(action: TimelockAction) -> RegistryResult<TimelockedAction> {
+    let caller = ic_cdk::caller();
+    TimelockManager::schedule_admin_action(action, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn cancel_admin_action(action_id: String) -> RegistryResult<TimelockedAction> {
+    let caller = ic_cdk::caller();
+    TimelockManager::cancel_admin_action(action_id, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn execute_admin_action(action_id: String) -> RegistryResult<TimelockedAction> {
+    TimelockManager::execute_admin_action(action_id)
+}
+
+#[ic_cdk::query]
+pub fn get_timelocked_actions(status: Option<TimelockStatus>) -> Vec<TimelockedAction> {
+    TimelockManager::get_timelocked_actions(status)
+}
+
+#[ic_cdk::query]
+pub fn get_timelock_config() -> TimelockConfig {
+    TimelockManager::get_timelock_config()
+}
+
+#[ic_cdk::query]
+pub fn get_policy_history(
+    parameter: Option<String>,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> CommunityPage<PolicyChange> {
+    PolicyLog::get_policy_history(parameter, offset, limit)
+}
+
+// Reviewer pool endpoints
+#[ic_cdk::query]
+pub fn get_reviewer_pool_config() -> ReviewerPoolConfig {
+    ReviewerPoolManager::get_reviewer_pool_config()
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub async fn join_reviewer_pool(amount: u64) -> RegistryResult<Reviewer> {
+    let caller = ic_cdk::caller();
+    ReviewerPoolManager::join_reviewer_pool(amount, caller).await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub async fn leave_reviewer_pool() -> RegistryResult<Nat> {
+    let caller = ic_cdk::caller();
+    ReviewerPoolManager::leave_reviewer_pool(caller).await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub async fn claim_reviewer_rewards() -> RegistryResult<Nat> {
+    let caller = ic_cdk::caller();
+    ReviewerPoolManager::claim_reviewer_rewards(caller).await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub async fn open_review_case(task_id: String) -> RegistryResult<ReviewCase> {
+    let caller = ic_cdk::caller();
+    ReviewerPoolManager::open_review_case(task_id, caller).await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn cast_review_vote(task_id: String, uphold: bool) -> RegistryResult<ReviewCase> {
+    let caller = ic_cdk::caller();
+    ReviewerPoolManager::cast_review_vote(task_id, uphold, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn tally_review_case(task_id: String) -> RegistryResult<ReviewCase> {
+    let caller = ic_cdk::caller();
+    ReviewerPoolManager::tally_review_case(task_id, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_reviewer(principal: Principal) -> Option<Reviewer> {
+    ReviewerPoolManager::get_reviewer(principal)
+}
+
+#[ic_cdk::query]
+pub fn get_reviewer_pool() -> Vec<Reviewer> {
+    ReviewerPoolManager::get_reviewer_pool()
+}
+
+#[ic_cdk::query]
+pub fn get_review_case(task_id: String) -> Option<ReviewCase> {
+    ReviewerPoolManager::get_review_case(task_id)
+}
+
+#[ic_cdk::query]
+pub fn get_review_cases(status: Option<ReviewCaseStatus>) -> Vec<ReviewCase> {
+    ReviewerPoolManager::get_review_cases(status)
+}
+
+// Registry admin endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn add_admin(principal: Principal) -> RegistryResult<Admin> {
+    let caller = ic_cdk::caller();
+    AdminManager::add_admin(principal, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn remove_admin(principal: Principal) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    AdminManager::remove_admin(principal, caller)
+}
+
+#[ic_cdk::query]
+pub fn list_admins() -> RegistryResult<Vec<Admin>> {
+    let caller = ic_cdk::caller();
+    AdminManager::list_admins(caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn suspend_company(company_id: String, reason: String) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    AdminManager::suspend_company(company_id, reason, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn confirm_company_suspension(company_id: String) -> RegistryResult<types::EmergencySuspension> {
+    let caller = ic_cdk::caller();
+    AdminManager::confirm_suspension(company_id, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_emergency_suspensions() -> RegistryResult<Vec<types::EmergencySuspension>> {
+    let caller = ic_cdk::caller();
+    AdminManager::get_emergency_suspensions(caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn reinstate_company(company_id: String) -> RegistryResult<CompanyStatus> {
+    let caller = ic_cdk::caller();
+    AdminManager::reinstate_company(company_id, caller)
+}
+
+// Listing partners
+#[ic_cdk::update(guard = "track_update")]
+fn add_partner(principal: Principal, name: String) -> RegistryResult<Partner> {
+    let caller = ic_cdk::caller();
+    PartnerManager::add_partner(principal, name, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+fn remove_partner(principal: Principal) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    PartnerManager::remove_partner(principal, caller)
+}
+
+#[ic_cdk::query]
+fn list_partners() -> RegistryResult<Vec<Partner>> {
+    let caller = ic_cdk::caller();
+    PartnerManager::list_partners(caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+fn set_partner_callback(callback: Option<PartnerCallback>) -> RegistryResult<Partner> {
+    let caller = ic_cdk::caller();
+    PartnerManager::set_partner_callback(callback, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+fn subscribe_company_status(company_ids: Vec<String>) -> RegistryResult<Vec<PartnerSubscription>> {
+    let caller = ic_cdk::caller();
+    PartnerManager::subscribe_company_status(company_ids, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+fn unsubscribe_company_status(company_ids: Vec<String>) -> RegistryResult<u32> {
+    let caller = ic_cdk::caller();
+    PartnerManager::unsubscribe_company_status(company_ids, caller)
+}
+
+#[ic_cdk::query]
+fn get_partner_subscriptions() -> RegistryResult<Vec<PartnerSubscription>> {
+    let caller = ic_cdk::caller();
+    PartnerManager::get_partner_subscriptions(caller)
+}
+
+#[ic_cdk::query]
+fn get_partner_status_changes(after_seq: u64, limit: Option<u32>) -> RegistryResult<Vec<CompanyStatusChange>> {
+    let caller = ic_cdk::caller();
+    PartnerManager::get_partner_status_changes(after_seq, limit, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+async fn get_attestation_signer() -> RegistryResult<types::EvmSigner> {
+    EvmAttestationManager::get_attestation_signer().await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+async fn request_evm_attestation(company_id: String) -> RegistryResult<types::EvmAttestation> {
+    let caller = ic_cdk::caller();
+    EvmAttestationManager::request_evm_attestation(company_id, caller).await
+}
+
+#[ic_cdk::query]
+fn get_evm_attestation(company_id: String) -> RegistryResult<types::EvmAttestation> {
+    EvmAttestationManager::get_evm_attestation(company_id)
+}
+
+#[ic_cdk::query]
+fn get_evm_bridge_config() -> types::EvmBridgeConfig {
+    EvmBridgeManager::get_evm_bridge_config()
+}
+
+#[ic_cdk::update(guard = "track_update")]
+fn set_evm_bridge_config(config: types::EvmBridgeConfig) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    EvmBridgeManager::set_evm_bridge_config(config, caller)
+}
+
+#[ic_cdk::query]
+fn get_evm_bridge_status() -> types::EvmBridgeStatus {
+    EvmBridgeManager::get_evm_bridge_status()
+}
+
+#[ic_cdk::update(guard = "track_update")]
+async fn run_evm_bridge() -> RegistryResult<types::EvmBridgeStatus> {
+    let caller = ic_cdk::caller();
+    EvmBridgeManager::trigger_evm_bridge(caller).await
+}
+
+#[ic_cdk::query]
+fn get_evm_merkle_proof(company_id: String) -> RegistryResult<types::EvmMerkleProof> {
+    EvmBridgeManager::get_evm_merkle_proof(company_id)
+}
+
+#[ic_cdk::query]
+fn get_listing_attestation(company_id: String) -> RegistryResult<ListingAttestation> {
+    let caller = ic_cdk::caller();
+    PartnerManager::get_listing_attestation(company_id, caller)
+}
+
+// Registry federation
+#[ic_cdk::update(guard = "track_update")]
+fn add_federation_peer(canister_id: Principal, name: String) -> RegistryResult<FederationPeer> {
+    let caller = ic_cdk::caller();
+    FederationManager::add_federation_peer(canister_id, name, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+fn remove_federation_peer(canister_id: Principal) -> RegistryResult<u64> {
+    let caller = ic_cdk::caller();
+    FederationManager::remove_federation_peer(canister_id, caller)
+}
+
+#[ic_cdk::query]
+fn get_federation_peers() -> Vec<FederationPeer> {
+    FederationManager::get_federation_peers()
+}
+
+#[ic_cdk::query]
+fn export_attestations(after: Option<String>, limit: Option<u32>) -> RegistryResult<FederationExport> {
+    let caller = ic_cdk::caller();
+    FederationManager::export_attestations(after, limit, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+async fn sync_federation_peer(canister_id: Principal) -> RegistryResult<u32> {
+    let caller = ic_cdk::caller();
+    FederationManager::sync_federation_peer(canister_id, caller).await
+}
+
+#[ic_cdk::query]
+fn search_registries(
+    query: String,
+    include_federated: Option<bool>,
+    federated_cursor: Option<String>,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> RegistrySearchPage {
+    FederationManager::search_registries(query, include_federated, federated_cursor, offset, limit)
+}
+
+// Replication endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn add_replica(canister_id: Principal) -> RegistryResult<types::ReplicaInfo> {
+    let caller = ic_cdk::caller();
+    ReplicationManager::add_replica(canister_id, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn remove_replica(canister_id: Principal) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    ReplicationManager::remove_replica(canister_id, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_replication_checkpoint() -> types::ReplicationCheckpoint {
+    ReplicationManager::get_replication_checkpoint()
+}
+
+#[ic_cdk::query]
+pub fn get_replication_batch(after_seq: u64, limit: Option<u32>) -> RegistryResult<types::ReplicationBatch> {
+    let caller = ic_cdk::caller();
+    ReplicationManager::get_replication_batch(after_seq, limit, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_replication_snapshot(
+    after: Option<String>,
+    limit: Option<u32>,
+) -> RegistryResult<types::ReplicationSnapshotPage> {
+    let caller = ic_cdk::caller();
+    ReplicationManager::get_replication_snapshot(after, limit, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn report_replica_progress(applied_seq: u64) -> RegistryResult<types::ReplicaInfo> {
+    let caller = ic_cdk::caller();
+    ReplicationManager::report_replica_progress(applied_seq, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub async fn sync_replication() -> RegistryResult<u32> {
+    let caller = ic_cdk::caller();
+    ReplicationManager::sync_replication(caller).await
+}
+
+#[ic_cdk::query]
+pub fn get_replication_status() -> types::ReplicationStatus {
+    ReplicationManager::get_replication_status()
+}
+
+// Sharding endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn add_shard(canister_id: Principal) -> RegistryResult<types::Shard> {
+    let caller = ic_cdk::caller();
+    ShardManager::add_shard(canister_id, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn remove_shard(canister_id: Principal) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    ShardManager::remove_shard(canister_id, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn assign_shard_slots(
+    first_slot: u32,
+    last_slot: u32,
+    canister_id: Principal,
+) -> RegistryResult<types::ShardMap> {
+    let caller = ic_cdk::caller();
+    ShardManager::assign_shard_slots(first_slot, last_slot, canister_id, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub async fn drain_shards() -> RegistryResult<u64> {
+    let caller = ic_cdk::caller();
+    ShardManager::drain_shards(caller).await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn import_shard_companies(companies: Vec<Company>) -> RegistryResult<types::ShardImportResult> {
+    let caller = ic_cdk::caller();
+    ShardManager::import_shard_companies(companies, caller)
+}
+
+#[ic_cdk::query]
+pub fn locate_company(company_id: String) -> types::ShardLocation {
+    ShardManager::locate_company(company_id)
+}
+
+#[ic_cdk::query]
+pub fn get_shard_map() -> types::ShardMap {
+    ShardManager::get_shard_map()
+}
+
+#[ic_cdk::query(composite = true)]
+pub async fn get_global_statistics() -> RegistryResult<HashMap<String, u64>> {
+    ShardManager::get_global_statistics().await
+}
+
+#[ic_cdk::query(composite = true)]
+pub async fn search_all_shards(
+    query: String,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> RegistryResult<CommunityPage<SearchResult>> {
+    ShardManager::search_all_shards(query, offset, limit).await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn force_unverify(
+    company_id: String,
+    verification_type: VerificationType,
+    reason: String,
+) -> RegistryResult<u32> {
+    let caller = ic_cdk::caller();
+    AdminManager::force_unverify(company_id, verification_type, reason, caller)
+}
+
+// Moderation endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn add_moderator(principal: Principal) -> RegistryResult<Moderator> {
+    let caller = ic_cdk::caller();
+    ModerationManager::add_moderator(principal, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn remove_moderator(principal: Principal) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    ModerationManager::remove_moderator(principal, caller)
+}
+
+#[ic_cdk::query]
+pub fn list_moderators() -> RegistryResult<Vec<Moderator>> {
+    let caller = ic_cdk::caller();
+    ModerationManager::list_moderators(caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn open_moderation_task(
+    kind: ModerationTaskKind,
+    company_id: String,
+    reference_id: String,
+    summary: String,
+) -> RegistryResult<ModerationTask> {
+    let caller = ic_cdk::caller();
+    ModerationManager::open_moderation_task(kind, company_id, reference_id, summary, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn assign_moderation_task(task_id: String, assignee: Principal) -> RegistryResult<ModerationTask> {
+    let caller = ic_cdk::caller();
+    ModerationManager::assign_moderation_task(task_id, assignee, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn close_moderation_task(task_id: String, outcome: String) -> RegistryResult<ModerationTask> {
+    let caller = ic_cdk::caller();
+    ModerationManager::close_moderation_task(task_id, outcome, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_moderation_tasks(
+    status: Option<ModerationTaskStatus>,
+    assignee: Option<Principal>,
+) -> RegistryResult<Vec<ModerationTask>> {
+    let caller = ic_cdk::caller();
+    ModerationManager::get_moderation_tasks(status, assignee, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_moderator_workload() -> RegistryResult<Vec<ModeratorWorkload>> {
+    let caller = ic_cdk::caller();
+    ModerationManager::get_moderator_workload(caller)
+}
+
+// Moderator note endpoints
+#[ic_cdk::update(guard = "track_update")]
+async fn get_moderator_notes_public_key() -> RegistryResult<Vec<u8>> {
+    let caller = ic_cdk::caller();
+    ModeratorNoteManager::get_moderator_notes_public_key(caller).await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+async fn derive_moderator_note_key(task_id: String, transport_public_key: Vec<u8>) -> RegistryResult<Vec<u8>> {
+    let caller = ic_cdk::caller();
+    ModeratorNoteManager::derive_moderator_note_key(task_id, transport_public_key, caller).await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn add_moderator_note(task_id: String, ciphertext: Vec<u8>) -> RegistryResult<types::ModeratorNote> {
+    let caller = ic_cdk::caller();
+    ModeratorNoteManager::add_moderator_note(task_id, ciphertext, caller)
+}
+
+// An update call so the read is recorded
+#[ic_cdk::update(guard = "track_update")]
+pub fn get_moderator_notes(task_id: String) -> RegistryResult<Vec<types::ModeratorNote>> {
+    let caller = ic_cdk::caller();
+    ModeratorNoteManager::get_moderator_notes(task_id, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_moderator_note_access_log(task_id: String) -> RegistryResult<Vec<types::NoteAccess>> {
+    let caller = ic_cdk::caller();
+    ModeratorNoteManager::get_moderator_note_access_log(task_id, caller)
+}
+
+// Private message endpoints
+#[ic_cdk::update(guard = "track_update")]
+async fn get_private_message_public_key() -> RegistryResult<Vec<u8>> {
+    PrivateMessageManager::get_private_message_public_key().await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn send_private_message(company_id: String, ciphertext: Vec<u8>) -> RegistryResult<types::PrivateMessage> {
+    let caller = ic_cdk::caller();
+    PrivateMessageManager::send_private_message(company_id, ciphertext, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+async fn derive_private_message_key(company_id: String, transport_public_key: Vec<u8>) -> RegistryResult<Vec<u8>> {
+    let caller = ic_cdk::caller();
+    PrivateMessageManager::derive_private_message_key(company_id, transport_public_key, caller).await
+}
+
+#[ic_cdk::query]
+pub fn get_private_messages(company_id: String) -> RegistryResult<Vec<types::PrivateMessage>> {
+    let caller = ic_cdk::caller();
+    PrivateMessageManager::get_private_messages(company_id, caller)
+}
+
+// Dispute endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn review_report(task_id: String) -> RegistryResult<types::Dispute> {
+    let caller = ic_cdk::caller();
+    DisputeManager::review_report(task_id, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn resolve_report(
+    task_id: String,
+    outcome: types::DisputeOutcome,
+    resolution: String,
+) -> RegistryResult<types::Dispute> {
+    let caller = ic_cdk::caller();
+    DisputeManager::resolve_report(task_id, outcome, resolution, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_company_disputes(
+    company_id: String,
+    status: Option<types::DisputeStatus>,
+) -> RegistryResult<Vec<types::Dispute>> {
+    let caller = ic_cdk::caller();
+    DisputeManager::get_company_disputes(company_id, status, caller)
+}
+
+// Monitoring endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn enable_contract_monitoring(
+    company_id: String,
+    chain_type: ChainType,
+    contract_address: String,
+) -> RegistryResult<ContractWatch> {
+    let caller = ic_cdk::caller();
+    MonitoringSystem::enable_contract_monitoring(company_id, chain_type, contract_address, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn disable_contract_monitoring(
+    company_id: String,
+    chain_type: ChainType,
+    contract_address: String,
+) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    MonitoringSystem::disable_contract_monitoring(company_id, chain_type, contract_address, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_contract_watches(company_id: String) -> Vec<ContractWatch> {
+    MonitoringSystem::get_contract_watches(company_id)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub async fn run_contract_monitoring() -> RegistryResult<u32> {
+    let caller = ic_cdk::caller();
+    MonitoringSystem::trigger_contract_monitoring(caller).await
+}
+
+#[ic_cdk::query]
+pub fn get_canister_snapshots(company_id: String) -> Vec<CanisterSnapshot> {
+    MonitoringSystem::get_canister_snapshots(company_id)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub async fn run_canister_monitoring() -> RegistryResult<u32> {
+    let caller = ic_cdk::caller();
+    MonitoringSystem::trigger_canister_monitoring(caller).await
+}
+
+// Job runner endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn cancel_job(job_id: String) -> RegistryResult<types::JobProgress> {
+    let caller = ic_cdk::caller();
+    JobRunner::cancel_job(job_id, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_jobs(status: Option<types::JobStatus>) -> RegistryResult<Vec<types::JobProgress>> {
+    let caller = ic_cdk::caller();
+    JobRunner::get_jobs(status, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn set_job_concurrency_limit(provider: Option<String>, limit: Option<u32>) -> RegistryResult<types::JobConcurrencyLimits> {
+    let caller = ic_cdk::caller();
+    JobRunner::set_job_concurrency_limit(provider, limit, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_job_concurrency_limits() -> types::JobConcurrencyLimits {
+    JobRunner::get_job_concurrency_limits()
+}
+
+#[ic_cdk::query]
+pub fn get_treasury_snapshots(company_id: String) -> Vec<TreasurySnapshot> {
+    MonitoringSystem::get_treasury_snapshots(company_id)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub async fn run_treasury_monitoring() -> RegistryResult<u32> {
+    let caller = ic_cdk::caller();
+    MonitoringSystem::trigger_treasury_monitoring(caller).await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub async fn run_proof_monitoring() -> RegistryResult<u32> {
+    let caller = ic_cdk::caller();
+    MonitoringSystem::trigger_proof_monitoring(caller).await
+}
+
+#[ic_cdk::query]
+pub fn get_monitoring_config() -> MonitoringConfig {
+    MonitoringSystem::get_monitoring_config()
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn set_monitoring_config(config: MonitoringConfig) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    MonitoringSystem::set_monitoring_config(config, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_inactivity_policy() -> InactivityPolicy {
+    InactivityManager::get_inactivity_policy()
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn set_inactivity_policy(policy: InactivityPolicy) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    InactivityManager::set_inactivity_policy(policy, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn run_inactivity_sweep() -> RegistryResult<u32> {
+    let caller = ic_cdk::caller();
+    InactivityManager::trigger_inactivity_sweep(caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn create_reverification_campaign(request: CreateCampaignRequest) -> RegistryResult<ReverificationCampaign> {
+    let caller = ic_cdk::caller();
+    CampaignManager::create_reverification_campaign(request, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn cancel_reverification_campaign(campaign_id: String) -> RegistryResult<ReverificationCampaign> {
+    let caller = ic_cdk::caller();
+    CampaignManager::cancel_reverification_campaign(campaign_id, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn run_campaign_enforcement() -> RegistryResult<u32> {
+    let caller = ic_cdk::caller();
+    CampaignManager::trigger_campaign_enforcement(caller)
+}
+
+#[ic_cdk::query]
+pub fn list_reverification_campaigns() -> Vec<ReverificationCampaign> {
+    CampaignManager::list_reverification_campaigns()
+}
+
+#[ic_cdk::query]
+pub fn get_campaign_progress(campaign_id: String) -> RegistryResult<CampaignProgress> {
+    CampaignManager::get_campaign_progress(campaign_id)
+}
+
+#[ic_cdk::query]
+pub fn get_my_campaign_tasks() -> Vec<CampaignTask> {
+    let caller = ic_cdk::caller();
+    CampaignManager::get_my_campaign_tasks(caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn confirm_company_active(company_id: String) -> RegistryResult<CompanyStatus> {
+    let caller = ic_cdk::caller();
+    InactivityManager::confirm_company_active(company_id, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_community_alerts(company_id: String) -> Vec<CommunityAlert> {
+    let caller = ic_cdk::caller();
+    MonitoringSystem::get_community_alerts(company_id, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn register_webhook(
+    target: WebhookTarget,
+    company_ids: Vec<String>,
+    min_severity: AlertSeverity,
+    include_security_events: bool,
+) -> RegistryResult<WebhookSubscription> {
+    let caller = ic_cdk::caller();
+    WebhookManager::register_webhook(target, company_ids, min_severity, include_security_events, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn remove_webhook(id: String) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    WebhookManager::remove_webhook(id, caller)
 }
 
 #[ic_cdk::query]
-pub fn get_company(company_id: String) -> RegistryResult<Company> {
-    RegistryAPI::get_company(company_id)
+pub fn get_my_webhooks() -> Vec<WebhookSubscription> {
+    let caller = ic_cdk::caller();
+    WebhookManager::get_my_webhooks(caller)
 }
 
-#[ic_cdk::update]
-pub fn update_company(request: UpdateCompanyRequest) -> RegistryResult<()> {
+#[ic_cdk::query]
+pub fn get_moderator_alert_queue() -> RegistryResult<Vec<CommunityAlert>> {
     let caller = ic_cdk::caller();
-    RegistryAPI::update_company(request, caller)
+    MonitoringSystem::get_moderator_alert_queue(caller)
+}
+
+// Alert routing endpoints (admin only)
+#[ic_cdk::update(guard = "track_update")]
+pub fn set_alert_routing_rule(
+    alert_type: AlertType,
+    severity: AlertSeverity,
+    visibility: AlertVisibility,
+    channels: Vec<NotificationChannel>,
+) -> RegistryResult<AlertRoutingRule> {
+    let caller = ic_cdk::caller();
+    AlertRoutingManager::set_alert_routing_rule(alert_type, severity, visibility, channels, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn remove_alert_routing_rule(alert_type: AlertType, severity: AlertSeverity) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    AlertRoutingManager::remove_alert_routing_rule(alert_type, severity, caller)
 }
 
 #[ic_cdk::query]
-pub fn list_companies(
+pub fn list_alert_routing_rules() -> RegistryResult<Vec<AlertRoutingRule>> {
+    let caller = ic_cdk::caller();
+    AlertRoutingManager::list_alert_routing_rules(caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn acknowledge_alert(alert_id: String) -> RegistryResult<CommunityAlert> {
+    let caller = ic_cdk::caller();
+    MonitoringSystem::acknowledge_alert(alert_id, caller)
+}
+
+// Security event endpoints
+#[ic_cdk::query]
+pub fn get_security_events_by_principal(
+    filter: Option<SecurityEventFilter>,
     offset: Option<u32>,
     limit: Option<u32>,
-    filters: Option<SearchFilters>,
-) -> Vec<Company> {
-    RegistryAPI::list_companies(offset, limit, filters)
+) -> RegistryResult<CommunityPage<SecurityEvent>> {
+    let caller = ic_cdk::caller();
+    SecurityLog::get_security_events_by_principal(filter, offset, limit, caller)
 }
 
 #[ic_cdk::query]
-pub fn search_companies(query: String) -> Vec<Company> {
-    RegistryAPI::search_companies(query)
+pub fn get_moderator_security_events(
+    principal: Principal,
+    filter: Option<SecurityEventFilter>,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> RegistryResult<CommunityPage<SecurityEvent>> {
+    let caller = ic_cdk::caller();
+    SecurityLog::get_moderator_security_events(principal, filter, offset, limit, caller)
 }
 
+// Owner dashboard
 #[ic_cdk::query]
-pub fn get_company_count() -> u64 {
-    RegistryAPI::get_company_count()
+pub fn get_my_dashboard() -> RegistryResult<OwnerDashboard> {
+    let caller = ic_cdk::caller();
+    DashboardManager::get_my_dashboard(caller)
 }
 
+// Notification endpoints
 #[ic_cdk::query]
-pub fn get_statistics() -> HashMap<String, u64> {
-    RegistryAPI::get_statistics()
+pub fn get_my_notifications(unread_only: bool) -> Vec<Notification> {
+    let caller = ic_cdk::caller();
+    NotificationManager::get_my_notifications(caller, unread_only)
 }
 
-// Verification API endpoints
-#[ic_cdk::update]
-async fn verify_github_organization(
-    company_id: String,
-    github_org: String,
-) -> RegistryResult<VerificationResult> {
+#[ic_cdk::update(guard = "track_update")]
+pub fn mark_notification_read(notification_id: String) -> RegistryResult<()> {
     let caller = ic_cdk::caller();
-    VerificationManager::verify_github_organization(company_id, github_org, caller).await
+    NotificationManager::mark_notification_read(notification_id, caller)
 }
 
-#[ic_cdk::update]
-fn create_domain_verification_challenge(
+// Address rotation endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn deprecate_address(
     company_id: String,
-) -> RegistryResult<DomainVerificationChallenge> {
+    chain_type: ChainType,
+    address: String,
+    successor_address: String,
+    reason: String,
+) -> RegistryResult<AddressRotation> {
     let caller = ic_cdk::caller();
-    VerificationManager::create_domain_verification_challenge(company_id, caller)
+    RotationManager::deprecate_address(company_id, chain_type, address, successor_address, reason, caller)
 }
 
-#[ic_cdk::update]
-async fn verify_domain_ownership(company_id: String) -> RegistryResult<VerificationResult> {
+#[ic_cdk::update(guard = "track_update")]
+pub fn cancel_address_rotation(company_id: String, rotation_id: String) -> RegistryResult<AddressRotation> {
     let caller = ic_cdk::caller();
-    VerificationManager::verify_domain_ownership(company_id, caller).await
+    RotationManager::cancel_address_rotation(company_id, rotation_id, caller)
 }
 
-#[ic_cdk::update]
-fn verify_social_media_manual(
+#[ic_cdk::query]
+pub fn get_address_rotations(company_id: String) -> RegistryResult<Vec<AddressRotation>> {
+    RotationManager::get_address_rotations(company_id)
+}
+
+#[ic_cdk::query]
+pub fn get_address_lineage(chain_type: ChainType, address: String) -> Option<AddressLineage> {
+    RotationManager::get_address_lineage(chain_type, address)
+}
+
+// Warning banner endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn publish_warning_banner(
     company_id: String,
-    platform: String,
-    proof_url: String,
-) -> RegistryResult<VerificationResult> {
+    message: String,
+    severity: BannerSeverity,
+    starts_at: Option<u64>,
+    ends_at: u64,
+) -> RegistryResult<WarningBanner> {
     let caller = ic_cdk::caller();
-    VerificationManager::verify_social_media_manual(company_id, platform, proof_url, caller)
+    BannerManager::publish_warning_banner(company_id, message, severity, starts_at, ends_at, caller)
 }
 
-#[ic_cdk::update]
-fn verify_social_media_with_proof(
-    company_id: String,
-    platform: String,
-    proof_url: String,
-) -> RegistryResult<VerificationResult> {
+#[ic_cdk::update(guard = "track_update")]
+pub fn remove_warning_banner(company_id: String) -> RegistryResult<()> {
     let caller = ic_cdk::caller();
-    VerificationManager::verify_social_media_with_proof(company_id, platform, proof_url, caller)
+    BannerManager::remove_warning_banner(company_id, caller)
 }
 
-#[ic_cdk::update]
-async fn verify_proof_still_exists(
+#[ic_cdk::query]
+pub fn get_warning_banner(company_id: String) -> RegistryResult<Option<WarningBanner>> {
+    BannerManager::get_warning_banner(company_id)
+}
+
+// Impersonation report endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn report_impersonation(
     company_id: String,
-    proof_url: String,
-) -> RegistryResult<types::ProofCheckResult> {
+    channel: ImpersonationChannel,
+    impersonator: String,
+    evidence: String,
+) -> RegistryResult<ImpersonationReport> {
     let caller = ic_cdk::caller();
-    VerificationManager::verify_proof_still_exists(company_id, proof_url, caller).await
+    ImpersonationManager::report_impersonation(company_id, channel, impersonator, evidence, caller)
 }
 
-#[ic_cdk::update]
-fn report_verification_issue(
+#[ic_cdk::update(guard = "track_update")]
+pub fn review_impersonation_report(report_id: String, confirmed: bool) -> RegistryResult<ImpersonationReport> {
+    let caller = ic_cdk::caller();
+    ImpersonationManager::review_impersonation_report(report_id, confirmed, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_impersonation_reports(company_id: String) -> RegistryResult<Vec<ImpersonationReport>> {
+    let caller = ic_cdk::caller();
+    ImpersonationManager::get_impersonation_reports(company_id, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_impersonation_warnings(company_id: String) -> RegistryResult<Vec<ImpersonationWarning>> {
+    ImpersonationManager::get_impersonation_warnings(company_id)
+}
+
+#[ic_cdk::query]
+pub fn check_impersonator(value: String) -> Vec<ImpersonationWarning> {
+    ImpersonationManager::check_impersonator(value)
+}
+
+// Hiring endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn set_hiring_status(
     company_id: String,
-    proof_url: String,
-    report_type: types::ReportType,
-    evidence: String,
-) -> RegistryResult<String> {
+    actively_hiring: bool,
+    job_links: Vec<JobLink>,
+) -> RegistryResult<HiringInfo> {
     let caller = ic_cdk::caller();
-    VerificationManager::report_verification_issue(company_id, proof_url, report_type, evidence, caller)
+    HiringManager::set_hiring_status(company_id, actively_hiring, job_links, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub async fn check_job_links(company_id: String) -> RegistryResult<HiringInfo> {
+    let caller = ic_cdk::caller();
+    HiringManager::check_job_links(company_id, caller).await
+}
+
+// Grant program endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn register_grant_program(
+    issuer_company_id: String,
+    name: String,
+    description: String,
+) -> RegistryResult<GrantProgram> {
+    let caller = ic_cdk::caller();
+    GrantManager::register_grant_program(issuer_company_id, name, description, caller)
 }
 
-// Verification utility endpoints
 #[ic_cdk::query]
-fn get_domain_verification_challenge(company_id: String) -> Option<DomainVerificationChallenge> {
-    StorageManager::get_domain_challenge(&company_id)
+pub fn get_grant_programs(issuer_company_id: String) -> Vec<GrantProgram> {
+    GrantManager::get_grant_programs(issuer_company_id)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn issue_grant_attestation(
+    program_id: String,
+    recipient_company_id: String,
+    amount_range: GrantAmountRange,
+    awarded_date: String,
+) -> RegistryResult<GrantAttestation> {
+    let caller = ic_cdk::caller();
+    GrantManager::issue_grant_attestation(program_id, recipient_company_id, amount_range, awarded_date, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn revoke_grant_attestation(attestation_id: String) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    GrantManager::revoke_grant_attestation(attestation_id, caller)
 }
 
 #[ic_cdk::query]
-fn get_verification_instructions(verification_type: VerificationType) -> String {
-    VerificationManager::get_verification_instructions(verification_type)
+pub fn get_grants_for_company(company_id: String) -> Vec<GrantAttestation> {
+    GrantManager::get_grants_for_company(company_id)
 }
 
-// Cross-chain verification API endpoints
-#[ic_cdk::update]
-fn create_crosschain_challenge(
-    request: CrossChainVerificationRequest,
-) -> RegistryResult<CrossChainChallenge> {
+#[ic_cdk::query]
+pub fn get_grants_issued_by(issuer_company_id: String) -> Vec<GrantAttestation> {
+    GrantManager::get_grants_issued_by(issuer_company_id)
+}
+
+// Corporate hierarchy endpoints
+#[ic_cdk::update(guard = "track_update")]
+pub fn propose_company_relationship(
+    child_company_id: String,
+    parent_company_id: String,
+    kind: RelationshipKind,
+) -> RegistryResult<CompanyRelationship> {
     let caller = ic_cdk::caller();
-    CrossChainVerifier::create_crosschain_challenge(request, caller)
+    RelationshipManager::propose_relationship(child_company_id, parent_company_id, kind, caller)
 }
 
-#[ic_cdk::update]
-async fn verify_ethereum_contract(
-    company_id: String,
-    contract_address: String,
-) -> RegistryResult<VerificationResult> {
-    CrossChainVerifier::verify_ethereum_contract(company_id, contract_address).await
+#[ic_cdk::update(guard = "track_update")]
+pub fn confirm_company_relationship(
+    child_company_id: String,
+    parent_company_id: String,
+) -> RegistryResult<CompanyRelationship> {
+    let caller = ic_cdk::caller();
+    RelationshipManager::confirm_relationship(child_company_id, parent_company_id, caller)
 }
 
-#[ic_cdk::update]
-async fn verify_bitcoin_address(
-    company_id: String,
-    bitcoin_address: String,
-) -> RegistryResult<VerificationResult> {
-    CrossChainVerifier::verify_bitcoin_address(company_id, bitcoin_address).await
+#[ic_cdk::update(guard = "track_update")]
+pub fn remove_company_relationship(
+    child_company_id: String,
+    parent_company_id: String,
+) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    RelationshipManager::remove_relationship(child_company_id, parent_company_id, caller)
 }
 
-#[ic_cdk::update]
-async fn verify_icp_canister(
-    company_id: String,
-    canister_id: String,
-) -> RegistryResult<VerificationResult> {
-    CrossChainVerifier::verify_icp_canister(company_id, canister_id).await
+#[ic_cdk::query]
+pub fn get_company_relationships(company_id: String) -> Vec<CompanyRelationship> {
+    RelationshipManager::get_relationships_for_company(company_id)
 }
 
 #[ic_cdk::query]
-fn get_crosschain_verification_instructions(chain_type: ChainType) -> String {
-    CrossChainVerifier::get_crosschain_verification_instructions(chain_type)
+pub fn get_corporate_graph(company_id: String) -> RegistryResult<CorporateGraph> {
+    RelationshipManager::get_corporate_graph(company_id)
 }
 
 #[ic_cdk::query]
-fn get_crosschain_challenges_for_company(company_id: String) -> Vec<CrossChainChallenge> {
-    StorageManager::get_crosschain_challenges_for_company(&company_id)
+pub fn get_group_signals(company_id: String) -> RegistryResult<GroupSignals> {
+    RelationshipManager::get_group_signals(company_id)
 }
 
 // HTTP transform functions for HTTPS outcalls
@@ -196,8 +2081,48 @@ fn transform_proof_check(raw: TransformArgs) -> ic_cdk::api::management_canister
 }
 
 #[ic_cdk::query]
-fn transform_etherscan_response(raw: TransformArgs) -> ic_cdk::api::management_canister::http_request::HttpResponse {
-    crosschain::transform_etherscan_response(raw)
+fn transform_webhook_response(raw: TransformArgs) -> ic_cdk::api::management_canister::http_request::HttpResponse {
+    webhooks::transform_webhook_response(raw)
+}
+
+#[ic_cdk::query]
+fn transform_proof_reference(raw: TransformArgs) -> ic_cdk::api::management_canister::http_request::HttpResponse {
+    proof_migration::transform_proof_reference(raw)
+}
+
+#[ic_cdk::query]
+fn transform_contact_check(raw: TransformArgs) -> ic_cdk::api::management_canister::http_request::HttpResponse {
+    contacts::transform_contact_check(raw)
+}
+
+#[ic_cdk::query]
+fn transform_evm_block_number(raw: TransformArgs) -> ic_cdk::api::management_canister::http_request::HttpResponse {
+    monitoring::transform_evm_block_number(raw)
+}
+
+#[ic_cdk::query]
+fn transform_evm_logs(raw: TransformArgs) -> ic_cdk::api::management_canister::http_request::HttpResponse {
+    monitoring::transform_evm_logs(raw)
+}
+
+#[ic_cdk::query]
+fn transform_evm_balance(raw: TransformArgs) -> ic_cdk::api::management_canister::http_request::HttpResponse {
+    monitoring::transform_evm_balance(raw)
+}
+
+#[ic_cdk::query]
+fn transform_bitcoin_balance(raw: TransformArgs) -> ic_cdk::api::management_canister::http_request::HttpResponse {
+    monitoring::transform_bitcoin_balance(raw)
+}
+
+#[ic_cdk::query]
+fn transform_evm_transaction(raw: TransformArgs) -> ic_cdk::api::management_canister::http_request::HttpResponse {
+    crosschain::transform_evm_transaction(raw)
+}
+
+#[ic_cdk::query]
+fn transform_evm_owner(raw: TransformArgs) -> ic_cdk::api::management_canister::http_request::HttpResponse {
+    crosschain::transform_evm_owner(raw)
 }
 
 #[ic_cdk::query]
@@ -205,10 +2130,45 @@ fn transform_blockchain_response(raw: TransformArgs) -> ic_cdk::api::management_
     crosschain::transform_blockchain_response(raw)
 }
 
+#[ic_cdk::query]
+fn transform_solana_signatures(raw: TransformArgs) -> ic_cdk::api::management_canister::http_request::HttpResponse {
+    crosschain::transform_solana_signatures(raw)
+}
+
+#[ic_cdk::query]
+fn transform_solana_transaction(raw: TransformArgs) -> ic_cdk::api::management_canister::http_request::HttpResponse {
+    crosschain::transform_solana_transaction(raw)
+}
+
+#[ic_cdk::query]
+fn transform_ton_transactions(raw: TransformArgs) -> ic_cdk::api::management_canister::http_request::HttpResponse {
+    crosschain::transform_ton_transactions(raw)
+}
+
+#[ic_cdk::query]
+fn transform_ton_balance(raw: TransformArgs) -> ic_cdk::api::management_canister::http_request::HttpResponse {
+    monitoring::transform_ton_balance(raw)
+}
+
+#[ic_cdk::query]
+fn transform_evm_quantity(raw: TransformArgs) -> ic_cdk::api::management_canister::http_request::HttpResponse {
+    evm_bridge::transform_evm_quantity(raw)
+}
+
+#[ic_cdk::query]
+fn transform_evm_send(raw: TransformArgs) -> ic_cdk::api::management_canister::http_request::HttpResponse {
+    evm_bridge::transform_evm_send(raw)
+}
+
+#[ic_cdk::query]
+fn transform_evm_receipt(raw: TransformArgs) -> ic_cdk::api::management_canister::http_request::HttpResponse {
+    evm_bridge::transform_evm_receipt(raw)
+}
+
 // Community Validation API endpoints
 
 // Endorsement endpoints
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "track_update")]
 pub fn add_endorsement(
     company_id: String,
     endorser_company_id: String,
@@ -218,7 +2178,7 @@ pub fn add_endorsement(
     CommunityValidationManager::add_endorsement(company_id, endorser_company_id, message, caller)
 }
 
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "track_update")]
 pub fn remove_endorsement(
     company_id: String,
     endorser_company_id: String,
@@ -232,8 +2192,23 @@ pub fn get_endorsements_for_company(company_id: String) -> RegistryResult<Vec<En
     CommunityValidationManager::get_endorsements_for_company(company_id)
 }
 
+#[ic_cdk::query]
+pub fn get_endorsement_weight(endorser_company_id: String) -> RegistryResult<types::EndorsementWeight> {
+    CommunityValidationManager::get_endorsement_weight(endorser_company_id)
+}
+
+#[ic_cdk::query]
+pub fn get_endorsements_page(
+    company_id: String,
+    offset: Option<u32>,
+    limit: Option<u32>,
+    sort: Option<CommunitySortOrder>,
+) -> RegistryResult<CommunityPage<Endorsement>> {
+    CommunityValidationManager::get_endorsements_page(company_id, offset, limit, sort)
+}
+
 // Testimonial endpoints
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "track_update")]
 pub fn add_testimonial(
     company_id: String,
     author_name: String,
@@ -244,7 +2219,7 @@ pub fn add_testimonial(
     CommunityValidationManager::add_testimonial(company_id, author_name, role, message, caller)
 }
 
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "track_update")]
 pub fn remove_testimonial(
     company_id: String,
     author_name: String,
@@ -253,7 +2228,7 @@ pub fn remove_testimonial(
     CommunityValidationManager::remove_testimonial(company_id, author_name, caller)
 }
 
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "track_update")]
 pub fn verify_testimonial(
     company_id: String,
     author_name: String,
@@ -267,8 +2242,18 @@ pub fn get_testimonials_for_company(company_id: String) -> RegistryResult<Vec<Te
     CommunityValidationManager::get_testimonials_for_company(company_id)
 }
 
+#[ic_cdk::query]
+pub fn get_testimonials_page(
+    company_id: String,
+    offset: Option<u32>,
+    limit: Option<u32>,
+    sort: Option<CommunitySortOrder>,
+) -> RegistryResult<CommunityPage<Testimonial>> {
+    CommunityValidationManager::get_testimonials_page(company_id, offset, limit, sort)
+}
+
 // Community vouch endpoints
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "track_update")]
 pub fn add_vouch(
     company_id: String,
     message: String,
@@ -277,25 +2262,85 @@ pub fn add_vouch(
     CommunityValidationManager::add_vouch(company_id, message, caller)
 }
 
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "track_update")]
 pub fn remove_vouch(company_id: String) -> RegistryResult<()> {
     let caller = ic_cdk::caller();
     CommunityValidationManager::remove_vouch(company_id, caller)
 }
 
+#[ic_cdk::update(guard = "track_update")]
+pub fn raise_concern(company_id: String, reason: String) -> RegistryResult<Concern> {
+    let caller = ic_cdk::caller();
+    ConcernManager::raise_concern(company_id, reason, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn withdraw_concern(company_id: String) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    ConcernManager::withdraw_concern(company_id, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_concerns(company_id: String) -> ConcernSummary {
+    ConcernManager::get_concerns(company_id)
+}
+
+#[ic_cdk::query]
+pub fn get_community_cooldown_policy() -> CommunityCooldownPolicy {
+    CommunityValidationManager::get_community_cooldown_policy()
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn set_community_cooldown_policy(policy: CommunityCooldownPolicy) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    CommunityValidationManager::set_community_cooldown_policy(policy, caller)
+}
+
 #[ic_cdk::query]
 pub fn get_vouches_for_company(company_id: String) -> RegistryResult<Vec<Vouch>> {
     CommunityValidationManager::get_vouches_for_company(company_id)
 }
 
+#[ic_cdk::query]
+pub fn get_vouches_page(
+    company_id: String,
+    offset: Option<u32>,
+    limit: Option<u32>,
+    sort: Option<CommunitySortOrder>,
+) -> RegistryResult<CommunityPage<Vouch>> {
+    CommunityValidationManager::get_vouches_page(company_id, offset, limit, sort)
+}
+
 // Reputation management endpoints
-#[ic_cdk::update]
-pub fn stake_reputation(
+#[ic_cdk::query]
+pub fn get_reputation_staking_config() -> ReputationStakingConfig {
+    ReputationStakingManager::get_reputation_staking_config()
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub async fn stake_reputation(
     company_id: String,
     amount: u64,
-) -> RegistryResult<()> {
+) -> RegistryResult<ReputationStake> {
     let caller = ic_cdk::caller();
-    CommunityValidationManager::stake_reputation(company_id, amount, caller)
+    ReputationStakingManager::stake_reputation(company_id, amount, caller).await
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn unstake_reputation(company_id: String, amount: u64) -> RegistryResult<ReputationStake> {
+    let caller = ic_cdk::caller();
+    ReputationStakingManager::unstake_reputation(company_id, amount, caller)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub async fn claim_unstaked_reputation(company_id: String) -> RegistryResult<Nat> {
+    let caller = ic_cdk::caller();
+    ReputationStakingManager::claim_unstaked_reputation(company_id, caller).await
+}
+
+#[ic_cdk::query]
+pub fn get_reputation_stakes(company_id: String) -> Vec<ReputationStake> {
+    ReputationStakingManager::get_reputation_stakes(company_id)
 }
 
 // Community validation query endpoints
@@ -321,6 +2366,53 @@ pub fn get_reputation_leaderboard(limit: Option<u32>) -> Vec<ReputationLeaderboa
     CommunityValidationManager::get_reputation_leaderboard(limit)
 }
 
+#[ic_cdk::update(guard = "track_update")]
+pub fn rebuild_reputation_index() -> RegistryResult<u64> {
+    let caller = ic_cdk::caller();
+    CommunityValidationManager::rebuild_reputation_index(caller)
+}
+
+#[ic_cdk::query]
+pub fn get_reputation_escrow(company_id: String) -> RegistryResult<types::ReputationEscrow> {
+    ReputationEscrowManager::get_reputation_escrow(company_id)
+}
+
+#[ic_cdk::query]
+pub fn get_reputation_escrow_policy() -> types::ReputationEscrowPolicy {
+    ReputationEscrowManager::get_reputation_escrow_policy()
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn set_reputation_escrow_policy(policy: types::ReputationEscrowPolicy) -> RegistryResult<()> {
+    let caller = ic_cdk::caller();
+    ReputationEscrowManager::set_reputation_escrow_policy(policy, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_tier_definitions() -> types::TierDefinitions {
+    TierEngine::get_tier_definitions()
+}
+
+#[ic_cdk::query]
+pub fn get_company_tier_report(company_id: String) -> RegistryResult<types::CompanyTierReport> {
+    TierEngine::get_company_tier_report(company_id)
+}
+
+#[ic_cdk::update(guard = "track_update")]
+pub fn recompute_reputation(company_id: String) -> RegistryResult<ReputationRecomputation> {
+    let caller = ic_cdk::caller();
+    ReputationJournal::recompute_reputation(company_id, caller)
+}
+
+#[ic_cdk::query]
+pub fn get_reputation_events(
+    company_id: String,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> CommunityPage<ReputationEvent> {
+    ReputationJournal::get_reputation_events(company_id, offset, limit)
+}
+
 #[ic_cdk::query]
 pub fn get_endorsements_by_company(endorser_company_id: String) -> RegistryResult<Vec<(String, Endorsement)>> {
     CommunityValidationManager::get_endorsements_by_company(endorser_company_id)
@@ -345,8 +2437,7 @@ pub fn validate_endorsement_eligibility(
     CommunityValidationManager::validate_endorsement_eligibility(endorser_company_id, target_company_id)
 }
 
-// Moderation endpoints (for future admin features)
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "track_update")]
 pub fn flag_testimonial(
     company_id: String,
     author_name: String,
@@ -369,4 +2460,14 @@ pub fn get_address_validation_rules(chain: String) -> RegistryResult<String> {
 #[ic_cdk::query]
 pub fn get_supported_chains() -> RegistryResult<Vec<String>> {
     RegistryAPI::get_supported_chains()
-}
\ No newline at end of file
+}
+
+// Candid interface generated from the endpoint signatures above. Keep this at the end of
+// the file: only endpoints declared before the macro are exported.
+ic_cdk::export_candid!();
+
+// Lets dfx, agents and frontends fetch the interface the deployed wasm actually serves
+#[ic_cdk::query(name = "__get_candid_interface_tmp_hack")]
+fn get_candid_interface() -> String {
+    __export_service()
+}
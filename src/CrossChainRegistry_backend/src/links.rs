@@ -0,0 +1,67 @@
+use crate::http_client;
+use crate::sanitize::SafeUrl;
+use crate::types::{LinkCheck, LinkStatus};
+use ic_cdk::api::management_canister::http_request::{
+    CanisterHttpRequestArgument, HttpHeader, HttpMethod, TransformContext,
+};
+use ic_cdk::api::time;
+
+// Liveness checks for external links shown on profiles (announcements, job boards)
+
+pub struct LinkChecker;
+
+impl LinkChecker {
+    // Returns the normalized URL to store
+    pub fn validate_link_url(url: &str, field_name: &str) -> Result<String, String> {
+        SafeUrl::parse(url, field_name).map(SafeUrl::into_string)
+    }
+
+    pub fn unchecked() -> LinkCheck {
+        LinkCheck {
+            status: LinkStatus::Unchecked,
+            status_code: None,
+            checked_at: None,
+        }
+    }
+
+    // HEAD request so only the status line is transferred
+    pub async fn check_link(url: &str) -> LinkCheck {
+        let request = CanisterHttpRequestArgument {
+            url: url.to_string(),
+            method: HttpMethod::HEAD,
+            body: None,
+            max_response_bytes: Some(4096),
+            transform: Some(TransformContext::from_name(
+                "transform_proof_check".to_string(),
+                vec![],
+            )),
+            headers: vec![HttpHeader {
+                name: "User-Agent".to_string(),
+                value: "ICP-CrossChainRegistry-LinkChecker/1.0".to_string(),
+            }],
+        };
+
+        match http_client::send(request, 10_000_000_000).await {
+            Ok(response) => {
+                let code: u32 = response.status.0.try_into().unwrap_or(0);
+                // Sites that block bots or HEAD requests do not prove the page is gone
+                let status = match code {
+                    200..=399 => LinkStatus::Alive,
+                    403 | 405 | 429 => LinkStatus::Unknown,
+                    400..=499 => LinkStatus::Dead,
+                    _ => LinkStatus::Unknown,
+                };
+                LinkCheck {
+                    status,
+                    status_code: Some(code),
+                    checked_at: Some(time()),
+                }
+            }
+            Err(_) => LinkCheck {
+                status: LinkStatus::Unknown,
+                status_code: None,
+                checked_at: Some(time()),
+            },
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use crate::audit::AuditManager;
+use crate::crosschain::CrossChainVerifier;
+use crate::sanitize::{self, SafeText};
+use crate::settings::SettingsManager;
+use crate::storage::StorageManager;
+use crate::verification::VerificationManager;
+use crate::types::{Company, DataMigration, MigrationKind, MigrationStatus, RegistryResult};
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Admin-run rewrites of values stored inside company profiles, for when the focus-area
+// taxonomy or the chain registry changes. A migration walks companies in id order a batch
+// at a time and remembers where it stopped, so even a large registry is migrated in
+// calls that stay well inside the instruction limit. Writes go through update_company,
+// which keeps the reputation and search indexes and the event log in step.
+
+pub struct MigrationManager;
+
+impl MigrationManager {
+    const DEFAULT_BATCH_SIZE: u32 = 100;
+    const MAX_BATCH_SIZE: u32 = 1_000;
+    // Stop a batch early past this many instructions; updates may use 40B
+    const INSTRUCTION_BUDGET: u64 = 10_000_000_000;
+
+    pub fn start_migration(kind: MigrationKind, caller_principal: Principal) -> RegistryResult<DataMigration> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let kind = match Self::validate(kind) {
+            Ok(kind) => kind,
+            Err(err) => return RegistryResult::Err(err),
+        };
+        // Two live migrations could undo each other (X -> Y next to Y -> X)
+        if StorageManager::get_all_data_migrations()
+            .iter()
+            .any(|migration| migration.status == MigrationStatus::Running)
+        {
+            return RegistryResult::Err("Another migration is still running; finish or cancel it first".to_string());
+        }
+
+        let migration = DataMigration {
+            id: format!("migration_{:06}", StorageManager::data_migration_count() + 1),
+            kind,
+            status: MigrationStatus::Running,
+            started_by: caller_principal,
+            started_at: time(),
+            cursor: None,
+            companies_total: StorageManager::get_companies_count(),
+            companies_scanned: 0,
+            companies_changed: 0,
+            batches_run: 0,
+            finished_at: None,
+        };
+        StorageManager::insert_data_migration(migration.clone());
+        AuditManager::record(
+            "migration_started",
+            &migration.id,
+            vec![caller_principal],
+            format!("{:?}", migration.kind),
+        );
+        RegistryResult::Ok(migration)
+    }
+
+    // Processes the next batch; call until the status is Completed
+    pub fn run_migration_batch(
+        migration_id: String,
+        batch_size: Option<u32>,
+        caller_principal: Principal,
+    ) -> RegistryResult<DataMigration> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let mut migration = match StorageManager::get_data_migration(&migration_id) {
+            Some(migration) => migration,
+            None => return RegistryResult::Err("Migration not found".to_string()),
+        };
+        if migration.status != MigrationStatus::Running {
+            return RegistryResult::Err(format!("Migration is {:?}", migration.status));
+        }
+
+        let batch_size = batch_size
+            .unwrap_or(Self::DEFAULT_BATCH_SIZE)
+            .clamp(1, Self::MAX_BATCH_SIZE) as usize;
+        let companies = StorageManager::get_companies_after(migration.cursor.as_deref(), batch_size);
+        let exhausted = companies.len() < batch_size;
+        let mut stopped_early = false;
+
+        for company in companies {
+            if ic_cdk::api::instruction_counter() > Self::INSTRUCTION_BUDGET {
+                stopped_early = true;
+                break;
+            }
+            let mut migrated = company.clone();
+            if Self::apply(&migration.kind, &mut migrated) {
+                if migration.kind == MigrationKind::AssignProofIds {
+                    Self::rekey_proof_state(&company, &migrated);
+                }
+                StorageManager::update_company(&company.id, |stored| {
+                    stored.basic_info.focus_areas = migrated.basic_info.focus_areas;
+                    stored.cross_chain_presence.treasury_wallets = migrated.cross_chain_presence.treasury_wallets;
+                    stored.cross_chain_presence.token_contracts = migrated.cross_chain_presence.token_contracts;
+                    stored.web3_identity.verification_proofs = migrated.web3_identity.verification_proofs;
+                });
+                migration.companies_changed += 1;
+            }
+            migration.companies_scanned += 1;
+            migration.cursor = Some(company.id);
+        }
+
+        migration.batches_run += 1;
+        if exhausted && !stopped_early {
+            migration.status = MigrationStatus::Completed;
+            migration.finished_at = Some(time());
+            AuditManager::record(
+                "migration_completed",
+                &migration.id,
+                vec![caller_principal],
+                format!(
+                    "{} of {} companies changed",
+                    migration.companies_changed, migration.companies_scanned
+                ),
+            );
+        }
+        StorageManager::insert_data_migration(migration.clone());
+        RegistryResult::Ok(migration)
+    }
+
+    pub fn cancel_migration(migration_id: String, caller_principal: Principal) -> RegistryResult<DataMigration> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let mut migration = match StorageManager::get_data_migration(&migration_id) {
+            Some(migration) => migration,
+            None => return RegistryResult::Err("Migration not found".to_string()),
+        };
+        if migration.status != MigrationStatus::Running {
+            return RegistryResult::Err(format!("Migration is {:?}", migration.status));
+        }
+        // Companies already migrated keep their new values
+        migration.status = MigrationStatus::Cancelled;
+        migration.finished_at = Some(time());
+        StorageManager::insert_data_migration(migration.clone());
+        AuditManager::record("migration_cancelled", &migration.id, vec![caller_principal], String::new());
+        RegistryResult::Ok(migration)
+    }
+
+    pub fn list_migrations(caller_principal: Principal) -> RegistryResult<Vec<DataMigration>> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let mut migrations = StorageManager::get_all_data_migrations();
+        migrations.sort_by_key(|migration| std::cmp::Reverse(migration.started_at));
+        RegistryResult::Ok(migrations)
+    }
+
+    fn validate(kind: MigrationKind) -> Result<MigrationKind, String> {
+        let policy = sanitize::policy();
+        match kind {
+            MigrationKind::RenameFocusArea { from, to } => {
+                let from = SafeText::parse_required(&from, "Focus area", policy.max_label)?.into_inner();
+                let to = match to {
+                    Some(to) => Some(SafeText::parse_required(&to, "Focus area", policy.max_label)?.into_inner()),
+                    None => None,
+                };
+                if to.as_deref() == Some(from.as_str()) {
+                    return Err("Source and target focus areas are the same".to_string());
+                }
+                Ok(MigrationKind::RenameFocusArea { from, to })
+            }
+            MigrationKind::RemapChain { from, to } => {
+                let from = from.trim().to_string();
+                if from.is_empty() {
+                    return Err("Source chain cannot be empty".to_string());
+                }
+                // Targets must be canonical, so remapping never introduces a new alias
+                let to = match CrossChainVerifier::chain_from_name(&to) {
+                    Some(chain_type) => CrossChainVerifier::chain_name(&chain_type).to_string(),
+                    None => return Err(format!("Unknown target chain '{}'", to.trim())),
+                };
+                if from.eq_ignore_ascii_case(&to) {
+                    return Err("Source and target chains are the same".to_string());
+                }
+                Ok(MigrationKind::RemapChain { from, to })
+            }
+            MigrationKind::AssignProofIds => Ok(MigrationKind::AssignProofIds),
+        }
+    }
+
+    // Returns whether anything changed
+    fn apply(kind: &MigrationKind, company: &mut Company) -> bool {
+        match kind {
+            MigrationKind::RenameFocusArea { from, to } => {
+                let areas = &mut company.basic_info.focus_areas;
+                if !areas.iter().any(|area| area.trim().eq_ignore_ascii_case(from)) {
+                    return false;
+                }
+                let mut migrated: Vec<String> = Vec::with_capacity(areas.len());
+                for area in areas.iter() {
+                    let area = if area.trim().eq_ignore_ascii_case(from) {
+                        match to {
+                            Some(to) => to.clone(),
+                            None => continue,
+                        }
+                    } else {
+                        area.clone()
+                    };
+                    // Renaming into an area the company already lists merges the two
+                    if !migrated.iter().any(|existing| existing.eq_ignore_ascii_case(&area)) {
+                        migrated.push(area);
+                    }
+                }
+                *areas = migrated;
+                true
+            }
+            MigrationKind::RemapChain { from, to } => {
+                let presence = &mut company.cross_chain_presence;
+                let mut changed = false;
+                for wallet in presence.treasury_wallets.iter_mut() {
+                    if wallet.chain.trim().eq_ignore_ascii_case(from) {
+                        wallet.chain = to.clone();
+                        changed = true;
+                    }
+                }
+                for token in presence.token_contracts.iter_mut() {
+                    if token.chain.trim().eq_ignore_ascii_case(from) {
+                        token.chain = to.clone();
+                        changed = true;
+                    }
+                }
+                changed
+            }
+            MigrationKind::AssignProofIds => {
+                let company_id = company.id.clone();
+                let mut changed = false;
+                for (position, proof) in company.web3_identity.verification_proofs.iter_mut().enumerate() {
+                    if proof.id.is_none() {
+                        let seed = format!("{}:{}", position, proof.proof_url);
+                        proof.id = Some(VerificationManager::new_proof_id(&company_id, &seed));
+                        changed = true;
+                    }
+                }
+                changed
+            }
+        }
+    }
+
+    // Check history and monitoring positions were keyed by the legacy reference
+    fn rekey_proof_state(before: &Company, after: &Company) {
+        let pairs = before
+            .web3_identity
+            .verification_proofs
+            .iter()
+            .zip(after.web3_identity.verification_proofs.iter());
+        for (old, new) in pairs {
+            if let (None, Some(proof_id)) = (&old.id, &new.id) {
+                StorageManager::rekey_proof_state(&before.id, &VerificationManager::legacy_proof_id(old), proof_id);
+            }
+        }
+    }
+}
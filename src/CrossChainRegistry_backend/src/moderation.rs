@@ -0,0 +1,337 @@
+use crate::admins::AdminManager;
+use crate::sanitize;
+use crate::storage::StorageManager;
+use crate::types::{
+    ModerationTask, ModerationTaskKind, ModerationTaskStatus, Moderator, ModeratorWorkload,
+    RegistryResult,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+use unicode_segmentation::UnicodeSegmentation;
+
+// Moderation task queue: disputes, appeals and flagged registrations are assigned to
+// moderators, and only the assignee or a super-admin (registry admin or controller) may
+// take the final action on an assigned task.
+
+pub struct ModerationManager;
+
+impl ModerationManager {
+    const MAX_SUMMARY_LENGTH: usize = 500;
+    const MAX_OUTCOME_LENGTH: usize = 500;
+    const THROUGHPUT_WINDOW_NS: u64 = 7 * 24 * 60 * 60 * 1_000_000_000; // 7 days
+
+    // Moderator roster
+
+    pub fn is_moderator(principal: Principal) -> bool {
+        AdminManager::is_admin(principal) || StorageManager::get_moderator(&principal).is_some()
+    }
+
+    pub fn ensure_moderator(principal: Principal) -> Result<(), String> {
+        if Self::is_moderator(principal) {
+            Ok(())
+        } else {
+            Err("Unauthorized: Moderator access required".to_string())
+        }
+    }
+
+    pub fn add_moderator(principal: Principal, caller_principal: Principal) -> RegistryResult<Moderator> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if principal == Principal::anonymous() {
+            return RegistryResult::Err("The anonymous principal cannot be a moderator".to_string());
+        }
+        if StorageManager::get_moderator(&principal).is_some() {
+            return RegistryResult::Err("Principal is already a moderator".to_string());
+        }
+
+        let moderator = Moderator {
+            principal,
+            added_by: caller_principal,
+            added_at: time(),
+        };
+        StorageManager::insert_moderator(moderator.clone());
+        RegistryResult::Ok(moderator)
+    }
+
+    // Open tasks assigned to the removed moderator go back to the queue
+    pub fn remove_moderator(principal: Principal, caller_principal: Principal) -> RegistryResult<()> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if StorageManager::remove_moderator(&principal).is_none() {
+            return RegistryResult::Err("Moderator not found".to_string());
+        }
+
+        for mut task in StorageManager::get_all_moderation_tasks() {
+            if task.status == ModerationTaskStatus::Assigned && task.assignee == Some(principal) {
+                task.status = ModerationTaskStatus::Open;
+                task.assignee = None;
+                task.assigned_by = None;
+                task.assigned_at = None;
+                StorageManager::insert_moderation_task(task.id.clone(), task);
+            }
+        }
+
+        RegistryResult::Ok(())
+    }
+
+    pub fn list_moderators(caller_principal: Principal) -> RegistryResult<Vec<Moderator>> {
+        if let Err(err) = Self::ensure_moderator(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        RegistryResult::Ok(StorageManager::get_all_moderators())
+    }
+
+    // Task lifecycle
+
+    // Queue a task unless one is already open for the same reference
+    pub fn create_task(
+        kind: ModerationTaskKind,
+        company_id: &str,
+        reference_id: &str,
+        summary: String,
+    ) -> ModerationTask {
+        if let Some(existing) = Self::find_open_task(&kind, reference_id) {
+            return existing;
+        }
+
+        let mut summary = summary;
+        if sanitize::grapheme_len(&summary) > Self::MAX_SUMMARY_LENGTH {
+            summary = summary.graphemes(true).take(Self::MAX_SUMMARY_LENGTH).collect();
+        }
+
+        let task = ModerationTask {
+            id: format!("task_{}_{}", time(), StorageManager::moderation_task_count()),
+            kind,
+            company_id: company_id.to_string(),
+            reference_id: reference_id.to_string(),
+            summary,
+            status: ModerationTaskStatus::Open,
+            created_at: time(),
+            assignee: None,
+            assigned_by: None,
+            assigned_at: None,
+            closed_by: None,
+            closed_at: None,
+            outcome: None,
+            dispute_status: None,
+            review_started_at: None,
+        };
+
+        StorageManager::insert_moderation_task(task.id.clone(), task.clone());
+        task
+    }
+
+    pub fn open_moderation_task(
+        kind: ModerationTaskKind,
+        company_id: String,
+        reference_id: String,
+        summary: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<ModerationTask> {
+        if let Err(err) = Self::ensure_moderator(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if StorageManager::get_company(&company_id).is_none() {
+            return RegistryResult::Err("Company not found".to_string());
+        }
+        if reference_id.trim().is_empty() || reference_id.len() > 200 {
+            return RegistryResult::Err("Reference must be 1-200 characters".to_string());
+        }
+        if sanitize::grapheme_len(&summary) > Self::MAX_SUMMARY_LENGTH {
+            return RegistryResult::Err("Summary exceeds maximum length of 500 characters".to_string());
+        }
+
+        RegistryResult::Ok(Self::create_task(kind, &company_id, reference_id.trim(), summary))
+    }
+
+    // Super-admins assign to anyone on the roster; moderators may only claim unassigned tasks
+    pub fn assign_moderation_task(
+        task_id: String,
+        assignee: Principal,
+        caller_principal: Principal,
+    ) -> RegistryResult<ModerationTask> {
+        let mut task = match StorageManager::get_moderation_task(&task_id) {
+            Some(task) => task,
+            None => return RegistryResult::Err("Moderation task not found".to_string()),
+        };
+
+        let is_super_admin = AdminManager::is_admin(caller_principal);
+        if !is_super_admin {
+            if let Err(err) = Self::ensure_moderator(caller_principal) {
+                return RegistryResult::Err(err);
+            }
+            if assignee != caller_principal || task.assignee.is_some() {
+                return RegistryResult::Err(
+                    "Unauthorized: Moderators can only claim unassigned tasks for themselves".to_string(),
+                );
+            }
+        }
+
+        if task.status == ModerationTaskStatus::Closed {
+            return RegistryResult::Err("Moderation task is already closed".to_string());
+        }
+        if !Self::is_moderator(assignee) {
+            return RegistryResult::Err("Assignee must be a moderator".to_string());
+        }
+
+        task.status = ModerationTaskStatus::Assigned;
+        task.assignee = Some(assignee);
+        task.assigned_by = Some(caller_principal);
+        task.assigned_at = Some(time());
+        StorageManager::insert_moderation_task(task_id, task.clone());
+
+        RegistryResult::Ok(task)
+    }
+
+    pub fn close_moderation_task(
+        task_id: String,
+        outcome: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<ModerationTask> {
+        let task = match StorageManager::get_moderation_task(&task_id) {
+            Some(task) => task,
+            None => return RegistryResult::Err("Moderation task not found".to_string()),
+        };
+
+        if let Err(err) = Self::ensure_can_finalize(&task, caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if task.status == ModerationTaskStatus::Closed {
+            return RegistryResult::Err("Moderation task is already closed".to_string());
+        }
+        if task.dispute_status.is_some() {
+            return RegistryResult::Err("Proof disputes are closed with resolve_report".to_string());
+        }
+        if outcome.trim().is_empty() || sanitize::grapheme_len(&outcome) > Self::MAX_OUTCOME_LENGTH {
+            return RegistryResult::Err("Outcome must be 1-500 characters".to_string());
+        }
+
+        RegistryResult::Ok(Self::close_task(task, outcome, caller_principal))
+    }
+
+    // Final actions on an assigned task belong to its assignee or a super-admin;
+    // unassigned tasks can be finalized by any moderator
+    pub fn ensure_can_finalize(task: &ModerationTask, caller_principal: Principal) -> Result<(), String> {
+        if AdminManager::is_admin(caller_principal) {
+            return Ok(());
+        }
+        Self::ensure_moderator(caller_principal)?;
+        match task.assignee {
+            Some(assignee) if assignee != caller_principal => Err(
+                "Unauthorized: Only the assigned moderator or a super-admin can finalize this task".to_string(),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    // Used by flows that act on a task's reference (e.g. reviewing an impersonation report)
+    pub fn ensure_can_finalize_reference(
+        kind: &ModerationTaskKind,
+        reference_id: &str,
+        caller_principal: Principal,
+    ) -> Result<(), String> {
+        match Self::find_open_task(kind, reference_id) {
+            Some(task) => Self::ensure_can_finalize(&task, caller_principal),
+            None => Self::ensure_moderator(caller_principal),
+        }
+    }
+
+    pub fn close_reference(kind: &ModerationTaskKind, reference_id: &str, outcome: String, closed_by: Principal) {
+        if let Some(task) = Self::find_open_task(kind, reference_id) {
+            Self::close_task(task, outcome, closed_by);
+        }
+    }
+
+    pub fn close_task(mut task: ModerationTask, outcome: String, closed_by: Principal) -> ModerationTask {
+        task.status = ModerationTaskStatus::Closed;
+        task.closed_by = Some(closed_by);
+        task.closed_at = Some(time());
+        task.outcome = Some(outcome.trim().to_string());
+        StorageManager::insert_moderation_task(task.id.clone(), task.clone());
+        task
+    }
+
+    fn find_open_task(kind: &ModerationTaskKind, reference_id: &str) -> Option<ModerationTask> {
+        StorageManager::get_all_moderation_tasks()
+            .into_iter()
+            .find(|t| t.kind == *kind && t.reference_id == reference_id && t.status != ModerationTaskStatus::Closed)
+    }
+
+    // Queries
+
+    pub fn get_moderation_tasks(
+        status: Option<ModerationTaskStatus>,
+        assignee: Option<Principal>,
+        caller_principal: Principal,
+    ) -> RegistryResult<Vec<ModerationTask>> {
+        if let Err(err) = Self::ensure_moderator(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        let mut tasks: Vec<ModerationTask> = StorageManager::get_all_moderation_tasks()
+            .into_iter()
+            .filter(|t| status.as_ref().is_none_or(|s| t.status == *s))
+            .filter(|t| assignee.is_none() || t.assignee == assignee)
+            .collect();
+        tasks.sort_by_key(|t| t.created_at);
+        RegistryResult::Ok(tasks)
+    }
+
+    pub fn get_moderator_workload(caller_principal: Principal) -> RegistryResult<Vec<ModeratorWorkload>> {
+        if let Err(err) = Self::ensure_moderator(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        let tasks = StorageManager::get_all_moderation_tasks();
+        let now = time();
+
+        // Roster members plus anyone (e.g. a super-admin) who has handled a task
+        let mut moderators: Vec<Principal> = StorageManager::get_all_moderators()
+            .into_iter()
+            .map(|m| m.principal)
+            .collect();
+        for task in &tasks {
+            for principal in [task.assignee, task.closed_by].into_iter().flatten() {
+                if !moderators.contains(&principal) {
+                    moderators.push(principal);
+                }
+            }
+        }
+
+        let workloads = moderators
+            .into_iter()
+            .map(|moderator| {
+                let open_tasks = tasks
+                    .iter()
+                    .filter(|t| t.status == ModerationTaskStatus::Assigned && t.assignee == Some(moderator))
+                    .count() as u32;
+                let closed: Vec<&ModerationTask> = tasks
+                    .iter()
+                    .filter(|t| t.status == ModerationTaskStatus::Closed && t.closed_by == Some(moderator))
+                    .collect();
+                let closed_last_7_days = closed
+                    .iter()
+                    .filter(|t| t.closed_at.unwrap_or(0).saturating_add(Self::THROUGHPUT_WINDOW_NS) >= now)
+                    .count() as u32;
+                let durations: Vec<u64> = closed
+                    .iter()
+                    .filter_map(|t| Some(t.closed_at?.saturating_sub(t.assigned_at?)))
+                    .collect();
+                let avg_resolution_secs = (durations.iter().sum::<u64>() / 1_000_000_000)
+                    .checked_div(durations.len() as u64);
+
+                ModeratorWorkload {
+                    moderator,
+                    open_tasks,
+                    closed_tasks: closed.len() as u32,
+                    closed_last_7_days,
+                    avg_resolution_secs,
+                }
+            })
+            .collect();
+
+        RegistryResult::Ok(workloads)
+    }
+}
@@ -0,0 +1,142 @@
+use crate::admins::AdminManager;
+use crate::moderation::ModerationManager;
+use crate::security::SecurityLog;
+use crate::storage::StorageManager;
+use crate::types::{
+    ModerationTask, ModeratorNote, ModeratorNoteThread, NoteAccess, NoteAccessKind, RegistryResult,
+    SecurityEventType,
+};
+use crate::vetkd::VetKdManager;
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Private investigation notes on moderation tasks (disputes, appeals, flagged
+// registrations). Each task has its own vetKD key, derived from the task id; moderators
+// fetch it encrypted to a transport key, encrypt notes client-side and hand the canister
+// ciphertext only, so neither the canister state nor a node operator sees the text.
+//
+// Only the moderator roster (admins and controllers included) can derive a task's key or
+// read and add its notes. Reads are update calls so every access lands in the task's
+// access trail and the moderator's security feed; admins review the trail.
+
+pub struct ModeratorNoteManager;
+
+impl ModeratorNoteManager {
+    const KEY_CONTEXT: &'static [u8] = b"moderator_notes";
+    const MAX_NOTE_BYTES: usize = 8 * 1024;
+    const MAX_NOTES_PER_TASK: usize = 200;
+    const MAX_ACCESS_LOG: usize = 500;
+
+    pub async fn get_moderator_notes_public_key(caller_principal: Principal) -> RegistryResult<Vec<u8>> {
+        if let Err(err) = ModerationManager::ensure_moderator(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        match VetKdManager::public_key(Self::KEY_CONTEXT).await {
+            Ok(public_key) => RegistryResult::Ok(public_key),
+            Err(err) => RegistryResult::Err(err),
+        }
+    }
+
+    pub async fn derive_moderator_note_key(
+        task_id: String,
+        transport_public_key: Vec<u8>,
+        caller_principal: Principal,
+    ) -> RegistryResult<Vec<u8>> {
+        let task = match Self::authorized_task(&task_id, caller_principal) {
+            Ok(task) => task,
+            Err(err) => return RegistryResult::Err(err),
+        };
+
+        match VetKdManager::derive_key(Self::KEY_CONTEXT, task.id.as_bytes(), transport_public_key).await {
+            Ok(encrypted_key) => {
+                Self::record_access(&task, caller_principal, NoteAccessKind::KeyDerived);
+                RegistryResult::Ok(encrypted_key)
+            }
+            Err(err) => RegistryResult::Err(err),
+        }
+    }
+
+    pub fn add_moderator_note(
+        task_id: String,
+        ciphertext: Vec<u8>,
+        caller_principal: Principal,
+    ) -> RegistryResult<ModeratorNote> {
+        let task = match Self::authorized_task(&task_id, caller_principal) {
+            Ok(task) => task,
+            Err(err) => return RegistryResult::Err(err),
+        };
+        if ciphertext.is_empty() || ciphertext.len() > Self::MAX_NOTE_BYTES {
+            return RegistryResult::Err(format!("Note must be 1-{} bytes of ciphertext", Self::MAX_NOTE_BYTES));
+        }
+
+        let mut thread = Self::thread(&task);
+        if thread.notes.len() >= Self::MAX_NOTES_PER_TASK {
+            return RegistryResult::Err(format!(
+                "Task already has the maximum of {} notes",
+                Self::MAX_NOTES_PER_TASK
+            ));
+        }
+
+        let note = ModeratorNote {
+            id: format!("note_{}_{}", time(), thread.notes.len()),
+            ciphertext,
+            author: caller_principal,
+            created_at: time(),
+        };
+        thread.notes.push(note.clone());
+        StorageManager::insert_moderator_note_thread(thread);
+        Self::record_access(&task, caller_principal, NoteAccessKind::NoteAdded);
+        RegistryResult::Ok(note)
+    }
+
+    // Oldest first
+    pub fn get_moderator_notes(task_id: String, caller_principal: Principal) -> RegistryResult<Vec<ModeratorNote>> {
+        let task = match Self::authorized_task(&task_id, caller_principal) {
+            Ok(task) => task,
+            Err(err) => return RegistryResult::Err(err),
+        };
+        let notes = Self::thread(&task).notes;
+        Self::record_access(&task, caller_principal, NoteAccessKind::NotesRead);
+        RegistryResult::Ok(notes)
+    }
+
+    pub fn get_moderator_note_access_log(
+        task_id: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<Vec<NoteAccess>> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        match StorageManager::get_moderator_note_thread(&task_id) {
+            Some(thread) => RegistryResult::Ok(thread.access_log),
+            None if StorageManager::get_moderation_task(&task_id).is_some() => RegistryResult::Ok(Vec::new()),
+            None => RegistryResult::Err("Moderation task not found".to_string()),
+        }
+    }
+
+    fn authorized_task(task_id: &str, caller_principal: Principal) -> Result<ModerationTask, String> {
+        ModerationManager::ensure_moderator(caller_principal)?;
+        StorageManager::get_moderation_task(task_id).ok_or_else(|| "Moderation task not found".to_string())
+    }
+
+    fn thread(task: &ModerationTask) -> ModeratorNoteThread {
+        StorageManager::get_moderator_note_thread(&task.id).unwrap_or_else(|| ModeratorNoteThread {
+            task_id: task.id.clone(),
+            company_id: task.company_id.clone(),
+            notes: Vec::new(),
+            access_log: Vec::new(),
+        })
+    }
+
+    fn record_access(task: &ModerationTask, principal: Principal, kind: NoteAccessKind) {
+        let details = format!("{:?} on {}", kind, task.id);
+        let mut thread = Self::thread(task);
+        thread.access_log.push(NoteAccess { principal, kind, at: time() });
+        if thread.access_log.len() > Self::MAX_ACCESS_LOG {
+            let excess = thread.access_log.len() - Self::MAX_ACCESS_LOG;
+            thread.access_log.drain(..excess);
+        }
+        StorageManager::insert_moderator_note_thread(thread);
+        SecurityLog::record(principal, SecurityEventType::ModeratorNoteAccess, Some(task.company_id.clone()), details);
+    }
+}
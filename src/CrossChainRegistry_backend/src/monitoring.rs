@@ -0,0 +1,1303 @@
+use crate::admins::AdminManager;
+use crate::alert_routing::AlertRoutingManager;
+use crate::jobs::{JobRunner, JobTask};
+use crate::moderation::ModerationManager;
+use crate::notifications::NotificationManager;
+use crate::ownership::OwnershipManager;
+use crate::policy::PolicyLog;
+use crate::settings::SettingsManager;
+use crate::storage::StorageManager;
+use crate::types::{
+    AlertSeverity, AlertType, AlertVisibility, ApiProvider, CanisterSnapshot, ChainType, CommunityAlert,
+    ContractWatch, EvmLogEvent, MonitoringConfig, NotificationChannel, ProofStatus, ProviderEndpoint, RegistryResult,
+    TreasuryBalanceSample, TreasurySnapshot,
+};
+use crate::verification::VerificationManager;
+use crate::webhooks::WebhookManager;
+use candid::Principal;
+use ic_cdk::api::management_canister::http_request::{
+    CanisterHttpRequestArgument, HttpHeader, HttpMethod, HttpResponse, TransformArgs,
+    TransformContext,
+};
+use ic_cdk::api::management_canister::main::{
+    canister_info, CanisterInfoRequest, CanisterInfoResponse,
+};
+use ic_cdk::api::time;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+// Background monitoring of verified assets and the community alerts it raises.
+//
+// Contract monitoring: owners opt verified Ethereum/Polygon contracts in, and an hourly
+// timer queries eth_getLogs for ownership-transfer and proxy-upgrade events. A contract
+// changing hands invalidates the trust its verification implied, so each event becomes a
+// public alert on the company.
+//
+// Canister monitoring: every verified ICP canister is checked via canister_info. Module
+// upgrades raise an informational alert; a changed controller set means the canister may
+// have been sold or hijacked, so it raises a critical alert and the canister loses its
+// verified status until the owner verifies it again.
+//
+// Treasury monitoring: balances of verified treasury wallets are sampled hourly. A drop
+// from the window's peak beyond the configured percentage raises an alert and notifies
+// the owner, as an early warning of a rug-pull or a compromised key.
+
+// keccak256("OwnershipTransferred(address,address)")
+const OWNERSHIP_TRANSFERRED_TOPIC: &str =
+    "0x8be0079c531659141344cd1fd0a4f28419497f9722a3daafe3b4186f6b6457e0";
+// keccak256("Upgraded(address)")
+const UPGRADED_TOPIC: &str = "0xbc7cd75a20ee27fd9adebab32041f755214dbc6bffa90cc0225b39da2e5c2d3b";
+// keccak256("AdminChanged(address,address)")
+const ADMIN_CHANGED_TOPIC: &str =
+    "0x7e644d79422f17c01e4894b5f4f588d331ebfa28653d42ae832dc59e38c9798f";
+
+pub struct MonitoringSystem;
+
+impl MonitoringSystem {
+    const DEFAULT_CONTRACT_INTERVAL_MINS: u32 = 60;
+    const DEFAULT_CANISTER_INTERVAL_MINS: u32 = 6 * 60;
+    const DEFAULT_TREASURY_INTERVAL_MINS: u32 = 60;
+    const DEFAULT_ESCALATION_INTERVAL_MINS: u32 = 60;
+    const DEFAULT_PROOF_INTERVAL_MINS: u32 = 30;
+    const DEFAULT_PROOF_CHECKS_PER_RUN: u32 = 20;
+    const MAX_PROOF_CHECKS_PER_RUN: u32 = 200;
+    // Evidence kept per alert for dedup; log scans only look back a bounded block range
+    const MAX_EVIDENCE_KEYS: usize = 500;
+    const MIN_INTERVAL_MINS: u32 = 5;
+    const MAX_INTERVAL_MINS: u32 = 7 * 24 * 60;
+    const DEFAULT_OUTFLOW_THRESHOLD_PERCENT: u32 = 30;
+    const DEFAULT_OUTFLOW_WINDOW_HOURS: u32 = 24;
+    const MAX_OUTFLOW_WINDOW_HOURS: u32 = 30 * 24;
+    const DEFAULT_DEDUP_WINDOW_HOURS: u32 = 24;
+    const MAX_DEDUP_WINDOW_HOURS: u32 = 7 * 24;
+    const DEFAULT_ESCALATION_AFTER_HOURS: u32 = 12;
+    const MAX_ESCALATION_AFTER_HOURS: u32 = 7 * 24;
+    const MAX_WATCHES_PER_COMPANY: usize = 20;
+    const MAX_BLOCK_RANGE: u64 = 2_000;
+    // Replicas must agree on the block range, so the head is taken a few blocks back and
+    // rounded down (see transform_evm_block_number)
+    const CONFIRMATION_BLOCKS: u64 = 12;
+    const BLOCK_ROUNDING: u64 = 16;
+
+    // Background jobs; the names key the last-run times in storage
+    const JOBS: [&'static str; 5] = ["contracts", "canisters", "treasury", "escalation", "proofs"];
+
+    // Monitoring runs off a single one-shot timer armed for whichever job is due next,
+    // so nothing wakes the canister between runs. Last-run times are stable, so after an
+    // upgrade an overdue job runs right away and the rest keep their rhythm.
+    pub fn start_timers() {
+        Self::schedule_next();
+    }
+
+    fn job_interval_ns(config: &MonitoringConfig, job: &str) -> u64 {
+        let mins = match job {
+            "contracts" => config.contract_interval_mins.unwrap_or(Self::DEFAULT_CONTRACT_INTERVAL_MINS),
+            "canisters" => config.canister_interval_mins.unwrap_or(Self::DEFAULT_CANISTER_INTERVAL_MINS),
+            "treasury" => config.treasury_interval_mins.unwrap_or(Self::DEFAULT_TREASURY_INTERVAL_MINS),
+            "proofs" => config.proof_interval_mins.unwrap_or(Self::DEFAULT_PROOF_INTERVAL_MINS),
+            _ => config.escalation_interval_mins.unwrap_or(Self::DEFAULT_ESCALATION_INTERVAL_MINS),
+        };
+        mins as u64 * 60 * 1_000_000_000
+    }
+
+    // A job that never ran is due immediately
+    fn job_due_at(config: &MonitoringConfig, job: &str) -> u64 {
+        StorageManager::get_monitoring_last_run(job)
+            .map_or(0, |last_run| last_run.saturating_add(Self::job_interval_ns(config, job)))
+    }
+
+    // Re-arms the wakeup; called at start, after every wakeup and when intervals change
+    fn schedule_next() {
+        let config = Self::get_monitoring_config();
+        let next_due = Self::JOBS
+            .iter()
+            .map(|job| Self::job_due_at(&config, job))
+            .min()
+            .unwrap_or(0);
+        let delay = Duration::from_nanos(next_due.saturating_sub(time()));
+        let timer_id = ic_cdk_timers::set_timer(delay, || {
+            Self::run_due_jobs();
+            Self::schedule_next();
+        });
+        if let Some(previous) = StorageManager::replace_monitoring_wakeup(timer_id) {
+            ic_cdk_timers::clear_timer(previous);
+        }
+    }
+
+    fn run_due_jobs() {
+        let config = Self::get_monitoring_config();
+        let now = time();
+        for job in Self::JOBS {
+            if Self::job_due_at(&config, job) <= now {
+                Self::run_job(job);
+            }
+        }
+    }
+
+    fn run_job(job: &'static str) {
+        StorageManager::set_monitoring_last_run(job, time());
+        match job {
+            "contracts" => ic_cdk::spawn(async {
+                Self::run_contract_monitoring().await;
+            }),
+            "canisters" => ic_cdk::spawn(async {
+                Self::run_canister_monitoring().await;
+            }),
+            "treasury" => ic_cdk::spawn(async {
+                Self::run_treasury_monitoring().await;
+            }),
+            "proofs" => ic_cdk::spawn(async {
+                Self::run_proof_monitoring().await;
+            }),
+            _ => {
+                Self::run_alert_escalation();
+            }
+        }
+    }
+
+    fn rpc_provider(chain_type: &ChainType) -> Result<ApiProvider, String> {
+        match chain_type {
+            ChainType::Ethereum => Ok(ApiProvider::EthereumRpc),
+            ChainType::Polygon => Ok(ApiProvider::PolygonRpc),
+            _ => Err("Contract monitoring is only available for Ethereum and Polygon".to_string()),
+        }
+    }
+
+    fn watch_key(chain_type: &ChainType, contract_address: &str) -> String {
+        format!("{:?}_{}", chain_type, contract_address.to_lowercase())
+    }
+
+    // Alert operations
+
+    // Visibility and notifications follow the alert routing policy. A repeat of an open
+    // (unacknowledged) alert for the same company, type and subject within the dedup window
+    // adds its evidence to that alert and bumps the occurrence count instead of creating a
+    // new one; evidence the alert already holds is not counted again.
+    pub fn raise_alert(
+        company_id: &str,
+        alert_type: AlertType,
+        severity: AlertSeverity,
+        message: String,
+        chain_type: Option<ChainType>,
+        subject: &str,
+        evidence: &str,
+    ) -> CommunityAlert {
+        let alerts: Vec<CommunityAlert> = StorageManager::get_all_community_alerts()
+            .into_iter()
+            .filter(|a| a.company_id == company_id && a.alert_type == alert_type)
+            .collect();
+
+        // The same on-chain event is only reported once
+        if let Some(existing) = alerts.iter().find(|a| Self::has_evidence(a, evidence)) {
+            return existing.clone();
+        }
+
+        let now = time();
+        let window_ns = Self::dedup_window_hours() as u64 * 60 * 60 * 1_000_000_000;
+        if let Some(mut open) = alerts.into_iter().find(|a| {
+            a.subject == subject
+                && a.acknowledged_at.is_none()
+                && a.last_seen_at.unwrap_or(a.created_at).saturating_add(window_ns) >= now
+        }) {
+            let mut keys = open.evidence_keys.take().unwrap_or_else(|| vec![open.evidence.clone()]);
+            keys.push(evidence.to_string());
+            if keys.len() > Self::MAX_EVIDENCE_KEYS {
+                keys.drain(..keys.len() - Self::MAX_EVIDENCE_KEYS);
+            }
+            open.evidence_keys = Some(keys);
+            open.occurrence_count = Some(open.occurrence_count.unwrap_or(1).saturating_add(1));
+            open.last_seen_at = Some(now);
+            open.severity = open.severity.max(severity);
+            open.message = message;
+            open.evidence = evidence.to_string();
+            StorageManager::insert_community_alert(open.id.clone(), open.clone());
+            return open;
+        }
+
+        let (visibility, channels) = AlertRoutingManager::resolve_route(&alert_type, &severity);
+
+        let alert = CommunityAlert {
+            id: format!("alert_{}_{}", time(), StorageManager::community_alert_count()),
+            company_id: company_id.to_string(),
+            alert_type,
+            severity,
+            message,
+            chain_type,
+            subject: subject.to_string(),
+            evidence: evidence.to_string(),
+            created_at: time(),
+            acknowledged_by: None,
+            acknowledged_at: None,
+            visibility: Some(visibility),
+            channels: Some(channels.clone()),
+            occurrence_count: None,
+            last_seen_at: None,
+            escalated_at: None,
+            score_paused: None,
+            evidence_keys: None,
+        };
+
+        StorageManager::insert_community_alert(alert.id.clone(), alert.clone());
+        WebhookManager::enqueue_alert(&alert);
+
+        if channels.contains(&NotificationChannel::OwnerInbox) {
+            if let Some(company) = StorageManager::get_company(company_id) {
+                NotificationManager::notify(
+                    company.created_by,
+                    company_id,
+                    Some(alert.id.clone()),
+                    alert.message.clone(),
+                );
+            }
+        }
+
+        alert
+    }
+
+    pub fn can_view_alert(alert: &CommunityAlert, caller_principal: Principal) -> bool {
+        match alert.visibility.as_ref().unwrap_or(&AlertVisibility::Public) {
+            AlertVisibility::Public => true,
+            AlertVisibility::OwnerOnly => {
+                StorageManager::get_company(&alert.company_id).is_some_and(|c| OwnershipManager::is_owner(&c, caller_principal))
+                    || ModerationManager::is_moderator(caller_principal)
+            }
+            AlertVisibility::Moderators => ModerationManager::is_moderator(caller_principal),
+        }
+    }
+
+    pub fn get_community_alerts(company_id: String, caller_principal: Principal) -> Vec<CommunityAlert> {
+        let mut alerts: Vec<CommunityAlert> = StorageManager::get_all_community_alerts()
+            .into_iter()
+            .filter(|a| a.company_id == company_id && Self::can_view_alert(a, caller_principal))
+            .collect();
+        alerts.sort_by_key(|a| std::cmp::Reverse(a.created_at));
+        alerts
+    }
+
+    // Unacknowledged alerts routed to moderators, most severe first
+    pub fn get_moderator_alert_queue(caller_principal: Principal) -> RegistryResult<Vec<CommunityAlert>> {
+        if let Err(err) = ModerationManager::ensure_moderator(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        let mut alerts: Vec<CommunityAlert> = StorageManager::get_all_community_alerts()
+            .into_iter()
+            .filter(|a| {
+                a.acknowledged_at.is_none()
+                    && a
+                        .channels
+                        .as_ref()
+                        .is_some_and(|channels| channels.contains(&NotificationChannel::ModeratorQueue))
+            })
+            .collect();
+        alerts.sort_by_key(|a| (std::cmp::Reverse(a.severity.clone()), a.created_at));
+        RegistryResult::Ok(alerts)
+    }
+
+    pub fn acknowledge_alert(alert_id: String, caller_principal: Principal) -> RegistryResult<CommunityAlert> {
+        let mut alert = match StorageManager::get_community_alert(&alert_id) {
+            Some(alert) => alert,
+            None => return RegistryResult::Err("Alert not found".to_string()),
+        };
+
+        let is_moderator = ModerationManager::is_moderator(caller_principal);
+        let is_owner = StorageManager::get_company(&alert.company_id)
+            .is_some_and(|c| OwnershipManager::is_owner(&c, caller_principal));
+        if !is_owner && !is_moderator {
+            return RegistryResult::Err(
+                "Unauthorized: Only moderators or the company owner can acknowledge alerts".to_string(),
+            );
+        }
+        if alert.acknowledged_at.is_some() {
+            return RegistryResult::Err("Alert has already been acknowledged".to_string());
+        }
+
+        // Owners cannot lift a protective measure on their own score
+        let score_paused = alert.score_paused.unwrap_or(false);
+        if score_paused && !is_moderator {
+            return RegistryResult::Err(
+                "This alert paused score contributions and must be acknowledged by a moderator".to_string(),
+            );
+        }
+
+        alert.acknowledged_by = Some(caller_principal);
+        alert.acknowledged_at = Some(time());
+        StorageManager::insert_community_alert(alert_id, alert.clone());
+
+        if score_paused {
+            let subject = alert.subject.clone();
+            StorageManager::update_company(&alert.company_id, |company| {
+                if let Some(paused) = company.paused_score_sources.as_mut() {
+                    paused.retain(|address| *address != subject);
+                }
+                company.verification_score = VerificationManager::calculate_verification_score(company);
+            });
+        }
+
+        RegistryResult::Ok(alert)
+    }
+
+    // Contract watch operations
+
+    pub fn enable_contract_monitoring(
+        company_id: String,
+        chain_type: ChainType,
+        contract_address: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<ContractWatch> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        if !OwnershipManager::is_owner(&company, caller_principal) {
+            return RegistryResult::Err(
+                "Unauthorized: Only company creator can configure contract monitoring".to_string(),
+            );
+        }
+
+        if let Err(err) = Self::rpc_provider(&chain_type) {
+            return RegistryResult::Err(err);
+        }
+
+        let contract_address = contract_address.trim().to_string();
+        let verified_contracts = match chain_type {
+            ChainType::Polygon => &company.cross_chain_presence.polygon_contracts,
+            _ => &company.cross_chain_presence.ethereum_contracts,
+        };
+        if !verified_contracts.iter().any(|c| c.eq_ignore_ascii_case(&contract_address)) {
+            return RegistryResult::Err("Contract is not a verified contract of this company".to_string());
+        }
+
+        let key = Self::watch_key(&chain_type, &contract_address);
+        if StorageManager::get_contract_watch(&key).is_some() {
+            return RegistryResult::Err("Contract monitoring is already enabled".to_string());
+        }
+
+        let existing = Self::get_contract_watches(company_id.clone());
+        if existing.len() >= Self::MAX_WATCHES_PER_COMPANY {
+            return RegistryResult::Err(format!(
+                "Cannot monitor more than {} contracts per company",
+                Self::MAX_WATCHES_PER_COMPANY
+            ));
+        }
+
+        let watch = ContractWatch {
+            company_id,
+            chain_type,
+            contract_address,
+            enabled_by: caller_principal,
+            enabled_at: time(),
+            last_checked_block: None,
+            last_checked_at: None,
+            last_error: None,
+        };
+
+        StorageManager::insert_contract_watch(key, watch.clone());
+        RegistryResult::Ok(watch)
+    }
+
+    pub fn disable_contract_monitoring(
+        company_id: String,
+        chain_type: ChainType,
+        contract_address: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<()> {
+        let key = Self::watch_key(&chain_type, contract_address.trim());
+        let watch = match StorageManager::get_contract_watch(&key) {
+            Some(watch) if watch.company_id == company_id => watch,
+            _ => return RegistryResult::Err("Contract monitoring is not enabled".to_string()),
+        };
+
+        let is_owner = StorageManager::get_company(&watch.company_id)
+            .is_some_and(|c| OwnershipManager::is_owner(&c, caller_principal));
+        if !is_owner && !AdminManager::is_admin(caller_principal) {
+            return RegistryResult::Err(
+                "Unauthorized: Only company creator can configure contract monitoring".to_string(),
+            );
+        }
+
+        StorageManager::remove_contract_watch(&key);
+        RegistryResult::Ok(())
+    }
+
+    pub fn get_contract_watches(company_id: String) -> Vec<ContractWatch> {
+        StorageManager::get_all_contract_watches()
+            .into_iter()
+            .map(|(_, watch)| watch)
+            .filter(|watch| watch.company_id == company_id)
+            .collect()
+    }
+
+    // Check every watched contract once; returns the number of alerts raised
+    pub async fn run_contract_monitoring() -> u32 {
+        let tasks = StorageManager::get_all_contract_watches()
+            .into_iter()
+            .map(|(key, watch)| {
+                let provider = Self::rpc_provider(&watch.chain_type)
+                    .map_or("unsupported".to_string(), |provider| format!("{:?}", provider));
+                JobTask::new(provider, move || Self::monitor_contract(key, watch))
+            })
+            .collect();
+        JobRunner::run("monitoring:contracts", tasks).await
+    }
+
+    async fn monitor_contract(key: String, watch: ContractWatch) -> Result<u32, String> {
+        let result = Self::check_contract(&watch).await;
+
+        // The watch may have been removed while the outcalls were in flight
+        let Some(mut current) = StorageManager::get_contract_watch(&key) else {
+            return Ok(0);
+        };
+        current.last_checked_at = Some(time());
+        let mut raised = 0;
+        let outcome = match result {
+            Ok((checked_to, events)) => {
+                current.last_checked_block = Some(checked_to);
+                current.last_error = None;
+                for event in events {
+                    if Self::alert_for_event(&current, &event).is_some() {
+                        raised += 1;
+                    }
+                }
+                Ok(raised)
+            }
+            Err(err) => {
+                current.last_error = Some(err.clone());
+                Err(err)
+            }
+        };
+        StorageManager::insert_contract_watch(key, current);
+        outcome
+    }
+
+    pub async fn trigger_contract_monitoring(caller_principal: Principal) -> RegistryResult<u32> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        RegistryResult::Ok(Self::run_contract_monitoring().await)
+    }
+
+    // Canister monitoring operations
+
+    pub fn get_canister_snapshots(company_id: String) -> Vec<CanisterSnapshot> {
+        StorageManager::get_all_canister_snapshots()
+            .into_iter()
+            .filter(|snapshot| snapshot.company_id == company_id)
+            .collect()
+    }
+
+    // Check every verified canister once; returns the number of alerts raised
+    pub async fn run_canister_monitoring() -> u32 {
+        let targets: Vec<(String, String)> = StorageManager::get_all_companies()
+            .into_iter()
+            .flat_map(|company| {
+                let company_id = company.id;
+                company
+                    .cross_chain_presence
+                    .icp_canisters
+                    .into_iter()
+                    .map(move |canister_id| (company_id.clone(), canister_id))
+            })
+            .collect();
+
+        // Forget canisters that are no longer verified
+        for snapshot in StorageManager::get_all_canister_snapshots() {
+            let still_verified = targets.iter().any(|(company_id, canister_id)| {
+                *company_id == snapshot.company_id && *canister_id == snapshot.canister_id
+            });
+            if !still_verified {
+                StorageManager::remove_canister_snapshot(&snapshot.canister_id);
+            }
+        }
+
+        let tasks = targets
+            .into_iter()
+            .map(|(company_id, canister_id)| {
+                JobTask::new("canister_info", move || Self::monitor_canister(company_id, canister_id))
+            })
+            .collect();
+        JobRunner::run("monitoring:canisters", tasks).await
+    }
+
+    async fn monitor_canister(company_id: String, canister_id: String) -> Result<u32, String> {
+        let principal = Principal::from_text(&canister_id).map_err(|_| format!("Invalid canister id {}", canister_id))?;
+        let request = CanisterInfoRequest {
+            canister_id: principal,
+            num_requested_changes: None,
+        };
+
+        match canister_info(request).await {
+            Ok((info,)) => Ok(Self::record_canister_info(&company_id, &canister_id, info)),
+            Err((code, message)) => {
+                let err = format!("canister_info failed ({:?}): {}", code, message);
+                if let Some(mut snapshot) = StorageManager::get_canister_snapshot(&canister_id) {
+                    snapshot.last_checked_at = time();
+                    snapshot.last_error = Some(err.clone());
+                    StorageManager::insert_canister_snapshot(canister_id, snapshot);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    pub async fn trigger_canister_monitoring(caller_principal: Principal) -> RegistryResult<u32> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        RegistryResult::Ok(Self::run_canister_monitoring().await)
+    }
+
+    fn record_canister_info(company_id: &str, canister_id: &str, info: CanisterInfoResponse) -> u32 {
+        let module_hash = info
+            .module_hash
+            .map(|hash| hash.iter().map(|b| format!("{:02x}", b)).collect::<String>());
+        let mut controllers = info.controllers;
+        controllers.sort();
+        let now = time();
+
+        // Verification may have been removed while the call was in flight
+        let still_verified = StorageManager::get_company(company_id)
+            .is_some_and(|c| c.cross_chain_presence.icp_canisters.iter().any(|id| id == canister_id));
+        if !still_verified {
+            StorageManager::remove_canister_snapshot(canister_id);
+            return 0;
+        }
+
+        let mut snapshot = match StorageManager::get_canister_snapshot(canister_id) {
+            Some(previous) if previous.company_id == company_id => previous,
+            _ => {
+                // First sighting becomes the baseline
+                StorageManager::insert_canister_snapshot(
+                    canister_id.to_string(),
+                    CanisterSnapshot {
+                        company_id: company_id.to_string(),
+                        canister_id: canister_id.to_string(),
+                        module_hash,
+                        controllers,
+                        total_num_changes: info.total_num_changes,
+                        recorded_at: now,
+                        last_checked_at: now,
+                        last_error: None,
+                    },
+                );
+                return 0;
+            }
+        };
+
+        let evidence = format!("{}@{}", canister_id, info.total_num_changes);
+
+        if snapshot.controllers != controllers {
+            Self::raise_alert(
+                company_id,
+                AlertType::CanisterControllersChanged,
+                AlertSeverity::Critical,
+                format!(
+                    "Controllers of verified canister {} changed; the canister must be verified again",
+                    canister_id
+                ),
+                Some(ChainType::ICP),
+                canister_id,
+                &evidence,
+            );
+
+            StorageManager::update_company(company_id, |company| {
+                company.cross_chain_presence.icp_canisters.retain(|id| id != canister_id);
+                company.verification_score = VerificationManager::calculate_verification_score(company);
+            });
+            StorageManager::remove_canister_snapshot(canister_id);
+            return 1;
+        }
+
+        let mut raised = 0;
+        if snapshot.module_hash != module_hash {
+            Self::raise_alert(
+                company_id,
+                AlertType::CanisterModuleChanged,
+                AlertSeverity::Info,
+                format!(
+                    "Verified canister {} was upgraded to module {}",
+                    canister_id,
+                    module_hash.as_deref().unwrap_or("(none)")
+                ),
+                Some(ChainType::ICP),
+                canister_id,
+                &evidence,
+            );
+            snapshot.module_hash = module_hash;
+            snapshot.recorded_at = now;
+            raised = 1;
+        }
+
+        snapshot.total_num_changes = info.total_num_changes;
+        snapshot.last_checked_at = now;
+        snapshot.last_error = None;
+        StorageManager::insert_canister_snapshot(canister_id.to_string(), snapshot);
+        raised
+    }
+
+    // Treasury monitoring operations
+
+    pub fn get_monitoring_config() -> MonitoringConfig {
+        StorageManager::get_monitoring_config().unwrap_or(MonitoringConfig {
+            treasury_outflow_threshold_percent: Self::DEFAULT_OUTFLOW_THRESHOLD_PERCENT,
+            treasury_outflow_window_hours: Self::DEFAULT_OUTFLOW_WINDOW_HOURS,
+            alert_dedup_window_hours: None,
+            escalation_after_hours: None,
+            escalation_pauses_score: None,
+            contract_interval_mins: None,
+            canister_interval_mins: None,
+            treasury_interval_mins: None,
+            escalation_interval_mins: None,
+            proof_interval_mins: None,
+            proof_checks_per_run: None,
+        })
+    }
+
+    // Escalate Error alerts nobody acknowledged in time; returns the number escalated
+    pub fn run_alert_escalation() -> u32 {
+        let config = Self::get_monitoring_config();
+        let after_ns = config
+            .escalation_after_hours
+            .unwrap_or(Self::DEFAULT_ESCALATION_AFTER_HOURS) as u64
+            * 60
+            * 60
+            * 1_000_000_000;
+        let pause_score = config.escalation_pauses_score.unwrap_or(false);
+        let now = time();
+
+        let mut escalated = 0;
+        for mut alert in StorageManager::get_all_community_alerts() {
+            if alert.severity != AlertSeverity::Error
+                || alert.acknowledged_at.is_some()
+                || alert.escalated_at.is_some()
+                || alert.created_at.saturating_add(after_ns) > now
+            {
+                continue;
+            }
+
+            alert.severity = AlertSeverity::Critical;
+            alert.escalated_at = Some(now);
+            let channels = alert.channels.get_or_insert_with(Vec::new);
+            if !channels.contains(&NotificationChannel::ModeratorQueue) {
+                channels.push(NotificationChannel::ModeratorQueue);
+            }
+
+            if pause_score {
+                let subject = alert.subject.clone();
+                let paused = StorageManager::update_company(&alert.company_id, |company| {
+                    let sources = company.paused_score_sources.get_or_insert_with(Vec::new);
+                    if !sources.contains(&subject) {
+                        sources.push(subject);
+                    }
+                    company.verification_score = VerificationManager::calculate_verification_score(company);
+                });
+                alert.score_paused = Some(paused);
+            }
+
+            StorageManager::insert_community_alert(alert.id.clone(), alert);
+            escalated += 1;
+        }
+        escalated
+    }
+
+    fn has_evidence(alert: &CommunityAlert, evidence: &str) -> bool {
+        match &alert.evidence_keys {
+            Some(keys) => keys.iter().any(|key| key == evidence),
+            None => alert.evidence == evidence,
+        }
+    }
+
+    fn dedup_window_hours() -> u32 {
+        Self::get_monitoring_config()
+            .alert_dedup_window_hours
+            .unwrap_or(Self::DEFAULT_DEDUP_WINDOW_HOURS)
+    }
+
+    pub fn set_monitoring_config(config: MonitoringConfig, caller_principal: Principal) -> RegistryResult<()> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        if config.treasury_outflow_threshold_percent == 0 || config.treasury_outflow_threshold_percent > 100 {
+            return RegistryResult::Err("Outflow threshold must be 1-100 percent".to_string());
+        }
+        if config.treasury_outflow_window_hours == 0
+            || config.treasury_outflow_window_hours > Self::MAX_OUTFLOW_WINDOW_HOURS
+        {
+            return RegistryResult::Err(format!(
+                "Outflow window must be 1-{} hours",
+                Self::MAX_OUTFLOW_WINDOW_HOURS
+            ));
+        }
+
+        if config
+            .alert_dedup_window_hours
+            .is_some_and(|hours| hours == 0 || hours > Self::MAX_DEDUP_WINDOW_HOURS)
+        {
+            return RegistryResult::Err(format!(
+                "Alert dedup window must be 1-{} hours",
+                Self::MAX_DEDUP_WINDOW_HOURS
+            ));
+        }
+
+        if config
+            .escalation_after_hours
+            .is_some_and(|hours| hours == 0 || hours > Self::MAX_ESCALATION_AFTER_HOURS)
+        {
+            return RegistryResult::Err(format!(
+                "Escalation delay must be 1-{} hours",
+                Self::MAX_ESCALATION_AFTER_HOURS
+            ));
+        }
+
+        for mins in [
+            config.contract_interval_mins,
+            config.canister_interval_mins,
+            config.treasury_interval_mins,
+            config.escalation_interval_mins,
+            config.proof_interval_mins,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if !(Self::MIN_INTERVAL_MINS..=Self::MAX_INTERVAL_MINS).contains(&mins) {
+                return RegistryResult::Err(format!(
+                    "Job intervals must be {}-{} minutes",
+                    Self::MIN_INTERVAL_MINS,
+                    Self::MAX_INTERVAL_MINS
+                ));
+            }
+        }
+
+        if config
+            .proof_checks_per_run
+            .is_some_and(|checks| checks == 0 || checks > Self::MAX_PROOF_CHECKS_PER_RUN)
+        {
+            return RegistryResult::Err(format!(
+                "Proof checks per run must be 1-{}",
+                Self::MAX_PROOF_CHECKS_PER_RUN
+            ));
+        }
+
+        PolicyLog::record(
+            "monitoring_config",
+            Some(format!("{:?}", Self::get_monitoring_config())),
+            format!("{:?}", config),
+            vec![caller_principal],
+            "set_monitoring_config",
+        );
+        StorageManager::set_monitoring_config(config);
+        // New intervals take effect now rather than after the currently armed wakeup
+        Self::schedule_next();
+        RegistryResult::Ok(())
+    }
+
+    fn treasury_key(chain: &str, address: &str) -> String {
+        format!("{}_{}", chain, address)
+    }
+
+    pub fn get_treasury_snapshots(company_id: String) -> Vec<TreasurySnapshot> {
+        StorageManager::get_all_treasury_snapshots()
+            .into_iter()
+            .map(|(_, snapshot)| snapshot)
+            .filter(|snapshot| snapshot.company_id == company_id)
+            .collect()
+    }
+
+    // Re-fetch the social proofs that were checked longest ago, up to the per-run budget,
+    // so deleted posts and edited-out challenge text are noticed without anyone reporting
+    // them. Removed proofs are checked too: a post that was put back gets its status
+    // restored. Returns the number of proofs found removed or disputed.
+    pub async fn run_proof_monitoring() -> u32 {
+        let budget = Self::get_monitoring_config()
+            .proof_checks_per_run
+            .unwrap_or(Self::DEFAULT_PROOF_CHECKS_PER_RUN) as usize;
+        let targets: Vec<(String, String, String)> = StorageManager::get_all_companies()
+            .into_iter()
+            .flat_map(|company| {
+                let company_id = company.id;
+                company
+                    .web3_identity
+                    .verification_proofs
+                    .into_iter()
+                    .filter(|proof| VerificationManager::social_proof_domains(&proof.verification_type).is_some())
+                    .filter(|proof| proof.status != ProofStatus::Disputed)
+                    .map(move |proof| {
+                        let key = format!("{}:{}", company_id, VerificationManager::proof_id(&proof));
+                        (key, company_id.clone(), proof.proof_url)
+                    })
+            })
+            .collect();
+
+        // Forget proofs that no longer exist, then take the least recently checked
+        let target_keys: BTreeSet<&String> = targets.iter().map(|(key, _, _)| key).collect();
+        let mut last_checked: BTreeMap<String, u64> = BTreeMap::new();
+        for (key, checked_at) in StorageManager::get_all_proof_checks() {
+            if target_keys.contains(&key) {
+                last_checked.insert(key, checked_at);
+            } else {
+                StorageManager::remove_proof_check(&key);
+            }
+        }
+        let mut due: Vec<(u64, String, String, String)> = targets
+            .into_iter()
+            .map(|(key, company_id, proof_url)| (last_checked.get(&key).copied().unwrap_or(0), key, company_id, proof_url))
+            .collect();
+        due.sort();
+        due.truncate(budget);
+
+        let tasks = due
+            .into_iter()
+            .map(|(_, key, company_id, proof_url)| {
+                // One limit per host, so a slow platform does not hold up the others
+                let provider = VerificationManager::extract_domain_from_url(&proof_url).unwrap_or_default();
+                JobTask::new(provider, move || Self::monitor_proof(key, company_id, proof_url))
+            })
+            .collect();
+        JobRunner::run("monitoring:proofs", tasks).await
+    }
+
+    // 1 if the proof was found removed or disputed
+    async fn monitor_proof(key: String, company_id: String, proof_url: String) -> Result<u32, String> {
+        StorageManager::set_proof_checked(&key, time());
+        match VerificationManager::check_proof_content(company_id, proof_url, ic_cdk::id()).await {
+            RegistryResult::Ok(result) => Ok(u32::from(result.status_found != ProofStatus::Active)),
+            RegistryResult::Err(err) => Err(err),
+        }
+    }
+
+    pub async fn trigger_proof_monitoring(caller_principal: Principal) -> RegistryResult<u32> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        RegistryResult::Ok(Self::run_proof_monitoring().await)
+    }
+
+    // Sample every verified treasury wallet once; returns the number of alerts raised
+    pub async fn run_treasury_monitoring() -> u32 {
+        let targets: Vec<(String, String, String)> = StorageManager::get_all_companies()
+            .into_iter()
+            .flat_map(|company| {
+                let company_id = company.id;
+                company
+                    .cross_chain_presence
+                    .treasury_wallets
+                    .into_iter()
+                    .filter(|wallet| wallet.verified)
+                    .map(move |wallet| (company_id.clone(), wallet.chain.to_lowercase(), wallet.address))
+            })
+            .filter(|(_, chain, _)| matches!(chain.as_str(), "ethereum" | "polygon" | "bitcoin" | "ton"))
+            .collect();
+
+        // Forget wallets that are no longer verified treasuries
+        for (key, snapshot) in StorageManager::get_all_treasury_snapshots() {
+            let still_verified = targets.iter().any(|(company_id, chain, address)| {
+                *company_id == snapshot.company_id && *chain == snapshot.chain && *address == snapshot.address
+            });
+            if !still_verified {
+                StorageManager::remove_treasury_snapshot(&key);
+            }
+        }
+
+        let tasks = targets
+            .into_iter()
+            .map(|(company_id, chain, address)| {
+                let provider = Self::treasury_provider(&chain);
+                JobTask::new(provider, move || async move {
+                    let balance = Self::fetch_treasury_balance(&chain, &address).await;
+                    Ok(Self::record_treasury_balance(&company_id, &chain, &address, balance))
+                })
+            })
+            .collect();
+        JobRunner::run("monitoring:treasury", tasks).await
+    }
+
+    fn treasury_provider(chain: &str) -> String {
+        let provider = match chain {
+            "ethereum" => ApiProvider::EthereumRpc,
+            "polygon" => ApiProvider::PolygonRpc,
+            "bitcoin" => ApiProvider::Bitcoin,
+            _ => ApiProvider::TonBalance,
+        };
+        format!("{:?}", provider)
+    }
+
+    pub async fn trigger_treasury_monitoring(caller_principal: Principal) -> RegistryResult<u32> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        RegistryResult::Ok(Self::run_treasury_monitoring().await)
+    }
+
+    fn record_treasury_balance(
+        company_id: &str,
+        chain: &str,
+        address: &str,
+        balance: Result<u128, String>,
+    ) -> u32 {
+        let key = Self::treasury_key(chain, address);
+        let now = time();
+        let mut snapshot = match StorageManager::get_treasury_snapshot(&key) {
+            Some(snapshot) if snapshot.company_id == company_id => snapshot,
+            _ => TreasurySnapshot {
+                company_id: company_id.to_string(),
+                chain: chain.to_string(),
+                address: address.to_string(),
+                samples: Vec::new(),
+                last_checked_at: now,
+                last_error: None,
+            },
+        };
+        snapshot.last_checked_at = now;
+
+        let balance = match balance {
+            Ok(balance) => balance,
+            Err(err) => {
+                snapshot.last_error = Some(err);
+                StorageManager::insert_treasury_snapshot(key, snapshot);
+                return 0;
+            }
+        };
+        snapshot.last_error = None;
+
+        let config = Self::get_monitoring_config();
+        let window_ns = config.treasury_outflow_window_hours as u64 * 60 * 60 * 1_000_000_000;
+        snapshot
+            .samples
+            .retain(|sample| sample.timestamp.saturating_add(window_ns) >= now);
+        let peak = snapshot.samples.iter().map(|sample| sample.balance).max().unwrap_or(0);
+
+        let sample = TreasuryBalanceSample { timestamp: now, balance };
+        let mut raised = 0;
+        if peak > balance {
+            let drop_percent = (peak - balance).saturating_mul(100) / peak;
+            if drop_percent >= config.treasury_outflow_threshold_percent as u128 {
+                Self::raise_alert(
+                    company_id,
+                    AlertType::TreasuryOutflow,
+                    AlertSeverity::Error,
+                    format!(
+                        "Verified {} treasury {} dropped {}% within {}h (from {} to {} base units)",
+                        chain, address, drop_percent, config.treasury_outflow_window_hours, peak, balance
+                    ),
+                    None,
+                    address,
+                    &format!("{}@{}", address, now),
+                );
+
+                // Start a new baseline so the same outflow is reported once
+                snapshot.samples.clear();
+                raised = 1;
+            }
+        }
+        snapshot.samples.push(sample);
+
+        StorageManager::insert_treasury_snapshot(key, snapshot);
+        raised
+    }
+
+    async fn fetch_treasury_balance(chain: &str, address: &str) -> Result<u128, String> {
+        match chain {
+            "ethereum" => Self::fetch_evm_balance(ApiProvider::EthereumRpc, address).await,
+            "polygon" => Self::fetch_evm_balance(ApiProvider::PolygonRpc, address).await,
+            "bitcoin" => Self::fetch_bitcoin_balance(address).await,
+            "ton" => Self::fetch_ton_balance(address).await,
+            _ => Err(format!("Balance monitoring is not supported on {}", chain)),
+        }
+    }
+
+    async fn fetch_evm_balance(provider: ApiProvider, address: &str) -> Result<u128, String> {
+        // Read at the agreed block height so every replica sees the same balance
+        let block = Self::fetch_block_number(provider.clone()).await?;
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_getBalance",
+            "params": [address, format!("0x{:x}", block)],
+        });
+
+        let response = Self::rpc_call(provider, body, "transform_evm_balance", 1_000).await?;
+        String::from_utf8_lossy(&response.body)
+            .parse::<u128>()
+            .map_err(|_| "Invalid eth_getBalance response".to_string())
+    }
+
+    async fn fetch_bitcoin_balance(address: &str) -> Result<u128, String> {
+        let build_request = |endpoint: &ProviderEndpoint| CanisterHttpRequestArgument {
+            url: endpoint.url_for(address),
+            method: HttpMethod::GET,
+            body: None,
+            max_response_bytes: Some(200_000),
+            transform: Some(TransformContext::from_name(
+                "transform_bitcoin_balance".to_string(),
+                vec![],
+            )),
+            headers: vec![HttpHeader {
+                name: "User-Agent".to_string(),
+                value: "ICP-CrossChainRegistry-Monitor/1.0".to_string(),
+            }],
+        };
+
+        let (_, response) =
+            SettingsManager::http_request_with_failover(ApiProvider::Bitcoin, build_request, 15_000_000_000)
+                .await?;
+        if response.status != 200u32 {
+            return Err(format!("Balance lookup failed with HTTP status {}", response.status));
+        }
+        String::from_utf8_lossy(&response.body)
+            .parse::<u128>()
+            .map_err(|_| "Invalid Bitcoin balance response".to_string())
+    }
+
+    async fn fetch_ton_balance(address: &str) -> Result<u128, String> {
+        let build_request = |endpoint: &ProviderEndpoint| CanisterHttpRequestArgument {
+            url: endpoint.url_for(address),
+            method: HttpMethod::GET,
+            body: None,
+            max_response_bytes: Some(20_000),
+            transform: Some(TransformContext::from_name(
+                "transform_ton_balance".to_string(),
+                vec![],
+            )),
+            headers: vec![HttpHeader {
+                name: "User-Agent".to_string(),
+                value: "ICP-CrossChainRegistry-Monitor/1.0".to_string(),
+            }],
+        };
+
+        let (_, response) =
+            SettingsManager::http_request_with_failover(ApiProvider::TonBalance, build_request, 15_000_000_000)
+                .await?;
+        if response.status != 200u32 {
+            return Err(format!("Balance lookup failed with HTTP status {}", response.status));
+        }
+        String::from_utf8_lossy(&response.body)
+            .parse::<u128>()
+            .map_err(|_| "Invalid TON balance response".to_string())
+    }
+
+    fn alert_for_event(watch: &ContractWatch, event: &EvmLogEvent) -> Option<CommunityAlert> {
+        let (alert_type, severity, what) = match event.topic.as_str() {
+            OWNERSHIP_TRANSFERRED_TOPIC => (
+                AlertType::ContractOwnershipTransferred,
+                AlertSeverity::Critical,
+                "ownership was transferred",
+            ),
+            ADMIN_CHANGED_TOPIC => (
+                AlertType::ContractAdminChanged,
+                AlertSeverity::Critical,
+                "proxy admin was changed",
+            ),
+            UPGRADED_TOPIC => (
+                AlertType::ContractProxyUpgraded,
+                AlertSeverity::Error,
+                "proxy implementation was upgraded",
+            ),
+            _ => return None,
+        };
+
+        Some(Self::raise_alert(
+            &watch.company_id,
+            alert_type,
+            severity,
+            format!(
+                "Verified contract {} {} in block {}; earlier verification may no longer reflect who controls it",
+                watch.contract_address, what, event.block_number
+            ),
+            Some(watch.chain_type.clone()),
+            &watch.contract_address,
+            // One transaction can emit several events, so the log index is part of the key
+            &format!("{}:{}", event.transaction_hash, event.log_index),
+        ))
+    }
+
+    // Returns the last block checked and the events found up to it
+    async fn check_contract(watch: &ContractWatch) -> Result<(u64, Vec<EvmLogEvent>), String> {
+        let provider = Self::rpc_provider(&watch.chain_type)?;
+        let head = Self::fetch_block_number(provider.clone()).await?;
+
+        // First run only records where monitoring starts
+        let from_block = match watch.last_checked_block {
+            Some(last) => last + 1,
+            None => return Ok((head, Vec::new())),
+        };
+        if from_block > head {
+            return Ok((from_block - 1, Vec::new()));
+        }
+        let to_block = head.min(from_block + Self::MAX_BLOCK_RANGE - 1);
+
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_getLogs",
+            "params": [{
+                "address": watch.contract_address,
+                "fromBlock": format!("0x{:x}", from_block),
+                "toBlock": format!("0x{:x}", to_block),
+                "topics": [[OWNERSHIP_TRANSFERRED_TOPIC, UPGRADED_TOPIC, ADMIN_CHANGED_TOPIC]],
+            }],
+        });
+
+        let response = Self::rpc_call(provider, body, "transform_evm_logs", 200_000).await?;
+        let events = serde_json::from_slice::<Vec<EvmLogEvent>>(&response.body)
+            .map_err(|_| "Invalid eth_getLogs response".to_string())?;
+
+        Ok((to_block, events))
+    }
+
+    async fn fetch_block_number(provider: ApiProvider) -> Result<u64, String> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_blockNumber",
+            "params": [],
+        });
+
+        let response = Self::rpc_call(provider, body, "transform_evm_block_number", 1_000).await?;
+        String::from_utf8_lossy(&response.body)
+            .parse::<u64>()
+            .map_err(|_| "Invalid eth_blockNumber response".to_string())
+    }
+
+    async fn rpc_call(
+        provider: ApiProvider,
+        body: serde_json::Value,
+        transform: &str,
+        max_response_bytes: u64,
+    ) -> Result<HttpResponse, String> {
+        let body = body.to_string().into_bytes();
+        let build_request = |endpoint: &ProviderEndpoint| CanisterHttpRequestArgument {
+            url: endpoint.url_template.clone(),
+            method: HttpMethod::POST,
+            body: Some(body.clone()),
+            max_response_bytes: Some(max_response_bytes),
+            transform: Some(TransformContext::from_name(transform.to_string(), vec![])),
+            headers: vec![
+                HttpHeader {
+                    name: "Content-Type".to_string(),
+                    value: "application/json".to_string(),
+                },
+                HttpHeader {
+                    name: "User-Agent".to_string(),
+                    value: "ICP-CrossChainRegistry-Monitor/1.0".to_string(),
+                },
+            ],
+        };
+
+        let (_, response) =
+            SettingsManager::http_request_with_failover(provider, build_request, 15_000_000_000).await?;
+        if response.status != 200u32 {
+            return Err(format!("RPC request failed with HTTP status {}", response.status));
+        }
+        Ok(response)
+    }
+}
+
+fn parse_hex_u64(value: &serde_json::Value) -> Option<u64> {
+    value
+        .as_str()
+        .and_then(|hex| u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+}
+
+// Reduce eth_getBalance to a decimal wei amount
+pub fn transform_evm_balance(raw: TransformArgs) -> HttpResponse {
+    let balance = serde_json::from_slice::<serde_json::Value>(&raw.response.body)
+        .ok()
+        .and_then(|json| {
+            json["result"]
+                .as_str()
+                .and_then(|hex| u128::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+        });
+
+    HttpResponse {
+        status: raw.response.status.clone(),
+        body: balance.map(|b| b.to_string()).unwrap_or_default().into_bytes(),
+        headers: vec![],
+    }
+}
+
+// Reduce an Esplora or blockchain.info address response to its confirmed balance in satoshi
+pub fn transform_bitcoin_balance(raw: TransformArgs) -> HttpResponse {
+    let balance = serde_json::from_slice::<serde_json::Value>(&raw.response.body)
+        .ok()
+        .and_then(|json| {
+            if let Some(stats) = json.get("chain_stats") {
+                let funded = stats["funded_txo_sum"].as_u64()?;
+                let spent = stats["spent_txo_sum"].as_u64()?;
+                Some(funded.saturating_sub(spent))
+            } else {
+                json["final_balance"].as_u64()
+            }
+        });
+
+    HttpResponse {
+        status: raw.response.status.clone(),
+        body: balance.map(|b| b.to_string()).unwrap_or_default().into_bytes(),
+        headers: vec![],
+    }
+}
+
+// Reduce a toncenter getAddressBalance or tonapi account response to its balance in nanoton
+pub fn transform_ton_balance(raw: TransformArgs) -> HttpResponse {
+    let balance = serde_json::from_slice::<serde_json::Value>(&raw.response.body)
+        .ok()
+        .and_then(|json| match &json["result"] {
+            serde_json::Value::String(balance) => balance.parse::<u128>().ok(),
+            _ => json["balance"].as_u64().map(u128::from),
+        });
+
+    HttpResponse {
+        status: raw.response.status.clone(),
+        body: balance.map(|b| b.to_string()).unwrap_or_default().into_bytes(),
+        headers: vec![],
+    }
+}
+
+// Reduce eth_blockNumber to a confirmed, rounded block height so all replicas agree
+pub fn transform_evm_block_number(raw: TransformArgs) -> HttpResponse {
+    let block = serde_json::from_slice::<serde_json::Value>(&raw.response.body)
+        .ok()
+        .and_then(|json| parse_hex_u64(&json["result"]))
+        .map(|head| {
+            let confirmed = head.saturating_sub(MonitoringSystem::CONFIRMATION_BLOCKS);
+            confirmed - confirmed % MonitoringSystem::BLOCK_ROUNDING
+        });
+
+    HttpResponse {
+        status: raw.response.status.clone(),
+        body: block.map(|b| b.to_string()).unwrap_or_default().into_bytes(),
+        headers: vec![],
+    }
+}
+
+// Reduce eth_getLogs to the fields monitoring needs, in a stable order
+pub fn transform_evm_logs(raw: TransformArgs) -> HttpResponse {
+    let events = serde_json::from_slice::<serde_json::Value>(&raw.response.body)
+        .ok()
+        .and_then(|json| json["result"].as_array().cloned())
+        .map(|logs| {
+            let mut events: Vec<serde_json::Value> = logs
+                .iter()
+                .filter_map(|log| {
+                    Some(serde_json::json!({
+                        "topic": log["topics"][0].as_str()?.to_lowercase(),
+                        "block_number": parse_hex_u64(&log["blockNumber"])?,
+                        "transaction_hash": log["transactionHash"].as_str()?.to_lowercase(),
+                        "log_index": parse_hex_u64(&log["logIndex"])?,
+                    }))
+                })
+                .collect();
+            events.sort_by_key(|e| (e["block_number"].as_u64(), e["log_index"].as_u64()));
+            events
+        });
+
+    HttpResponse {
+        status: raw.response.status.clone(),
+        body: match events {
+            Some(events) => serde_json::Value::Array(events).to_string().into_bytes(),
+            None => b"invalid_response".to_vec(),
+        },
+        headers: vec![],
+    }
+}
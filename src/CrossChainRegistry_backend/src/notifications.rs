@@ -0,0 +1,59 @@
+use crate::storage::StorageManager;
+use crate::types::{Notification, RegistryResult};
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Per-principal notification inbox, read by polling get_my_notifications
+
+pub struct NotificationManager;
+
+impl NotificationManager {
+    const MAX_NOTIFICATIONS_PER_RECIPIENT: usize = 200;
+
+    pub fn notify(recipient: Principal, company_id: &str, alert_id: Option<String>, message: String) {
+        let mut existing: Vec<Notification> = StorageManager::get_all_notifications()
+            .into_iter()
+            .filter(|n| n.recipient == recipient)
+            .collect();
+
+        // Drop the oldest entries once the inbox is full
+        if existing.len() >= Self::MAX_NOTIFICATIONS_PER_RECIPIENT {
+            existing.sort_by_key(|n| n.created_at);
+            let excess = existing.len() + 1 - Self::MAX_NOTIFICATIONS_PER_RECIPIENT;
+            for old in existing.iter().take(excess) {
+                StorageManager::remove_notification(&old.id);
+            }
+        }
+
+        let notification = Notification {
+            id: format!("notification_{}_{}", time(), StorageManager::notification_count()),
+            recipient,
+            company_id: company_id.to_string(),
+            alert_id,
+            message,
+            created_at: time(),
+            read: false,
+        };
+        StorageManager::insert_notification(notification.id.clone(), notification);
+    }
+
+    pub fn get_my_notifications(caller_principal: Principal, unread_only: bool) -> Vec<Notification> {
+        let mut notifications: Vec<Notification> = StorageManager::get_all_notifications()
+            .into_iter()
+            .filter(|n| n.recipient == caller_principal && (!unread_only || !n.read))
+            .collect();
+        notifications.sort_by_key(|n| std::cmp::Reverse(n.created_at));
+        notifications
+    }
+
+    pub fn mark_notification_read(notification_id: String, caller_principal: Principal) -> RegistryResult<()> {
+        let mut notification = match StorageManager::get_notification(&notification_id) {
+            Some(notification) if notification.recipient == caller_principal => notification,
+            _ => return RegistryResult::Err("Notification not found".to_string()),
+        };
+
+        notification.read = true;
+        StorageManager::insert_notification(notification_id, notification);
+        RegistryResult::Ok(())
+    }
+}
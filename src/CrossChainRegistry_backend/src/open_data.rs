@@ -0,0 +1,178 @@
+use crate::admins::AdminManager;
+use crate::storage::StorageManager;
+use crate::types::{
+    HttpRequest, HttpResponse, OpenDataCompany, OpenDataDocument, OpenDataSnapshot,
+    OpenDataSnapshotInfo, RegistryResult,
+};
+use crate::verification::VerificationManager;
+use candid::Principal;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use ic_cdk::api::time;
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::time::Duration;
+
+// Periodic gzip'd JSON export of public registry data, served over the HTTP gateway
+// so bulk consumers don't page through query endpoints. Responses are not certified,
+// so fetch from the raw domain (<canister-id>.raw.icp0.io) and check the published
+// SHA-256 against the downloaded bytes.
+
+pub struct OpenDataManager;
+
+impl OpenDataManager {
+    pub const SNAPSHOT_PATH: &'static str = "/open-data/registry.json.gz";
+    const HASH_PATH: &'static str = "/open-data/registry.json.gz.sha256";
+    const MANIFEST_PATH: &'static str = "/open-data/manifest.json";
+    const SCHEMA_VERSION: u32 = 1;
+    const SNAPSHOT_INTERVAL_SECS: u64 = 6 * 60 * 60;
+    const CACHE_MAX_AGE_SECS: u64 = 60 * 60;
+
+    pub fn start_timer() {
+        // Build one right away so a fresh install has something to serve
+        ic_cdk_timers::set_timer(Duration::ZERO, || {
+            Self::build_snapshot();
+        });
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::SNAPSHOT_INTERVAL_SECS), || {
+            Self::build_snapshot();
+        });
+    }
+
+    pub fn build_snapshot() -> OpenDataSnapshotInfo {
+        let mut companies: Vec<OpenDataCompany> = StorageManager::get_all_companies()
+            .into_iter()
+            .map(|company| {
+                let badges = VerificationManager::completed_badges(&company.id);
+
+                OpenDataCompany {
+                    id: company.id,
+                    name: company.basic_info.name,
+                    website: company.basic_info.website,
+                    entity_type: company.entity_type.unwrap_or_default(),
+                    status: company.status,
+                    verification_score: company.verification_score,
+                    badges,
+                    focus_areas: company.basic_info.focus_areas,
+                    updated_at: company.updated_at,
+                }
+            })
+            .collect();
+        companies.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let now = time();
+        let company_count = companies.len() as u32;
+        let document = OpenDataDocument {
+            schema_version: Self::SCHEMA_VERSION,
+            generated_at: now,
+            companies,
+        };
+        let json = serde_json::to_vec(&document).unwrap_or_default();
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        // Writing into a Vec cannot fail
+        let gzip_body = encoder.write_all(&json).and_then(|_| encoder.finish()).unwrap_or_default();
+        let sha256 = Sha256::digest(&gzip_body)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+
+        let snapshot = OpenDataSnapshot {
+            generated_at: now,
+            company_count,
+            sha256,
+            gzip_body,
+        };
+        let info = Self::info(&snapshot);
+        StorageManager::set_open_data_snapshot(snapshot);
+        info
+    }
+
+    pub fn run_open_data_snapshot(caller_principal: Principal) -> RegistryResult<OpenDataSnapshotInfo> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        RegistryResult::Ok(Self::build_snapshot())
+    }
+
+    pub fn get_open_data_snapshot_info() -> Option<OpenDataSnapshotInfo> {
+        StorageManager::get_open_data_snapshot().map(|snapshot| Self::info(&snapshot))
+    }
+
+    fn info(snapshot: &OpenDataSnapshot) -> OpenDataSnapshotInfo {
+        OpenDataSnapshotInfo {
+            generated_at: snapshot.generated_at,
+            company_count: snapshot.company_count,
+            sha256: snapshot.sha256.clone(),
+            size_bytes: snapshot.gzip_body.len() as u64,
+            path: Self::SNAPSHOT_PATH.to_string(),
+        }
+    }
+
+    pub fn http_request(request: HttpRequest) -> HttpResponse {
+        if request.method != "GET" && request.method != "HEAD" {
+            return Self::text_response(405, "Method not allowed");
+        }
+
+        let path = request.url.split('?').next().unwrap_or("");
+        let snapshot = match StorageManager::get_open_data_snapshot() {
+            Some(snapshot) => snapshot,
+            None if path.starts_with("/open-data/") => {
+                return Self::text_response(503, "Snapshot not generated yet");
+            }
+            None => return Self::text_response(404, "Not found"),
+        };
+
+        let cache = format!("public, max-age={}", Self::CACHE_MAX_AGE_SECS);
+        match path {
+            Self::SNAPSHOT_PATH => HttpResponse {
+                status_code: 200,
+                headers: vec![
+                    ("Content-Type".to_string(), "application/gzip".to_string()),
+                    (
+                        "Content-Disposition".to_string(),
+                        "attachment; filename=\"registry.json.gz\"".to_string(),
+                    ),
+                    ("X-Content-SHA256".to_string(), snapshot.sha256.clone()),
+                    ("Cache-Control".to_string(), cache),
+                    ("Access-Control-Allow-Origin".to_string(), "*".to_string()),
+                ],
+                body: snapshot.gzip_body,
+                upgrade: None,
+            },
+            Self::HASH_PATH => Self::text_response(
+                200,
+                &format!("{}  registry.json.gz\n", snapshot.sha256),
+            ),
+            Self::MANIFEST_PATH => {
+                let manifest = serde_json::json!({
+                    "schema_version": Self::SCHEMA_VERSION,
+                    "generated_at": snapshot.generated_at,
+                    "company_count": snapshot.company_count,
+                    "size_bytes": snapshot.gzip_body.len(),
+                    "sha256": snapshot.sha256,
+                    "path": Self::SNAPSHOT_PATH,
+                });
+                HttpResponse {
+                    status_code: 200,
+                    headers: vec![
+                        ("Content-Type".to_string(), "application/json".to_string()),
+                        ("Cache-Control".to_string(), cache),
+                        ("Access-Control-Allow-Origin".to_string(), "*".to_string()),
+                    ],
+                    body: manifest.to_string().into_bytes(),
+                    upgrade: None,
+                }
+            }
+            _ => Self::text_response(404, "Not found"),
+        }
+    }
+
+    fn text_response(status_code: u16, body: &str) -> HttpResponse {
+        HttpResponse {
+            status_code,
+            headers: vec![("Content-Type".to_string(), "text/plain; charset=utf-8".to_string())],
+            body: body.as_bytes().to_vec(),
+            upgrade: None,
+        }
+    }
+}
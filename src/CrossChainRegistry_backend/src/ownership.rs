@@ -0,0 +1,293 @@
+use crate::audit::AuditManager;
+use crate::inactivity::InactivityManager;
+use crate::monitoring::MonitoringSystem;
+use crate::notifications::NotificationManager;
+use crate::storage::StorageManager;
+use crate::types::{
+    AlertSeverity, AlertType, Company, CompanyManager, CompanyRole, OwnershipTransfer, RegistryResult,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Company ownership. `created_by` holds the primary owner; further principals can be
+// registered as managers with a role, and every company-scoped endpoint asks `has_role`
+// (or `is_owner`) instead of comparing principals itself. Handing the company over takes
+// two steps, so a profile can't be pushed onto a mistyped or unwilling principal: the
+// primary owner proposes, the new principal accepts.
+
+pub struct OwnershipManager;
+
+impl OwnershipManager {
+    const TRANSFER_TTL_NS: u64 = 7 * 24 * 60 * 60 * 1_000_000_000; // 7 days
+    const MAX_MANAGERS_PER_COMPANY: usize = 20;
+
+    pub fn role_of(company: &Company, principal: Principal) -> Option<CompanyRole> {
+        if company.created_by == principal {
+            return Some(CompanyRole::Owner);
+        }
+        company
+            .managers
+            .as_ref()?
+            .iter()
+            .find(|m| m.principal == principal)
+            .map(|m| m.role.clone())
+    }
+
+    // Owners hold every role
+    pub fn has_role(company: &Company, principal: Principal, role: CompanyRole) -> bool {
+        match Self::role_of(company, principal) {
+            Some(CompanyRole::Owner) => true,
+            Some(held) => held == role,
+            None => false,
+        }
+    }
+
+    pub fn is_owner(company: &Company, principal: Principal) -> bool {
+        Self::has_role(company, principal, CompanyRole::Owner)
+    }
+
+    // Every principal `has_role` accepts for `role`, the primary owner first
+    pub fn holders_of(company: &Company, role: CompanyRole) -> Vec<Principal> {
+        let mut holders = vec![company.created_by];
+        holders.extend(
+            company
+                .managers
+                .iter()
+                .flatten()
+                .map(|m| m.principal)
+                .filter(|principal| {
+                    *principal != company.created_by && Self::has_role(company, *principal, role.clone())
+                }),
+        );
+        holders
+    }
+
+    // Any role at all, e.g. for listing "my companies"
+    pub fn is_manager(company: &Company, principal: Principal) -> bool {
+        Self::role_of(company, principal).is_some()
+    }
+
+    // Adds the principal or changes its role
+    pub fn add_company_manager(
+        company_id: String,
+        principal: Principal,
+        role: CompanyRole,
+        caller_principal: Principal,
+    ) -> RegistryResult<Vec<CompanyManager>> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+        if !Self::is_owner(&company, caller_principal) {
+            return RegistryResult::Err("Unauthorized: Only company owners can manage managers".to_string());
+        }
+        if principal == Principal::anonymous() {
+            return RegistryResult::Err("The anonymous principal cannot manage a company".to_string());
+        }
+        if principal == company.created_by {
+            return RegistryResult::Err("The primary owner already holds every role".to_string());
+        }
+        let managers = company.managers.unwrap_or_default();
+        if managers.len() >= Self::MAX_MANAGERS_PER_COMPANY && !managers.iter().any(|m| m.principal == principal) {
+            return RegistryResult::Err(format!(
+                "A company can have at most {} managers",
+                Self::MAX_MANAGERS_PER_COMPANY
+            ));
+        }
+
+        let mut updated = Vec::new();
+        StorageManager::update_company(&company_id, |company| {
+            let managers = company.managers.get_or_insert_with(Vec::new);
+            match managers.iter_mut().find(|m| m.principal == principal) {
+                Some(manager) => manager.role = role.clone(),
+                None => managers.push(CompanyManager {
+                    principal,
+                    role: role.clone(),
+                    added_by: caller_principal,
+                    added_at: time(),
+                }),
+            }
+            updated = managers.clone();
+        });
+        AuditManager::record(
+            "company_manager_set",
+            &company_id,
+            vec![caller_principal, principal],
+            format!("{:?}", role),
+        );
+        RegistryResult::Ok(updated)
+    }
+
+    // Owners remove anyone; a manager may also step down on their own
+    pub fn remove_company_manager(
+        company_id: String,
+        principal: Principal,
+        caller_principal: Principal,
+    ) -> RegistryResult<Vec<CompanyManager>> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+        if !Self::is_owner(&company, caller_principal) && principal != caller_principal {
+            return RegistryResult::Err("Unauthorized: Only company owners can manage managers".to_string());
+        }
+        if !company.managers.iter().flatten().any(|m| m.principal == principal) {
+            return RegistryResult::Err("Principal is not a manager of this company".to_string());
+        }
+
+        let mut updated = Vec::new();
+        StorageManager::update_company(&company_id, |company| {
+            if let Some(managers) = company.managers.as_mut() {
+                managers.retain(|m| m.principal != principal);
+                updated = managers.clone();
+            }
+        });
+        AuditManager::record(
+            "company_manager_removed",
+            &company_id,
+            vec![caller_principal, principal],
+            String::new(),
+        );
+        RegistryResult::Ok(updated)
+    }
+
+    pub fn get_company_managers(company_id: String) -> RegistryResult<Vec<CompanyManager>> {
+        match StorageManager::get_company(&company_id) {
+            Some(company) => RegistryResult::Ok(company.managers.unwrap_or_default()),
+            None => RegistryResult::Err("Company not found".to_string()),
+        }
+    }
+
+    // Replaces any transfer already pending for the company
+    pub fn transfer_company_ownership(
+        company_id: String,
+        new_principal: Principal,
+        caller_principal: Principal,
+    ) -> RegistryResult<OwnershipTransfer> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+        if company.created_by != caller_principal {
+            return RegistryResult::Err("Unauthorized: Only the primary owner can transfer ownership".to_string());
+        }
+        if new_principal == Principal::anonymous() {
+            return RegistryResult::Err("Cannot transfer ownership to the anonymous principal".to_string());
+        }
+        if new_principal == caller_principal {
+            return RegistryResult::Err("New owner must be a different principal".to_string());
+        }
+        if company.permanently_suspended.unwrap_or(false) {
+            return RegistryResult::Err("Permanently suspended companies cannot change hands".to_string());
+        }
+
+        let now = time();
+        let transfer = OwnershipTransfer {
+            company_id: company_id.clone(),
+            from: caller_principal,
+            to: new_principal,
+            proposed_at: now,
+            expires_at: now + Self::TRANSFER_TTL_NS,
+        };
+        StorageManager::insert_ownership_transfer(transfer.clone());
+        AuditManager::record(
+            "ownership_transfer_proposed",
+            &company_id,
+            vec![caller_principal, new_principal],
+            String::new(),
+        );
+        NotificationManager::notify(
+            new_principal,
+            &company_id,
+            None,
+            format!(
+                "You have been offered ownership of {}. Call accept_company_ownership within 7 days to take it over.",
+                company.basic_info.name
+            ),
+        );
+        RegistryResult::Ok(transfer)
+    }
+
+    pub fn accept_company_ownership(company_id: String, caller_principal: Principal) -> RegistryResult<()> {
+        let transfer = match StorageManager::get_ownership_transfer(&company_id) {
+            Some(transfer) if transfer.to == caller_principal => transfer,
+            _ => return RegistryResult::Err("No pending ownership transfer to you for this company".to_string()),
+        };
+        if transfer.expires_at < time() {
+            StorageManager::remove_ownership_transfer(&company_id);
+            return RegistryResult::Err("Ownership transfer has expired".to_string());
+        }
+
+        // The proposer may have lost the company in the meantime
+        let mut owner_changed = false;
+        let success = StorageManager::update_company(&company_id, |company| {
+            if company.created_by == transfer.from {
+                company.created_by = transfer.to;
+                // The new owner no longer needs a separate manager entry
+                if let Some(managers) = company.managers.as_mut() {
+                    managers.retain(|m| m.principal != transfer.to);
+                }
+                InactivityManager::mark_active(company);
+                owner_changed = true;
+            }
+        });
+        StorageManager::remove_ownership_transfer(&company_id);
+        if !success {
+            return RegistryResult::Err("Company not found".to_string());
+        }
+        if !owner_changed {
+            return RegistryResult::Err("Ownership changed since the transfer was proposed".to_string());
+        }
+
+        AuditManager::record(
+            "ownership_transferred",
+            &company_id,
+            vec![transfer.from, transfer.to],
+            String::new(),
+        );
+        MonitoringSystem::raise_alert(
+            &company_id,
+            AlertType::CompanyOwnershipTransferred,
+            AlertSeverity::Warning,
+            format!("Registry ownership moved from {} to {}", transfer.from, transfer.to),
+            None,
+            &company_id,
+            &format!("{}@{}", transfer.to, transfer.proposed_at),
+        );
+        NotificationManager::notify(
+            transfer.from,
+            &company_id,
+            None,
+            format!("Ownership of this company was accepted by {}", transfer.to),
+        );
+        RegistryResult::Ok(())
+    }
+
+    // The owner withdraws the offer or the recipient declines it
+    pub fn cancel_ownership_transfer(company_id: String, caller_principal: Principal) -> RegistryResult<()> {
+        match StorageManager::get_ownership_transfer(&company_id) {
+            Some(transfer) if transfer.from == caller_principal || transfer.to == caller_principal => {
+                StorageManager::remove_ownership_transfer(&company_id);
+                AuditManager::record(
+                    "ownership_transfer_cancelled",
+                    &company_id,
+                    vec![caller_principal],
+                    String::new(),
+                );
+                RegistryResult::Ok(())
+            }
+            _ => RegistryResult::Err("No pending ownership transfer for this company".to_string()),
+        }
+    }
+
+    // Unexpired transfers the caller proposed or was offered
+    pub fn get_my_ownership_transfers(caller_principal: Principal) -> Vec<OwnershipTransfer> {
+        let now = time();
+        let mut transfers: Vec<OwnershipTransfer> = StorageManager::get_all_ownership_transfers()
+            .into_iter()
+            .filter(|t| (t.from == caller_principal || t.to == caller_principal) && t.expires_at >= now)
+            .collect();
+        transfers.sort_by_key(|t| std::cmp::Reverse(t.proposed_at));
+        transfers
+    }
+}
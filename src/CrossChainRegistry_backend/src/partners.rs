@@ -0,0 +1,527 @@
+use crate::admins::AdminManager;
+use crate::sanitize::{self, SafeText};
+use crate::storage::StorageManager;
+use crate::types::{
+    AlertSeverity, Company, CompanyStatus, CompanyStatusChange, ListingAttestation, ListingSignature,
+    ListingSnapshot, Partner, PartnerCallback, PartnerSubscription, ProofStatus, RegistryResult, RiskLevel,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+// Integration surface for exchanges and launchpads that gate listings on registry status.
+// Admins register partner principals; partners subscribe to companies and receive a
+// CompanyStatusChange whenever a subscribed company's status moves, either by polling
+// their outbox or through a callback canister (delivered like the cache hook: in order,
+// retried with backoff).
+//
+// Listing attestations are canister signatures. Every company's attestation is a leaf
+// SHA-256(0x00 || fields joined by '\n': company_id, company_name, status, verification
+// score, reputation score, risk level, attested_at); parents are SHA-256(0x01 || left ||
+// right), an odd node is paired with itself, and the root is the canister's certified
+// data. A verifier recomputes the root from the leaf and merkle_path and checks it against
+// /canister/<id>/certified_data in the certificate.
+
+pub struct PartnerManager;
+
+impl PartnerManager {
+    const JOURNAL_CONSUMER: &'static str = "partner_status";
+    const PROCESS_INTERVAL_SECS: u64 = 30;
+    const EVENTS_PER_RUN: usize = 500;
+    const MAX_SUBSCRIPTIONS_PER_PARTNER: usize = 1_000;
+    const MAX_CHANGES_PER_PARTNER: usize = 1_000;
+    const MAX_BATCH_CHANGES: usize = 100;
+    const MAX_PAGE_SIZE: u32 = 200;
+    const MAX_METHOD_LENGTH: usize = 64;
+    const MAX_ERROR_LENGTH: usize = 200;
+    const BASE_BACKOFF_NS: u64 = 30_000_000_000; // 30 seconds
+    const MAX_BACKOFF_NS: u64 = 60 * 60 * 1_000_000_000; // 1 hour
+    // Alerts and proofs feed the risk level without touching the journal
+    const SNAPSHOT_MAX_AGE_NS: u64 = 10 * 60 * 1_000_000_000;
+
+    pub fn start_timer() {
+        // Certify right away so attestations are available soon after install
+        ic_cdk_timers::set_timer(Duration::ZERO, || {
+            Self::refresh_listing_snapshot();
+        });
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::PROCESS_INTERVAL_SECS), || {
+            Self::process_journal();
+            Self::refresh_listing_snapshot();
+            for partner in StorageManager::get_all_partners() {
+                if partner.callback.is_some() {
+                    ic_cdk::spawn(async move {
+                        Self::deliver_pending(partner.principal).await;
+                    });
+                }
+            }
+        });
+    }
+
+    // Partner roster (registry admins)
+
+    pub fn add_partner(principal: Principal, name: String, caller_principal: Principal) -> RegistryResult<Partner> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if principal == Principal::anonymous() {
+            return RegistryResult::Err("The anonymous principal cannot be a partner".to_string());
+        }
+        if StorageManager::get_partner(&principal).is_some() {
+            return RegistryResult::Err("Principal is already a partner".to_string());
+        }
+        let name = match SafeText::parse_required(&name, "Partner name", sanitize::policy().max_name) {
+            Ok(text) => text.into_inner(),
+            Err(err) => return RegistryResult::Err(err),
+        };
+
+        let partner = Partner {
+            principal,
+            name,
+            added_by: caller_principal,
+            added_at: time(),
+            callback: None,
+            delivered_seq: 0,
+            consecutive_failures: 0,
+            last_error: None,
+            next_attempt_at: 0,
+        };
+        StorageManager::insert_partner(partner.clone());
+        RegistryResult::Ok(partner)
+    }
+
+    pub fn remove_partner(principal: Principal, caller_principal: Principal) -> RegistryResult<()> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if StorageManager::remove_partner(&principal).is_none() {
+            return RegistryResult::Err("Partner not found".to_string());
+        }
+        StorageManager::remove_partner_data(&principal);
+        RegistryResult::Ok(())
+    }
+
+    pub fn list_partners(caller_principal: Principal) -> RegistryResult<Vec<Partner>> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let mut partners = StorageManager::get_all_partners();
+        partners.sort_by_key(|p| p.added_at);
+        RegistryResult::Ok(partners)
+    }
+
+    // Partner self-service
+
+    pub fn set_partner_callback(
+        callback: Option<PartnerCallback>,
+        caller_principal: Principal,
+    ) -> RegistryResult<Partner> {
+        let mut partner = match StorageManager::get_partner(&caller_principal) {
+            Some(partner) => partner,
+            None => return RegistryResult::Err("Unauthorized: Registered partner access required".to_string()),
+        };
+        if let Some(callback) = &callback {
+            let valid_method = !callback.method.is_empty()
+                && callback.method.len() <= Self::MAX_METHOD_LENGTH
+                && callback.method.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid_method {
+                return RegistryResult::Err("Method must be a plain Candid method name".to_string());
+            }
+            if callback.target == Principal::anonymous() || callback.target == ic_cdk::id() {
+                return RegistryResult::Err("Target must be another canister".to_string());
+            }
+        }
+
+        partner.callback = callback;
+        partner.consecutive_failures = 0;
+        partner.last_error = None;
+        partner.next_attempt_at = 0;
+        StorageManager::insert_partner(partner.clone());
+        RegistryResult::Ok(partner)
+    }
+
+    // Returns the new subscriptions; companies already subscribed are skipped
+    pub fn subscribe_company_status(
+        company_ids: Vec<String>,
+        caller_principal: Principal,
+    ) -> RegistryResult<Vec<PartnerSubscription>> {
+        if let Err(err) = Self::ensure_partner(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let existing: Vec<String> = StorageManager::get_partner_subscriptions(&caller_principal)
+            .into_iter()
+            .map(|s| s.company_id)
+            .collect();
+        let mut added = Vec::new();
+        for company_id in company_ids {
+            if existing.contains(&company_id) || added.iter().any(|s: &PartnerSubscription| s.company_id == company_id) {
+                continue;
+            }
+            let company = match StorageManager::get_company(&company_id) {
+                Some(company) => company,
+                None => return RegistryResult::Err(format!("Company {} not found", company_id)),
+            };
+            added.push(PartnerSubscription {
+                partner: caller_principal,
+                company_id,
+                last_status: company.status,
+                subscribed_at: time(),
+            });
+        }
+        if existing.len() + added.len() > Self::MAX_SUBSCRIPTIONS_PER_PARTNER {
+            return RegistryResult::Err(format!(
+                "At most {} subscriptions per partner",
+                Self::MAX_SUBSCRIPTIONS_PER_PARTNER
+            ));
+        }
+
+        for subscription in &added {
+            StorageManager::insert_partner_subscription(subscription.clone());
+        }
+        RegistryResult::Ok(added)
+    }
+
+    pub fn unsubscribe_company_status(company_ids: Vec<String>, caller_principal: Principal) -> RegistryResult<u32> {
+        if let Err(err) = Self::ensure_partner(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let removed = company_ids
+            .iter()
+            .filter(|company_id| StorageManager::remove_partner_subscription(company_id, &caller_principal).is_some())
+            .count();
+        RegistryResult::Ok(removed as u32)
+    }
+
+    pub fn get_partner_subscriptions(caller_principal: Principal) -> RegistryResult<Vec<PartnerSubscription>> {
+        if let Err(err) = Self::ensure_partner(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        RegistryResult::Ok(StorageManager::get_partner_subscriptions(&caller_principal))
+    }
+
+    // Polling alternative to the callback; oldest first
+    pub fn get_partner_status_changes(
+        after_seq: u64,
+        limit: Option<u32>,
+        caller_principal: Principal,
+    ) -> RegistryResult<Vec<CompanyStatusChange>> {
+        if let Err(err) = Self::ensure_partner(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let limit = limit.unwrap_or(Self::MAX_PAGE_SIZE).min(Self::MAX_PAGE_SIZE) as usize;
+        RegistryResult::Ok(StorageManager::get_partner_status_changes_after(&caller_principal, after_seq, limit))
+    }
+
+    // Query only: the certificate is not available in replicated execution
+    pub fn get_listing_attestation(company_id: String, caller_principal: Principal) -> RegistryResult<ListingAttestation> {
+        if StorageManager::get_partner(&caller_principal).is_none() && !AdminManager::is_admin(caller_principal) {
+            return RegistryResult::Err("Unauthorized: Registered partner access required".to_string());
+        }
+        let certificate = match ic_cdk::api::data_certificate() {
+            Some(certificate) => certificate,
+            None => return RegistryResult::Err("Listing attestations can only be fetched with a query call".to_string()),
+        };
+
+        StorageManager::with_listing_snapshot(|snapshot| {
+            let snapshot = match snapshot {
+                Some(snapshot) => snapshot,
+                None => return RegistryResult::Err("No certified snapshot yet; try again shortly".to_string()),
+            };
+            let index = match snapshot
+                .attestations
+                .binary_search_by(|a| a.company_id.as_str().cmp(company_id.as_str()))
+            {
+                Ok(index) => index,
+                Err(_) => {
+                    return RegistryResult::Err(
+                        "Company is not in the certified snapshot; try again shortly".to_string(),
+                    )
+                }
+            };
+
+            let mut attestation = snapshot.attestations[index].clone();
+            attestation.signature = Some(Self::listing_signature(snapshot, index, certificate));
+            RegistryResult::Ok(attestation)
+        })
+    }
+
+    // Merkle path from the attestation at `index` up to the snapshot's root
+    pub fn listing_signature(snapshot: &ListingSnapshot, index: usize, certificate: Vec<u8>) -> ListingSignature {
+        let mut merkle_path = Vec::new();
+        let mut position = index;
+        for level in &snapshot.levels[..snapshot.levels.len() - 1] {
+            let sibling = if position.is_multiple_of(2) {
+                level.get(position + 1).unwrap_or(&level[position])
+            } else {
+                &level[position - 1]
+            };
+            merkle_path.push(sibling.to_vec());
+            position /= 2;
+        }
+        ListingSignature {
+            leaf_index: index as u64,
+            merkle_path,
+            root: snapshot.levels[snapshot.levels.len() - 1][0].to_vec(),
+            certificate,
+        }
+    }
+
+    // Whether the attestation's fields hash up its Merkle path to the root it carries.
+    // Checking that root against a certificate or a trusted source is up to the caller.
+    pub fn verify_listing_signature(attestation: &ListingAttestation) -> bool {
+        let signature = match &attestation.signature {
+            Some(signature) => signature,
+            None => return false,
+        };
+        let mut current = Self::leaf_hash(attestation);
+        let mut position = signature.leaf_index;
+        for sibling in &signature.merkle_path {
+            let sibling: [u8; 32] = match sibling.as_slice().try_into() {
+                Ok(sibling) => sibling,
+                Err(_) => return false,
+            };
+            current = if position.is_multiple_of(2) {
+                Self::node_hash(&current, &sibling)
+            } else {
+                Self::node_hash(&sibling, &current)
+            };
+            position /= 2;
+        }
+        position == 0 && current.as_slice() == signature.root.as_slice()
+    }
+
+    // Background work
+
+    // Turns journaled company writes into status changes for subscribers; returns the
+    // number of changes recorded
+    pub fn process_journal() -> u32 {
+        let cursor = StorageManager::get_journal_cursor(Self::JOURNAL_CONSUMER);
+        let events = StorageManager::get_company_events_after(cursor, Self::EVENTS_PER_RUN);
+        let last_seq = match events.last() {
+            Some(event) => event.seq,
+            None => return 0,
+        };
+
+        // Only the latest state matters, so each company is looked at once
+        let mut latest: BTreeMap<String, u64> = BTreeMap::new();
+        for event in events {
+            latest.insert(event.company_id, event.seq);
+        }
+
+        let alert_severities = Self::open_alert_severities();
+        let now = time();
+        let mut recorded = 0;
+        for (company_id, seq) in latest {
+            let subscriptions = StorageManager::get_company_subscriptions(&company_id);
+            if subscriptions.is_empty() {
+                continue;
+            }
+            let company = StorageManager::get_company(&company_id);
+            for mut subscription in subscriptions {
+                let status = company.as_ref().map(|c| c.status.clone());
+                let unchanged = status.as_ref().is_some_and(|status| {
+                    std::mem::discriminant(status) == std::mem::discriminant(&subscription.last_status)
+                });
+                if unchanged {
+                    continue;
+                }
+
+                let change = CompanyStatusChange {
+                    seq,
+                    company_id: company_id.clone(),
+                    previous_status: subscription.last_status.clone(),
+                    status: status.clone(),
+                    verification_score: company.as_ref().map_or(0, |c| c.verification_score),
+                    risk_level: company
+                        .as_ref()
+                        .map_or(RiskLevel::High, |c| Self::risk_level(c, alert_severities.get(&company_id))),
+                    changed_at: now,
+                };
+                StorageManager::append_partner_status_change(&subscription.partner, change, Self::MAX_CHANGES_PER_PARTNER);
+                match status {
+                    Some(status) => {
+                        subscription.last_status = status;
+                        StorageManager::insert_partner_subscription(subscription);
+                    }
+                    None => {
+                        StorageManager::remove_partner_subscription(&company_id, &subscription.partner);
+                    }
+                }
+                recorded += 1;
+            }
+        }
+
+        StorageManager::set_journal_cursor(Self::JOURNAL_CONSUMER, last_seq);
+        recorded
+    }
+
+    // Sends at most one batch to the partner's callback; returns the number accepted
+    pub async fn deliver_pending(partner_principal: Principal) -> u32 {
+        let partner = match StorageManager::get_partner(&partner_principal) {
+            Some(partner) if time() >= partner.next_attempt_at => partner,
+            _ => return 0,
+        };
+        let callback = match &partner.callback {
+            Some(callback) => callback.clone(),
+            None => return 0,
+        };
+        let changes =
+            StorageManager::get_partner_status_changes_after(&partner_principal, partner.delivered_seq, Self::MAX_BATCH_CHANGES);
+        let last_seq = match changes.last() {
+            Some(change) => change.seq,
+            None => return 0,
+        };
+        let change_count = changes.len() as u32;
+        if !StorageManager::try_begin_partner_delivery(&partner_principal) {
+            return 0;
+        }
+
+        let result: Result<(), _> = ic_cdk::call(callback.target, &callback.method, (changes,)).await;
+        StorageManager::end_partner_delivery(&partner_principal);
+
+        // The partner may have been removed or re-pointed while the call was out
+        let mut current = match StorageManager::get_partner(&partner_principal) {
+            Some(current) if current.callback.as_ref().is_some_and(|c| c.target == callback.target) => current,
+            _ => return 0,
+        };
+        match result {
+            Ok(()) => {
+                current.delivered_seq = last_seq;
+                current.consecutive_failures = 0;
+                current.last_error = None;
+                current.next_attempt_at = 0;
+                StorageManager::insert_partner(current);
+                change_count
+            }
+            Err((code, message)) => {
+                current.consecutive_failures += 1;
+                let error = format!("{:?}: {}", code, message);
+                current.last_error = Some(error.chars().take(Self::MAX_ERROR_LENGTH).collect());
+                let backoff = Self::BASE_BACKOFF_NS
+                    .saturating_mul(1u64 << current.consecutive_failures.min(16))
+                    .min(Self::MAX_BACKOFF_NS);
+                current.next_attempt_at = time().saturating_add(backoff);
+                StorageManager::insert_partner(current);
+                0
+            }
+        }
+    }
+
+    // Rebuilds the certified attestation tree when companies changed or it got old
+    pub fn refresh_listing_snapshot() {
+        let journal_seq = StorageManager::latest_company_event_seq();
+        let now = time();
+        let fresh = StorageManager::with_listing_snapshot(|snapshot| {
+            snapshot.is_some_and(|s| {
+                s.journal_seq == journal_seq && now.saturating_sub(s.built_at) < Self::SNAPSHOT_MAX_AGE_NS
+            })
+        });
+        if fresh {
+            return;
+        }
+
+        let alert_severities = Self::open_alert_severities();
+        let mut attestations: Vec<ListingAttestation> = StorageManager::get_all_companies()
+            .iter()
+            .map(|company| ListingAttestation {
+                company_id: company.id.clone(),
+                company_name: company.basic_info.name.clone(),
+                status: company.status.clone(),
+                verification_score: company.verification_score,
+                reputation_score: company.community_validation.reputation_score,
+                risk_level: Self::risk_level(company, alert_severities.get(&company.id)),
+                attested_at: now,
+                signature: None,
+            })
+            .collect();
+        if attestations.is_empty() {
+            return;
+        }
+        attestations.sort_by(|a, b| a.company_id.cmp(&b.company_id));
+
+        let mut levels = vec![attestations.iter().map(Self::leaf_hash).collect::<Vec<[u8; 32]>>()];
+        while levels[levels.len() - 1].len() > 1 {
+            let next = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| Self::node_hash(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+                .collect();
+            levels.push(next);
+        }
+
+        ic_cdk::api::set_certified_data(&levels[levels.len() - 1][0]);
+        StorageManager::set_listing_snapshot(ListingSnapshot {
+            journal_seq,
+            built_at: now,
+            attestations,
+            levels,
+        });
+    }
+
+    pub fn risk_level(company: &Company, open_alert_severity: Option<&AlertSeverity>) -> RiskLevel {
+        if matches!(company.status, CompanyStatus::Suspended | CompanyStatus::Flagged)
+            || open_alert_severity.is_some_and(|severity| *severity >= AlertSeverity::Error)
+        {
+            return RiskLevel::High;
+        }
+        let broken_proofs = company
+            .web3_identity
+            .verification_proofs
+            .iter()
+            .any(|proof| proof.status != ProofStatus::Active);
+        if matches!(company.status, CompanyStatus::Pending | CompanyStatus::Stale)
+            || company.warning_banner.is_some()
+            || broken_proofs
+            || open_alert_severity.is_some()
+        {
+            RiskLevel::Medium
+        } else {
+            RiskLevel::Low
+        }
+    }
+
+    // Most severe unacknowledged alert per company
+    fn open_alert_severities() -> BTreeMap<String, AlertSeverity> {
+        let mut severities: BTreeMap<String, AlertSeverity> = BTreeMap::new();
+        for alert in StorageManager::get_all_community_alerts() {
+            if alert.acknowledged_at.is_some() {
+                continue;
+            }
+            let entry = severities.entry(alert.company_id).or_insert(AlertSeverity::Info);
+            *entry = entry.clone().max(alert.severity);
+        }
+        severities
+    }
+
+    fn leaf_hash(attestation: &ListingAttestation) -> [u8; 32] {
+        let fields = format!(
+            "{}\n{}\n{:?}\n{}\n{}\n{:?}\n{}",
+            attestation.company_id,
+            attestation.company_name,
+            attestation.status,
+            attestation.verification_score,
+            attestation.reputation_score,
+            attestation.risk_level,
+            attestation.attested_at,
+        );
+        let mut hasher = Sha256::new();
+        hasher.update([0u8]);
+        hasher.update(fields.as_bytes());
+        hasher.finalize().into()
+    }
+
+    fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update([1u8]);
+        hasher.update(left);
+        hasher.update(right);
+        hasher.finalize().into()
+    }
+
+    fn ensure_partner(principal: Principal) -> Result<(), String> {
+        match StorageManager::get_partner(&principal) {
+            Some(_) => Ok(()),
+            None => Err("Unauthorized: Registered partner access required".to_string()),
+        }
+    }
+}
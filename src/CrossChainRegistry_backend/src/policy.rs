@@ -0,0 +1,86 @@
+use crate::community::CommunityValidationManager;
+use crate::storage::StorageManager;
+use crate::types::{CommunityPage, PolicyChange, ProviderEndpoint};
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Public changelog of the parameters scores and checks are computed with: score weights,
+// fees, thresholds and provider configuration. Entries are written when a change takes
+// effect (for timelocked changes, at execution rather than proposal), so a company whose
+// score moved can be pointed at what changed and when. Values are recorded as readable
+// text; provider endpoints are reduced to name, style and host so API keys embedded in
+// URL templates never end up in public.
+
+pub struct PolicyLog;
+
+impl PolicyLog {
+    const MAX_ENTRIES: u64 = 10_000;
+
+    pub fn record(
+        parameter: &str,
+        previous_value: Option<String>,
+        new_value: String,
+        changed_by: Vec<Principal>,
+        source: &str,
+    ) {
+        if previous_value.as_deref() == Some(new_value.as_str()) {
+            return;
+        }
+        StorageManager::append_policy_change(
+            PolicyChange {
+                seq: 0,
+                parameter: parameter.to_string(),
+                previous_value,
+                new_value,
+                changed_by,
+                source: source.to_string(),
+                effective_at: time(),
+            },
+            Self::MAX_ENTRIES,
+        );
+    }
+
+    pub fn describe_endpoints(endpoints: &[ProviderEndpoint]) -> String {
+        let described: Vec<String> = endpoints
+            .iter()
+            .map(|endpoint| {
+                let host = endpoint
+                    .url_template
+                    .trim_start_matches("https://")
+                    .split(['/', '?', '#'])
+                    .next()
+                    .unwrap_or_default();
+                format!(
+                    "{} ({:?}, {}{})",
+                    endpoint.name,
+                    endpoint.api_style,
+                    host,
+                    if endpoint.enabled { "" } else { ", disabled" }
+                )
+            })
+            .collect();
+        described.join("; ")
+    }
+
+    // Newest first; `parameter` matches exactly, or as a prefix when it ends in ':'
+    pub fn get_policy_history(
+        parameter: Option<String>,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> CommunityPage<PolicyChange> {
+        let mut changes: Vec<PolicyChange> = StorageManager::get_all_policy_changes()
+            .into_iter()
+            .filter(|change| {
+                parameter.as_ref().is_none_or(|parameter| {
+                    if parameter.ends_with(':') {
+                        change.parameter.starts_with(parameter.as_str())
+                    } else {
+                        change.parameter == *parameter
+                    }
+                })
+            })
+            .collect();
+        changes.reverse();
+        CommunityValidationManager::paginate(changes, offset, limit)
+    }
+}
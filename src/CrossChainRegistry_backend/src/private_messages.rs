@@ -0,0 +1,113 @@
+use crate::audit::AuditManager;
+use crate::moderation::ModerationManager;
+use crate::notifications::NotificationManager;
+use crate::ownership::OwnershipManager;
+use crate::storage::StorageManager;
+use crate::types::{CompanyRole, PrivateMessage, RegistryResult};
+use crate::vetkd::VetKdManager;
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Private channel from moderators to company owners, for things that shouldn't be a
+// public alert ("your domain appears compromised"). Senders IBE-encrypt to the company id
+// under the channel's vetKD public key, so they never need a secret; owners derive the
+// company's key, encrypted to their transport key, and decrypt locally. The canister
+// only ever holds ciphertext. Owners are notified when something arrives.
+
+pub struct PrivateMessageManager;
+
+impl PrivateMessageManager {
+    const KEY_CONTEXT: &'static [u8] = b"company_messages";
+    const MAX_MESSAGE_BYTES: usize = 8 * 1024;
+    const MAX_MESSAGES_PER_COMPANY: usize = 100;
+
+    // Public key senders encrypt under, with the company id as the identity
+    pub async fn get_private_message_public_key() -> RegistryResult<Vec<u8>> {
+        match VetKdManager::public_key(Self::KEY_CONTEXT).await {
+            Ok(public_key) => RegistryResult::Ok(public_key),
+            Err(err) => RegistryResult::Err(err),
+        }
+    }
+
+    pub fn send_private_message(
+        company_id: String,
+        ciphertext: Vec<u8>,
+        caller_principal: Principal,
+    ) -> RegistryResult<PrivateMessage> {
+        if let Err(err) = ModerationManager::ensure_moderator(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+        if ciphertext.is_empty() || ciphertext.len() > Self::MAX_MESSAGE_BYTES {
+            return RegistryResult::Err(format!(
+                "Message must be 1-{} bytes of ciphertext",
+                Self::MAX_MESSAGE_BYTES
+            ));
+        }
+
+        // Drop the oldest messages once the inbox is full
+        let existing = StorageManager::get_private_messages(&company_id);
+        if existing.len() >= Self::MAX_MESSAGES_PER_COMPANY {
+            let excess = existing.len() + 1 - Self::MAX_MESSAGES_PER_COMPANY;
+            for old in existing.iter().take(excess) {
+                StorageManager::remove_private_message(&company_id, &old.id);
+            }
+        }
+
+        // Zero-padded so a company's messages sort by send time
+        let message = PrivateMessage {
+            id: format!("pm_{:020}_{}", time(), StorageManager::private_message_count()),
+            company_id: company_id.clone(),
+            ciphertext,
+            sender: caller_principal,
+            sent_at: time(),
+        };
+        StorageManager::insert_private_message(message.clone());
+        AuditManager::record("send_private_message", &company_id, vec![caller_principal], message.id.clone());
+        // Everyone who can read the thread hears about it, not just the primary owner
+        for recipient in OwnershipManager::holders_of(&company, CompanyRole::Owner) {
+            NotificationManager::notify(
+                recipient,
+                &company_id,
+                None,
+                "You have a new private message from the registry moderators".to_string(),
+            );
+        }
+        RegistryResult::Ok(message)
+    }
+
+    // The company's vetKey, encrypted to the caller's transport key; owners only
+    pub async fn derive_private_message_key(
+        company_id: String,
+        transport_public_key: Vec<u8>,
+        caller_principal: Principal,
+    ) -> RegistryResult<Vec<u8>> {
+        if let Err(err) = Self::ensure_owner(&company_id, caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        match VetKdManager::derive_key(Self::KEY_CONTEXT, company_id.as_bytes(), transport_public_key).await {
+            Ok(encrypted_key) => RegistryResult::Ok(encrypted_key),
+            Err(err) => RegistryResult::Err(err),
+        }
+    }
+
+    // Oldest first
+    pub fn get_private_messages(company_id: String, caller_principal: Principal) -> RegistryResult<Vec<PrivateMessage>> {
+        if let Err(err) = Self::ensure_owner(&company_id, caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        RegistryResult::Ok(StorageManager::get_private_messages(&company_id))
+    }
+
+    fn ensure_owner(company_id: &str, caller_principal: Principal) -> Result<(), String> {
+        let company = StorageManager::get_company(company_id).ok_or_else(|| "Company not found".to_string())?;
+        if OwnershipManager::is_owner(&company, caller_principal) {
+            Ok(())
+        } else {
+            Err("Unauthorized: Only company owners can read private messages".to_string())
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use crate::admins::AdminManager;
+use crate::audit::AuditManager;
+use crate::http_client;
+use crate::ownership::OwnershipManager;
+use crate::sanitize::SafeUrl;
+use crate::storage::StorageManager;
+use crate::types::{CompanyRole, ProofStatus, RegistryResult, VerificationResult};
+use crate::verification::VerificationManager;
+use candid::Principal;
+use ic_cdk::api::management_canister::http_request::{
+    CanisterHttpRequestArgument, HttpHeader, HttpMethod, HttpResponse, TransformArgs,
+    TransformContext,
+};
+use ic_cdk::api::time;
+
+// Moves a social proof to a new URL when the account behind it was renamed or moved
+// (a new Twitter handle, a new Discord invite). The new post has to mention the old
+// identity, so a migration can't be used to swap in an unrelated account. The proof keeps
+// its original verification time and score, and monitoring checks the new URL from then on.
+
+pub struct ProofMigrationManager;
+
+impl ProofMigrationManager {
+    const MAX_PREVIOUS_URLS: usize = 10;
+
+    pub async fn migrate_proof(
+        company_id: String,
+        old_proof_url: String,
+        new_proof_url: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<VerificationResult> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+        if !OwnershipManager::has_role(&company, caller_principal, CompanyRole::Verifier) {
+            return RegistryResult::Err("Unauthorized: Only company owners or verifiers can migrate proofs".to_string());
+        }
+        if let Err(err) = AdminManager::ensure_not_suspended(&company) {
+            return RegistryResult::Err(err);
+        }
+
+        let old_proof = match company
+            .web3_identity
+            .verification_proofs
+            .iter()
+            .find(|proof| proof.proof_url == old_proof_url.trim())
+        {
+            Some(proof) => proof.clone(),
+            None => return RegistryResult::Err("No proof with that URL on this company".to_string()),
+        };
+        let allowed_domains = match VerificationManager::social_proof_domains(&old_proof.verification_type) {
+            Some(domains) => domains,
+            None => return RegistryResult::Err("Only social media proofs can be migrated".to_string()),
+        };
+        let new_url = match SafeUrl::parse_in_domains(&new_proof_url, "New proof URL", allowed_domains) {
+            Ok(url) => url,
+            Err(e) => return RegistryResult::Err(e),
+        };
+        if new_url.as_str() == old_proof.proof_url {
+            return RegistryResult::Err("The new proof URL is the same as the old one".to_string());
+        }
+        if company
+            .web3_identity
+            .verification_proofs
+            .iter()
+            .any(|proof| proof.proof_url == new_url.as_str())
+        {
+            return RegistryResult::Err("The new proof URL is already one of this company's proofs".to_string());
+        }
+
+        if let Err(err) = StorageManager::check_http_rate_limit(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let needles = Self::old_identity_needles(&old_proof.proof_url);
+        if let Err(message) = Self::check_reference(new_url.as_str(), &needles).await {
+            return RegistryResult::Ok(VerificationResult {
+                success: false,
+                message,
+                verified_at: None,
+                proof_id: None,
+                verification_type: Some(old_proof.verification_type),
+                chain_type: None,
+                score_delta: 0,
+                retry_after_secs: None,
+                next_action: Some(format!(
+                    "Publish the new post so that it mentions {}, then retry",
+                    needles.first().cloned().unwrap_or(old_proof.proof_url)
+                )),
+                providers: None,
+            });
+        }
+
+        // Re-read after the outcall; the proof may have been removed or migrated meanwhile
+        let mut migrated = false;
+        let mut score_delta = 0;
+        StorageManager::update_company(&company_id, |company| {
+            let previous_score = company.verification_score;
+            let proof = match company
+                .web3_identity
+                .verification_proofs
+                .iter_mut()
+                .find(|proof| proof.proof_url == old_proof.proof_url)
+            {
+                Some(proof) => proof,
+                None => return,
+            };
+            let previous_urls = proof.previous_urls.get_or_insert_with(Vec::new);
+            previous_urls.push(proof.proof_url.clone());
+            if previous_urls.len() > Self::MAX_PREVIOUS_URLS {
+                previous_urls.remove(0);
+            }
+            proof.proof_url = new_url.as_str().to_string();
+            // A proof that broke because of the move is restored, not penalized
+            proof.status = ProofStatus::Active;
+            let verification_type = proof.verification_type.clone();
+            VerificationManager::apply_social_identity(company, &verification_type, &new_url);
+            company.verification_score = VerificationManager::calculate_verification_score(company);
+            score_delta = company.verification_score as i32 - previous_score as i32;
+            migrated = true;
+        });
+        if !migrated {
+            return RegistryResult::Err("The proof changed while the new URL was being checked".to_string());
+        }
+
+        AuditManager::record(
+            "proof_migrated",
+            &company_id,
+            vec![caller_principal],
+            format!("{} -> {}", old_proof.proof_url, new_url.as_str()),
+        );
+
+        RegistryResult::Ok(VerificationResult {
+            success: true,
+            message: "Proof migrated; monitoring now follows the new URL".to_string(),
+            verified_at: Some(time()),
+            proof_id: Some(VerificationManager::proof_id(&old_proof)),
+            verification_type: Some(old_proof.verification_type),
+            chain_type: None,
+            score_delta,
+            retry_after_secs: None,
+            next_action: VerificationManager::recommend_next_action(&company_id),
+            providers: None,
+        })
+    }
+
+    // Strings the new post may use to refer to the old account: the handle, invite code or
+    // channel name (first path segment) and the full old URL, all lowercased
+    fn old_identity_needles(old_proof_url: &str) -> Vec<String> {
+        let mut needles = Vec::new();
+        if let Ok(url) = SafeUrl::parse(old_proof_url, "Proof URL") {
+            let segments = url.path_segments();
+            // discord.com/invite/<code>: the code is the second segment
+            let identity = match segments.as_slice() {
+                ["invite", code, ..] => Some(*code),
+                [first, ..] => Some(*first),
+                [] => None,
+            };
+            if let Some(identity) = identity.filter(|identity| identity.len() >= 3) {
+                needles.push(identity.to_lowercase());
+            }
+        }
+        needles.push(old_proof_url.to_lowercase());
+        needles
+    }
+
+    async fn check_reference(new_proof_url: &str, needles: &[String]) -> Result<(), String> {
+        let request = CanisterHttpRequestArgument {
+            url: new_proof_url.to_string(),
+            method: HttpMethod::GET,
+            body: None,
+            max_response_bytes: Some(256 * 1024),
+            transform: Some(TransformContext::from_name(
+                "transform_proof_reference".to_string(),
+                needles.join("\n").into_bytes(),
+            )),
+            headers: vec![HttpHeader {
+                name: "User-Agent".to_string(),
+                value: "ICP-CrossChainRegistry-ProofChecker/1.0".to_string(),
+            }],
+        };
+
+        match http_client::send(request, 20_000_000_000).await {
+            Ok(response) => {
+                if response.status != 200u32 {
+                    return Err(format!("New proof URL returned HTTP status {}", response.status));
+                }
+                if response.body == b"reference_found" {
+                    Ok(())
+                } else {
+                    Err("The new proof does not mention the old account".to_string())
+                }
+            }
+            Err(err) => Err(format!("Fetching the new proof failed: {}", err)),
+        }
+    }
+}
+
+// Reduce the page to whether it mentions any of the newline-separated needles passed as
+// context, case-insensitively
+pub fn transform_proof_reference(raw: TransformArgs) -> HttpResponse {
+    let body = String::from_utf8_lossy(&raw.response.body).to_lowercase();
+    let context = String::from_utf8_lossy(&raw.context).to_string();
+    let found = context
+        .split('\n')
+        .filter(|needle| !needle.is_empty())
+        .any(|needle| body.contains(needle));
+
+    HttpResponse {
+        status: raw.response.status.clone(),
+        body: if found { b"reference_found".to_vec() } else { b"reference_missing".to_vec() },
+        headers: vec![],
+    }
+}
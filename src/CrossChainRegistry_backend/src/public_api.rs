@@ -0,0 +1,330 @@
+use crate::api::RegistryAPI;
+use crate::api_keys::ApiKeyManager;
+use crate::community::CommunityValidationManager;
+use crate::storage::StorageManager;
+use crate::types::{HttpRequest, HttpResponse, RegistryResult};
+use candid::Principal;
+use ic_cdk::api::time;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+// Keyless, read-only JSON under /public/v1/ on the HTTP gateway, for sites and crawlers
+// that don't speak Candid. It serves what the public query endpoints already return to
+// any caller; heavier consumers belong on the keyed /api/v1/ routes or the open data
+// snapshot. Responses are not certified.
+//
+// Everything here stays on the query path. Stats, the default leaderboard and the most
+// reputable profiles are pre-rendered by a timer and served with ETags and a longer
+// shared-cache lifetime, so gateways absorb repeat traffic. Clients without a User-Agent
+// are refused and crawlers only get pre-rendered responses.
+//
+// A query can't keep counters, so anything not in the cache is upgraded to
+// http_request_update and counted there with the fixed-window rate limiter: per API key
+// when the request sends one, otherwise per caller principal. Gateway requests without a
+// key all arrive as the anonymous principal, so those are counted per client instead, by
+// a hash of the forwarding headers and User-Agent. Clients can vary those headers; the
+// fingerprint only keeps one scraper from using up everyone else's budget.
+
+// A pre-rendered body with its strong ETag
+pub struct CachedResponse {
+    pub body: Vec<u8>,
+    pub etag: String,
+}
+
+pub struct PublicApiManager;
+
+impl PublicApiManager {
+    const PATH_PREFIX: &'static str = "/public/v1/";
+    const DEFAULT_LEADERBOARD_SIZE: u32 = 20;
+    const MAX_LEADERBOARD_SIZE: u32 = 100;
+    const CACHE_MAX_AGE_SECS: u64 = 60;
+    const CACHED_MAX_AGE_SECS: u64 = 300;
+    const CACHE_REFRESH_INTERVAL_SECS: u64 = 5 * 60;
+    const POPULAR_PROFILES: u32 = 100;
+    const CRAWLER_RETRY_AFTER_SECS: u64 = 300;
+    const MAX_URL_LENGTH: usize = 512;
+    const CRAWLER_MARKERS: [&'static str; 8] =
+        ["bot", "crawl", "spider", "slurp", "scrapy", "python-requests", "go-http-client", "headless"];
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer(Duration::ZERO, Self::refresh_cache);
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::CACHE_REFRESH_INTERVAL_SECS), Self::refresh_cache);
+    }
+
+    // Re-renders every cached route; profiles that dropped out of the top are evicted
+    pub fn refresh_cache() {
+        let mut routes = vec![
+            "stats".to_string(),
+            format!("leaderboard?limit={}", Self::DEFAULT_LEADERBOARD_SIZE),
+        ];
+        routes.extend(
+            CommunityValidationManager::get_reputation_leaderboard(Some(Self::POPULAR_PROFILES))
+                .into_iter()
+                .map(|entry| format!("companies/{}", entry.company_id)),
+        );
+
+        let cache: BTreeMap<String, CachedResponse> = routes
+            .into_iter()
+            .filter_map(|route| {
+                let body = Self::render(&route).ok()?;
+                let digest = Sha256::digest(&body);
+                let etag = format!("\"{}\"", digest.iter().map(|b| format!("{:02x}", b)).collect::<String>());
+                Some((route, CachedResponse { body, etag }))
+            })
+            .collect();
+        StorageManager::set_public_response_cache(cache, time());
+    }
+
+    pub fn is_public_path(url: &str) -> bool {
+        url.split('?').next().unwrap_or("").starts_with(Self::PATH_PREFIX)
+    }
+
+    pub fn http_request(request: HttpRequest) -> HttpResponse {
+        if request.method == "OPTIONS" {
+            let mut response = Self::json_response(204, Vec::new());
+            response.headers.push(("Access-Control-Allow-Methods".to_string(), "GET, HEAD".to_string()));
+            response.headers.push(("Access-Control-Allow-Headers".to_string(), "If-None-Match, X-API-Key".to_string()));
+            return response;
+        }
+        if request.method != "GET" && request.method != "HEAD" {
+            return Self::json_error(405, "Method not allowed");
+        }
+        if request.url.len() > Self::MAX_URL_LENGTH {
+            return Self::json_error(414, "URL too long");
+        }
+        let user_agent = request
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("user-agent"))
+            .map(|(_, value)| value.trim().to_lowercase())
+            .unwrap_or_default();
+        if user_agent.is_empty() {
+            return Self::json_error(400, "A User-Agent header is required");
+        }
+
+        let route = match Self::route_key(&request.url) {
+            Ok(route) => route,
+            Err(response) => return response,
+        };
+        let head = request.method == "HEAD";
+
+        let cached = StorageManager::with_public_response_cache(|cache, built_at| {
+            cache.get(&route).map(|cached| (cached.body.clone(), cached.etag.clone(), built_at))
+        });
+        if let Some((body, etag, built_at)) = cached {
+            let not_modified = request
+                .headers
+                .iter()
+                .any(|(name, value)| name.eq_ignore_ascii_case("if-none-match") && value.trim() == etag);
+            let (status_code, body) = match (not_modified, head) {
+                (true, _) => (304, Vec::new()),
+                (false, true) => (200, Vec::new()),
+                (false, false) => (200, body),
+            };
+            let mut response = Self::json_response(status_code, body);
+            response.headers.push((
+                "Cache-Control".to_string(),
+                format!(
+                    "public, max-age={}, stale-while-revalidate={}",
+                    Self::CACHED_MAX_AGE_SECS,
+                    Self::CACHE_REFRESH_INTERVAL_SECS
+                ),
+            ));
+            response.headers.push(("ETag".to_string(), etag));
+            response.headers.push(("Age".to_string(), (time().saturating_sub(built_at) / 1_000_000_000).to_string()));
+            response.headers.push(("Access-Control-Expose-Headers".to_string(), "ETag".to_string()));
+            return response;
+        }
+
+        // Crawlers walk every id; point them at the snapshot rather than rendering each one
+        if Self::CRAWLER_MARKERS.iter().any(|marker| user_agent.contains(marker)) {
+            let mut response = Self::json_error(
+                429,
+                "Crawlers are served pre-rendered pages only; fetch /open-data/registry.json.gz for the full registry",
+            );
+            response.headers.push(("Retry-After".to_string(), Self::CRAWLER_RETRY_AFTER_SECS.to_string()));
+            return response;
+        }
+
+        HttpResponse {
+            status_code: 200,
+            headers: Vec::new(),
+            body: Vec::new(),
+            upgrade: Some(true),
+        }
+    }
+
+    // Cache misses, replayed by the gateway after the query path asked for an upgrade
+    pub fn http_request_update(request: HttpRequest, caller_principal: Principal) -> HttpResponse {
+        if request.method != "GET" && request.method != "HEAD" {
+            return Self::json_error(405, "Method not allowed");
+        }
+        let route = match Self::route_key(&request.url) {
+            Ok(route) => route,
+            Err(response) => return response,
+        };
+
+        if ApiKeyManager::has_key(&request) {
+            if let Err(response) = ApiKeyManager::authenticate(&request) {
+                return response;
+            }
+        } else if let Err(retry_after) =
+            StorageManager::check_public_api_rate_limit(caller_principal, &Self::rate_limit_bucket(&request, caller_principal))
+        {
+            let mut response =
+                Self::json_error(429, &format!("Rate limit exceeded. Retry after {}s, or send an X-API-Key.", retry_after));
+            response.headers.push(("Retry-After".to_string(), retry_after.to_string()));
+            return response;
+        }
+
+        let body = match Self::render(&route) {
+            Ok(body) => body,
+            Err(response) => return response,
+        };
+        let mut response = Self::json_response(200, if request.method == "HEAD" { Vec::new() } else { body });
+        response
+            .headers
+            .push(("Cache-Control".to_string(), format!("public, max-age={}", Self::CACHE_MAX_AGE_SECS)));
+        response
+    }
+
+    // Authenticated callers are counted by principal; anonymous ones by client fingerprint
+    fn rate_limit_bucket(request: &HttpRequest, caller_principal: Principal) -> String {
+        if caller_principal != Principal::anonymous() {
+            return caller_principal.to_text();
+        }
+        let mut hasher = Sha256::new();
+        for name in ["x-forwarded-for", "x-real-ip", "user-agent"] {
+            let value = request
+                .headers
+                .iter()
+                .find(|(header, _)| header.eq_ignore_ascii_case(name))
+                .map_or("", |(_, value)| value.trim());
+            hasher.update(value.as_bytes());
+            hasher.update([0]);
+        }
+        let digest = hasher.finalize();
+        format!("client:{}", digest[..16].iter().map(|b| format!("{:02x}", b)).collect::<String>())
+    }
+
+    // The cache key of a request: the route under the prefix, with only the query
+    // parameters it uses, so cache-busting parameters don't bypass the cache
+    fn route_key(url: &str) -> Result<String, HttpResponse> {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let route = path[Self::PATH_PREFIX.len()..].trim_end_matches('/');
+        if route != "leaderboard" {
+            return Ok(route.to_string());
+        }
+        let limit = match Self::query_param(query, "limit") {
+            Some(value) => match value.parse::<u32>() {
+                Ok(limit) if (1..=Self::MAX_LEADERBOARD_SIZE).contains(&limit) => limit,
+                _ => return Err(Self::json_error(400, &format!("limit must be 1-{}", Self::MAX_LEADERBOARD_SIZE))),
+            },
+            None => Self::DEFAULT_LEADERBOARD_SIZE,
+        };
+        Ok(format!("leaderboard?limit={}", limit))
+    }
+
+    fn render(route: &str) -> Result<Vec<u8>, HttpResponse> {
+        let (path, query) = route.split_once('?').unwrap_or((route, ""));
+        match path.split('/').collect::<Vec<_>>().as_slice() {
+            ["companies", company_id] if !company_id.is_empty() => {
+                match RegistryAPI::get_company(company_id.to_string(), None) {
+                    RegistryResult::Ok(company) => Ok(serde_json::to_vec(&company).unwrap_or_default()),
+                    RegistryResult::Err(err) => Err(Self::json_error(404, &err)),
+                }
+            }
+            ["leaderboard"] => {
+                let limit = Self::query_param(query, "limit").and_then(|value| value.parse::<u32>().ok());
+                let leaderboard = CommunityValidationManager::get_reputation_leaderboard(limit);
+                Ok(serde_json::json!({ "leaderboard": leaderboard }).to_string().into_bytes())
+            }
+            ["stats"] => {
+                // Sorted, so unchanged numbers give identical bodies
+                let stats: BTreeMap<String, u64> = RegistryAPI::get_statistics().into_iter().collect();
+                Ok(serde_json::to_vec(&stats).unwrap_or_default())
+            }
+            _ => Err(Self::json_error(404, "Not found")),
+        }
+    }
+
+    fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    }
+
+    fn json_response(status_code: u16, body: Vec<u8>) -> HttpResponse {
+        HttpResponse {
+            status_code,
+            headers: vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("Access-Control-Allow-Origin".to_string(), "*".to_string()),
+                ("X-Content-Type-Options".to_string(), "nosniff".to_string()),
+            ],
+            body,
+            upgrade: None,
+        }
+    }
+
+    fn json_error(status_code: u16, message: &str) -> HttpResponse {
+        let mut response = Self::json_response(
+            status_code,
+            serde_json::json!({ "error": message }).to_string().into_bytes(),
+        );
+        response.headers.push(("Cache-Control".to_string(), "no-store".to_string()));
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(headers: &[(&str, &str)]) -> HttpRequest {
+        HttpRequest {
+            method: "GET".to_string(),
+            url: "/public/v1/stats".to_string(),
+            headers: headers.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect(),
+            body: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn keyless_clients_get_separate_buckets() {
+        let anonymous = Principal::anonymous();
+        let first = request(&[("X-Forwarded-For", "203.0.113.7"), ("User-Agent", "Mozilla/5.0")]);
+        let second = request(&[("X-Forwarded-For", "198.51.100.23"), ("User-Agent", "Mozilla/5.0")]);
+        let other_agent = request(&[("X-Forwarded-For", "203.0.113.7"), ("User-Agent", "curl/8.5.0")]);
+        let by_real_ip = request(&[("X-Real-IP", "203.0.113.7"), ("User-Agent", "Mozilla/5.0")]);
+
+        let bucket = PublicApiManager::rate_limit_bucket(&first, anonymous);
+        assert!(bucket.starts_with("client:"));
+        assert_ne!(bucket, PublicApiManager::rate_limit_bucket(&second, anonymous));
+        assert_ne!(bucket, PublicApiManager::rate_limit_bucket(&other_agent, anonymous));
+        assert_ne!(bucket, PublicApiManager::rate_limit_bucket(&by_real_ip, anonymous));
+        assert_ne!(bucket, anonymous.to_text());
+    }
+
+    #[test]
+    fn same_client_keeps_its_bucket() {
+        let anonymous = Principal::anonymous();
+        let first = request(&[("x-forwarded-for", "203.0.113.7"), ("user-agent", "Mozilla/5.0")]);
+        let again = request(&[("User-Agent", " Mozilla/5.0 "), ("X-Forwarded-For", "203.0.113.7")]);
+        assert_eq!(
+            PublicApiManager::rate_limit_bucket(&first, anonymous),
+            PublicApiManager::rate_limit_bucket(&again, anonymous)
+        );
+    }
+
+    #[test]
+    fn authenticated_callers_are_counted_by_principal() {
+        let caller = Principal::from_slice(&[7; 29]);
+        let headers = request(&[("X-Forwarded-For", "203.0.113.7"), ("User-Agent", "Mozilla/5.0")]);
+        assert_eq!(PublicApiManager::rate_limit_bucket(&headers, caller), caller.to_text());
+        assert_eq!(PublicApiManager::rate_limit_bucket(&request(&[]), caller), caller.to_text());
+    }
+}
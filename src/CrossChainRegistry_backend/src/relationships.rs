@@ -0,0 +1,334 @@
+use crate::ownership::OwnershipManager;
+use crate::storage::StorageManager;
+use crate::types::{
+    Company, CompanyRelationship, CompanyStatus, CorporateGraph, CorporateGraphNode, GroupSignals,
+    RegistryResult, RelationshipKind, RelationshipStatus,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+use std::collections::{HashSet, VecDeque};
+
+// Corporate hierarchy: parent/subsidiary and product-of links confirmed by both sides
+
+pub struct RelationshipManager;
+
+impl RelationshipManager {
+    const MAX_GRAPH_DEPTH: u32 = 10;
+    const MAX_GRAPH_NODES: usize = 200;
+    const MAX_CHILDREN_PER_COMPANY: usize = 100;
+
+    fn relationship_key(child_company_id: &str, parent_company_id: &str) -> String {
+        format!("{}:{}", child_company_id, parent_company_id)
+    }
+
+    // Either side proposes; the proposing side is confirmed immediately
+    pub fn propose_relationship(
+        child_company_id: String,
+        parent_company_id: String,
+        kind: RelationshipKind,
+        caller_principal: Principal,
+    ) -> RegistryResult<CompanyRelationship> {
+        if child_company_id == parent_company_id {
+            return RegistryResult::Err("A company cannot be linked to itself".to_string());
+        }
+
+        let child = match StorageManager::get_company(&child_company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Child company not found".to_string()),
+        };
+        let parent = match StorageManager::get_company(&parent_company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Parent company not found".to_string()),
+        };
+
+        let child_side = OwnershipManager::is_owner(&child, caller_principal);
+        let parent_side = OwnershipManager::is_owner(&parent, caller_principal);
+        if !child_side && !parent_side {
+            return RegistryResult::Err(
+                "Unauthorized: Only an owner of either company can propose a relationship".to_string(),
+            );
+        }
+
+        let key = Self::relationship_key(&child_company_id, &parent_company_id);
+        let existing = StorageManager::get_relationship(&key);
+        if let Some(existing) = &existing {
+            if existing.status == RelationshipStatus::Confirmed {
+                return RegistryResult::Err("Relationship already exists".to_string());
+            }
+        }
+        // Matching proposals from both sides count as mutual confirmation
+        let (child_side, parent_side) = match existing {
+            Some(existing) if existing.kind == kind => (
+                child_side || existing.child_confirmed,
+                parent_side || existing.parent_confirmed,
+            ),
+            _ => (child_side, parent_side),
+        };
+
+        if Self::confirmed_parent(&child_company_id).is_some() {
+            return RegistryResult::Err("Child company already has a confirmed parent".to_string());
+        }
+        if Self::creates_cycle(&child_company_id, &parent_company_id) {
+            return RegistryResult::Err(
+                "Relationship would create a cycle in the corporate hierarchy".to_string(),
+            );
+        }
+        if Self::confirmed_children(&parent_company_id).len() >= Self::MAX_CHILDREN_PER_COMPANY {
+            return RegistryResult::Err("Parent company has reached the maximum number of linked entities".to_string());
+        }
+
+        let relationship = CompanyRelationship {
+            child_company_id,
+            parent_company_id,
+            kind,
+            status: RelationshipStatus::Proposed,
+            child_confirmed: child_side,
+            parent_confirmed: parent_side,
+            proposed_by: caller_principal,
+            proposed_at: time(),
+            confirmed_at: None,
+        };
+
+        // A single owner of both companies confirms both sides at once
+        let relationship = Self::finalize(relationship);
+        StorageManager::insert_relationship(key, relationship.clone());
+
+        RegistryResult::Ok(relationship)
+    }
+
+    pub fn confirm_relationship(
+        child_company_id: String,
+        parent_company_id: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<CompanyRelationship> {
+        let key = Self::relationship_key(&child_company_id, &parent_company_id);
+        let mut relationship = match StorageManager::get_relationship(&key) {
+            Some(relationship) => relationship,
+            None => return RegistryResult::Err("Relationship not found".to_string()),
+        };
+
+        if relationship.status == RelationshipStatus::Confirmed {
+            return RegistryResult::Ok(relationship);
+        }
+
+        let child = StorageManager::get_company(&child_company_id);
+        let parent = StorageManager::get_company(&parent_company_id);
+        let (child_side, parent_side) = match (child, parent) {
+            (Some(child), Some(parent)) => (
+                OwnershipManager::is_owner(&child, caller_principal),
+                OwnershipManager::is_owner(&parent, caller_principal),
+            ),
+            _ => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        if child_side {
+            relationship.child_confirmed = true;
+        }
+        if parent_side {
+            relationship.parent_confirmed = true;
+        }
+        if !child_side && !parent_side {
+            return RegistryResult::Err(
+                "Unauthorized: Only an owner of either company can confirm a relationship".to_string(),
+            );
+        }
+
+        // Re-check hierarchy constraints, other links may have been confirmed meanwhile
+        if relationship.child_confirmed && relationship.parent_confirmed {
+            if Self::confirmed_parent(&child_company_id).is_some() {
+                return RegistryResult::Err("Child company already has a confirmed parent".to_string());
+            }
+            if Self::creates_cycle(&child_company_id, &parent_company_id) {
+                return RegistryResult::Err(
+                    "Relationship would create a cycle in the corporate hierarchy".to_string(),
+                );
+            }
+        }
+
+        let relationship = Self::finalize(relationship);
+        StorageManager::insert_relationship(key, relationship.clone());
+
+        RegistryResult::Ok(relationship)
+    }
+
+    // Either side can dissolve a link or withdraw a proposal
+    pub fn remove_relationship(
+        child_company_id: String,
+        parent_company_id: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<()> {
+        let key = Self::relationship_key(&child_company_id, &parent_company_id);
+        if StorageManager::get_relationship(&key).is_none() {
+            return RegistryResult::Err("Relationship not found".to_string());
+        }
+
+        let authorized = [&child_company_id, &parent_company_id].iter().any(|id| {
+            StorageManager::get_company(id).is_some_and(|c| OwnershipManager::is_owner(&c, caller_principal))
+        });
+        if !authorized {
+            return RegistryResult::Err(
+                "Unauthorized: Only an owner of either company can remove a relationship".to_string(),
+            );
+        }
+
+        StorageManager::remove_relationship(&key);
+        RegistryResult::Ok(())
+    }
+
+    pub fn get_relationships_for_company(company_id: String) -> Vec<CompanyRelationship> {
+        StorageManager::get_all_relationships()
+            .into_iter()
+            .filter(|r| r.child_company_id == company_id || r.parent_company_id == company_id)
+            .collect()
+    }
+
+    // Confirmed hierarchy around a company, from its top-level parent down
+    pub fn get_corporate_graph(company_id: String) -> RegistryResult<CorporateGraph> {
+        if StorageManager::get_company(&company_id).is_none() {
+            return RegistryResult::Err("Company not found".to_string());
+        }
+
+        let root_company_id = Self::find_root(&company_id);
+        let (nodes, edges) = Self::collect_subtree(&root_company_id);
+        let signals = Self::aggregate_signals(&root_company_id, &nodes);
+
+        RegistryResult::Ok(CorporateGraph {
+            root_company_id,
+            nodes,
+            edges,
+            signals,
+        })
+    }
+
+    // Signals aggregated over a company and every confirmed descendant
+    pub fn get_group_signals(company_id: String) -> RegistryResult<GroupSignals> {
+        if StorageManager::get_company(&company_id).is_none() {
+            return RegistryResult::Err("Company not found".to_string());
+        }
+
+        let (nodes, _) = Self::collect_subtree(&company_id);
+        RegistryResult::Ok(Self::aggregate_signals(&company_id, &nodes))
+    }
+
+    fn finalize(mut relationship: CompanyRelationship) -> CompanyRelationship {
+        if relationship.child_confirmed && relationship.parent_confirmed {
+            relationship.status = RelationshipStatus::Confirmed;
+            relationship.confirmed_at = Some(time());
+        }
+        relationship
+    }
+
+    fn confirmed_relationships() -> Vec<CompanyRelationship> {
+        StorageManager::get_all_relationships()
+            .into_iter()
+            .filter(|r| r.status == RelationshipStatus::Confirmed)
+            .collect()
+    }
+
+    fn confirmed_parent(company_id: &str) -> Option<CompanyRelationship> {
+        Self::confirmed_relationships()
+            .into_iter()
+            .find(|r| r.child_company_id == company_id)
+    }
+
+    fn confirmed_children(company_id: &str) -> Vec<CompanyRelationship> {
+        Self::confirmed_relationships()
+            .into_iter()
+            .filter(|r| r.parent_company_id == company_id)
+            .collect()
+    }
+
+    // Linking child under parent is a cycle if the child is already an ancestor of the parent
+    fn creates_cycle(child_company_id: &str, parent_company_id: &str) -> bool {
+        let mut current = parent_company_id.to_string();
+        for _ in 0..=Self::MAX_GRAPH_DEPTH {
+            if current == child_company_id {
+                return true;
+            }
+            match Self::confirmed_parent(&current) {
+                Some(relationship) => current = relationship.parent_company_id,
+                None => return false,
+            }
+        }
+        // Hierarchies deeper than the limit are rejected as well
+        true
+    }
+
+    fn find_root(company_id: &str) -> String {
+        let mut current = company_id.to_string();
+        for _ in 0..Self::MAX_GRAPH_DEPTH {
+            match Self::confirmed_parent(&current) {
+                Some(relationship) => current = relationship.parent_company_id,
+                None => break,
+            }
+        }
+        current
+    }
+
+    fn collect_subtree(root_company_id: &str) -> (Vec<CorporateGraphNode>, Vec<CompanyRelationship>) {
+        let relationships = Self::confirmed_relationships();
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        queue.push_back((root_company_id.to_string(), 0u32, None));
+
+        while let Some((company_id, depth, kind)) = queue.pop_front() {
+            if nodes.len() >= Self::MAX_GRAPH_NODES || !visited.insert(company_id.clone()) {
+                continue;
+            }
+            let company = match StorageManager::get_company(&company_id) {
+                Some(company) => company,
+                None => continue,
+            };
+            nodes.push(Self::graph_node(&company, depth, kind));
+
+            if depth >= Self::MAX_GRAPH_DEPTH {
+                continue;
+            }
+            for relationship in relationships.iter().filter(|r| r.parent_company_id == company_id) {
+                edges.push(relationship.clone());
+                queue.push_back((
+                    relationship.child_company_id.clone(),
+                    depth + 1,
+                    Some(relationship.kind.clone()),
+                ));
+            }
+        }
+
+        (nodes, edges)
+    }
+
+    fn graph_node(company: &Company, depth: u32, kind: Option<RelationshipKind>) -> CorporateGraphNode {
+        CorporateGraphNode {
+            company_id: company.id.clone(),
+            name: company.basic_info.name.clone(),
+            status: company.status.clone(),
+            verification_score: company.verification_score,
+            domain_verified: company.web3_identity.domain_verified,
+            depth,
+            relationship_to_parent: kind,
+        }
+    }
+
+    fn aggregate_signals(company_id: &str, nodes: &[CorporateGraphNode]) -> GroupSignals {
+        let entity_count = nodes.len() as u32;
+        let total_score: u32 = nodes.iter().map(|n| n.verification_score).sum();
+
+        GroupSignals {
+            company_id: company_id.to_string(),
+            entity_count,
+            verified_entity_count: nodes
+                .iter()
+                .filter(|n| matches!(n.status, CompanyStatus::Verified | CompanyStatus::Trusted))
+                .count() as u32,
+            flagged_entity_count: nodes
+                .iter()
+                .filter(|n| matches!(n.status, CompanyStatus::Flagged | CompanyStatus::Suspended))
+                .count() as u32,
+            domain_verified_count: nodes.iter().filter(|n| n.domain_verified).count() as u32,
+            average_verification_score: total_score.checked_div(entity_count).unwrap_or(0),
+            max_verification_score: nodes.iter().map(|n| n.verification_score).max().unwrap_or(0),
+        }
+    }
+}
@@ -0,0 +1,437 @@
+use crate::audit::AuditManager;
+use crate::settings::SettingsManager;
+use crate::storage::StorageManager;
+use crate::types::{
+    RegistryInitArgs, RegistryResult, ReplicaInfo, ReplicatedChange, ReplicationBatch, ReplicationCheckpoint,
+    ReplicationSnapshotPage, ReplicationState, ReplicationStatus,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+// Read replicas. A canister installed with a replication source mirrors that registry's
+// companies and serves the same get/list/search queries, taking query load off the
+// primary. The replica follows the primary's company event journal: each batch names the
+// companies that changed and carries their current state, so applying a batch twice is
+// harmless. A new replica, or one that fell behind what the journal still holds, first
+// copies every company a page at a time and then follows the journal from where that
+// copy started.
+//
+// Replicas report their position back to the primary, which keeps journal events until
+// every registered replica has them; a replica that stops reporting holds pruning back
+// until it is removed. On a replica, updates from anyone but a controller are refused and
+// the background jobs that write to companies do not run.
+
+pub struct ReplicationManager;
+
+impl ReplicationManager {
+    const SYNC_INTERVAL_SECS: u64 = 30;
+    const MAX_BATCH_EVENTS: u32 = 200;
+    const MAX_SNAPSHOT_PAGE: u32 = 50; // Whole profiles; keeps a page well under the reply limit
+    const MAX_PAGES_PER_SYNC: usize = 10;
+    const MAX_REPLICAS: usize = 10;
+    const MAX_ERROR_LENGTH: usize = 200;
+
+    fn journal_consumer(canister_id: &Principal) -> String {
+        format!("replica:{}", canister_id.to_text())
+    }
+
+    pub fn is_replica() -> bool {
+        StorageManager::get_replication_state().is_some()
+    }
+
+    // Install/upgrade argument: a source makes this canister a replica of it (starting
+    // over if the source changed), an explicit None promotes it, no argument keeps the role
+    pub fn configure(args: Option<RegistryInitArgs>) {
+        let args = match args {
+            Some(args) => args,
+            None => return,
+        };
+        match args.replication_source {
+            Some(source) => {
+                if source == ic_cdk::id() || source == Principal::anonymous() {
+                    ic_cdk::trap("Replication source must be another registry canister");
+                }
+                if StorageManager::get_replication_state().is_some_and(|state| state.source == source) {
+                    return;
+                }
+                StorageManager::set_replication_state(ReplicationState {
+                    source,
+                    applied_seq: 0,
+                    resyncing: true,
+                    snapshot_cursor: None,
+                    snapshot_seq: 0,
+                    source_latest_seq: 0,
+                    last_synced_at: None,
+                    consecutive_failures: 0,
+                    last_error: None,
+                });
+            }
+            None => StorageManager::clear_replication_state(),
+        }
+    }
+
+    // Update guard: a replica only takes writes from the primary's journal
+    pub fn ensure_writable(caller_principal: Principal) -> Result<(), String> {
+        match StorageManager::get_replication_state() {
+            Some(state) if !ic_cdk::api::is_controller(&caller_principal) => Err(format!(
+                "This canister is a read replica of {}; send updates there",
+                state.source.to_text()
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::SYNC_INTERVAL_SECS), || {
+            if Self::is_replica() {
+                ic_cdk::spawn(async {
+                    let _ = Self::sync().await;
+                });
+            }
+        });
+    }
+
+    // Primary: replica roster (controllers)
+
+    pub fn add_replica(canister_id: Principal, caller_principal: Principal) -> RegistryResult<ReplicaInfo> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if canister_id == Principal::anonymous() || canister_id == ic_cdk::id() {
+            return RegistryResult::Err("Replica must be another registry canister".to_string());
+        }
+        if StorageManager::get_replica(&canister_id).is_some() {
+            return RegistryResult::Err("Canister is already a replica".to_string());
+        }
+        if StorageManager::get_all_replicas().len() >= Self::MAX_REPLICAS {
+            return RegistryResult::Err(format!("At most {} replicas", Self::MAX_REPLICAS));
+        }
+
+        let latest_seq = StorageManager::latest_company_event_seq();
+        let replica = ReplicaInfo {
+            canister_id,
+            added_by: caller_principal,
+            added_at: time(),
+            applied_seq: 0,
+            lag_events: latest_seq,
+            last_reported_at: None,
+        };
+        StorageManager::insert_replica(replica.clone());
+        // A new replica starts with a full copy, so only later events need keeping
+        StorageManager::set_journal_cursor(&Self::journal_consumer(&canister_id), latest_seq);
+        AuditManager::record("replica_added", &canister_id.to_text(), vec![caller_principal], String::new());
+        RegistryResult::Ok(replica)
+    }
+
+    pub fn remove_replica(canister_id: Principal, caller_principal: Principal) -> RegistryResult<()> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if StorageManager::remove_replica(&canister_id).is_none() {
+            return RegistryResult::Err("Replica not found".to_string());
+        }
+        StorageManager::remove_journal_cursor(&Self::journal_consumer(&canister_id));
+        AuditManager::record("replica_removed", &canister_id.to_text(), vec![caller_principal], String::new());
+        RegistryResult::Ok(())
+    }
+
+    // Primary: serving replicas
+
+    pub fn get_replication_checkpoint() -> ReplicationCheckpoint {
+        ReplicationCheckpoint {
+            registry: ic_cdk::id(),
+            latest_seq: StorageManager::latest_company_event_seq(),
+            oldest_seq: StorageManager::first_company_event_seq(),
+            company_count: StorageManager::get_companies_count(),
+            taken_at: time(),
+        }
+    }
+
+    fn ensure_replica(caller_principal: Principal) -> Result<(), String> {
+        if StorageManager::get_replica(&caller_principal).is_some() || ic_cdk::api::is_controller(&caller_principal) {
+            Ok(())
+        } else {
+            Err("Unauthorized: Registered replica access required".to_string())
+        }
+    }
+
+    // Changes after `after_seq`, folded to the latest per company
+    pub fn get_replication_batch(
+        after_seq: u64,
+        limit: Option<u32>,
+        caller_principal: Principal,
+    ) -> RegistryResult<ReplicationBatch> {
+        if let Err(err) = Self::ensure_replica(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let checkpoint = Self::get_replication_checkpoint();
+        // Either the events were pruned, or the journal was rebuilt behind the replica
+        let resync_required = after_seq > checkpoint.latest_seq
+            || (checkpoint.oldest_seq > 0 && after_seq.saturating_add(1) < checkpoint.oldest_seq);
+        if resync_required {
+            return RegistryResult::Ok(ReplicationBatch {
+                last_seq: after_seq,
+                checkpoint,
+                changes: Vec::new(),
+                resync_required,
+            });
+        }
+
+        let limit = limit.unwrap_or(Self::MAX_BATCH_EVENTS).clamp(1, Self::MAX_BATCH_EVENTS) as usize;
+        let events = StorageManager::get_company_events_after(after_seq, limit);
+        let last_seq = events.last().map_or(after_seq, |event| event.seq);
+        let mut changes: Vec<ReplicatedChange> = Vec::new();
+        for event in events {
+            changes.retain(|change| change.company_id != event.company_id);
+            changes.push(ReplicatedChange {
+                seq: event.seq,
+                company: StorageManager::get_company(&event.company_id),
+                company_id: event.company_id,
+            });
+        }
+
+        RegistryResult::Ok(ReplicationBatch {
+            checkpoint,
+            changes,
+            last_seq,
+            resync_required: false,
+        })
+    }
+
+    // Every company in id order after `after`, for a replica's full copy
+    pub fn get_replication_snapshot(
+        after: Option<String>,
+        limit: Option<u32>,
+        caller_principal: Principal,
+    ) -> RegistryResult<ReplicationSnapshotPage> {
+        if let Err(err) = Self::ensure_replica(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let limit = limit.unwrap_or(Self::MAX_SNAPSHOT_PAGE).clamp(1, Self::MAX_SNAPSHOT_PAGE) as usize;
+        let checkpoint = Self::get_replication_checkpoint();
+        let companies = StorageManager::get_companies_after(after.as_deref(), limit);
+        let next_after = if companies.len() == limit {
+            companies.last().map(|company| company.id.clone())
+        } else {
+            None
+        };
+        RegistryResult::Ok(ReplicationSnapshotPage {
+            checkpoint,
+            companies,
+            next_after,
+        })
+    }
+
+    // Called by a replica after each sync with the journal position it has applied
+    pub fn report_replica_progress(applied_seq: u64, caller_principal: Principal) -> RegistryResult<ReplicaInfo> {
+        let mut replica = match StorageManager::get_replica(&caller_principal) {
+            Some(replica) => replica,
+            None => return RegistryResult::Err("Unauthorized: Registered replica access required".to_string()),
+        };
+        let latest_seq = StorageManager::latest_company_event_seq();
+        let applied_seq = applied_seq.min(latest_seq);
+        replica.applied_seq = applied_seq;
+        replica.lag_events = latest_seq - applied_seq;
+        replica.last_reported_at = Some(time());
+        StorageManager::insert_replica(replica.clone());
+        StorageManager::set_journal_cursor(&Self::journal_consumer(&caller_principal), applied_seq);
+        RegistryResult::Ok(replica)
+    }
+
+    // Replica: following the source
+
+    pub async fn sync_replication(caller_principal: Principal) -> RegistryResult<u32> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if !Self::is_replica() {
+            return RegistryResult::Err("This canister is not a read replica".to_string());
+        }
+        match Self::sync().await {
+            Ok(applied) => RegistryResult::Ok(applied),
+            Err(err) => RegistryResult::Err(err),
+        }
+    }
+
+    // Pulls up to MAX_PAGES_PER_SYNC pages and reports back; returns the number of
+    // companies written or removed
+    async fn sync() -> Result<u32, String> {
+        if !StorageManager::try_begin_replication_sync() {
+            return Err("A replication sync is already running".to_string());
+        }
+        let mut result = Self::pull_pages().await;
+        if result.is_ok() {
+            if let Err(err) = Self::report_progress().await {
+                result = Err(err);
+            }
+        }
+        StorageManager::end_replication_sync();
+
+        if let Some(mut state) = StorageManager::get_replication_state() {
+            match &result {
+                Ok(_) => {
+                    state.consecutive_failures = 0;
+                    state.last_error = None;
+                    state.last_synced_at = Some(time());
+                }
+                Err(err) => {
+                    state.consecutive_failures += 1;
+                    state.last_error = Some(err.chars().take(Self::MAX_ERROR_LENGTH).collect());
+                }
+            }
+            StorageManager::set_replication_state(state);
+        }
+        result
+    }
+
+    async fn pull_pages() -> Result<u32, String> {
+        let mut applied = 0;
+        for _ in 0..Self::MAX_PAGES_PER_SYNC {
+            let state = match StorageManager::get_replication_state() {
+                Some(state) => state,
+                None => return Ok(applied),
+            };
+            if state.resyncing {
+                applied += Self::pull_snapshot_page(state).await?;
+                continue;
+            }
+
+            let call: Result<(RegistryResult<ReplicationBatch>,), _> = ic_cdk::call(
+                state.source,
+                "get_replication_batch",
+                (state.applied_seq, Some(Self::MAX_BATCH_EVENTS)),
+            )
+            .await;
+            let batch = match call {
+                Ok((RegistryResult::Ok(batch),)) => batch,
+                Ok((RegistryResult::Err(err),)) => return Err(err),
+                Err((code, message)) => return Err(format!("{:?}: {}", code, message)),
+            };
+            let mut state = Self::current_state(state.source, &batch.checkpoint)?;
+            state.source_latest_seq = batch.checkpoint.latest_seq;
+            if batch.resync_required {
+                state.resyncing = true;
+                state.snapshot_cursor = None;
+                StorageManager::set_replication_state(state);
+                continue;
+            }
+
+            for change in batch.changes {
+                match change.company {
+                    Some(company) => StorageManager::insert_company(change.company_id, company),
+                    None => {
+                        StorageManager::remove_company(&change.company_id);
+                    }
+                }
+                applied += 1;
+            }
+            state.applied_seq = batch.last_seq;
+            let caught_up = state.applied_seq >= batch.checkpoint.latest_seq;
+            StorageManager::set_replication_state(state);
+            if caught_up {
+                break;
+            }
+        }
+        Ok(applied)
+    }
+
+    // Copies one page of the source's companies, dropping local ones the page skipped over
+    async fn pull_snapshot_page(state: ReplicationState) -> Result<u32, String> {
+        let call: Result<(RegistryResult<ReplicationSnapshotPage>,), _> = ic_cdk::call(
+            state.source,
+            "get_replication_snapshot",
+            (state.snapshot_cursor.clone(), Some(Self::MAX_SNAPSHOT_PAGE)),
+        )
+        .await;
+        let page = match call {
+            Ok((RegistryResult::Ok(page),)) => page,
+            Ok((RegistryResult::Err(err),)) => return Err(err),
+            Err((code, message)) => return Err(format!("{:?}: {}", code, message)),
+        };
+        let mut state = Self::current_state(state.source, &page.checkpoint)?;
+        if state.snapshot_cursor.is_none() {
+            state.snapshot_seq = page.checkpoint.latest_seq;
+        }
+        state.source_latest_seq = page.checkpoint.latest_seq;
+
+        let listed: BTreeSet<&str> = page.companies.iter().map(|company| company.id.as_str()).collect();
+        let up_to = page.next_after.as_deref();
+        let in_range = match (state.snapshot_cursor.as_deref(), up_to) {
+            (Some(after), Some(up_to)) => after < up_to,
+            _ => true,
+        };
+        let mut applied = 0;
+        if in_range {
+            for company_id in StorageManager::get_company_ids_between(state.snapshot_cursor.as_deref(), up_to) {
+                if !listed.contains(company_id.as_str()) {
+                    StorageManager::remove_company(&company_id);
+                    applied += 1;
+                }
+            }
+        }
+        for company in page.companies {
+            StorageManager::insert_company(company.id.clone(), company);
+            applied += 1;
+        }
+
+        match page.next_after {
+            Some(after) => state.snapshot_cursor = Some(after),
+            None => {
+                // Changes made while the copy ran are picked up from the journal
+                state.resyncing = false;
+                state.snapshot_cursor = None;
+                state.applied_seq = state.snapshot_seq;
+            }
+        }
+        StorageManager::set_replication_state(state);
+        Ok(applied)
+    }
+
+    // Re-read after the call: an upgrade may have pointed the replica elsewhere meanwhile
+    fn current_state(source: Principal, checkpoint: &ReplicationCheckpoint) -> Result<ReplicationState, String> {
+        if checkpoint.registry != source {
+            return Err("Source answered for a different registry".to_string());
+        }
+        match StorageManager::get_replication_state() {
+            Some(state) if state.source == source => Ok(state),
+            _ => Err("Replication source changed during sync".to_string()),
+        }
+    }
+
+    // Lets the primary track lag and keep the journal events this replica still needs
+    async fn report_progress() -> Result<(), String> {
+        let state = match StorageManager::get_replication_state() {
+            Some(state) => state,
+            None => return Ok(()),
+        };
+        let position = if state.resyncing { state.snapshot_seq } else { state.applied_seq };
+        let call: Result<(RegistryResult<ReplicaInfo>,), _> =
+            ic_cdk::call(state.source, "report_replica_progress", (position,)).await;
+        match call {
+            Ok((RegistryResult::Ok(_),)) => Ok(()),
+            Ok((RegistryResult::Err(err),)) => Err(err),
+            Err((code, message)) => Err(format!("{:?}: {}", code, message)),
+        }
+    }
+
+    // Queries
+
+    pub fn get_replication_status() -> ReplicationStatus {
+        match StorageManager::get_replication_state() {
+            Some(state) => ReplicationStatus::Replica {
+                lag_events: state.source_latest_seq.saturating_sub(state.applied_seq),
+                state,
+            },
+            None => {
+                let mut replicas = StorageManager::get_all_replicas();
+                replicas.sort_by_key(|replica| replica.added_at);
+                ReplicationStatus::Primary {
+                    latest_seq: StorageManager::latest_company_event_seq(),
+                    replicas,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use crate::admins::AdminManager;
+use crate::audit::AuditManager;
+use crate::community::CommunityValidationManager;
+use crate::concerns::ConcernManager;
+use crate::escrow::ReputationEscrowManager;
+use crate::grants::GrantManager;
+use crate::storage::StorageManager;
+use crate::types::{
+    Company, CommunityPage, RegistryResult, ReputationEvent, ReputationEventKind, ReputationRecomputation,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+use std::collections::BTreeMap;
+
+// Reputation journal. Community actions that move the reputation score are appended here
+// as they happen, so the score can be rebuilt from history when an in-place update went
+// wrong. The verification and grant parts of the score are not journaled: they are
+// already derived from proofs and attestations, which are their own source of truth.
+
+// Everything the reputation score is computed from
+#[derive(Default)]
+pub struct ReputationInputs {
+    pub endorsers: BTreeMap<String, u32>, // Endorser company -> points
+    pub testimonials: BTreeMap<String, bool>,
+    pub vouches: BTreeMap<Principal, u32>,
+    pub reputation_staked: u64,
+    pub verification_score: u32,
+    pub grant_points: u32,
+    pub concern_penalty: u32,
+    pub escrowed_permille: u32, // Share of the community points still held in escrow
+}
+
+impl ReputationInputs {
+    pub fn from_company(company: &Company) -> ReputationInputs {
+        let community = &company.community_validation;
+        ReputationInputs {
+            endorsers: community
+                .peer_endorsements
+                .iter()
+                .map(|e| (e.endorser_company_id.clone(), Self::endorsement_points(e.weight)))
+                .collect(),
+            testimonials: community
+                .employee_testimonials
+                .iter()
+                .map(|t| (t.author_name.clone(), t.verified))
+                .collect(),
+            vouches: community
+                .community_vouches
+                .iter()
+                .map(|v| (v.voucher_principal, v.weight))
+                .collect(),
+            reputation_staked: community.reputation_staked,
+            verification_score: company.verification_score,
+            grant_points: GrantManager::reputation_points(&company.id),
+            concern_penalty: ConcernManager::reputation_penalty(&company.id),
+            escrowed_permille: ReputationEscrowManager::escrowed_permille(company, time()),
+        }
+    }
+
+    fn apply(&mut self, kind: &ReputationEventKind) {
+        match kind {
+            ReputationEventKind::Baseline {
+                endorser_company_ids,
+                endorser_weights,
+                testimonials,
+                vouches,
+                reputation_staked,
+            } => {
+                self.endorsers = match endorser_weights {
+                    Some(weights) => weights.iter().cloned().collect(),
+                    None => endorser_company_ids
+                        .iter()
+                        .map(|id| (id.clone(), Self::endorsement_points(None)))
+                        .collect(),
+                };
+                self.testimonials = testimonials.iter().cloned().collect();
+                self.vouches = vouches.iter().cloned().collect();
+                self.reputation_staked = *reputation_staked;
+            }
+            ReputationEventKind::EndorsementAdded { endorser_company_id, weight } => {
+                self.endorsers.insert(endorser_company_id.clone(), Self::endorsement_points(*weight));
+            }
+            ReputationEventKind::EndorsementRemoved { endorser_company_id } => {
+                self.endorsers.remove(endorser_company_id);
+            }
+            ReputationEventKind::TestimonialAdded { author_name, verified }
+            | ReputationEventKind::TestimonialVerificationChanged { author_name, verified } => {
+                self.testimonials.insert(author_name.clone(), *verified);
+            }
+            ReputationEventKind::TestimonialRemoved { author_name } => {
+                self.testimonials.remove(author_name);
+            }
+            ReputationEventKind::VouchAdded { voucher_principal, weight } => {
+                self.vouches.insert(*voucher_principal, *weight);
+            }
+            ReputationEventKind::VouchRemoved { voucher_principal } => {
+                self.vouches.remove(voucher_principal);
+            }
+            ReputationEventKind::StakeChanged { reputation_staked } => {
+                self.reputation_staked = *reputation_staked;
+            }
+            ReputationEventKind::ProofRemoved { .. } | ReputationEventKind::ProofRestored { .. } => {}
+        }
+    }
+
+    // Largest stake a company can hold; the log-scale bonus stops growing here too
+    pub const MAX_REPUTATION_STAKE: u64 = 1_000_000;
+
+    // What every endorsement was worth before weighting
+    const LEGACY_ENDORSEMENT_POINTS: u32 = 10;
+
+    fn endorsement_points(weight: Option<u32>) -> u32 {
+        weight.unwrap_or(Self::LEGACY_ENDORSEMENT_POINTS)
+    }
+
+    // Endorsements, testimonials and vouches; the part of the score that can be escrowed
+    pub fn community_points(&self) -> u32 {
+        let mut points = 0u32;
+
+        // Endorsements (weighted by the endorser's standing when they endorsed)
+        points = points.saturating_add(
+            self.endorsers
+                .values()
+                .fold(0u32, |total, endorsement| total.saturating_add(*endorsement)),
+        );
+
+        // Verified testimonials (medium weight), unverified ones (low weight)
+        let verified_testimonials = self.testimonials.values().filter(|verified| **verified).count() as u32;
+        let unverified_testimonials = (self.testimonials.len() as u32).saturating_sub(verified_testimonials);
+        points = points.saturating_add(verified_testimonials.saturating_mul(5));
+        points = points.saturating_add(unverified_testimonials.saturating_mul(2));
+
+        // Community vouches (weighted by voucher reputation)
+        points = points.saturating_add(
+            self.vouches
+                .values()
+                .fold(0u32, |total, weight| total.saturating_add(weight.saturating_mul(3))),
+        );
+
+        points
+    }
+
+    pub fn escrowed_points(&self) -> u32 {
+        (u64::from(self.community_points()) * u64::from(self.escrowed_permille.min(1000)) / 1000) as u32
+    }
+
+    // Saturating throughout, so no combination of inputs can overflow
+    pub fn score(&self) -> u32 {
+        let mut score = 0u32;
+
+        // Base score from verification
+        score = score.saturating_add(self.verification_score / 4);
+
+        // Community reputation released from escrow so far
+        score = score.saturating_add(self.community_points() - self.escrowed_points());
+
+        // Reputation staking bonus (logarithmic scale); stakes recorded before the cap
+        // count as the cap
+        if self.reputation_staked > 0 {
+            let stake = self.reputation_staked.min(Self::MAX_REPUTATION_STAKE);
+            score = score.saturating_add((stake as f64).log10().ceil() as u32 * 2);
+        }
+
+        // Grants attested by verified foundations
+        score = score.saturating_add(self.grant_points);
+
+        // Corroborated community concerns
+        score.saturating_sub(self.concern_penalty)
+    }
+}
+
+pub struct ReputationJournal;
+
+impl ReputationJournal {
+    // Call after the change has been written to the company
+    pub fn record(company_id: &str, kind: ReputationEventKind, actor: Option<Principal>) {
+        if !StorageManager::has_reputation_events(company_id) {
+            if let Some(company) = StorageManager::get_company(company_id) {
+                Self::append(company_id, Self::baseline(&company), None);
+            }
+        }
+        Self::append(company_id, kind, actor);
+    }
+
+    // Replays the journal and writes the resulting score. Companies without events have
+    // never changed since the journal existed, so their live state is the baseline.
+    pub fn recompute_reputation(
+        company_id: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<ReputationRecomputation> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        let events = StorageManager::get_reputation_events(&company_id);
+        let mut inputs = ReputationInputs::from_company(&company);
+        if !events.is_empty() {
+            inputs.endorsers.clear();
+            inputs.testimonials.clear();
+            inputs.vouches.clear();
+            inputs.reputation_staked = 0;
+            for event in &events {
+                inputs.apply(&event.kind);
+            }
+        }
+        let recomputed_score = inputs.score();
+
+        StorageManager::update_company(&company_id, |company| {
+            company.community_validation.reputation_escrow = ReputationEscrowManager::pending_escrow(company, &inputs);
+            CommunityValidationManager::set_reputation_score(company, recomputed_score);
+        });
+        let previous_score = company.community_validation.reputation_score;
+        AuditManager::record(
+            "reputation_recomputed",
+            &company_id,
+            vec![caller_principal],
+            format!("{} -> {} from {} events", previous_score, recomputed_score, events.len()),
+        );
+        RegistryResult::Ok(ReputationRecomputation {
+            company_id,
+            previous_score,
+            recomputed_score,
+            events_replayed: events.len() as u32,
+        })
+    }
+
+    // Newest first
+    pub fn get_reputation_events(
+        company_id: String,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> CommunityPage<ReputationEvent> {
+        let mut events = StorageManager::get_reputation_events(&company_id);
+        events.reverse();
+        CommunityValidationManager::paginate(events, offset, limit)
+    }
+
+    fn baseline(company: &Company) -> ReputationEventKind {
+        let inputs = ReputationInputs::from_company(company);
+        ReputationEventKind::Baseline {
+            endorser_company_ids: inputs.endorsers.keys().cloned().collect(),
+            endorser_weights: Some(inputs.endorsers.into_iter().collect()),
+            testimonials: inputs.testimonials.into_iter().collect(),
+            vouches: inputs.vouches.into_iter().collect(),
+            reputation_staked: inputs.reputation_staked,
+        }
+    }
+
+    fn append(company_id: &str, kind: ReputationEventKind, actor: Option<Principal>) {
+        StorageManager::append_reputation_event(ReputationEvent {
+            seq: 0, // Assigned by storage
+            company_id: company_id.to_string(),
+            kind,
+            actor,
+            timestamp: time(),
+        });
+    }
+}
@@ -0,0 +1,268 @@
+use crate::admins::AdminManager;
+use crate::ownership::OwnershipManager;
+use crate::sanitize::{self, SafeUrl};
+use crate::storage::StorageManager;
+use crate::types::{
+    Company, CompanyStatus, NameMatchRule, ProofStatus, RegistryResult, ReserveNameRequest, ReservedName,
+    VerificationType,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Reserved name protection: well-known names can only be registered by their legitimate owner
+
+pub struct ReservedNameManager;
+
+impl ReservedNameManager {
+    const MAX_RESERVED_NAME_LENGTH: usize = 100;
+    const MAX_REQUIRED_DOMAINS: usize = 10;
+    const MAX_REASON_LENGTH: usize = 500;
+
+    // Case, whitespace and punctuation insensitive form used for keys and matching
+    pub fn normalize_name(name: &str) -> String {
+        name.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(|c| c.to_lowercase())
+            .collect()
+    }
+
+    // Punycode form, so an internationalized domain matches however it was written
+    fn normalize_domain(domain: &str) -> String {
+        let domain = domain.trim().to_lowercase();
+        let domain = domain.split(':').next().unwrap_or("");
+        let domain = sanitize::domain_to_ascii(domain).unwrap_or_else(|_| domain.to_string());
+        domain.strip_prefix("www.").unwrap_or(&domain).to_string()
+    }
+
+    // Normalized and Contains rules also catch lookalikes built from confusable characters
+    pub fn matches(reserved: &ReservedName, candidate: &str) -> bool {
+        let reserved_key = sanitize::confusable_key(&reserved.name);
+        let candidate_key = sanitize::confusable_key(candidate);
+
+        match reserved.match_rule {
+            NameMatchRule::Exact => reserved.name.trim().to_lowercase() == candidate.trim().to_lowercase(),
+            NameMatchRule::Normalized => {
+                Self::normalize_name(&reserved.name) == Self::normalize_name(candidate) || reserved_key == candidate_key
+            }
+            NameMatchRule::Contains => !reserved_key.is_empty() && candidate_key.contains(&reserved_key),
+        }
+    }
+
+    // A name that differs from an established company's only by confusable characters is
+    // almost always an impersonation attempt; an identical name is left to moderation
+    fn find_lookalike_company(name: &str, company_id: Option<&str>) -> Option<Company> {
+        let key = sanitize::confusable_key(name);
+        let normalized = Self::normalize_name(name);
+        if key.is_empty() {
+            return None;
+        }
+        StorageManager::get_all_companies().into_iter().find(|company| {
+            Some(company.id.as_str()) != company_id
+                && matches!(company.status, CompanyStatus::Verified | CompanyStatus::Trusted)
+                && sanitize::confusable_key(&company.basic_info.name) == key
+                && Self::normalize_name(&company.basic_info.name) != normalized
+        })
+    }
+
+    // A website matches if its domain is a required domain or one of its subdomains
+    fn website_matches_domains(website: &str, required_domains: &[String]) -> bool {
+        let url = match SafeUrl::parse_web(website, "Website") {
+            Ok(url) => url,
+            Err(_) => return false,
+        };
+        let domain = url.site_host();
+
+        required_domains.iter().any(|required| {
+            let required = Self::normalize_domain(required);
+            domain == required || domain.ends_with(&format!(".{}", required))
+        })
+    }
+
+    // The owner-typed website proves nothing; only an active domain proof on a required
+    // domain shows the company controls it
+    fn has_verified_domain(company: &Company, required_domains: &[String]) -> bool {
+        company.web3_identity.domain_verified
+            && company.web3_identity.verification_proofs.iter().any(|proof| {
+                matches!(proof.verification_type, VerificationType::Domain)
+                    && proof.status == ProofStatus::Active
+                    && Self::website_matches_domains(&proof.proof_url, required_domains)
+            })
+    }
+
+    pub fn find_matching_reservations(name: &str) -> Vec<ReservedName> {
+        StorageManager::get_all_reserved_names()
+            .into_iter()
+            .filter(|reserved| Self::matches(reserved, name))
+            .collect()
+    }
+
+    // Called on registration and profile updates. A new company can't have verified a
+    // domain yet, so the legitimate owner registers under another name, verifies the
+    // domain and then renames.
+    pub fn check_registration(name: &str, website: &str, company: Option<&Company>) -> Result<(), String> {
+        let company_id = company.map(|company| company.id.as_str());
+        for reserved in Self::find_matching_reservations(name) {
+            if let Some(claimed_by) = &reserved.claimed_by {
+                if Some(claimed_by.as_str()) != company_id {
+                    return Err(format!(
+                        "Name '{}' is reserved and has been claimed by its owner",
+                        reserved.name
+                    ));
+                }
+                continue;
+            }
+
+            if !Self::website_matches_domains(website, &reserved.required_domains) {
+                return Err(format!(
+                    "Name '{}' is reserved: registration requires a website on {}",
+                    reserved.name,
+                    reserved.required_domains.join(", ")
+                ));
+            }
+            if !company.is_some_and(|company| Self::has_verified_domain(company, &reserved.required_domains)) {
+                return Err(format!(
+                    "Name '{}' is reserved: verify ownership of {} before taking this name",
+                    reserved.name,
+                    reserved.required_domains.join(", ")
+                ));
+            }
+        }
+
+        if let Some(company) = Self::find_lookalike_company(name, company_id) {
+            return Err(format!(
+                "Name is confusingly similar to registered company '{}'",
+                company.basic_info.name
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Admin operations
+    pub fn reserve_name(request: ReserveNameRequest, caller_principal: Principal) -> RegistryResult<ReservedName> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        let name = request.name.trim().to_string();
+        if name.is_empty() || sanitize::grapheme_len(&name) > Self::MAX_RESERVED_NAME_LENGTH {
+            return RegistryResult::Err(format!(
+                "Reserved name must be 1-{} characters",
+                Self::MAX_RESERVED_NAME_LENGTH
+            ));
+        }
+        if Self::normalize_name(&name).is_empty() {
+            return RegistryResult::Err("Reserved name must contain letters or digits".to_string());
+        }
+        if request.required_domains.is_empty() || request.required_domains.len() > Self::MAX_REQUIRED_DOMAINS {
+            return RegistryResult::Err(format!(
+                "Between 1 and {} required domains must be provided",
+                Self::MAX_REQUIRED_DOMAINS
+            ));
+        }
+        for domain in &request.required_domains {
+            let domain = Self::normalize_domain(domain);
+            if domain.is_empty() || !domain.contains('.') || domain.contains('/') || domain.len() > 253 {
+                return RegistryResult::Err(format!("Invalid required domain '{}'", domain));
+            }
+        }
+        if let Some(reason) = &request.reason {
+            if sanitize::grapheme_len(reason) > Self::MAX_REASON_LENGTH {
+                return RegistryResult::Err("Reason exceeds maximum length of 500 characters".to_string());
+            }
+        }
+
+        let key = Self::normalize_name(&name);
+        // Re-reserving keeps an existing claim intact
+        let existing = StorageManager::get_reserved_name(&key);
+        let reserved = ReservedName {
+            name,
+            match_rule: request.match_rule,
+            required_domains: request
+                .required_domains
+                .iter()
+                .map(|domain| Self::normalize_domain(domain))
+                .collect(),
+            reason: request.reason,
+            reserved_by: caller_principal,
+            reserved_at: time(),
+            claimed_by: existing.as_ref().and_then(|r| r.claimed_by.clone()),
+            claimed_at: existing.and_then(|r| r.claimed_at),
+        };
+
+        StorageManager::insert_reserved_name(key, reserved.clone());
+        RegistryResult::Ok(reserved)
+    }
+
+    pub fn release_reserved_name(name: String, caller_principal: Principal) -> RegistryResult<()> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        match StorageManager::remove_reserved_name(&Self::normalize_name(&name)) {
+            Some(_) => RegistryResult::Ok(()),
+            None => RegistryResult::Err("Reserved name not found".to_string()),
+        }
+    }
+
+    pub fn list_reserved_names() -> Vec<ReservedName> {
+        StorageManager::get_all_reserved_names()
+    }
+
+    // Claim flow: the legitimate owner proves control of a required domain
+    pub fn claim_reserved_name(
+        company_id: String,
+        name: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<ReservedName> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        if !OwnershipManager::is_owner(&company, caller_principal) {
+            return RegistryResult::Err(
+                "Unauthorized: Only company creator can claim a reserved name".to_string(),
+            );
+        }
+
+        let key = Self::normalize_name(&name);
+        let mut reserved = match StorageManager::get_reserved_name(&key) {
+            Some(reserved) => reserved,
+            None => return RegistryResult::Err("Reserved name not found".to_string()),
+        };
+
+        if let Some(claimed_by) = &reserved.claimed_by {
+            if claimed_by != &company_id {
+                return RegistryResult::Err("Reserved name has already been claimed".to_string());
+            }
+            return RegistryResult::Ok(reserved);
+        }
+
+        if !Self::matches(&reserved, &company.basic_info.name) {
+            return RegistryResult::Err(format!(
+                "Company name does not match reserved name '{}'",
+                reserved.name
+            ));
+        }
+
+        if !Self::website_matches_domains(&company.basic_info.website, &reserved.required_domains) {
+            return RegistryResult::Err(format!(
+                "Company website must be on one of: {}",
+                reserved.required_domains.join(", ")
+            ));
+        }
+
+        if !Self::has_verified_domain(&company, &reserved.required_domains) {
+            return RegistryResult::Err(
+                "Domain ownership must be verified before claiming a reserved name".to_string(),
+            );
+        }
+
+        reserved.claimed_by = Some(company_id);
+        reserved.claimed_at = Some(time());
+        StorageManager::insert_reserved_name(key, reserved.clone());
+
+        RegistryResult::Ok(reserved)
+    }
+}
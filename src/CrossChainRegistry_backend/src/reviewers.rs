@@ -0,0 +1,539 @@
+use crate::feature_flags::{self, FeatureFlagManager};
+use crate::ledger::{LedgerClient, LedgerError};
+use crate::moderation::ModerationManager;
+use crate::ownership::OwnershipManager;
+use crate::storage::StorageManager;
+use crate::types::{
+    ModerationTaskKind, ModerationTaskStatus, RegistryResult, ReviewCase, ReviewCaseStatus,
+    ReviewVote, Reviewer, ReviewerPoolConfig,
+};
+use candid::{Nat, Principal};
+use ic_cdk::api::management_canister::main::raw_rand;
+use ic_cdk::api::time;
+
+// Community review pool: reviewers stake ICRC tokens to vote on dispute tasks. Once a
+// case is tallied, reviewers on the losing side lose `slash_percent` of their stake,
+// which is shared among the winning side as claimable rewards.
+//
+// Each case gets a committee drawn from the eligible pool with raw_rand, so reviewers
+// cannot pick the cases they vote on. The seed and pool size are kept on the case so
+// the draw can be audited.
+
+pub struct ReviewerPoolManager;
+
+impl ReviewerPoolManager {
+    const DEFAULT_TRANSFER_FEE: u64 = 10_000;
+    const DEFAULT_MIN_STAKE: u64 = 100_000_000; // 1 token at 8 decimals
+    const DEFAULT_SLASH_PERCENT: u32 = 10;
+    const DEFAULT_QUORUM: u32 = 3;
+    const DEFAULT_VOTING_PERIOD_HOURS: u32 = 72;
+    const MAX_VOTING_PERIOD_HOURS: u32 = 30 * 24;
+    const DEFAULT_COMMITTEE_SIZE: u32 = 5;
+    const MAX_COMMITTEE_SIZE: u32 = 51;
+
+    // Configuration
+
+    pub fn get_reviewer_pool_config() -> ReviewerPoolConfig {
+        StorageManager::get_reviewer_pool_config().unwrap_or(ReviewerPoolConfig {
+            ledger_canister_id: None,
+            transfer_fee: Self::DEFAULT_TRANSFER_FEE,
+            min_stake: Self::DEFAULT_MIN_STAKE,
+            slash_percent: Self::DEFAULT_SLASH_PERCENT,
+            quorum: Self::DEFAULT_QUORUM,
+            voting_period_hours: Self::DEFAULT_VOTING_PERIOD_HOURS,
+            committee_size: None,
+        })
+    }
+
+    // Changes go through the admin timelock (TimelockAction::ReviewerPoolConfig)
+    pub fn validate_reviewer_pool_config(config: &ReviewerPoolConfig) -> Result<(), String> {
+        if config.min_stake <= config.transfer_fee {
+            return Err("Minimum stake must exceed the ledger transfer fee".to_string());
+        }
+        if config.slash_percent > 100 {
+            return Err("Slash percent must be 0-100".to_string());
+        }
+        if config.quorum == 0 {
+            return Err("Quorum must be at least 1".to_string());
+        }
+        if config.voting_period_hours == 0 || config.voting_period_hours > Self::MAX_VOTING_PERIOD_HOURS {
+            return Err(format!(
+                "Voting period must be 1-{} hours",
+                Self::MAX_VOTING_PERIOD_HOURS
+            ));
+        }
+
+        let committee_size = config.committee_size.unwrap_or(Self::DEFAULT_COMMITTEE_SIZE);
+        if committee_size < config.quorum || committee_size > Self::MAX_COMMITTEE_SIZE {
+            return Err(format!(
+                "Committee size must be between the quorum and {}",
+                Self::MAX_COMMITTEE_SIZE
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn ledger(config: &ReviewerPoolConfig) -> Result<Principal, String> {
+        config
+            .ledger_canister_id
+            .ok_or_else(|| "Reviewer staking is not configured".to_string())
+    }
+
+    // Staking
+
+    // Requires a prior icrc2_approve of `amount` plus the ledger fee for this canister.
+    // Existing reviewers may call this again to top up their stake. A bond whose ledger
+    // outcome is unknown stays recorded, and joining again with the same amount resends it
+    // unchanged so the ledger deduplicates it.
+    pub async fn join_reviewer_pool(amount: u64, caller_principal: Principal) -> RegistryResult<Reviewer> {
+        if caller_principal == Principal::anonymous() {
+            return RegistryResult::Err("Anonymous principals cannot join the reviewer pool".to_string());
+        }
+        if let Err(err) = FeatureFlagManager::ensure_enabled(feature_flags::REVIEWER_STAKING, caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        let config = Self::get_reviewer_pool_config();
+        let ledger = match Self::ledger(&config) {
+            Ok(ledger) => ledger,
+            Err(err) => return RegistryResult::Err(err),
+        };
+
+        let existing = StorageManager::get_reviewer(&caller_principal);
+        let intake = match existing.as_ref().and_then(|r| r.pending_stake.clone()) {
+            Some(intake) => {
+                if intake.amount != amount {
+                    return RegistryResult::Err(format!(
+                        "An earlier bond of {} is not confirmed by the ledger yet; join again with {} to retry it",
+                        intake.amount, intake.amount
+                    ));
+                }
+                intake
+            }
+            None => {
+                let current_stake = existing.as_ref().map_or(0, |r| r.staked);
+                if amount == 0 || current_stake.saturating_add(amount) < config.min_stake {
+                    return RegistryResult::Err(format!("Stake must total at least {}", config.min_stake));
+                }
+
+                // Recorded before the call so a lost reply can be retried with the same key.
+                // A placeholder record has no stake, so it is never drawn for a committee.
+                let intake = LedgerClient::pending_intake("reviewer_stake", caller_principal, amount, time());
+                let mut reviewer = existing.unwrap_or(Reviewer {
+                    principal: caller_principal,
+                    staked: 0,
+                    pending_rewards: 0,
+                    total_rewards: 0,
+                    total_slashed: 0,
+                    accurate_votes: 0,
+                    inaccurate_votes: 0,
+                    joined_at: time(),
+                    pending_claim: None,
+                    pending_exit: None,
+                    pending_stake: None,
+                });
+                reviewer.pending_stake = Some(intake.clone());
+                StorageManager::insert_reviewer(reviewer);
+                intake
+            }
+        };
+
+        let result = LedgerClient::pull(ledger, caller_principal, &intake).await;
+        if let Err(LedgerError::Unknown(err)) = result {
+            return RegistryResult::Err(format!("{}; join again to retry the same transfer", err));
+        }
+
+        // Re-read after the await; only the call that clears the record credits the bond
+        let mut reviewer = match StorageManager::get_reviewer(&caller_principal) {
+            Some(reviewer)
+                if reviewer.pending_stake.as_ref().is_some_and(|p| p.created_at_time == intake.created_at_time) =>
+            {
+                reviewer
+            }
+            _ => return RegistryResult::Err("This bond was already settled by another call".to_string()),
+        };
+        reviewer.pending_stake = None;
+        if let Err(err) = result {
+            Self::store_or_drop(reviewer);
+            return RegistryResult::Err(err.to_string());
+        }
+        reviewer.staked = reviewer.staked.saturating_add(intake.amount);
+        StorageManager::insert_reviewer(reviewer.clone());
+
+        RegistryResult::Ok(reviewer)
+    }
+
+    // Returns stake plus unclaimed rewards, minus the ledger fee. If the ledger outcome is
+    // unknown the exit stays recorded, and leaving again resends the same transfer.
+    pub async fn leave_reviewer_pool(caller_principal: Principal) -> RegistryResult<Nat> {
+        let config = Self::get_reviewer_pool_config();
+        let ledger = match Self::ledger(&config) {
+            Ok(ledger) => ledger,
+            Err(err) => return RegistryResult::Err(err),
+        };
+        let mut reviewer = match StorageManager::get_reviewer(&caller_principal) {
+            Some(reviewer) => reviewer,
+            None => return RegistryResult::Err("Not a member of the reviewer pool".to_string()),
+        };
+
+        let payout = match reviewer.pending_exit.clone() {
+            Some(payout) => payout,
+            None => {
+                // A committee seat counts even before the vote: leaving would let a drawn
+                // reviewer dodge a case (and its slashing) and shrink the committee below quorum
+                let on_open_case = StorageManager::get_all_review_cases().iter().any(|case| {
+                    case.status == ReviewCaseStatus::Open
+                        && (case.votes.iter().any(|v| v.reviewer == caller_principal)
+                            || case.committee.as_ref().is_some_and(|committee| committee.contains(&caller_principal)))
+                });
+                if on_open_case {
+                    return RegistryResult::Err("Cannot leave while serving on the committee of an open case".to_string());
+                }
+
+                let total = reviewer.staked.saturating_add(reviewer.pending_rewards);
+                reviewer.staked = 0;
+                reviewer.pending_rewards = 0;
+                if total <= config.transfer_fee {
+                    Self::store_or_drop(reviewer);
+                    return RegistryResult::Ok(Nat::from(0u64));
+                }
+
+                // Debit before the call so a concurrent exit cannot pay out twice
+                let payout = LedgerClient::pending_payout("reviewer_exit", caller_principal, total, config.transfer_fee, time());
+                reviewer.pending_exit = Some(payout.clone());
+                StorageManager::insert_reviewer(reviewer);
+                payout
+            }
+        };
+
+        let result = LedgerClient::pay(ledger, caller_principal, &payout).await;
+        if let Err(LedgerError::Unknown(err)) = result {
+            return RegistryResult::Err(format!("{}; leave again to retry the same transfer", err));
+        }
+
+        // Settled: on a rejection the amount goes back to the stake, so leaving again
+        // returns it in full
+        if let Some(mut reviewer) = StorageManager::get_reviewer(&caller_principal) {
+            if reviewer.pending_exit.as_ref().is_some_and(|p| p.created_at_time == payout.created_at_time) {
+                reviewer.pending_exit = None;
+                if result.is_err() {
+                    reviewer.staked = reviewer.staked.saturating_add(payout.amount);
+                }
+                Self::store_or_drop(reviewer);
+            }
+        }
+
+        match result {
+            Ok(block_index) => RegistryResult::Ok(block_index),
+            Err(err) => RegistryResult::Err(err.to_string()),
+        }
+    }
+
+    // A claim whose ledger outcome is unknown stays recorded; claiming again resends it
+    // unchanged so the ledger deduplicates it
+    pub async fn claim_reviewer_rewards(caller_principal: Principal) -> RegistryResult<Nat> {
+        let config = Self::get_reviewer_pool_config();
+        let ledger = match Self::ledger(&config) {
+            Ok(ledger) => ledger,
+            Err(err) => return RegistryResult::Err(err),
+        };
+        let mut reviewer = match StorageManager::get_reviewer(&caller_principal) {
+            Some(reviewer) => reviewer,
+            None => return RegistryResult::Err("Not a member of the reviewer pool".to_string()),
+        };
+
+        let payout = match reviewer.pending_claim.clone() {
+            Some(payout) => payout,
+            None => {
+                let pending = reviewer.pending_rewards;
+                if pending <= config.transfer_fee {
+                    return RegistryResult::Err("Pending rewards do not cover the ledger fee".to_string());
+                }
+
+                // Debit before the call so a concurrent claim cannot pay out twice
+                let payout = LedgerClient::pending_payout("reviewer_rewards", caller_principal, pending, config.transfer_fee, time());
+                reviewer.pending_rewards = 0;
+                reviewer.pending_claim = Some(payout.clone());
+                StorageManager::insert_reviewer(reviewer);
+                payout
+            }
+        };
+
+        let result = LedgerClient::pay(ledger, caller_principal, &payout).await;
+        if let Err(LedgerError::Unknown(err)) = result {
+            return RegistryResult::Err(format!("{}; claim again to retry the same transfer", err));
+        }
+
+        if let Some(mut reviewer) = StorageManager::get_reviewer(&caller_principal) {
+            if reviewer.pending_claim.as_ref().is_some_and(|p| p.created_at_time == payout.created_at_time) {
+                reviewer.pending_claim = None;
+                match &result {
+                    Ok(_) => reviewer.total_rewards = reviewer.total_rewards.saturating_add(payout.amount),
+                    Err(_) => reviewer.pending_rewards = reviewer.pending_rewards.saturating_add(payout.amount),
+                }
+                Self::store_or_drop(reviewer);
+            }
+        }
+
+        match result {
+            Ok(block_index) => RegistryResult::Ok(block_index),
+            Err(err) => RegistryResult::Err(err.to_string()),
+        }
+    }
+
+    // A reviewer who left keeps a record only while a payout is unresolved
+    fn store_or_drop(reviewer: Reviewer) {
+        if reviewer.staked == 0
+            && reviewer.pending_rewards == 0
+            && reviewer.pending_claim.is_none()
+            && reviewer.pending_exit.is_none()
+            && reviewer.pending_stake.is_none()
+        {
+            StorageManager::remove_reviewer(&reviewer.principal);
+        } else {
+            StorageManager::insert_reviewer(reviewer);
+        }
+    }
+
+    // Review cases
+
+    pub async fn open_review_case(task_id: String, caller_principal: Principal) -> RegistryResult<ReviewCase> {
+        if let Err(err) = ModerationManager::ensure_moderator(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        let task = match StorageManager::get_moderation_task(&task_id) {
+            Some(task) => task,
+            None => return RegistryResult::Err("Moderation task not found".to_string()),
+        };
+        if task.kind != ModerationTaskKind::Dispute {
+            return RegistryResult::Err("Only dispute tasks can go to community review".to_string());
+        }
+        if task.status == ModerationTaskStatus::Closed {
+            return RegistryResult::Err("Moderation task is already closed".to_string());
+        }
+        if let Err(err) = ModerationManager::ensure_can_finalize(&task, caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if StorageManager::get_review_case(&task_id).is_some() {
+            return RegistryResult::Err("A review case already exists for this task".to_string());
+        }
+
+        let config = Self::get_reviewer_pool_config();
+        let company = StorageManager::get_company(&task.company_id);
+
+        // Sorted so the draw is reproducible from the recorded seed
+        let mut pool: Vec<Principal> = StorageManager::get_all_reviewers()
+            .into_iter()
+            .filter(|r| {
+                r.staked >= config.min_stake
+                    && !company.as_ref().is_some_and(|c| OwnershipManager::is_owner(c, r.principal))
+            })
+            .map(|r| r.principal)
+            .collect();
+        pool.sort_by_key(|p| p.to_text());
+        if (pool.len() as u32) < config.quorum {
+            return RegistryResult::Err("Not enough eligible reviewers to form a committee".to_string());
+        }
+
+        let seed = match raw_rand().await {
+            Ok((bytes,)) => bytes,
+            Err((code, message)) => {
+                return RegistryResult::Err(format!("raw_rand failed ({:?}): {}", code, message))
+            }
+        };
+        if StorageManager::get_review_case(&task_id).is_some() {
+            return RegistryResult::Err("A review case already exists for this task".to_string());
+        }
+
+        let committee_size = config.committee_size.unwrap_or(Self::DEFAULT_COMMITTEE_SIZE) as usize;
+        let committee = Self::draw_committee(&pool, &seed, committee_size);
+
+        let now = time();
+        let case = ReviewCase {
+            task_id: task.id,
+            company_id: task.company_id,
+            opened_by: caller_principal,
+            opened_at: now,
+            closes_at: now + config.voting_period_hours as u64 * 60 * 60 * 1_000_000_000,
+            votes: Vec::new(),
+            status: ReviewCaseStatus::Open,
+            upheld: None,
+            slashed_total: 0,
+            decided_at: None,
+            committee: Some(committee),
+            committee_seed: Some(seed.iter().map(|b| format!("{:02x}", b)).collect()),
+            committee_pool_size: Some(pool.len() as u32),
+        };
+
+        StorageManager::insert_review_case(case.clone());
+        RegistryResult::Ok(case)
+    }
+
+    pub fn cast_review_vote(
+        task_id: String,
+        uphold: bool,
+        caller_principal: Principal,
+    ) -> RegistryResult<ReviewCase> {
+        let config = Self::get_reviewer_pool_config();
+        match StorageManager::get_reviewer(&caller_principal) {
+            Some(reviewer) if reviewer.staked >= config.min_stake => {}
+            Some(_) => return RegistryResult::Err("Stake is below the pool minimum".to_string()),
+            None => return RegistryResult::Err("Not a member of the reviewer pool".to_string()),
+        }
+
+        let mut case = match StorageManager::get_review_case(&task_id) {
+            Some(case) => case,
+            None => return RegistryResult::Err("Review case not found".to_string()),
+        };
+        if case.status != ReviewCaseStatus::Open || time() >= case.closes_at {
+            return RegistryResult::Err("Voting on this case has closed".to_string());
+        }
+        if case.committee.as_ref().is_some_and(|c| !c.contains(&caller_principal)) {
+            return RegistryResult::Err("Only members of the case committee may vote".to_string());
+        }
+        if case.votes.iter().any(|v| v.reviewer == caller_principal) {
+            return RegistryResult::Err("You have already voted on this case".to_string());
+        }
+        if StorageManager::get_company(&case.company_id).is_some_and(|c| OwnershipManager::is_owner(&c, caller_principal)) {
+            return RegistryResult::Err("Cannot review a dispute involving your own company".to_string());
+        }
+
+        case.votes.push(ReviewVote {
+            reviewer: caller_principal,
+            uphold,
+            voted_at: time(),
+        });
+        StorageManager::insert_review_case(case.clone());
+
+        RegistryResult::Ok(case)
+    }
+
+    // Anyone may tally once voting closes; moderators may tally early once quorum is met
+    pub fn tally_review_case(task_id: String, caller_principal: Principal) -> RegistryResult<ReviewCase> {
+        let mut case = match StorageManager::get_review_case(&task_id) {
+            Some(case) => case,
+            None => return RegistryResult::Err("Review case not found".to_string()),
+        };
+        if case.status != ReviewCaseStatus::Open {
+            return RegistryResult::Err("Review case has already been tallied".to_string());
+        }
+
+        let config = Self::get_reviewer_pool_config();
+        let now = time();
+        let quorum_met = case.votes.len() as u32 >= config.quorum;
+        if now < case.closes_at && !(quorum_met && ModerationManager::is_moderator(caller_principal)) {
+            return RegistryResult::Err("Voting is still open".to_string());
+        }
+
+        let uphold_votes = case.votes.iter().filter(|v| v.uphold).count();
+        let reject_votes = case.votes.len() - uphold_votes;
+
+        // Without quorum or a majority the task stays with the moderators and nobody is slashed
+        if !quorum_met || uphold_votes == reject_votes {
+            case.status = ReviewCaseStatus::Expired;
+            case.decided_at = Some(now);
+            StorageManager::insert_review_case(case.clone());
+            return RegistryResult::Ok(case);
+        }
+
+        let upheld = uphold_votes > reject_votes;
+        let (majority, minority): (Vec<&ReviewVote>, Vec<&ReviewVote>) =
+            case.votes.iter().partition(|v| v.uphold == upheld);
+
+        let mut slashed_total: u64 = 0;
+        for vote in &minority {
+            if let Some(mut reviewer) = StorageManager::get_reviewer(&vote.reviewer) {
+                let slash = (reviewer.staked as u128 * config.slash_percent as u128 / 100) as u64;
+                reviewer.staked -= slash;
+                reviewer.total_slashed = reviewer.total_slashed.saturating_add(slash);
+                reviewer.inaccurate_votes += 1;
+                slashed_total = slashed_total.saturating_add(slash);
+                StorageManager::insert_reviewer(reviewer);
+            }
+        }
+
+        // Rounding remainder goes to the earliest majority voter
+        let share = slashed_total / majority.len() as u64;
+        let remainder = slashed_total % majority.len() as u64;
+        for (index, vote) in majority.iter().enumerate() {
+            if let Some(mut reviewer) = StorageManager::get_reviewer(&vote.reviewer) {
+                let reward = if index == 0 { share + remainder } else { share };
+                reviewer.pending_rewards = reviewer.pending_rewards.saturating_add(reward);
+                reviewer.accurate_votes += 1;
+                StorageManager::insert_reviewer(reviewer);
+            }
+        }
+
+        case.status = ReviewCaseStatus::Decided;
+        case.upheld = Some(upheld);
+        case.slashed_total = slashed_total;
+        case.decided_at = Some(now);
+        StorageManager::insert_review_case(case.clone());
+
+        if let Some(task) = StorageManager::get_moderation_task(&case.task_id) {
+            if task.status != ModerationTaskStatus::Closed {
+                let outcome = format!(
+                    "Community review {} the dispute ({} uphold, {} reject)",
+                    if upheld { "upheld" } else { "rejected" },
+                    uphold_votes,
+                    reject_votes
+                );
+                ModerationManager::close_task(task, outcome, ic_cdk::id());
+            }
+        }
+
+        RegistryResult::Ok(case)
+    }
+
+    // Partial Fisher-Yates shuffle driven by a splitmix64 stream seeded from raw_rand
+    fn draw_committee(pool: &[Principal], seed: &[u8], size: usize) -> Vec<Principal> {
+        let mut state = seed.chunks(8).fold(0u64, |acc, chunk| {
+            let mut word = [0u8; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            acc ^ u64::from_le_bytes(word)
+        });
+        let mut next = || {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        };
+
+        let mut candidates = pool.to_vec();
+        let size = size.min(candidates.len());
+        for i in 0..size {
+            let j = i + (next() % (candidates.len() - i) as u64) as usize;
+            candidates.swap(i, j);
+        }
+        candidates.truncate(size);
+        candidates
+    }
+
+    // Queries
+
+    pub fn get_reviewer(principal: Principal) -> Option<Reviewer> {
+        StorageManager::get_reviewer(&principal)
+    }
+
+    pub fn get_reviewer_pool() -> Vec<Reviewer> {
+        let mut reviewers = StorageManager::get_all_reviewers();
+        reviewers.sort_by_key(|r| std::cmp::Reverse(r.staked));
+        reviewers
+    }
+
+    pub fn get_review_case(task_id: String) -> Option<ReviewCase> {
+        StorageManager::get_review_case(&task_id)
+    }
+
+    pub fn get_review_cases(status: Option<ReviewCaseStatus>) -> Vec<ReviewCase> {
+        let mut cases: Vec<ReviewCase> = StorageManager::get_all_review_cases()
+            .into_iter()
+            .filter(|c| status.as_ref().is_none_or(|s| c.status == *s))
+            .collect();
+        cases.sort_by_key(|c| std::cmp::Reverse(c.opened_at));
+        cases
+    }
+}
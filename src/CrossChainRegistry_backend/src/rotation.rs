@@ -0,0 +1,267 @@
+use crate::crosschain::CrossChainVerifier;
+use crate::ownership::OwnershipManager;
+use crate::sanitize;
+use crate::storage::StorageManager;
+use crate::types::{
+    AddressLineage, AddressRotation, AddressRotationStatus, ChainType, Company, CrossChainPresence,
+    RegistryResult,
+};
+use crate::verification::VerificationManager;
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Supervised rotation of verified addresses and contracts.
+//
+// 1. The owner deprecates a verified address and names its successor.
+// 2. The successor is verified through the normal cross-chain challenge flow.
+// 3. On success the old address leaves the active presence lists, wallet and token entries
+//    move to the successor, and the rotation record keeps the old -> new link.
+
+pub struct RotationManager;
+
+impl RotationManager {
+    const MAX_REASON_LENGTH: usize = 500;
+    const MAX_ROTATIONS: usize = 100;
+    const MAX_LINEAGE_DEPTH: usize = 50;
+
+    pub fn active_addresses<'a>(presence: &'a CrossChainPresence, chain_type: &ChainType) -> &'a Vec<String> {
+        match chain_type {
+            ChainType::Ethereum => &presence.ethereum_contracts,
+            ChainType::Bitcoin => &presence.bitcoin_addresses,
+            ChainType::ICP => &presence.icp_canisters,
+            ChainType::Polygon => &presence.polygon_contracts,
+            ChainType::Solana => &presence.solana_addresses,
+            ChainType::Sui => &presence.sui_addresses,
+            ChainType::TON => &presence.ton_addresses,
+        }
+    }
+
+    fn active_addresses_mut<'a>(
+        presence: &'a mut CrossChainPresence,
+        chain_type: &ChainType,
+    ) -> &'a mut Vec<String> {
+        match chain_type {
+            ChainType::Ethereum => &mut presence.ethereum_contracts,
+            ChainType::Bitcoin => &mut presence.bitcoin_addresses,
+            ChainType::ICP => &mut presence.icp_canisters,
+            ChainType::Polygon => &mut presence.polygon_contracts,
+            ChainType::Solana => &mut presence.solana_addresses,
+            ChainType::Sui => &mut presence.sui_addresses,
+            ChainType::TON => &mut presence.ton_addresses,
+        }
+    }
+
+    pub fn deprecate_address(
+        company_id: String,
+        chain_type: ChainType,
+        address: String,
+        successor_address: String,
+        reason: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<AddressRotation> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        if !OwnershipManager::is_owner(&company, caller_principal) {
+            return RegistryResult::Err(
+                "Unauthorized: Only company creator can rotate addresses".to_string(),
+            );
+        }
+
+        let address = address.trim().to_string();
+        let successor_address = successor_address.trim().to_string();
+
+        if !Self::active_addresses(&company.cross_chain_presence, &chain_type).contains(&address) {
+            return RegistryResult::Err("Address is not a verified address of this company".to_string());
+        }
+        if successor_address == address {
+            return RegistryResult::Err("Successor must differ from the deprecated address".to_string());
+        }
+        if let Err(err) = CrossChainVerifier::validate_address_format(&chain_type, &successor_address) {
+            return RegistryResult::Err(err);
+        }
+        if sanitize::grapheme_len(&reason) > Self::MAX_REASON_LENGTH {
+            return RegistryResult::Err("Reason exceeds maximum length of 500 characters".to_string());
+        }
+
+        let rotations = company.address_rotations.unwrap_or_default();
+        if rotations.len() >= Self::MAX_ROTATIONS {
+            return RegistryResult::Err("Company has reached the maximum number of address rotations".to_string());
+        }
+        if rotations.iter().any(|r| {
+            r.status == AddressRotationStatus::PendingSuccessorVerification
+                && r.chain_type == chain_type
+                && r.deprecated_address == address
+        }) {
+            return RegistryResult::Err("A rotation for this address is already pending".to_string());
+        }
+
+        let rotation = AddressRotation {
+            id: format!("rotation_{}_{}", time(), rotations.len()),
+            chain_type: chain_type.clone(),
+            deprecated_address: address,
+            successor_address: successor_address.clone(),
+            reason: reason.trim().to_string(),
+            status: AddressRotationStatus::PendingSuccessorVerification,
+            initiated_by: caller_principal,
+            initiated_at: time(),
+            completed_at: None,
+        };
+
+        let rotation_id = rotation.id.clone();
+        let mut stored = rotation.clone();
+        StorageManager::update_company(&company_id, |company| {
+            company.address_rotations.get_or_insert_with(Vec::new).push(rotation);
+
+            // A successor that is already verified completes the rotation right away
+            if Self::complete_verified_successor(company, &chain_type, &successor_address) {
+                company.verification_score = VerificationManager::calculate_verification_score(company);
+            }
+            if let Some(current) = company
+                .address_rotations
+                .as_ref()
+                .and_then(|rotations| rotations.iter().find(|r| r.id == rotation_id))
+            {
+                stored = current.clone();
+            }
+        });
+
+        RegistryResult::Ok(stored)
+    }
+
+    // Called from the cross-chain verification success paths while the company is being
+    // updated, so it only touches the record it is given. Returns whether anything changed.
+    pub fn complete_verified_successor(company: &mut Company, chain_type: &ChainType, verified_address: &str) -> bool {
+        let chain = CrossChainVerifier::chain_name(chain_type);
+        let now = time();
+        let mut retired = Vec::new();
+
+        if let Some(rotations) = company.address_rotations.as_mut() {
+            for rotation in rotations.iter_mut() {
+                if rotation.status == AddressRotationStatus::PendingSuccessorVerification
+                    && rotation.chain_type == *chain_type
+                    && rotation.successor_address == verified_address
+                {
+                    rotation.status = AddressRotationStatus::Completed;
+                    rotation.completed_at = Some(now);
+                    retired.push(rotation.deprecated_address.clone());
+                }
+            }
+        }
+
+        if retired.is_empty() {
+            return false;
+        }
+
+        let presence = &mut company.cross_chain_presence;
+        Self::active_addresses_mut(presence, chain_type).retain(|a| !retired.contains(a));
+        for wallet in presence.treasury_wallets.iter_mut() {
+            if wallet.chain == chain && retired.contains(&wallet.address) {
+                wallet.address = verified_address.to_string();
+                wallet.verified = true;
+            }
+        }
+        for token in presence.token_contracts.iter_mut() {
+            if token.chain == chain && retired.contains(&token.contract_address) {
+                token.contract_address = verified_address.to_string();
+                token.verified = true;
+            }
+        }
+        true
+    }
+
+    pub fn cancel_address_rotation(
+        company_id: String,
+        rotation_id: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<AddressRotation> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        if !OwnershipManager::is_owner(&company, caller_principal) {
+            return RegistryResult::Err(
+                "Unauthorized: Only company creator can rotate addresses".to_string(),
+            );
+        }
+
+        match company
+            .address_rotations
+            .as_ref()
+            .and_then(|rotations| rotations.iter().find(|r| r.id == rotation_id))
+        {
+            Some(rotation) if rotation.status == AddressRotationStatus::PendingSuccessorVerification => {}
+            Some(_) => return RegistryResult::Err("Only pending rotations can be cancelled".to_string()),
+            None => return RegistryResult::Err("Address rotation not found".to_string()),
+        }
+
+        let mut cancelled = None;
+        StorageManager::update_company(&company_id, |company| {
+            if let Some(rotations) = company.address_rotations.as_mut() {
+                if let Some(rotation) = rotations.iter_mut().find(|r| r.id == rotation_id) {
+                    rotation.status = AddressRotationStatus::Cancelled;
+                    rotation.completed_at = Some(time());
+                    cancelled = Some(rotation.clone());
+                }
+            }
+        });
+
+        match cancelled {
+            Some(rotation) => RegistryResult::Ok(rotation),
+            None => RegistryResult::Err("Address rotation not found".to_string()),
+        }
+    }
+
+    pub fn get_address_rotations(company_id: String) -> RegistryResult<Vec<AddressRotation>> {
+        match StorageManager::get_company(&company_id) {
+            Some(company) => RegistryResult::Ok(company.address_rotations.unwrap_or_default()),
+            None => RegistryResult::Err("Company not found".to_string()),
+        }
+    }
+
+    // Resolve an address seen in an old proof to the company and its current address
+    pub fn get_address_lineage(chain_type: ChainType, address: String) -> Option<AddressLineage> {
+        let address = address.trim().to_string();
+
+        for company in StorageManager::get_all_companies() {
+            let rotations = company.address_rotations.clone().unwrap_or_default();
+            let is_active = Self::active_addresses(&company.cross_chain_presence, &chain_type).contains(&address);
+            let is_retired = rotations.iter().any(|r| {
+                r.status == AddressRotationStatus::Completed
+                    && r.chain_type == chain_type
+                    && r.deprecated_address == address
+            });
+            if !is_active && !is_retired {
+                continue;
+            }
+
+            let mut current = address.clone();
+            let mut followed = Vec::new();
+            while followed.len() < Self::MAX_LINEAGE_DEPTH {
+                match rotations.iter().find(|r| {
+                    r.status == AddressRotationStatus::Completed
+                        && r.chain_type == chain_type
+                        && r.deprecated_address == current
+                }) {
+                    Some(rotation) => {
+                        current = rotation.successor_address.clone();
+                        followed.push(rotation.clone());
+                    }
+                    None => break,
+                }
+            }
+
+            return Some(AddressLineage {
+                company_id: company.id,
+                company_name: company.basic_info.name,
+                current_address: current,
+                rotations: followed,
+            });
+        }
+
+        None
+    }
+}
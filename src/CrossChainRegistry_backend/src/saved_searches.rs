@@ -0,0 +1,164 @@
+use crate::api::RegistryAPI;
+use crate::notifications::NotificationManager;
+use crate::sanitize;
+use crate::storage::StorageManager;
+use crate::types::{CompanyEvent, CompanyEventKind, RegistryResult, SavedSearch, SearchFilters};
+use candid::Principal;
+use ic_cdk::api::time;
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+// Saved SearchFilters queries. Matches are tracked per search and only re-evaluated for
+// companies that appear in the event journal since the last run, so a new match costs
+// one filter check instead of a full scan.
+
+pub struct SavedSearchManager;
+
+impl SavedSearchManager {
+    const JOURNAL_CONSUMER: &'static str = "saved_searches";
+    const MAX_SEARCHES_PER_PRINCIPAL: usize = 20;
+    const MAX_NAME_LENGTH: usize = 100;
+    const EVENTS_PER_RUN: usize = 500;
+    const EVALUATION_INTERVAL_SECS: u64 = 5 * 60;
+    const JOURNAL_RETENTION_NS: u64 = 30 * 24 * 60 * 60 * 1_000_000_000; // 30 days
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::EVALUATION_INTERVAL_SECS), || {
+            Self::process_journal();
+        });
+    }
+
+    pub fn save_search(
+        name: String,
+        filters: SearchFilters,
+        caller_principal: Principal,
+    ) -> RegistryResult<SavedSearch> {
+        if caller_principal == Principal::anonymous() {
+            return RegistryResult::Err("Anonymous principals cannot save searches".to_string());
+        }
+        let name = name.trim().to_string();
+        if name.is_empty() || sanitize::grapheme_len(&name) > Self::MAX_NAME_LENGTH {
+            return RegistryResult::Err("Name must be 1-100 characters".to_string());
+        }
+        if Self::searches_of(caller_principal).len() >= Self::MAX_SEARCHES_PER_PRINCIPAL {
+            return RegistryResult::Err(format!(
+                "At most {} saved searches per principal",
+                Self::MAX_SEARCHES_PER_PRINCIPAL
+            ));
+        }
+
+        // Seed with today's matches so only companies that start matching later notify
+        let matched_company_ids = StorageManager::get_all_companies()
+            .iter()
+            .filter(|company| RegistryAPI::matches_filters(company, &filters))
+            .map(|company| company.id.clone())
+            .collect();
+
+        let now = time();
+        let search = SavedSearch {
+            id: format!("search_{}_{}", now, StorageManager::get_all_saved_searches().len()),
+            owner: caller_principal,
+            name,
+            filters,
+            created_at: now,
+            matched_company_ids,
+            last_match_at: None,
+        };
+
+        StorageManager::insert_saved_search(search.clone());
+        RegistryResult::Ok(search)
+    }
+
+    pub fn delete_saved_search(search_id: String, caller_principal: Principal) -> RegistryResult<()> {
+        match StorageManager::get_saved_search(&search_id) {
+            Some(search) if search.owner == caller_principal => {
+                StorageManager::remove_saved_search(&search_id);
+                RegistryResult::Ok(())
+            }
+            _ => RegistryResult::Err("Saved search not found".to_string()),
+        }
+    }
+
+    pub fn get_my_saved_searches(caller_principal: Principal) -> Vec<SavedSearch> {
+        Self::searches_of(caller_principal)
+    }
+
+    fn searches_of(principal: Principal) -> Vec<SavedSearch> {
+        let mut searches: Vec<SavedSearch> = StorageManager::get_all_saved_searches()
+            .into_iter()
+            .filter(|s| s.owner == principal)
+            .collect();
+        searches.sort_by_key(|s| s.created_at);
+        searches
+    }
+
+    // Consume journal events since the last run; returns the number of notifications sent
+    pub fn process_journal() -> u32 {
+        let cursor = StorageManager::get_journal_cursor(Self::JOURNAL_CONSUMER);
+        let events: Vec<CompanyEvent> = StorageManager::get_company_events_after(cursor, Self::EVENTS_PER_RUN);
+        let last_seq = match events.last() {
+            Some(event) => event.seq,
+            None => return 0,
+        };
+
+        let mut changed: BTreeSet<String> = BTreeSet::new();
+        let mut deleted: BTreeSet<String> = BTreeSet::new();
+        for event in &events {
+            if event.kind == CompanyEventKind::Deleted {
+                deleted.insert(event.company_id.clone());
+                changed.remove(&event.company_id);
+            } else {
+                deleted.remove(&event.company_id);
+                changed.insert(event.company_id.clone());
+            }
+        }
+
+        let companies: Vec<_> = changed.iter().filter_map(|id| StorageManager::get_company(id)).collect();
+        let now = time();
+        let mut notified = 0;
+
+        for mut search in StorageManager::get_all_saved_searches() {
+            let mut modified = false;
+            search.matched_company_ids.retain(|id| {
+                let keep = !deleted.contains(id);
+                modified |= !keep;
+                keep
+            });
+
+            for company in &companies {
+                let was_matching = search.matched_company_ids.contains(&company.id);
+                let is_matching = RegistryAPI::matches_filters(company, &search.filters);
+
+                if is_matching && !was_matching {
+                    search.matched_company_ids.push(company.id.clone());
+                    search.last_match_at = Some(now);
+                    NotificationManager::notify(
+                        search.owner,
+                        &company.id,
+                        None,
+                        format!(
+                            "'{}' now matches your saved search '{}'",
+                            company.basic_info.name, search.name
+                        ),
+                    );
+                    notified += 1;
+                    modified = true;
+                } else if !is_matching && was_matching {
+                    search.matched_company_ids.retain(|id| id != &company.id);
+                    modified = true;
+                }
+            }
+
+            if modified {
+                StorageManager::insert_saved_search(search);
+            }
+        }
+
+        StorageManager::set_journal_cursor(Self::JOURNAL_CONSUMER, last_seq);
+        // Always keep the newest event: sequence numbers continue from the last key
+        let prunable = StorageManager::min_journal_cursor()
+            .min(StorageManager::latest_company_event_seq().saturating_sub(1));
+        StorageManager::prune_company_events(prunable, now.saturating_sub(Self::JOURNAL_RETENTION_NS));
+        notified
+    }
+}
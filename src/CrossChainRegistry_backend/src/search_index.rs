@@ -0,0 +1,133 @@
+use crate::storage::StorageManager;
+use crate::types::{Company, SearchField};
+use std::collections::{BTreeMap, HashMap};
+
+// Inverted index behind search_companies_ranked. Names, focus areas and descriptions are
+// split into lowercase word tokens; each (token, company) posting records which fields the
+// token appears in. StorageManager keeps postings in step with every company write, and
+// queries are answered with prefix range scans, so "sta" finds "staking" without touching
+// companies that don't match.
+
+pub struct SearchIndex;
+
+impl SearchIndex {
+    pub const FIELD_NAME: u8 = 0b100;
+    pub const FIELD_FOCUS_AREA: u8 = 0b010;
+    pub const FIELD_DESCRIPTION: u8 = 0b001;
+
+    const MIN_TOKEN_CHARS: usize = 2;
+    const MAX_TOKEN_CHARS: usize = 32;
+    const MAX_QUERY_TOKENS: usize = 8;
+    // Bounds the work of very short prefixes like "co"
+    const MAX_POSTINGS_PER_TOKEN: usize = 5_000;
+
+    const NAME_WEIGHT: u32 = 60;
+    const FOCUS_AREA_WEIGHT: u32 = 30;
+    const DESCRIPTION_WEIGHT: u32 = 15;
+    // Whole-word matches beat prefix matches
+    const EXACT_TOKEN_BONUS: u32 = 20;
+
+    // Lowercase runs of letters and digits; shorter ones are noise, longer ones are cut
+    pub fn tokenize(text: &str) -> Vec<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .map(|word| word.to_lowercase())
+            .filter(|word| word.chars().count() >= Self::MIN_TOKEN_CHARS)
+            .map(|word| word.chars().take(Self::MAX_TOKEN_CHARS).collect())
+            .collect()
+    }
+
+    // token -> FIELD_* bits of the fields it occurs in
+    pub fn company_terms(company: &Company) -> BTreeMap<String, u8> {
+        let info = &company.basic_info;
+        let mut terms = BTreeMap::new();
+        let mut add = |text: &str, field: u8| {
+            for token in Self::tokenize(text) {
+                *terms.entry(token).or_insert(0) |= field;
+            }
+        };
+        add(&info.name, Self::FIELD_NAME);
+        for area in &info.focus_areas {
+            add(area, Self::FIELD_FOCUS_AREA);
+        }
+        add(&info.description, Self::FIELD_DESCRIPTION);
+        terms
+    }
+
+    // Company ids with their relevance and best-matching field, most relevant first. Every
+    // query token has to match some token of the company as a prefix.
+    pub fn rank(query: &str) -> Vec<(String, u32, SearchField)> {
+        let mut query_tokens = Self::tokenize(query);
+        query_tokens.dedup();
+        query_tokens.truncate(Self::MAX_QUERY_TOKENS);
+        if query_tokens.is_empty() {
+            return Vec::new();
+        }
+
+        // company id -> (relevance so far, fields matched, query tokens matched)
+        let mut scores: HashMap<String, (u32, u8, usize)> = HashMap::new();
+        for (matched_so_far, query_token) in query_tokens.iter().enumerate() {
+            let mut best: HashMap<String, (u32, u8)> = HashMap::new();
+            for (token, company_id, fields) in Self::postings_with_prefix(query_token) {
+                let score = Self::field_weight(fields) + if token == *query_token { Self::EXACT_TOKEN_BONUS } else { 0 };
+                let entry = best.entry(company_id).or_insert((0, 0));
+                entry.0 = entry.0.max(score);
+                entry.1 |= fields;
+            }
+            for (company_id, (score, fields)) in best {
+                let entry = scores.entry(company_id).or_insert((0, 0, 0));
+                // Only companies that matched every earlier token stay in the running
+                if entry.2 == matched_so_far {
+                    entry.0 += score;
+                    entry.1 |= fields;
+                    entry.2 += 1;
+                }
+            }
+        }
+
+        let mut ranked: Vec<(String, u32, SearchField)> = scores
+            .into_iter()
+            .filter(|(_, (_, _, matched))| *matched == query_tokens.len())
+            .map(|(company_id, (relevance, fields, _))| (company_id, relevance, Self::best_field(fields)))
+            .collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked
+    }
+
+    // Falls back to tokenizing every company until rebuild_search_index has covered
+    // companies stored before the index existed
+    fn postings_with_prefix(prefix: &str) -> Vec<(String, String, u8)> {
+        if StorageManager::search_index_is_complete() {
+            return StorageManager::get_search_postings(prefix, Self::MAX_POSTINGS_PER_TOKEN);
+        }
+        StorageManager::get_all_companies()
+            .iter()
+            .flat_map(|company| {
+                Self::company_terms(company)
+                    .into_iter()
+                    .filter(|(token, _)| token.starts_with(prefix))
+                    .map(|(token, fields)| (token, company.id.clone(), fields))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn field_weight(fields: u8) -> u32 {
+        if fields & Self::FIELD_NAME != 0 {
+            Self::NAME_WEIGHT
+        } else if fields & Self::FIELD_FOCUS_AREA != 0 {
+            Self::FOCUS_AREA_WEIGHT
+        } else {
+            Self::DESCRIPTION_WEIGHT
+        }
+    }
+
+    fn best_field(fields: u8) -> SearchField {
+        if fields & Self::FIELD_NAME != 0 {
+            SearchField::Name
+        } else if fields & Self::FIELD_FOCUS_AREA != 0 {
+            SearchField::FocusArea
+        } else {
+            SearchField::Description
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::community::CommunityValidationManager;
+use crate::moderation::ModerationManager;
+use crate::storage::StorageManager;
+use crate::types::{CommunityPage, RegistryResult, SecurityEvent, SecurityEventFilter, SecurityEventType};
+use crate::webhooks::WebhookManager;
+use candid::Principal;
+use ic_cdk::api::time;
+
+// Per-principal feed of security-relevant events (throttling, key changes). Principals
+// read their own feed; moderators can read anyone's when investigating abuse.
+
+pub struct SecurityLog;
+
+impl SecurityLog {
+    const MAX_EVENTS: u64 = 100_000;
+    const MAX_DETAILS_LENGTH: usize = 200;
+
+    pub fn record(principal: Principal, event_type: SecurityEventType, company_id: Option<String>, details: String) {
+        let event = SecurityEvent {
+            seq: StorageManager::next_security_event_seq(),
+            principal,
+            event_type,
+            company_id,
+            details: details.chars().take(Self::MAX_DETAILS_LENGTH).collect(),
+            timestamp: time(),
+        };
+        if Self::is_high_severity(&event.event_type) {
+            WebhookManager::enqueue_security_event(&event);
+        }
+        StorageManager::insert_security_event(event, Self::MAX_EVENTS);
+    }
+
+    // Worth pushing to moderator webhooks rather than waiting for someone to look
+    fn is_high_severity(event_type: &SecurityEventType) -> bool {
+        matches!(
+            event_type,
+            SecurityEventType::VerificationLockout
+                | SecurityEventType::AdminAdded
+                | SecurityEventType::AdminRemoved
+                | SecurityEventType::RegistryImpersonation
+        )
+    }
+
+    pub fn get_security_events_by_principal(
+        filter: Option<SecurityEventFilter>,
+        offset: Option<u32>,
+        limit: Option<u32>,
+        caller_principal: Principal,
+    ) -> RegistryResult<CommunityPage<SecurityEvent>> {
+        if caller_principal == Principal::anonymous() {
+            return RegistryResult::Err("Anonymous principals have no security events".to_string());
+        }
+        RegistryResult::Ok(Self::page(caller_principal, filter.unwrap_or_default(), offset, limit))
+    }
+
+    pub fn get_moderator_security_events(
+        principal: Principal,
+        filter: Option<SecurityEventFilter>,
+        offset: Option<u32>,
+        limit: Option<u32>,
+        caller_principal: Principal,
+    ) -> RegistryResult<CommunityPage<SecurityEvent>> {
+        if let Err(err) = ModerationManager::ensure_moderator(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        RegistryResult::Ok(Self::page(principal, filter.unwrap_or_default(), offset, limit))
+    }
+
+    fn matches(event: &SecurityEvent, filter: &SecurityEventFilter) -> bool {
+        filter.event_types.as_ref().is_none_or(|types| types.contains(&event.event_type))
+            && filter.from.is_none_or(|from| event.timestamp >= from)
+            && filter.to.is_none_or(|to| event.timestamp < to)
+    }
+
+    // Newest first
+    fn page(
+        principal: Principal,
+        filter: SecurityEventFilter,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> CommunityPage<SecurityEvent> {
+        let events: Vec<SecurityEvent> = StorageManager::get_security_events_for(&principal)
+            .into_iter()
+            .filter(|event| Self::matches(event, &filter))
+            .collect();
+        CommunityValidationManager::paginate(events, offset, limit)
+    }
+}
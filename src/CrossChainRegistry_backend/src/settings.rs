@@ -0,0 +1,627 @@
+use crate::admins::AdminManager;
+use crate::http_client;
+use crate::policy::PolicyLog;
+use crate::sanitize;
+use crate::storage::StorageManager;
+use crate::types::{
+    ApiConfig, ApiConfigInfo, ApiProvider, ApiStyle, ProviderEndpoint, ProviderEndpointList, ProviderHealth,
+    RegistryResult,
+};
+use candid::Principal;
+use ic_cdk::api::management_canister::http_request::{
+    CanisterHttpRequestArgument, HttpHeader, HttpResponse,
+};
+use ic_cdk::api::time;
+
+// Registry settings: external provider endpoints with health tracking and failover, and
+// per-provider base URL and API key overrides so providers can be rotated without a redeploy
+
+pub struct SettingsManager;
+
+impl SettingsManager {
+    // Health tracking constants
+    const UNHEALTHY_FAILURE_THRESHOLD: u32 = 3;
+    const FAILURE_COOLDOWN_NS: u64 = 300_000_000_000; // 5 minutes
+    const MAX_ENDPOINTS_PER_PROVIDER: usize = 5;
+    const MAX_ERROR_LENGTH: usize = 200;
+    const MAX_API_KEY_LENGTH: usize = 256;
+    const API_KEY_PLACEHOLDER: &'static str = "{api_key}";
+
+    pub const ALL_PROVIDERS: [ApiProvider; 8] = [
+        ApiProvider::GitHub,
+        ApiProvider::Dns,
+        ApiProvider::Bitcoin,
+        ApiProvider::EthereumRpc,
+        ApiProvider::PolygonRpc,
+        ApiProvider::SolanaRpc,
+        ApiProvider::Ton,
+        ApiProvider::TonBalance,
+    ];
+
+    // Canister-level configuration (canister controllers only); running the registry goes
+    // through AdminManager::ensure_admin
+    pub fn ensure_admin(caller_principal: Principal) -> Result<(), String> {
+        if Self::is_controller(&caller_principal) {
+            Ok(())
+        } else {
+            Err("Unauthorized: Admin access required".to_string())
+        }
+    }
+
+    #[cfg(not(test))]
+    fn is_controller(principal: &Principal) -> bool {
+        ic_cdk::api::is_controller(principal)
+    }
+
+    // Unit tests run outside a canister, where nobody is a controller
+    #[cfg(test)]
+    fn is_controller(_principal: &Principal) -> bool {
+        false
+    }
+
+    pub fn provider_key(provider: &ApiProvider) -> &'static str {
+        match provider {
+            ApiProvider::GitHub => "github",
+            ApiProvider::Dns => "dns",
+            ApiProvider::Bitcoin => "bitcoin",
+            ApiProvider::Ethereum => "ethereum",
+            ApiProvider::EthereumRpc => "ethereum_rpc",
+            ApiProvider::PolygonRpc => "polygon_rpc",
+            ApiProvider::SolanaRpc => "solana_rpc",
+            ApiProvider::Ton => "ton",
+            ApiProvider::TonBalance => "ton_balance",
+        }
+    }
+
+    // Built-in endpoints used until an admin configures a provider
+    pub fn default_endpoints(provider: &ApiProvider) -> Vec<ProviderEndpoint> {
+        let endpoint = |name: &str, url_template: &str, api_style: ApiStyle| ProviderEndpoint {
+            name: name.to_string(),
+            url_template: url_template.to_string(),
+            api_style,
+            enabled: true,
+        };
+
+        match provider {
+            ApiProvider::GitHub => vec![endpoint(
+                "github",
+                "https://api.github.com/orgs/{query}",
+                ApiStyle::GitHubRest,
+            )],
+            ApiProvider::Dns => vec![
+                endpoint(
+                    "google",
+                    "https://dns.google/resolve?name={query}&type=TXT",
+                    ApiStyle::DnsJson,
+                ),
+                endpoint(
+                    "cloudflare",
+                    "https://cloudflare-dns.com/dns-query?name={query}&type=TXT",
+                    ApiStyle::DnsJson,
+                ),
+            ],
+            ApiProvider::Bitcoin => vec![
+                endpoint(
+                    "blockchain.info",
+                    "https://blockchain.info/rawaddr/{query}?limit=50",
+                    ApiStyle::BlockchainInfo,
+                ),
+                endpoint(
+                    "blockstream",
+                    "https://blockstream.info/api/address/{query}",
+                    ApiStyle::Esplora,
+                ),
+                endpoint(
+                    "mempool.space",
+                    "https://mempool.space/api/address/{query}",
+                    ApiStyle::Esplora,
+                ),
+            ],
+            // Ethereum verification reads transactions over JSON-RPC (EthereumRpc) now
+            ApiProvider::Ethereum => Vec::new(),
+            ApiProvider::EthereumRpc => vec![
+                endpoint("publicnode", "https://ethereum-rpc.publicnode.com", ApiStyle::EvmJsonRpc),
+                endpoint("cloudflare", "https://cloudflare-eth.com", ApiStyle::EvmJsonRpc),
+            ],
+            ApiProvider::PolygonRpc => vec![
+                endpoint("polygon-rpc", "https://polygon-rpc.com", ApiStyle::EvmJsonRpc),
+                endpoint("publicnode", "https://polygon-bor-rpc.publicnode.com", ApiStyle::EvmJsonRpc),
+            ],
+            ApiProvider::SolanaRpc => vec![
+                endpoint("solana-mainnet", "https://api.mainnet-beta.solana.com", ApiStyle::SolanaJsonRpc),
+                endpoint("publicnode", "https://solana-rpc.publicnode.com", ApiStyle::SolanaJsonRpc),
+            ],
+            ApiProvider::Ton => vec![
+                endpoint(
+                    "toncenter",
+                    "https://toncenter.com/api/v2/getTransactions?address={query}&limit=20&archival=true",
+                    ApiStyle::Toncenter,
+                ),
+                endpoint(
+                    "tonapi",
+                    "https://tonapi.io/v2/blockchain/accounts/{query}/transactions?limit=20",
+                    ApiStyle::TonApi,
+                ),
+            ],
+            ApiProvider::TonBalance => vec![
+                endpoint(
+                    "toncenter",
+                    "https://toncenter.com/api/v2/getAddressBalance?address={query}",
+                    ApiStyle::Toncenter,
+                ),
+                endpoint("tonapi", "https://tonapi.io/v2/accounts/{query}", ApiStyle::TonApi),
+            ],
+        }
+    }
+
+    pub fn get_provider_endpoints(provider: &ApiProvider) -> Vec<ProviderEndpoint> {
+        match StorageManager::get_provider_endpoints(Self::provider_key(provider)) {
+            Some(list) => list.endpoints,
+            None => Self::default_endpoints(provider),
+        }
+    }
+
+    pub fn set_provider_endpoints(
+        provider: ApiProvider,
+        endpoints: Vec<ProviderEndpoint>,
+        caller_principal: Principal,
+    ) -> RegistryResult<()> {
+        if let Err(err) = Self::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        if endpoints.is_empty() {
+            return RegistryResult::Err("At least one endpoint is required".to_string());
+        }
+        if endpoints.len() > Self::MAX_ENDPOINTS_PER_PROVIDER {
+            return RegistryResult::Err(format!(
+                "Cannot configure more than {} endpoints per provider",
+                Self::MAX_ENDPOINTS_PER_PROVIDER
+            ));
+        }
+
+        for (index, endpoint) in endpoints.iter().enumerate() {
+            if endpoint.name.trim().is_empty() || sanitize::grapheme_len(&endpoint.name) > 50 {
+                return RegistryResult::Err("Endpoint name must be 1-50 characters".to_string());
+            }
+            if endpoints[..index].iter().any(|e| e.name == endpoint.name) {
+                return RegistryResult::Err(format!("Duplicate endpoint name '{}'", endpoint.name));
+            }
+            if !endpoint.url_template.starts_with("https://") || endpoint.url_template.len() > 500 {
+                return RegistryResult::Err(format!(
+                    "Endpoint '{}' must be an HTTPS URL of at most 500 characters",
+                    endpoint.name
+                ));
+            }
+            // JSON-RPC endpoints take the query in the POST body
+            let is_json_rpc = matches!(endpoint.api_style, ApiStyle::EvmJsonRpc | ApiStyle::SolanaJsonRpc);
+            if !is_json_rpc && !endpoint.url_template.contains("{query}") {
+                return RegistryResult::Err(format!(
+                    "Endpoint '{}' URL template must contain the {{query}} placeholder",
+                    endpoint.name
+                ));
+            }
+        }
+
+        let quorum = StorageManager::get_provider_endpoints(Self::provider_key(&provider)).and_then(|list| list.quorum);
+        let required = quorum.unwrap_or_else(|| Self::default_quorum(&provider));
+        if endpoints.iter().filter(|endpoint| endpoint.enabled).count() < required as usize {
+            return RegistryResult::Err(format!(
+                "Provider quorum is {}; keep at least that many endpoints enabled",
+                required
+            ));
+        }
+
+        PolicyLog::record(
+            &format!("provider_endpoints:{:?}", provider),
+            Some(PolicyLog::describe_endpoints(&Self::get_provider_endpoints(&provider))),
+            PolicyLog::describe_endpoints(&endpoints),
+            vec![caller_principal],
+            "set_provider_endpoints",
+        );
+        StorageManager::insert_provider_endpoints(
+            Self::provider_key(&provider).to_string(),
+            ProviderEndpointList { endpoints, quorum },
+        );
+
+        RegistryResult::Ok(())
+    }
+
+    // Lookups that prove ownership want independent agreement; everything else takes the
+    // first healthy answer
+    fn default_quorum(provider: &ApiProvider) -> u32 {
+        match provider {
+            ApiProvider::Bitcoin | ApiProvider::EthereumRpc => 2,
+            _ => 1,
+        }
+    }
+
+    pub fn get_provider_quorum(provider: &ApiProvider) -> u32 {
+        StorageManager::get_provider_endpoints(Self::provider_key(provider))
+            .and_then(|list| list.quorum)
+            .unwrap_or_else(|| Self::default_quorum(provider))
+    }
+
+    // None restores the provider default
+    pub fn set_provider_quorum(
+        provider: ApiProvider,
+        quorum: Option<u32>,
+        caller_principal: Principal,
+    ) -> RegistryResult<u32> {
+        if let Err(err) = Self::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let endpoints = Self::get_provider_endpoints(&provider);
+        let enabled = endpoints.iter().filter(|endpoint| endpoint.enabled).count() as u32;
+        let effective = quorum.unwrap_or_else(|| Self::default_quorum(&provider));
+        if effective == 0 || effective > Self::MAX_ENDPOINTS_PER_PROVIDER as u32 {
+            return RegistryResult::Err(format!("Quorum must be 1-{}", Self::MAX_ENDPOINTS_PER_PROVIDER));
+        }
+        if effective > enabled {
+            return RegistryResult::Err(format!(
+                "A quorum of {} needs at least {} enabled endpoints; {} are enabled",
+                effective, effective, enabled
+            ));
+        }
+
+        PolicyLog::record(
+            &format!("provider_quorum:{:?}", provider),
+            Some(Self::get_provider_quorum(&provider).to_string()),
+            effective.to_string(),
+            vec![caller_principal],
+            "set_provider_quorum",
+        );
+        StorageManager::insert_provider_endpoints(
+            Self::provider_key(&provider).to_string(),
+            ProviderEndpointList { endpoints, quorum },
+        );
+        RegistryResult::Ok(effective)
+    }
+
+    // Enabled endpoints with their configured position, healthy ones first, ordered by
+    // recent failures
+    pub fn endpoints_in_failover_order(provider: &ApiProvider) -> Vec<(usize, ProviderEndpoint)> {
+        let now = time();
+        let mut endpoints: Vec<(bool, u32, usize, ProviderEndpoint)> = Self::get_provider_endpoints(provider)
+            .into_iter()
+            .filter(|endpoint| endpoint.enabled)
+            .enumerate()
+            .map(|(position, endpoint)| {
+                let health = Self::health_for(provider, &endpoint.name);
+                let cooling_down = health.cooldown_until.is_some_and(|until| until > now);
+                (cooling_down, health.consecutive_failures, position, endpoint)
+            })
+            .collect();
+
+        // Unhealthy endpoints are kept as a last resort rather than skipped
+        endpoints.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+        endpoints.into_iter().map(|(_, _, position, endpoint)| (position, endpoint)).collect()
+    }
+
+    pub fn record_provider_success(provider: &ApiProvider, endpoint_name: &str) {
+        let mut health = Self::health_for(provider, endpoint_name);
+        health.consecutive_failures = 0;
+        health.total_successes += 1;
+        health.last_success_at = Some(time());
+        health.cooldown_until = None;
+        health.healthy = true;
+        StorageManager::insert_provider_health(Self::health_key(provider, endpoint_name), health);
+    }
+
+    pub fn record_provider_failure(provider: &ApiProvider, endpoint_name: &str, error: &str) {
+        let now = time();
+        let mut health = Self::health_for(provider, endpoint_name);
+        health.consecutive_failures += 1;
+        health.total_failures += 1;
+        health.last_failure_at = Some(now);
+        health.last_error = Some(error.chars().take(Self::MAX_ERROR_LENGTH).collect());
+
+        if health.consecutive_failures >= Self::UNHEALTHY_FAILURE_THRESHOLD {
+            health.cooldown_until = Some(now + Self::FAILURE_COOLDOWN_NS);
+            health.healthy = false;
+        }
+
+        StorageManager::insert_provider_health(Self::health_key(provider, endpoint_name), health);
+    }
+
+    // Admin query: health of every configured endpoint
+    pub fn get_provider_health(caller_principal: Principal) -> RegistryResult<Vec<ProviderHealth>> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        let health = Self::ALL_PROVIDERS
+            .iter()
+            .flat_map(|provider| {
+                Self::get_provider_endpoints(provider)
+                    .into_iter()
+                    .map(move |endpoint| Self::health_for(provider, &endpoint.name))
+            })
+            .collect();
+
+        RegistryResult::Ok(health)
+    }
+
+    // Try each endpoint of a provider in failover order until one answers.
+    // Transport errors, rate limiting (429) and server errors (5xx) fail over to the
+    // next endpoint; any other status is a real answer and is returned to the caller.
+    pub async fn http_request_with_failover<F>(
+        provider: ApiProvider,
+        build_request: F,
+        cycles: u128,
+    ) -> Result<(ProviderEndpoint, HttpResponse), String>
+    where
+        F: Fn(&ProviderEndpoint) -> CanisterHttpRequestArgument,
+    {
+        let endpoints = Self::endpoints_in_failover_order(&provider);
+        if endpoints.is_empty() {
+            return Err(format!(
+                "No enabled endpoints configured for provider '{}'",
+                Self::provider_key(&provider)
+            ));
+        }
+
+        let config = StorageManager::get_api_config(Self::provider_key(&provider));
+        let mut errors = Vec::new();
+        for (position, endpoint) in endpoints {
+            let request = Self::prepare_request(&config, position, &endpoint, &build_request);
+            match http_client::send(request, cycles).await.map_err(|error| Self::redact(&config, error)) {
+                Ok(response) => {
+                    if response.status == 429u32 || response.status >= 500u32 {
+                        let error = format!("HTTP status {}", response.status);
+                        Self::record_provider_failure(&provider, &endpoint.name, &error);
+                        errors.push(format!("{}: {}", endpoint.name, error));
+                    } else {
+                        Self::record_provider_success(&provider, &endpoint.name);
+                        return Ok((endpoint, response));
+                    }
+                }
+                Err(error) => {
+                    Self::record_provider_failure(&provider, &endpoint.name, &error);
+                    errors.push(format!("{}: {}", endpoint.name, error));
+                }
+            }
+        }
+
+        Err(format!(
+            "All {} providers failed ({})",
+            Self::provider_key(&provider),
+            errors.join("; ")
+        ))
+    }
+
+    // Asks endpoints in failover order until the provider's quorum of them return the same
+    // body. Transforms reduce responses to the facts that matter, so agreeing endpoints give
+    // identical bytes. Returns the names of the endpoints that agreed.
+    pub async fn http_request_with_quorum<F>(
+        provider: ApiProvider,
+        build_request: F,
+        cycles: u128,
+    ) -> Result<(Vec<String>, HttpResponse), String>
+    where
+        F: Fn(&ProviderEndpoint) -> CanisterHttpRequestArgument,
+    {
+        let quorum = Self::get_provider_quorum(&provider) as usize;
+        let endpoints = Self::endpoints_in_failover_order(&provider);
+        if endpoints.len() < quorum {
+            return Err(format!(
+                "Provider '{}' needs {} enabled endpoints for its quorum, {} are enabled",
+                Self::provider_key(&provider),
+                quorum,
+                endpoints.len()
+            ));
+        }
+
+        // Distinct answers so far, with the endpoints that gave each
+        let config = StorageManager::get_api_config(Self::provider_key(&provider));
+        let mut answers: Vec<(HttpResponse, Vec<String>)> = Vec::new();
+        let mut errors = Vec::new();
+        for (position, endpoint) in endpoints {
+            let request = Self::prepare_request(&config, position, &endpoint, &build_request);
+            match http_client::send(request, cycles).await.map_err(|error| Self::redact(&config, error)) {
+                Ok(response) if response.status == 429u32 || response.status >= 500u32 => {
+                    let error = format!("HTTP status {}", response.status);
+                    Self::record_provider_failure(&provider, &endpoint.name, &error);
+                    errors.push(format!("{}: {}", endpoint.name, error));
+                }
+                Ok(response) => {
+                    Self::record_provider_success(&provider, &endpoint.name);
+                    if response.status != 200u32 {
+                        errors.push(format!("{}: HTTP status {}", endpoint.name, response.status));
+                        continue;
+                    }
+                    match answers.iter_mut().find(|(answer, _)| answer.body == response.body) {
+                        Some((_, names)) => names.push(endpoint.name.clone()),
+                        None => answers.push((response, vec![endpoint.name.clone()])),
+                    }
+                    if let Some(index) = answers.iter().position(|(_, names)| names.len() >= quorum) {
+                        let (response, names) = answers.swap_remove(index);
+                        return Ok((names, response));
+                    }
+                }
+                Err(error) => {
+                    Self::record_provider_failure(&provider, &endpoint.name, &error);
+                    errors.push(format!("{}: {}", endpoint.name, error));
+                }
+            }
+        }
+
+        if answers.len() > 1 {
+            return Err(format!(
+                "{} providers disagree ({} different answers, {} needed to agree)",
+                Self::provider_key(&provider),
+                answers.len(),
+                quorum
+            ));
+        }
+        let agreeing = answers.first().map_or(0, |(_, names)| names.len());
+        Err(format!(
+            "Only {} of the {} {} providers needed answered ({})",
+            agreeing,
+            quorum,
+            Self::provider_key(&provider),
+            errors.join("; ")
+        ))
+    }
+
+    // API configuration
+
+    // None for both clears the provider's overrides
+    pub fn set_api_config(
+        provider: ApiProvider,
+        base_url: Option<String>,
+        api_key: Option<String>,
+        caller_principal: Principal,
+    ) -> RegistryResult<Option<ApiConfigInfo>> {
+        if let Err(err) = Self::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let base_url = base_url.map(|url| url.trim().trim_end_matches('/').to_string()).filter(|url| !url.is_empty());
+        if let Some(url) = &base_url {
+            let host = url.strip_prefix("https://").unwrap_or("");
+            if host.is_empty() || host.contains(['/', '?', '#', '{']) || url.len() > 200 {
+                return RegistryResult::Err(
+                    "Base URL must be https:// followed by a host (and optional port) only".to_string(),
+                );
+            }
+        }
+        let api_key = api_key.map(|key| key.trim().to_string()).filter(|key| !key.is_empty());
+        if let Some(key) = &api_key {
+            if key.len() > Self::MAX_API_KEY_LENGTH || !key.chars().all(|c| c.is_ascii_graphic()) {
+                return RegistryResult::Err(format!(
+                    "API key must be at most {} printable ASCII characters",
+                    Self::MAX_API_KEY_LENGTH
+                ));
+            }
+        }
+
+        let key = Self::provider_key(&provider);
+        let previous = StorageManager::get_api_config(key);
+        let config = (base_url.is_some() || api_key.is_some()).then(|| ApiConfig {
+            base_url,
+            api_key,
+            updated_at: time(),
+            updated_by: caller_principal,
+        });
+        // The key itself never goes into the policy log
+        PolicyLog::record(
+            &format!("api_config:{:?}", provider),
+            previous.as_ref().map(Self::describe_api_config),
+            config.as_ref().map_or("none".to_string(), Self::describe_api_config),
+            vec![caller_principal],
+            "set_api_config",
+        );
+        StorageManager::set_api_config(key, config.clone());
+        RegistryResult::Ok(config.map(|config| Self::api_config_info(provider, config)))
+    }
+
+    pub fn get_api_configs(caller_principal: Principal) -> RegistryResult<Vec<ApiConfigInfo>> {
+        if let Err(err) = Self::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let configs = Self::ALL_PROVIDERS
+            .iter()
+            .filter_map(|provider| {
+                StorageManager::get_api_config(Self::provider_key(provider))
+                    .map(|config| Self::api_config_info(provider.clone(), config))
+            })
+            .collect();
+        RegistryResult::Ok(configs)
+    }
+
+    fn api_config_info(provider: ApiProvider, config: ApiConfig) -> ApiConfigInfo {
+        ApiConfigInfo {
+            provider,
+            base_url: config.base_url,
+            api_key_hint: config.api_key.map(|key| Self::key_hint(&key)),
+            updated_at: config.updated_at,
+            updated_by: config.updated_by,
+        }
+    }
+
+    fn key_hint(key: &str) -> String {
+        let tail: String = key.chars().rev().take(4).collect::<Vec<_>>().into_iter().rev().collect();
+        format!("...{}", tail)
+    }
+
+    fn describe_api_config(config: &ApiConfig) -> String {
+        format!(
+            "base_url={} api_key={}",
+            config.base_url.as_deref().unwrap_or("default"),
+            config.api_key.as_deref().map_or("none".to_string(), Self::key_hint)
+        )
+    }
+
+    // Builds the outcall for an endpoint with the provider's overrides applied: the base URL
+    // replaces the first enabled endpoint's scheme and host, the key fills {api_key} and goes out as
+    // a bearer token to GitHub-style APIs
+    fn prepare_request<F>(
+        config: &Option<ApiConfig>,
+        position: usize,
+        endpoint: &ProviderEndpoint,
+        build_request: &F,
+    ) -> CanisterHttpRequestArgument
+    where
+        F: Fn(&ProviderEndpoint) -> CanisterHttpRequestArgument,
+    {
+        let mut resolved = endpoint.clone();
+        if let Some(base_url) = config.as_ref().and_then(|config| config.base_url.as_deref()) {
+            if position == 0 {
+                let rest = resolved.url_template.strip_prefix("https://").unwrap_or(&resolved.url_template);
+                let path = rest.find('/').map_or("", |index| &rest[index..]);
+                resolved.url_template = format!("{}{}", base_url, path);
+            }
+        }
+        let api_key = config.as_ref().and_then(|config| config.api_key.as_deref());
+        resolved.url_template = resolved.url_template.replace(Self::API_KEY_PLACEHOLDER, api_key.unwrap_or(""));
+
+        let mut request = build_request(&resolved);
+        if let (Some(api_key), ApiStyle::GitHubRest) = (api_key, &endpoint.api_style) {
+            request.headers.push(HttpHeader {
+                name: "Authorization".to_string(),
+                value: format!("Bearer {}", api_key),
+            });
+        }
+        request
+    }
+
+    // Outcall errors can echo the URL; keep the key out of health records and results
+    fn redact(config: &Option<ApiConfig>, error: String) -> String {
+        match config.as_ref().and_then(|config| config.api_key.as_deref()) {
+            Some(api_key) => error.replace(api_key, "***"),
+            None => error,
+        }
+    }
+
+    fn health_key(provider: &ApiProvider, endpoint_name: &str) -> String {
+        format!("{}:{}", Self::provider_key(provider), endpoint_name)
+    }
+
+    fn health_for(provider: &ApiProvider, endpoint_name: &str) -> ProviderHealth {
+        let mut health = StorageManager::get_provider_health(&Self::health_key(provider, endpoint_name))
+            .unwrap_or_else(|| ProviderHealth {
+                provider: provider.clone(),
+                endpoint_name: endpoint_name.to_string(),
+                healthy: true,
+                consecutive_failures: 0,
+                total_successes: 0,
+                total_failures: 0,
+                last_success_at: None,
+                last_failure_at: None,
+                last_error: None,
+                cooldown_until: None,
+            });
+
+        // Endpoints become eligible again once their cooldown has passed
+        if let Some(until) = health.cooldown_until {
+            health.healthy = until <= time();
+        }
+        health
+    }
+}
+
+impl ProviderEndpoint {
+    pub fn url_for(&self, query: &str) -> String {
+        self.url_template.replace("{query}", query)
+    }
+}
@@ -0,0 +1,389 @@
+use crate::api::RegistryAPI;
+use crate::audit::AuditManager;
+use crate::community::CommunityValidationManager;
+use crate::settings::SettingsManager;
+use crate::storage::StorageManager;
+use crate::types::{
+    CommunityPage, Company, RegistryResult, SearchResult, Shard, ShardImportResult, ShardLocation, ShardMap,
+    ShardSlotRange,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+// Sharding. Every company id hashes to one of SLOT_COUNT slots, and each slot lives on
+// one canister: this one unless a controller assigned it to a registered shard (another
+// registry canister running the same code). Assigning a slot only changes the map; a
+// timer then hands the slot's companies over a batch at a time, so new companies
+// created here in a slot that has moved follow on the next pass. Only the profile moves:
+// working state kept beside it (open challenges, check history) starts over on the shard.
+//
+// A slot is handed over once, from the canister that holds it; moving it on or back is
+// done from the shard it went to. locate_company says where a company lives, and the
+// cross-shard queries ask every shard and merge the answers. They are composite queries,
+// so shards must be on this canister's subnet.
+
+pub struct ShardManager;
+
+impl ShardManager {
+    pub const SLOT_COUNT: u32 = 4096;
+    const DRAIN_INTERVAL_SECS: u64 = 60;
+    const DRAIN_BATCH: usize = 25; // Whole profiles per call
+    const MAX_BATCHES_PER_DRAIN: usize = 20;
+    const MAX_SHARDS: usize = 64;
+    const MAX_IMPORT: usize = 100;
+    const MAX_ERROR_LENGTH: usize = 200;
+    const SEARCH_RESULTS_PER_SHARD: u32 = 100; // The largest page search_companies_ranked serves
+
+    pub fn slot_of(company_id: &str) -> u32 {
+        let digest = Sha256::digest(company_id.as_bytes());
+        let mut prefix = [0u8; 8];
+        prefix.copy_from_slice(&digest[..8]);
+        (u64::from_be_bytes(prefix) % Self::SLOT_COUNT as u64) as u32
+    }
+
+    // Where the slot lives; None for this canister
+    fn remote_shard(company_id: &str) -> Option<Principal> {
+        StorageManager::get_slot_shard(Self::slot_of(company_id))
+    }
+
+    // Not-found message for a lookup that may belong elsewhere
+    pub fn missing_company_error(company_id: &str) -> String {
+        match Self::remote_shard(company_id) {
+            Some(shard) => format!("Company not found; company {} is stored on shard {}", company_id, shard.to_text()),
+            None => "Company not found".to_string(),
+        }
+    }
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::DRAIN_INTERVAL_SECS), || {
+            if !StorageManager::get_remote_slots().is_empty() {
+                ic_cdk::spawn(async {
+                    let _ = Self::drain().await;
+                });
+            }
+        });
+    }
+
+    // Shard roster (controllers)
+
+    // Both sides register each other: a shard only accepts companies from registered shards
+    pub fn add_shard(canister_id: Principal, caller_principal: Principal) -> RegistryResult<Shard> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if canister_id == Principal::anonymous() || canister_id == ic_cdk::id() {
+            return RegistryResult::Err("Shard must be another registry canister".to_string());
+        }
+        if StorageManager::get_shard(&canister_id).is_some() {
+            return RegistryResult::Err("Canister is already a shard".to_string());
+        }
+        if StorageManager::get_all_shards().len() >= Self::MAX_SHARDS {
+            return RegistryResult::Err(format!("At most {} shards", Self::MAX_SHARDS));
+        }
+
+        let shard = Shard {
+            canister_id,
+            added_by: caller_principal,
+            added_at: time(),
+            moved_out: 0,
+            last_move_at: None,
+            last_error: None,
+        };
+        StorageManager::insert_shard(shard.clone());
+        AuditManager::record("shard_added", &canister_id.to_text(), vec![caller_principal], String::new());
+        RegistryResult::Ok(shard)
+    }
+
+    pub fn remove_shard(canister_id: Principal, caller_principal: Principal) -> RegistryResult<()> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if StorageManager::get_shard(&canister_id).is_none() {
+            return RegistryResult::Err("Shard not found".to_string());
+        }
+        let assigned = StorageManager::get_remote_slots()
+            .iter()
+            .filter(|(_, shard)| *shard == canister_id)
+            .count();
+        if assigned > 0 {
+            return RegistryResult::Err(format!("Shard still holds {} slots", assigned));
+        }
+        StorageManager::remove_shard(&canister_id);
+        AuditManager::record("shard_removed", &canister_id.to_text(), vec![caller_principal], String::new());
+        RegistryResult::Ok(())
+    }
+
+    // Rebalancing
+
+    // Hands slots first_slot..=last_slot to the shard; their companies move on later drains
+    pub fn assign_shard_slots(
+        first_slot: u32,
+        last_slot: u32,
+        canister_id: Principal,
+        caller_principal: Principal,
+    ) -> RegistryResult<ShardMap> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if first_slot > last_slot || last_slot >= Self::SLOT_COUNT {
+            return RegistryResult::Err(format!("Slots must be a range within 0-{}", Self::SLOT_COUNT - 1));
+        }
+        if StorageManager::get_shard(&canister_id).is_none() {
+            return RegistryResult::Err("Shard not found".to_string());
+        }
+        if let Some((slot, shard)) = StorageManager::get_remote_slots()
+            .into_iter()
+            .find(|(slot, _)| (first_slot..=last_slot).contains(slot))
+        {
+            return RegistryResult::Err(format!(
+                "Slot {} already belongs to shard {}; rebalance it from there",
+                slot,
+                shard.to_text()
+            ));
+        }
+
+        for slot in first_slot..=last_slot {
+            StorageManager::set_slot_shard(slot, &canister_id);
+        }
+        AuditManager::record(
+            "shard_slots_assigned",
+            &canister_id.to_text(),
+            vec![caller_principal],
+            format!("slots {}-{}", first_slot, last_slot),
+        );
+        RegistryResult::Ok(Self::get_shard_map())
+    }
+
+    pub async fn drain_shards(caller_principal: Principal) -> RegistryResult<u64> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        match Self::drain().await {
+            Ok(moved) => RegistryResult::Ok(moved),
+            Err(err) => RegistryResult::Err(err),
+        }
+    }
+
+    // Hands up to MAX_BATCHES_PER_DRAIN batches of companies in assigned slots to their
+    // shards; returns the number moved
+    async fn drain() -> Result<u64, String> {
+        if !StorageManager::try_begin_shard_drain() {
+            return Err("A shard drain is already running".to_string());
+        }
+        let result = Self::drain_batches().await;
+        StorageManager::end_shard_drain();
+        Ok(result)
+    }
+
+    async fn drain_batches() -> u64 {
+        // Companies from before the index existed
+        if !StorageManager::shard_slot_index_is_complete() {
+            StorageManager::rebuild_shard_slot_index();
+        }
+
+        let mut by_shard: BTreeMap<Principal, Vec<u32>> = BTreeMap::new();
+        for (slot, shard) in StorageManager::get_remote_slots() {
+            by_shard.entry(shard).or_default().push(slot);
+        }
+
+        let mut moved = 0;
+        let mut batches = 0;
+        for (shard, slots) in by_shard {
+            let mut pending = slots.into_iter();
+            let mut slot = pending.next();
+            while let Some(current) = slot {
+                if batches >= Self::MAX_BATCHES_PER_DRAIN {
+                    return moved;
+                }
+                let ids = StorageManager::get_company_ids_in_slot(current, Self::DRAIN_BATCH);
+                if ids.is_empty() {
+                    slot = pending.next();
+                    continue;
+                }
+                batches += 1;
+                match Self::hand_over(shard, ids).await {
+                    Ok(count) if count > 0 => moved += count,
+                    // Nothing accepted: move on rather than resend the same batch
+                    _ => slot = pending.next(),
+                }
+            }
+        }
+        moved
+    }
+
+    // Sends one batch and drops what the shard took, unless it changed here meanwhile
+    async fn hand_over(shard: Principal, company_ids: Vec<String>) -> Result<u64, String> {
+        let companies: Vec<Company> = company_ids.iter().filter_map(|id| StorageManager::get_company(id)).collect();
+        let sent: HashMap<String, u64> = companies.iter().map(|c| (c.id.clone(), c.updated_at)).collect();
+
+        let call: Result<(RegistryResult<ShardImportResult>,), _> =
+            ic_cdk::call(shard, "import_shard_companies", (companies,)).await;
+        let result = match call {
+            Ok((RegistryResult::Ok(result),)) => Ok(result),
+            Ok((RegistryResult::Err(err),)) => Err(err),
+            Err((code, message)) => Err(format!("{:?}: {}", code, message)),
+        };
+
+        let mut moved = 0;
+        let mut error = None;
+        match &result {
+            Ok(result) => {
+                for company_id in &result.imported {
+                    let unchanged = StorageManager::get_company(company_id)
+                        .is_some_and(|company| sent.get(company_id) == Some(&company.updated_at));
+                    if unchanged {
+                        StorageManager::remove_company(company_id);
+                        StorageManager::remove_company_dependents(company_id);
+                        moved += 1;
+                    }
+                }
+                if !result.conflicts.is_empty() {
+                    error = Some(format!("Id conflicts on shard: {}", result.conflicts.join(", ")));
+                }
+            }
+            Err(err) => error = Some(err.clone()),
+        }
+
+        if let Some(mut record) = StorageManager::get_shard(&shard) {
+            record.moved_out += moved;
+            if moved > 0 {
+                record.last_move_at = Some(time());
+            }
+            record.last_error = error.map(|err| err.chars().take(Self::MAX_ERROR_LENGTH).collect());
+            StorageManager::insert_shard(record);
+        }
+        result.map(|_| moved)
+    }
+
+    // Shard side of a hand-over. A company already here is replaced only if it is the
+    // same company (same creator and creation time); another company's id is a conflict.
+    pub fn import_shard_companies(
+        companies: Vec<Company>,
+        caller_principal: Principal,
+    ) -> RegistryResult<ShardImportResult> {
+        if StorageManager::get_shard(&caller_principal).is_none() {
+            return RegistryResult::Err("Unauthorized: Registered shard access required".to_string());
+        }
+        if companies.len() > Self::MAX_IMPORT {
+            return RegistryResult::Err(format!("At most {} companies per import", Self::MAX_IMPORT));
+        }
+
+        let mut imported = Vec::new();
+        let mut conflicts = Vec::new();
+        for company in companies {
+            let same = StorageManager::get_company(&company.id)
+                .is_none_or(|existing| existing.created_by == company.created_by && existing.created_at == company.created_at);
+            if same {
+                imported.push(company.id.clone());
+                StorageManager::insert_company(company.id.clone(), company);
+            } else {
+                conflicts.push(company.id);
+            }
+        }
+        RegistryResult::Ok(ShardImportResult { imported, conflicts })
+    }
+
+    // Queries
+
+    pub fn locate_company(company_id: String) -> ShardLocation {
+        let slot = Self::slot_of(&company_id);
+        ShardLocation {
+            stored_here: StorageManager::get_company(&company_id).is_some(),
+            canister_id: StorageManager::get_slot_shard(slot).unwrap_or_else(ic_cdk::id),
+            company_id,
+            slot,
+        }
+    }
+
+    pub fn get_shard_map() -> ShardMap {
+        let local = ic_cdk::id();
+        let remote: BTreeMap<u32, Principal> = StorageManager::get_remote_slots().into_iter().collect();
+        let mut ranges: Vec<ShardSlotRange> = Vec::new();
+        for slot in 0..Self::SLOT_COUNT {
+            let canister_id = remote.get(&slot).copied().unwrap_or(local);
+            match ranges.last_mut() {
+                Some(range) if range.canister_id == canister_id => range.last_slot = slot,
+                _ => ranges.push(ShardSlotRange {
+                    first_slot: slot,
+                    last_slot: slot,
+                    canister_id,
+                }),
+            }
+        }
+        let pending_moves = remote.keys().map(|slot| StorageManager::count_companies_in_slot(*slot)).sum();
+        let mut shards = StorageManager::get_all_shards();
+        shards.sort_by_key(|shard| shard.added_at);
+
+        ShardMap {
+            registry: local,
+            slot_count: Self::SLOT_COUNT,
+            ranges,
+            shards,
+            pending_moves,
+        }
+    }
+
+    // Cross-shard queries
+
+    // get_statistics summed over this canister and every shard
+    pub async fn get_global_statistics() -> RegistryResult<HashMap<String, u64>> {
+        let mut totals = RegistryAPI::get_statistics();
+        for shard in StorageManager::get_all_shards() {
+            let call: Result<(HashMap<String, u64>,), _> = ic_cdk::call(shard.canister_id, "get_statistics", ()).await;
+            match call {
+                Ok((stats,)) => {
+                    for (key, value) in stats {
+                        *totals.entry(key).or_insert(0) += value;
+                    }
+                }
+                Err((code, message)) => {
+                    return RegistryResult::Err(format!("Shard {}: {:?}: {}", shard.canister_id, code, message))
+                }
+            }
+        }
+        RegistryResult::Ok(totals)
+    }
+
+    // Ranked search over every shard. Each shard returns at most one full page, so only
+    // its first SEARCH_RESULTS_PER_SHARD results can be reached.
+    pub async fn search_all_shards(
+        query: String,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> RegistryResult<CommunityPage<SearchResult>> {
+        let offset = offset.unwrap_or(0);
+        let wanted = Self::SEARCH_RESULTS_PER_SHARD;
+        let local = RegistryAPI::search_companies_ranked(query.clone(), Some(0), Some(wanted));
+        let mut total = local.total;
+        let mut results = local.items;
+        for shard in StorageManager::get_all_shards() {
+            let call: Result<(CommunityPage<SearchResult>,), _> = ic_cdk::call(
+                shard.canister_id,
+                "search_companies_ranked",
+                (query.clone(), Some(0u32), Some(wanted)),
+            )
+            .await;
+            match call {
+                Ok((page,)) => {
+                    total = total.saturating_add(page.total);
+                    results.extend(page.items);
+                }
+                Err((code, message)) => {
+                    return RegistryResult::Err(format!("Shard {}: {:?}: {}", shard.canister_id, code, message))
+                }
+            }
+        }
+
+        // The same order search_companies_ranked uses on one canister
+        results.sort_by_key(|r| {
+            std::cmp::Reverse((r.relevance + r.company.verification_score / 2, r.company.created_at))
+        });
+        let mut page = CommunityValidationManager::paginate(results, Some(offset), limit);
+        page.total = total;
+        page.has_more = offset.saturating_add(page.limit) < total;
+        RegistryResult::Ok(page)
+    }
+}
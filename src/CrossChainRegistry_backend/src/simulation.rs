@@ -0,0 +1,95 @@
+use crate::sanitize;
+use crate::settings::SettingsManager;
+use crate::storage::StorageManager;
+use crate::types::{RegistryResult, SimulationFixture, SimulationStatus};
+use candid::Principal;
+
+// Simulation mode for local replicas, which usually can't reach GitHub, block explorers or the
+// RPC providers. Only compiled with the `simulation` feature: while it is on, every HTTPS
+// outcall is answered from the fixtures set here (see http_client::mock), so the full
+// challenge -> publish -> verify flow can be clicked through without a network. Fixtures
+// hold the raw provider response; the canister's own transforms still run on it.
+
+pub struct SimulationManager;
+
+impl SimulationManager {
+    const MAX_FIXTURES: usize = 200;
+    const MAX_BODY_BYTES: usize = 64 * 1024;
+
+    pub fn set_simulation_fixture(mut fixture: SimulationFixture, caller_principal: Principal) -> RegistryResult<()> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        fixture.url_prefix = fixture.url_prefix.trim().to_string();
+        if !fixture.url_prefix.starts_with("https://") {
+            return RegistryResult::Err("URL prefix must start with https://".to_string());
+        }
+        if sanitize::grapheme_len(&fixture.url_prefix) > sanitize::policy().max_url {
+            return RegistryResult::Err(format!(
+                "URL prefix exceeds maximum length of {} characters",
+                sanitize::policy().max_url
+            ));
+        }
+        if !(100..=599).contains(&fixture.status) {
+            return RegistryResult::Err("Status must be a valid HTTP status code".to_string());
+        }
+        if fixture.body.len() > Self::MAX_BODY_BYTES {
+            return RegistryResult::Err(format!("Body exceeds {} bytes", Self::MAX_BODY_BYTES));
+        }
+        fixture.body_contains = fixture.body_contains.filter(|needle| !needle.is_empty());
+
+        let key = Self::fixture_key(&fixture.url_prefix, &fixture.body_contains);
+        let exists = StorageManager::get_simulation_fixtures()
+            .iter()
+            .any(|existing| Self::fixture_key(&existing.url_prefix, &existing.body_contains) == key);
+        if !exists && StorageManager::get_simulation_fixtures().len() >= Self::MAX_FIXTURES {
+            return RegistryResult::Err(format!("At most {} fixtures; clear some first", Self::MAX_FIXTURES));
+        }
+        StorageManager::insert_simulation_fixture(key, fixture);
+        RegistryResult::Ok(())
+    }
+
+    pub fn remove_simulation_fixture(
+        url_prefix: String,
+        body_contains: Option<String>,
+        caller_principal: Principal,
+    ) -> RegistryResult<()> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let body_contains = body_contains.filter(|needle| !needle.is_empty());
+        if StorageManager::remove_simulation_fixture(&Self::fixture_key(url_prefix.trim(), &body_contains)) {
+            RegistryResult::Ok(())
+        } else {
+            RegistryResult::Err("Fixture not found".to_string())
+        }
+    }
+
+    pub fn clear_simulation_fixtures(caller_principal: Principal) -> RegistryResult<()> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        StorageManager::clear_simulation_fixtures();
+        RegistryResult::Ok(())
+    }
+
+    // Off sends outcalls to the real providers again without dropping the fixtures
+    pub fn set_simulation_mode(enabled: bool, caller_principal: Principal) -> RegistryResult<()> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        StorageManager::set_simulation_enabled(enabled);
+        RegistryResult::Ok(())
+    }
+
+    pub fn get_simulation_status() -> SimulationStatus {
+        SimulationStatus {
+            enabled: StorageManager::is_simulation_enabled(),
+            fixtures: StorageManager::get_simulation_fixtures(),
+        }
+    }
+
+    fn fixture_key(url_prefix: &str, body_contains: &Option<String>) -> String {
+        format!("{}|{}", url_prefix, body_contains.as_deref().unwrap_or(""))
+    }
+}
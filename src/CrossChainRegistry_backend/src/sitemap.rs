@@ -0,0 +1,168 @@
+use crate::admins::AdminManager;
+use crate::settings::SettingsManager;
+use crate::storage::StorageManager;
+use crate::types::{Company, CompanyEventKind, CompanyStatus, HttpResponse, RegistryResult};
+use candid::Principal;
+use std::time::Duration;
+
+// /sitemap.xml for the frontend's company profile pages. Only verified and trusted
+// companies are listed. The entry set is kept in stable memory and maintained from the
+// company event journal, so each run touches just the companies written since the last.
+
+pub struct SitemapManager;
+
+impl SitemapManager {
+    pub const SITEMAP_PATH: &'static str = "/sitemap.xml";
+    const JOURNAL_CONSUMER: &'static str = "sitemap";
+    const PROFILE_ROUTE: &'static str = "/company/";
+    const EVENTS_PER_RUN: usize = 500;
+    const UPDATE_INTERVAL_SECS: u64 = 10 * 60;
+    const MAX_URLS: usize = 50_000; // Per-file limit of the sitemap protocol
+    const CACHE_MAX_AGE_SECS: u64 = 60 * 60;
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer(Duration::ZERO, || {
+            Self::process_journal();
+        });
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::UPDATE_INTERVAL_SECS), || {
+            Self::process_journal();
+        });
+    }
+
+    fn is_listed(company: &Company) -> bool {
+        matches!(company.status, CompanyStatus::Verified | CompanyStatus::Trusted)
+    }
+
+    // Full scan, used the first time round and whenever an admin asks for it
+    pub fn rebuild() -> u32 {
+        let cursor = StorageManager::latest_company_event_seq();
+        StorageManager::clear_sitemap_entries();
+        let mut listed = 0;
+        for company in StorageManager::get_all_companies() {
+            if Self::is_listed(&company) {
+                StorageManager::set_sitemap_entry(&company.id, company.updated_at);
+                listed += 1;
+            }
+        }
+        StorageManager::set_journal_cursor(Self::JOURNAL_CONSUMER, cursor);
+        listed
+    }
+
+    // Apply journal events since the last run; returns the number of events consumed
+    pub fn process_journal() -> u32 {
+        let cursor = StorageManager::get_journal_cursor(Self::JOURNAL_CONSUMER);
+        if cursor == 0 && StorageManager::get_sitemap_entries().is_empty() {
+            Self::rebuild();
+            return 0;
+        }
+
+        let events = StorageManager::get_company_events_after(cursor, Self::EVENTS_PER_RUN);
+        let last_seq = match events.last() {
+            Some(event) => event.seq,
+            None => return 0,
+        };
+        for event in &events {
+            let company = match event.kind {
+                CompanyEventKind::Deleted => None,
+                _ => StorageManager::get_company(&event.company_id),
+            };
+            match company {
+                Some(company) if Self::is_listed(&company) => {
+                    StorageManager::set_sitemap_entry(&company.id, company.updated_at)
+                }
+                _ => StorageManager::remove_sitemap_entry(&event.company_id),
+            }
+        }
+        StorageManager::set_journal_cursor(Self::JOURNAL_CONSUMER, last_seq);
+        events.len() as u32
+    }
+
+    pub fn set_sitemap_base_url(base_url: String, caller_principal: Principal) -> RegistryResult<()> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let base_url = base_url.trim().trim_end_matches('/').to_string();
+        if !base_url.starts_with("https://") || base_url.len() <= "https://".len() {
+            return RegistryResult::Err("Base URL must be an https:// origin".to_string());
+        }
+        StorageManager::set_sitemap_base_url(base_url);
+        RegistryResult::Ok(())
+    }
+
+    pub fn rebuild_sitemap(caller_principal: Principal) -> RegistryResult<u32> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        RegistryResult::Ok(Self::rebuild())
+    }
+
+    pub fn http_request() -> HttpResponse {
+        let base_url = match StorageManager::get_sitemap_base_url() {
+            Some(base_url) => base_url,
+            None => {
+                return HttpResponse {
+                    status_code: 503,
+                    headers: vec![("Content-Type".to_string(), "text/plain; charset=utf-8".to_string())],
+                    body: b"Sitemap base URL not configured".to_vec(),
+                    upgrade: None,
+                }
+            }
+        };
+
+        let mut entries = StorageManager::get_sitemap_entries();
+        entries.sort_by_key(|(_, last_modified)| std::cmp::Reverse(*last_modified));
+        entries.truncate(Self::MAX_URLS);
+
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+        );
+        for (company_id, last_modified) in entries {
+            xml.push_str(&format!(
+                "  <url><loc>{}{}{}</loc><lastmod>{}</lastmod></url>\n",
+                Self::escape_xml(&base_url),
+                Self::PROFILE_ROUTE,
+                Self::escape_xml(&company_id),
+                Self::iso_date(last_modified)
+            ));
+        }
+        xml.push_str("</urlset>\n");
+
+        HttpResponse {
+            status_code: 200,
+            headers: vec![
+                ("Content-Type".to_string(), "application/xml; charset=utf-8".to_string()),
+                ("Cache-Control".to_string(), format!("public, max-age={}", Self::CACHE_MAX_AGE_SECS)),
+            ],
+            body: xml.into_bytes(),
+            upgrade: None,
+        }
+    }
+
+    // Public profile page, once the base URL is configured
+    pub fn profile_url(company_id: &str) -> Option<String> {
+        StorageManager::get_sitemap_base_url()
+            .map(|base_url| format!("{}{}{}", base_url, Self::PROFILE_ROUTE, company_id))
+    }
+
+    fn escape_xml(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&apos;")
+    }
+
+    // YYYY-MM-DD from nanoseconds since the epoch (Howard Hinnant's civil-from-days)
+    fn iso_date(timestamp_ns: u64) -> String {
+        let days = (timestamp_ns / 1_000_000_000 / 86_400) as i64 + 719_468;
+        let era = days / 146_097;
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    }
+}
@@ -0,0 +1,376 @@
+use crate::admins::AdminManager;
+use crate::community::CommunityValidationManager;
+use crate::ledger::{LedgerClient, LedgerError};
+use crate::ownership::OwnershipManager;
+use crate::reputation::{ReputationInputs, ReputationJournal};
+use crate::storage::StorageManager;
+use crate::types::{
+    PendingPayout, RegistryResult, ReputationEventKind, ReputationStake, ReputationStakingConfig,
+};
+use candid::{Nat, Principal};
+use ic_cdk::api::time;
+
+// Reputation staking backed by ICRC tokens. A company owner approves this canister on the
+// configured ledger (icrc2_approve) and stakes; the tokens are pulled into the canister's
+// account and the company's reputation_staked total grows by the same amount. Balances
+// are kept per company and staker, so after an ownership transfer each stake still goes
+// back to whoever paid it. Unstaking takes effect on the score at once, but the tokens
+// only become claimable after the cool-down, so a stake can't be flashed in for a
+// listing decision and pulled right after.
+//
+// Totals staked before the ledger integration are unbacked; they still count toward the
+// score but can't be withdrawn.
+
+pub struct ReputationStakingManager;
+
+impl ReputationStakingManager {
+    const DEFAULT_TRANSFER_FEE: u64 = 10_000;
+    const DEFAULT_UNSTAKE_COOLDOWN_DAYS: u32 = 14;
+    const MAX_UNSTAKE_COOLDOWN_DAYS: u32 = 365;
+    const DAY_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+    // Configuration
+
+    pub fn get_reputation_staking_config() -> ReputationStakingConfig {
+        StorageManager::get_reputation_staking_config().unwrap_or(ReputationStakingConfig {
+            ledger_canister_id: None,
+            transfer_fee: Self::DEFAULT_TRANSFER_FEE,
+            unstake_cooldown_days: Self::DEFAULT_UNSTAKE_COOLDOWN_DAYS,
+        })
+    }
+
+    // Changes go through the admin timelock (TimelockAction::ReputationStakingConfig)
+    pub fn validate_reputation_staking_config(config: &ReputationStakingConfig) -> Result<(), String> {
+        if config.transfer_fee >= ReputationInputs::MAX_REPUTATION_STAKE {
+            return Err("Transfer fee must be below the per-company stake cap".to_string());
+        }
+        if config.unstake_cooldown_days > Self::MAX_UNSTAKE_COOLDOWN_DAYS {
+            return Err(format!(
+                "Unstake cool-down must be at most {} days",
+                Self::MAX_UNSTAKE_COOLDOWN_DAYS
+            ));
+        }
+        Ok(())
+    }
+
+    fn ledger(config: &ReputationStakingConfig) -> Result<Principal, String> {
+        config
+            .ledger_canister_id
+            .ok_or_else(|| "Reputation staking is not configured".to_string())
+    }
+
+    // New total after staking `amount` more, or why the stake is out of range
+    fn checked_stake_total(current: u64, amount: u64) -> Result<u64, String> {
+        let max = ReputationInputs::MAX_REPUTATION_STAKE;
+        if amount > max {
+            return Err(format!("Stake amount must be at most {}", max));
+        }
+        match current.checked_add(amount) {
+            Some(total) if total <= max => Ok(total),
+            _ => Err(format!(
+                "Stake would exceed the per-company cap of {} ({} already staked)",
+                max, current
+            )),
+        }
+    }
+
+    // Moves the company's total and records the change in the reputation journal
+    fn apply_stake_change(
+        company_id: &str,
+        change: impl FnOnce(u64) -> Result<u64, String>,
+        actor: Principal,
+    ) -> Result<u64, String> {
+        let mut result = Err("Company not found".to_string());
+        StorageManager::update_company(company_id, |company| {
+            result = change(company.community_validation.reputation_staked);
+            if let Ok(total) = result {
+                company.community_validation.reputation_staked = total;
+                CommunityValidationManager::update_reputation_score(company);
+            }
+        });
+        if let Ok(reputation_staked) = result {
+            ReputationJournal::record(
+                company_id,
+                ReputationEventKind::StakeChanged { reputation_staked },
+                Some(actor),
+            );
+        }
+        result
+    }
+
+    // Staking
+
+    // Requires a prior icrc2_approve of `amount` plus the ledger fee for this canister. A
+    // stake whose ledger outcome is unknown stays recorded; staking the same amount again
+    // resends it unchanged, so the ledger deduplicates it instead of pulling twice.
+    pub async fn stake_reputation(
+        company_id: String,
+        amount: u64,
+        caller_principal: Principal,
+    ) -> RegistryResult<ReputationStake> {
+        // Validate that company exists and caller is authorized
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+        let config = Self::get_reputation_staking_config();
+        let ledger = match Self::ledger(&config) {
+            Ok(ledger) => ledger,
+            Err(err) => return RegistryResult::Err(err),
+        };
+        let existing = StorageManager::get_reputation_stake(&company_id, caller_principal);
+
+        let intake = match existing.as_ref().and_then(|stake| stake.pending_stake.clone()) {
+            // The tokens may already have moved, so the retry skips the checks below
+            Some(intake) => {
+                if intake.amount != amount {
+                    return RegistryResult::Err(format!(
+                        "An earlier stake of {} is not confirmed by the ledger yet; stake {} again to retry it",
+                        intake.amount, intake.amount
+                    ));
+                }
+                intake
+            }
+            None => {
+                if !OwnershipManager::is_owner(&company, caller_principal) {
+                    return RegistryResult::Err(
+                        "Unauthorized: Only company owner can stake reputation".to_string(),
+                    );
+                }
+                if let Err(err) = AdminManager::ensure_not_suspended(&company) {
+                    return RegistryResult::Err(err);
+                }
+
+                if amount == 0 {
+                    return RegistryResult::Err("Stake amount must be greater than 0".to_string());
+                }
+                if let Err(err) = Self::checked_stake_total(company.community_validation.reputation_staked, amount) {
+                    return RegistryResult::Err(err);
+                }
+
+                // Recorded before the call so a lost reply can be retried with the same key
+                let now = time();
+                let intake = LedgerClient::pending_intake(&format!("stake:{}", company_id), caller_principal, amount, now);
+                let mut stake = existing.unwrap_or(ReputationStake {
+                    company_id: company_id.clone(),
+                    staker: caller_principal,
+                    staked: 0,
+                    pending_unstake: 0,
+                    unstake_available_at: None,
+                    staked_at: now,
+                    updated_at: now,
+                    pending_payout: None,
+                    pending_stake: None,
+                });
+                stake.pending_stake = Some(intake.clone());
+                StorageManager::insert_reputation_stake(stake);
+                intake
+            }
+        };
+
+        let result = LedgerClient::pull(ledger, caller_principal, &intake).await;
+        if let Err(LedgerError::Unknown(err)) = result {
+            return RegistryResult::Err(format!("{}; stake again to retry the same transfer", err));
+        }
+
+        // Settled either way. Only the call that clears the record credits it, so two
+        // retries of the same intake can't both count it.
+        let mut stake = match StorageManager::get_reputation_stake(&company_id, caller_principal) {
+            Some(stake)
+                if stake.pending_stake.as_ref().is_some_and(|p| p.created_at_time == intake.created_at_time) =>
+            {
+                stake
+            }
+            _ => return RegistryResult::Err("This stake was already settled by another call".to_string()),
+        };
+        stake.pending_stake = None;
+        stake.updated_at = time();
+        if let Err(err) = result {
+            Self::store_or_drop(stake);
+            return RegistryResult::Err(err.to_string());
+        }
+
+        // Re-checked against the stored total in case another stake landed while the
+        // transfer was out; if it no longer fits, the tokens go back
+        if let Err(err) = Self::apply_stake_change(
+            &company_id,
+            |current| Self::checked_stake_total(current, intake.amount),
+            caller_principal,
+        ) {
+            return RegistryResult::Err(Self::refund_stake(ledger, &config, stake, intake.amount, err).await);
+        }
+
+        stake.staked = stake.staked.saturating_add(intake.amount);
+        StorageManager::insert_reputation_stake(stake.clone());
+        RegistryResult::Ok(stake)
+    }
+
+    // Returns a stake that was pulled but could not be credited. The refund is recorded as
+    // the stake's pending payout first, so if it fails it can be retried through
+    // claim_unstaked_reputation; when a payout is already pending (or the amount doesn't
+    // cover the fee) it is added to pending_unstake instead. Returns the error to report.
+    async fn refund_stake(
+        ledger: Principal,
+        config: &ReputationStakingConfig,
+        mut stake: ReputationStake,
+        amount: u64,
+        reason: String,
+    ) -> String {
+        if stake.pending_payout.is_some() || amount <= config.transfer_fee {
+            stake.pending_unstake = stake.pending_unstake.saturating_add(amount);
+            StorageManager::insert_reputation_stake(stake);
+            return format!("{}; the tokens were added to your pending unstake", reason);
+        }
+
+        let payout = LedgerClient::pending_payout(
+            &format!("stake_refund:{}", stake.company_id),
+            stake.staker,
+            amount,
+            config.transfer_fee,
+            time(),
+        );
+        let (company_id, staker) = (stake.company_id.clone(), stake.staker);
+        stake.pending_payout = Some(payout.clone());
+        StorageManager::insert_reputation_stake(stake);
+
+        match Self::send_payout(ledger, &company_id, staker, &payout).await {
+            Ok(_) => format!("{}; the tokens were refunded", reason),
+            Err(LedgerError::Unknown(err)) => {
+                format!("{}; the refund is pending ({}), claim unstaked reputation to retry it", reason, err)
+            }
+            Err(err) => format!("{}; the refund failed ({}), claim unstaked reputation to retry it", reason, err),
+        }
+    }
+
+    // Starts the cool-down for `amount` of the caller's stake. Asking again adds to the
+    // pending amount and restarts the cool-down.
+    pub fn unstake_reputation(
+        company_id: String,
+        amount: u64,
+        caller_principal: Principal,
+    ) -> RegistryResult<ReputationStake> {
+        let mut stake = match StorageManager::get_reputation_stake(&company_id, caller_principal) {
+            Some(stake) => stake,
+            None => return RegistryResult::Err("No stake from this principal".to_string()),
+        };
+        if amount == 0 || amount > stake.staked {
+            return RegistryResult::Err(format!("Unstake amount must be 1-{}", stake.staked));
+        }
+        let unstaked = Self::apply_stake_change(&company_id, |current| Ok(current.saturating_sub(amount)), caller_principal);
+        if let Err(err) = unstaked {
+            return RegistryResult::Err(err);
+        }
+
+        let config = Self::get_reputation_staking_config();
+        let now = time();
+        stake.staked -= amount;
+        stake.pending_unstake = stake.pending_unstake.saturating_add(amount);
+        stake.unstake_available_at = Some(now.saturating_add(config.unstake_cooldown_days as u64 * Self::DAY_NS));
+        stake.updated_at = now;
+        StorageManager::insert_reputation_stake(stake.clone());
+        RegistryResult::Ok(stake)
+    }
+
+    // Pays out the unstaked amount once the cool-down is over, minus the ledger fee. A claim
+    // whose ledger outcome is unknown stays recorded; claiming again resends it unchanged,
+    // so the ledger deduplicates it instead of paying twice.
+    pub async fn claim_unstaked_reputation(company_id: String, caller_principal: Principal) -> RegistryResult<Nat> {
+        let config = Self::get_reputation_staking_config();
+        let ledger = match Self::ledger(&config) {
+            Ok(ledger) => ledger,
+            Err(err) => return RegistryResult::Err(err),
+        };
+        let mut stake = match StorageManager::get_reputation_stake(&company_id, caller_principal) {
+            Some(stake) => stake,
+            None => return RegistryResult::Err("No stake from this principal".to_string()),
+        };
+
+        let payout = match stake.pending_payout.clone() {
+            Some(payout) => payout,
+            None => {
+                let pending = stake.pending_unstake;
+                if pending == 0 {
+                    return RegistryResult::Err("Nothing is being unstaked".to_string());
+                }
+                let now = time();
+                if let Some(available_at) = stake.unstake_available_at {
+                    if now < available_at {
+                        return RegistryResult::Err(format!(
+                            "Unstaked tokens become claimable in {} hours",
+                            (available_at - now).div_ceil(60 * 60 * 1_000_000_000)
+                        ));
+                    }
+                }
+                if pending <= config.transfer_fee {
+                    return RegistryResult::Err("Unstaked amount does not cover the ledger fee".to_string());
+                }
+
+                // Debit before the call so a concurrent claim cannot pay out twice
+                let payout = LedgerClient::pending_payout(
+                    &format!("unstake:{}", company_id),
+                    caller_principal,
+                    pending,
+                    config.transfer_fee,
+                    now,
+                );
+                stake.pending_unstake = 0;
+                stake.unstake_available_at = None;
+                stake.pending_payout = Some(payout.clone());
+                StorageManager::insert_reputation_stake(stake);
+                payout
+            }
+        };
+
+        match Self::send_payout(ledger, &company_id, caller_principal, &payout).await {
+            Ok(block_index) => RegistryResult::Ok(block_index),
+            Err(LedgerError::Unknown(err)) => {
+                RegistryResult::Err(format!("{}; claim again to retry the same transfer", err))
+            }
+            Err(err) => RegistryResult::Err(err.to_string()),
+        }
+    }
+
+    // Sends the stake's recorded payout. An unknown outcome leaves it recorded for the next
+    // claim; otherwise the record is cleared, and on a rejection the tokens go back to
+    // pending_unstake (staying claimable, as the cool-down already passed).
+    async fn send_payout(
+        ledger: Principal,
+        company_id: &str,
+        staker: Principal,
+        payout: &PendingPayout,
+    ) -> Result<Nat, LedgerError> {
+        let result = LedgerClient::pay(ledger, staker, payout).await;
+        if matches!(result, Err(LedgerError::Unknown(_))) {
+            return result;
+        }
+
+        if let Some(mut stake) = StorageManager::get_reputation_stake(company_id, staker) {
+            if stake.pending_payout.as_ref().is_some_and(|p| p.created_at_time == payout.created_at_time) {
+                stake.pending_payout = None;
+                if result.is_err() {
+                    stake.pending_unstake = stake.pending_unstake.saturating_add(payout.amount);
+                }
+                stake.updated_at = time();
+                Self::store_or_drop(stake);
+            }
+        }
+        result
+    }
+
+    // An emptied stake has nothing left to track
+    fn store_or_drop(stake: ReputationStake) {
+        if stake.staked == 0
+            && stake.pending_unstake == 0
+            && stake.pending_payout.is_none()
+            && stake.pending_stake.is_none()
+        {
+            StorageManager::remove_reputation_stake(&stake.company_id, stake.staker);
+        } else {
+            StorageManager::insert_reputation_stake(stake);
+        }
+    }
+
+    pub fn get_reputation_stakes(company_id: String) -> Vec<ReputationStake> {
+        StorageManager::get_reputation_stakes_for_company(&company_id)
+    }
+}
@@ -1,9 +1,35 @@
-use crate::types::{Company, DomainVerificationChallenge, CrossChainChallenge};
+use crate::crosschain::CrossChainVerifier;
+use crate::jobs::JobRegistry;
+use crate::public_api::CachedResponse;
+use crate::search_index::SearchIndex;
+use crate::sharding::ShardManager;
+use crate::security::SecurityLog;
+use crate::usage::UsageTracker;
+use crate::types::{
+    Company, DomainVerificationChallenge, CrossChainChallenge, ProviderEndpointList, ProviderHealth, ApiConfig,
+    CompanyRelationship, GrantAttestation, GrantProgram, ImpersonationReport, ReservedName,
+    CanisterSnapshot, CommunityAlert, ContractWatch, MonitoringConfig, Notification,
+    TreasurySnapshot, AlertRoutingRule, ModerationTask, Moderator, ModeratorNoteThread, PrivateMessage, ReviewCase, Reviewer,
+    ReviewerPoolConfig, ScoreWeights, TimelockConfig, TimelockedAction, CriticalActionProposal,
+    BlocklistEntry, AuditLogEntry, OpenDataSnapshot, CompanyEvent, CompanyEventKind, SavedSearch,
+    TagProposal, UsageStats, ApiKey, CacheInvalidationHook, RateLimitQuota, SecurityEvent,
+    SecurityEventType, VerificationFailures, FeatureFlag, DataMigration, ArchivedCompany,
+    ReputationEvent, InactivityPolicy, OwnershipTransfer, ReverificationCampaign, Admin, Partner,
+    PartnerSubscription, CompanyStatusChange, ListingSnapshot, RateLimitBucket, RateLimitWindow,
+    ChainCounters, ChainContribution, RegistryIdentity, ProofMonitoring, CommunityCooldownPolicy,
+    Concern, WebhookSubscription, WebhookEvent, ReputationStakingConfig, ReputationStake,
+    PolicyChange, FederationPeer, FederatedAttestation, ReplicaInfo, ReplicationState, Shard,
+    ReputationEscrowPolicy, TierDefinitions, EvmSigner, EvmAttestation,
+    EvmBridgeConfig, EvmRootSnapshot, EmergencySuspension,
+};
+#[cfg(feature = "simulation")]
+use crate::types::SimulationFixture;
 use ic_cdk::api::time;
+use ic_cdk_timers::TimerId;
 use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
 use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use candid::Principal;
 
 type Memory = VirtualMemory<DefaultMemoryImpl>;
@@ -32,115 +58,2894 @@ thread_local! {
         )
     );
 
-    // Rate limiting storage (in-memory, resets on canister upgrade)
-    static HTTP_RATE_LIMITS: RefCell<HashMap<Principal, Vec<u64>>> = RefCell::new(HashMap::new());
+    static PROVIDER_ENDPOINTS: RefCell<StableBTreeMap<String, ProviderEndpointList, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(3)))
+        )
+    );
+
+    static RESERVED_NAMES: RefCell<StableBTreeMap<String, ReservedName, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(4)))
+        )
+    );
+
+    static RELATIONSHIPS: RefCell<StableBTreeMap<String, CompanyRelationship, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(5)))
+        )
+    );
+
+    static GRANT_PROGRAMS: RefCell<StableBTreeMap<String, GrantProgram, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(6)))
+        )
+    );
+
+    static GRANT_ATTESTATIONS: RefCell<StableBTreeMap<String, GrantAttestation, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(7)))
+        )
+    );
+
+    static IMPERSONATION_REPORTS: RefCell<StableBTreeMap<String, ImpersonationReport, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(8)))
+        )
+    );
+
+    static COMMUNITY_ALERTS: RefCell<StableBTreeMap<String, CommunityAlert, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(9)))
+        )
+    );
+
+    static CONTRACT_WATCHES: RefCell<StableBTreeMap<String, ContractWatch, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(10)))
+        )
+    );
+
+    static CANISTER_SNAPSHOTS: RefCell<StableBTreeMap<String, CanisterSnapshot, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(11)))
+        )
+    );
+
+    static TREASURY_SNAPSHOTS: RefCell<StableBTreeMap<String, TreasurySnapshot, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(12)))
+        )
+    );
+
+    static MONITORING_CONFIG: RefCell<StableBTreeMap<String, MonitoringConfig, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(13)))
+        )
+    );
+
+    static NOTIFICATIONS: RefCell<StableBTreeMap<String, Notification, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(14)))
+        )
+    );
+
+    static ALERT_ROUTING_RULES: RefCell<StableBTreeMap<String, AlertRoutingRule, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(15)))
+        )
+    );
+
+    static MODERATION_TASKS: RefCell<StableBTreeMap<String, ModerationTask, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(16)))
+        )
+    );
+
+    static MODERATORS: RefCell<StableBTreeMap<String, Moderator, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(17)))
+        )
+    );
+
+    static REVIEWER_POOL_CONFIG: RefCell<StableBTreeMap<String, ReviewerPoolConfig, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(18)))
+        )
+    );
+
+    static REVIEWERS: RefCell<StableBTreeMap<String, Reviewer, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(19)))
+        )
+    );
+
+    static REVIEW_CASES: RefCell<StableBTreeMap<String, ReviewCase, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(20)))
+        )
+    );
+
+    static TIMELOCKED_ACTIONS: RefCell<StableBTreeMap<String, TimelockedAction, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(21)))
+        )
+    );
+
+    static TIMELOCK_CONFIG: RefCell<StableBTreeMap<String, TimelockConfig, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(22)))
+        )
+    );
+
+    static SCORE_WEIGHT_OVERRIDES: RefCell<StableBTreeMap<String, ScoreWeights, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(23)))
+        )
+    );
+
+    static CRITICAL_ACTION_PROPOSALS: RefCell<StableBTreeMap<String, CriticalActionProposal, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(24)))
+        )
+    );
+
+    static BLOCKLIST: RefCell<StableBTreeMap<String, BlocklistEntry, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(25)))
+        )
+    );
+
+    static AUDIT_LOG: RefCell<StableBTreeMap<String, AuditLogEntry, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(26)))
+        )
+    );
+
+    static OPEN_DATA_SNAPSHOT: RefCell<StableBTreeMap<String, OpenDataSnapshot, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(27)))
+        )
+    );
+
+    static COMPANY_EVENTS: RefCell<StableBTreeMap<u64, CompanyEvent, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(28)))
+        )
+    );
+
+    static SAVED_SEARCHES: RefCell<StableBTreeMap<String, SavedSearch, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(29)))
+        )
+    );
+
+    // Journal consumer positions, keyed by consumer name
+    static JOURNAL_CURSORS: RefCell<StableBTreeMap<String, u64, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(30)))
+        )
+    );
+
+    static TAG_PROPOSALS: RefCell<StableBTreeMap<String, TagProposal, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(31)))
+        )
+    );
+
+    static USAGE_STATS: RefCell<StableBTreeMap<String, UsageStats, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(32)))
+        )
+    );
+
+    // Keyed by key hash
+    static API_KEYS: RefCell<StableBTreeMap<String, ApiKey, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(33)))
+        )
+    );
+
+    // Public profile ids in the sitemap -> last modification time
+    static SITEMAP_ENTRIES: RefCell<StableBTreeMap<String, u64, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(34)))
+        )
+    );
+
+    static SITEMAP_SETTINGS: RefCell<StableBTreeMap<String, String, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(35)))
+        )
+    );
+
+    static CACHE_INVALIDATION_HOOK: RefCell<StableBTreeMap<String, CacheInvalidationHook, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(36)))
+        )
+    );
+
+    static SECURITY_EVENTS: RefCell<StableBTreeMap<u64, SecurityEvent, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(37)))
+        )
+    );
+
+    static FEATURE_FLAGS: RefCell<StableBTreeMap<String, FeatureFlag, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(39)))
+        )
+    );
+
+    // Secondary index over COMPANIES: reputation_index_key(score, id) -> company id, so
+    // iteration runs from the highest score down
+    static REPUTATION_INDEX: RefCell<StableBTreeMap<String, String, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(40)))
+        )
+    );
+
+    // Inverted index over company text: "token\0company_id" -> SearchIndex::FIELD_* bits
+    static SEARCH_POSTINGS: RefCell<StableBTreeMap<String, u8, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(41)))
+        )
+    );
+
+    // Companies covered by SEARCH_POSTINGS, with their distinct token count
+    static SEARCH_DOCUMENTS: RefCell<StableBTreeMap<String, u32, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(42)))
+        )
+    );
+
+    static DATA_MIGRATIONS: RefCell<StableBTreeMap<String, DataMigration, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(43)))
+        )
+    );
+
+    // Companies moved out of COMPANIES by archive_company / delete_company
+    static ARCHIVED_COMPANIES: RefCell<StableBTreeMap<String, ArchivedCompany, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(44)))
+        )
+    );
+
+    // Reputation journal: "company_id:seq" (seq zero-padded) -> event
+    static REPUTATION_EVENTS: RefCell<StableBTreeMap<String, ReputationEvent, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(45)))
+        )
+    );
+
+    static INACTIVITY_POLICY: RefCell<StableBTreeMap<String, InactivityPolicy, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(46)))
+        )
+    );
+
+    // Pending ownership transfers keyed by company ID (at most one per company)
+    static OWNERSHIP_TRANSFERS: RefCell<StableBTreeMap<String, OwnershipTransfer, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(47)))
+        )
+    );
+
+    static REVERIFICATION_CAMPAIGNS: RefCell<StableBTreeMap<String, ReverificationCampaign, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(48)))
+        )
+    );
+
+    // Registry admins keyed by principal text
+    static ADMINS: RefCell<StableBTreeMap<String, Admin, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(49)))
+        )
+    );
+
+    // Listing partners keyed by principal text
+    static PARTNERS: RefCell<StableBTreeMap<String, Partner, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(50)))
+        )
+    );
+
+    // Keyed by "<company_id>|<partner principal>" so a company's subscribers are one range
+    static PARTNER_SUBSCRIPTIONS: RefCell<StableBTreeMap<String, PartnerSubscription, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(51)))
+        )
+    );
+
+    // Per-partner outbox keyed by "<partner principal>:<seq>"
+    static PARTNER_STATUS_CHANGES: RefCell<StableBTreeMap<String, CompanyStatusChange, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(52)))
+        )
+    );
+
+    // Rate-limit window counters keyed by principal text; stable so an upgrade doesn't
+    // hand everyone a fresh allowance
+    static RATE_LIMITS: RefCell<StableBTreeMap<String, RateLimitBucket, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(53)))
+        )
+    );
+
+    // Per-chain statistics counters keyed by chain name
+    static CHAIN_COUNTERS: RefCell<StableBTreeMap<String, ChainCounters, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(54)))
+        )
+    );
+
+    // Each company's share of the chain counters, keyed by company id
+    static CHAIN_CONTRIBUTIONS: RefCell<StableBTreeMap<String, ChainContribution, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(55)))
+        )
+    );
+
+    // Verification targets ("chain:company_id:address") that passed a cross-chain check,
+    // with the time they passed
+    static VERIFIED_CHAIN_ADDRESSES: RefCell<StableBTreeMap<String, u64, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(56)))
+        )
+    );
+
+    // Last start time of each background monitoring job, keyed by job name
+    static MONITORING_RUNS: RefCell<StableBTreeMap<String, u64, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(57)))
+        )
+    );
+
+    // Provider health carried across an upgrade: written in pre_upgrade, moved back to
+    // the heap and cleared in post_upgrade
+    static PROVIDER_HEALTH_SNAPSHOT: RefCell<StableBTreeMap<String, ProviderHealth, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(58)))
+        )
+    );
+
+    // Canisters and domains of the registry itself, under the key "registry"
+    static REGISTRY_IDENTITY: RefCell<StableBTreeMap<String, RegistryIdentity, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(59)))
+        )
+    );
+
+    // When monitoring last re-fetched each social proof, keyed "company_id:proof_id"
+    static PROOF_CHECKS: RefCell<StableBTreeMap<String, u64, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(60)))
+        )
+    );
+
+    // Check history per proof, keyed "company_id:proof_id"
+    static PROOF_MONITORING: RefCell<StableBTreeMap<String, ProofMonitoring, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(61)))
+        )
+    );
+
+    static COMMUNITY_COOLDOWN_POLICY: RefCell<StableBTreeMap<String, CommunityCooldownPolicy, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(62)))
+        )
+    );
+
+    // When a principal last vouched for a company ("vouch:principal:company_id") and when an
+    // endorsement was removed ("unendorse:company_id:endorser_company_id")
+    static COMMUNITY_COOLDOWNS: RefCell<StableBTreeMap<String, u64, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(63)))
+        )
+    );
+
+    // Concerns keyed "company_id:principal", one per principal and company
+    static CONCERNS: RefCell<StableBTreeMap<String, Concern, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(64)))
+        )
+    );
+
+    static WEBHOOK_SUBSCRIPTIONS: RefCell<StableBTreeMap<String, WebhookSubscription, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(65)))
+        )
+    );
+
+    // Alerts and security events waiting for webhook delivery, keyed by sequence number
+    static WEBHOOK_OUTBOX: RefCell<StableBTreeMap<u64, WebhookEvent, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(66)))
+        )
+    );
+
+    static REPUTATION_STAKING_CONFIG: RefCell<StableBTreeMap<String, ReputationStakingConfig, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(67)))
+        )
+    );
+
+    // Ledger-backed reputation stakes keyed "company_id:staker"
+    static REPUTATION_STAKES: RefCell<StableBTreeMap<String, ReputationStake, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(68)))
+        )
+    );
+
+    // Public policy changelog keyed by sequence number
+    static POLICY_CHANGES: RefCell<StableBTreeMap<u64, PolicyChange, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(69)))
+        )
+    );
+
+    // Stable proof id -> id of the company holding the proof
+    static PROOF_INDEX: RefCell<StableBTreeMap<String, String, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(70)))
+        )
+    );
+
+    static FEDERATION_PEERS: RefCell<StableBTreeMap<String, FederationPeer, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(71)))
+        )
+    );
+
+    // Keyed by "<peer principal>|<company_id>" so a peer's attestations are one range
+    static FEDERATED_ATTESTATIONS: RefCell<StableBTreeMap<String, FederatedAttestation, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(72)))
+        )
+    );
+
+    // Read replicas registered with this registry, keyed by canister id text
+    static REPLICAS: RefCell<StableBTreeMap<String, ReplicaInfo, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(73)))
+        )
+    );
+
+    // Present only on a read replica, under "replica"
+    static REPLICATION_STATE: RefCell<StableBTreeMap<String, ReplicationState, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(74)))
+        )
+    );
+
+    static SHARDS: RefCell<StableBTreeMap<String, Shard, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(75)))
+        )
+    );
+
+    // Slot -> shard canister id text; slots not listed live on this canister
+    static SHARD_SLOTS: RefCell<StableBTreeMap<u32, String, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(76)))
+        )
+    );
+
+    // "slot|company_id" for every local company, so a slot's companies are one range
+    static SHARD_SLOT_INDEX: RefCell<StableBTreeMap<String, u8, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(77)))
+        )
+    );
+
+    // Job runner concurrency overrides: provider name (or "*" for the overall cap) -> limit
+    static JOB_CONCURRENCY_LIMITS: RefCell<StableBTreeMap<String, u32, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(78)))
+        )
+    );
+
+    // Provider base URL and API key overrides keyed by SettingsManager::provider_key
+    static API_CONFIGS: RefCell<StableBTreeMap<String, ApiConfig, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(79)))
+        )
+    );
+
+    // Encrypted moderator notes and their access trail, keyed by moderation task id
+    static MODERATOR_NOTES: RefCell<StableBTreeMap<String, ModeratorNoteThread, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(80)))
+        )
+    );
+
+    // "company_id|message_id" for encrypted owner messages; message ids sort by send time
+    static PRIVATE_MESSAGES: RefCell<StableBTreeMap<String, PrivateMessage, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(81)))
+        )
+    );
+
+    static REPUTATION_ESCROW_POLICY: RefCell<StableBTreeMap<String, ReputationEscrowPolicy, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(82)))
+        )
+    );
+
+    static TIER_DEFINITIONS: RefCell<StableBTreeMap<String, TierDefinitions, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(83)))
+        )
+    );
+
+    // Keyed "key_name:purpose"
+    static EVM_SIGNERS: RefCell<StableBTreeMap<String, EvmSigner, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(84)))
+        )
+    );
+
+    // Latest signed attestation per company
+    static EVM_ATTESTATIONS: RefCell<StableBTreeMap<String, EvmAttestation, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(85)))
+        )
+    );
+
+    static EVM_BRIDGE_CONFIG: RefCell<StableBTreeMap<String, EvmBridgeConfig, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(86)))
+        )
+    );
+
+    // "latest", "pending" and "published" root snapshots
+    static EVM_ROOT_SNAPSHOTS: RefCell<StableBTreeMap<String, EvmRootSnapshot, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(87)))
+        )
+    );
+
+    // Emergency suspensions keyed by company id, until reinstated
+    static EMERGENCY_SUSPENSIONS: RefCell<StableBTreeMap<String, EmergencySuspension, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(88)))
+        )
+    );
+
+    // Monotonic counters behind generated ids, keyed by id kind
+    static ID_COUNTERS: RefCell<StableBTreeMap<String, u64, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(89)))
+        )
+    );
+
+    // Per API key (window start, requests in window); kept across upgrades so they don't reset limits
+    static API_KEY_RATE_LIMITS: RefCell<StableBTreeMap<String, (u64, u32), Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(90)))
+        )
+    );
+
+    // Failed verification streaks keyed by verification_failure_key; kept across upgrades
+    // so they don't lift cooldowns
+    static VERIFICATION_FAILURES: RefCell<StableBTreeMap<String, VerificationFailures, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(91)))
+        )
+    );
+
+    // Keyed by SimulationManager::fixture_key
+    #[cfg(feature = "simulation")]
+    static SIMULATION_FIXTURES: RefCell<StableBTreeMap<String, SimulationFixture, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(38)))
+        )
+    );
+
+    // Set while a hook delivery is awaiting the target (in-memory, resets on canister upgrade)
+    static CACHE_HOOK_IN_FLIGHT: RefCell<bool> = const { RefCell::new(false) };
+
+    // Attestations behind the certified data (in-memory, resets on canister upgrade)
+    static LISTING_SNAPSHOT: RefCell<Option<ListingSnapshot>> = const { RefCell::new(None) };
+
+    // Partners whose callback is awaiting a reply (in-memory, resets on canister upgrade)
+    static PARTNER_DELIVERIES_IN_FLIGHT: RefCell<BTreeSet<String>> = const { RefCell::new(BTreeSet::new()) };
+
+    // Whether an EVM bridge run is in progress, and the last run's error (in-memory, resets on canister upgrade)
+    static EVM_BRIDGE_RUN: RefCell<(bool, Option<String>)> = const { RefCell::new((false, None)) };
+
+    // Webhook subscriptions with a delivery in progress (in-memory, resets on canister upgrade)
+    static WEBHOOK_DELIVERIES_IN_FLIGHT: RefCell<BTreeSet<String>> = const { RefCell::new(BTreeSet::new()) };
+
+    // Peers with a federation sync running (in-memory, resets on canister upgrade)
+    static FEDERATION_SYNCS_IN_FLIGHT: RefCell<BTreeSet<String>> = const { RefCell::new(BTreeSet::new()) };
+
+    // Background jobs and their queued tasks (in-memory, resets on canister upgrade)
+    static JOB_REGISTRY: RefCell<JobRegistry> = RefCell::new(JobRegistry::default());
+
+    // Pre-rendered /public/v1/ responses by route, and when they were built (in-memory, resets on canister upgrade)
+    static PUBLIC_RESPONSE_CACHE: RefCell<(BTreeMap<String, CachedResponse>, u64)> = const { RefCell::new((BTreeMap::new(), 0)) };
+
+    // Set while companies are being handed to shards (in-memory, resets on canister upgrade)
+    static SHARD_DRAIN_IN_FLIGHT: RefCell<bool> = const { RefCell::new(false) };
+
+    // Set while a replica is pulling from its source (in-memory, resets on canister upgrade)
+    static REPLICATION_SYNC_IN_FLIGHT: RefCell<bool> = const { RefCell::new(false) };
+
+    // Proofs with an owner-requested re-check scheduled or running (in-memory, resets on canister upgrade)
+    static PROOF_RECHECKS_PENDING: RefCell<BTreeSet<String>> = const { RefCell::new(BTreeSet::new()) };
+
+    // The one-shot timer that runs the next due monitoring job (in-memory, resets on canister upgrade)
+    static MONITORING_WAKEUP: RefCell<Option<TimerId>> = const { RefCell::new(None) };
+
+    // Provider health tracking (in-memory, resets on canister upgrade)
+    static PROVIDER_HEALTH: RefCell<HashMap<String, ProviderHealth>> = RefCell::new(HashMap::new());
+
+    // Simulation builds start with simulation on (in-memory, resets on canister upgrade)
+    #[cfg(feature = "simulation")]
+    static SIMULATION_ENABLED: RefCell<bool> = const { RefCell::new(true) };
 }
 
-// Storage abstraction layer
-pub struct StorageManager;
+// Storage abstraction layer
+pub struct StorageManager;
+
+impl StorageManager {
+    // Company storage operations
+    pub fn insert_company(company_id: String, company: Company) {
+        let score = company.community_validation.reputation_score;
+        let terms = SearchIndex::company_terms(&company);
+        let proof_ids = Self::company_proof_ids(&company);
+        let previous = COMPANIES.with(|companies| {
+            companies.borrow_mut().insert(company_id.clone(), company)
+        });
+        let previous_score = previous.as_ref().map(|c| c.community_validation.reputation_score);
+        Self::reindex_reputation(&company_id, previous_score, Some(score));
+        let previous_terms = previous.as_ref().map(SearchIndex::company_terms);
+        Self::reindex_search_terms(&company_id, previous_terms, Some(terms));
+        Self::reindex_proofs(&company_id, previous.as_ref().map(Self::company_proof_ids), Some(proof_ids));
+        if previous.is_none() {
+            Self::reindex_shard_slot(&company_id, true);
+        }
+        let kind = if previous.is_none() { CompanyEventKind::Created } else { CompanyEventKind::Updated };
+        Self::append_company_event(&company_id, kind);
+    }
+
+    // For newly created companies: an id already in use is refused instead of overwritten
+    pub fn insert_new_company(company: Company) -> Result<(), String> {
+        if Self::get_company(&company.id).is_some() || Self::get_archived_company(&company.id).is_some() {
+            return Err(format!("Company id {} is already in use", company.id));
+        }
+        Self::insert_company(company.id.clone(), company);
+        Ok(())
+    }
+
+    pub fn get_company(company_id: &str) -> Option<Company> {
+        COMPANIES.with(|companies| {
+            companies.borrow().get(&company_id.to_string())
+        })
+    }
+
+    pub fn update_company<F>(company_id: &str, update_fn: F) -> bool 
+    where 
+        F: FnOnce(&mut Company)
+    {
+        let changes = COMPANIES.with(|companies| {
+            let mut companies_map = companies.borrow_mut();
+            if let Some(mut company) = companies_map.get(&company_id.to_string()) {
+                let score_before = company.community_validation.reputation_score;
+                let terms_before = SearchIndex::company_terms(&company);
+                let proofs_before = Self::company_proof_ids(&company);
+                update_fn(&mut company);
+                company.updated_at = time();
+                let score_after = company.community_validation.reputation_score;
+                let terms_after = SearchIndex::company_terms(&company);
+                let proofs_after = Self::company_proof_ids(&company);
+                companies_map.insert(company_id.to_string(), company);
+                Some((score_before, score_after, terms_before, terms_after, proofs_before, proofs_after))
+            } else {
+                None
+            }
+        });
+        let updated = changes.is_some();
+        if let Some((score_before, score_after, terms_before, terms_after, proofs_before, proofs_after)) = changes {
+            Self::reindex_reputation(company_id, Some(score_before), Some(score_after));
+            Self::reindex_search_terms(company_id, Some(terms_before), Some(terms_after));
+            Self::reindex_proofs(company_id, Some(proofs_before), Some(proofs_after));
+            Self::append_company_event(company_id, CompanyEventKind::Updated);
+        }
+        updated
+    }
+
+    pub fn get_all_companies() -> Vec<Company> {
+        COMPANIES.with(|companies| {
+            companies
+                .borrow()
+                .iter()
+                .map(|(_, company)| company)
+                .collect()
+        })
+    }
+
+    pub fn remove_company(company_id: &str) -> Option<Company> {
+        let removed = COMPANIES.with(|companies| {
+            companies.borrow_mut().remove(&company_id.to_string())
+        });
+        if let Some(company) = &removed {
+            Self::reindex_reputation(company_id, Some(company.community_validation.reputation_score), None);
+            Self::reindex_search_terms(company_id, Some(SearchIndex::company_terms(company)), None);
+            Self::reindex_proofs(company_id, Some(Self::company_proof_ids(company)), None);
+            Self::reindex_shard_slot(company_id, false);
+            Self::append_company_event(company_id, CompanyEventKind::Deleted);
+        }
+        removed
+    }
+
+    // Reputation index operations
+    fn reputation_index_key(score: u32, company_id: &str) -> String {
+        // Inverted and zero-padded so lexicographic order is descending score
+        format!("{:010}:{}", u32::MAX - score, company_id)
+    }
+
+    fn reindex_reputation(company_id: &str, before: Option<u32>, after: Option<u32>) {
+        if before.is_some() && before == after {
+            return;
+        }
+        REPUTATION_INDEX.with(|index| {
+            let mut index = index.borrow_mut();
+            if let Some(score) = before {
+                index.remove(&Self::reputation_index_key(score, company_id));
+            }
+            if let Some(score) = after {
+                index.insert(Self::reputation_index_key(score, company_id), company_id.to_string());
+            }
+        });
+    }
+
+    // Highest score first. None while the index doesn't cover every company (companies
+    // stored before the index existed), in which case callers fall back to a full scan.
+    pub fn get_company_ids_by_reputation(min_score: u32, limit: usize) -> Option<Vec<String>> {
+        REPUTATION_INDEX.with(|index| {
+            let index = index.borrow();
+            if index.len() != Self::get_companies_count() {
+                return None;
+            }
+            Some(
+                index
+                    .range(..=Self::reputation_index_key(min_score, "\u{10FFFF}"))
+                    .take(limit)
+                    .map(|(_, company_id)| company_id)
+                    .collect(),
+            )
+        })
+    }
+
+    // Search index operations
+    fn search_posting_key(token: &str, company_id: &str) -> String {
+        format!("{}\u{0}{}", token, company_id)
+    }
+
+    // Writes only the postings that changed; None means the company doesn't exist on that side
+    fn reindex_search_terms(
+        company_id: &str,
+        before: Option<BTreeMap<String, u8>>,
+        after: Option<BTreeMap<String, u8>>,
+    ) {
+        let before = before.unwrap_or_default();
+        SEARCH_POSTINGS.with(|postings| {
+            let mut postings = postings.borrow_mut();
+            for token in before.keys() {
+                if after.as_ref().is_none_or(|after| !after.contains_key(token)) {
+                    postings.remove(&Self::search_posting_key(token, company_id));
+                }
+            }
+            for (token, fields) in after.iter().flatten() {
+                if before.get(token) != Some(fields) {
+                    postings.insert(Self::search_posting_key(token, company_id), *fields);
+                }
+            }
+        });
+        SEARCH_DOCUMENTS.with(|documents| {
+            let mut documents = documents.borrow_mut();
+            match &after {
+                Some(terms) => documents.insert(company_id.to_string(), terms.len() as u32),
+                None => documents.remove(&company_id.to_string()),
+            };
+        });
+    }
+
+    fn company_proof_ids(company: &Company) -> Vec<String> {
+        company
+            .web3_identity
+            .verification_proofs
+            .iter()
+            .filter_map(|proof| proof.id.clone())
+            .collect()
+    }
+
+    fn reindex_proofs(company_id: &str, before: Option<Vec<String>>, after: Option<Vec<String>>) {
+        PROOF_INDEX.with(|index| {
+            let mut index = index.borrow_mut();
+            for proof_id in before.iter().flatten() {
+                if after.as_ref().is_none_or(|after| !after.contains(proof_id)) {
+                    index.remove(proof_id);
+                }
+            }
+            for proof_id in after.iter().flatten() {
+                index.insert(proof_id.clone(), company_id.to_string());
+            }
+        });
+    }
+
+    fn shard_slot_key(slot: u32, company_id: &str) -> String {
+        format!("{:05}|{}", slot, company_id)
+    }
+
+    fn reindex_shard_slot(company_id: &str, present: bool) {
+        let key = Self::shard_slot_key(ShardManager::slot_of(company_id), company_id);
+        SHARD_SLOT_INDEX.with(|index| {
+            let mut index = index.borrow_mut();
+            if present {
+                index.insert(key, 0);
+            } else {
+                index.remove(&key);
+            }
+        });
+    }
+
+    pub fn shard_slot_index_is_complete() -> bool {
+        SHARD_SLOT_INDEX.with(|index| index.borrow().len()) == Self::get_companies_count()
+    }
+
+    pub fn rebuild_shard_slot_index() -> u64 {
+        let keys: Vec<String> = COMPANIES.with(|companies| {
+            companies
+                .borrow()
+                .iter()
+                .map(|(id, _)| Self::shard_slot_key(ShardManager::slot_of(&id), &id))
+                .collect()
+        });
+        SHARD_SLOT_INDEX.with(|index| {
+            let mut index = index.borrow_mut();
+            let stale: Vec<String> = index.iter().map(|(key, _)| key).collect();
+            for key in stale {
+                index.remove(&key);
+            }
+            for key in keys {
+                index.insert(key, 0);
+            }
+            index.len()
+        })
+    }
+
+    // Up to `limit` local company ids in the slot
+    pub fn get_company_ids_in_slot(slot: u32, limit: usize) -> Vec<String> {
+        let prefix = format!("{:05}|", slot);
+        SHARD_SLOT_INDEX.with(|index| {
+            index
+                .borrow()
+                .range(prefix.clone()..)
+                .take_while(|(key, _)| key.starts_with(&prefix))
+                .take(limit)
+                .map(|(key, _)| key[prefix.len()..].to_string())
+                .collect()
+        })
+    }
+
+    pub fn count_companies_in_slot(slot: u32) -> u64 {
+        let prefix = format!("{:05}|", slot);
+        SHARD_SLOT_INDEX.with(|index| {
+            index
+                .borrow()
+                .range(prefix.clone()..)
+                .take_while(|(key, _)| key.starts_with(&prefix))
+                .count() as u64
+        })
+    }
+
+    pub fn get_proof_company_id(proof_id: &str) -> Option<String> {
+        PROOF_INDEX.with(|index| index.borrow().get(&proof_id.to_string()))
+    }
+
+    pub fn search_index_is_complete() -> bool {
+        SEARCH_DOCUMENTS.with(|documents| documents.borrow().len()) == Self::get_companies_count()
+    }
+
+    // (token, company id, fields) for tokens starting with `prefix`, in token order
+    pub fn get_search_postings(prefix: &str, limit: usize) -> Vec<(String, String, u8)> {
+        SEARCH_POSTINGS.with(|postings| {
+            postings
+                .borrow()
+                .range(prefix.to_string()..)
+                .take_while(|(key, _)| key.starts_with(prefix))
+                .take(limit)
+                .filter_map(|(key, fields)| {
+                    let (token, company_id) = key.split_once('\u{0}')?;
+                    Some((token.to_string(), company_id.to_string(), fields))
+                })
+                .collect()
+        })
+    }
+
+    // Returns the number of indexed companies
+    pub fn rebuild_search_index() -> u64 {
+        SEARCH_POSTINGS.with(|postings| {
+            let mut postings = postings.borrow_mut();
+            let stale: Vec<String> = postings.iter().map(|(key, _)| key).collect();
+            for key in stale {
+                postings.remove(&key);
+            }
+        });
+        SEARCH_DOCUMENTS.with(|documents| {
+            let mut documents = documents.borrow_mut();
+            let stale: Vec<String> = documents.iter().map(|(key, _)| key).collect();
+            for key in stale {
+                documents.remove(&key);
+            }
+        });
+        for company in Self::get_all_companies() {
+            Self::reindex_search_terms(&company.id, None, Some(SearchIndex::company_terms(&company)));
+        }
+        SEARCH_DOCUMENTS.with(|documents| documents.borrow().len())
+    }
+
+    // Returns the number of indexed companies
+    pub fn rebuild_reputation_index() -> u64 {
+        let entries: Vec<(String, String)> = COMPANIES.with(|companies| {
+            companies
+                .borrow()
+                .iter()
+                .map(|(id, company)| (Self::reputation_index_key(company.community_validation.reputation_score, &id), id))
+                .collect()
+        });
+        REPUTATION_INDEX.with(|index| {
+            let mut index = index.borrow_mut();
+            let stale: Vec<String> = index.iter().map(|(key, _)| key).collect();
+            for key in stale {
+                index.remove(&key);
+            }
+            for (key, company_id) in entries {
+                index.insert(key, company_id);
+            }
+            index.len()
+        })
+    }
+
+    pub fn get_companies_count() -> u64 {
+        COMPANIES.with(|companies| companies.borrow().len())
+    }
+
+    // Up to `limit` companies in id order, starting after `cursor`
+    pub fn get_companies_after(cursor: Option<&str>, limit: usize) -> Vec<Company> {
+        COMPANIES.with(|companies| {
+            let companies = companies.borrow();
+            let range = match cursor {
+                Some(cursor) => companies.range((
+                    std::ops::Bound::Excluded(cursor.to_string()),
+                    std::ops::Bound::Unbounded,
+                )),
+                None => companies.range(..),
+            };
+            range.take(limit).map(|(_, company)| company).collect()
+        })
+    }
+
+    // Ids after `after` up to and including `up_to` (to the end when None)
+    pub fn get_company_ids_between(after: Option<&str>, up_to: Option<&str>) -> Vec<String> {
+        use std::ops::Bound;
+        let start = after.map_or(Bound::Unbounded, |after| Bound::Excluded(after.to_string()));
+        let end = up_to.map_or(Bound::Unbounded, |up_to| Bound::Included(up_to.to_string()));
+        COMPANIES.with(|companies| companies.borrow().range((start, end)).map(|(id, _)| id).collect())
+    }
+
+    // Archived company storage operations
+    pub fn insert_archived_company(archived: ArchivedCompany) {
+        ARCHIVED_COMPANIES.with(|archive| {
+            archive.borrow_mut().insert(archived.company.id.clone(), archived);
+        });
+    }
+
+    pub fn get_archived_company(company_id: &str) -> Option<ArchivedCompany> {
+        ARCHIVED_COMPANIES.with(|archive| archive.borrow().get(&company_id.to_string()))
+    }
+
+    pub fn remove_archived_company(company_id: &str) -> Option<ArchivedCompany> {
+        ARCHIVED_COMPANIES.with(|archive| archive.borrow_mut().remove(&company_id.to_string()))
+    }
+
+    pub fn get_all_archived_companies() -> Vec<ArchivedCompany> {
+        ARCHIVED_COMPANIES.with(|archive| {
+            archive.borrow().iter().map(|(_, archived)| archived).collect()
+        })
+    }
+
+    // Domain challenge storage operations
+    pub fn insert_domain_challenge(company_id: String, challenge: DomainVerificationChallenge) {
+        DOMAIN_CHALLENGES.with(|challenges| {
+            challenges.borrow_mut().insert(company_id, challenge);
+        });
+    }
+
+    pub fn get_domain_challenge(company_id: &str) -> Option<DomainVerificationChallenge> {
+        DOMAIN_CHALLENGES.with(|challenges| {
+            challenges.borrow().get(&company_id.to_string())
+        })
+    }
+
+    pub fn remove_domain_challenge(company_id: &str) -> Option<DomainVerificationChallenge> {
+        DOMAIN_CHALLENGES.with(|challenges| {
+            challenges.borrow_mut().remove(&company_id.to_string())
+        })
+    }
+
+    // Cross-chain challenge storage operations
+    pub fn insert_crosschain_challenge(challenge_key: String, challenge: CrossChainChallenge) {
+        CROSSCHAIN_CHALLENGES.with(|challenges| {
+            challenges.borrow_mut().insert(challenge_key, challenge);
+        });
+    }
+
+    pub fn get_crosschain_challenge(challenge_key: &str) -> Option<CrossChainChallenge> {
+        CROSSCHAIN_CHALLENGES.with(|challenges| {
+            challenges.borrow().get(&challenge_key.to_string())
+        })
+    }
+
+    pub fn remove_crosschain_challenge(challenge_key: &str) -> Option<CrossChainChallenge> {
+        CROSSCHAIN_CHALLENGES.with(|challenges| {
+            challenges.borrow_mut().remove(&challenge_key.to_string())
+        })
+    }
+
+    pub fn get_crosschain_challenges_for_company(company_id: &str) -> Vec<CrossChainChallenge> {
+        CROSSCHAIN_CHALLENGES.with(|challenges| {
+            challenges
+                .borrow()
+                .iter()
+                .filter_map(|(_, challenge)| {
+                    if challenge.company_id == company_id {
+                        Some(challenge)
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        })
+    }
+
+    // Provider settings storage operations
+    pub fn insert_provider_endpoints(provider_key: String, endpoints: ProviderEndpointList) {
+        PROVIDER_ENDPOINTS.with(|providers| {
+            providers.borrow_mut().insert(provider_key, endpoints);
+        });
+    }
+
+    pub fn get_provider_endpoints(provider_key: &str) -> Option<ProviderEndpointList> {
+        PROVIDER_ENDPOINTS.with(|providers| {
+            providers.borrow().get(&provider_key.to_string())
+        })
+    }
+
+    pub fn get_api_config(provider_key: &str) -> Option<ApiConfig> {
+        API_CONFIGS.with(|configs| configs.borrow().get(&provider_key.to_string()))
+    }
+
+    pub fn set_api_config(provider_key: &str, config: Option<ApiConfig>) {
+        API_CONFIGS.with(|configs| {
+            let mut configs = configs.borrow_mut();
+            match config {
+                Some(config) => configs.insert(provider_key.to_string(), config),
+                None => configs.remove(&provider_key.to_string()),
+            };
+        });
+    }
+
+    pub fn get_provider_health(health_key: &str) -> Option<ProviderHealth> {
+        PROVIDER_HEALTH.with(|health| health.borrow().get(health_key).cloned())
+    }
+
+    pub fn insert_provider_health(health_key: String, health: ProviderHealth) {
+        PROVIDER_HEALTH.with(|all_health| {
+            all_health.borrow_mut().insert(health_key, health);
+        });
+    }
+
+    pub fn snapshot_provider_health() {
+        PROVIDER_HEALTH.with(|all_health| {
+            PROVIDER_HEALTH_SNAPSHOT.with(|snapshot| {
+                let mut snapshot = snapshot.borrow_mut();
+                for (health_key, health) in all_health.borrow().iter() {
+                    snapshot.insert(health_key.clone(), health.clone());
+                }
+            });
+        });
+    }
+
+    pub fn restore_provider_health() {
+        let restored: Vec<(String, ProviderHealth)> = PROVIDER_HEALTH_SNAPSHOT.with(|snapshot| {
+            let entries: Vec<(String, ProviderHealth)> = snapshot.borrow().iter().collect();
+            let mut snapshot = snapshot.borrow_mut();
+            for (health_key, _) in &entries {
+                snapshot.remove(health_key);
+            }
+            entries
+        });
+        PROVIDER_HEALTH.with(|all_health| {
+            all_health.borrow_mut().extend(restored);
+        });
+    }
+
+    pub fn get_proof_monitoring(company_id: &str, proof_id: &str) -> Option<ProofMonitoring> {
+        PROOF_MONITORING.with(|monitoring| monitoring.borrow().get(&format!("{}:{}", company_id, proof_id)))
+    }
+
+    pub fn insert_proof_monitoring(proof_monitoring: ProofMonitoring) {
+        PROOF_MONITORING.with(|monitoring| {
+            monitoring.borrow_mut().insert(
+                format!("{}:{}", proof_monitoring.company_id, proof_monitoring.proof_id),
+                proof_monitoring,
+            );
+        });
+    }
+
+    // Moves the check history and monitoring position of a proof to a new id
+    pub fn rekey_proof_state(company_id: &str, from_proof_id: &str, to_proof_id: &str) {
+        let from_key = format!("{}:{}", company_id, from_proof_id);
+        let to_key = format!("{}:{}", company_id, to_proof_id);
+        PROOF_MONITORING.with(|monitoring| {
+            let mut monitoring = monitoring.borrow_mut();
+            if let Some(mut entry) = monitoring.remove(&from_key) {
+                entry.proof_id = to_proof_id.to_string();
+                monitoring.insert(to_key.clone(), entry);
+            }
+        });
+        PROOF_CHECKS.with(|checks| {
+            let mut checks = checks.borrow_mut();
+            if let Some(checked_at) = checks.remove(&from_key) {
+                checks.insert(to_key, checked_at);
+            }
+        });
+    }
+
+    pub fn get_all_proof_checks() -> Vec<(String, u64)> {
+        PROOF_CHECKS.with(|checks| checks.borrow().iter().collect())
+    }
+
+    pub fn set_proof_checked(key: &str, checked_at: u64) {
+        PROOF_CHECKS.with(|checks| {
+            checks.borrow_mut().insert(key.to_string(), checked_at);
+        });
+    }
+
+    pub fn remove_proof_check(key: &str) {
+        PROOF_CHECKS.with(|checks| {
+            checks.borrow_mut().remove(&key.to_string());
+        });
+    }
+
+    // Returns the wakeup it replaced, which the caller should clear
+    pub fn replace_monitoring_wakeup(timer_id: TimerId) -> Option<TimerId> {
+        MONITORING_WAKEUP.with(|wakeup| wakeup.borrow_mut().replace(timer_id))
+    }
+
+    pub fn set_monitoring_last_run(job: &str, started_at: u64) {
+        MONITORING_RUNS.with(|runs| {
+            runs.borrow_mut().insert(job.to_string(), started_at);
+        });
+    }
+
+    pub fn get_monitoring_last_run(job: &str) -> Option<u64> {
+        MONITORING_RUNS.with(|runs| {
+            runs.borrow().get(&job.to_string())
+        })
+    }
+
+    // Reserved name storage operations
+    pub fn insert_reserved_name(name_key: String, reserved: ReservedName) {
+        RESERVED_NAMES.with(|names| {
+            names.borrow_mut().insert(name_key, reserved);
+        });
+    }
+
+    pub fn get_reserved_name(name_key: &str) -> Option<ReservedName> {
+        RESERVED_NAMES.with(|names| {
+            names.borrow().get(&name_key.to_string())
+        })
+    }
+
+    pub fn remove_reserved_name(name_key: &str) -> Option<ReservedName> {
+        RESERVED_NAMES.with(|names| {
+            names.borrow_mut().remove(&name_key.to_string())
+        })
+    }
+
+    pub fn get_all_reserved_names() -> Vec<ReservedName> {
+        RESERVED_NAMES.with(|names| {
+            names.borrow().iter().map(|(_, reserved)| reserved).collect()
+        })
+    }
+
+    // Company relationship storage operations
+    pub fn insert_relationship(relationship_key: String, relationship: CompanyRelationship) {
+        RELATIONSHIPS.with(|relationships| {
+            relationships.borrow_mut().insert(relationship_key, relationship);
+        });
+    }
+
+    pub fn get_relationship(relationship_key: &str) -> Option<CompanyRelationship> {
+        RELATIONSHIPS.with(|relationships| {
+            relationships.borrow().get(&relationship_key.to_string())
+        })
+    }
+
+    pub fn remove_relationship(relationship_key: &str) -> Option<CompanyRelationship> {
+        RELATIONSHIPS.with(|relationships| {
+            relationships.borrow_mut().remove(&relationship_key.to_string())
+        })
+    }
+
+    pub fn get_all_relationships() -> Vec<CompanyRelationship> {
+        RELATIONSHIPS.with(|relationships| {
+            relationships.borrow().iter().map(|(_, relationship)| relationship).collect()
+        })
+    }
+
+    // Grant storage operations
+    pub fn insert_grant_program(program_id: String, program: GrantProgram) {
+        GRANT_PROGRAMS.with(|programs| {
+            programs.borrow_mut().insert(program_id, program);
+        });
+    }
+
+    pub fn get_grant_program(program_id: &str) -> Option<GrantProgram> {
+        GRANT_PROGRAMS.with(|programs| {
+            programs.borrow().get(&program_id.to_string())
+        })
+    }
+
+    pub fn get_all_grant_programs() -> Vec<GrantProgram> {
+        GRANT_PROGRAMS.with(|programs| {
+            programs.borrow().iter().map(|(_, program)| program).collect()
+        })
+    }
+
+    pub fn grant_program_count() -> u64 {
+        GRANT_PROGRAMS.with(|programs| programs.borrow().len())
+    }
+
+    pub fn insert_grant_attestation(attestation_id: String, attestation: GrantAttestation) {
+        GRANT_ATTESTATIONS.with(|attestations| {
+            attestations.borrow_mut().insert(attestation_id, attestation);
+        });
+    }
+
+    pub fn get_grant_attestation(attestation_id: &str) -> Option<GrantAttestation> {
+        GRANT_ATTESTATIONS.with(|attestations| {
+            attestations.borrow().get(&attestation_id.to_string())
+        })
+    }
+
+    pub fn get_all_grant_attestations() -> Vec<GrantAttestation> {
+        GRANT_ATTESTATIONS.with(|attestations| {
+            attestations.borrow().iter().map(|(_, attestation)| attestation).collect()
+        })
+    }
+
+    pub fn grant_attestation_count() -> u64 {
+        GRANT_ATTESTATIONS.with(|attestations| attestations.borrow().len())
+    }
+
+    // Impersonation report storage operations
+    pub fn insert_impersonation_report(report_id: String, report: ImpersonationReport) {
+        IMPERSONATION_REPORTS.with(|reports| {
+            reports.borrow_mut().insert(report_id, report);
+        });
+    }
+
+    pub fn get_impersonation_report(report_id: &str) -> Option<ImpersonationReport> {
+        IMPERSONATION_REPORTS.with(|reports| {
+            reports.borrow().get(&report_id.to_string())
+        })
+    }
+
+    pub fn get_all_impersonation_reports() -> Vec<ImpersonationReport> {
+        IMPERSONATION_REPORTS.with(|reports| {
+            reports.borrow().iter().map(|(_, report)| report).collect()
+        })
+    }
+
+    pub fn impersonation_report_count() -> u64 {
+        IMPERSONATION_REPORTS.with(|reports| reports.borrow().len())
+    }
+
+    // Community alert storage operations
+    pub fn insert_community_alert(alert_id: String, alert: CommunityAlert) {
+        COMMUNITY_ALERTS.with(|alerts| {
+            alerts.borrow_mut().insert(alert_id, alert);
+        });
+    }
+
+    pub fn get_community_alert(alert_id: &str) -> Option<CommunityAlert> {
+        COMMUNITY_ALERTS.with(|alerts| {
+            alerts.borrow().get(&alert_id.to_string())
+        })
+    }
+
+    pub fn get_all_community_alerts() -> Vec<CommunityAlert> {
+        COMMUNITY_ALERTS.with(|alerts| {
+            alerts.borrow().iter().map(|(_, alert)| alert).collect()
+        })
+    }
+
+    pub fn community_alert_count() -> u64 {
+        COMMUNITY_ALERTS.with(|alerts| alerts.borrow().len())
+    }
+
+    // Drops pending challenges and monitoring state of a company that is going away
+    pub fn remove_company_dependents(company_id: &str) {
+        Self::remove_domain_challenge(company_id);
+        CROSSCHAIN_CHALLENGES.with(|challenges| {
+            let mut challenges = challenges.borrow_mut();
+            let keys: Vec<String> = challenges
+                .iter()
+                .filter(|(_, challenge)| challenge.company_id == company_id)
+                .map(|(key, _)| key)
+                .collect();
+            for key in keys {
+                challenges.remove(&key);
+            }
+        });
+        CONTRACT_WATCHES.with(|watches| {
+            let mut watches = watches.borrow_mut();
+            let keys: Vec<String> = watches
+                .iter()
+                .filter(|(_, watch)| watch.company_id == company_id)
+                .map(|(key, _)| key)
+                .collect();
+            for key in keys {
+                watches.remove(&key);
+            }
+        });
+        CANISTER_SNAPSHOTS.with(|snapshots| {
+            let mut snapshots = snapshots.borrow_mut();
+            let keys: Vec<String> = snapshots
+                .iter()
+                .filter(|(_, snapshot)| snapshot.company_id == company_id)
+                .map(|(key, _)| key)
+                .collect();
+            for key in keys {
+                snapshots.remove(&key);
+            }
+        });
+        TREASURY_SNAPSHOTS.with(|snapshots| {
+            let mut snapshots = snapshots.borrow_mut();
+            let keys: Vec<String> = snapshots
+                .iter()
+                .filter(|(_, snapshot)| snapshot.company_id == company_id)
+                .map(|(key, _)| key)
+                .collect();
+            for key in keys {
+                snapshots.remove(&key);
+            }
+        });
+        Self::remove_ownership_transfer(company_id);
+    }
+
+    // Contract watch storage operations
+    pub fn insert_contract_watch(watch_key: String, watch: ContractWatch) {
+        CONTRACT_WATCHES.with(|watches| {
+            watches.borrow_mut().insert(watch_key, watch);
+        });
+    }
+
+    pub fn get_contract_watch(watch_key: &str) -> Option<ContractWatch> {
+        CONTRACT_WATCHES.with(|watches| {
+            watches.borrow().get(&watch_key.to_string())
+        })
+    }
+
+    pub fn remove_contract_watch(watch_key: &str) -> Option<ContractWatch> {
+        CONTRACT_WATCHES.with(|watches| {
+            watches.borrow_mut().remove(&watch_key.to_string())
+        })
+    }
+
+    pub fn get_all_contract_watches() -> Vec<(String, ContractWatch)> {
+        CONTRACT_WATCHES.with(|watches| {
+            watches.borrow().iter().collect()
+        })
+    }
+
+    // Canister snapshot storage operations
+    pub fn insert_canister_snapshot(canister_id: String, snapshot: CanisterSnapshot) {
+        CANISTER_SNAPSHOTS.with(|snapshots| {
+            snapshots.borrow_mut().insert(canister_id, snapshot);
+        });
+    }
+
+    pub fn get_canister_snapshot(canister_id: &str) -> Option<CanisterSnapshot> {
+        CANISTER_SNAPSHOTS.with(|snapshots| {
+            snapshots.borrow().get(&canister_id.to_string())
+        })
+    }
+
+    pub fn remove_canister_snapshot(canister_id: &str) -> Option<CanisterSnapshot> {
+        CANISTER_SNAPSHOTS.with(|snapshots| {
+            snapshots.borrow_mut().remove(&canister_id.to_string())
+        })
+    }
+
+    pub fn get_all_canister_snapshots() -> Vec<CanisterSnapshot> {
+        CANISTER_SNAPSHOTS.with(|snapshots| {
+            snapshots.borrow().iter().map(|(_, snapshot)| snapshot).collect()
+        })
+    }
+
+    // Treasury snapshot storage operations
+    pub fn insert_treasury_snapshot(snapshot_key: String, snapshot: TreasurySnapshot) {
+        TREASURY_SNAPSHOTS.with(|snapshots| {
+            snapshots.borrow_mut().insert(snapshot_key, snapshot);
+        });
+    }
+
+    pub fn get_treasury_snapshot(snapshot_key: &str) -> Option<TreasurySnapshot> {
+        TREASURY_SNAPSHOTS.with(|snapshots| {
+            snapshots.borrow().get(&snapshot_key.to_string())
+        })
+    }
+
+    pub fn remove_treasury_snapshot(snapshot_key: &str) -> Option<TreasurySnapshot> {
+        TREASURY_SNAPSHOTS.with(|snapshots| {
+            snapshots.borrow_mut().remove(&snapshot_key.to_string())
+        })
+    }
+
+    pub fn get_all_treasury_snapshots() -> Vec<(String, TreasurySnapshot)> {
+        TREASURY_SNAPSHOTS.with(|snapshots| {
+            snapshots.borrow().iter().collect()
+        })
+    }
+
+    // Monitoring config storage operations (single entry)
+    pub fn get_monitoring_config() -> Option<MonitoringConfig> {
+        MONITORING_CONFIG.with(|config| {
+            config.borrow().get(&"monitoring".to_string())
+        })
+    }
+
+    pub fn set_monitoring_config(monitoring_config: MonitoringConfig) {
+        MONITORING_CONFIG.with(|config| {
+            config.borrow_mut().insert("monitoring".to_string(), monitoring_config);
+        });
+    }
+
+    // Notification storage operations
+    pub fn insert_notification(notification_id: String, notification: Notification) {
+        NOTIFICATIONS.with(|notifications| {
+            notifications.borrow_mut().insert(notification_id, notification);
+        });
+    }
+
+    pub fn get_notification(notification_id: &str) -> Option<Notification> {
+        NOTIFICATIONS.with(|notifications| {
+            notifications.borrow().get(&notification_id.to_string())
+        })
+    }
+
+    pub fn remove_notification(notification_id: &str) -> Option<Notification> {
+        NOTIFICATIONS.with(|notifications| {
+            notifications.borrow_mut().remove(&notification_id.to_string())
+        })
+    }
+
+    pub fn get_all_notifications() -> Vec<Notification> {
+        NOTIFICATIONS.with(|notifications| {
+            notifications.borrow().iter().map(|(_, notification)| notification).collect()
+        })
+    }
+
+    pub fn notification_count() -> u64 {
+        NOTIFICATIONS.with(|notifications| notifications.borrow().len())
+    }
+
+    // Alert routing rule storage operations
+    pub fn insert_alert_routing_rule(rule_key: String, rule: AlertRoutingRule) {
+        ALERT_ROUTING_RULES.with(|rules| {
+            rules.borrow_mut().insert(rule_key, rule);
+        });
+    }
+
+    pub fn get_alert_routing_rule(rule_key: &str) -> Option<AlertRoutingRule> {
+        ALERT_ROUTING_RULES.with(|rules| {
+            rules.borrow().get(&rule_key.to_string())
+        })
+    }
+
+    pub fn remove_alert_routing_rule(rule_key: &str) -> Option<AlertRoutingRule> {
+        ALERT_ROUTING_RULES.with(|rules| {
+            rules.borrow_mut().remove(&rule_key.to_string())
+        })
+    }
+
+    pub fn get_all_alert_routing_rules() -> Vec<AlertRoutingRule> {
+        ALERT_ROUTING_RULES.with(|rules| {
+            rules.borrow().iter().map(|(_, rule)| rule).collect()
+        })
+    }
+
+    // Moderation task storage operations
+    pub fn insert_moderation_task(task_id: String, task: ModerationTask) {
+        MODERATION_TASKS.with(|tasks| {
+            tasks.borrow_mut().insert(task_id, task);
+        });
+    }
+
+    pub fn get_moderation_task(task_id: &str) -> Option<ModerationTask> {
+        MODERATION_TASKS.with(|tasks| {
+            tasks.borrow().get(&task_id.to_string())
+        })
+    }
+
+    pub fn get_all_moderation_tasks() -> Vec<ModerationTask> {
+        MODERATION_TASKS.with(|tasks| {
+            tasks.borrow().iter().map(|(_, task)| task).collect()
+        })
+    }
+
+    pub fn moderation_task_count() -> u64 {
+        MODERATION_TASKS.with(|tasks| tasks.borrow().len())
+    }
+
+    // Moderator note storage operations
+    pub fn get_moderator_note_thread(task_id: &str) -> Option<ModeratorNoteThread> {
+        MODERATOR_NOTES.with(|threads| threads.borrow().get(&task_id.to_string()))
+    }
+
+    pub fn insert_moderator_note_thread(thread: ModeratorNoteThread) {
+        MODERATOR_NOTES.with(|threads| {
+            threads.borrow_mut().insert(thread.task_id.clone(), thread);
+        });
+    }
+
+    // Threshold ECDSA signer storage operations
+    pub fn get_evm_signer(key_name: &str, purpose: &str) -> Option<EvmSigner> {
+        EVM_SIGNERS.with(|signers| signers.borrow().get(&format!("{}:{}", key_name, purpose)))
+    }
+
+    pub fn set_evm_signer(signer: EvmSigner) {
+        EVM_SIGNERS.with(|signers| {
+            signers.borrow_mut().insert(format!("{}:{}", signer.key_name, signer.purpose), signer);
+        });
+    }
+
+    // EVM attestation storage operations
+    pub fn get_evm_attestation(company_id: &str) -> Option<EvmAttestation> {
+        EVM_ATTESTATIONS.with(|attestations| attestations.borrow().get(&company_id.to_string()))
+    }
+
+    pub fn insert_evm_attestation(attestation: EvmAttestation) {
+        EVM_ATTESTATIONS.with(|attestations| {
+            attestations.borrow_mut().insert(attestation.company_id.clone(), attestation);
+        });
+    }
+
+    // EVM bridge storage operations
+    pub fn get_evm_bridge_config() -> Option<EvmBridgeConfig> {
+        EVM_BRIDGE_CONFIG.with(|config| config.borrow().get(&"evm_bridge".to_string()))
+    }
+
+    pub fn set_evm_bridge_config(evm_bridge_config: EvmBridgeConfig) {
+        EVM_BRIDGE_CONFIG.with(|config| {
+            config.borrow_mut().insert("evm_bridge".to_string(), evm_bridge_config);
+        });
+    }
+
+    pub fn get_evm_root_snapshot(slot: &str) -> Option<EvmRootSnapshot> {
+        EVM_ROOT_SNAPSHOTS.with(|snapshots| snapshots.borrow().get(&slot.to_string()))
+    }
+
+    pub fn set_evm_root_snapshot(slot: &str, snapshot: EvmRootSnapshot) {
+        EVM_ROOT_SNAPSHOTS.with(|snapshots| {
+            snapshots.borrow_mut().insert(slot.to_string(), snapshot);
+        });
+    }
+
+    pub fn remove_evm_root_snapshot(slot: &str) {
+        EVM_ROOT_SNAPSHOTS.with(|snapshots| {
+            snapshots.borrow_mut().remove(&slot.to_string());
+        });
+    }
+
+    pub fn try_begin_evm_bridge_run() -> bool {
+        EVM_BRIDGE_RUN.with(|run| !std::mem::replace(&mut run.borrow_mut().0, true))
+    }
+
+    pub fn end_evm_bridge_run(error: Option<String>) {
+        EVM_BRIDGE_RUN.with(|run| *run.borrow_mut() = (false, error));
+    }
+
+    pub fn get_evm_bridge_last_error() -> Option<String> {
+        EVM_BRIDGE_RUN.with(|run| run.borrow().1.clone())
+    }
+
+    // Private message storage operations
+    pub fn insert_private_message(message: PrivateMessage) {
+        PRIVATE_MESSAGES.with(|messages| {
+            messages.borrow_mut().insert(format!("{}|{}", message.company_id, message.id), message);
+        });
+    }
+
+    pub fn remove_private_message(company_id: &str, message_id: &str) {
+        PRIVATE_MESSAGES.with(|messages| {
+            messages.borrow_mut().remove(&format!("{}|{}", company_id, message_id));
+        });
+    }
+
+    // Oldest first
+    pub fn get_private_messages(company_id: &str) -> Vec<PrivateMessage> {
+        let prefix = format!("{}|", company_id);
+        PRIVATE_MESSAGES.with(|messages| {
+            messages
+                .borrow()
+                .range(prefix.clone()..)
+                .take_while(|(key, _)| key.starts_with(&prefix))
+                .map(|(_, message)| message)
+                .collect()
+        })
+    }
+
+    pub fn private_message_count() -> u64 {
+        PRIVATE_MESSAGES.with(|messages| messages.borrow().len())
+    }
+
+    // Moderator storage operations
+    pub fn insert_moderator(moderator: Moderator) {
+        MODERATORS.with(|moderators| {
+            moderators.borrow_mut().insert(moderator.principal.to_text(), moderator);
+        });
+    }
+
+    pub fn get_moderator(principal: &Principal) -> Option<Moderator> {
+        MODERATORS.with(|moderators| {
+            moderators.borrow().get(&principal.to_text())
+        })
+    }
+
+    pub fn remove_moderator(principal: &Principal) -> Option<Moderator> {
+        MODERATORS.with(|moderators| {
+            moderators.borrow_mut().remove(&principal.to_text())
+        })
+    }
+
+    pub fn get_all_moderators() -> Vec<Moderator> {
+        MODERATORS.with(|moderators| {
+            moderators.borrow().iter().map(|(_, moderator)| moderator).collect()
+        })
+    }
+
+    // Partner storage operations
+    pub fn insert_partner(partner: Partner) {
+        PARTNERS.with(|partners| {
+            partners.borrow_mut().insert(partner.principal.to_text(), partner);
+        });
+    }
+
+    pub fn get_partner(principal: &Principal) -> Option<Partner> {
+        PARTNERS.with(|partners| {
+            partners.borrow().get(&principal.to_text())
+        })
+    }
+
+    pub fn remove_partner(principal: &Principal) -> Option<Partner> {
+        PARTNERS.with(|partners| {
+            partners.borrow_mut().remove(&principal.to_text())
+        })
+    }
+
+    pub fn get_all_partners() -> Vec<Partner> {
+        PARTNERS.with(|partners| {
+            partners.borrow().iter().map(|(_, partner)| partner).collect()
+        })
+    }
+
+    // Chain statistics storage operations
+    pub fn get_chain_counters(chain: &str) -> ChainCounters {
+        CHAIN_COUNTERS.with(|counters| {
+            counters.borrow().get(&chain.to_string()).unwrap_or_default()
+        })
+    }
+
+    pub fn set_chain_counters(chain: &str, chain_counters: ChainCounters) {
+        CHAIN_COUNTERS.with(|counters| {
+            counters.borrow_mut().insert(chain.to_string(), chain_counters);
+        });
+    }
+
+    pub fn set_chain_contribution(company_id: &str, contribution: ChainContribution) {
+        CHAIN_CONTRIBUTIONS.with(|contributions| {
+            contributions.borrow_mut().insert(company_id.to_string(), contribution);
+        });
+    }
+
+    pub fn remove_chain_contribution(company_id: &str) -> Option<ChainContribution> {
+        CHAIN_CONTRIBUTIONS.with(|contributions| {
+            contributions.borrow_mut().remove(&company_id.to_string())
+        })
+    }
+
+    pub fn has_chain_contributions() -> bool {
+        CHAIN_CONTRIBUTIONS.with(|contributions| !contributions.borrow().is_empty())
+    }
+
+    pub fn clear_chain_contributions() {
+        CHAIN_CONTRIBUTIONS.with(|contributions| {
+            let ids: Vec<String> = contributions.borrow().iter().map(|(id, _)| id).collect();
+            let mut contributions = contributions.borrow_mut();
+            for id in ids {
+                contributions.remove(&id);
+            }
+        });
+    }
+
+    pub fn mark_chain_address_verified(target: &str) {
+        VERIFIED_CHAIN_ADDRESSES.with(|addresses| {
+            addresses.borrow_mut().insert(target.to_string(), time());
+        });
+    }
+
+    pub fn is_chain_address_verified(target: &str) -> bool {
+        VERIFIED_CHAIN_ADDRESSES.with(|addresses| {
+            addresses.borrow().contains_key(&target.to_string())
+        })
+    }
+
+    fn partner_subscription_key(company_id: &str, partner: &Principal) -> String {
+        format!("{}|{}", company_id, partner.to_text())
+    }
+
+    pub fn insert_partner_subscription(subscription: PartnerSubscription) {
+        PARTNER_SUBSCRIPTIONS.with(|subscriptions| {
+            subscriptions.borrow_mut().insert(
+                Self::partner_subscription_key(&subscription.company_id, &subscription.partner),
+                subscription,
+            );
+        });
+    }
+
+    pub fn remove_partner_subscription(company_id: &str, partner: &Principal) -> Option<PartnerSubscription> {
+        PARTNER_SUBSCRIPTIONS.with(|subscriptions| {
+            subscriptions.borrow_mut().remove(&Self::partner_subscription_key(company_id, partner))
+        })
+    }
+
+    pub fn get_company_subscriptions(company_id: &str) -> Vec<PartnerSubscription> {
+        let prefix = format!("{}|", company_id);
+        PARTNER_SUBSCRIPTIONS.with(|subscriptions| {
+            subscriptions
+                .borrow()
+                .range(prefix.clone()..)
+                .take_while(|(key, _)| key.starts_with(&prefix))
+                .map(|(_, subscription)| subscription)
+                .collect()
+        })
+    }
+
+    pub fn get_partner_subscriptions(partner: &Principal) -> Vec<PartnerSubscription> {
+        PARTNER_SUBSCRIPTIONS.with(|subscriptions| {
+            subscriptions
+                .borrow()
+                .iter()
+                .map(|(_, subscription)| subscription)
+                .filter(|subscription| subscription.partner == *partner)
+                .collect()
+        })
+    }
+
+    fn partner_status_change_key(partner: &Principal, seq: u64) -> String {
+        format!("{}:{:020}", partner.to_text(), seq)
+    }
+
+    // Keeps the newest `max_per_partner` changes per partner
+    pub fn append_partner_status_change(partner: &Principal, change: CompanyStatusChange, max_per_partner: usize) {
+        let prefix = format!("{}:", partner.to_text());
+        PARTNER_STATUS_CHANGES.with(|changes| {
+            let mut changes = changes.borrow_mut();
+            changes.insert(Self::partner_status_change_key(partner, change.seq), change);
+            let keys: Vec<String> = changes
+                .range(prefix.clone()..)
+                .take_while(|(key, _)| key.starts_with(&prefix))
+                .map(|(key, _)| key)
+                .collect();
+            for key in keys.iter().take(keys.len().saturating_sub(max_per_partner)) {
+                changes.remove(key);
+            }
+        });
+    }
+
+    // Oldest first
+    pub fn get_partner_status_changes_after(partner: &Principal, after_seq: u64, limit: usize) -> Vec<CompanyStatusChange> {
+        let prefix = format!("{}:", partner.to_text());
+        PARTNER_STATUS_CHANGES.with(|changes| {
+            changes
+                .borrow()
+                .range(Self::partner_status_change_key(partner, after_seq.saturating_add(1))..)
+                .take_while(|(key, _)| key.starts_with(&prefix))
+                .take(limit)
+                .map(|(_, change)| change)
+                .collect()
+        })
+    }
+
+    pub fn remove_partner_data(partner: &Principal) {
+        let prefix = format!("{}:", partner.to_text());
+        PARTNER_STATUS_CHANGES.with(|changes| {
+            let keys: Vec<String> = changes
+                .borrow()
+                .range(prefix.clone()..)
+                .take_while(|(key, _)| key.starts_with(&prefix))
+                .map(|(key, _)| key)
+                .collect();
+            let mut changes = changes.borrow_mut();
+            for key in keys {
+                changes.remove(&key);
+            }
+        });
+        PARTNER_SUBSCRIPTIONS.with(|subscriptions| {
+            let keys: Vec<String> = subscriptions
+                .borrow()
+                .iter()
+                .filter(|(_, subscription)| subscription.partner == *partner)
+                .map(|(key, _)| key)
+                .collect();
+            let mut subscriptions = subscriptions.borrow_mut();
+            for key in keys {
+                subscriptions.remove(&key);
+            }
+        });
+    }
+
+    // Returns false if a delivery to this partner was already in flight
+    pub fn try_begin_partner_delivery(partner: &Principal) -> bool {
+        PARTNER_DELIVERIES_IN_FLIGHT.with(|in_flight| in_flight.borrow_mut().insert(partner.to_text()))
+    }
+
+    pub fn end_partner_delivery(partner: &Principal) {
+        PARTNER_DELIVERIES_IN_FLIGHT.with(|in_flight| {
+            in_flight.borrow_mut().remove(&partner.to_text());
+        });
+    }
+
+    // Appends to the policy changelog, dropping the oldest entries beyond `max_entries`
+    pub fn append_policy_change(mut change: PolicyChange, max_entries: u64) {
+        POLICY_CHANGES.with(|changes| {
+            let mut changes = changes.borrow_mut();
+            change.seq = changes.last_key_value().map(|(seq, _)| seq).unwrap_or(0) + 1;
+            changes.insert(change.seq, change);
+            while changes.len() > max_entries {
+                match changes.first_key_value() {
+                    Some((oldest, _)) => changes.remove(&oldest),
+                    None => break,
+                };
+            }
+        });
+    }
+
+    // Oldest first
+    pub fn get_all_policy_changes() -> Vec<PolicyChange> {
+        POLICY_CHANGES.with(|changes| changes.borrow().iter().map(|(_, change)| change).collect())
+    }
+
+    // Webhook storage operations
+    pub fn get_webhook_subscription(id: &str) -> Option<WebhookSubscription> {
+        WEBHOOK_SUBSCRIPTIONS.with(|subscriptions| subscriptions.borrow().get(&id.to_string()))
+    }
+
+    pub fn get_all_webhook_subscriptions() -> Vec<WebhookSubscription> {
+        WEBHOOK_SUBSCRIPTIONS.with(|subscriptions| {
+            subscriptions.borrow().iter().map(|(_, subscription)| subscription).collect()
+        })
+    }
+
+    pub fn webhook_subscription_count() -> u64 {
+        WEBHOOK_SUBSCRIPTIONS.with(|subscriptions| subscriptions.borrow().len())
+    }
+
+    pub fn insert_webhook_subscription(subscription: WebhookSubscription) {
+        WEBHOOK_SUBSCRIPTIONS.with(|subscriptions| {
+            subscriptions.borrow_mut().insert(subscription.id.clone(), subscription);
+        });
+    }
+
+    pub fn remove_webhook_subscription(id: &str) -> Option<WebhookSubscription> {
+        WEBHOOK_SUBSCRIPTIONS.with(|subscriptions| subscriptions.borrow_mut().remove(&id.to_string()))
+    }
+
+    pub fn latest_webhook_event_seq() -> u64 {
+        WEBHOOK_OUTBOX.with(|outbox| outbox.borrow().last_key_value().map(|(seq, _)| seq).unwrap_or(0))
+    }
+
+    // Appends to the outbox, dropping the oldest entries beyond `max_events`
+    pub fn append_webhook_event(mut event: WebhookEvent, max_events: u64) -> u64 {
+        WEBHOOK_OUTBOX.with(|outbox| {
+            let mut outbox = outbox.borrow_mut();
+            event.seq = outbox.last_key_value().map(|(seq, _)| seq).unwrap_or(0) + 1;
+            let seq = event.seq;
+            outbox.insert(seq, event);
+            while outbox.len() > max_events {
+                match outbox.first_key_value() {
+                    Some((oldest, _)) => outbox.remove(&oldest),
+                    None => break,
+                };
+            }
+            seq
+        })
+    }
+
+    pub fn get_webhook_events_after(seq: u64, limit: usize) -> Vec<WebhookEvent> {
+        WEBHOOK_OUTBOX.with(|outbox| {
+            outbox
+                .borrow()
+                .range(seq + 1..)
+                .take(limit)
+                .map(|(_, event)| event)
+                .collect()
+        })
+    }
+
+    // Drops entries up to and including `up_to_seq`
+    pub fn prune_webhook_events(up_to_seq: u64) {
+        WEBHOOK_OUTBOX.with(|outbox| {
+            let delivered: Vec<u64> = outbox.borrow().range(..=up_to_seq).map(|(seq, _)| seq).collect();
+            let mut outbox = outbox.borrow_mut();
+            for seq in delivered {
+                outbox.remove(&seq);
+            }
+        });
+    }
+
+    pub fn try_begin_webhook_delivery(id: &str) -> bool {
+        WEBHOOK_DELIVERIES_IN_FLIGHT.with(|in_flight| in_flight.borrow_mut().insert(id.to_string()))
+    }
+
+    pub fn end_webhook_delivery(id: &str) {
+        WEBHOOK_DELIVERIES_IN_FLIGHT.with(|in_flight| {
+            in_flight.borrow_mut().remove(id);
+        });
+    }
+
+    pub fn set_listing_snapshot(snapshot: ListingSnapshot) {
+        LISTING_SNAPSHOT.with(|current| {
+            *current.borrow_mut() = Some(snapshot);
+        });
+    }
+
+    pub fn with_listing_snapshot<R>(f: impl FnOnce(Option<&ListingSnapshot>) -> R) -> R {
+        LISTING_SNAPSHOT.with(|current| f(current.borrow().as_ref()))
+    }
+
+    // Admin storage operations
+    pub fn insert_admin(admin: Admin) {
+        ADMINS.with(|admins| {
+            admins.borrow_mut().insert(admin.principal.to_text(), admin);
+        });
+    }
+
+    pub fn get_admin(principal: &Principal) -> Option<Admin> {
+        ADMINS.with(|admins| {
+            admins.borrow().get(&principal.to_text())
+        })
+    }
+
+    pub fn remove_admin(principal: &Principal) -> Option<Admin> {
+        ADMINS.with(|admins| {
+            admins.borrow_mut().remove(&principal.to_text())
+        })
+    }
+
+    pub fn get_all_admins() -> Vec<Admin> {
+        ADMINS.with(|admins| {
+            admins.borrow().iter().map(|(_, admin)| admin).collect()
+        })
+    }
+
+    pub fn insert_emergency_suspension(suspension: EmergencySuspension) {
+        EMERGENCY_SUSPENSIONS.with(|suspensions| {
+            suspensions.borrow_mut().insert(suspension.company_id.clone(), suspension);
+        });
+    }
+
+    pub fn get_emergency_suspension(company_id: &str) -> Option<EmergencySuspension> {
+        EMERGENCY_SUSPENSIONS.with(|suspensions| suspensions.borrow().get(&company_id.to_string()))
+    }
+
+    pub fn remove_emergency_suspension(company_id: &str) -> Option<EmergencySuspension> {
+        EMERGENCY_SUSPENSIONS.with(|suspensions| suspensions.borrow_mut().remove(&company_id.to_string()))
+    }
+
+    pub fn get_all_emergency_suspensions() -> Vec<EmergencySuspension> {
+        EMERGENCY_SUSPENSIONS.with(|suspensions| {
+            suspensions.borrow().iter().map(|(_, suspension)| suspension).collect()
+        })
+    }
+
+    // Reviewer pool config storage operations (single entry)
+    pub fn get_reviewer_pool_config() -> Option<ReviewerPoolConfig> {
+        REVIEWER_POOL_CONFIG.with(|config| {
+            config.borrow().get(&"reviewer_pool".to_string())
+        })
+    }
+
+    pub fn set_reviewer_pool_config(pool_config: ReviewerPoolConfig) {
+        REVIEWER_POOL_CONFIG.with(|config| {
+            config.borrow_mut().insert("reviewer_pool".to_string(), pool_config);
+        });
+    }
+
+    // Reputation staking config storage operations (single entry)
+    pub fn get_reputation_staking_config() -> Option<ReputationStakingConfig> {
+        REPUTATION_STAKING_CONFIG.with(|config| {
+            config.borrow().get(&"reputation_staking".to_string())
+        })
+    }
+
+    pub fn set_reputation_staking_config(staking_config: ReputationStakingConfig) {
+        REPUTATION_STAKING_CONFIG.with(|config| {
+            config.borrow_mut().insert("reputation_staking".to_string(), staking_config);
+        });
+    }
+
+    fn reputation_stake_key(company_id: &str, staker: Principal) -> String {
+        format!("{}:{}", company_id, staker.to_text())
+    }
+
+    pub fn get_reputation_stake(company_id: &str, staker: Principal) -> Option<ReputationStake> {
+        REPUTATION_STAKES.with(|stakes| stakes.borrow().get(&Self::reputation_stake_key(company_id, staker)))
+    }
+
+    pub fn get_reputation_stakes_for_company(company_id: &str) -> Vec<ReputationStake> {
+        let prefix = format!("{}:", company_id);
+        REPUTATION_STAKES.with(|stakes| {
+            stakes
+                .borrow()
+                .range(prefix.clone()..)
+                .take_while(|(key, _)| key.starts_with(&prefix))
+                .map(|(_, stake)| stake)
+                .collect()
+        })
+    }
+
+    pub fn insert_reputation_stake(stake: ReputationStake) {
+        REPUTATION_STAKES.with(|stakes| {
+            stakes
+                .borrow_mut()
+                .insert(Self::reputation_stake_key(&stake.company_id, stake.staker), stake);
+        });
+    }
+
+    pub fn remove_reputation_stake(company_id: &str, staker: Principal) {
+        REPUTATION_STAKES.with(|stakes| {
+            stakes.borrow_mut().remove(&Self::reputation_stake_key(company_id, staker));
+        });
+    }
+
+    // Reviewer storage operations
+    pub fn insert_reviewer(reviewer: Reviewer) {
+        REVIEWERS.with(|reviewers| {
+            reviewers.borrow_mut().insert(reviewer.principal.to_text(), reviewer);
+        });
+    }
+
+    pub fn get_reviewer(principal: &Principal) -> Option<Reviewer> {
+        REVIEWERS.with(|reviewers| {
+            reviewers.borrow().get(&principal.to_text())
+        })
+    }
+
+    pub fn remove_reviewer(principal: &Principal) -> Option<Reviewer> {
+        REVIEWERS.with(|reviewers| {
+            reviewers.borrow_mut().remove(&principal.to_text())
+        })
+    }
+
+    pub fn get_all_reviewers() -> Vec<Reviewer> {
+        REVIEWERS.with(|reviewers| {
+            reviewers.borrow().iter().map(|(_, reviewer)| reviewer).collect()
+        })
+    }
+
+    // Review case storage operations
+    pub fn insert_review_case(case: ReviewCase) {
+        REVIEW_CASES.with(|cases| {
+            cases.borrow_mut().insert(case.task_id.clone(), case);
+        });
+    }
+
+    pub fn get_review_case(task_id: &str) -> Option<ReviewCase> {
+        REVIEW_CASES.with(|cases| {
+            cases.borrow().get(&task_id.to_string())
+        })
+    }
+
+    pub fn get_all_review_cases() -> Vec<ReviewCase> {
+        REVIEW_CASES.with(|cases| {
+            cases.borrow().iter().map(|(_, case)| case).collect()
+        })
+    }
+
+    // Timelocked action storage operations
+    pub fn insert_timelocked_action(action: TimelockedAction) {
+        TIMELOCKED_ACTIONS.with(|actions| {
+            actions.borrow_mut().insert(action.id.clone(), action);
+        });
+    }
+
+    pub fn get_timelocked_action(action_id: &str) -> Option<TimelockedAction> {
+        TIMELOCKED_ACTIONS.with(|actions| {
+            actions.borrow().get(&action_id.to_string())
+        })
+    }
+
+    pub fn get_all_timelocked_actions() -> Vec<TimelockedAction> {
+        TIMELOCKED_ACTIONS.with(|actions| {
+            actions.borrow().iter().map(|(_, action)| action).collect()
+        })
+    }
+
+    pub fn timelocked_action_count() -> u64 {
+        TIMELOCKED_ACTIONS.with(|actions| actions.borrow().len())
+    }
+
+    // Timelock config storage operations (single entry)
+    pub fn get_timelock_config() -> Option<TimelockConfig> {
+        TIMELOCK_CONFIG.with(|config| {
+            config.borrow().get(&"timelock".to_string())
+        })
+    }
+
+    pub fn set_timelock_config(timelock_config: TimelockConfig) {
+        TIMELOCK_CONFIG.with(|config| {
+            config.borrow_mut().insert("timelock".to_string(), timelock_config);
+        });
+    }
+
+    // Ownership transfer storage operations
+    pub fn get_ownership_transfer(company_id: &str) -> Option<OwnershipTransfer> {
+        OWNERSHIP_TRANSFERS.with(|transfers| transfers.borrow().get(&company_id.to_string()))
+    }
+
+    pub fn insert_ownership_transfer(transfer: OwnershipTransfer) {
+        OWNERSHIP_TRANSFERS.with(|transfers| {
+            transfers.borrow_mut().insert(transfer.company_id.clone(), transfer);
+        });
+    }
+
+    pub fn remove_ownership_transfer(company_id: &str) -> Option<OwnershipTransfer> {
+        OWNERSHIP_TRANSFERS.with(|transfers| transfers.borrow_mut().remove(&company_id.to_string()))
+    }
+
+    pub fn get_all_ownership_transfers() -> Vec<OwnershipTransfer> {
+        OWNERSHIP_TRANSFERS.with(|transfers| {
+            transfers.borrow().iter().map(|(_, transfer)| transfer).collect()
+        })
+    }
+
+    // Re-verification campaign storage operations
+    pub fn insert_reverification_campaign(campaign: ReverificationCampaign) {
+        REVERIFICATION_CAMPAIGNS.with(|campaigns| {
+            campaigns.borrow_mut().insert(campaign.id.clone(), campaign);
+        });
+    }
+
+    pub fn get_reverification_campaign(campaign_id: &str) -> Option<ReverificationCampaign> {
+        REVERIFICATION_CAMPAIGNS.with(|campaigns| campaigns.borrow().get(&campaign_id.to_string()))
+    }
+
+    pub fn get_all_reverification_campaigns() -> Vec<ReverificationCampaign> {
+        REVERIFICATION_CAMPAIGNS.with(|campaigns| {
+            campaigns.borrow().iter().map(|(_, campaign)| campaign).collect()
+        })
+    }
+
+    pub fn reverification_campaign_count() -> u64 {
+        REVERIFICATION_CAMPAIGNS.with(|campaigns| campaigns.borrow().len())
+    }
+
+    // Inactivity policy storage operations (single entry)
+    pub fn get_inactivity_policy() -> Option<InactivityPolicy> {
+        INACTIVITY_POLICY.with(|policy| {
+            policy.borrow().get(&"inactivity".to_string())
+        })
+    }
+
+    pub fn set_inactivity_policy(inactivity_policy: InactivityPolicy) {
+        INACTIVITY_POLICY.with(|policy| {
+            policy.borrow_mut().insert("inactivity".to_string(), inactivity_policy);
+        });
+    }
+
+    // Reputation escrow policy storage operations (single entry)
+    pub fn get_reputation_escrow_policy() -> Option<ReputationEscrowPolicy> {
+        REPUTATION_ESCROW_POLICY.with(|policy| {
+            policy.borrow().get(&"reputation_escrow".to_string())
+        })
+    }
+
+    pub fn set_reputation_escrow_policy(reputation_escrow_policy: ReputationEscrowPolicy) {
+        REPUTATION_ESCROW_POLICY.with(|policy| {
+            policy.borrow_mut().insert("reputation_escrow".to_string(), reputation_escrow_policy);
+        });
+    }
+
+    // Tier definitions storage operations (single entry)
+    pub fn get_tier_definitions() -> Option<TierDefinitions> {
+        TIER_DEFINITIONS.with(|definitions| {
+            definitions.borrow().get(&"tiers".to_string())
+        })
+    }
+
+    pub fn set_tier_definitions(tier_definitions: TierDefinitions) {
+        TIER_DEFINITIONS.with(|definitions| {
+            definitions.borrow_mut().insert("tiers".to_string(), tier_definitions);
+        });
+    }
+
+    fn concern_key(company_id: &str, principal: Principal) -> String {
+        format!("{}:{}", company_id, principal.to_text())
+    }
+
+    pub fn get_concern(company_id: &str, principal: Principal) -> Option<Concern> {
+        CONCERNS.with(|concerns| concerns.borrow().get(&Self::concern_key(company_id, principal)))
+    }
+
+    pub fn get_concerns_for_company(company_id: &str) -> Vec<Concern> {
+        let prefix = format!("{}:", company_id);
+        CONCERNS.with(|concerns| {
+            concerns
+                .borrow()
+                .range(prefix.clone()..)
+                .take_while(|(key, _)| key.starts_with(&prefix))
+                .map(|(_, concern)| concern)
+                .collect()
+        })
+    }
+
+    pub fn get_all_concerns() -> Vec<Concern> {
+        CONCERNS.with(|concerns| concerns.borrow().iter().map(|(_, concern)| concern).collect())
+    }
+
+    pub fn insert_concern(concern: Concern) {
+        CONCERNS.with(|concerns| {
+            concerns
+                .borrow_mut()
+                .insert(Self::concern_key(&concern.company_id, concern.raised_by), concern);
+        });
+    }
+
+    pub fn remove_concern(company_id: &str, principal: Principal) -> Option<Concern> {
+        CONCERNS.with(|concerns| concerns.borrow_mut().remove(&Self::concern_key(company_id, principal)))
+    }
+
+    // Community cool-down policy storage operations (single entry)
+    pub fn get_community_cooldown_policy() -> Option<CommunityCooldownPolicy> {
+        COMMUNITY_COOLDOWN_POLICY.with(|policy| {
+            policy.borrow().get(&"community_cooldowns".to_string())
+        })
+    }
+
+    pub fn set_community_cooldown_policy(community_cooldown_policy: CommunityCooldownPolicy) {
+        COMMUNITY_COOLDOWN_POLICY.with(|policy| {
+            policy.borrow_mut().insert("community_cooldowns".to_string(), community_cooldown_policy);
+        });
+    }
+
+    pub fn get_cooldown_mark(key: &str) -> Option<u64> {
+        COMMUNITY_COOLDOWNS.with(|marks| marks.borrow().get(&key.to_string()))
+    }
+
+    pub fn get_cooldown_marks_with_prefix(prefix: &str) -> Vec<(String, u64)> {
+        COMMUNITY_COOLDOWNS.with(|marks| {
+            marks
+                .borrow()
+                .range(prefix.to_string()..)
+                .take_while(|(key, _)| key.starts_with(prefix))
+                .collect()
+        })
+    }
+
+    pub fn set_cooldown_mark(key: &str, at: u64) {
+        COMMUNITY_COOLDOWNS.with(|marks| {
+            marks.borrow_mut().insert(key.to_string(), at);
+        });
+    }
+
+    pub fn remove_cooldown_mark(key: &str) {
+        COMMUNITY_COOLDOWNS.with(|marks| {
+            marks.borrow_mut().remove(&key.to_string());
+        });
+    }
+
+    // Drops marks of the given prefix set before `before`
+    pub fn prune_cooldown_marks(prefix: &str, before: u64) {
+        let stale: Vec<String> = Self::get_cooldown_marks_with_prefix(prefix)
+            .into_iter()
+            .filter(|(_, at)| *at < before)
+            .map(|(key, _)| key)
+            .collect();
+        COMMUNITY_COOLDOWNS.with(|marks| {
+            let mut marks = marks.borrow_mut();
+            for key in stale {
+                marks.remove(&key);
+            }
+        });
+    }
+
+    pub fn get_registry_identity() -> Option<RegistryIdentity> {
+        REGISTRY_IDENTITY.with(|identity| {
+            identity.borrow().get(&"registry".to_string())
+        })
+    }
+
+    pub fn set_registry_identity(registry_identity: RegistryIdentity) {
+        REGISTRY_IDENTITY.with(|identity| {
+            identity.borrow_mut().insert("registry".to_string(), registry_identity);
+        });
+    }
+
+    // Score weight override storage operations
+    pub fn get_score_weight_override(key: &str) -> Option<ScoreWeights> {
+        SCORE_WEIGHT_OVERRIDES.with(|overrides| {
+            overrides.borrow().get(&key.to_string())
+        })
+    }
+
+    pub fn set_score_weight_override(key: String, weights: Option<ScoreWeights>) {
+        SCORE_WEIGHT_OVERRIDES.with(|overrides| {
+            match weights {
+                Some(weights) => overrides.borrow_mut().insert(key, weights),
+                None => overrides.borrow_mut().remove(&key),
+            };
+        });
+    }
+
+    // Critical action proposal storage operations
+    pub fn insert_critical_action_proposal(proposal: CriticalActionProposal) {
+        CRITICAL_ACTION_PROPOSALS.with(|proposals| {
+            proposals.borrow_mut().insert(proposal.id.clone(), proposal);
+        });
+    }
+
+    pub fn get_critical_action_proposal(proposal_id: &str) -> Option<CriticalActionProposal> {
+        CRITICAL_ACTION_PROPOSALS.with(|proposals| {
+            proposals.borrow().get(&proposal_id.to_string())
+        })
+    }
+
+    pub fn get_all_critical_action_proposals() -> Vec<CriticalActionProposal> {
+        CRITICAL_ACTION_PROPOSALS.with(|proposals| {
+            proposals.borrow().iter().map(|(_, proposal)| proposal).collect()
+        })
+    }
+
+    pub fn critical_action_proposal_count() -> u64 {
+        CRITICAL_ACTION_PROPOSALS.with(|proposals| proposals.borrow().len())
+    }
+
+    // Blocklist storage operations (keyed by "<kind>:<value>")
+    pub fn insert_blocklist_entry(key: String, entry: BlocklistEntry) {
+        BLOCKLIST.with(|blocklist| {
+            blocklist.borrow_mut().insert(key, entry);
+        });
+    }
+
+    pub fn get_blocklist_entry(key: &str) -> Option<BlocklistEntry> {
+        BLOCKLIST.with(|blocklist| {
+            blocklist.borrow().get(&key.to_string())
+        })
+    }
+
+    pub fn get_all_blocklist_entries() -> Vec<BlocklistEntry> {
+        BLOCKLIST.with(|blocklist| {
+            blocklist.borrow().iter().map(|(_, entry)| entry).collect()
+        })
+    }
+
+    // Audit log storage operations
+    pub fn insert_audit_log_entry(entry: AuditLogEntry) {
+        AUDIT_LOG.with(|log| {
+            log.borrow_mut().insert(entry.id.clone(), entry);
+        });
+    }
+
+    pub fn get_all_audit_log_entries() -> Vec<AuditLogEntry> {
+        AUDIT_LOG.with(|log| {
+            log.borrow().iter().map(|(_, entry)| entry).collect()
+        })
+    }
+
+    pub fn audit_log_count() -> u64 {
+        AUDIT_LOG.with(|log| log.borrow().len())
+    }
+
+    // Open data snapshot storage operations (single entry)
+    pub fn get_open_data_snapshot() -> Option<OpenDataSnapshot> {
+        OPEN_DATA_SNAPSHOT.with(|snapshot| {
+            snapshot.borrow().get(&"latest".to_string())
+        })
+    }
+
+    pub fn set_open_data_snapshot(open_data: OpenDataSnapshot) {
+        OPEN_DATA_SNAPSHOT.with(|snapshot| {
+            snapshot.borrow_mut().insert("latest".to_string(), open_data);
+        });
+    }
+
+    // Company event journal operations
+    fn append_company_event(company_id: &str, kind: CompanyEventKind) {
+        COMPANY_EVENTS.with(|events| {
+            let mut events = events.borrow_mut();
+            let seq = events.last_key_value().map_or(1, |(seq, _)| seq + 1);
+            events.insert(seq, CompanyEvent {
+                seq,
+                company_id: company_id.to_string(),
+                kind,
+                timestamp: time(),
+            });
+        });
+    }
+
+    pub fn get_company_events_after(after_seq: u64, limit: usize) -> Vec<CompanyEvent> {
+        COMPANY_EVENTS.with(|events| {
+            events
+                .borrow()
+                .range(after_seq.saturating_add(1)..)
+                .take(limit)
+                .map(|(_, event)| event)
+                .collect()
+        })
+    }
+
+    pub fn first_company_event_seq() -> u64 {
+        COMPANY_EVENTS.with(|events| {
+            events.borrow().first_key_value().map_or(0, |(seq, _)| seq)
+        })
+    }
+
+    pub fn latest_company_event_seq() -> u64 {
+        COMPANY_EVENTS.with(|events| {
+            events.borrow().last_key_value().map_or(0, |(seq, _)| seq)
+        })
+    }
+
+    // Reputation journal operations
+    fn reputation_event_key(company_id: &str, seq: u64) -> String {
+        format!("{}:{:010}", company_id, seq)
+    }
+
+    pub fn append_reputation_event(mut event: ReputationEvent) -> u64 {
+        let prefix = format!("{}:", event.company_id);
+        REPUTATION_EVENTS.with(|events| {
+            let mut events = events.borrow_mut();
+            let last_seq = events
+                .range(..Self::reputation_event_key(&event.company_id, u64::MAX))
+                .next_back()
+                .filter(|(key, _)| key.starts_with(&prefix))
+                .map_or(0, |(_, last)| last.seq);
+            event.seq = last_seq + 1;
+            let seq = event.seq;
+            events.insert(Self::reputation_event_key(&event.company_id, seq), event);
+            seq
+        })
+    }
+
+    // Oldest first
+    pub fn get_reputation_events(company_id: &str) -> Vec<ReputationEvent> {
+        let prefix = format!("{}:", company_id);
+        REPUTATION_EVENTS.with(|events| {
+            events
+                .borrow()
+                .range(prefix.clone()..)
+                .take_while(|(key, _)| key.starts_with(&prefix))
+                .map(|(_, event)| event)
+                .collect()
+        })
+    }
+
+    pub fn has_reputation_events(company_id: &str) -> bool {
+        let prefix = format!("{}:", company_id);
+        REPUTATION_EVENTS.with(|events| {
+            events
+                .borrow()
+                .range(prefix.clone()..)
+                .next()
+                .is_some_and(|(key, _)| key.starts_with(&prefix))
+        })
+    }
+
+    // Drops events up to and including `up_to_seq` that are older than `before`
+    pub fn prune_company_events(up_to_seq: u64, before: u64) {
+        COMPANY_EVENTS.with(|events| {
+            let stale: Vec<u64> = events
+                .borrow()
+                .range(..=up_to_seq)
+                .take_while(|(_, event)| event.timestamp < before)
+                .map(|(seq, _)| seq)
+                .collect();
+            let mut events = events.borrow_mut();
+            for seq in stale {
+                events.remove(&seq);
+            }
+        });
+    }
+
+    pub fn get_journal_cursor(consumer: &str) -> u64 {
+        JOURNAL_CURSORS.with(|cursors| {
+            cursors.borrow().get(&consumer.to_string()).unwrap_or(0)
+        })
+    }
+
+    // Events at or below this sequence have been seen by every consumer
+    pub fn min_journal_cursor() -> u64 {
+        JOURNAL_CURSORS.with(|cursors| {
+            cursors.borrow().iter().map(|(_, seq)| seq).min().unwrap_or(0)
+        })
+    }
+
+    pub fn set_journal_cursor(consumer: &str, seq: u64) {
+        JOURNAL_CURSORS.with(|cursors| {
+            cursors.borrow_mut().insert(consumer.to_string(), seq);
+        });
+    }
+
+    pub fn remove_journal_cursor(consumer: &str) {
+        JOURNAL_CURSORS.with(|cursors| {
+            cursors.borrow_mut().remove(&consumer.to_string());
+        });
+    }
+
+    // Saved search storage operations
+    pub fn insert_saved_search(search: SavedSearch) {
+        SAVED_SEARCHES.with(|searches| {
+            searches.borrow_mut().insert(search.id.clone(), search);
+        });
+    }
+
+    pub fn get_saved_search(search_id: &str) -> Option<SavedSearch> {
+        SAVED_SEARCHES.with(|searches| {
+            searches.borrow().get(&search_id.to_string())
+        })
+    }
+
+    pub fn remove_saved_search(search_id: &str) -> Option<SavedSearch> {
+        SAVED_SEARCHES.with(|searches| {
+            searches.borrow_mut().remove(&search_id.to_string())
+        })
+    }
+
+    pub fn get_all_saved_searches() -> Vec<SavedSearch> {
+        SAVED_SEARCHES.with(|searches| {
+            searches.borrow().iter().map(|(_, search)| search).collect()
+        })
+    }
+
+    // Tag proposal storage operations (keyed by "<company_id>:<tag>")
+    pub fn insert_tag_proposal(proposal: TagProposal) {
+        TAG_PROPOSALS.with(|proposals| {
+            proposals
+                .borrow_mut()
+                .insert(format!("{}:{}", proposal.company_id, proposal.tag), proposal);
+        });
+    }
+
+    pub fn get_tag_proposal(company_id: &str, tag: &str) -> Option<TagProposal> {
+        TAG_PROPOSALS.with(|proposals| {
+            proposals.borrow().get(&format!("{}:{}", company_id, tag))
+        })
+    }
+
+    pub fn get_tag_proposals_for_company(company_id: &str) -> Vec<TagProposal> {
+        let prefix = format!("{}:", company_id);
+        TAG_PROPOSALS.with(|proposals| {
+            proposals
+                .borrow()
+                .range(prefix.clone()..)
+                .take_while(|(key, _)| key.starts_with(&prefix))
+                .map(|(_, proposal)| proposal)
+                .collect()
+        })
+    }
+
+    pub fn get_all_tag_proposals() -> Vec<TagProposal> {
+        TAG_PROPOSALS.with(|proposals| {
+            proposals.borrow().iter().map(|(_, proposal)| proposal).collect()
+        })
+    }
+
+    // Usage stats storage operations
+    pub fn get_usage_stats(principal: &Principal) -> Option<UsageStats> {
+        USAGE_STATS.with(|usage| {
+            usage.borrow().get(&principal.to_text())
+        })
+    }
+
+    pub fn insert_usage_stats(stats: UsageStats) {
+        USAGE_STATS.with(|usage| {
+            usage.borrow_mut().insert(stats.principal.to_text(), stats);
+        });
+    }
+
+    pub fn get_all_usage_stats() -> Vec<UsageStats> {
+        USAGE_STATS.with(|usage| {
+            usage.borrow().iter().map(|(_, stats)| stats).collect()
+        })
+    }
+
+    // Drops callers idle since `idle_before`, then the least recently seen beyond `max_entries`
+    pub fn prune_usage_stats(idle_before: u64, max_entries: u64) -> u64 {
+        USAGE_STATS.with(|usage| {
+            let mut seen: Vec<(u64, String)> = usage
+                .borrow()
+                .iter()
+                .map(|(key, stats)| (stats.last_seen, key))
+                .collect();
+            seen.sort();
+            let over_cap = (seen.len() as u64).saturating_sub(max_entries) as usize;
+            let stale: Vec<String> = seen
+                .into_iter()
+                .enumerate()
+                .take_while(|(index, (last_seen, _))| *index < over_cap || *last_seen < idle_before)
+                .map(|(_, (_, key))| key)
+                .collect();
+            let mut usage = usage.borrow_mut();
+            for key in &stale {
+                usage.remove(key);
+            }
+            stale.len() as u64
+        })
+    }
 
-impl StorageManager {
-    // Company storage operations
-    pub fn insert_company(company_id: String, company: Company) {
-        COMPANIES.with(|companies| {
-            companies.borrow_mut().insert(company_id, company);
+    // API key storage operations
+    pub fn insert_api_key(api_key: ApiKey) {
+        API_KEYS.with(|keys| {
+            keys.borrow_mut().insert(api_key.key_hash.clone(), api_key);
         });
     }
 
-    pub fn get_company(company_id: &str) -> Option<Company> {
-        COMPANIES.with(|companies| {
-            companies.borrow().get(&company_id.to_string())
+    pub fn get_api_key_by_hash(key_hash: &str) -> Option<ApiKey> {
+        API_KEYS.with(|keys| {
+            keys.borrow().get(&key_hash.to_string())
         })
     }
 
-    pub fn update_company<F>(company_id: &str, update_fn: F) -> bool 
-    where 
-        F: FnOnce(&mut Company)
-    {
-        COMPANIES.with(|companies| {
-            let mut companies_map = companies.borrow_mut();
-            if let Some(mut company) = companies_map.get(&company_id.to_string()) {
-                update_fn(&mut company);
-                company.updated_at = time();
-                companies_map.insert(company_id.to_string(), company);
-                true
-            } else {
-                false
-            }
+    pub fn get_all_api_keys() -> Vec<ApiKey> {
+        API_KEYS.with(|keys| {
+            keys.borrow().iter().map(|(_, api_key)| api_key).collect()
         })
     }
 
-    pub fn get_all_companies() -> Vec<Company> {
-        COMPANIES.with(|companies| {
-            companies
-                .borrow()
-                .iter()
-                .map(|(_, company)| company)
-                .collect()
+    // Fixed one-minute window per key; Err carries the seconds until the window ends
+    pub fn check_api_key_rate_limit(key_id: &str, per_minute: u32) -> Result<(), u64> {
+        API_KEY_RATE_LIMITS.with(|limits| {
+            let mut limits = limits.borrow_mut();
+            let now = time();
+            let window_start = now - now % Self::API_KEY_RATE_LIMIT_WINDOW_NS;
+            let count = match limits.get(&key_id.to_string()) {
+                Some((start, count)) if start == window_start => count,
+                _ => 0,
+            };
+            if count >= per_minute {
+                return Err(Self::secs_until(now, window_start + Self::API_KEY_RATE_LIMIT_WINDOW_NS));
+            }
+            limits.insert(key_id.to_string(), (window_start, count + 1));
+            Ok(())
         })
     }
 
-    pub fn get_companies_count() -> u64 {
-        COMPANIES.with(|companies| companies.borrow().len())
+    // Sitemap storage operations
+    pub fn set_sitemap_entry(company_id: &str, last_modified: u64) {
+        SITEMAP_ENTRIES.with(|entries| {
+            entries.borrow_mut().insert(company_id.to_string(), last_modified);
+        });
     }
 
-    // Domain challenge storage operations
-    pub fn insert_domain_challenge(company_id: String, challenge: DomainVerificationChallenge) {
-        DOMAIN_CHALLENGES.with(|challenges| {
-            challenges.borrow_mut().insert(company_id, challenge);
+    pub fn remove_sitemap_entry(company_id: &str) {
+        SITEMAP_ENTRIES.with(|entries| {
+            entries.borrow_mut().remove(&company_id.to_string());
         });
     }
 
-    pub fn get_domain_challenge(company_id: &str) -> Option<DomainVerificationChallenge> {
-        DOMAIN_CHALLENGES.with(|challenges| {
-            challenges.borrow().get(&company_id.to_string())
+    pub fn get_sitemap_entries() -> Vec<(String, u64)> {
+        SITEMAP_ENTRIES.with(|entries| {
+            entries.borrow().iter().collect()
         })
     }
 
-    pub fn remove_domain_challenge(company_id: &str) -> Option<DomainVerificationChallenge> {
-        DOMAIN_CHALLENGES.with(|challenges| {
-            challenges.borrow_mut().remove(&company_id.to_string())
+    pub fn clear_sitemap_entries() {
+        SITEMAP_ENTRIES.with(|entries| {
+            let ids: Vec<String> = entries.borrow().iter().map(|(id, _)| id).collect();
+            let mut entries = entries.borrow_mut();
+            for id in ids {
+                entries.remove(&id);
+            }
+        });
+    }
+
+    pub fn get_sitemap_base_url() -> Option<String> {
+        SITEMAP_SETTINGS.with(|settings| {
+            settings.borrow().get(&"base_url".to_string())
         })
     }
 
-    // Cross-chain challenge storage operations
-    pub fn insert_crosschain_challenge(challenge_key: String, challenge: CrossChainChallenge) {
-        CROSSCHAIN_CHALLENGES.with(|challenges| {
-            challenges.borrow_mut().insert(challenge_key, challenge);
+    pub fn set_sitemap_base_url(base_url: String) {
+        SITEMAP_SETTINGS.with(|settings| {
+            settings.borrow_mut().insert("base_url".to_string(), base_url);
         });
     }
 
-    pub fn get_crosschain_challenge(challenge_key: &str) -> Option<CrossChainChallenge> {
-        CROSSCHAIN_CHALLENGES.with(|challenges| {
-            challenges.borrow().get(&challenge_key.to_string())
+    // Cache invalidation hook storage operations
+    pub fn get_cache_invalidation_hook() -> Option<CacheInvalidationHook> {
+        CACHE_INVALIDATION_HOOK.with(|hook| {
+            hook.borrow().get(&"frontend".to_string())
         })
     }
 
-    pub fn remove_crosschain_challenge(challenge_key: &str) -> Option<CrossChainChallenge> {
-        CROSSCHAIN_CHALLENGES.with(|challenges| {
-            challenges.borrow_mut().remove(&challenge_key.to_string())
+    pub fn set_cache_invalidation_hook(cache_hook: CacheInvalidationHook) {
+        CACHE_INVALIDATION_HOOK.with(|hook| {
+            hook.borrow_mut().insert("frontend".to_string(), cache_hook);
+        });
+    }
+
+    pub fn remove_cache_invalidation_hook() {
+        CACHE_INVALIDATION_HOOK.with(|hook| {
+            hook.borrow_mut().remove(&"frontend".to_string());
+        });
+    }
+
+    // Returns false if a delivery was already in flight
+    pub fn try_begin_cache_hook_delivery() -> bool {
+        CACHE_HOOK_IN_FLIGHT.with(|in_flight| !in_flight.replace(true))
+    }
+
+    pub fn end_cache_hook_delivery() {
+        CACHE_HOOK_IN_FLIGHT.with(|in_flight| {
+            *in_flight.borrow_mut() = false;
+        });
+    }
+
+    // Returns false if a re-check of this proof is already pending
+    pub fn try_begin_proof_recheck(key: &str) -> bool {
+        PROOF_RECHECKS_PENDING.with(|pending| pending.borrow_mut().insert(key.to_string()))
+    }
+
+    pub fn end_proof_recheck(key: &str) {
+        PROOF_RECHECKS_PENDING.with(|pending| {
+            pending.borrow_mut().remove(key);
+        });
+    }
+
+    // Data migration storage operations
+    pub fn insert_data_migration(migration: DataMigration) {
+        DATA_MIGRATIONS.with(|migrations| {
+            migrations.borrow_mut().insert(migration.id.clone(), migration);
+        });
+    }
+
+    pub fn get_data_migration(migration_id: &str) -> Option<DataMigration> {
+        DATA_MIGRATIONS.with(|migrations| migrations.borrow().get(&migration_id.to_string()))
+    }
+
+    pub fn get_all_data_migrations() -> Vec<DataMigration> {
+        DATA_MIGRATIONS.with(|migrations| {
+            migrations.borrow().iter().map(|(_, migration)| migration).collect()
         })
     }
 
-    pub fn get_crosschain_challenges_for_company(company_id: &str) -> Vec<CrossChainChallenge> {
-        CROSSCHAIN_CHALLENGES.with(|challenges| {
-            challenges
+    pub fn data_migration_count() -> u64 {
+        DATA_MIGRATIONS.with(|migrations| migrations.borrow().len())
+    }
+
+    // Feature flag storage operations
+    pub fn get_feature_flag(name: &str) -> Option<FeatureFlag> {
+        FEATURE_FLAGS.with(|flags| flags.borrow().get(&name.to_string()))
+    }
+
+    pub fn insert_feature_flag(flag: FeatureFlag) {
+        FEATURE_FLAGS.with(|flags| {
+            flags.borrow_mut().insert(flag.name.clone(), flag);
+        });
+    }
+
+    pub fn remove_feature_flag(name: &str) -> bool {
+        FEATURE_FLAGS.with(|flags| flags.borrow_mut().remove(&name.to_string()).is_some())
+    }
+
+    // Simulation mode storage operations
+    #[cfg(feature = "simulation")]
+    pub fn is_simulation_enabled() -> bool {
+        SIMULATION_ENABLED.with(|enabled| *enabled.borrow())
+    }
+
+    #[cfg(feature = "simulation")]
+    pub fn set_simulation_enabled(value: bool) {
+        SIMULATION_ENABLED.with(|enabled| *enabled.borrow_mut() = value);
+    }
+
+    #[cfg(feature = "simulation")]
+    pub fn get_simulation_fixtures() -> Vec<SimulationFixture> {
+        SIMULATION_FIXTURES.with(|fixtures| fixtures.borrow().iter().map(|(_, fixture)| fixture).collect())
+    }
+
+    #[cfg(feature = "simulation")]
+    pub fn insert_simulation_fixture(key: String, fixture: SimulationFixture) {
+        SIMULATION_FIXTURES.with(|fixtures| {
+            fixtures.borrow_mut().insert(key, fixture);
+        });
+    }
+
+    #[cfg(feature = "simulation")]
+    pub fn remove_simulation_fixture(key: &str) -> bool {
+        SIMULATION_FIXTURES.with(|fixtures| fixtures.borrow_mut().remove(&key.to_string()).is_some())
+    }
+
+    #[cfg(feature = "simulation")]
+    pub fn clear_simulation_fixtures() {
+        SIMULATION_FIXTURES.with(|fixtures| {
+            let keys: Vec<String> = fixtures.borrow().iter().map(|(key, _)| key).collect();
+            let mut fixtures = fixtures.borrow_mut();
+            for key in keys {
+                fixtures.remove(&key);
+            }
+        });
+    }
+
+    // Security event storage operations
+    pub fn next_security_event_seq() -> u64 {
+        SECURITY_EVENTS.with(|events| {
+            events.borrow().last_key_value().map_or(1, |(seq, _)| seq + 1)
+        })
+    }
+
+    // Keeps at most `max_events`, dropping the oldest
+    pub fn insert_security_event(event: SecurityEvent, max_events: u64) {
+        SECURITY_EVENTS.with(|events| {
+            let mut events = events.borrow_mut();
+            events.insert(event.seq, event);
+            while events.len() > max_events {
+                match events.first_key_value() {
+                    Some((seq, _)) => events.remove(&seq),
+                    None => break,
+                };
+            }
+        });
+    }
+
+    // Newest first
+    pub fn get_security_events_for(principal: &Principal) -> Vec<SecurityEvent> {
+        SECURITY_EVENTS.with(|events| {
+            events
                 .borrow()
                 .iter()
-                .filter_map(|(_, challenge)| {
-                    if challenge.company_id == company_id {
-                        Some(challenge)
-                    } else {
-                        None
-                    }
-                })
+                .rev()
+                .map(|(_, event)| event)
+                .filter(|event| &event.principal == principal)
                 .collect()
         })
     }
 
     // Utility functions
+    // Unique across shards and within a round: this canister's id plus a counter that
+    // survives upgrades
     pub fn generate_company_id() -> String {
-        format!("company_{}", time())
+        format!("company_{}_{}", ic_cdk::id(), Self::next_id_counter("company"))
+    }
+
+    fn next_id_counter(kind: &str) -> u64 {
+        ID_COUNTERS.with(|counters| {
+            let mut counters = counters.borrow_mut();
+            let next = counters.get(&kind.to_string()).unwrap_or(0);
+            counters.insert(kind.to_string(), next + 1);
+            next
+        })
     }
 
     pub fn generate_crosschain_challenge_key(company_id: &str, chain_type: &str, address: &str) -> String {
@@ -153,16 +2958,8 @@ impl StorageManager {
                 .borrow()
                 .iter()
                 .find_map(|(key, challenge)| {
-                    let challenge_chain = match challenge.chain_type {
-                        crate::types::ChainType::Ethereum => "ethereum",
-                        crate::types::ChainType::Bitcoin => "bitcoin",
-                        crate::types::ChainType::ICP => "icp",
-                        crate::types::ChainType::Polygon => "polygon",
-                        crate::types::ChainType::Solana => "solana",
-                        crate::types::ChainType::Sui => "sui",
-                        crate::types::ChainType::TON => "ton",
-                    };
-                    
+                    let challenge_chain = CrossChainVerifier::chain_name(&challenge.chain_type);
+
                     if challenge.company_id == company_id 
                         && challenge_chain == chain_type 
                         && challenge.address_or_contract == address {
@@ -174,81 +2971,463 @@ impl StorageManager {
         })
     }
 
-    // Enhanced rate limiting functions with security improvements
-    pub fn check_http_rate_limit(principal: Principal) -> bool {
-        Self::check_rate_limit_with_config(principal, 10, 60_000_000_000)
+    // Rate limiting: every action has its own quota, counted in fixed windows aligned to
+    // multiples of the window length, so the outcome depends only on the time and the count
+    const HTTP_RATE_LIMIT: (&'static str, u32, u64) = ("http_outcall", 10, 60_000_000_000); // 10 per minute
+    const VERIFICATION_RATE_LIMIT: (&'static str, u32, u64) = ("verification", 5, 300_000_000_000); // 5 per 5 minutes
+    const REPORT_RATE_LIMIT: (&'static str, u32, u64) = ("report", 3, 600_000_000_000); // 3 per 10 minutes
+    const PUBLIC_API_RATE_LIMIT: (&'static str, u32, u64) = ("public_api", 60, 60_000_000_000); // 60 per minute
+    const RATE_LIMIT_ACTIONS: [(&'static str, u32, u64); 4] = [
+        Self::HTTP_RATE_LIMIT,
+        Self::VERIFICATION_RATE_LIMIT,
+        Self::REPORT_RATE_LIMIT,
+        Self::PUBLIC_API_RATE_LIMIT,
+    ];
+    const API_KEY_RATE_LIMIT_WINDOW_NS: u64 = 60_000_000_000;
+
+    pub fn check_http_rate_limit(principal: Principal) -> Result<(), String> {
+        let result = Self::consume_rate_limit(&principal.to_text(), Self::HTTP_RATE_LIMIT);
+        match result {
+            Ok(()) => UsageTracker::record_outcall(principal),
+            Err(_) => SecurityLog::record(principal, SecurityEventType::RateLimited, None, "http_outcall".to_string()),
+        }
+        result
     }
 
-    pub fn check_verification_rate_limit(principal: Principal) -> bool {
-        // Stricter limit for verification attempts
-        Self::check_rate_limit_with_config(principal, 5, 300_000_000_000) // 5 per 5 minutes
+    pub fn check_verification_rate_limit(principal: Principal) -> Result<(), String> {
+        let result = Self::consume_rate_limit(&principal.to_text(), Self::VERIFICATION_RATE_LIMIT);
+        match result {
+            Ok(()) => UsageTracker::record_outcall(principal),
+            Err(_) => SecurityLog::record(principal, SecurityEventType::RateLimited, None, "verification".to_string()),
+        }
+        result
     }
 
-    pub fn check_report_rate_limit(principal: Principal) -> bool {
-        // Even stricter limit for reporting
-        Self::check_rate_limit_with_config(principal, 3, 600_000_000_000) // 3 per 10 minutes
+    pub fn check_report_rate_limit(principal: Principal) -> Result<(), String> {
+        let result = Self::consume_rate_limit(&principal.to_text(), Self::REPORT_RATE_LIMIT);
+        if result.is_err() {
+            SecurityLog::record(principal, SecurityEventType::RateLimited, None, "report".to_string());
+        }
+        result
     }
 
-    fn check_rate_limit_with_config(
-        principal: Principal, 
-        max_requests: usize, 
-        window_size_ns: u64
-    ) -> bool {
-        HTTP_RATE_LIMITS.with(|limits| {
-            let mut limits = limits.borrow_mut();
-            let now = time();
-            let window_start = now.saturating_sub(window_size_ns);
+    // Uncached /public/v1/ renders, counted per `bucket` (the caller principal, or a client
+    // fingerprint for keyless gateway traffic); Err carries the seconds until the window ends
+    pub fn check_public_api_rate_limit(principal: Principal, bucket: &str) -> Result<(), u64> {
+        let (action, _, window_ns) = Self::PUBLIC_API_RATE_LIMIT;
+        if Self::consume_rate_limit(bucket, Self::PUBLIC_API_RATE_LIMIT).is_ok() {
+            return Ok(());
+        }
+        SecurityLog::record(principal, SecurityEventType::RateLimited, None, action.to_string());
+        let now = time();
+        Err(Self::secs_until(now, now - now % window_ns + window_ns))
+    }
+
+    // Remaining allowance per limited action in its current window
+    pub fn get_rate_limit_quotas(principal: Principal) -> Vec<RateLimitQuota> {
+        let now = time();
+        let windows = Self::get_rate_limit_bucket(&principal.to_text()).windows.unwrap_or_default();
+        Self::RATE_LIMIT_ACTIONS
+            .into_iter()
+            .map(|(action, limit, window_ns)| {
+                let window_start = now - now % window_ns;
+                let count = windows
+                    .iter()
+                    .find(|w| w.action == action && w.window_start == window_start)
+                    .map_or(0, |w| w.count);
+                RateLimitQuota {
+                    action: action.to_string(),
+                    limit,
+                    remaining: limit.saturating_sub(count),
+                    window_secs: window_ns / 1_000_000_000,
+                    resets_at: (count > 0).then_some(window_start + window_ns),
+                }
+            })
+            .collect()
+    }
 
-            // Get or create the request history for this principal
-            let requests = limits.entry(principal).or_insert_with(Vec::new);
+    fn get_rate_limit_bucket(key: &str) -> RateLimitBucket {
+        RATE_LIMITS.with(|limits| {
+            limits.borrow().get(&key.to_string()).unwrap_or_default()
+        })
+    }
+
+    // Counts one request against the action's current window, or says how long until it ends
+    fn consume_rate_limit(key: &str, (action, limit, window_ns): (&str, u32, u64)) -> Result<(), String> {
+        let now = time();
+        let window_start = now - now % window_ns;
+        let mut windows = Self::get_rate_limit_bucket(key).windows.unwrap_or_default();
+
+        // Only current windows count; anything older is dropped here
+        windows.retain(|w| Self::is_current_window(w, now));
+        let position = windows.iter().position(|w| w.action == action);
+        let count = position.map_or(0, |i| windows[i].count);
+        if count >= limit {
+            return Err(format!(
+                "Rate limit exceeded: {} {} requests per {}s. Retry after {}s.",
+                limit,
+                action,
+                window_ns / 1_000_000_000,
+                Self::secs_until(now, window_start + window_ns)
+            ));
+        }
+        match position {
+            Some(i) => windows[i].count += 1,
+            None => windows.push(RateLimitWindow {
+                action: action.to_string(),
+                window_start,
+                count: 1,
+            }),
+        }
+        RATE_LIMITS.with(|limits| {
+            limits.borrow_mut().insert(key.to_string(), RateLimitBucket { windows: Some(windows) });
+        });
+        Ok(())
+    }
+
+    fn is_current_window(window: &RateLimitWindow, now: u64) -> bool {
+        Self::RATE_LIMIT_ACTIONS
+            .iter()
+            .find(|(action, _, _)| *action == window.action)
+            .is_some_and(|(_, _, window_ns)| window.window_start + window_ns > now)
+    }
+
+    // Whole seconds from now until `end`, rounded up so a retry at that point succeeds
+    fn secs_until(now: u64, end: u64) -> u64 {
+        end.saturating_sub(now).div_ceil(1_000_000_000)
+    }
+
+    // Principal text never contains ':', so the first one ends it
+    fn verification_failure_key(principal: Principal, target: &str) -> String {
+        format!("{}:{}", principal.to_text(), target)
+    }
+
+    pub fn get_verification_failures(principal: Principal, target: &str) -> Option<VerificationFailures> {
+        VERIFICATION_FAILURES.with(|failures| {
+            failures.borrow().get(&Self::verification_failure_key(principal, target))
+        })
+    }
+
+    pub fn set_verification_failures(principal: Principal, target: &str, record: VerificationFailures) {
+        VERIFICATION_FAILURES.with(|failures| {
+            failures.borrow_mut().insert(Self::verification_failure_key(principal, target), record);
+        });
+    }
 
-            // Remove requests older than the time window
-            requests.retain(|&timestamp| timestamp > window_start);
+    pub fn clear_verification_failures(principal: Principal, target: &str) {
+        VERIFICATION_FAILURES.with(|failures| {
+            failures.borrow_mut().remove(&Self::verification_failure_key(principal, target));
+        });
+    }
 
-            // Security: Prevent memory exhaustion by limiting history size
-            if requests.len() > 1000 {
-                requests.drain(..requests.len() - 100); // Keep only recent 100 requests
+    // Drops records whose last failure is older than `before`
+    pub fn prune_verification_failures(before: u64) {
+        VERIFICATION_FAILURES.with(|failures| {
+            let stale: Vec<String> = failures
+                .borrow()
+                .iter()
+                .filter(|(_, record)| record.last_failure_at < before)
+                .map(|(key, _)| key)
+                .collect();
+            let mut failures = failures.borrow_mut();
+            for key in &stale {
+                failures.remove(key);
             }
+        });
+    }
 
-            // Check if under the rate limit
-            if requests.len() < max_requests {
-                requests.push(now);
-                true // Allow request
-            } else {
-                false // Rate limit exceeded
+    // Drops buckets with nothing left in any window, and API key windows that ended;
+    // returns how many principal buckets were removed
+    pub fn compact_rate_limits() -> u64 {
+        let now = time();
+        API_KEY_RATE_LIMITS.with(|limits| {
+            let ended: Vec<String> = limits
+                .borrow()
+                .iter()
+                .filter(|(_, (window_start, _))| window_start + Self::API_KEY_RATE_LIMIT_WINDOW_NS <= now)
+                .map(|(key, _)| key)
+                .collect();
+            let mut limits = limits.borrow_mut();
+            for key in &ended {
+                limits.remove(key);
+            }
+        });
+        RATE_LIMITS.with(|limits| {
+            let idle: Vec<String> = limits
+                .borrow()
+                .iter()
+                .filter(|(_, bucket)| !bucket.windows.as_ref().is_some_and(|windows| windows.iter().any(|w| Self::is_current_window(w, now))))
+                .map(|(key, _)| key)
+                .collect();
+            let mut limits = limits.borrow_mut();
+            for key in &idle {
+                limits.remove(key);
             }
+            idle.len() as u64
         })
     }
 
-    pub fn get_rate_limit_info(principal: Principal) -> (usize, u64) {
-        HTTP_RATE_LIMITS.with(|limits| {
-            let limits = limits.borrow();
-            if let Some(requests) = limits.get(&principal) {
-                let now = time();
-                let window_start = now.saturating_sub(60_000_000_000); // 1 minute window
-                let recent_requests = requests.iter().filter(|&&timestamp| timestamp > window_start).count();
-                let oldest_request = requests.first().copied().unwrap_or(now);
-                (recent_requests, now - oldest_request)
-            } else {
-                (0, 0)
+    // Federation storage operations
+    pub fn insert_federation_peer(peer: FederationPeer) {
+        FEDERATION_PEERS.with(|peers| {
+            peers.borrow_mut().insert(peer.canister_id.to_text(), peer);
+        });
+    }
+
+    pub fn get_federation_peer(canister_id: &Principal) -> Option<FederationPeer> {
+        FEDERATION_PEERS.with(|peers| peers.borrow().get(&canister_id.to_text()))
+    }
+
+    pub fn remove_federation_peer(canister_id: &Principal) -> Option<FederationPeer> {
+        FEDERATION_PEERS.with(|peers| peers.borrow_mut().remove(&canister_id.to_text()))
+    }
+
+    pub fn get_all_federation_peers() -> Vec<FederationPeer> {
+        FEDERATION_PEERS.with(|peers| peers.borrow().iter().map(|(_, peer)| peer).collect())
+    }
+
+    fn federated_attestation_key(peer: &Principal, company_id: &str) -> String {
+        format!("{}|{}", peer.to_text(), company_id)
+    }
+
+    pub fn insert_federated_attestation(attestation: FederatedAttestation) {
+        FEDERATED_ATTESTATIONS.with(|attestations| {
+            attestations.borrow_mut().insert(
+                Self::federated_attestation_key(&attestation.peer, &attestation.attestation.company_id),
+                attestation,
+            );
+        });
+    }
+
+    pub fn has_federated_attestation(peer: &Principal, company_id: &str) -> bool {
+        FEDERATED_ATTESTATIONS.with(|attestations| {
+            attestations.borrow().contains_key(&Self::federated_attestation_key(peer, company_id))
+        })
+    }
+
+    pub fn get_federated_attestations_for_peer(peer: &Principal) -> Vec<FederatedAttestation> {
+        let prefix = format!("{}|", peer.to_text());
+        FEDERATED_ATTESTATIONS.with(|attestations| {
+            attestations
+                .borrow()
+                .range(prefix.clone()..)
+                .take_while(|(key, _)| key.starts_with(&prefix))
+                .map(|(_, attestation)| attestation)
+                .collect()
+        })
+    }
+
+    // Up to `limit` attestations in key order after `after`, with their keys
+    pub fn get_federated_attestations_after(after: Option<&str>, limit: usize) -> Vec<(String, FederatedAttestation)> {
+        use std::ops::Bound;
+        let start = after.map_or(Bound::Unbounded, |after| Bound::Excluded(after.to_string()));
+        FEDERATED_ATTESTATIONS.with(|attestations| {
+            attestations.borrow().range((start, Bound::Unbounded)).take(limit).collect()
+        })
+    }
+
+    // Drops the peer's attestations imported before `cutoff` (all of them for u64::MAX);
+    // returns how many were removed
+    pub fn remove_federated_attestations_before(peer: &Principal, cutoff: u64) -> u64 {
+        let stale: Vec<String> = Self::get_federated_attestations_for_peer(peer)
+            .into_iter()
+            .filter(|attestation| attestation.imported_at < cutoff)
+            .map(|attestation| Self::federated_attestation_key(peer, &attestation.attestation.company_id))
+            .collect();
+        FEDERATED_ATTESTATIONS.with(|attestations| {
+            let mut attestations = attestations.borrow_mut();
+            for key in &stale {
+                attestations.remove(key);
             }
+        });
+        stale.len() as u64
+    }
+
+    pub fn try_begin_federation_sync(peer: &Principal) -> bool {
+        FEDERATION_SYNCS_IN_FLIGHT.with(|in_flight| in_flight.borrow_mut().insert(peer.to_text()))
+    }
+
+    pub fn end_federation_sync(peer: &Principal) {
+        FEDERATION_SYNCS_IN_FLIGHT.with(|in_flight| {
+            in_flight.borrow_mut().remove(&peer.to_text());
+        });
+    }
+
+    // Replication operations
+    pub fn insert_replica(replica: ReplicaInfo) {
+        REPLICAS.with(|replicas| {
+            replicas.borrow_mut().insert(replica.canister_id.to_text(), replica);
+        });
+    }
+
+    pub fn get_replica(canister_id: &Principal) -> Option<ReplicaInfo> {
+        REPLICAS.with(|replicas| replicas.borrow().get(&canister_id.to_text()))
+    }
+
+    pub fn remove_replica(canister_id: &Principal) -> Option<ReplicaInfo> {
+        REPLICAS.with(|replicas| replicas.borrow_mut().remove(&canister_id.to_text()))
+    }
+
+    pub fn get_all_replicas() -> Vec<ReplicaInfo> {
+        REPLICAS.with(|replicas| replicas.borrow().iter().map(|(_, replica)| replica).collect())
+    }
+
+    pub fn get_replication_state() -> Option<ReplicationState> {
+        REPLICATION_STATE.with(|state| state.borrow().get(&"replica".to_string()))
+    }
+
+    pub fn set_replication_state(replication_state: ReplicationState) {
+        REPLICATION_STATE.with(|state| {
+            state.borrow_mut().insert("replica".to_string(), replication_state);
+        });
+    }
+
+    pub fn clear_replication_state() {
+        REPLICATION_STATE.with(|state| {
+            state.borrow_mut().remove(&"replica".to_string());
+        });
+    }
+
+    // False when a sync is already running
+    pub fn try_begin_replication_sync() -> bool {
+        REPLICATION_SYNC_IN_FLIGHT.with(|in_flight| !in_flight.replace(true))
+    }
+
+    pub fn end_replication_sync() {
+        REPLICATION_SYNC_IN_FLIGHT.with(|in_flight| in_flight.replace(false));
+    }
+
+    // Job runner operations
+    pub fn with_public_response_cache<R>(f: impl FnOnce(&BTreeMap<String, CachedResponse>, u64) -> R) -> R {
+        PUBLIC_RESPONSE_CACHE.with(|cache| {
+            let cache = cache.borrow();
+            f(&cache.0, cache.1)
         })
     }
 
-    // Clean up old rate limit data (called periodically)
-    pub fn cleanup_rate_limits() {
-        const CLEANUP_WINDOW_NS: u64 = 300_000_000_000; // 5 minutes in nanoseconds
+    pub fn set_public_response_cache(responses: BTreeMap<String, CachedResponse>, built_at: u64) {
+        PUBLIC_RESPONSE_CACHE.with(|cache| *cache.borrow_mut() = (responses, built_at));
+    }
+
+    pub fn with_job_registry<R>(f: impl FnOnce(&mut JobRegistry) -> R) -> R {
+        JOB_REGISTRY.with(|registry| f(&mut registry.borrow_mut()))
+    }
+
+    pub fn get_job_concurrency_limit(key: &str) -> Option<u32> {
+        JOB_CONCURRENCY_LIMITS.with(|limits| limits.borrow().get(&key.to_string()))
+    }
+
+    pub fn get_all_job_concurrency_limits() -> Vec<(String, u32)> {
+        JOB_CONCURRENCY_LIMITS.with(|limits| limits.borrow().iter().collect())
+    }
 
-        HTTP_RATE_LIMITS.with(|limits| {
+    pub fn set_job_concurrency_limit(key: &str, limit: Option<u32>) {
+        JOB_CONCURRENCY_LIMITS.with(|limits| {
             let mut limits = limits.borrow_mut();
-            let now = time();
-            let cleanup_threshold = now.saturating_sub(CLEANUP_WINDOW_NS);
+            match limit {
+                Some(limit) => limits.insert(key.to_string(), limit),
+                None => limits.remove(&key.to_string()),
+            };
+        });
+    }
 
-            // Remove entries that are completely outside the cleanup window
-            limits.retain(|_, requests| {
-                requests.retain(|&timestamp| timestamp > cleanup_threshold);
-                !requests.is_empty()
-            });
+    // Sharding operations
+    pub fn insert_shard(shard: Shard) {
+        SHARDS.with(|shards| {
+            shards.borrow_mut().insert(shard.canister_id.to_text(), shard);
+        });
+    }
+
+    pub fn get_shard(canister_id: &Principal) -> Option<Shard> {
+        SHARDS.with(|shards| shards.borrow().get(&canister_id.to_text()))
+    }
+
+    pub fn remove_shard(canister_id: &Principal) -> Option<Shard> {
+        SHARDS.with(|shards| shards.borrow_mut().remove(&canister_id.to_text()))
+    }
+
+    pub fn get_all_shards() -> Vec<Shard> {
+        SHARDS.with(|shards| shards.borrow().iter().map(|(_, shard)| shard).collect())
+    }
+
+    pub fn get_slot_shard(slot: u32) -> Option<Principal> {
+        SHARD_SLOTS.with(|slots| slots.borrow().get(&slot)).and_then(|text| Principal::from_text(text).ok())
+    }
+
+    pub fn set_slot_shard(slot: u32, canister_id: &Principal) {
+        SHARD_SLOTS.with(|slots| {
+            slots.borrow_mut().insert(slot, canister_id.to_text());
+        });
+    }
+
+    // Slots assigned away from this canister, in slot order
+    pub fn get_remote_slots() -> Vec<(u32, Principal)> {
+        SHARD_SLOTS.with(|slots| {
+            slots
+                .borrow()
+                .iter()
+                .filter_map(|(slot, text)| Some((slot, Principal::from_text(text).ok()?)))
+                .collect()
         })
     }
-}
\ No newline at end of file
+
+    // False when a drain is already running
+    pub fn try_begin_shard_drain() -> bool {
+        SHARD_DRAIN_IN_FLIGHT.with(|in_flight| !in_flight.replace(true))
+    }
+
+    pub fn end_shard_drain() {
+        SHARD_DRAIN_IN_FLIGHT.with(|in_flight| in_flight.replace(false));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // An upgrade keeps stable memory and initializes the maps over it again
+    #[test]
+    fn api_key_limits_and_verification_failures_survive_reinit() {
+        let memory = DefaultMemoryImpl::default();
+        let principal = Principal::from_slice(&[1; 29]);
+        let key = StorageManager::verification_failure_key(principal, "ethereum:company_1:0xabc");
+        {
+            let manager = MemoryManager::init(memory.clone());
+            let mut limits: StableBTreeMap<String, (u64, u32), Memory> = StableBTreeMap::init(manager.get(MemoryId::new(90)));
+            limits.insert("key_1".to_string(), (60_000_000_000, 7));
+            let mut failures: StableBTreeMap<String, VerificationFailures, Memory> =
+                StableBTreeMap::init(manager.get(MemoryId::new(91)));
+            failures.insert(
+                key.clone(),
+                VerificationFailures {
+                    consecutive: 8,
+                    last_failure_at: 1_000,
+                    blocked_until: 2_000,
+                },
+            );
+        }
+
+        let manager = MemoryManager::init(memory);
+        let limits: StableBTreeMap<String, (u64, u32), Memory> = StableBTreeMap::init(manager.get(MemoryId::new(90)));
+        assert_eq!(limits.get(&"key_1".to_string()), Some((60_000_000_000, 7)));
+        let failures: StableBTreeMap<String, VerificationFailures, Memory> =
+            StableBTreeMap::init(manager.get(MemoryId::new(91)));
+        let record = failures.get(&key).expect("failure record kept");
+        assert_eq!((record.consecutive, record.last_failure_at, record.blocked_until), (8, 1_000, 2_000));
+    }
+
+    #[test]
+    fn verification_failure_keys_separate_principals_and_targets() {
+        let first = Principal::from_slice(&[1; 29]);
+        let second = Principal::from_slice(&[2; 29]);
+        let target = "ethereum:company_1:0xabc";
+        assert_ne!(
+            StorageManager::verification_failure_key(first, target),
+            StorageManager::verification_failure_key(second, target)
+        );
+        assert_ne!(
+            StorageManager::verification_failure_key(first, target),
+            StorageManager::verification_failure_key(first, "ethereum:company_2:0xabc")
+        );
+    }
+}
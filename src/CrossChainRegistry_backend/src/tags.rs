@@ -0,0 +1,214 @@
+use crate::moderation::ModerationManager;
+use crate::ownership::OwnershipManager;
+use crate::storage::StorageManager;
+use crate::types::{CompanyStatus, RegistryResult, TagProposal, TagStats, TagStatus};
+use candid::Principal;
+use ic_cdk::api::time;
+use std::collections::BTreeMap;
+
+// Community-proposed tags. Anyone signed in may propose; a tag only shows on the profile
+// once the owner of a verified (or trusted) company or a moderator approves it.
+
+pub struct TagManager;
+
+impl TagManager {
+    const MIN_TAG_LENGTH: usize = 2;
+    const MAX_TAG_LENGTH: usize = 30;
+    const MAX_TAGS_PER_COMPANY: usize = 20;
+    const MAX_OPEN_PROPOSALS_PER_COMPANY: usize = 20;
+    const MAX_PROPOSALS_PER_DAY: usize = 10;
+    const DAY_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+    // Lowercase slug: letters, digits and single hyphens
+    pub fn normalize_tag(tag: &str) -> Result<String, String> {
+        let tag = tag.trim().to_lowercase();
+        let valid_chars = tag.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        if tag.len() < Self::MIN_TAG_LENGTH
+            || tag.len() > Self::MAX_TAG_LENGTH
+            || !valid_chars
+            || tag.starts_with('-')
+            || tag.ends_with('-')
+            || tag.contains("--")
+        {
+            return Err(format!(
+                "Tags must be {}-{} characters of a-z, 0-9 and single hyphens",
+                Self::MIN_TAG_LENGTH,
+                Self::MAX_TAG_LENGTH
+            ));
+        }
+        Ok(tag)
+    }
+
+    pub fn propose_tag(company_id: String, tag: String, caller_principal: Principal) -> RegistryResult<TagProposal> {
+        if caller_principal == Principal::anonymous() {
+            return RegistryResult::Err("Anonymous principals cannot propose tags".to_string());
+        }
+        let tag = match Self::normalize_tag(&tag) {
+            Ok(tag) => tag,
+            Err(err) => return RegistryResult::Err(err),
+        };
+        if StorageManager::get_company(&company_id).is_none() {
+            return RegistryResult::Err("Company not found".to_string());
+        }
+        if let Some(existing) = StorageManager::get_tag_proposal(&company_id, &tag) {
+            let status = format!("{:?}", existing.status).to_lowercase();
+            return RegistryResult::Err(format!("Tag has already been {}", status));
+        }
+
+        let now = time();
+        let all = StorageManager::get_all_tag_proposals();
+        let recent_by_caller = all
+            .iter()
+            .filter(|p| p.proposed_by == caller_principal && p.proposed_at.saturating_add(Self::DAY_NS) > now)
+            .count();
+        if recent_by_caller >= Self::MAX_PROPOSALS_PER_DAY {
+            return RegistryResult::Err(format!(
+                "Rate limit: at most {} tag proposals per day",
+                Self::MAX_PROPOSALS_PER_DAY
+            ));
+        }
+        let open_for_company = all
+            .iter()
+            .filter(|p| p.company_id == company_id && p.status == TagStatus::Proposed)
+            .count();
+        if open_for_company >= Self::MAX_OPEN_PROPOSALS_PER_COMPANY {
+            return RegistryResult::Err("This company has too many open tag proposals".to_string());
+        }
+
+        let proposal = TagProposal {
+            company_id,
+            tag,
+            proposed_by: caller_principal,
+            proposed_at: now,
+            status: TagStatus::Proposed,
+            decided_by: None,
+            decided_at: None,
+        };
+        StorageManager::insert_tag_proposal(proposal.clone());
+        RegistryResult::Ok(proposal)
+    }
+
+    pub fn review_tag(
+        company_id: String,
+        tag: String,
+        approve: bool,
+        caller_principal: Principal,
+    ) -> RegistryResult<TagProposal> {
+        let tag = match Self::normalize_tag(&tag) {
+            Ok(tag) => tag,
+            Err(err) => return RegistryResult::Err(err),
+        };
+        let mut proposal = match StorageManager::get_tag_proposal(&company_id, &tag) {
+            Some(proposal) => proposal,
+            None => return RegistryResult::Err("Tag proposal not found".to_string()),
+        };
+        if proposal.status != TagStatus::Proposed {
+            return RegistryResult::Err("Tag proposal has already been decided".to_string());
+        }
+        if let Err(err) = Self::ensure_curator(&company_id, caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        if approve {
+            let tag_count = StorageManager::get_company(&company_id)
+                .and_then(|company| company.tags)
+                .map_or(0, |tags| tags.len());
+            if tag_count >= Self::MAX_TAGS_PER_COMPANY {
+                return RegistryResult::Err(format!(
+                    "Companies can carry at most {} tags",
+                    Self::MAX_TAGS_PER_COMPANY
+                ));
+            }
+            StorageManager::update_company(&company_id, |company| {
+                let tags = company.tags.get_or_insert_with(Vec::new);
+                if !tags.contains(&tag) {
+                    tags.push(tag.clone());
+                }
+            });
+        }
+
+        proposal.status = if approve { TagStatus::Approved } else { TagStatus::Rejected };
+        proposal.decided_by = Some(caller_principal);
+        proposal.decided_at = Some(time());
+        StorageManager::insert_tag_proposal(proposal.clone());
+        RegistryResult::Ok(proposal)
+    }
+
+    // Moderators can take an approved tag back off a profile
+    pub fn remove_tag(company_id: String, tag: String, caller_principal: Principal) -> RegistryResult<()> {
+        if let Err(err) = ModerationManager::ensure_moderator(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let tag = match Self::normalize_tag(&tag) {
+            Ok(tag) => tag,
+            Err(err) => return RegistryResult::Err(err),
+        };
+
+        let mut removed = false;
+        StorageManager::update_company(&company_id, |company| {
+            if let Some(tags) = company.tags.as_mut() {
+                let before = tags.len();
+                tags.retain(|t| t != &tag);
+                removed = tags.len() != before;
+            }
+        });
+        if !removed {
+            return RegistryResult::Err("Tag not found on this company".to_string());
+        }
+
+        if let Some(mut proposal) = StorageManager::get_tag_proposal(&company_id, &tag) {
+            proposal.status = TagStatus::Rejected;
+            proposal.decided_by = Some(caller_principal);
+            proposal.decided_at = Some(time());
+            StorageManager::insert_tag_proposal(proposal);
+        }
+        RegistryResult::Ok(())
+    }
+
+    // The tagged company's owner once it is Verified or Trusted, or any moderator
+    fn ensure_curator(company_id: &str, caller_principal: Principal) -> Result<(), String> {
+        if ModerationManager::is_moderator(caller_principal) {
+            return Ok(());
+        }
+        let company = StorageManager::get_company(company_id).ok_or("Company not found")?;
+        let verified = matches!(company.status, CompanyStatus::Verified | CompanyStatus::Trusted);
+        if OwnershipManager::is_owner(&company, caller_principal) && verified {
+            Ok(())
+        } else {
+            Err("Unauthorized: Only verified company owners or moderators can review tags".to_string())
+        }
+    }
+
+    pub fn get_tag_proposals(company_id: String, status: Option<TagStatus>) -> Vec<TagProposal> {
+        let mut proposals: Vec<TagProposal> = StorageManager::get_tag_proposals_for_company(&company_id)
+            .into_iter()
+            .filter(|p| status.as_ref().is_none_or(|s| p.status == *s))
+            .collect();
+        proposals.sort_by_key(|p| std::cmp::Reverse(p.proposed_at));
+        proposals
+    }
+
+    // Most used tags first
+    pub fn get_tag_stats() -> Vec<TagStats> {
+        let mut stats: BTreeMap<String, TagStats> = BTreeMap::new();
+        for proposal in StorageManager::get_all_tag_proposals() {
+            let entry = stats.entry(proposal.tag.clone()).or_insert(TagStats {
+                tag: proposal.tag.clone(),
+                approved_count: 0,
+                proposed_count: 0,
+            });
+            match proposal.status {
+                TagStatus::Approved => entry.approved_count += 1,
+                TagStatus::Proposed => entry.proposed_count += 1,
+                TagStatus::Rejected => {}
+            }
+        }
+
+        let mut stats: Vec<TagStats> = stats
+            .into_values()
+            .filter(|s| s.approved_count + s.proposed_count > 0)
+            .collect();
+        stats.sort_by_key(|s| std::cmp::Reverse((s.approved_count, s.proposed_count)));
+        stats
+    }
+}
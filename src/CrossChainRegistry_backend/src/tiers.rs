@@ -0,0 +1,202 @@
+use crate::community::CommunityValidationManager;
+use crate::storage::StorageManager;
+use crate::types::{
+    Company, CompanyStatus, CompanyTierReport, ProofStatus, RegistryResult, RequirementCheck, TierDefinition,
+    TierDefinitions, TierEvaluation, TierRequirement,
+};
+use crate::verification::VerificationManager;
+use ic_cdk::api::time;
+use std::time::Duration;
+
+// Verification tiers. A company's status is the highest tier whose requirements it meets,
+// climbing from Pending through each tier in order. Requirements are data (score, proofs
+// of given types, proof uptime, stake, age) set by controllers through the timelock, so
+// the bar for Verified and Trusted can change without a release, but never overnight.
+// The defaults reproduce the old score bands.
+//
+// Status is re-evaluated whenever the score is rewritten, and daily for requirements that
+// move on their own (age, uptime). Stale and Suspended companies keep their status.
+
+pub struct TierEngine;
+
+impl TierEngine {
+    const DEFAULT_VERIFIED_MIN_SCORE: u32 = 21;
+    const DEFAULT_TRUSTED_MIN_SCORE: u32 = 51;
+    const MAX_TIER_NAME_LENGTH: usize = 40;
+    const MAX_REQUIREMENTS_PER_TIER: usize = 20;
+    const MAX_MIN_AGE_DAYS: u32 = 5 * 365;
+    const REEVALUATION_INTERVAL_SECS: u64 = 24 * 60 * 60;
+    const DAY_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::REEVALUATION_INTERVAL_SECS), || {
+            Self::run_tier_reevaluation();
+        });
+    }
+
+    pub fn get_tier_definitions() -> TierDefinitions {
+        StorageManager::get_tier_definitions().unwrap_or_else(|| TierDefinitions {
+            tiers: vec![
+                TierDefinition {
+                    status: CompanyStatus::Verified,
+                    name: "Verified".to_string(),
+                    requirements: vec![TierRequirement::MinReputationScore(Self::DEFAULT_VERIFIED_MIN_SCORE)],
+                },
+                TierDefinition {
+                    status: CompanyStatus::Trusted,
+                    name: "Trusted".to_string(),
+                    requirements: vec![TierRequirement::MinReputationScore(Self::DEFAULT_TRUSTED_MIN_SCORE)],
+                },
+            ],
+        })
+    }
+
+    // Changes go through the admin timelock (TimelockAction::TierDefinitions)
+    pub fn validate_tier_definitions(definitions: &TierDefinitions) -> Result<(), String> {
+        let mut previous_rank = 0;
+        for tier in &definitions.tiers {
+            let rank = match tier.status {
+                CompanyStatus::Verified => 1,
+                CompanyStatus::Trusted => 2,
+                _ => return Err("Tiers can only define Verified and Trusted".to_string()),
+            };
+            if rank <= previous_rank {
+                return Err("Tiers must be listed lowest first, each status at most once".to_string());
+            }
+            previous_rank = rank;
+
+            let name_length = tier.name.trim().chars().count();
+            if name_length == 0 || name_length > Self::MAX_TIER_NAME_LENGTH {
+                return Err(format!("Tier names must be 1-{} characters", Self::MAX_TIER_NAME_LENGTH));
+            }
+            if tier.requirements.len() > Self::MAX_REQUIREMENTS_PER_TIER {
+                return Err(format!(
+                    "A tier can have at most {} requirements",
+                    Self::MAX_REQUIREMENTS_PER_TIER
+                ));
+            }
+            for requirement in &tier.requirements {
+                match requirement {
+                    TierRequirement::MinProofUptimePercent(percent) if *percent > 100 => {
+                        return Err("Proof uptime must be a percentage".to_string());
+                    }
+                    TierRequirement::MinAgeDays(days) if *days > Self::MAX_MIN_AGE_DAYS => {
+                        return Err(format!("Minimum age must be at most {} days", Self::MAX_MIN_AGE_DAYS));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Status the company's requirements support; expects reputation_score to be current
+    pub fn tier_status(company: &Company) -> CompanyStatus {
+        Self::evaluate(company).1
+    }
+
+    fn evaluate(company: &Company) -> (Vec<TierEvaluation>, CompanyStatus) {
+        let now = time();
+        let uptime = Self::proof_uptime_percent(company);
+        let mut status = CompanyStatus::Pending;
+        let mut reachable = true;
+        let mut evaluations = Vec::new();
+
+        for tier in Self::get_tier_definitions().tiers {
+            let requirements: Vec<RequirementCheck> = tier
+                .requirements
+                .into_iter()
+                .map(|requirement| Self::check(company, requirement, now, uptime))
+                .collect();
+            let qualified = requirements.iter().all(|check| check.met);
+            if reachable && qualified {
+                status = tier.status.clone();
+            } else {
+                reachable = false;
+            }
+            evaluations.push(TierEvaluation {
+                status: tier.status,
+                name: tier.name,
+                qualified,
+                requirements,
+            });
+        }
+        (evaluations, status)
+    }
+
+    fn check(company: &Company, requirement: TierRequirement, now: u64, uptime: u64) -> RequirementCheck {
+        let (current, required) = match &requirement {
+            TierRequirement::MinReputationScore(score) => {
+                (company.community_validation.reputation_score as u64, *score as u64)
+            }
+            TierRequirement::Verification(verification_type) => {
+                let present = company.web3_identity.verification_proofs.iter().any(|proof| {
+                    matches!(proof.status, ProofStatus::Active)
+                        && std::mem::discriminant(&proof.verification_type) == std::mem::discriminant(verification_type)
+                });
+                (present as u64, 1)
+            }
+            TierRequirement::MinProofUptimePercent(percent) => (uptime, *percent as u64),
+            TierRequirement::MinReputationStake(stake) => (company.community_validation.reputation_staked, *stake),
+            TierRequirement::MinAgeDays(days) => {
+                (now.saturating_sub(company.created_at) / Self::DAY_NS, *days as u64)
+            }
+        };
+        RequirementCheck {
+            requirement,
+            met: current >= required,
+            current,
+        }
+    }
+
+    // Share of recorded checks, across all the company's proofs, that found the proof
+    // Active; companies with no checks yet count as fully up
+    fn proof_uptime_percent(company: &Company) -> u64 {
+        let mut active = 0u64;
+        let mut total = 0u64;
+        for proof in &company.web3_identity.verification_proofs {
+            let proof_id = VerificationManager::proof_id(proof);
+            if let Some(monitoring) = StorageManager::get_proof_monitoring(&company.id, &proof_id) {
+                total += monitoring.check_results.len() as u64;
+                active += monitoring
+                    .check_results
+                    .iter()
+                    .filter(|result| matches!(result.status_found, ProofStatus::Active))
+                    .count() as u64;
+            }
+        }
+        (active * 100).checked_div(total).unwrap_or(100)
+    }
+
+    pub fn get_company_tier_report(company_id: String) -> RegistryResult<CompanyTierReport> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+        let (tiers, tier_status) = Self::evaluate(&company);
+        RegistryResult::Ok(CompanyTierReport {
+            company_id,
+            status: company.status,
+            tier_status,
+            tiers,
+        })
+    }
+
+    // Rescores companies whose tier no longer matches their status; returns how many changed.
+    // Flagged companies are left for moderators.
+    pub fn run_tier_reevaluation() -> u32 {
+        let mut changed = 0;
+        for company in StorageManager::get_all_companies() {
+            if matches!(company.status, CompanyStatus::Stale | CompanyStatus::Suspended | CompanyStatus::Flagged) {
+                continue;
+            }
+            let tier_status = Self::tier_status(&company);
+            if std::mem::discriminant(&tier_status) == std::mem::discriminant(&company.status) {
+                continue;
+            }
+            CommunityValidationManager::recalculate_reputation(&company.id);
+            changed += 1;
+        }
+        changed
+    }
+}
@@ -0,0 +1,302 @@
+use crate::admins::AdminManager;
+use crate::audit::AuditManager;
+use crate::policy::PolicyLog;
+use crate::reviewers::ReviewerPoolManager;
+use crate::sanitize;
+use crate::settings::SettingsManager;
+use crate::staking::ReputationStakingManager;
+use crate::storage::StorageManager;
+use crate::tiers::TierEngine;
+use crate::types::{
+    CompanyStatus, RegistryResult, TimelockAction, TimelockConfig, TimelockStatus, TimelockedAction,
+};
+use crate::verification::VerificationManager;
+use candid::Principal;
+use ic_cdk::api::time;
+use std::time::Duration;
+
+// Sensitive admin changes (suspensions, score formula, tier requirements, reviewer fees)
+// are queued and only applied once the delay has passed. The queue is public so anyone
+// can see a change coming, and admins can cancel a queued action before it executes.
+//
+// A company status action can only take a company down (Suspended or Flagged) or lift a
+// suspension (Pending, after which the status is recomputed from the evidence); verified
+// tiers come from proofs, never from an admin. A suspension that went through the queue
+// is lifted through the queue as well, not with an instant reinstatement.
+
+pub struct TimelockManager;
+
+impl TimelockManager {
+    const DEFAULT_DELAY_HOURS: u32 = 48;
+    const MIN_DELAY_HOURS: u32 = 1;
+    const MAX_DELAY_HOURS: u32 = 30 * 24;
+    const MAX_REASON_LENGTH: usize = 500;
+    const EXECUTION_INTERVAL_SECS: u64 = 15 * 60;
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::EXECUTION_INTERVAL_SECS), || {
+            Self::execute_due_actions();
+        });
+    }
+
+    pub fn get_timelock_config() -> TimelockConfig {
+        StorageManager::get_timelock_config().unwrap_or(TimelockConfig {
+            delay_hours: Self::DEFAULT_DELAY_HOURS,
+        })
+    }
+
+    pub fn schedule_admin_action(
+        action: TimelockAction,
+        caller_principal: Principal,
+    ) -> RegistryResult<TimelockedAction> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        if let Err(err) = Self::validate(&action) {
+            return RegistryResult::Err(err);
+        }
+
+        let now = time();
+        let delay_ns = Self::get_timelock_config().delay_hours as u64 * 60 * 60 * 1_000_000_000;
+        let queued = TimelockedAction {
+            id: format!("timelock_{}_{}", now, StorageManager::timelocked_action_count()),
+            action,
+            proposed_by: caller_principal,
+            proposed_at: now,
+            executable_at: now + delay_ns,
+            status: TimelockStatus::Queued,
+            executed_at: None,
+            cancelled_by: None,
+            cancelled_at: None,
+            error: None,
+        };
+
+        StorageManager::insert_timelocked_action(queued.clone());
+        RegistryResult::Ok(queued)
+    }
+
+    pub fn cancel_admin_action(action_id: String, caller_principal: Principal) -> RegistryResult<TimelockedAction> {
+        if let Err(err) = SettingsManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        let mut queued = match StorageManager::get_timelocked_action(&action_id) {
+            Some(queued) => queued,
+            None => return RegistryResult::Err("Timelocked action not found".to_string()),
+        };
+        if queued.status != TimelockStatus::Queued {
+            return RegistryResult::Err("Only queued actions can be cancelled".to_string());
+        }
+
+        queued.status = TimelockStatus::Cancelled;
+        queued.cancelled_by = Some(caller_principal);
+        queued.cancelled_at = Some(time());
+        StorageManager::insert_timelocked_action(queued.clone());
+
+        RegistryResult::Ok(queued)
+    }
+
+    // Anyone may trigger execution once the delay has passed; the timer does it too
+    pub fn execute_admin_action(action_id: String) -> RegistryResult<TimelockedAction> {
+        let queued = match StorageManager::get_timelocked_action(&action_id) {
+            Some(queued) => queued,
+            None => return RegistryResult::Err("Timelocked action not found".to_string()),
+        };
+        if queued.status != TimelockStatus::Queued {
+            return RegistryResult::Err("Action is not queued".to_string());
+        }
+        if time() < queued.executable_at {
+            return RegistryResult::Err("Timelock delay has not passed yet".to_string());
+        }
+
+        RegistryResult::Ok(Self::execute(queued))
+    }
+
+    pub fn execute_due_actions() -> u32 {
+        let now = time();
+        let mut due: Vec<TimelockedAction> = StorageManager::get_all_timelocked_actions()
+            .into_iter()
+            .filter(|a| a.status == TimelockStatus::Queued && a.executable_at <= now)
+            .collect();
+        due.sort_by_key(|a| a.executable_at);
+
+        let count = due.len() as u32;
+        for queued in due {
+            Self::execute(queued);
+        }
+        count
+    }
+
+    fn execute(mut queued: TimelockedAction) -> TimelockedAction {
+        let previous = Self::policy_value(&queued.action);
+        // Re-validate: state may have changed during the delay
+        let result = Self::validate(&queued.action).and_then(|_| Self::apply(&queued.action));
+
+        queued.executed_at = Some(time());
+        match result {
+            Ok(()) => {
+                queued.status = TimelockStatus::Executed;
+                if let (Some((parameter, previous)), Some((_, current))) = (previous, Self::policy_value(&queued.action)) {
+                    PolicyLog::record(
+                        &parameter,
+                        Some(previous),
+                        current,
+                        vec![queued.proposed_by],
+                        &format!("timelock:{}", queued.id),
+                    );
+                }
+                AuditManager::record(
+                    "timelock_execution",
+                    &queued.id,
+                    vec![queued.proposed_by],
+                    format!("{:?}", queued.action),
+                );
+            }
+            Err(err) => {
+                queued.status = TimelockStatus::Failed;
+                queued.error = Some(err);
+            }
+        }
+        StorageManager::insert_timelocked_action(queued.clone());
+        queued
+    }
+
+    fn validate(action: &TimelockAction) -> Result<(), String> {
+        match action {
+            TimelockAction::CompanyStatus { company_id, status, reason } => {
+                let company = StorageManager::get_company(company_id).ok_or("Company not found")?;
+                if company.permanently_suspended.unwrap_or(false) {
+                    return Err("Company is permanently suspended".to_string());
+                }
+                match status {
+                    CompanyStatus::Suspended | CompanyStatus::Flagged => {}
+                    CompanyStatus::Pending => {
+                        if !matches!(company.status, CompanyStatus::Suspended) {
+                            return Err("Only a suspended company can be set back to Pending".to_string());
+                        }
+                    }
+                    _ => {
+                        return Err("A timelocked status change can only suspend, flag or reinstate a company".to_string())
+                    }
+                }
+                if reason.trim().is_empty() || sanitize::grapheme_len(reason) > Self::MAX_REASON_LENGTH {
+                    return Err("Reason must be 1-500 characters".to_string());
+                }
+                Ok(())
+            }
+            TimelockAction::ScoreWeights { .. } => Ok(()),
+            TimelockAction::ReviewerPoolConfig(config) => {
+                ReviewerPoolManager::validate_reviewer_pool_config(config)
+            }
+            TimelockAction::ReputationStakingConfig(config) => {
+                ReputationStakingManager::validate_reputation_staking_config(config)
+            }
+            TimelockAction::TimelockDelay { delay_hours } => {
+                if *delay_hours < Self::MIN_DELAY_HOURS || *delay_hours > Self::MAX_DELAY_HOURS {
+                    return Err(format!(
+                        "Timelock delay must be {}-{} hours",
+                        Self::MIN_DELAY_HOURS,
+                        Self::MAX_DELAY_HOURS
+                    ));
+                }
+                Ok(())
+            }
+            TimelockAction::TierDefinitions(definitions) => TierEngine::validate_tier_definitions(definitions),
+        }
+    }
+
+    // The policy parameter an action changes and its current value; None for actions on
+    // a single company
+    fn policy_value(action: &TimelockAction) -> Option<(String, String)> {
+        match action {
+            TimelockAction::CompanyStatus { .. } => None,
+            TimelockAction::ScoreWeights { entity_type, .. } => Some((
+                format!("score_weights:{:?}", entity_type),
+                format!("{:?}", VerificationManager::score_weights(entity_type)),
+            )),
+            TimelockAction::ReviewerPoolConfig(_) => Some((
+                "reviewer_pool_config".to_string(),
+                format!("{:?}", ReviewerPoolManager::get_reviewer_pool_config()),
+            )),
+            TimelockAction::ReputationStakingConfig(_) => Some((
+                "reputation_staking_config".to_string(),
+                format!("{:?}", ReputationStakingManager::get_reputation_staking_config()),
+            )),
+            TimelockAction::TimelockDelay { .. } => Some((
+                "timelock_delay_hours".to_string(),
+                Self::get_timelock_config().delay_hours.to_string(),
+            )),
+            TimelockAction::TierDefinitions(_) => Some((
+                "tier_definitions".to_string(),
+                format!("{:?}", TierEngine::get_tier_definitions()),
+            )),
+        }
+    }
+
+    fn apply(action: &TimelockAction) -> Result<(), String> {
+        match action {
+            TimelockAction::CompanyStatus {
+                company_id,
+                status: CompanyStatus::Pending,
+                ..
+            } => {
+                AdminManager::lift_suspension(company_id);
+            }
+            TimelockAction::CompanyStatus { company_id, status, .. } => {
+                let updated = StorageManager::update_company(company_id, |company| {
+                    company.status = status.clone();
+                    company.updated_at = time();
+                });
+                if !updated {
+                    return Err("Company not found".to_string());
+                }
+            }
+            TimelockAction::ScoreWeights { entity_type, weights } => {
+                StorageManager::set_score_weight_override(format!("{:?}", entity_type), weights.clone());
+                VerificationManager::recalculate_all_scores();
+            }
+            TimelockAction::ReviewerPoolConfig(config) => {
+                StorageManager::set_reviewer_pool_config(config.clone());
+            }
+            TimelockAction::ReputationStakingConfig(config) => {
+                StorageManager::set_reputation_staking_config(config.clone());
+            }
+            TimelockAction::TimelockDelay { delay_hours } => {
+                StorageManager::set_timelock_config(TimelockConfig {
+                    delay_hours: *delay_hours,
+                });
+            }
+            TimelockAction::TierDefinitions(definitions) => {
+                StorageManager::set_tier_definitions(definitions.clone());
+                TierEngine::run_tier_reevaluation();
+            }
+        }
+        Ok(())
+    }
+
+    // Whether the company's current suspension was applied by an executed timelock action
+    // that no later one has lifted
+    pub fn suspended_by_timelock(company_id: &str) -> bool {
+        StorageManager::get_all_timelocked_actions()
+            .into_iter()
+            .filter(|queued| queued.status == TimelockStatus::Executed)
+            .filter_map(|queued| match queued.action {
+                TimelockAction::CompanyStatus { company_id: id, status, .. } if id == company_id => {
+                    Some((queued.executed_at.unwrap_or(queued.proposed_at), status))
+                }
+                _ => None,
+            })
+            .max_by_key(|(executed_at, _)| *executed_at)
+            .is_some_and(|(_, status)| matches!(status, CompanyStatus::Suspended))
+    }
+
+    // Public queue, newest first
+    pub fn get_timelocked_actions(status: Option<TimelockStatus>) -> Vec<TimelockedAction> {
+        let mut actions: Vec<TimelockedAction> = StorageManager::get_all_timelocked_actions()
+            .into_iter()
+            .filter(|a| status.as_ref().is_none_or(|s| a.status == *s))
+            .collect();
+        actions.sort_by_key(|a| std::cmp::Reverse(a.proposed_at));
+        actions
+    }
+}
@@ -1,4 +1,4 @@
-use candid::{CandidType, Deserialize, Principal};
+use candid::{CandidType, Deserialize, Nat, Principal};
 use ic_stable_structures::storable::Bound;
 use ic_stable_structures::Storable;
 use serde::Serialize;
@@ -73,6 +73,7 @@ pub struct CommunityValidation {
     pub community_vouches: Vec<Vouch>,
     pub reputation_score: u32,
     pub reputation_staked: u64, // tokens staked for credibility
+    pub reputation_escrow: Option<ReputationEscrow>, // Set while community reputation is still maturing
 }
 
 #[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
@@ -81,6 +82,19 @@ pub struct Endorsement {
     pub message: String,
     pub timestamp: u64,
     pub endorser_principal: Principal,
+    // Reputation points, fixed when the endorsement was made; None for endorsements made
+    // before weighting, which count the old flat 10
+    pub weight: Option<u32>,
+}
+
+// What an endorsement from a company would add right now
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EndorsementWeight {
+    pub endorser_company_id: String,
+    pub reputation_score: u32,
+    pub verification_score: u32,
+    pub points: u32,
+    pub max_points: u32,
 }
 
 #[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
@@ -100,6 +114,34 @@ pub struct Vouch {
     pub weight: u32, // based on voucher's reputation
 }
 
+// A soft negative signal; only lowers reputation once corroborated (see ConcernManager)
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct Concern {
+    pub company_id: String,
+    pub raised_by: Principal,
+    pub source_company_id: String, // The Verified or Trusted company the principal speaks for
+    pub reason: String,
+    pub weight: u32, // 1 for a Verified source, 2 for a Trusted one
+    pub raised_at: u64,
+    pub expires_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ConcernSummary {
+    pub company_id: String,
+    pub concerns: Vec<Concern>, // Unexpired, newest first
+    pub distinct_sources: u32,
+    pub corroborated: bool,
+    pub reputation_penalty: u32,
+}
+
+// Limits on how fast community signals can be churned
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct CommunityCooldownPolicy {
+    pub max_vouches_per_day: u32,    // Companies one principal may vouch for in any 24 hours
+    pub endorsement_readd_days: u32, // Wait before re-endorsing a target after removal; 0 disables
+}
+
 #[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
 pub enum VerificationStatus {
     Pending,
@@ -115,6 +157,209 @@ pub enum CompanyStatus {
     Trusted,      // High reputation, community validated
     Flagged,      // Community reported issues
     Suspended,    // Admin action or severe violations
+    Stale,        // No owner activity or passing proof check within the inactivity period
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Default)]
+pub enum EntityType {
+    #[default]
+    Company,
+    Dao,
+    OpenSourceProject,
+    IndividualBuilder,
+}
+
+// Points awarded per signal in the verification score, tuned per entity type
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ScoreWeights {
+    pub basic_info_field: u32,
+    pub github: u32,
+    pub domain: u32,
+    pub social: u32,
+    pub per_chain: u32,
+    pub treasury: u32,
+    pub tokens: u32,
+    pub per_verified_member: u32,
+    pub team_max: u32,
+    pub community_max: u32,
+}
+
+// Liveness of an external link shown on a profile
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum LinkStatus {
+    Unchecked,
+    Alive,
+    Dead,    // 4xx other than bot blocking
+    Unknown, // Outcall failed or the site refused to answer
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct LinkCheck {
+    pub status: LinkStatus,
+    pub status_code: Option<u32>,
+    pub checked_at: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum FundingStage {
+    PreSeed,
+    Seed,
+    SeriesA,
+    SeriesB,
+    SeriesC,
+    SeriesDPlus,
+    Strategic,
+    PublicSale,
+    Grant,
+    Other,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct Investor {
+    pub name: String,
+    pub company_id: Option<String>, // Set when the investor is a registered company
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct FundingRound {
+    pub stage: FundingStage,
+    pub amount_usd: Option<u64>,
+    pub date: Option<String>,
+    pub investors: Vec<Investor>,
+    pub announcement_url: Option<String>,
+    pub announcement_link: LinkCheck, // Managed by the registry, ignored on input
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct FundingInfo {
+    pub rounds: Vec<FundingRound>,
+    pub updated_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct InvestmentRecord {
+    pub company_id: String,
+    pub company_name: String,
+    pub stage: FundingStage,
+    pub amount_usd: Option<u64>,
+    pub date: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct JobLink {
+    pub label: String, // e.g. "Careers page", "Wellfound"
+    pub url: String,
+    pub link: LinkCheck, // Managed by the registry, ignored on input
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct HiringInfo {
+    pub actively_hiring: bool,
+    pub job_links: Vec<JobLink>,
+    pub updated_at: u64,
+}
+
+// Support channels, kept apart from the marketing socials in Web3Identity
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum ContactChannelType {
+    Email,
+    Discord,  // Invite code
+    Telegram, // Public username
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ContactChannel {
+    pub id: String,
+    pub channel_type: ContactChannelType,
+    pub value: String,
+    pub label: String, // e.g. "Support", "Security disclosures"
+    pub verified: bool,
+    pub challenge_token: String,
+    pub added_at: u64,
+    pub verified_at: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ContactChannelMatch {
+    pub matched: bool,
+    pub company_id: Option<String>,
+    pub company_name: Option<String>,
+    pub label: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum ImpersonationChannel {
+    Website,
+    Twitter,
+    Discord,
+    Telegram,
+    Email,
+    Other,
+}
+
+// Public warning about a confirmed impersonator, shown on the victim's profile
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ImpersonationWarning {
+    pub report_id: String,
+    pub channel: ImpersonationChannel,
+    pub impersonator: String,
+    pub confirmed_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub enum BannerSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum BannerSource {
+    Owner,
+    Moderator,
+}
+
+// Time-boxed warning shown with the company while starts_at <= now < ends_at
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct WarningBanner {
+    pub message: String,
+    pub severity: BannerSeverity,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    pub source: BannerSource,
+    pub published_by: Principal,
+    pub published_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum AddressRotationStatus {
+    PendingSuccessorVerification,
+    Completed,
+    Cancelled,
+}
+
+// Links a retired address or contract to its replacement so proofs made with the old one
+// still resolve to the company after a wallet rotation or redeploy
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct AddressRotation {
+    pub id: String,
+    pub chain_type: ChainType,
+    pub deprecated_address: String,
+    pub successor_address: String,
+    pub reason: String,
+    pub status: AddressRotationStatus,
+    pub initiated_by: Principal,
+    pub initiated_at: u64,
+    pub completed_at: Option<u64>,
+}
+
+// Where an address (current or retired) leads after following completed rotations
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AddressLineage {
+    pub company_id: String,
+    pub company_name: String,
+    pub current_address: String,
+    pub rotations: Vec<AddressRotation>,
 }
 
 #[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
@@ -130,45 +375,43 @@ pub struct Company {
     pub updated_at: u64,
     pub created_by: Principal,
     pub verification_score: u32, // Composite score based on all verifications
+    pub entity_type: Option<EntityType>, // None for records created before entity types
+    pub funding: Option<FundingInfo>,
+    pub hiring: Option<HiringInfo>,
+    pub contact_channels: Option<Vec<ContactChannel>>,
+    pub impersonation_warnings: Option<Vec<ImpersonationWarning>>,
+    pub warning_banner: Option<WarningBanner>,
+    pub address_rotations: Option<Vec<AddressRotation>>,
+    pub paused_score_sources: Option<Vec<String>>, // Addresses excluded from the verification score
+    pub permanently_suspended: Option<bool>, // Set via two-moderator approval; cannot be lifted
+    pub tags: Option<Vec<String>>, // Approved community tags
+    pub last_owner_activity_at: Option<u64>, // None until the inactivity sweep backfills it from updated_at
+    pub inactivity_warned_at: Option<u64>,   // Set when the owner was warned of upcoming staleness
+    pub managers: Option<Vec<CompanyManager>>, // Principals acting for the company besides created_by
 }
 
 // API Request/Response Types
 
+// Optional sections of a Company response. Basic info, status, score and any active
+// warning banner are always returned.
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum CompanySection {
+    Team,      // team_members
+    Proofs,    // web3_identity.verification_proofs
+    Community, // endorsements, testimonials and vouches
+    Presence,  // cross_chain_presence
+    Funding,
+    Hiring,
+    Contacts,  // contact_channels and impersonation_warnings
+    History,   // address_rotations and paused_score_sources
+}
+
 #[derive(CandidType, Deserialize)]
 pub enum RegistryResult<T> {
     Ok(T),
     Err(String),
 }
 
-// Community Validation Request Types
-
-#[derive(CandidType, Deserialize, Clone)]
-pub struct CreateEndorsementRequest {
-    pub company_id: String,
-    pub endorser_company_id: String,
-    pub message: String,
-}
-
-#[derive(CandidType, Deserialize, Clone)]
-pub struct CreateTestimonialRequest {
-    pub company_id: String,
-    pub author_name: String,
-    pub role: String,
-    pub message: String,
-}
-
-#[derive(CandidType, Deserialize, Clone)]
-pub struct CreateVouchRequest {
-    pub company_id: String,
-    pub message: String,
-}
-
-#[derive(CandidType, Deserialize, Clone)]
-pub struct StakeReputationRequest {
-    pub company_id: String,
-    pub amount: u64,
-}
-
 // Community Validation Response Types
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
@@ -181,7 +424,7 @@ pub struct CommunityValidationStats {
     pub reputation_staked: u64,
 }
 
-#[derive(CandidType, Deserialize, Clone, Debug)]
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
 pub struct ReputationLeaderboard {
     pub company_id: String,
     pub company_name: String,
@@ -189,12 +432,29 @@ pub struct ReputationLeaderboard {
     pub reputation_staked: u64,
 }
 
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum CommunitySortOrder {
+    Newest,
+    Oldest,
+    Weight, // Endorser reputation, testimonial verification, or vouch weight
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct CommunityPage<T> {
+    pub items: Vec<T>,
+    pub total: u32,
+    pub offset: u32,
+    pub limit: u32,
+    pub has_more: bool,
+}
+
 #[derive(CandidType, Deserialize, Clone)]
 pub struct CreateCompanyRequest {
     pub basic_info: CompanyBasicInfo,
     pub web3_identity: Web3Identity,
     pub cross_chain_presence: CrossChainPresence,
     pub team_members: Vec<TeamMember>,
+    pub entity_type: Option<EntityType>, // Defaults to Company
 }
 
 #[derive(CandidType, Deserialize, Clone)]
@@ -204,6 +464,24 @@ pub struct UpdateCompanyRequest {
     pub web3_identity: Option<Web3Identity>,
     pub cross_chain_presence: Option<CrossChainPresence>,
     pub team_members: Option<Vec<TeamMember>>,
+    pub entity_type: Option<EntityType>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum SearchField {
+    Name,
+    FocusArea,
+    Description,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SearchResult {
+    pub company: Company,
+    pub relevance: u32,            // Sum of the field weights that matched
+    pub matched_field: SearchField, // Highest-weighted field that matched
+    pub snippet: String,
+    pub highlight_start: u32,      // Char offset of the match within `snippet`
+    pub highlight_length: u32,     // Match length in chars
 }
 
 #[derive(CandidType, Deserialize, Clone)]
@@ -213,17 +491,16 @@ pub struct SearchFilters {
     pub min_verification_score: Option<u32>,
     pub has_github: Option<bool>,
     pub has_contracts: Option<bool>,
+    pub entity_type: Option<EntityType>,
+    pub min_funding_stage: Option<FundingStage>, // e.g. SeriesA for "Series A+"
+    pub actively_hiring: Option<bool>,
+    pub on_chain: Option<ChainType>, // Has at least one address on this chain
+    pub tags: Option<Vec<String>>,   // Must carry every listed approved tag
+    pub include_archived: Option<bool>, // Archived companies are left out unless true
 }
 
 // Verification System Types
 
-#[derive(CandidType, Deserialize, Clone, Debug)]
-pub struct VerificationRequest {
-    pub company_id: String,
-    pub verification_type: VerificationType,
-    pub proof_data: String, // Challenge response or proof
-}
-
 #[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
 pub enum VerificationType {
     GitHub,
@@ -241,6 +518,11 @@ pub struct VerificationProof {
     pub verification_method: VerificationMethod,
     pub challenge_data: Option<String>, // For domain/GitHub challenges
     pub status: ProofStatus,
+    // Earlier URLs of this proof, oldest first, kept when an account moves platforms
+    pub previous_urls: Option<Vec<String>>,
+    // Stable opaque reference, kept across URL migrations and re-verification; None for
+    // proofs stored before ids were assigned (see MigrationKind::AssignProofIds)
+    pub id: Option<String>,
 }
 
 #[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
@@ -257,6 +539,7 @@ pub enum ProofStatus {
     Disputed,    // Community flagged as suspicious
 }
 
+// Check history of one proof, newest result last
 #[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
 pub struct ProofMonitoring {
     pub proof_id: String,
@@ -290,11 +573,103 @@ pub enum ReportType {
     FakeProfile,
 }
 
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum ImpersonationReportStatus {
+    Pending,
+    Confirmed,
+    Rejected,
+}
+
+// Report about an unregistered site or handle impersonating a registered company
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ImpersonationReport {
+    pub id: String,
+    pub company_id: String, // The impersonated (victim) company
+    pub channel: ImpersonationChannel,
+    pub impersonator: String, // Normalized URL or handle
+    pub evidence: String,
+    pub reporter_principal: Principal,
+    pub reported_at: u64,
+    pub status: ImpersonationReportStatus,
+    pub reviewed_by: Option<Principal>,
+    pub reviewed_at: Option<u64>,
+}
+
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct VerificationResult {
     pub success: bool,
     pub message: String,
     pub verified_at: Option<u64>,
+    pub proof_id: Option<String>,                     // Proof created or updated by this verification
+    pub verification_type: Option<VerificationType>,  // Set for identity verifications
+    pub chain_type: Option<ChainType>,                // Set for cross-chain verifications
+    pub score_delta: i32,                             // Change in verification score
+    pub retry_after_secs: Option<u64>,                // Cooldown before this target may be retried
+    pub next_action: Option<String>,                  // Recommended next step for the company
+    pub providers: Option<Vec<String>>,               // Endpoints whose answers agreed, for quorum checks
+}
+
+// A proof looked up by its stable id, with the company it belongs to
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ResolvedProof {
+    pub proof_id: String,
+    pub company_id: String,
+    pub company_name: String,
+    pub company_status: CompanyStatus,
+    pub status: ProofStatus,
+    pub proof: VerificationProof,
+    pub last_checked: Option<u64>, // Last content or availability check, if any
+}
+
+// One step of a dry-run verification
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct PreviewCheck {
+    pub check: String,   // e.g. "challenge", "dns_query", "challenge_text"
+    pub passed: bool,
+    pub detail: String,  // What was found, or why the check failed
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ProofPreview {
+    pub verification_type: VerificationType,
+    pub target: String,               // Domain or proof URL that was checked
+    pub would_pass: bool,             // Every check passed
+    pub checks: Vec<PreviewCheck>,
+    pub checked_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum VerificationState {
+    NotStarted,
+    Pending,  // Open challenge awaiting completion
+    Complete,
+    Failed,   // Proof removed or disputed
+    Expired,  // Challenge expired before completion
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VerificationSummaryItem {
+    pub verification_type: Option<VerificationType>, // Set for identity verifications
+    pub chain_type: Option<ChainType>,               // Set for cross-chain verifications
+    pub state: VerificationState,
+    pub score_contribution: u32,
+    pub proof_ids: Vec<String>,
+    pub open_challenges: u32,
+    pub challenge_expires_at: Option<u64>, // Earliest expiry of an open challenge
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VerificationSummary {
+    pub company_id: String,
+    pub verification_score: u32,
+    pub items: Vec<VerificationSummaryItem>,
+    pub profile_score: u32, // Basic info, team and community points not tied to a verification
+    pub treasury_contribution: u32, // Treasury and token points have no item of their own
+    pub token_contribution: u32,
+    pub complete_count: u32,
+    pub pending_count: u32,
+    pub failed_count: u32,
+    pub expired_count: u32,
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
@@ -308,7 +683,8 @@ pub struct DomainVerificationChallenge {
 
 //Cross-Chain Verification Types
 
-#[derive(CandidType, Deserialize, Clone, Debug)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
 pub enum ChainType {
     Ethereum,
     Bitcoin,
@@ -344,106 +720,2823 @@ pub struct CrossChainChallenge {
     pub verification_method: CrossChainVerificationMethod,
     pub created_at: u64,
     pub expires_at: u64,
+    pub nonce: Option<String>, // Registry-issued; None on challenges created before nonces
 }
 
 // API Response structures for different chains
+// eth_getTransactionByHash reduced by transform_evm_transaction; hex fields lowercased
 #[derive(Deserialize, Debug)]
-pub struct EtherscanContractResponse {
-    pub status: String,
-    pub message: String,
-    pub result: Vec<EtherscanTransaction>,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct EtherscanTransaction {
-    pub hash: String,
+pub struct EvmTransaction {
     pub from: String,
-    pub to: String,
-    pub value: String,
     pub input: String,
-    #[serde(rename = "timeStamp")]
-    pub timestamp: String,
+    pub block_number: u64,
 }
 
+// Only the fields the verifiers read; serde skips the rest
 #[derive(Deserialize, Debug)]
 pub struct BlockchainInfoResponse {
-    pub address: String,
-    pub hash160: String,
     pub n_tx: u32,
-    pub n_unredeemed: u32,
-    pub total_received: u64,
-    pub total_sent: u64,
-    pub final_balance: u64,
 }
 
 #[derive(Deserialize, Debug)]
-pub struct ICPCanisterResponse {
-    pub canister_id: String,
-    pub status: String,
-    pub controllers: Vec<String>,
-    pub memory_size: u64,
-    pub cycles: u64,
+pub struct EsploraAddressResponse {
+    pub chain_stats: EsploraAddressStats,
+    pub mempool_stats: EsploraAddressStats,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct EsploraAddressStats {
+    pub tx_count: u32,
+}
+
+// One entry of a getSignaturesForAddress result, as reduced by the transform
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SolanaSignatureInfo {
+    pub signature: String,
+    pub memo: Option<String>,
+    pub succeeded: bool,
 }
 
 // GitHub API Response structures
 #[derive(Deserialize)]
 pub struct GitHubOrgResponse {
-    pub login: String,
-    pub id: u64,
-    pub name: Option<String>,
-    pub blog: Option<String>,
-    pub location: Option<String>,
-    pub email: Option<String>,
     pub public_repos: u32,
-    pub followers: u32,
-    pub created_at: String,
 }
 
-#[derive(Deserialize)]
-pub struct GitHubRepoResponse {
+// Settings Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum ApiProvider {
+    GitHub,
+    Dns,
+    Bitcoin,
+    Ethereum, // Etherscan-style txlist; no longer queried, kept for stored settings
+    EthereumRpc,
+    PolygonRpc,
+    SolanaRpc,
+    Ton,
+    TonBalance,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum ApiStyle {
+    GitHubRest,          // api.github.com compatible
+    DnsJson,             // DNS-over-HTTPS JSON (Google, Cloudflare)
+    BlockchainInfo,      // blockchain.info rawaddr
+    Esplora,             // blockstream.info, mempool.space
+    EtherscanCompatible, // Etherscan, Blockscout txlist (no longer queried)
+    EvmJsonRpc,          // Ethereum JSON-RPC node (eth_blockNumber, eth_getLogs, eth_getTransactionByHash)
+    SolanaJsonRpc,       // Solana JSON-RPC node (getSignaturesForAddress, getTransaction)
+    Toncenter,           // toncenter.com API v2
+    TonApi,              // tonapi.io v2
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ProviderEndpoint {
     pub name: String,
-    pub full_name: String,
-    pub description: Option<String>,
-    pub created_at: String,
-    pub updated_at: String,
-    pub stargazers_count: u32,
-    pub forks_count: u32,
+    pub url_template: String, // "{query}" is replaced with the org, domain or address
+    pub api_style: ApiStyle,
+    pub enabled: bool,
 }
 
-// Implement Storable for types that need to be stored in stable structures
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ProviderEndpointList {
+    pub endpoints: Vec<ProviderEndpoint>,
+    pub quorum: Option<u32>, // Agreeing endpoints required by quorum lookups; None for the provider default
+}
 
-impl Storable for Company {
-    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
-        Cow::Owned(candid::encode_one(self).unwrap())
-    }
+// Per-provider overrides set by controllers. The key fills {api_key} in URL templates
+// (and is sent as a bearer token to GitHub-style APIs); it is never returned by queries.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ApiConfig {
+    pub base_url: Option<String>, // Replaces the scheme and host of the provider's first enabled endpoint
+    pub api_key: Option<String>,
+    pub updated_at: u64,
+    pub updated_by: Principal,
+}
 
-    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
-        candid::decode_one(&bytes).unwrap()
-    }
+// ApiConfig as shown to controllers, with the key masked
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ApiConfigInfo {
+    pub provider: ApiProvider,
+    pub base_url: Option<String>,
+    pub api_key_hint: Option<String>, // Last four characters
+    pub updated_at: u64,
+    pub updated_by: Principal,
+}
 
-    const BOUND: Bound = Bound::Unbounded;
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ProviderHealth {
+    pub provider: ApiProvider,
+    pub endpoint_name: String,
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+    pub total_successes: u64,
+    pub total_failures: u64,
+    pub last_success_at: Option<u64>,
+    pub last_failure_at: Option<u64>,
+    pub last_error: Option<String>,
+    pub cooldown_until: Option<u64>,
 }
 
-impl Storable for DomainVerificationChallenge {
-    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
-        Cow::Owned(candid::encode_one(self).unwrap())
-    }
+// Reserved Name Types
 
-    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
-        candid::decode_one(&bytes).unwrap()
-    }
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum NameMatchRule {
+    Exact,      // Case-insensitive equality
+    Normalized, // Ignores case, whitespace and punctuation ("Maker DAO" == "makerdao")
+    Contains,   // Normalized name contains the reserved name ("Coinbase Support")
+}
 
-    const BOUND: Bound = Bound::Unbounded;
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ReservedName {
+    pub name: String,
+    pub match_rule: NameMatchRule,
+    pub required_domains: Vec<String>, // Registrant website must be on one of these
+    pub reason: Option<String>,
+    pub reserved_by: Principal,
+    pub reserved_at: u64,
+    pub claimed_by: Option<String>, // Company ID of the verified owner
+    pub claimed_at: Option<u64>,
 }
 
-impl Storable for CrossChainChallenge {
-    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
-        Cow::Owned(candid::encode_one(self).unwrap())
-    }
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ReserveNameRequest {
+    pub name: String,
+    pub match_rule: NameMatchRule,
+    pub required_domains: Vec<String>,
+    pub reason: Option<String>,
+}
 
-    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
-        candid::decode_one(&bytes).unwrap()
-    }
+// Corporate Hierarchy Types
 
-    const BOUND: Bound = Bound::Unbounded;
-}
\ No newline at end of file
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum RelationshipKind {
+    SubsidiaryOf,
+    ProductOf,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum RelationshipStatus {
+    Proposed,  // Waiting for the other side to confirm
+    Confirmed, // Both owners agreed
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct CompanyRelationship {
+    pub child_company_id: String,
+    pub parent_company_id: String,
+    pub kind: RelationshipKind,
+    pub status: RelationshipStatus,
+    pub child_confirmed: bool,
+    pub parent_confirmed: bool,
+    pub proposed_by: Principal,
+    pub proposed_at: u64,
+    pub confirmed_at: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct CorporateGraphNode {
+    pub company_id: String,
+    pub name: String,
+    pub status: CompanyStatus,
+    pub verification_score: u32,
+    pub domain_verified: bool,
+    pub depth: u32, // 0 for the top-level parent
+    pub relationship_to_parent: Option<RelationshipKind>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct GroupSignals {
+    pub company_id: String,
+    pub entity_count: u32,
+    pub verified_entity_count: u32,
+    pub flagged_entity_count: u32,
+    pub domain_verified_count: u32,
+    pub average_verification_score: u32,
+    pub max_verification_score: u32,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct CorporateGraph {
+    pub root_company_id: String,
+    pub nodes: Vec<CorporateGraphNode>,
+    pub edges: Vec<CompanyRelationship>, // Confirmed links only
+    pub signals: GroupSignals,
+}
+
+// Grant Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub enum GrantAmountRange {
+    Undisclosed,
+    Under10K,
+    From10KTo50K,
+    From50KTo100K,
+    From100KTo500K,
+    Over500K,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct GrantProgram {
+    pub id: String,
+    pub issuer_company_id: String, // Foundation running the program
+    pub name: String,
+    pub description: String,
+    pub created_at: u64,
+    pub active: bool,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct GrantAttestation {
+    pub id: String,
+    pub program_id: String,
+    pub program_name: String,
+    pub issuer_company_id: String,
+    pub recipient_company_id: String,
+    pub amount_range: GrantAmountRange,
+    pub awarded_date: String,
+    pub issued_at: u64,
+    pub issued_by: Principal,
+    pub revoked: bool,
+}
+
+
+
+// Monitoring Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AlertSeverity {
+    Info,
+    Warning,
+    Error,
+    Critical,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum AlertType {
+    ContractOwnershipTransferred, // Ownable OwnershipTransferred
+    ContractProxyUpgraded,        // EIP-1967 Upgraded
+    ContractAdminChanged,         // EIP-1967 AdminChanged
+    CanisterModuleChanged,        // Wasm module hash differs from the last snapshot
+    CanisterControllersChanged,   // Controller set differs; verification is revoked
+    TreasuryOutflow,              // Verified treasury balance dropped past the threshold
+    CompanyOwnershipTransferred,  // Registry profile accepted by a new owner principal
+    CompanySuspended,             // Registry admin froze the profile
+    CompanyReinstated,            // Registry admin lifted a suspension
+    RegistryImpersonation,        // Profile claims one of the registry's own canisters or domains
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum AlertVisibility {
+    OwnerOnly,  // Company owner and moderators
+    Moderators, // Moderators only
+    Public,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum NotificationChannel {
+    OwnerInbox,     // Notification to the company owner
+    ModeratorQueue, // Listed in the moderator alert queue until acknowledged
+}
+
+// Routing policy for alerts of one type and severity
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct AlertRoutingRule {
+    pub alert_type: AlertType,
+    pub severity: AlertSeverity,
+    pub visibility: AlertVisibility,
+    pub channels: Vec<NotificationChannel>,
+    pub updated_by: Principal,
+    pub updated_at: u64,
+}
+
+// Alert raised by the monitoring system about a company's verified assets
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct CommunityAlert {
+    pub id: String,
+    pub company_id: String,
+    pub alert_type: AlertType,
+    pub severity: AlertSeverity,
+    pub message: String,
+    pub chain_type: Option<ChainType>,
+    pub subject: String,  // Address or canister the alert is about; also the dedup evidence key
+    pub evidence: String, // e.g. transaction hash of the latest occurrence
+    pub created_at: u64,
+    pub acknowledged_by: Option<Principal>,
+    pub acknowledged_at: Option<u64>,
+    pub visibility: Option<AlertVisibility>, // None for alerts raised before routing (public)
+    pub channels: Option<Vec<NotificationChannel>>,
+    pub occurrence_count: Option<u32>, // None means a single occurrence
+    pub last_seen_at: Option<u64>,
+    pub escalated_at: Option<u64>,
+    pub score_paused: Option<bool>, // Subject's score contribution paused until a moderator acknowledges
+    pub evidence_keys: Option<Vec<String>>, // Every occurrence counted so far; None means just `evidence`
+}
+
+// Opt-in event monitoring for a verified EVM contract
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ContractWatch {
+    pub company_id: String,
+    pub chain_type: ChainType,
+    pub contract_address: String,
+    pub enabled_by: Principal,
+    pub enabled_at: u64,
+    pub last_checked_block: Option<u64>,
+    pub last_checked_at: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+// Last observed state of a verified ICP canister (from canister_info)
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct CanisterSnapshot {
+    pub company_id: String,
+    pub canister_id: String,
+    pub module_hash: Option<String>, // Hex-encoded SHA-256; None while the canister is empty
+    pub controllers: Vec<Principal>, // Sorted
+    pub total_num_changes: u64,
+    pub recorded_at: u64,
+    pub last_checked_at: u64,
+    pub last_error: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct TreasuryBalanceSample {
+    pub timestamp: u64,
+    pub balance: u128, // Base units (wei, satoshi)
+}
+
+// Recent balances of a verified treasury wallet, trimmed to the outflow window
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct TreasurySnapshot {
+    pub company_id: String,
+    pub chain: String,
+    pub address: String,
+    pub samples: Vec<TreasuryBalanceSample>,
+    pub last_checked_at: u64,
+    pub last_error: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct MonitoringConfig {
+    pub treasury_outflow_threshold_percent: u32, // Drop from the window's peak that raises an alert
+    pub treasury_outflow_window_hours: u32,
+    pub alert_dedup_window_hours: Option<u32>, // Repeats within the window update the open alert
+    pub escalation_after_hours: Option<u32>,   // Unacknowledged Error alerts become Critical after this
+    pub escalation_pauses_score: Option<bool>, // Escalation also pauses the subject's score contribution
+    // How often each background job runs; None keeps the built-in default
+    pub contract_interval_mins: Option<u32>,
+    pub canister_interval_mins: Option<u32>,
+    pub treasury_interval_mins: Option<u32>,
+    pub escalation_interval_mins: Option<u32>,
+    pub proof_interval_mins: Option<u32>,
+    pub proof_checks_per_run: Option<u32>, // Outcall budget of one proof monitoring run
+}
+
+// Event reduced by the eth_getLogs transform
+#[derive(Deserialize, Clone, Debug)]
+pub struct EvmLogEvent {
+    pub topic: String,
+    pub block_number: u64,
+    pub transaction_hash: String,
+    pub log_index: u64,
+}
+
+// Notification Types
+
+// Inbox entry for a principal, e.g. the owner of a company an alert was raised on
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct Notification {
+    pub id: String,
+    pub recipient: Principal,
+    pub company_id: String,
+    pub alert_id: Option<String>,
+    pub message: String,
+    pub created_at: u64,
+    pub read: bool,
+}
+
+// Moderation Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum ModerationTaskKind {
+    Dispute,             // Reported proof or impersonation report
+    Appeal,              // Company contesting a moderation decision
+    FlaggedRegistration, // Registration that needs a manual look
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum ModerationTaskStatus {
+    Open,
+    Assigned,
+    Closed,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ModerationTask {
+    pub id: String,
+    pub kind: ModerationTaskKind,
+    pub company_id: String,
+    pub reference_id: String, // Report ID, proof URL, etc.
+    pub summary: String,
+    pub status: ModerationTaskStatus,
+    pub created_at: u64,
+    pub assignee: Option<Principal>,
+    pub assigned_by: Option<Principal>,
+    pub assigned_at: Option<u64>,
+    pub closed_by: Option<Principal>,
+    pub closed_at: Option<u64>,
+    pub outcome: Option<String>,
+    pub dispute_status: Option<DisputeStatus>, // Dispute tasks only; None on tasks filed before the dispute lifecycle
+    pub review_started_at: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum DisputeStatus {
+    Open,
+    UnderReview,
+    Resolved, // Report upheld; proof and company statuses were updated
+    Rejected, // Report dismissed; nothing changed
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum DisputeOutcome {
+    Upheld,
+    Rejected,
+}
+
+// A dispute task joined with the reports it collected
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct Dispute {
+    pub task_id: String,
+    pub company_id: String,
+    pub proof_id: String,
+    pub status: DisputeStatus,
+    pub summary: String,
+    pub reports: Vec<CommunityReport>,
+    pub reviewer: Option<Principal>,
+    pub opened_at: u64,
+    pub review_started_at: Option<u64>,
+    pub resolved_by: Option<Principal>,
+    pub resolved_at: Option<u64>,
+    pub resolution: Option<String>,
+    pub proof_status: Option<ProofStatus>, // Current status, None once the proof is gone
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct Moderator {
+    pub principal: Principal,
+    pub added_by: Principal,
+    pub added_at: u64,
+}
+
+// Registry admin appointed by a controller; holds the moderator powers plus the
+// privileged moderation endpoints
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct Admin {
+    pub principal: Principal,
+    pub added_by: Principal,
+    pub added_at: u64,
+}
+
+// A suspension one admin applied without the timelock. It stands only if a second admin
+// confirms it before confirm_by; otherwise it lapses and the company is reinstated.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct EmergencySuspension {
+    pub company_id: String,
+    pub reason: String,
+    pub suspended_by: Principal,
+    pub suspended_at: u64,
+    pub confirm_by: u64,
+    pub confirmed_by: Option<Principal>,
+    pub confirmed_at: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ModeratorWorkload {
+    pub moderator: Principal,
+    pub open_tasks: u32,
+    pub closed_tasks: u32,
+    pub closed_last_7_days: u32,
+    pub avg_resolution_secs: Option<u64>, // From assignment to close
+}
+
+// Moderator Note Types
+
+// Investigation note on a moderation task, encrypted by the moderator's client under the
+// task's vetKD-derived key. The canister stores and returns ciphertext only.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ModeratorNote {
+    pub id: String,
+    pub ciphertext: Vec<u8>, // Client-chosen layout, e.g. AES-GCM nonce || ciphertext
+    pub author: Principal,
+    pub created_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum NoteAccessKind {
+    KeyDerived,
+    NotesRead,
+    NoteAdded,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct NoteAccess {
+    pub principal: Principal,
+    pub kind: NoteAccessKind,
+    pub at: u64,
+}
+
+// Notes and the access trail for one moderation task, keyed by task id
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ModeratorNoteThread {
+    pub task_id: String,
+    pub company_id: String,
+    pub notes: Vec<ModeratorNote>,
+    pub access_log: Vec<NoteAccess>, // Oldest first, capped
+}
+
+// Private Message Types
+
+// Moderator-to-owner message, IBE-encrypted by the sender's client to the company's
+// vetKD identity; only a company owner can derive the key that opens it
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct PrivateMessage {
+    pub id: String,
+    pub company_id: String,
+    pub ciphertext: Vec<u8>,
+    pub sender: Principal,
+    pub sent_at: u64,
+}
+
+// vetKD Types (subset of the management canister interface)
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum VetKdCurve {
+    #[serde(rename = "bls12_381_g2")]
+    Bls12381G2,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VetKdKeyId {
+    pub curve: VetKdCurve,
+    pub name: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VetKdPublicKeyArgs {
+    pub canister_id: Option<Principal>,
+    pub context: Vec<u8>,
+    pub key_id: VetKdKeyId,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VetKdPublicKeyResult {
+    pub public_key: Vec<u8>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VetKdDeriveKeyArgs {
+    pub input: Vec<u8>,
+    pub context: Vec<u8>,
+    pub transport_public_key: Vec<u8>,
+    pub key_id: VetKdKeyId,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VetKdDeriveKeyResult {
+    pub encrypted_key: Vec<u8>,
+}
+
+// ICRC Ledger Types (subset of the ICRC-1/ICRC-2 interface)
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct IcrcAccount {
+    pub owner: Principal,
+    pub subaccount: Option<Vec<u8>>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct IcrcTransferArg {
+    pub from_subaccount: Option<Vec<u8>>,
+    pub to: IcrcAccount,
+    pub amount: Nat,
+    pub fee: Option<Nat>,
+    pub memo: Option<Vec<u8>>,
+    pub created_at_time: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum IcrcTransferError {
+    BadFee { expected_fee: Nat },
+    BadBurn { min_burn_amount: Nat },
+    InsufficientFunds { balance: Nat },
+    TooOld,
+    CreatedInFuture { ledger_time: u64 },
+    TemporarilyUnavailable,
+    Duplicate { duplicate_of: Nat },
+    GenericError { error_code: Nat, message: String },
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct IcrcTransferFromArgs {
+    pub spender_subaccount: Option<Vec<u8>>,
+    pub from: IcrcAccount,
+    pub to: IcrcAccount,
+    pub amount: Nat,
+    pub fee: Option<Nat>,
+    pub memo: Option<Vec<u8>>,
+    pub created_at_time: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum IcrcTransferFromError {
+    BadFee { expected_fee: Nat },
+    BadBurn { min_burn_amount: Nat },
+    InsufficientFunds { balance: Nat },
+    InsufficientAllowance { allowance: Nat },
+    TooOld,
+    CreatedInFuture { ledger_time: u64 },
+    Duplicate { duplicate_of: Nat },
+    TemporarilyUnavailable,
+    GenericError { error_code: Nat, message: String },
+}
+
+// Reviewer Pool Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ReviewerPoolConfig {
+    pub ledger_canister_id: Option<Principal>, // Staking is disabled until a ledger is set
+    pub transfer_fee: u64,                      // Ledger fee in base units, paid from payouts
+    pub min_stake: u64,
+    pub slash_percent: u32,      // Share of stake lost by reviewers who voted with the minority
+    pub quorum: u32,             // Minimum votes for a case to be decided
+    pub voting_period_hours: u32,
+    pub committee_size: Option<u32>, // Reviewers drawn per case; defaults to 5
+}
+
+// Reputation Staking Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ReputationStakingConfig {
+    pub ledger_canister_id: Option<Principal>, // Staking is disabled until a ledger is set
+    pub transfer_fee: u64,                      // Ledger fee in base units, paid from payouts
+    pub unstake_cooldown_days: u32,
+}
+
+// Tokens one principal has staked on one company
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ReputationStake {
+    pub company_id: String,
+    pub staker: Principal,
+    pub staked: u64,          // Counts toward the company's reputation
+    pub pending_unstake: u64, // No longer counts; claimable after the cool-down
+    pub unstake_available_at: Option<u64>,
+    pub staked_at: u64,
+    pub updated_at: u64,
+    pub pending_payout: Option<PendingPayout>, // A claim whose ledger outcome is not known yet
+    pub pending_stake: Option<PendingIntake>,  // A stake whose ledger outcome is not known yet
+}
+
+// A payout debited from a record but not confirmed by the ledger. Retrying with the same
+// memo and created_at_time lets the ledger answer Duplicate instead of paying twice.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct PendingPayout {
+    pub amount: u64, // Debited; restored only if the ledger rejects the transfer
+    pub payout: u64, // Sent to the ledger, net of the fee
+    pub memo: Vec<u8>,
+    pub created_at_time: u64,
+}
+
+// Tokens being pulled from a user, recorded before the call. The retry resends the same
+// memo and created_at_time, and the amount is credited only once the ledger confirms it.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct PendingIntake {
+    pub amount: u64,
+    pub memo: Vec<u8>,
+    pub created_at_time: u64,
+}
+
+// Reputation Escrow Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ReputationEscrowPolicy {
+    pub maturation_days: u32, // 0 credits community reputation at once
+}
+
+// How much of a company's community reputation (endorsements, testimonials, vouches)
+// counts yet; the rest is released linearly until matures_at
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ReputationEscrow {
+    pub community_points: u32,
+    pub released_points: u32,
+    pub escrowed_points: u32,
+    pub release_started_at: u64, // Registration time
+    pub matures_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct Reviewer {
+    pub principal: Principal,
+    pub staked: u64,
+    pub pending_rewards: u64,
+    pub total_rewards: u64,
+    pub total_slashed: u64,
+    pub accurate_votes: u32,
+    pub inaccurate_votes: u32,
+    pub joined_at: u64,
+    pub pending_claim: Option<PendingPayout>, // Rewards claim whose ledger outcome is not known yet
+    pub pending_exit: Option<PendingPayout>,  // Same for a pool exit; retried by leaving again
+    pub pending_stake: Option<PendingIntake>, // A bond whose ledger outcome is not known yet; retried by joining again
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ReviewVote {
+    pub reviewer: Principal,
+    pub uphold: bool, // true = the report/dispute is valid
+    pub voted_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum ReviewCaseStatus {
+    Open,
+    Decided,
+    Expired, // Closed without quorum; nobody is slashed
+}
+
+// Community vote on a dispute moderation task
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ReviewCase {
+    pub task_id: String,
+    pub company_id: String,
+    pub opened_by: Principal,
+    pub opened_at: u64,
+    pub closes_at: u64,
+    pub votes: Vec<ReviewVote>,
+    pub status: ReviewCaseStatus,
+    pub upheld: Option<bool>,
+    pub slashed_total: u64,
+    pub decided_at: Option<u64>,
+    // Randomly drawn committee; only its members may vote
+    pub committee: Option<Vec<Principal>>,
+    pub committee_seed: Option<String>,     // Hex of the raw_rand output used for the draw
+    pub committee_pool_size: Option<u32>,  // Eligible reviewers at draw time
+}
+
+// Verification Tier Types
+
+// One condition of a tier; a company qualifies for a tier when it meets all of them
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub enum TierRequirement {
+    MinReputationScore(u32),
+    Verification(VerificationType), // An Active proof of this type
+    MinProofUptimePercent(u32),     // Share of proof checks that found the proof Active
+    MinReputationStake(u64),
+    MinAgeDays(u32), // Days since registration
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct TierDefinition {
+    pub status: CompanyStatus, // Verified or Trusted
+    pub name: String,          // Display name, e.g. "Silver"
+    pub requirements: Vec<TierRequirement>,
+}
+
+// Ordered lowest first; a company reaches a tier only through every tier below it
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct TierDefinitions {
+    pub tiers: Vec<TierDefinition>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RequirementCheck {
+    pub requirement: TierRequirement,
+    pub met: bool,
+    pub current: u64, // Score, percent, tokens or days; 1 if the verification is present
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct TierEvaluation {
+    pub status: CompanyStatus,
+    pub name: String,
+    pub qualified: bool,
+    pub requirements: Vec<RequirementCheck>,
+}
+
+// Where a company stands against every tier, with what it is still missing
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct CompanyTierReport {
+    pub company_id: String,
+    pub status: CompanyStatus,      // Stored status, which may be Stale, Flagged or Suspended
+    pub tier_status: CompanyStatus, // Highest tier the requirements support
+    pub tiers: Vec<TierEvaluation>,
+}
+
+// Threshold ECDSA Attestation Types
+
+// One of the canister's threshold ECDSA keys, as EVM contracts see it
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct EvmSigner {
+    pub key_name: String,
+    pub purpose: String,     // Derivation path, e.g. "evm_attestations"
+    pub public_key: Vec<u8>, // SEC1 compressed secp256k1 point
+    pub evm_address: String, // Lowercase 0x address ecrecover returns
+    pub fetched_at: u64,
+}
+
+// "Company X was <status> at time T with these scores", signed with threshold ECDSA so a
+// contract can check it with ecrecover. Times are Unix seconds, as block.timestamp is.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct EvmAttestation {
+    pub company_id: String,
+    pub status: CompanyStatus, // Verified (1) or Trusted (2) in the signed payload
+    pub verification_score: u32,
+    pub reputation_score: u32,
+    pub attested_at: u64,
+    pub expires_at: u64,
+    pub struct_hash: String, // 0x keccak256 of the abi-encoded fields
+    pub digest: String,      // 0x EIP-191 hash of struct_hash, what was signed
+    pub signature: String,   // 0x r || s || v, v = 27 or 28
+    pub signer: String,
+}
+
+// EVM Root Bridge Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct EvmBridgeConfig {
+    pub enabled: bool,
+    pub chain_type: ChainType, // Ethereum or Polygon; picks the RPC provider
+    pub chain_id: u64,
+    pub contract_address: Option<String>, // Receives publishRoot(bytes32 root, uint64 seq)
+    pub publish_interval_mins: u32,       // Minimum time between publications
+    pub gas_limit: u64,
+    pub max_fee_per_gas: u64,          // Wei; publishing waits while gas costs more
+    pub max_priority_fee_per_gas: u64, // Wei
+}
+
+// One company in a root snapshot
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct EvmRootLeaf {
+    pub company_id: String,
+    pub status: CompanyStatus,
+    pub reputation_score: u32,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct EvmRootSnapshot {
+    pub seq: u64,
+    pub root: String,
+    pub built_at: u64,
+    pub leaves: Vec<EvmRootLeaf>, // Sorted by company id
+    pub tx_hash: Option<String>,  // Set once a publishRoot transaction was accepted
+    pub submitted_at: Option<u64>,
+    pub confirmed_at: Option<u64>,
+}
+
+// A snapshot without its leaves
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EvmRootSummary {
+    pub seq: u64,
+    pub root: String,
+    pub built_at: u64,
+    pub company_count: u32,
+    pub tx_hash: Option<String>,
+    pub submitted_at: Option<u64>,
+    pub confirmed_at: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EvmBridgeStatus {
+    pub config: EvmBridgeConfig,
+    pub sender: Option<String>, // Account that sends publishRoot; fund it for gas. None until first used
+    pub latest: Option<EvmRootSummary>,
+    pub pending: Option<EvmRootSummary>,   // Submitted, not yet mined
+    pub published: Option<EvmRootSummary>, // Last root confirmed on-chain
+    pub last_error: Option<String>,
+}
+
+// Everything a contract needs for MerkleProof.verify(proof, root, leaf)
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EvmMerkleProof {
+    pub company_id: String,
+    pub status: CompanyStatus,
+    pub status_code: u8,
+    pub reputation_score: u32,
+    pub leaf: String,
+    pub proof: Vec<String>, // Sibling hashes, leaf level first
+    pub root: String,
+    pub seq: u64,
+    pub tx_hash: Option<String>, // None if this root isn't on-chain
+}
+
+// Timelock Types
+
+// Sensitive admin changes that only take effect after the timelock delay
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub enum TimelockAction {
+    CompanyStatus {
+        company_id: String,
+        status: CompanyStatus,
+        reason: String,
+    },
+    ScoreWeights {
+        entity_type: EntityType,
+        weights: Option<ScoreWeights>, // None restores the built-in weights
+    },
+    ReviewerPoolConfig(ReviewerPoolConfig),
+    ReputationStakingConfig(ReputationStakingConfig),
+    TimelockDelay { delay_hours: u32 },
+    TierDefinitions(TierDefinitions),
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum TimelockStatus {
+    Queued,
+    Executed,
+    Cancelled,
+    Failed,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct TimelockedAction {
+    pub id: String,
+    pub action: TimelockAction,
+    pub proposed_by: Principal,
+    pub proposed_at: u64,
+    pub executable_at: u64,
+    pub status: TimelockStatus,
+    pub executed_at: Option<u64>,
+    pub cancelled_by: Option<Principal>,
+    pub cancelled_at: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct TimelockConfig {
+    pub delay_hours: u32,
+}
+
+// Ownership Types
+
+// What a manager may do for a company; `created_by` is always an Owner
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum CompanyRole {
+    Owner,    // Everything, including managing managers
+    Editor,   // Profile content, contacts, funding, hiring and community responses
+    Verifier, // Verification and cross-chain proof flows
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct CompanyManager {
+    pub principal: Principal,
+    pub role: CompanyRole,
+    pub added_by: Principal,
+    pub added_at: u64,
+}
+
+// Proposed by the current owner, completed when `to` accepts before `expires_at`
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct OwnershipTransfer {
+    pub company_id: String,
+    pub from: Principal,
+    pub to: Principal,
+    pub proposed_at: u64,
+    pub expires_at: u64,
+}
+
+// Re-verification Campaign Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub enum CampaignAction {
+    Reverify(VerificationType), // A fresh proof of this type after the campaign started
+    ConfirmActive,              // Owner activity (edit or confirm_company_active) after the start
+}
+
+// Applied at the deadline to enrolled companies that haven't finished
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum CampaignConsequence {
+    None,
+    MarkStale,
+    Flag,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum CampaignStatus {
+    Active,
+    Closed, // Deadline passed and consequences applied
+    Cancelled,
+}
+
+#[derive(CandidType, Deserialize, Clone)]
+pub struct CreateCampaignRequest {
+    pub title: String,
+    pub reason: String,
+    pub target: SearchFilters,
+    pub required_actions: Vec<CampaignAction>,
+    pub deadline_days: u32,
+    pub consequence: CampaignConsequence,
+}
+
+#[derive(CandidType, Deserialize, Clone)]
+pub struct ReverificationCampaign {
+    pub id: String,
+    pub title: String,
+    pub reason: String,
+    pub target: SearchFilters,
+    pub required_actions: Vec<CampaignAction>,
+    pub consequence: CampaignConsequence,
+    pub enrolled_company_ids: Vec<String>, // Matched when the campaign was created
+    pub created_by: Principal,
+    pub created_at: u64,
+    pub deadline: u64,
+    pub status: CampaignStatus,
+    pub reminder_sent_at: Option<u64>,
+    pub closed_at: Option<u64>,
+    pub consequences_applied: u32,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct CampaignProgress {
+    pub campaign_id: String,
+    pub status: CampaignStatus,
+    pub deadline: u64,
+    pub enrolled: u32,
+    pub completed: u32,
+    pub pending_company_ids: Vec<String>,
+}
+
+// An enrolled company's remaining work, for its managers
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct CampaignTask {
+    pub campaign_id: String,
+    pub title: String,
+    pub reason: String,
+    pub company_id: String,
+    pub deadline: u64,
+    pub missing_actions: Vec<CampaignAction>,
+    pub consequence: CampaignConsequence,
+}
+
+// Inactivity Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct InactivityPolicy {
+    pub stale_after_days: u32, // Verified and Trusted companies become Stale after this
+    pub warning_days: u32,     // The owner is notified this long before
+}
+
+// Two-Person Rule Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum BlocklistKind {
+    Principal,
+    Domain,
+}
+
+// Irreversible actions that need a second, distinct moderator to approve
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub enum CriticalAction {
+    PermanentSuspension { company_id: String },
+    CompanyDeletion { company_id: String },
+    BlocklistAddition { kind: BlocklistKind, value: String },
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum ProposalStatus {
+    Pending,
+    Executed,
+    Cancelled,
+    Expired,
+    Failed,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct CriticalActionProposal {
+    pub id: String,
+    pub action: CriticalAction,
+    pub reason: String,
+    pub proposed_by: Principal,
+    pub proposed_at: u64,
+    pub expires_at: u64,
+    pub status: ProposalStatus,
+    pub approved_by: Option<Principal>,
+    pub resolved_at: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct BlocklistEntry {
+    pub kind: BlocklistKind,
+    pub value: String, // Principal text or normalized domain
+    pub reason: String,
+    pub proposed_by: Principal,
+    pub approved_by: Principal,
+    pub added_at: u64,
+}
+
+// Admin Audit Trail Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct AuditLogEntry {
+    pub id: String,
+    pub action: String,
+    pub target: String,
+    pub actors: Vec<Principal>, // Everyone who authorized the action, proposer first
+    pub details: String,
+    pub timestamp: u64,
+}
+
+// Open Data Snapshot Types
+
+// One row of the public bulk-download file
+#[derive(Serialize, Clone, Debug)]
+pub struct OpenDataCompany {
+    pub id: String,
+    pub name: String,
+    pub website: String,
+    pub entity_type: EntityType,
+    pub status: CompanyStatus,
+    pub verification_score: u32,
+    pub badges: Vec<String>, // Completed verifications, e.g. "domain", "github", "chain:ethereum"
+    pub focus_areas: Vec<String>,
+    pub updated_at: u64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct OpenDataDocument {
+    pub schema_version: u32,
+    pub generated_at: u64,
+    pub companies: Vec<OpenDataCompany>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct OpenDataSnapshot {
+    pub generated_at: u64,
+    pub company_count: u32,
+    pub sha256: String,  // Hex digest of `gzip_body`
+    pub gzip_body: Vec<u8>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct OpenDataSnapshotInfo {
+    pub generated_at: u64,
+    pub company_count: u32,
+    pub sha256: String,
+    pub size_bytes: u64,
+    pub path: String,
+}
+
+// Canister HTTP interface (served through the HTTP gateway)
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct HttpResponse {
+    pub status_code: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    pub upgrade: Option<bool>, // Asks the gateway to replay the request as http_request_update
+}
+
+// Event Journal Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum CompanyEventKind {
+    Created,
+    Updated,
+    Deleted,
+}
+
+// Append-only record of company writes, consumed incrementally by background jobs
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct CompanyEvent {
+    pub seq: u64,
+    pub company_id: String,
+    pub kind: CompanyEventKind,
+    pub timestamp: u64,
+}
+
+// Reputation Journal Types
+
+// Inputs to the community part of the reputation score. Every kind is idempotent (it
+// states the new value rather than a delta), so replaying the journal is deterministic
+// even though the baseline is captured after the first journaled change.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub enum ReputationEventKind {
+    // State of a company that predates the journal, written before its first event
+    Baseline {
+        endorser_company_ids: Vec<String>,
+        endorser_weights: Option<Vec<(String, u32)>>, // None in baselines recorded before weighting
+        testimonials: Vec<(String, bool)>, // (author name, verified)
+        vouches: Vec<(Principal, u32)>,    // (voucher, weight)
+        reputation_staked: u64,
+    },
+    EndorsementAdded { endorser_company_id: String, weight: Option<u32> },
+    EndorsementRemoved { endorser_company_id: String },
+    TestimonialAdded { author_name: String, verified: bool },
+    TestimonialRemoved { author_name: String },
+    TestimonialVerificationChanged { author_name: String, verified: bool },
+    VouchAdded { voucher_principal: Principal, weight: u32 },
+    VouchRemoved { voucher_principal: Principal },
+    StakeChanged { reputation_staked: u64 },
+    // Recorded for the audit trail; proofs reach the score through verification_score
+    ProofRemoved { proof_url: String, proof_id: Option<String> },
+    ProofRestored { proof_url: String, proof_id: Option<String> },
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ReputationEvent {
+    pub seq: u64, // Per company, from 1
+    pub company_id: String,
+    pub kind: ReputationEventKind,
+    pub actor: Option<Principal>,
+    pub timestamp: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ReputationRecomputation {
+    pub company_id: String,
+    pub previous_score: u32,
+    pub recomputed_score: u32,
+    pub events_replayed: u32,
+}
+
+// Saved Search Types
+
+#[derive(CandidType, Deserialize, Clone)]
+pub struct SavedSearch {
+    pub id: String,
+    pub owner: Principal,
+    pub name: String,
+    pub filters: SearchFilters,
+    pub created_at: u64,
+    pub matched_company_ids: Vec<String>, // Current matches; new entries trigger a notification
+    pub last_match_at: Option<u64>,
+}
+
+// Community Tag Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum TagStatus {
+    Proposed,
+    Approved,
+    Rejected,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct TagProposal {
+    pub company_id: String,
+    pub tag: String, // Lowercase slug, e.g. "open-source"
+    pub proposed_by: Principal,
+    pub proposed_at: u64,
+    pub status: TagStatus,
+    pub decided_by: Option<Principal>,
+    pub decided_at: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct TagStats {
+    pub tag: String,
+    pub approved_count: u32,
+    pub proposed_count: u32,
+}
+
+// API Usage Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct UsageStats {
+    pub principal: Principal,
+    pub update_calls: u64,
+    pub outcall_calls: u64, // Calls that passed a rate limit guarding HTTPS outcalls
+    pub first_seen: u64,
+    pub last_seen: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum UsageMetric {
+    Updates,
+    Outcalls,
+}
+
+// API Key Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum ApiKeyScope {
+    ReadOnly,   // Company profiles and search
+    SearchOnly, // Search only
+}
+
+// Only the SHA-256 of the key is stored; the key itself is shown once at issuance
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ApiKey {
+    pub id: String, // Public identifier, safe to show in dashboards
+    pub key_hash: String,
+    pub owner: Principal,
+    pub label: String,
+    pub scope: ApiKeyScope,
+    pub rate_limit_per_minute: u32,
+    pub created_at: u64,
+    pub revoked_at: Option<u64>,
+    pub last_used_at: Option<u64>,
+    pub request_count: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct IssuedApiKey {
+    pub key: String, // Plaintext key, returned only once
+    pub info: ApiKey,
+}
+
+// Cache Invalidation Hook Types
+
+// Where to push batches of company changes (e.g. the frontend asset canister)
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct CacheInvalidationHook {
+    pub target: Principal,
+    pub method: String, // Called with (vec CompanyEvent), expected to return ()
+    pub configured_at: u64,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: u64, // Backoff after failures
+    pub last_delivered_at: Option<u64>,
+}
+
+// Consecutive failed verifications of one target by one principal
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, Default)]
+pub struct VerificationFailures {
+    pub consecutive: u32,
+    pub last_failure_at: u64,
+    pub blocked_until: u64,
+}
+
+// Owner Dashboard Types
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RateLimitQuota {
+    pub action: String, // "http_outcall", "verification" or "report"
+    pub limit: u32,
+    pub remaining: u32,
+    pub window_secs: u64,
+    pub resets_at: Option<u64>, // When the current window ends, if anything was counted in it
+}
+
+// Requests of one action counted in the fixed window starting at window_start
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct RateLimitWindow {
+    pub action: String,
+    pub window_start: u64,
+    pub count: u32,
+}
+
+// Current window counters of one principal, at most one per action. None for buckets
+// stored by the old timestamp limiter, whose history is dropped.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, Default)]
+pub struct RateLimitBucket {
+    pub windows: Option<Vec<RateLimitWindow>>,
+}
+
+// Everything a signed-in owner needs on one screen; new sections are added as opt fields
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct OwnerDashboard {
+    pub companies: Vec<Company>,
+    pub domain_challenges: Vec<DomainVerificationChallenge>,
+    pub crosschain_challenges: Vec<CrossChainChallenge>,
+    pub open_alerts: Vec<CommunityAlert>,
+    pub open_reports: Vec<ModerationTask>, // Disputes raised against the caller's companies
+    pub unread_notifications: u32,
+    pub rate_limits: Vec<RateLimitQuota>,
+    pub generated_at: u64,
+}
+
+// Security Event Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum SecurityEventType {
+    RateLimited,
+    ApiKeyIssued,
+    ApiKeyRevoked,
+    VerificationLockout,
+    AdminAdded,
+    AdminRemoved,
+    AdminAction, // Privileged moderation call by a registry admin
+    RegistryImpersonation, // Registration posing as the registry itself
+    ModeratorNoteAccess,   // Key derivation, read or write on a task's encrypted notes
+}
+
+// Something security-relevant that happened to (or was done by) `principal`
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct SecurityEvent {
+    pub seq: u64,
+    pub principal: Principal,
+    pub event_type: SecurityEventType,
+    pub company_id: Option<String>,
+    pub details: String,
+    pub timestamp: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Default)]
+pub struct SecurityEventFilter {
+    pub event_types: Option<Vec<SecurityEventType>>, // None matches every type
+    pub from: Option<u64>,                           // Inclusive, nanoseconds
+    pub to: Option<u64>,                             // Exclusive, nanoseconds
+}
+
+// Feature Flag Types
+
+// Effective state of a flag; flags never set by an admin report their built-in default
+// with `updated_by: None`
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct FeatureFlag {
+    pub name: String,
+    pub description: String,
+    pub enabled: bool,
+    pub rollout_percent: u8, // Share of principals (by hash) that see the feature, 0-100
+    pub updated_at: u64,
+    pub updated_by: Option<Principal>,
+}
+
+// Archive Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum ArchiveReason {
+    Archived, // Read-only, still reachable by id; the creator can restore it
+    Deleted,  // Hidden everywhere; kept for admins, who alone can restore it
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ArchivedCompany {
+    pub company: Company,
+    pub reason: ArchiveReason,
+    pub archived_by: Principal,
+    pub archived_at: u64,
+}
+
+// Data Migration Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum MigrationKind {
+    // `to: None` removes the focus area
+    RenameFocusArea { from: String, to: Option<String> },
+    // Rewrites the chain of treasury wallets and token contracts, e.g. "eth" -> "ethereum"
+    RemapChain { from: String, to: String },
+    // Gives proofs stored before stable ids their id, carrying over their monitoring history
+    AssignProofIds,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum MigrationStatus {
+    Running,
+    Completed,
+    Cancelled,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct DataMigration {
+    pub id: String,
+    pub kind: MigrationKind,
+    pub status: MigrationStatus,
+    pub started_by: Principal,
+    pub started_at: u64,
+    pub cursor: Option<String>, // Last company id processed; batches resume after it
+    pub companies_total: u64,   // Company count when the migration started
+    pub companies_scanned: u64,
+    pub companies_changed: u64,
+    pub batches_run: u32,
+    pub finished_at: Option<u64>,
+}
+
+// Partner Integration Types
+
+// Canister an exchange or launchpad runs to receive status changes; called with
+// (vec CompanyStatusChange) and expected to return ()
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct PartnerCallback {
+    pub target: Principal,
+    pub method: String,
+}
+
+// Exchange or launchpad registered by an admin to gate listings on registry status
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct Partner {
+    pub principal: Principal,
+    pub name: String,
+    pub added_by: Principal,
+    pub added_at: u64,
+    pub callback: Option<PartnerCallback>,
+    pub delivered_seq: u64, // Last change the callback accepted
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: u64, // Backoff after failures
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct PartnerSubscription {
+    pub partner: Principal,
+    pub company_id: String,
+    pub last_status: CompanyStatus,
+    pub subscribed_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct CompanyStatusChange {
+    pub seq: u64, // Company event journal sequence that revealed the change
+    pub company_id: String,
+    pub previous_status: CompanyStatus,
+    pub status: Option<CompanyStatus>, // None when the company left the registry
+    pub verification_score: u32,
+    pub risk_level: RiskLevel,
+    pub changed_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RiskLevel {
+    Low,
+    Medium,
+    High,
+}
+
+// Canister signature over a listing attestation: a Merkle path from the attestation's
+// leaf hash to the root the canister keeps as its certified data, and the subnet-signed
+// certificate covering that root
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ListingSignature {
+    pub leaf_index: u64,
+    pub merkle_path: Vec<Vec<u8>>, // Sibling hashes, leaf level first
+    pub root: Vec<u8>,
+    pub certificate: Vec<u8>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ListingAttestation {
+    pub company_id: String,
+    pub company_name: String,
+    pub status: CompanyStatus,
+    pub verification_score: u32,
+    pub reputation_score: u32,
+    pub risk_level: RiskLevel,
+    pub attested_at: u64, // When the certified snapshot was built
+    pub signature: Option<ListingSignature>,
+}
+
+// Attestations covered by the current certified data, sorted by company id, with every
+// level of the Merkle tree built over them (in memory, rebuilt by the partner timer)
+#[derive(Clone, Debug, Default)]
+pub struct ListingSnapshot {
+    pub journal_seq: u64,
+    pub built_at: u64,
+    pub attestations: Vec<ListingAttestation>,
+    pub levels: Vec<Vec<[u8; 32]>>, // Leaf hashes first, root last
+}
+
+// Federation Types
+
+// Another registry canister whose listing attestations are imported
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct FederationPeer {
+    pub canister_id: Principal,
+    pub name: String,
+    pub added_by: Principal,
+    pub added_at: u64,
+    pub sync_cursor: Option<String>,  // Last company id fetched in the current pass
+    pub pass_started_at: Option<u64>, // When the current pass began; None between passes
+    pub last_synced_at: Option<u64>,  // When the last full pass completed
+    pub imported_count: u64,          // Attestations held after the last full pass
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+}
+
+// A page of this registry's certified listing attestations, as served to peers. Each
+// attestation carries its Merkle path to `root`; `certificate` covers `root` when the
+// page was fetched with a query call and is empty otherwise.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct FederationExport {
+    pub registry: Principal,
+    pub root: Vec<u8>,
+    pub built_at: u64,
+    pub certificate: Vec<u8>,
+    pub attestations: Vec<ListingAttestation>,
+    pub next_after: Option<String>, // Pass as `after` for the next page; None on the last page
+}
+
+// A peer's attestation as imported, keyed by peer and company id
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct FederatedAttestation {
+    pub peer: Principal,
+    pub attestation: ListingAttestation,
+    pub imported_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum Provenance {
+    Local,
+    Peer {
+        canister_id: Principal,
+        name: String,
+        attested_at: u64, // When the peer built the attested snapshot
+        imported_at: u64,
+    },
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct FederatedSearchResult {
+    pub company_id: String, // Unique within its registry only
+    pub company_name: String,
+    pub status: CompanyStatus,
+    pub verification_score: u32,
+    pub reputation_score: u32,
+    pub relevance: u32,
+    pub provenance: Provenance,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RegistrySearchPage {
+    pub page: CommunityPage<FederatedSearchResult>,
+    pub federated_cursor: Option<String>, // Set when peer attestations remain past the scanned window
+}
+
+// Replication Types
+
+// Install and upgrade argument; omitted on upgrade, the canister keeps its role
+#[derive(CandidType, Deserialize, Clone, Debug, Default)]
+pub struct RegistryInitArgs {
+    pub replication_source: Option<Principal>, // Set to install a read replica of that registry
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ReplicationCheckpoint {
+    pub registry: Principal,
+    pub latest_seq: u64,
+    pub oldest_seq: u64, // First event still in the journal; 0 when it is empty
+    pub company_count: u64,
+    pub taken_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ReplicatedChange {
+    pub seq: u64,
+    pub company_id: String,
+    pub company: Option<Company>, // Current state; None once deleted
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ReplicationBatch {
+    pub checkpoint: ReplicationCheckpoint,
+    pub changes: Vec<ReplicatedChange>, // Latest change per company, in journal order
+    pub last_seq: u64,                  // Continue after this; covers changes folded away
+    pub resync_required: bool,          // The events after the requested seq were pruned
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ReplicationSnapshotPage {
+    pub checkpoint: ReplicationCheckpoint,
+    pub companies: Vec<Company>,
+    pub next_after: Option<String>,
+}
+
+// A read replica registered with the primary
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ReplicaInfo {
+    pub canister_id: Principal,
+    pub added_by: Principal,
+    pub added_at: u64,
+    pub applied_seq: u64,
+    pub lag_events: u64,
+    pub last_reported_at: Option<u64>,
+}
+
+// Kept by a replica about the registry it mirrors
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ReplicationState {
+    pub source: Principal,
+    pub applied_seq: u64,
+    pub resyncing: bool,                 // Copying every company before following the journal again
+    pub snapshot_cursor: Option<String>, // Last company id copied in the current resync
+    pub snapshot_seq: u64,               // Journal position the current resync started from
+    pub source_latest_seq: u64,
+    pub last_synced_at: Option<u64>,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum ReplicationStatus {
+    Primary { latest_seq: u64, replicas: Vec<ReplicaInfo> },
+    Replica { state: ReplicationState, lag_events: u64 },
+}
+
+// Sharding Types
+
+// Another registry canister that holds the companies of the slots assigned to it
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct Shard {
+    pub canister_id: Principal,
+    pub added_by: Principal,
+    pub added_at: u64,
+    pub moved_out: u64, // Companies this canister has handed over to the shard
+    pub last_move_at: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ShardImportResult {
+    pub imported: Vec<String>,
+    pub conflicts: Vec<String>, // Ids already used by a different company on the shard
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ShardLocation {
+    pub company_id: String,
+    pub slot: u32,
+    pub canister_id: Principal, // Where the slot lives
+    pub stored_here: bool,      // Still on this canister, e.g. not moved yet
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ShardSlotRange {
+    pub first_slot: u32,
+    pub last_slot: u32,
+    pub canister_id: Principal,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ShardMap {
+    pub registry: Principal,
+    pub slot_count: u32,
+    pub ranges: Vec<ShardSlotRange>, // Covers every slot, in slot order
+    pub shards: Vec<Shard>,
+    pub pending_moves: u64, // Companies still here whose slot belongs to a shard
+}
+
+// Background Job Types
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum JobStatus {
+    Running,
+    Completed,
+    Cancelled, // Queued tasks were dropped; tasks already running still finish
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct JobProgress {
+    pub id: String,
+    pub name: String,
+    pub status: JobStatus,
+    pub total: u32,
+    pub pending: u32,
+    pub in_flight: u32,
+    pub completed: u32,
+    pub failed: u32,
+    pub result: u32, // Sum of what the finished tasks reported, e.g. alerts raised
+    pub started_at: u64,
+    pub finished_at: Option<u64>,
+    pub last_error: Option<String>,
+    pub cancelled_by: Option<Principal>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct JobConcurrencyLimits {
+    pub max_in_flight: u32,                  // Across every job and provider
+    pub default_per_provider: u32,
+    pub provider_limits: Vec<(String, u32)>, // Overrides by provider name
+}
+
+// Registry Identity Types
+
+// Canisters and domains that belong to the registry itself; a company claiming one is
+// flagged. The registry's own canister ID is always protected and need not be listed.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct RegistryIdentity {
+    pub canister_ids: Vec<String>,
+    pub domains: Vec<String>,
+    pub updated_by: Principal,
+    pub updated_at: u64,
+}
+
+// Chain Statistics Types
+
+// Running totals for one chain, keyed by chain name. Presence and address counts follow
+// the company event journal; attempts and successes are booked as verifications finish.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, Default)]
+pub struct ChainCounters {
+    pub companies_with_presence: u64,
+    pub listed_addresses: u64,
+    pub verified_addresses: u64,
+    pub verification_attempts: u64,
+    pub verification_successes: u64,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ChainContributionEntry {
+    pub chain: String,
+    pub listed_addresses: u64,
+    pub verified_addresses: u64,
+}
+
+// What one company currently adds to the chain counters, so a change is applied as a delta
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, Default)]
+pub struct ChainContribution {
+    pub chains: Vec<ChainContributionEntry>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ChainStatistics {
+    pub chain: ChainType,
+    pub companies_with_presence: u64,
+    pub listed_addresses: u64,
+    pub verified_addresses: u64,
+    pub verification_attempts: u64,
+    pub verification_successes: u64,
+    pub success_rate_percent: Option<u32>, // None until a verification has been attempted
+}
+
+// Webhook Types
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum WebhookTarget {
+    Https { url: String },                       // Receives a JSON POST {"events": [...]}
+    Canister { target: Principal, method: String }, // No longer accepted; kept so older subscriptions decode
+}
+
+// A principal's push subscription for alerts and, for moderators, security events
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct WebhookSubscription {
+    pub id: String,
+    pub owner: Principal,
+    pub target: WebhookTarget,
+    pub company_ids: Vec<String>, // Empty means every company the owner may see alerts for
+    pub min_severity: AlertSeverity,
+    pub include_security_events: bool,
+    pub created_at: u64,
+    pub delivered_seq: u64, // Last outbox entry the target accepted
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: u64, // Backoff after failures
+    pub last_delivered_at: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub enum WebhookPayload {
+    Alert(CommunityAlert),
+    Security(SecurityEvent),
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct WebhookEvent {
+    pub seq: u64,
+    pub payload: WebhookPayload,
+    pub created_at: u64,
+}
+
+// Policy Changelog Types
+
+// One change to a registry-wide parameter, recorded when it took effect
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct PolicyChange {
+    pub seq: u64,
+    pub parameter: String, // e.g. "score_weights:Company", "provider_endpoints:Etherscan"
+    pub previous_value: Option<String>,
+    pub new_value: String,
+    pub changed_by: Vec<Principal>,
+    pub source: String, // Endpoint name, or "timelock:<action id>"
+    pub effective_at: u64,
+}
+
+// Implement Storable for types that need to be stored in stable structures
+
+impl Storable for Company {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for DomainVerificationChallenge {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for CrossChainChallenge {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ProviderEndpointList {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ReservedName {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for CompanyRelationship {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for GrantProgram {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for GrantAttestation {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ImpersonationReport {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for CommunityAlert {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ContractWatch {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for CanisterSnapshot {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for TreasurySnapshot {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for MonitoringConfig {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for Notification {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for AlertRoutingRule {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ModerationTask {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for Moderator {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ReviewerPoolConfig {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for Reviewer {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ReviewCase {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for TimelockedAction {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for OwnershipTransfer {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ReverificationCampaign {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for VerificationFailures {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for RateLimitBucket {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ChainCounters {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ChainContribution {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ProviderHealth {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for RegistryIdentity {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ProofMonitoring {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for CommunityCooldownPolicy {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for Concern {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for WebhookSubscription {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for WebhookEvent {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ReputationStakingConfig {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ReputationStake {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for PolicyChange {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for FederationPeer {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for FederatedAttestation {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ReplicaInfo {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ReplicationState {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for Shard {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ApiConfig {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ModeratorNoteThread {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for PrivateMessage {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ReputationEscrowPolicy {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for TierDefinitions {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for EvmSigner {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for EvmAttestation {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for EvmBridgeConfig {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for EvmRootSnapshot {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for Partner {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for PartnerSubscription {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for CompanyStatusChange {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for Admin {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for EmergencySuspension {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for InactivityPolicy {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for TimelockConfig {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ScoreWeights {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for CriticalActionProposal {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for BlocklistEntry {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for AuditLogEntry {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for OpenDataSnapshot {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for CompanyEvent {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for SavedSearch {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for TagProposal {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for UsageStats {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ApiKey {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for CacheInvalidationHook {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for SecurityEvent {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for FeatureFlag {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ReputationEvent {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ArchivedCompany {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for DataMigration {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+// Canned provider answer used in simulation mode (builds with the `simulation` feature
+// only). Matches outcalls whose URL starts with `url_prefix` and, if set, whose request
+// body contains `body_contains`, e.g. "getSignaturesForAddress" for a Solana RPC method.
+#[cfg(feature = "simulation")]
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SimulationFixture {
+    pub url_prefix: String,
+    pub body_contains: Option<String>,
+    pub status: u16,
+    pub body: String,
+}
+
+#[cfg(feature = "simulation")]
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SimulationStatus {
+    pub enabled: bool,
+    pub fixtures: Vec<SimulationFixture>,
+}
+
+#[cfg(feature = "simulation")]
+impl Storable for SimulationFixture {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
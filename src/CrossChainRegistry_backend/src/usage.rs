@@ -0,0 +1,90 @@
+use crate::admins::AdminManager;
+use crate::storage::StorageManager;
+use crate::types::{RateLimitQuota, RegistryResult, UsageMetric, UsageStats};
+use crate::verification_guard::VerificationGuard;
+use candid::Principal;
+use ic_cdk::api::time;
+use std::time::Duration;
+
+// Per-caller call accounting, fed by the update guards in lib.rs. The numbers are meant
+// for spotting heavy integrations and for future quota policies. Queries aren't counted:
+// a non-replicated query's state changes are discarded, so there is nothing to keep.
+
+pub struct UsageTracker;
+
+impl UsageTracker {
+    const MAX_REPORT_SIZE: u32 = 100;
+    const RATE_LIMIT_COMPACTION_INTERVAL_SECS: u64 = 10 * 60;
+    // Any principal can make calls, so the table is pruned like the rate-limit history
+    const MAX_TRACKED_CALLERS: u64 = 10_000;
+    const USAGE_RETENTION_NANOS: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+    // Rate-limit history, verification streaks and usage stats are stable, so idle callers
+    // have to be dropped explicitly
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::RATE_LIMIT_COMPACTION_INTERVAL_SECS), || {
+            StorageManager::compact_rate_limits();
+            VerificationGuard::prune_failures();
+            StorageManager::prune_usage_stats(
+                time().saturating_sub(Self::USAGE_RETENTION_NANOS),
+                Self::MAX_TRACKED_CALLERS,
+            );
+        });
+    }
+
+    fn record(principal: Principal, update: impl FnOnce(&mut UsageStats)) {
+        let now = time();
+        let mut stats = StorageManager::get_usage_stats(&principal).unwrap_or(UsageStats {
+            principal,
+            update_calls: 0,
+            outcall_calls: 0,
+            first_seen: now,
+            last_seen: now,
+        });
+        update(&mut stats);
+        stats.last_seen = now;
+        StorageManager::insert_usage_stats(stats);
+    }
+
+    pub fn record_update(principal: Principal) {
+        Self::record(principal, |stats| stats.update_calls += 1);
+    }
+
+    pub fn record_outcall(principal: Principal) {
+        Self::record(principal, |stats| stats.outcall_calls += 1);
+    }
+
+    pub fn get_my_usage(caller_principal: Principal) -> UsageStats {
+        StorageManager::get_usage_stats(&caller_principal).unwrap_or(UsageStats {
+            principal: caller_principal,
+            update_calls: 0,
+            outcall_calls: 0,
+            first_seen: 0,
+            last_seen: 0,
+        })
+    }
+
+    pub fn get_my_rate_limit_status(caller_principal: Principal) -> Vec<RateLimitQuota> {
+        StorageManager::get_rate_limit_quotas(caller_principal)
+    }
+
+    pub fn get_top_consumers(
+        metric: UsageMetric,
+        limit: Option<u32>,
+        caller_principal: Principal,
+    ) -> RegistryResult<Vec<UsageStats>> {
+        if let Err(err) = AdminManager::ensure_admin(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+
+        let mut stats = StorageManager::get_all_usage_stats();
+        stats.sort_by_key(|s| {
+            std::cmp::Reverse(match metric {
+                UsageMetric::Updates => s.update_calls,
+                UsageMetric::Outcalls => s.outcall_calls,
+            })
+        });
+        stats.truncate(limit.unwrap_or(20).min(Self::MAX_REPORT_SIZE) as usize);
+        RegistryResult::Ok(stats)
+    }
+}
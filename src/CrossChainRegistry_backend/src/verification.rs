@@ -1,91 +1,227 @@
+use crate::admins::AdminManager;
+use crate::crosschain::CrossChainVerifier;
+use crate::disputes::DisputeManager;
+use crate::http_client;
+use crate::inactivity::InactivityManager;
+use crate::notifications::NotificationManager;
+use crate::ownership::OwnershipManager;
+use crate::reputation::ReputationJournal;
+use crate::sanitize::{self, Handle, SafeUrl};
+use crate::settings::SettingsManager;
 use crate::storage::StorageManager;
 use crate::types::{
-    Company, CommunityReport, DomainVerificationChallenge, GitHubOrgResponse, ProofCheckResult,
-    ProofStatus, RegistryResult, ReportType, VerificationMethod, VerificationProof,
-    VerificationResult, VerificationStatus, VerificationType,
+    ApiProvider, ChainType, Company, CompanyRole, CrossChainPresence, CommunityReport, CrossChainChallenge,
+    DomainVerificationChallenge, EntityType, ScoreWeights, GitHubOrgResponse, ProofCheckResult, ProofMonitoring, ProofStatus, ProviderEndpoint,
+    PreviewCheck, ProofPreview, RegistryResult, ResolvedProof, ReportType, ReputationEventKind, VerificationMethod, VerificationProof, VerificationResult,
+    VerificationState, VerificationStatus, VerificationSummary, VerificationSummaryItem,
+    VerificationType,
 };
 use candid::Principal;
 use ic_cdk::api::management_canister::http_request::{
-    http_request, CanisterHttpRequestArgument, HttpHeader, HttpMethod, HttpResponse, TransformArgs,
+    CanisterHttpRequestArgument, HttpHeader, HttpMethod, HttpResponse, TransformArgs,
     TransformContext,
 };
 use ic_cdk::api::time;
 use regex::Regex;
-use serde_json;
+use sha2::{Digest, Sha256};
+use std::time::Duration;
 
 // Verification logic implementation
 
 pub struct VerificationManager;
 
+// Score points per verification source, before the overall cap
+struct VerificationContributions {
+    github: u32,
+    domain: u32,
+    social: u32,
+    chains: Vec<(ChainType, u32)>,
+    treasury: u32,
+    tokens: u32,
+}
+
+impl VerificationContributions {
+    fn total(&self) -> u32 {
+        self.chains
+            .iter()
+            .map(|(_, points)| *points)
+            .chain([self.github, self.domain, self.social, self.treasury, self.tokens])
+            .fold(0u32, u32::saturating_add)
+    }
+}
+
 impl VerificationManager {
+    const MAX_CHECK_RESULTS: usize = 20;
+    const MAX_COMMUNITY_REPORTS: usize = 20;
+
+    // Effective weights: a timelocked override if one has been applied, else the defaults
+    pub fn score_weights(entity_type: &EntityType) -> ScoreWeights {
+        StorageManager::get_score_weight_override(&format!("{:?}", entity_type))
+            .unwrap_or_else(|| Self::default_score_weights(entity_type))
+    }
+
+    // Built-in weights per entity type. Companies keep the original weights; other
+    // registrants shift points towards the signals they can realistically provide.
+    pub fn default_score_weights(entity_type: &EntityType) -> ScoreWeights {
+        match entity_type {
+            EntityType::Company => ScoreWeights {
+                basic_info_field: 5,
+                github: 10,
+                domain: 10,
+                social: 10,
+                per_chain: 5,
+                treasury: 5,
+                tokens: 5,
+                per_verified_member: 3,
+                team_max: 15,
+                community_max: 10,
+            },
+            // DAOs are judged by on-chain treasury and governance tokens rather than staff
+            EntityType::Dao => ScoreWeights {
+                basic_info_field: 5,
+                github: 10,
+                domain: 10,
+                social: 10,
+                per_chain: 5,
+                treasury: 10,
+                tokens: 10,
+                per_verified_member: 2,
+                team_max: 5,
+                community_max: 15,
+            },
+            // Open-source projects live on GitHub
+            EntityType::OpenSourceProject => ScoreWeights {
+                basic_info_field: 5,
+                github: 20,
+                domain: 5,
+                social: 10,
+                per_chain: 5,
+                treasury: 5,
+                tokens: 5,
+                per_verified_member: 3,
+                team_max: 10,
+                community_max: 15,
+            },
+            // Individual builders have no team; identity comes from their own accounts
+            EntityType::IndividualBuilder => ScoreWeights {
+                basic_info_field: 5,
+                github: 15,
+                domain: 10,
+                social: 15,
+                per_chain: 5,
+                treasury: 5,
+                tokens: 5,
+                per_verified_member: 0,
+                team_max: 0,
+                community_max: 15,
+            },
+        }
+    }
+
+    // Re-score every company, e.g. after the weights changed
+    pub fn recalculate_all_scores() {
+        for company in StorageManager::get_all_companies() {
+            StorageManager::update_company(&company.id, |company| {
+                company.verification_score = Self::calculate_verification_score(company);
+            });
+        }
+    }
+
+    // Points each identity and cross-chain source earns. Shared by the scorer and the
+    // verification summary so the two can't disagree about what counts.
+    fn verification_contributions(company: &Company, weights: &ScoreWeights) -> VerificationContributions {
+        let identity = &company.web3_identity;
+
+        // Addresses under an escalated alert do not count
+        let presence = &company.cross_chain_presence;
+        let paused = company.paused_score_sources.as_deref().unwrap_or_default();
+        let counts = |address: &String| !paused.contains(address);
+        let chains = Self::chain_addresses(presence)
+            .into_iter()
+            .map(|(chain_type, addresses)| {
+                // Polygon contracts are listed but not scored
+                let points = match chain_type {
+                    ChainType::Polygon => 0,
+                    _ if addresses.iter().any(counts) => weights.per_chain,
+                    _ => 0,
+                };
+                (chain_type, points)
+            })
+            .collect();
+
+        VerificationContributions {
+            github: if identity.github_org.is_some() { weights.github } else { 0 },
+            domain: if identity.domain_verified { weights.domain } else { 0 },
+            social: if matches!(identity.social_verification_status, VerificationStatus::Verified) {
+                weights.social
+            } else {
+                0
+            },
+            chains,
+            treasury: if presence.treasury_wallets.iter().any(|w| counts(&w.address)) {
+                weights.treasury
+            } else {
+                0
+            },
+            tokens: if presence.token_contracts.iter().any(|t| counts(&t.contract_address)) {
+                weights.tokens
+            } else {
+                0
+            },
+        }
+    }
+
+    fn chain_addresses(presence: &CrossChainPresence) -> [(ChainType, &Vec<String>); 7] {
+        [
+            (ChainType::Ethereum, &presence.ethereum_contracts),
+            (ChainType::Bitcoin, &presence.bitcoin_addresses),
+            (ChainType::ICP, &presence.icp_canisters),
+            (ChainType::Polygon, &presence.polygon_contracts),
+            (ChainType::Solana, &presence.solana_addresses),
+            (ChainType::Sui, &presence.sui_addresses),
+            (ChainType::TON, &presence.ton_addresses),
+        ]
+    }
+
     // Calculate verification score based on multiple signals
     pub fn calculate_verification_score(company: &Company) -> u32 {
+        let weights = Self::score_weights(&company.entity_type.clone().unwrap_or_default());
+        // Saturating throughout: weights are admin-set and may be large
         let mut score = 0u32;
 
-        // Basic info completeness (max 20 points)
+        // Basic info completeness
         if !company.basic_info.name.is_empty() {
-            score += 5;
+            score = score.saturating_add(weights.basic_info_field);
         }
         if !company.basic_info.description.is_empty() {
-            score += 5;
+            score = score.saturating_add(weights.basic_info_field);
         }
         if !company.basic_info.website.is_empty() {
-            score += 5;
+            score = score.saturating_add(weights.basic_info_field);
         }
         if !company.basic_info.focus_areas.is_empty() {
-            score += 5;
+            score = score.saturating_add(weights.basic_info_field);
         }
 
-        // Web3 identity verification (max 30 points)
-        if company.web3_identity.github_org.is_some() {
-            score += 10;
-        }
-        if company.web3_identity.domain_verified {
-            score += 10;
-        }
-        if matches!(
-            company.web3_identity.social_verification_status,
-            VerificationStatus::Verified
-        ) {
-            score += 10;
-        }
-
-        // Cross-chain presence (max 40 points)
-        if !company.cross_chain_presence.ethereum_contracts.is_empty() {
-            score += 5;
-        }
-        if !company.cross_chain_presence.bitcoin_addresses.is_empty() {
-            score += 5;
-        }
-        if !company.cross_chain_presence.icp_canisters.is_empty() {
-            score += 5;
-        }
-        if !company.cross_chain_presence.solana_addresses.is_empty() {
-            score += 5;
-        }
-        if !company.cross_chain_presence.sui_addresses.is_empty() {
-            score += 5;
-        }
-        if !company.cross_chain_presence.ton_addresses.is_empty() {
-            score += 5;
-        }
-        if !company.cross_chain_presence.treasury_wallets.is_empty() {
-            score += 5;
-        }
-        if !company.cross_chain_presence.token_contracts.is_empty() {
-            score += 5;
-        }
+        // Identity and cross-chain verifications
+        score = score.saturating_add(Self::verification_contributions(company, &weights).total());
 
-        // Team verification (max 15 points)
+        // Team verification
         let verified_team_count = company
             .team_members
             .iter()
             .filter(|m| m.verified)
             .count() as u32;
-        score += std::cmp::min(verified_team_count * 3, 15);
+        score = score.saturating_add(std::cmp::min(
+            verified_team_count.saturating_mul(weights.per_verified_member),
+            weights.team_max,
+        ));
 
-        // Community validation (max 10 points)
-        score += std::cmp::min(company.community_validation.reputation_score / 10, 10);
+        // Community validation
+        score = score.saturating_add(std::cmp::min(
+            company.community_validation.reputation_score / 10,
+            weights.community_max,
+        ));
 
         std::cmp::min(score, 100) // Cap at 100
     }
@@ -102,24 +238,27 @@ impl VerificationManager {
             None => return RegistryResult::Err("Company not found".to_string()),
         };
 
-        if company.created_by != caller_principal {
-            return RegistryResult::Err("Unauthorized: Only company creator can verify".to_string());
+        if !OwnershipManager::has_role(&company, caller_principal, CompanyRole::Verifier) {
+            return RegistryResult::Err("Unauthorized: Only company owners or verifiers can verify".to_string());
         }
+        if let Err(err) = AdminManager::ensure_not_suspended(&company) {
+            return RegistryResult::Err(err);
+        }
+
+        // The name is interpolated into the API URL
+        let github_org = match Handle::parse(&github_org, "GitHub organization") {
+            Ok(handle) => handle.as_str().to_string(),
+            Err(err) => return RegistryResult::Err(err),
+        };
 
         // Check verification-specific rate limiting
-        if !StorageManager::check_verification_rate_limit(caller_principal) {
-            let (current_requests, _) = StorageManager::get_rate_limit_info(caller_principal);
-            return RegistryResult::Err(format!(
-                "Verification rate limit exceeded ({} attempts). Please wait 5 minutes before trying again.", 
-                current_requests
-            ));
+        if let Err(err) = StorageManager::check_verification_rate_limit(caller_principal) {
+            return RegistryResult::Err(err);
         }
 
-        // Make HTTP request to GitHub API
-        let url = format!("https://api.github.com/orgs/{}", github_org);
-
-        let request = CanisterHttpRequestArgument {
-            url: url.clone(),
+        // Make HTTP request to the GitHub API, failing over between configured endpoints
+        let build_request = |endpoint: &ProviderEndpoint| CanisterHttpRequestArgument {
+            url: endpoint.url_for(&github_org),
             method: HttpMethod::GET,
             body: None,
             max_response_bytes: Some(4096),
@@ -139,21 +278,37 @@ impl VerificationManager {
             ],
         };
 
-        match http_request(request, 10_000_000_000).await {
-            Ok((response,)) => {
+        match SettingsManager::http_request_with_failover(ApiProvider::GitHub, build_request, 10_000_000_000).await {
+            Ok((_, response)) => {
                 if response.status == 200u32 {
                     // Parse GitHub API response
                     match serde_json::from_slice::<GitHubOrgResponse>(&response.body) {
                         Ok(github_data) => {
                             // Verify organization exists and has reasonable activity
                             if github_data.public_repos >= 1 {
+                                let proof = VerificationProof {
+                                    verification_type: VerificationType::GitHub,
+                                    proof_url: format!("https://github.com/{}", github_org),
+                                    verified_at: time(),
+                                    verification_method: VerificationMethod::Automated,
+                                    challenge_data: None,
+                                    status: ProofStatus::Active,
+                                    previous_urls: None,
+                                    id: Some(Self::new_proof_id(&company_id, &format!("github:{}", github_org))),
+                                };
+                                let mut proof_id = Self::proof_id(&proof);
+
                                 // Update company verification status
+                                let mut score_delta = 0;
                                 let success = StorageManager::update_company(&company_id, |company| {
+                                    let previous_score = company.verification_score;
                                     company.web3_identity.github_org = Some(github_org.clone());
                                     company.web3_identity.social_verification_status =
                                         VerificationStatus::Verified;
+                                    proof_id = Self::replace_automated_proof(company, proof);
                                     company.verification_score =
                                         Self::calculate_verification_score(company);
+                                    score_delta = company.verification_score as i32 - previous_score as i32;
                                 });
 
                                 if success {
@@ -164,6 +319,13 @@ impl VerificationManager {
                                             github_org
                                         ),
                                         verified_at: Some(time()),
+                                        proof_id: Some(proof_id),
+                                        verification_type: Some(VerificationType::GitHub),
+                                        chain_type: None,
+                                        score_delta,
+                                        retry_after_secs: None,
+                                        next_action: Self::recommend_next_action(&company_id),
+                                        providers: None,
                                     })
                                 } else {
                                     RegistryResult::Err("Failed to update company".to_string())
@@ -174,6 +336,16 @@ impl VerificationManager {
                                     message: "GitHub organization has no public repositories"
                                         .to_string(),
                                     verified_at: None,
+                                    proof_id: None,
+                                    verification_type: Some(VerificationType::GitHub),
+                                    chain_type: None,
+                                    score_delta: 0,
+                                    retry_after_secs: None,
+                                    next_action: Some(
+                                        "Publish at least one public repository and retry verify_github_organization"
+                                            .to_string(),
+                                    ),
+                                    providers: None,
                                 })
                             }
                         }
@@ -184,12 +356,22 @@ impl VerificationManager {
                         success: false,
                         message: "GitHub organization not found".to_string(),
                         verified_at: None,
+                        proof_id: None,
+                        verification_type: Some(VerificationType::GitHub),
+                        chain_type: None,
+                        score_delta: 0,
+                        retry_after_secs: None,
+                        next_action: Some(
+                            "Check the organization name spelling and retry verify_github_organization"
+                                .to_string(),
+                        ),
+                        providers: None,
                     })
                 } else {
                     RegistryResult::Err(format!("GitHub API error: {}", response.status))
                 }
             }
-            Err(err) => RegistryResult::Err(format!("HTTP request failed: {:?}", err)),
+            Err(err) => RegistryResult::Err(format!("HTTP request failed: {}", err)),
         }
     }
 
@@ -204,13 +386,16 @@ impl VerificationManager {
             None => return RegistryResult::Err("Company not found".to_string()),
         };
 
-        if company.created_by != caller_principal {
+        if !OwnershipManager::has_role(&company, caller_principal, CompanyRole::Verifier) {
             return RegistryResult::Err(
-                "Unauthorized: Only company creator can create challenges".to_string(),
+                "Unauthorized: Only company owners or verifiers can create challenges".to_string(),
             );
         }
+        if let Err(err) = AdminManager::ensure_not_suspended(&company) {
+            return RegistryResult::Err(err);
+        }
 
-        // Extract domain from company website
+        // Extract domain from company website (punycode, as DNS lookups need it)
         let domain = match Self::extract_domain_from_url(&company.basic_info.website) {
             Ok(domain) => domain,
             Err(err) => return RegistryResult::Err(err),
@@ -239,12 +424,8 @@ impl VerificationManager {
         caller_principal: Principal,
     ) -> RegistryResult<VerificationResult> {
         // Check verification-specific rate limiting
-        if !StorageManager::check_verification_rate_limit(caller_principal) {
-            let (current_requests, _) = StorageManager::get_rate_limit_info(caller_principal);
-            return RegistryResult::Err(format!(
-                "Verification rate limit exceeded ({} attempts). Please wait 5 minutes before trying again.", 
-                current_requests
-            ));
+        if let Err(err) = StorageManager::check_verification_rate_limit(caller_principal) {
+            return RegistryResult::Err(err);
         }
 
         // Get challenge
@@ -263,38 +444,43 @@ impl VerificationManager {
             return RegistryResult::Err("Domain verification challenge expired".to_string());
         }
 
-        // Check DNS TXT record
-        let verification_url = format!(
-            "https://dns.google/resolve?name={}&type=TXT",
-            challenge.domain
-        );
+        // A challenge issued before a suspension can't be redeemed during it
+        if let Some(company) = StorageManager::get_company(&company_id) {
+            if let Err(err) = AdminManager::ensure_not_suspended(&company) {
+                return RegistryResult::Err(err);
+            }
+        }
 
-        let request = CanisterHttpRequestArgument {
-            url: verification_url,
-            method: HttpMethod::GET,
-            body: None,
-            max_response_bytes: Some(1024),
-            transform: Some(TransformContext::from_name(
-                "transform_domain_response".to_string(),
-                vec![],
-            )),
-            headers: vec![HttpHeader {
-                name: "Accept".to_string(),
-                value: "application/json".to_string(),
-            }],
-        };
+        // Check DNS TXT record, failing over between DNS-over-HTTPS resolvers
+        let build_request = |endpoint: &ProviderEndpoint| Self::dns_request(endpoint, &challenge.domain);
 
-        match http_request(request, 10_000_000_000).await {
-            Ok((response,)) => {
+        match SettingsManager::http_request_with_failover(ApiProvider::Dns, build_request, 10_000_000_000).await {
+            Ok((_, response)) => {
                 if response.status == 200u32 {
                     // Parse DNS response and look for challenge token
                     let response_text = String::from_utf8_lossy(&response.body);
 
                     if response_text.contains(&challenge.challenge_token) {
                         // Verification successful
+                        let proof = VerificationProof {
+                            verification_type: VerificationType::Domain,
+                            proof_url: format!("https://{}", challenge.domain),
+                            verified_at: time(),
+                            verification_method: VerificationMethod::Automated,
+                            challenge_data: Some(challenge.challenge_token.clone()),
+                            status: ProofStatus::Active,
+                            previous_urls: None,
+                            id: Some(Self::new_proof_id(&company_id, &challenge.challenge_token)),
+                        };
+                        let mut proof_id = Self::proof_id(&proof);
+
+                        let mut score_delta = 0;
                         let success = StorageManager::update_company(&company_id, |company| {
+                            let previous_score = company.verification_score;
                             company.web3_identity.domain_verified = true;
+                            proof_id = Self::replace_automated_proof(company, proof);
                             company.verification_score = Self::calculate_verification_score(company);
+                            score_delta = company.verification_score as i32 - previous_score as i32;
                         });
 
                         if success {
@@ -305,6 +491,13 @@ impl VerificationManager {
                                 success: true,
                                 message: format!("Domain '{}' verified successfully", challenge.domain),
                                 verified_at: Some(time()),
+                                proof_id: Some(proof_id),
+                                verification_type: Some(VerificationType::Domain),
+                                chain_type: None,
+                                score_delta,
+                                retry_after_secs: None,
+                                next_action: Self::recommend_next_action(&company_id),
+                                providers: None,
                             })
                         } else {
                             RegistryResult::Err("Failed to update company".to_string())
@@ -317,13 +510,23 @@ impl VerificationManager {
                                 challenge.challenge_token, challenge.domain
                             ),
                             verified_at: None,
+                            proof_id: None,
+                            verification_type: Some(VerificationType::Domain),
+                            chain_type: None,
+                            score_delta: 0,
+                            retry_after_secs: None,
+                            next_action: Some(
+                                "Wait for DNS propagation of the TXT record and retry verify_domain_ownership"
+                                    .to_string(),
+                            ),
+                            providers: None,
                         })
                     }
                 } else {
                     RegistryResult::Err(format!("DNS query failed with status: {}", response.status))
                 }
             }
-            Err(err) => RegistryResult::Err(format!("DNS query request failed: {:?}", err)),
+            Err(err) => RegistryResult::Err(format!("DNS query request failed: {}", err)),
         }
     }
 
@@ -340,73 +543,51 @@ impl VerificationManager {
             None => return RegistryResult::Err("Company not found".to_string()),
         };
 
-        if company.created_by != caller_principal {
-            return RegistryResult::Err("Unauthorized: Only company creator can verify".to_string());
+        if !OwnershipManager::has_role(&company, caller_principal, CompanyRole::Verifier) {
+            return RegistryResult::Err("Unauthorized: Only company owners or verifiers can verify".to_string());
+        }
+        if let Err(err) = AdminManager::ensure_not_suspended(&company) {
+            return RegistryResult::Err(err);
         }
 
         // Secure URL validation with domain whitelisting
-        let verification_type = match platform.to_lowercase().as_str() {
-            "twitter" => {
-                if let Err(e) = Self::validate_secure_url(&proof_url, &["twitter.com", "x.com", "mobile.twitter.com"]) {
-                    return RegistryResult::Err(e);
-                }
-                VerificationType::Twitter
-            }
-            "discord" => {
-                if let Err(e) = Self::validate_secure_url(&proof_url, &["discord.gg", "discord.com", "discordapp.com"]) {
-                    return RegistryResult::Err(e);
-                }
-                VerificationType::Discord
-            }
-            "telegram" => {
-                if let Err(e) = Self::validate_secure_url(&proof_url, &["t.me", "telegram.me"]) {
-                    return RegistryResult::Err(e);
-                }
-                VerificationType::Telegram
-            }
-            _ => return RegistryResult::Err("Unsupported platform".to_string()),
+        let verification_type = match Self::social_platform(&platform) {
+            Some(verification_type) => verification_type,
+            None => return RegistryResult::Err("Unsupported platform".to_string()),
+        };
+        let allowed_domains = Self::social_proof_domains(&verification_type).unwrap_or(&[]);
+        let proof_url = match SafeUrl::parse_in_domains(&proof_url, "Proof URL", allowed_domains) {
+            Ok(url) => url,
+            Err(e) => return RegistryResult::Err(e),
         };
 
         // Create permanent verification proof with sanitized data
-        let sanitized_challenge = Self::sanitize_challenge_data(
+        let sanitized_challenge = sanitize::filter_token_chars(
             &format!("ICP CrossChain Registry - Company ID: {}", company_id)
         );
         let proof = VerificationProof {
             verification_type: verification_type.clone(),
-            proof_url: Self::sanitize_url(&proof_url),
+            proof_url: proof_url.as_str().to_string(),
             verified_at: time(),
             verification_method: VerificationMethod::ProofVisible,
             challenge_data: Some(sanitized_challenge),
             status: ProofStatus::Active,
+            previous_urls: None,
+            id: Some(Self::new_proof_id(&company_id, proof_url.as_str())),
         };
+        let proof_id = Self::proof_id(&proof);
 
         // Sanitize and update company with social media info and permanent proof
+        let mut score_delta = 0;
         let success = StorageManager::update_company(&company_id, |company| {
-            match platform.to_lowercase().as_str() {
-                "twitter" => {
-                    // Extract and sanitize username from URL
-                    if let Some(username) = Self::extract_twitter_username(&proof_url) {
-                        let sanitized_username = Self::sanitize_social_handle(&username);
-                        if !sanitized_username.is_empty() {
-                            company.web3_identity.twitter_handle = Some(sanitized_username);
-                        }
-                    }
-                }
-                "discord" => {
-                    let sanitized_url = Self::sanitize_url(&proof_url);
-                    company.web3_identity.discord_server = Some(sanitized_url);
-                }
-                "telegram" => {
-                    let sanitized_url = Self::sanitize_url(&proof_url);
-                    company.web3_identity.telegram_channel = Some(sanitized_url);
-                }
-                _ => {}
-            }
-            
+            let previous_score = company.verification_score;
+            Self::apply_social_identity(company, &verification_type, &proof_url);
+
             // Add permanent proof
             company.web3_identity.verification_proofs.push(proof.clone());
             company.web3_identity.social_verification_status = VerificationStatus::Verified;
             company.verification_score = Self::calculate_verification_score(company);
+            score_delta = company.verification_score as i32 - previous_score as i32;
         });
 
         if success {
@@ -417,12 +598,546 @@ impl VerificationManager {
                     platform
                 ),
                 verified_at: Some(time()),
+                proof_id: Some(proof_id),
+                verification_type: Some(verification_type),
+                chain_type: None,
+                score_delta,
+                retry_after_secs: None,
+                next_action: Self::recommend_next_action(&company_id),
+                providers: None,
             })
         } else {
             RegistryResult::Err("Failed to update company".to_string())
         }
     }
 
+    fn social_platform(platform: &str) -> Option<VerificationType> {
+        match platform.to_lowercase().as_str() {
+            "twitter" => Some(VerificationType::Twitter),
+            "discord" => Some(VerificationType::Discord),
+            "telegram" => Some(VerificationType::Telegram),
+            _ => None,
+        }
+    }
+
+    fn dns_request(endpoint: &ProviderEndpoint, domain: &str) -> CanisterHttpRequestArgument {
+        CanisterHttpRequestArgument {
+            url: endpoint.url_for(domain),
+            method: HttpMethod::GET,
+            body: None,
+            max_response_bytes: Some(1024),
+            transform: Some(TransformContext::from_name(
+                "transform_domain_response".to_string(),
+                vec![],
+            )),
+            headers: vec![HttpHeader {
+                name: "Accept".to_string(),
+                value: "application/dns-json".to_string(),
+            }],
+        }
+    }
+
+    // Dry runs: the same checks and outcalls as the real verifications, reported step by
+    // step and without touching the company, the challenge or the proof records. Checks
+    // after the first failure that makes the rest meaningless are skipped.
+
+    fn preview_check(checks: &mut Vec<PreviewCheck>, check: &str, result: Result<String, String>) -> bool {
+        let passed = result.is_ok();
+        checks.push(PreviewCheck {
+            check: check.to_string(),
+            passed,
+            detail: result.unwrap_or_else(|err| err),
+        });
+        passed
+    }
+
+    fn preview_result(verification_type: VerificationType, target: String, checks: Vec<PreviewCheck>) -> ProofPreview {
+        ProofPreview {
+            verification_type,
+            target,
+            would_pass: checks.iter().all(|check| check.passed),
+            checks,
+            checked_at: time(),
+        }
+    }
+
+    // Whether verify_domain_ownership would succeed right now with the current challenge
+    pub async fn preview_domain_check(company_id: String, caller_principal: Principal) -> RegistryResult<ProofPreview> {
+        if let Err(err) = StorageManager::check_verification_rate_limit(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+        if !OwnershipManager::has_role(&company, caller_principal, CompanyRole::Verifier) {
+            return RegistryResult::Err(
+                "Unauthorized: Only company owners or verifiers can preview verifications".to_string(),
+            );
+        }
+
+        let mut checks = Vec::new();
+        let standing_ok = Self::preview_check(
+            &mut checks,
+            "company_standing",
+            AdminManager::ensure_not_suspended(&company).map(|_| "Company is not suspended".to_string()),
+        );
+        let domain = Self::extract_domain_from_url(&company.basic_info.website);
+        let domain_ok = Self::preview_check(
+            &mut checks,
+            "domain",
+            domain.clone().map(|domain| format!("Website domain is '{}'", domain)),
+        );
+        let domain = domain.ok();
+        let target = domain.clone().unwrap_or_else(|| company.basic_info.website.clone());
+
+        let challenge = StorageManager::get_domain_challenge(&company_id);
+        let challenge_result = match &challenge {
+            None => Err("No domain verification challenge found. Create one first.".to_string()),
+            Some(challenge) if time() > challenge.expires_at => {
+                Err("Challenge has expired; create a new one".to_string())
+            }
+            Some(challenge) if Some(&challenge.domain) != domain.as_ref() => Err(format!(
+                "Challenge was issued for '{}'; create a new one for the current website",
+                challenge.domain
+            )),
+            Some(challenge) => Ok(format!(
+                "Challenge valid for {} more minutes",
+                (challenge.expires_at - time()) / (60 * 1_000_000_000)
+            )),
+        };
+        let challenge_ok = Self::preview_check(&mut checks, "challenge", challenge_result);
+        let challenge = match challenge {
+            Some(challenge) if standing_ok && domain_ok && challenge_ok => challenge,
+            _ => return RegistryResult::Ok(Self::preview_result(VerificationType::Domain, target, checks)),
+        };
+
+        let build_request = |endpoint: &ProviderEndpoint| Self::dns_request(endpoint, &challenge.domain);
+        let response =
+            SettingsManager::http_request_with_failover(ApiProvider::Dns, build_request, 10_000_000_000).await;
+        let body = match response {
+            Ok((_, response)) if response.status == 200u32 => {
+                Self::preview_check(&mut checks, "dns_query", Ok("DNS resolver answered".to_string()));
+                String::from_utf8_lossy(&response.body).to_string()
+            }
+            Ok((_, response)) => {
+                Self::preview_check(
+                    &mut checks,
+                    "dns_query",
+                    Err(format!("DNS query failed with status: {}", response.status)),
+                );
+                return RegistryResult::Ok(Self::preview_result(VerificationType::Domain, target, checks));
+            }
+            Err(err) => {
+                Self::preview_check(&mut checks, "dns_query", Err(format!("DNS query request failed: {}", err)));
+                return RegistryResult::Ok(Self::preview_result(VerificationType::Domain, target, checks));
+            }
+        };
+        let token_result = if body.contains(&challenge.challenge_token) {
+            Ok(format!("TXT record with token '{}' found", challenge.challenge_token))
+        } else {
+            Err(format!(
+                "TXT record with token '{}' not found in domain '{}'; DNS changes can take a while to propagate",
+                challenge.challenge_token, challenge.domain
+            ))
+        };
+        Self::preview_check(&mut checks, "txt_record", token_result);
+        RegistryResult::Ok(Self::preview_result(VerificationType::Domain, target, checks))
+    }
+
+    // Whether a planned social post would be accepted and would pass content monitoring:
+    // the URL must be on the platform's hosts, reachable, and carry the challenge text
+    pub async fn preview_social_proof(
+        company_id: String,
+        platform: String,
+        proof_url: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<ProofPreview> {
+        if let Err(err) = StorageManager::check_http_rate_limit(caller_principal) {
+            return RegistryResult::Err(err);
+        }
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+        if !OwnershipManager::has_role(&company, caller_principal, CompanyRole::Verifier) {
+            return RegistryResult::Err(
+                "Unauthorized: Only company owners or verifiers can preview verifications".to_string(),
+            );
+        }
+        let verification_type = match Self::social_platform(&platform) {
+            Some(verification_type) => verification_type,
+            None => return RegistryResult::Err("Unsupported platform".to_string()),
+        };
+
+        let mut checks = Vec::new();
+        let standing_ok = Self::preview_check(
+            &mut checks,
+            "company_standing",
+            AdminManager::ensure_not_suspended(&company).map(|_| "Company is not suspended".to_string()),
+        );
+        let allowed_domains = Self::social_proof_domains(&verification_type).unwrap_or(&[]);
+        let parsed = SafeUrl::parse_in_domains(&proof_url, "Proof URL", allowed_domains);
+        let url_ok = Self::preview_check(
+            &mut checks,
+            "proof_url",
+            parsed
+                .as_ref()
+                .map(|url| format!("{} is an accepted {} URL", url.as_str(), platform.to_lowercase()))
+                .map_err(|err| format!("{} (allowed hosts: {})", err, allowed_domains.join(", "))),
+        );
+        let proof_url = match parsed {
+            Ok(url) if standing_ok && url_ok => url.into_string(),
+            _ => return RegistryResult::Ok(Self::preview_result(verification_type, proof_url, checks)),
+        };
+
+        let required_text = format!("ICP CrossChain Registry - Company ID: {}", company_id);
+        let request = Self::proof_request(&proof_url, Some(&required_text));
+        let response = match http_client::send(request, 20_000_000_000).await {
+            Ok(response) => response,
+            Err(err) => {
+                Self::preview_check(&mut checks, "reachable", Err(format!("Proof fetch failed: {}", err)));
+                return RegistryResult::Ok(Self::preview_result(verification_type, proof_url, checks));
+            }
+        };
+        let reachable = if response.status == 200u32 {
+            Ok("Post is publicly reachable".to_string())
+        } else {
+            Err(format!("HTTP status: {}", response.status))
+        };
+        if Self::preview_check(&mut checks, "reachable", reachable) {
+            let content = if response.body == b"proof_content_missing" {
+                Err(format!("Post does not contain \"{}\"", required_text))
+            } else {
+                Ok("Challenge text present".to_string())
+            };
+            Self::preview_check(&mut checks, "challenge_text", content);
+        }
+        RegistryResult::Ok(Self::preview_result(verification_type, proof_url, checks))
+    }
+
+    // Completed verifications as short labels, e.g. "github" or "chain:ethereum"
+    pub fn completed_badges(company_id: &str) -> Vec<String> {
+        match Self::get_verification_summary(company_id.to_string()) {
+            RegistryResult::Ok(summary) => summary
+                .items
+                .iter()
+                .filter(|item| item.state == VerificationState::Complete)
+                .filter_map(|item| match (&item.verification_type, &item.chain_type) {
+                    (Some(verification_type), _) => Some(format!("{:?}", verification_type).to_lowercase()),
+                    (None, Some(chain_type)) => Some(format!("chain:{:?}", chain_type).to_lowercase()),
+                    (None, None) => None,
+                })
+                .collect(),
+            RegistryResult::Err(_) => Vec::new(),
+        }
+    }
+
+    // Hosts a social proof of the given type may live on; None for non-social types
+    pub fn social_proof_domains(verification_type: &VerificationType) -> Option<&'static [&'static str]> {
+        match verification_type {
+            VerificationType::Twitter => Some(&["twitter.com", "x.com", "mobile.twitter.com"]),
+            VerificationType::Discord => Some(&["discord.gg", "discord.com", "discordapp.com"]),
+            VerificationType::Telegram => Some(&["t.me", "telegram.me"]),
+            VerificationType::GitHub | VerificationType::Domain => None,
+        }
+    }
+
+    // Copies the account a social proof points at onto the company's identity
+    pub fn apply_social_identity(company: &mut Company, verification_type: &VerificationType, proof_url: &SafeUrl) {
+        match verification_type {
+            VerificationType::Twitter => {
+                // The username is the first path segment (twitter.com/<user>/status/...)
+                if let Some(username) = proof_url.path_segments().first() {
+                    let sanitized_username = Handle::sanitize(username);
+                    if !sanitized_username.is_empty() {
+                        company.web3_identity.twitter_handle = Some(sanitized_username);
+                    }
+                }
+            }
+            VerificationType::Discord => {
+                company.web3_identity.discord_server = Some(proof_url.as_str().to_string());
+            }
+            VerificationType::Telegram => {
+                company.web3_identity.telegram_channel = Some(proof_url.as_str().to_string());
+            }
+            VerificationType::GitHub | VerificationType::Domain => {}
+        }
+    }
+
+    // The stable id where one has been assigned, else the legacy reference
+    pub fn proof_id(proof: &VerificationProof) -> String {
+        proof.id.clone().unwrap_or_else(|| Self::legacy_proof_id(proof))
+    }
+
+    // Proofs stored before stable ids were referenced by type and creation time, which
+    // is neither unique across companies nor kept when a proof is re-issued
+    pub fn legacy_proof_id(proof: &VerificationProof) -> String {
+        let type_name = match proof.verification_type {
+            VerificationType::GitHub => "github",
+            VerificationType::Domain => "domain",
+            VerificationType::Twitter => "twitter",
+            VerificationType::Discord => "discord",
+            VerificationType::Telegram => "telegram",
+        };
+        format!("{}_{}", type_name, proof.verified_at)
+    }
+
+    // Opaque and unique; `seed` only spreads ids issued in the same round
+    pub fn new_proof_id(company_id: &str, seed: &str) -> String {
+        let mut nonce: u32 = 0;
+        loop {
+            let mut hasher = Sha256::new();
+            hasher.update(company_id.as_bytes());
+            hasher.update([0]);
+            hasher.update(seed.as_bytes());
+            hasher.update(time().to_be_bytes());
+            hasher.update(nonce.to_be_bytes());
+            let digest = hasher.finalize();
+            let hex: String = digest[..12].iter().map(|b| format!("{:02x}", b)).collect();
+            let proof_id = format!("proof_{}", hex);
+            if StorageManager::get_proof_company_id(&proof_id).is_none() {
+                return proof_id;
+            }
+            nonce += 1;
+        }
+    }
+
+    // Matches a stable id, a legacy reference or the proof URL, so references handed
+    // out before stable ids keep working
+    pub fn proof_matches(proof: &VerificationProof, reference: &str) -> bool {
+        proof.id.as_deref() == Some(reference)
+            || Self::legacy_proof_id(proof) == reference
+            || proof.proof_url == reference
+    }
+
+    pub fn find_proof<'a>(company: &'a Company, reference: &str) -> Option<&'a VerificationProof> {
+        let proofs = &company.web3_identity.verification_proofs;
+        // An exact id match wins over a legacy reference that happens to collide
+        proofs
+            .iter()
+            .find(|proof| proof.id.as_deref() == Some(reference))
+            .or_else(|| proofs.iter().find(|proof| Self::proof_matches(proof, reference)))
+    }
+
+    // Automated proofs are re-issued on re-verification instead of accumulating; the
+    // re-issued proof keeps the id of the one it replaces. Returns the proof's id.
+    fn replace_automated_proof(company: &mut Company, mut proof: VerificationProof) -> String {
+        let replaces = |existing: &VerificationProof, proof: &VerificationProof| {
+            matches!(existing.verification_method, VerificationMethod::Automated)
+                && std::mem::discriminant(&existing.verification_type)
+                    == std::mem::discriminant(&proof.verification_type)
+        };
+        let proofs = &mut company.web3_identity.verification_proofs;
+        if let Some(previous) = proofs.iter().find(|existing| replaces(existing, &proof)) {
+            proof.id = previous.id.clone().or(proof.id);
+        }
+        proofs.retain(|existing| !replaces(existing, &proof));
+        let proof_id = Self::proof_id(&proof);
+        proofs.push(proof);
+        proof_id
+    }
+
+    // Public lookup of a proof by its stable id
+    pub fn resolve_proof(proof_id: String) -> Option<ResolvedProof> {
+        let company_id = StorageManager::get_proof_company_id(&proof_id)?;
+        let company = StorageManager::get_company(&company_id)?;
+        let proof = company
+            .web3_identity
+            .verification_proofs
+            .iter()
+            .find(|proof| proof.id.as_deref() == Some(proof_id.as_str()))?
+            .clone();
+        let last_checked = StorageManager::get_proof_monitoring(&company_id, &proof_id)
+            .map(|monitoring| monitoring.last_checked)
+            .filter(|checked_at| *checked_at > 0);
+        Some(ResolvedProof {
+            proof_id,
+            company_id,
+            company_name: company.basic_info.name,
+            company_status: company.status,
+            status: proof.status.clone(),
+            proof,
+            last_checked,
+        })
+    }
+
+    // Suggest the verification step that would add the most to a company's profile
+    pub fn recommend_next_action(company_id: &str) -> Option<String> {
+        let company = StorageManager::get_company(company_id)?;
+        let identity = &company.web3_identity;
+        let presence = &company.cross_chain_presence;
+
+        if !identity.domain_verified {
+            Some("Verify domain ownership with create_domain_verification_challenge".to_string())
+        } else if identity.github_org.is_none() {
+            Some("Verify your GitHub organization with verify_github_organization".to_string())
+        } else if !identity
+            .verification_proofs
+            .iter()
+            .any(|p| matches!(p.verification_method, VerificationMethod::ProofVisible))
+        {
+            Some("Add a permanent social media proof with verify_social_media_with_proof".to_string())
+        } else if presence.ethereum_contracts.is_empty()
+            && presence.bitcoin_addresses.is_empty()
+            && presence.icp_canisters.is_empty()
+        {
+            Some("Verify a cross-chain address with create_crosschain_challenge".to_string())
+        } else {
+            None
+        }
+    }
+
+    // Per-company overview of every verification's state and score contribution
+    pub fn get_verification_summary(company_id: String) -> RegistryResult<VerificationSummary> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+
+        let now = time();
+        let weights = Self::score_weights(&company.entity_type.clone().unwrap_or_default());
+        let contributions = Self::verification_contributions(&company, &weights);
+        let identity = &company.web3_identity;
+        let mut social_points_assigned = false;
+        let mut items = Vec::new();
+
+        // Identity verifications, backed by proofs and domain challenges
+        for verification_type in [
+            VerificationType::GitHub,
+            VerificationType::Domain,
+            VerificationType::Twitter,
+            VerificationType::Discord,
+            VerificationType::Telegram,
+        ] {
+            let proofs: Vec<&VerificationProof> = identity
+                .verification_proofs
+                .iter()
+                .filter(|p| {
+                    std::mem::discriminant(&p.verification_type)
+                        == std::mem::discriminant(&verification_type)
+                })
+                .collect();
+
+            let proof_state = if proofs.iter().any(|p| p.status == ProofStatus::Active) {
+                VerificationState::Complete
+            } else if !proofs.is_empty() {
+                VerificationState::Failed
+            } else {
+                VerificationState::NotStarted
+            };
+
+            let mut open_challenges = 0;
+            let mut challenge_expires_at = None;
+            let (state, score_contribution) = match verification_type {
+                VerificationType::GitHub => {
+                    (proof_state, contributions.github)
+                }
+                VerificationType::Domain => {
+                    let challenge = StorageManager::get_domain_challenge(&company_id);
+                    let state = if identity.domain_verified {
+                        VerificationState::Complete
+                    } else {
+                        match challenge {
+                            Some(challenge) if challenge.expires_at > now => {
+                                open_challenges = 1;
+                                challenge_expires_at = Some(challenge.expires_at);
+                                VerificationState::Pending
+                            }
+                            Some(_) => VerificationState::Expired,
+                            None => proof_state,
+                        }
+                    };
+                    (state, contributions.domain)
+                }
+                _ => {
+                    // The social score is shared, so credit it to the first completed platform
+                    let points = if contributions.social > 0
+                        && !social_points_assigned
+                        && proof_state == VerificationState::Complete
+                    {
+                        social_points_assigned = true;
+                        contributions.social
+                    } else {
+                        0
+                    };
+                    (proof_state, points)
+                }
+            };
+
+            items.push(VerificationSummaryItem {
+                verification_type: Some(verification_type),
+                chain_type: None,
+                state,
+                score_contribution,
+                proof_ids: proofs.iter().map(|p| Self::proof_id(p)).collect(),
+                open_challenges,
+                challenge_expires_at,
+            });
+        }
+
+        // GitHub verification also marks social status as verified
+        if !social_points_assigned {
+            items[0].score_contribution = items[0].score_contribution.saturating_add(contributions.social);
+        }
+
+        // Cross-chain verifications, backed by addresses and open challenges
+        let challenges = StorageManager::get_crosschain_challenges_for_company(&company_id);
+        let presence = &company.cross_chain_presence;
+        let chain_points = contributions.chains.iter().map(|(_, points)| *points);
+        for ((chain_type, addresses), points) in Self::chain_addresses(presence).into_iter().zip(chain_points) {
+            let chain_challenges: Vec<&CrossChainChallenge> = challenges
+                .iter()
+                .filter(|c| std::mem::discriminant(&c.chain_type) == std::mem::discriminant(&chain_type))
+                .collect();
+            let open: Vec<u64> = chain_challenges
+                .iter()
+                .filter(|c| c.expires_at > now)
+                .map(|c| c.expires_at)
+                .collect();
+
+            let state = if !addresses.is_empty() {
+                VerificationState::Complete
+            } else if !open.is_empty() {
+                VerificationState::Pending
+            } else if !chain_challenges.is_empty() {
+                VerificationState::Expired
+            } else {
+                VerificationState::NotStarted
+            };
+
+            let chain_name = CrossChainVerifier::chain_name(&chain_type);
+            items.push(VerificationSummaryItem {
+                verification_type: None,
+                chain_type: Some(chain_type),
+                state,
+                score_contribution: points,
+                proof_ids: addresses
+                    .iter()
+                    .map(|address| format!("{}_{}", chain_name, address))
+                    .collect(),
+                open_challenges: open.len() as u32,
+                challenge_expires_at: open.iter().min().copied(),
+            });
+        }
+
+        let count = |state: VerificationState| items.iter().filter(|i| i.state == state).count() as u32;
+        let verification_points = contributions.total();
+
+        RegistryResult::Ok(VerificationSummary {
+            company_id,
+            verification_score: company.verification_score,
+            profile_score: company.verification_score.saturating_sub(verification_points),
+            treasury_contribution: contributions.treasury,
+            token_contribution: contributions.tokens,
+            complete_count: count(VerificationState::Complete),
+            pending_count: count(VerificationState::Pending),
+            failed_count: count(VerificationState::Failed),
+            expired_count: count(VerificationState::Expired),
+            items,
+        })
+    }
+
     // Legacy method for backward compatibility
     pub fn verify_social_media_manual(
         company_id: String,
@@ -484,68 +1199,6 @@ impl VerificationManager {
         }
     }
 
-    // Get personalized verification instructions with specific company ID
-    pub fn get_verification_instructions_with_company_id(
-        verification_type: VerificationType,
-        company_id: &str,
-    ) -> String {
-        let required_text = format!("ICP CrossChain Registry - Company ID: {}", company_id);
-        
-        match verification_type {
-            VerificationType::GitHub => {
-                "To verify your GitHub organization:\n\
-                1. Ensure your organization has at least 1 public repository\n\
-                2. Call verify_github_organization with your company ID and organization name\n\
-                3. The system will verify the organization exists and has activity"
-                    .to_string()
-            }
-            VerificationType::Domain => {
-                "To verify domain ownership:\n\
-                1. Call create_domain_verification_challenge with your company ID\n\
-                2. Add the provided challenge token as a TXT record to your domain's DNS\n\
-                3. Call verify_domain_ownership to complete verification\n\
-                4. TXT record format: 'icp-registry-verification=<token>'"
-                    .to_string()
-            }
-            VerificationType::Twitter => {
-                format!(
-                    "🐦 Twitter Verification (Permanent Proof Required):\n\
-                    1. Create a PUBLIC tweet with this exact text: '{}'\n\
-                    2. Add your company description and why you're joining the registry\n\
-                    3. Pin the tweet to your profile (recommended)\n\
-                    4. Call verify_social_media_with_proof with the tweet URL\n\
-                    ⚠️  WARNING: Deleting this tweet after verification will flag your company as suspicious\n\
-                    ✅ This tweet will be permanently linked to your company profile for transparency",
-                    required_text
-                )
-            }
-            VerificationType::Discord => {
-                format!(
-                    "💬 Discord Verification (Permanent Proof Required):\n\
-                    1. Create a public channel post with this exact text: '{}'\n\
-                    2. Include your server invite link and company details\n\
-                    3. Pin the message in your announcements channel\n\
-                    4. Call verify_social_media_with_proof with the message URL\n\
-                    ⚠️  WARNING: Deleting this message will trigger community review\n\
-                    ✅ This message link will be permanently displayed on your company profile",
-                    required_text
-                )
-            }
-            VerificationType::Telegram => {
-                format!(
-                    "📱 Telegram Verification (Permanent Proof Required):\n\
-                    1. Post in your public channel with this exact text: '{}'\n\
-                    2. Include channel description and company information\n\
-                    3. Pin the message to your channel\n\
-                    4. Call verify_social_media_with_proof with the message URL\n\
-                    ⚠️  WARNING: Removing this message will result in verification loss\n\
-                    ✅ This message will be permanently accessible via your company profile",
-                    required_text
-                )
-            }
-        }
-    }
-
     // Automated proof monitoring system
     pub async fn verify_proof_still_exists(
         company_id: String,
@@ -553,28 +1206,68 @@ impl VerificationManager {
         checker_principal: Principal,
     ) -> RegistryResult<ProofCheckResult> {
         // Check rate limiting first
-        if !StorageManager::check_http_rate_limit(checker_principal) {
-            return RegistryResult::Err("Rate limit exceeded. Please try again later.".to_string());
+        if let Err(err) = StorageManager::check_http_rate_limit(checker_principal) {
+            return RegistryResult::Err(err);
         }
+        Self::check_proof(company_id, proof_url, checker_principal).await
+    }
 
-        // Make HTTP request to check if the proof still exists
-        let request = CanisterHttpRequestArgument {
-            url: proof_url.clone(),
-            method: HttpMethod::GET,
-            body: None,
-            max_response_bytes: Some(4096),
-            transform: Some(TransformContext::from_name(
-                "transform_proof_check".to_string(),
-                vec![],
-            )),
-            headers: vec![HttpHeader {
-                name: "User-Agent".to_string(),
-                value: "ICP-CrossChainRegistry-ProofChecker/1.0".to_string(),
-            }],
+    // Lets an owner who put a deleted post back clear the Removed status without waiting
+    // for someone to check it. The check runs from a zero-delay timer, ahead of any
+    // periodic work, and the outcome arrives as a notification.
+    pub fn request_proof_recheck(
+        company_id: String,
+        proof_id: String,
+        caller_principal: Principal,
+    ) -> RegistryResult<String> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
         };
+        if !OwnershipManager::has_role(&company, caller_principal, CompanyRole::Verifier) {
+            return RegistryResult::Err("Unauthorized: Only company owners or verifiers can request re-checks".to_string());
+        }
+        if let Err(err) = AdminManager::ensure_not_suspended(&company) {
+            return RegistryResult::Err(err);
+        }
+        let (proof_id, proof_url) = match Self::find_proof(&company, &proof_id) {
+            Some(proof) => (Self::proof_id(proof), proof.proof_url.clone()),
+            None => return RegistryResult::Err("Proof not found".to_string()),
+        };
+
+        let recheck_key = format!("{}:{}", company_id, proof_id);
+        if !StorageManager::try_begin_proof_recheck(&recheck_key) {
+            return RegistryResult::Err("A re-check of this proof is already pending".to_string());
+        }
+        if let Err(err) = StorageManager::check_verification_rate_limit(caller_principal) {
+            StorageManager::end_proof_recheck(&recheck_key);
+            return RegistryResult::Err(err);
+        }
+
+        ic_cdk_timers::set_timer(Duration::ZERO, move || {
+            ic_cdk::spawn(async move {
+                let message = match Self::check_proof(company_id.clone(), proof_url, caller_principal).await {
+                    RegistryResult::Ok(result) => format!(
+                        "Re-check of proof {} finished: {:?} ({})",
+                        proof_id, result.status_found, result.notes
+                    ),
+                    RegistryResult::Err(err) => format!("Re-check of proof {} failed: {}", proof_id, err),
+                };
+                StorageManager::end_proof_recheck(&recheck_key);
+                NotificationManager::notify(caller_principal, &company_id, None, message);
+            });
+        });
+        RegistryResult::Ok("Re-check scheduled; the result will arrive as a notification".to_string())
+    }
 
-        match http_request(request, 10_000_000_000).await {
-            Ok((response,)) => {
+    pub async fn check_proof(
+        company_id: String,
+        proof_url: String,
+        checker_principal: Principal,
+    ) -> RegistryResult<ProofCheckResult> {
+        // Make HTTP request to check if the proof still exists
+        match http_client::send(Self::proof_request(&proof_url, None), 10_000_000_000).await {
+            Ok(response) => {
                 let status = if response.status == 200u32 {
                     ProofStatus::Active
                 } else if response.status == 404u32 {
@@ -582,37 +1275,222 @@ impl VerificationManager {
                 } else {
                     ProofStatus::Disputed
                 };
+                Self::apply_proof_status(&company_id, &proof_url, &status, checker_principal);
+
+                RegistryResult::Ok(Self::record_check(
+                    &company_id,
+                    &proof_url,
+                    ProofCheckResult {
+                        checker_principal,
+                        timestamp: time(),
+                        status_found: status,
+                        notes: format!("HTTP status: {}", response.status),
+                    },
+                ))
+            }
+            Err(err) => RegistryResult::Err(format!("Proof check failed: {}", err)),
+        }
+    }
 
-                // Update company verification status if proof was removed
-                if status == ProofStatus::Removed {
-                    StorageManager::update_company(&company_id, |company| {
-                        for proof in company.web3_identity.verification_proofs.iter_mut() {
-                            if proof.proof_url == proof_url {
-                                proof.status = ProofStatus::Removed;
-                            }
-                        }
-                        // Reduce verification score for removed proofs
-                        company.verification_score = Self::calculate_verification_score(company);
-                    });
+    pub async fn validate_proof_content(
+        company_id: String,
+        proof_url: String,
+        checker_principal: Principal,
+    ) -> RegistryResult<ProofCheckResult> {
+        if let Err(err) = StorageManager::check_http_rate_limit(checker_principal) {
+            return RegistryResult::Err(err);
+        }
+        Self::check_proof_content(company_id, proof_url, checker_principal).await
+    }
+
+    // Fetches a social proof and confirms the challenge text is still in the post. A post
+    // that is still up but no longer carries the text is downgraded to Disputed: editing
+    // the text out keeps the link alive while withdrawing the claim.
+    pub async fn check_proof_content(
+        company_id: String,
+        proof_url: String,
+        checker_principal: Principal,
+    ) -> RegistryResult<ProofCheckResult> {
+        let company = match StorageManager::get_company(&company_id) {
+            Some(company) => company,
+            None => return RegistryResult::Err("Company not found".to_string()),
+        };
+        match company.web3_identity.verification_proofs.iter().find(|proof| proof.proof_url == proof_url) {
+            Some(proof) if Self::social_proof_domains(&proof.verification_type).is_some() => {}
+            Some(_) => return RegistryResult::Err("Only social media proofs carry challenge text".to_string()),
+            None => return RegistryResult::Err("Proof not found".to_string()),
+        }
+
+        let required_text = format!("ICP CrossChain Registry - Company ID: {}", company_id);
+        let request = Self::proof_request(&proof_url, Some(&required_text));
+        let response = match http_client::send(request, 20_000_000_000).await {
+            Ok(response) => response,
+            Err(err) => return RegistryResult::Err(format!("Proof check failed: {}", err)),
+        };
+
+        let content_missing = response.status == 200u32 && response.body == b"proof_content_missing";
+        let (status, notes) = if content_missing {
+            (ProofStatus::Disputed, "Post is up but the challenge text is gone".to_string())
+        } else if response.status == 200u32 {
+            (ProofStatus::Active, "Challenge text present".to_string())
+        } else if response.status == 404u32 {
+            (ProofStatus::Removed, format!("HTTP status: {}", response.status))
+        } else {
+            (ProofStatus::Disputed, format!("HTTP status: {}", response.status))
+        };
+
+        if content_missing {
+            // Only a live proof is downgraded; a Removed one stays Removed until the
+            // post is back with its text
+            let mut newly_disputed = None;
+            StorageManager::update_company(&company_id, |company| {
+                for proof in company.web3_identity.verification_proofs.iter_mut() {
+                    if proof.proof_url == proof_url && proof.status == ProofStatus::Active {
+                        proof.status = ProofStatus::Disputed;
+                        newly_disputed = Some(Self::proof_id(proof));
+                    }
                 }
+                if newly_disputed.is_some() {
+                    company.verification_score = Self::calculate_verification_score(company);
+                }
+            });
+            if let Some(proof_id) = newly_disputed {
+                NotificationManager::notify(
+                    company.created_by,
+                    &company_id,
+                    None,
+                    format!(
+                        "Proof {} at {} no longer contains \"{}\" and has been marked Disputed",
+                        proof_id, proof_url, required_text
+                    ),
+                );
+            }
+        } else {
+            Self::apply_proof_status(&company_id, &proof_url, &status, checker_principal);
+        }
 
-                let result = ProofCheckResult {
-                    checker_principal,
-                    timestamp: time(),
-                    status_found: status.clone(),
-                    notes: format!("HTTP status: {}", response.status),
-                };
+        RegistryResult::Ok(Self::record_check(
+            &company_id,
+            &proof_url,
+            ProofCheckResult {
+                checker_principal,
+                timestamp: time(),
+                status_found: status,
+                notes,
+            },
+        ))
+    }
+
+    // With required text, the transform reads the page for it; otherwise only the status
+    // matters and the body is kept small
+    fn proof_request(proof_url: &str, required_text: Option<&str>) -> CanisterHttpRequestArgument {
+        CanisterHttpRequestArgument {
+            url: proof_url.to_string(),
+            method: HttpMethod::GET,
+            body: None,
+            max_response_bytes: Some(if required_text.is_some() { 256 * 1024 } else { 4096 }),
+            transform: Some(TransformContext::from_name(
+                "transform_proof_check".to_string(),
+                required_text.map(|text| text.as_bytes().to_vec()).unwrap_or_default(),
+            )),
+            headers: vec![HttpHeader {
+                name: "User-Agent".to_string(),
+                value: "ICP-CrossChainRegistry-ProofChecker/1.0".to_string(),
+            }],
+        }
+    }
 
-                RegistryResult::Ok(result)
+    fn apply_proof_status(company_id: &str, proof_url: &str, status: &ProofStatus, checker_principal: Principal) {
+        // Update company verification status if proof was removed
+        if *status == ProofStatus::Removed {
+            let mut newly_removed = None;
+            StorageManager::update_company(company_id, |company| {
+                for proof in company.web3_identity.verification_proofs.iter_mut() {
+                    if proof.proof_url == proof_url && proof.status != ProofStatus::Removed {
+                        proof.status = ProofStatus::Removed;
+                        newly_removed = Some(proof.id.clone());
+                    }
+                }
+                // Reduce verification score for removed proofs
+                company.verification_score = Self::calculate_verification_score(company);
+            });
+            if let Some(proof_id) = newly_removed {
+                ReputationJournal::record(
+                    company_id,
+                    ReputationEventKind::ProofRemoved { proof_url: proof_url.to_string(), proof_id },
+                    Some(checker_principal),
+                );
+            }
+        }
+
+        // A proof that is still up counts as the company being looked after, and one
+        // that was put back after removal gets its status and score back
+        if *status == ProofStatus::Active {
+            let mut restored = None;
+            StorageManager::update_company(company_id, |company| {
+                for proof in company.web3_identity.verification_proofs.iter_mut() {
+                    if proof.proof_url == proof_url && proof.status == ProofStatus::Removed {
+                        proof.status = ProofStatus::Active;
+                        restored = Some(proof.id.clone());
+                    }
+                }
+                if restored.is_some() {
+                    company.verification_score = Self::calculate_verification_score(company);
+                }
+                if company.web3_identity.verification_proofs.iter().any(|p| p.proof_url == proof_url) {
+                    InactivityManager::mark_active(company);
+                }
+            });
+            if let Some(proof_id) = restored {
+                ReputationJournal::record(
+                    company_id,
+                    ReputationEventKind::ProofRestored { proof_url: proof_url.to_string(), proof_id },
+                    Some(checker_principal),
+                );
             }
-            Err(err) => RegistryResult::Err(format!("Proof check failed: {:?}", err)),
         }
     }
 
+    // Appends to the proof's check history, keeping the newest entries
+    fn record_check(company_id: &str, proof_url: &str, result: ProofCheckResult) -> ProofCheckResult {
+        let proof_id = match StorageManager::get_company(company_id).and_then(|company| {
+            company
+                .web3_identity
+                .verification_proofs
+                .iter()
+                .find(|proof| proof.proof_url == proof_url)
+                .map(Self::proof_id)
+        }) {
+            Some(proof_id) => proof_id,
+            None => return result,
+        };
+        let mut monitoring = StorageManager::get_proof_monitoring(company_id, &proof_id).unwrap_or(ProofMonitoring {
+            proof_id,
+            company_id: company_id.to_string(),
+            last_checked: 0,
+            check_results: Vec::new(),
+            community_reports: Vec::new(),
+        });
+        monitoring.last_checked = result.timestamp;
+        monitoring.check_results.push(result.clone());
+        if monitoring.check_results.len() > Self::MAX_CHECK_RESULTS {
+            monitoring.check_results.remove(0);
+        }
+        StorageManager::insert_proof_monitoring(monitoring);
+        result
+    }
+
+    pub fn get_proof_monitoring(company_id: String, proof_id: String) -> Option<ProofMonitoring> {
+        let company = StorageManager::get_company(&company_id)?;
+        let proof = Self::find_proof(&company, &proof_id)?;
+        StorageManager::get_proof_monitoring(&company_id, &Self::proof_id(proof))
+    }
+
     // Community reporting for suspicious verification proofs
+    // `proof_id` is the proof's stable id; proof URLs are still accepted
     pub fn report_verification_issue(
         company_id: String,
-        proof_url: String,
+        proof_id: String,
         report_type: ReportType,
         evidence: String,
         reporter_principal: Principal,
@@ -623,89 +1501,49 @@ impl VerificationManager {
             None => return RegistryResult::Err("Company not found".to_string()),
         };
 
-        // Validate that the proof URL exists for this company
-        let proof_exists = company
-            .web3_identity
-            .verification_proofs
-            .iter()
-            .any(|proof| proof.proof_url == proof_url);
-
-        if !proof_exists {
-            return RegistryResult::Err("Verification proof not found for this company".to_string());
-        }
+        // Validate that the proof exists for this company
+        let (proof_id, proof_url) = match Self::find_proof(&company, &proof_id) {
+            Some(proof) => (Self::proof_id(proof), proof.proof_url.clone()),
+            None => return RegistryResult::Err("Verification proof not found for this company".to_string()),
+        };
 
         // Create community report
-        let _report = CommunityReport {
+        let report = CommunityReport {
             reporter_principal,
             report_type,
             evidence,
             timestamp: time(),
         };
 
-        // In a full implementation, this would be stored in a separate monitoring storage
-        // For now, we'll return success - the storage integration would be added later
-        
-        RegistryResult::Ok(format!(
-            "Report submitted successfully. Community moderators will review the verification proof at: {}",
-            proof_url
-        ))
-    }
-
-    // Secure URL validation with domain whitelisting
-    fn validate_secure_url(url: &str, allowed_domains: &[&str]) -> Result<(), String> {
-        // Basic HTTPS requirement
-        if !url.starts_with("https://") {
-            return Err("URL must use HTTPS protocol".to_string());
-        }
-
-        // Length validation to prevent resource exhaustion
-        if url.len() > 2048 {
-            return Err("URL exceeds maximum length".to_string());
-        }
-
-        // Extract hostname safely
-        let url_without_protocol = url.strip_prefix("https://")
-            .ok_or("Invalid URL format")?;
-        
-        let hostname = url_without_protocol
-            .split('/')
-            .next()
-            .ok_or("Cannot extract hostname")?
-            .split('?')
-            .next()
-            .unwrap_or("")
-            .split('#')
-            .next()
-            .unwrap_or("")
-            .to_lowercase();
-
-        // Check for homograph attacks (non-ASCII characters)
-        if !hostname.chars().all(|c| c.is_ascii()) {
-            return Err("Non-ASCII characters in domain not allowed".to_string());
-        }
+        // Queue the report for moderators
+        DisputeManager::open_dispute(
+            &company_id,
+            &proof_id,
+            format!("{:?} ({}): {}", report.report_type, proof_url, report.evidence),
+        );
 
-        // Domain whitelist validation
-        let is_valid_domain = allowed_domains.iter().any(|&domain| {
-            hostname == domain || hostname.ends_with(&format!(".{}", domain))
+        // Kept with the proof's check history
+        let mut monitoring = StorageManager::get_proof_monitoring(&company_id, &proof_id).unwrap_or(ProofMonitoring {
+            proof_id: proof_id.clone(),
+            company_id: company_id.clone(),
+            last_checked: 0,
+            check_results: Vec::new(),
+            community_reports: Vec::new(),
         });
-
-        if !is_valid_domain {
-            return Err(format!(
-                "URL must be from authorized domains: {}",
-                allowed_domains.join(", ")
-            ));
+        monitoring.community_reports.push(report);
+        if monitoring.community_reports.len() > Self::MAX_COMMUNITY_REPORTS {
+            monitoring.community_reports.remove(0);
         }
+        StorageManager::insert_proof_monitoring(monitoring);
 
-        // Additional security checks
-        if hostname.contains("..") || hostname.contains("--") {
-            return Err("Suspicious hostname pattern detected".to_string());
-        }
-
-        Ok(())
+        RegistryResult::Ok(format!(
+            "Report submitted successfully. Community moderators will review proof {} at: {}",
+            proof_id, proof_url
+        ))
     }
 
     // Helper functions
-    fn generate_challenge_token() -> String {
+    pub fn generate_challenge_token() -> String {
         // Use cryptographically secure token generation
         let timestamp = time();
         
@@ -717,13 +1555,13 @@ impl VerificationManager {
         // Fill entropy with pseudo-random but unpredictable values
         // In production, this should use ic_cdk::api::management_canister::main::raw_rand()
         // For now, we'll use a more secure PRNG based on system state
-        for i in 0..32 {
+        for (i, byte) in entropy.iter_mut().enumerate() {
             let value = random_seed
                 .wrapping_mul(0x41c64e6d)
                 .wrapping_add(0x3039)
                 .wrapping_add(i as u64)
                 .wrapping_mul(timestamp);
-            entropy[i] = (value >> (8 * (i % 8))) as u8;
+            *byte = (value >> (8 * (i % 8))) as u8;
         }
         
         // Create secure token from entropy
@@ -748,66 +1586,14 @@ impl VerificationManager {
     //     Ok(format!("icp-registry-{}-{}", timestamp, token_hex))
     // }
 
-    // Input sanitization functions
-    fn sanitize_url(url: &str) -> String {
-        // Remove potentially dangerous characters while preserving URL structure
-        url.chars()
-            .filter(|&c| {
-                c.is_ascii_alphanumeric() 
-                || matches!(c, '.' | '/' | ':' | '-' | '_' | '?' | '=' | '&' | '#')
-            })
-            .take(500) // Limit length
-            .collect()
-    }
-
-    fn sanitize_social_handle(handle: &str) -> String {
-        // Remove @ prefix and sanitize handle
-        let clean_handle = handle.trim_start_matches('@');
-        
-        // Only allow alphanumeric, underscore, and hyphen
-        clean_handle
-            .chars()
-            .filter(|&c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-'))
-            .take(50) // Limit length
-            .collect()
-    }
-
-    fn sanitize_challenge_data(data: &str) -> String {
-        // Sanitize challenge text to prevent injection
-        data.chars()
-            .filter(|&c| {
-                c.is_ascii_alphanumeric() 
-                || matches!(c, ' ' | '-' | ':' | '.' | '_')
-            })
-            .take(200) // Limit length
-            .collect()
-    }
-
     // Safe regex compilation utility
     fn safe_regex_new(pattern: &str) -> Result<Regex, String> {
         Regex::new(pattern).map_err(|e| format!("Regex compilation error: {}", e))
     }
 
-    fn extract_domain_from_url(url: &str) -> Result<String, String> {
-        let url_regex = Self::safe_regex_new(r"^https?://([^/]+)")?;
-        if let Some(captures) = url_regex.captures(url) {
-            if let Some(domain) = captures.get(1) {
-                Ok(domain.as_str().to_string())
-            } else {
-                Err("Invalid URL format".to_string())
-            }
-        } else {
-            Err("Invalid URL format".to_string())
-        }
-    }
-
-    fn extract_twitter_username(url: &str) -> Option<String> {
-        let twitter_regex = Self::safe_regex_new(r"(?:twitter\.com|x\.com)/([^/?]+)").ok()?;
-        if let Some(captures) = twitter_regex.captures(url) {
-            captures.get(1).map(|m| m.as_str().to_string())
-        } else {
-            None
-        }
+    // Lowercase ASCII host of an http(s) URL
+    pub fn extract_domain_from_url(url: &str) -> Result<String, String> {
+        SafeUrl::parse_web(url, "URL").map(|url| url.host().to_string())
     }
 
     // Cross-chain address validation functions
@@ -892,16 +1678,8 @@ impl VerificationManager {
 
     // Comprehensive cross-chain address validation
     pub fn validate_cross_chain_address(chain: &str, address: &str) -> bool {
-        match chain.to_lowercase().as_str() {
-            "bitcoin" | "btc" => Self::validate_bitcoin_address(address),
-            "ethereum" | "eth" => Self::validate_ethereum_address(address),
-            "solana" | "sol" => Self::validate_solana_address(address),
-            "sui" => Self::validate_sui_address(address),
-            "ton" => Self::validate_ton_address(address),
-            "icp" | "internet_computer" => Self::validate_icp_principal(address),
-            "polygon" | "matic" => Self::validate_polygon_address(address),
-            _ => false,
-        }
+        CrossChainVerifier::chain_from_name(chain)
+            .is_some_and(|chain_type| CrossChainVerifier::validate_address_format(&chain_type, address).is_ok())
     }
 
     // Get validation rules for different chains
@@ -1025,8 +1803,15 @@ pub fn transform_proof_check(raw: TransformArgs) -> HttpResponse {
         },
     ];
 
-    // Only return status and minimal body for proof checking
-    let minimal_body = if raw.response.status == 200u32 {
+    // Only return status and minimal body for proof checking. The context carries the
+    // challenge text when content is being validated; a page without it is reported as such.
+    let required_text = String::from_utf8_lossy(&raw.context).to_lowercase();
+    let minimal_body = if raw.response.status == 200u32
+        && !required_text.is_empty()
+        && !String::from_utf8_lossy(&raw.response.body).to_lowercase().contains(&required_text)
+    {
+        b"proof_content_missing".to_vec()
+    } else if raw.response.status == 200u32 {
         b"proof_exists".to_vec()
     } else if raw.response.status == 404u32 {
         b"proof_not_found".to_vec()
@@ -0,0 +1,120 @@
+use crate::chain_stats::ChainStatsManager;
+use crate::security::SecurityLog;
+use crate::storage::StorageManager;
+use crate::types::{RegistryResult, SecurityEventType, VerificationResult};
+use candid::Principal;
+use ic_cdk::api::time;
+use std::future::Future;
+
+// Escalating cooldowns for repeated failed verifications of the same target by the same
+// principal, on top of the plain rate limits. A couple of misses are free (typos happen);
+// after that each failure doubles the wait, and a long streak locks the target out for a
+// day. Any success clears the streak.
+
+pub struct VerificationGuard;
+
+impl VerificationGuard {
+    const FREE_FAILURES: u32 = 2;
+    const BASE_COOLDOWN_NS: u64 = 30_000_000_000; // 30 seconds
+    const MAX_COOLDOWN_NS: u64 = 60 * 60 * 1_000_000_000; // 1 hour
+    const LOCKOUT_AFTER_FAILURES: u32 = 8;
+    const LOCKOUT_NS: u64 = 24 * 60 * 60 * 1_000_000_000; // 24 hours
+    // A streak older than this is forgotten
+    const STREAK_RESET_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+    // e.g. "ethereum:company_123:0xabc..."
+    pub fn target(kind: &str, company_id: &str, subject: &str) -> String {
+        format!("{}:{}:{}", kind, company_id, subject.trim().to_lowercase())
+    }
+
+    // Streaks live in stable memory; the usage timer calls this to drop the forgotten ones
+    pub fn prune_failures() {
+        StorageManager::prune_verification_failures(time().saturating_sub(Self::STREAK_RESET_NS));
+    }
+
+    pub fn remaining_cooldown_secs(principal: Principal, target: &str) -> Option<u64> {
+        let now = time();
+        StorageManager::get_verification_failures(principal, target)
+            .filter(|record| record.blocked_until > now)
+            .map(|record| (record.blocked_until - now).div_ceil(1_000_000_000))
+    }
+
+    // Runs `attempt` unless the target is cooling down, and books the outcome. Errors count
+    // as failures too: probing someone else's company mostly ends in one.
+    pub async fn run(
+        principal: Principal,
+        target: String,
+        attempt: impl Future<Output = RegistryResult<VerificationResult>>,
+    ) -> RegistryResult<VerificationResult> {
+        if let Some(retry_after_secs) = Self::remaining_cooldown_secs(principal, &target) {
+            return RegistryResult::Ok(Self::cooling_down_result(retry_after_secs));
+        }
+
+        let mut result = attempt.await;
+        match &mut result {
+            RegistryResult::Ok(outcome) if outcome.success => {
+                StorageManager::clear_verification_failures(principal, &target);
+            }
+            RegistryResult::Ok(outcome) => {
+                outcome.retry_after_secs = Self::record_failure(principal, &target);
+            }
+            RegistryResult::Err(_) => {
+                Self::record_failure(principal, &target);
+            }
+        }
+        ChainStatsManager::record_verification(&target, matches!(&result, RegistryResult::Ok(outcome) if outcome.success));
+        result
+    }
+
+    // Returns the cooldown now in force, if any
+    fn record_failure(principal: Principal, target: &str) -> Option<u64> {
+        let now = time();
+        // Records wait for the next prune, so an old streak is skipped here instead
+        let mut record = StorageManager::get_verification_failures(principal, target)
+            .filter(|record| record.last_failure_at >= now.saturating_sub(Self::STREAK_RESET_NS))
+            .unwrap_or_default();
+        record.consecutive += 1;
+        record.last_failure_at = now;
+
+        let cooldown = if record.consecutive >= Self::LOCKOUT_AFTER_FAILURES {
+            if record.consecutive == Self::LOCKOUT_AFTER_FAILURES {
+                SecurityLog::record(
+                    principal,
+                    SecurityEventType::VerificationLockout,
+                    target.split(':').nth(1).map(|id| id.to_string()),
+                    format!("{} consecutive failures on {}", record.consecutive, target),
+                );
+            }
+            Self::LOCKOUT_NS
+        } else if record.consecutive > Self::FREE_FAILURES {
+            let doublings = record.consecutive - Self::FREE_FAILURES - 1;
+            Self::BASE_COOLDOWN_NS
+                .saturating_mul(1u64 << doublings.min(16))
+                .min(Self::MAX_COOLDOWN_NS)
+        } else {
+            0
+        };
+        record.blocked_until = now.saturating_add(cooldown);
+        StorageManager::set_verification_failures(principal, target, record);
+
+        (cooldown > 0).then_some(cooldown / 1_000_000_000)
+    }
+
+    fn cooling_down_result(retry_after_secs: u64) -> VerificationResult {
+        VerificationResult {
+            success: false,
+            message: format!(
+                "Too many failed attempts for this target; try again in {} seconds",
+                retry_after_secs
+            ),
+            verified_at: None,
+            proof_id: None,
+            verification_type: None,
+            chain_type: None,
+            score_delta: 0,
+            retry_after_secs: Some(retry_after_secs),
+            next_action: Some("Fix the published proof before retrying".to_string()),
+            providers: None,
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use crate::types::{
+    VetKdCurve, VetKdDeriveKeyArgs, VetKdDeriveKeyResult, VetKdKeyId, VetKdPublicKeyArgs,
+    VetKdPublicKeyResult,
+};
+use candid::Principal;
+use ic_cdk::api::call::call_with_payment128;
+
+// Thin wrapper over the management canister's vetKD endpoints, which ic-cdk 0.17 doesn't
+// bind. Keys are deterministic in (canister, context, input), so anything encrypted under
+// them stays readable across upgrades. Callers decide who may ask for a key; the key
+// leaves the canister only encrypted to the caller's transport key.
+
+pub struct VetKdManager;
+
+impl VetKdManager {
+    // Local replicas only provision the test key
+    #[cfg(feature = "simulation")]
+    const KEY_NAME: &'static str = "dfx_test_key";
+    #[cfg(not(feature = "simulation"))]
+    const KEY_NAME: &'static str = "key_1";
+
+    // Fee for key_1 on a 34-node subnet; unused cycles are refunded
+    const DERIVE_KEY_CYCLES: u128 = 26_153_846_153;
+    const TRANSPORT_KEY_LENGTH: usize = 48; // Compressed BLS12-381 G1 point
+
+    fn key_id() -> VetKdKeyId {
+        VetKdKeyId {
+            curve: VetKdCurve::Bls12381G2,
+            name: Self::KEY_NAME.to_string(),
+        }
+    }
+
+    // Verification key for `context`, which clients use to check a decrypted vetKey
+    pub async fn public_key(context: &[u8]) -> Result<Vec<u8>, String> {
+        let args = VetKdPublicKeyArgs {
+            canister_id: None,
+            context: context.to_vec(),
+            key_id: Self::key_id(),
+        };
+        let result: Result<(VetKdPublicKeyResult,), _> =
+            ic_cdk::call(Principal::management_canister(), "vetkd_public_key", (args,)).await;
+        match result {
+            Ok((result,)) => Ok(result.public_key),
+            Err((code, message)) => Err(format!("vetkd_public_key failed ({:?}): {}", code, message)),
+        }
+    }
+
+    // The key for (context, input), encrypted to `transport_public_key`
+    pub async fn derive_key(context: &[u8], input: &[u8], transport_public_key: Vec<u8>) -> Result<Vec<u8>, String> {
+        if transport_public_key.len() != Self::TRANSPORT_KEY_LENGTH {
+            return Err(format!("Transport public key must be {} bytes", Self::TRANSPORT_KEY_LENGTH));
+        }
+        let args = VetKdDeriveKeyArgs {
+            input: input.to_vec(),
+            context: context.to_vec(),
+            transport_public_key,
+            key_id: Self::key_id(),
+        };
+        let result: Result<(VetKdDeriveKeyResult,), _> = call_with_payment128(
+            Principal::management_canister(),
+            "vetkd_derive_key",
+            (args,),
+            Self::DERIVE_KEY_CYCLES,
+        )
+        .await;
+        match result {
+            Ok((result,)) => Ok(result.encrypted_key),
+            Err((code, message)) => Err(format!("vetkd_derive_key failed ({:?}): {}", code, message)),
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use crate::http_client;
+use crate::moderation::ModerationManager;
+use crate::monitoring::MonitoringSystem;
+use crate::sanitize::SafeUrl;
+use crate::storage::StorageManager;
+use crate::types::{
+    AlertSeverity, CommunityAlert, RegistryResult, SecurityEvent, WebhookEvent, WebhookPayload,
+    WebhookSubscription, WebhookTarget,
+};
+use candid::Principal;
+use ic_cdk::api::management_canister::http_request::{
+    CanisterHttpRequestArgument, HttpHeader, HttpMethod, HttpResponse, TransformArgs, TransformContext,
+};
+use ic_cdk::api::time;
+use std::time::Duration;
+
+// Push delivery for community alerts and high-severity security events. New alerts and
+// events go into one outbox; each subscription keeps its own position in it and is sent
+// the entries it may see, in order, as an HTTPS POST. Like the cache hook, the position only moves once the target accepts a batch,
+// so a failing target is retried with backoff. The outbox is capped, so a target that
+// stays down long enough misses the oldest entries rather than holding everyone back.
+//
+// Every replica sends an HTTPS delivery, so receivers should dedupe on the
+// Idempotency-Key header (subscription id and last sequence number).
+//
+// Canister targets are not accepted: a call to a user-chosen canister that never replies
+// would hold the registry's call context open, blocking stop and upgrade for good.
+// Subscriptions stored with one before that rule are kept but fail every delivery.
+
+pub struct WebhookManager;
+
+impl WebhookManager {
+    const DELIVERY_INTERVAL_SECS: u64 = 30;
+    const MAX_SUBSCRIPTIONS_PER_PRINCIPAL: usize = 5;
+    const MAX_COMPANIES_PER_SUBSCRIPTION: usize = 50;
+    const MAX_OUTBOX_EVENTS: u64 = 10_000;
+    const MAX_BATCH_EVENTS: usize = 20;
+    const MAX_ERROR_LENGTH: usize = 200;
+    const BASE_BACKOFF_NS: u64 = 30_000_000_000; // 30 seconds
+    const MAX_BACKOFF_NS: u64 = 60 * 60 * 1_000_000_000; // 1 hour
+    const HTTPS_CYCLES: u128 = 20_000_000_000;
+    const CANISTER_TARGET_ERROR: &'static str = "Webhooks are delivered over HTTPS only; canister targets are not supported";
+
+    pub fn start_timer() {
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(Self::DELIVERY_INTERVAL_SECS), || {
+            for subscription in StorageManager::get_all_webhook_subscriptions() {
+                ic_cdk::spawn(async move {
+                    Self::deliver_pending(subscription.id).await;
+                });
+            }
+            Self::prune_outbox();
+        });
+    }
+
+    // Called by MonitoringSystem::raise_alert for each new alert
+    pub fn enqueue_alert(alert: &CommunityAlert) {
+        Self::enqueue(WebhookPayload::Alert(alert.clone()));
+    }
+
+    // Called by SecurityLog for events it rates high severity
+    pub fn enqueue_security_event(event: &SecurityEvent) {
+        Self::enqueue(WebhookPayload::Security(event.clone()));
+    }
+
+    fn enqueue(payload: WebhookPayload) {
+        // Nobody would ever read it
+        if StorageManager::webhook_subscription_count() == 0 {
+            return;
+        }
+        StorageManager::append_webhook_event(
+            WebhookEvent {
+                seq: 0,
+                payload,
+                created_at: time(),
+            },
+            Self::MAX_OUTBOX_EVENTS,
+        );
+    }
+
+    fn validate_target(target: WebhookTarget) -> Result<WebhookTarget, String> {
+        match target {
+            WebhookTarget::Https { url } => {
+                let url = SafeUrl::parse(&url, "Webhook URL")?;
+                Ok(WebhookTarget::Https { url: url.into_string() })
+            }
+            WebhookTarget::Canister { .. } => Err(Self::CANISTER_TARGET_ERROR.to_string()),
+        }
+    }
+
+    pub fn register_webhook(
+        target: WebhookTarget,
+        company_ids: Vec<String>,
+        min_severity: AlertSeverity,
+        include_security_events: bool,
+        caller_principal: Principal,
+    ) -> RegistryResult<WebhookSubscription> {
+        if caller_principal == Principal::anonymous() {
+            return RegistryResult::Err("Anonymous principals cannot register webhooks".to_string());
+        }
+        if include_security_events && !ModerationManager::is_moderator(caller_principal) {
+            return RegistryResult::Err("Only moderators can receive security events".to_string());
+        }
+        let target = match Self::validate_target(target) {
+            Ok(target) => target,
+            Err(err) => return RegistryResult::Err(err),
+        };
+        if company_ids.len() > Self::MAX_COMPANIES_PER_SUBSCRIPTION {
+            return RegistryResult::Err(format!(
+                "A webhook can follow at most {} companies",
+                Self::MAX_COMPANIES_PER_SUBSCRIPTION
+            ));
+        }
+        if let Some(missing) = company_ids.iter().find(|id| StorageManager::get_company(id).is_none()) {
+            return RegistryResult::Err(format!("Company {} not found", missing));
+        }
+        let owned = StorageManager::get_all_webhook_subscriptions()
+            .into_iter()
+            .filter(|subscription| subscription.owner == caller_principal)
+            .count();
+        if owned >= Self::MAX_SUBSCRIPTIONS_PER_PRINCIPAL {
+            return RegistryResult::Err(format!(
+                "At most {} webhooks per principal",
+                Self::MAX_SUBSCRIPTIONS_PER_PRINCIPAL
+            ));
+        }
+
+        let mut company_ids = company_ids;
+        company_ids.sort();
+        company_ids.dedup();
+        let now = time();
+        let subscription = WebhookSubscription {
+            id: format!("webhook_{}_{}", now, StorageManager::webhook_subscription_count()),
+            owner: caller_principal,
+            target,
+            company_ids,
+            min_severity,
+            include_security_events,
+            created_at: now,
+            // Start from now; earlier alerts can be read through the query endpoints
+            delivered_seq: StorageManager::latest_webhook_event_seq(),
+            consecutive_failures: 0,
+            last_error: None,
+            next_attempt_at: 0,
+            last_delivered_at: None,
+        };
+        StorageManager::insert_webhook_subscription(subscription.clone());
+        RegistryResult::Ok(subscription)
+    }
+
+    pub fn remove_webhook(id: String, caller_principal: Principal) -> RegistryResult<()> {
+        match StorageManager::get_webhook_subscription(&id) {
+            Some(subscription)
+                if subscription.owner == caller_principal || ModerationManager::is_moderator(caller_principal) =>
+            {
+                StorageManager::remove_webhook_subscription(&id);
+                RegistryResult::Ok(())
+            }
+            Some(_) => RegistryResult::Err("Unauthorized: Only the owner can remove this webhook".to_string()),
+            None => RegistryResult::Err("Webhook not found".to_string()),
+        }
+    }
+
+    pub fn get_my_webhooks(caller_principal: Principal) -> Vec<WebhookSubscription> {
+        StorageManager::get_all_webhook_subscriptions()
+            .into_iter()
+            .filter(|subscription| subscription.owner == caller_principal)
+            .collect()
+    }
+
+    // Visibility is checked at delivery, so a principal that loses moderator rights stops
+    // receiving what only moderators may see
+    fn wants(subscription: &WebhookSubscription, event: &WebhookEvent) -> bool {
+        match &event.payload {
+            WebhookPayload::Alert(alert) => {
+                alert.severity >= subscription.min_severity
+                    && (subscription.company_ids.is_empty() || subscription.company_ids.contains(&alert.company_id))
+                    && MonitoringSystem::can_view_alert(alert, subscription.owner)
+            }
+            WebhookPayload::Security(_) => {
+                subscription.include_security_events && ModerationManager::is_moderator(subscription.owner)
+            }
+        }
+    }
+
+    // Sends at most one batch; returns the number of events the target accepted
+    pub async fn deliver_pending(id: String) -> u32 {
+        let subscription = match StorageManager::get_webhook_subscription(&id) {
+            Some(subscription) if time() >= subscription.next_attempt_at => subscription,
+            _ => return 0,
+        };
+        let scanned = StorageManager::get_webhook_events_after(subscription.delivered_seq, Self::MAX_BATCH_EVENTS * 10);
+        let last_scanned = match scanned.last() {
+            Some(event) => event.seq,
+            None => return 0,
+        };
+        let mut events: Vec<WebhookEvent> = scanned
+            .into_iter()
+            .filter(|event| Self::wants(&subscription, event))
+            .collect();
+        events.truncate(Self::MAX_BATCH_EVENTS);
+        // Everything up to the last event sent, or the whole scan if none matched
+        let last_seq = events.last().map(|event| event.seq).unwrap_or(last_scanned);
+        if events.is_empty() {
+            let mut current = subscription;
+            current.delivered_seq = last_seq;
+            StorageManager::insert_webhook_subscription(current);
+            return 0;
+        }
+        let event_count = events.len() as u32;
+        if !StorageManager::try_begin_webhook_delivery(&id) {
+            return 0;
+        }
+
+        let result = match &subscription.target {
+            WebhookTarget::Https { url } => Self::post(url, &subscription.id, last_seq, &events).await,
+            WebhookTarget::Canister { .. } => Err(Self::CANISTER_TARGET_ERROR.to_string()),
+        };
+        StorageManager::end_webhook_delivery(&id);
+
+        // The subscription may have been removed while the delivery was out
+        let mut current = match StorageManager::get_webhook_subscription(&id) {
+            Some(current) => current,
+            None => return 0,
+        };
+        let now = time();
+        match result {
+            Ok(()) => {
+                current.delivered_seq = last_seq;
+                current.consecutive_failures = 0;
+                current.last_error = None;
+                current.next_attempt_at = 0;
+                current.last_delivered_at = Some(now);
+                StorageManager::insert_webhook_subscription(current);
+                event_count
+            }
+            Err(error) => {
+                current.consecutive_failures += 1;
+                current.last_error = Some(error.chars().take(Self::MAX_ERROR_LENGTH).collect());
+                let backoff = Self::BASE_BACKOFF_NS
+                    .saturating_mul(1u64 << current.consecutive_failures.min(16))
+                    .min(Self::MAX_BACKOFF_NS);
+                current.next_attempt_at = now.saturating_add(backoff);
+                StorageManager::insert_webhook_subscription(current);
+                0
+            }
+        }
+    }
+
+    async fn post(url: &str, subscription_id: &str, last_seq: u64, events: &[WebhookEvent]) -> Result<(), String> {
+        let body = serde_json::to_vec(&serde_json::json!({ "events": events }))
+            .map_err(|err| format!("Failed to encode events: {}", err))?;
+        let request = CanisterHttpRequestArgument {
+            url: url.to_string(),
+            method: HttpMethod::POST,
+            body: Some(body),
+            max_response_bytes: Some(1024),
+            transform: Some(TransformContext::from_name("transform_webhook_response".to_string(), vec![])),
+            headers: vec![
+                HttpHeader {
+                    name: "Content-Type".to_string(),
+                    value: "application/json".to_string(),
+                },
+                HttpHeader {
+                    name: "Idempotency-Key".to_string(),
+                    value: format!("{}-{}", subscription_id, last_seq),
+                },
+                HttpHeader {
+                    name: "User-Agent".to_string(),
+                    value: "ICP-CrossChainRegistry-Webhooks/1.0".to_string(),
+                },
+            ],
+        };
+        let response = http_client::send(request, Self::HTTPS_CYCLES).await?;
+        if response.status >= 200u32 && response.status < 300u32 {
+            Ok(())
+        } else {
+            Err(format!("HTTP status: {}", response.status))
+        }
+    }
+
+    // Entries every subscription has accepted are no longer needed
+    fn prune_outbox() {
+        let delivered = StorageManager::get_all_webhook_subscriptions()
+            .into_iter()
+            .map(|subscription| subscription.delivered_seq)
+            .min()
+            .unwrap_or_else(StorageManager::latest_webhook_event_seq);
+        StorageManager::prune_webhook_events(delivered);
+    }
+}
+
+// Receivers answer differently per replica (dates, request ids); only the status matters
+pub fn transform_webhook_response(raw: TransformArgs) -> HttpResponse {
+    HttpResponse {
+        status: raw.response.status.clone(),
+        body: vec![],
+        headers: vec![],
+    }
+}
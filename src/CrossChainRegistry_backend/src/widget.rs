@@ -0,0 +1,119 @@
+use crate::sitemap::SitemapManager;
+use crate::storage::StorageManager;
+use crate::types::{HttpRequest, HttpResponse, ProofStatus};
+use crate::verification::VerificationManager;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+
+// Trust badge data at /widget/<company_id>.json for third-party sites. Only what a badge
+// renders, served with open CORS so it can be fetched from any page. Like the open data
+// snapshot the response is not certified; the SHA-256 in ETag and Repr-Digest lets an
+// embedder pin what it rendered.
+
+pub struct WidgetManager;
+
+impl WidgetManager {
+    const PATH_PREFIX: &'static str = "/widget/";
+    const PATH_SUFFIX: &'static str = ".json";
+    const SCHEMA_VERSION: u32 = 1;
+    const CACHE_MAX_AGE_SECS: u64 = 5 * 60;
+
+    pub fn is_widget_path(url: &str) -> bool {
+        let path = url.split('?').next().unwrap_or("");
+        path.starts_with(Self::PATH_PREFIX) && path.ends_with(Self::PATH_SUFFIX)
+    }
+
+    pub fn http_request(request: HttpRequest) -> HttpResponse {
+        if request.method == "OPTIONS" {
+            let mut response = Self::json_response(204, Vec::new());
+            response.headers.push(("Access-Control-Allow-Methods".to_string(), "GET, HEAD".to_string()));
+            response.headers.push(("Access-Control-Allow-Headers".to_string(), "If-None-Match".to_string()));
+            return response;
+        }
+        if request.method != "GET" && request.method != "HEAD" {
+            return Self::json_error(405, "Method not allowed");
+        }
+
+        let path = request.url.split('?').next().unwrap_or("");
+        let company_id = &path[Self::PATH_PREFIX.len()..path.len() - Self::PATH_SUFFIX.len()];
+        let company = match StorageManager::get_company(company_id) {
+            Some(company) if !company_id.is_empty() => company,
+            _ => return Self::json_error(404, "Company not found"),
+        };
+
+        let last_verified_at = company
+            .web3_identity
+            .verification_proofs
+            .iter()
+            .filter(|proof| proof.status == ProofStatus::Active)
+            .map(|proof| proof.verified_at)
+            .max();
+        let widget = serde_json::json!({
+            "schema_version": Self::SCHEMA_VERSION,
+            "id": company.id,
+            "name": company.basic_info.name,
+            "status": company.status,
+            "verification_score": company.verification_score,
+            "badges": VerificationManager::completed_badges(&company.id),
+            "last_verified_at": last_verified_at,
+            "profile_url": SitemapManager::profile_url(&company.id),
+        });
+        // No generation time in the body, so unchanged data keeps its ETag
+        let json = widget.to_string().into_bytes();
+        let digest = Sha256::digest(&json);
+        let sha256: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+        let etag = format!("\"{}\"", sha256);
+
+        let not_modified = request
+            .headers
+            .iter()
+            .any(|(name, value)| name.eq_ignore_ascii_case("if-none-match") && value.trim() == etag);
+        let (status_code, body) = if not_modified {
+            (304, Vec::new())
+        } else if request.method == "HEAD" {
+            (200, Vec::new())
+        } else {
+            (200, json)
+        };
+
+        HttpResponse {
+            status_code,
+            headers: vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("Cache-Control".to_string(), format!("public, max-age={}", Self::CACHE_MAX_AGE_SECS)),
+                ("Access-Control-Allow-Origin".to_string(), "*".to_string()),
+                ("Access-Control-Expose-Headers".to_string(), "ETag, Repr-Digest, X-Content-SHA256".to_string()),
+                ("ETag".to_string(), etag),
+                (
+                    "Repr-Digest".to_string(),
+                    format!("sha-256=:{}:", base64::engine::general_purpose::STANDARD.encode(digest)),
+                ),
+                ("X-Content-SHA256".to_string(), sha256),
+                ("X-Content-Type-Options".to_string(), "nosniff".to_string()),
+            ],
+            body,
+            upgrade: None,
+        }
+    }
+
+    fn json_response(status_code: u16, body: Vec<u8>) -> HttpResponse {
+        HttpResponse {
+            status_code,
+            headers: vec![
+                    ("Content-Type".to_string(), "application/json".to_string()),
+                    ("Access-Control-Allow-Origin".to_string(), "*".to_string()),
+            ],
+            body,
+            upgrade: None,
+        }
+    }
+
+    fn json_error(status_code: u16, message: &str) -> HttpResponse {
+        let mut response = Self::json_response(
+            status_code,
+            serde_json::json!({ "error": message }).to_string().into_bytes(),
+        );
+        response.headers.push(("Cache-Control".to_string(), "no-store".to_string()));
+        response
+    }
+}
@@ -0,0 +1,674 @@
+// PocketIC integration suite. Drives the `simulation` build, where every HTTPS outcall is
+// answered from fixtures set through set_simulation_fixture, so each verifier can be
+// exercised end to end without reaching GitHub, DNS resolvers or chain RPCs.
+//
+// Needs the simulation wasm and a PocketIC server; test/run_integration.sh builds the one
+// and points POCKET_IC_BIN at the other, then runs these with --ignored.
+
+use candid::{decode_one, encode_args, encode_one, CandidType, Deserialize, Nat, Principal, Reserved};
+use candid_parser::utils::{instantiate_candid, CandidSource};
+use k256::ecdsa::SigningKey;
+use pocket_ic::PocketIc;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+use std::time::Duration;
+
+const DID: &str = include_str!("../CrossChainRegistry_backend.did");
+const WASM_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../target/wasm32-unknown-unknown/release/cross_chain_registry_backend.wasm"
+);
+
+// Only the fields the tests look at; Candid skips the rest
+#[derive(CandidType, Deserialize, Debug)]
+enum RegistryResult<T> {
+    Ok(T),
+    Err(String),
+}
+
+impl<T: std::fmt::Debug> RegistryResult<T> {
+    fn unwrap(self) -> T {
+        match self {
+            RegistryResult::Ok(value) => value,
+            RegistryResult::Err(err) => panic!("registry returned an error: {}", err),
+        }
+    }
+
+    fn unwrap_err(self) -> String {
+        match self {
+            RegistryResult::Ok(value) => panic!("expected an error, got {:?}", value),
+            RegistryResult::Err(err) => err,
+        }
+    }
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+struct Company {
+    id: String,
+    basic_info: BasicInfo,
+    web3_identity: Web3Identity,
+    cross_chain_presence: CrossChainPresence,
+    verification_score: u32,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+struct BasicInfo {
+    name: String,
+    description: String,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+struct Web3Identity {
+    domain_verified: bool,
+    github_org: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+struct CrossChainPresence {
+    ethereum_contracts: Vec<String>,
+    bitcoin_addresses: Vec<String>,
+    solana_addresses: Vec<String>,
+    ton_addresses: Vec<String>,
+    icp_canisters: Vec<String>,
+    treasury_wallets: Vec<WalletInfo>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+struct WalletInfo {
+    chain: String,
+    address: String,
+    verified: bool,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+struct VerificationResult {
+    success: bool,
+    message: String,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+struct CrossChainChallenge {
+    challenge_message: String,
+    nonce: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+struct DomainVerificationChallenge {
+    domain: String,
+    challenge_token: String,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+struct CanisterSnapshot {
+    canister_id: String,
+    controllers: Vec<Principal>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+struct TreasurySnapshot {
+    address: String,
+    samples: Vec<TreasuryBalanceSample>,
+    last_error: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+struct TreasuryBalanceSample {
+    balance: Nat,
+}
+
+#[derive(CandidType)]
+struct SimulationFixture {
+    url_prefix: String,
+    body_contains: Option<String>,
+    status: u16,
+    body: String,
+}
+
+struct Registry {
+    pic: PocketIc,
+    canister: Principal,
+    wasm: Vec<u8>,
+}
+
+fn controller() -> Principal {
+    Principal::self_authenticating(b"controller")
+}
+
+fn owner() -> Principal {
+    Principal::self_authenticating(b"owner")
+}
+
+fn stranger() -> Principal {
+    Principal::self_authenticating(b"stranger")
+}
+
+// Encodes Candid text against the service's own types, the way dfx does for
+// `dfx canister call`; `None` picks the init arguments
+fn encode_text(method: Option<&str>, text: &str) -> Vec<u8> {
+    let (init_types, (env, service)) = instantiate_candid(CandidSource::Text(DID)).expect("parse the .did");
+    let types = match method {
+        Some(method) => env.get_method(&service, method).expect("method in the .did").args.clone(),
+        None => init_types,
+    };
+    candid_parser::parse_idl_args(text)
+        .expect("parse the Candid arguments")
+        .to_bytes_with_types(&env, &types)
+        .expect("encode the Candid arguments")
+}
+
+impl Registry {
+    fn new() -> Self {
+        let wasm_path = std::env::var("REGISTRY_WASM").unwrap_or_else(|_| WASM_PATH.to_string());
+        let wasm = std::fs::read(&wasm_path)
+            .unwrap_or_else(|err| panic!("read {} ({}); build it with --features simulation", wasm_path, err));
+
+        let pic = PocketIc::new();
+        let canister = pic.create_canister_with_settings(Some(controller()), None);
+        pic.add_cycles(canister, 100_000_000_000_000);
+        pic.install_canister(canister, wasm.clone(), encode_text(None, "(null)"), Some(controller()));
+
+        let registry = Registry { pic, canister, wasm };
+        registry.update::<RegistryResult<()>>(controller(), "set_simulation_mode", encode_one(true).unwrap()).unwrap();
+        registry
+    }
+
+    fn update<T: CandidType + for<'de> Deserialize<'de>>(&self, sender: Principal, method: &str, arg: Vec<u8>) -> T {
+        let reply = self
+            .pic
+            .update_call(self.canister, sender, method, arg)
+            .unwrap_or_else(|err| panic!("{} rejected: {:?}", method, err));
+        decode_one(&reply).unwrap_or_else(|err| panic!("decode {} reply: {}", method, err))
+    }
+
+    fn query<T: CandidType + for<'de> Deserialize<'de>>(&self, method: &str, arg: Vec<u8>) -> T {
+        let reply = self
+            .pic
+            .query_call(self.canister, stranger(), method, arg)
+            .unwrap_or_else(|err| panic!("{} rejected: {:?}", method, err));
+        decode_one(&reply).unwrap_or_else(|err| panic!("decode {} reply: {}", method, err))
+    }
+
+    fn fixture(&self, url_prefix: &str, body_contains: Option<&str>, status: u16, body: serde_json::Value) {
+        let fixture = SimulationFixture {
+            url_prefix: url_prefix.to_string(),
+            body_contains: body_contains.map(|needle| needle.to_string()),
+            status,
+            body: body.to_string(),
+        };
+        self.update::<RegistryResult<()>>(controller(), "set_simulation_fixture", encode_one(fixture).unwrap())
+            .unwrap();
+    }
+
+    // Registered by `owner()` with a treasury wallet on each monitored chain listed
+    fn create_company(&self, name: &str, treasury_wallets: &[(&str, &str)]) -> String {
+        let wallets: Vec<String> = treasury_wallets
+            .iter()
+            .map(|(chain, address)| {
+                format!(
+                    "record {{ chain = \"{}\"; address = \"{}\"; wallet_type = \"treasury\"; verified = false }}",
+                    chain, address
+                )
+            })
+            .collect();
+        let request = format!(
+            r#"(record {{
+                basic_info = record {{
+                    name = "{}";
+                    description = "Cross-chain tooling used by the integration suite";
+                    website = "https://integration-labs.example.org";
+                    founding_date = "2021-01-01";
+                    team_size = 5 : nat32;
+                    focus_areas = vec {{ "Infrastructure" }};
+                }};
+                web3_identity = record {{
+                    github_org = null;
+                    twitter_handle = null;
+                    discord_server = null;
+                    telegram_channel = null;
+                    domain_verified = false;
+                    social_verification_status = variant {{ Pending }};
+                    verification_proofs = vec {{}};
+                }};
+                cross_chain_presence = record {{
+                    ethereum_contracts = vec {{}};
+                    bitcoin_addresses = vec {{}};
+                    icp_canisters = vec {{}};
+                    polygon_contracts = vec {{}};
+                    solana_addresses = vec {{}};
+                    sui_addresses = vec {{}};
+                    ton_addresses = vec {{}};
+                    treasury_wallets = vec {{ {} }};
+                    token_contracts = vec {{}};
+                }};
+                team_members = vec {{}};
+                entity_type = null;
+            }})"#,
+            name,
+            wallets.join("; ")
+        );
+        self.update::<RegistryResult<String>>(owner(), "create_company", encode_text(Some("create_company"), &request))
+            .unwrap()
+    }
+
+    fn get_company(&self, company_id: &str) -> RegistryResult<Company> {
+        self.query("get_company", encode_args((company_id, None::<Vec<Reserved>>)).unwrap())
+    }
+
+    fn create_challenge(&self, company_id: &str, chain: &str, address: &str) -> RegistryResult<CrossChainChallenge> {
+        let request = format!(
+            r#"(record {{
+                company_id = "{}";
+                chain_type = variant {{ {} }};
+                address_or_contract = "{}";
+                verification_method = variant {{ SignMessage = record {{ message = "caller text must be ignored" }} }};
+            }})"#,
+            company_id, chain, address
+        );
+        self.update(
+            owner(),
+            "create_crosschain_challenge",
+            encode_text(Some("create_crosschain_challenge"), &request),
+        )
+    }
+
+    fn verify(&self, method: &str, arg: Vec<u8>) -> RegistryResult<VerificationResult> {
+        self.update(owner(), method, arg)
+    }
+}
+
+// personal_sign: 0x-prefixed r || s || v, v = 27 + recovery id
+fn eth_personal_sign(key: &SigningKey, message: &str) -> String {
+    let prefixed = format!("\x19Ethereum Signed Message:\n{}{}", message.len(), message);
+    let digest = Keccak256::digest(prefixed.as_bytes());
+    let (signature, recovery_id) = key.sign_prehash_recoverable(&digest).unwrap();
+    let mut bytes = signature.to_bytes().to_vec();
+    bytes.push(27 + recovery_id.to_byte());
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn eth_address(key: &SigningKey) -> String {
+    let public_key = key.verifying_key().to_encoded_point(false);
+    let hash = Keccak256::digest(&public_key.as_bytes()[1..]);
+    format!("0x{}", hex::encode(&hash[12..]))
+}
+
+// BIP-137 signature for a compressed-key P2PKH address, base64 as wallets export it
+fn btc_sign_message(key: &SigningKey, message: &str) -> String {
+    assert!(message.len() < 0xfd, "single-byte varint only");
+    let mut preimage = b"\x18Bitcoin Signed Message:\n".to_vec();
+    preimage.push(message.len() as u8);
+    preimage.extend_from_slice(message.as_bytes());
+    let digest = Sha256::digest(Sha256::digest(&preimage));
+    let (signature, recovery_id) = key.sign_prehash_recoverable(&digest).unwrap();
+    let mut bytes = vec![27 + 4 + recovery_id.to_byte()];
+    bytes.extend_from_slice(&signature.to_bytes());
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+fn btc_p2pkh_address(key: &SigningKey) -> String {
+    let public_key = key.verifying_key().to_encoded_point(true);
+    let hash160 = ripemd::Ripemd160::digest(Sha256::digest(public_key.as_bytes()));
+    let mut payload = vec![0x00];
+    payload.extend_from_slice(&hash160);
+    bs58::encode(payload).with_check().into_string()
+}
+
+fn signing_key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32].into()).unwrap()
+}
+
+#[test]
+#[ignore = "needs the simulation wasm and a PocketIC server; run test/run_integration.sh"]
+fn company_crud_survives_an_upgrade() {
+    let registry = Registry::new();
+    let company_id = registry.create_company("Integration Labs", &[]);
+    let company = registry.get_company(&company_id).unwrap();
+    assert_eq!(company.id, company_id);
+    assert_eq!(company.basic_info.name, "Integration Labs");
+    assert_eq!(registry.query::<u64>("get_company_count", encode_args(()).unwrap()), 1);
+
+    let update = format!(
+        r#"(record {{
+            company_id = "{}";
+            basic_info = opt record {{
+                name = "Integration Labs";
+                description = "Updated through update_company";
+                website = "https://integration-labs.example.org";
+                founding_date = "2021-01-01";
+                team_size = 6 : nat32;
+                focus_areas = vec {{ "Infrastructure" }};
+            }};
+            web3_identity = null;
+            cross_chain_presence = null;
+            team_members = null;
+            entity_type = null;
+        }})"#,
+        company_id
+    );
+    let update = encode_text(Some("update_company"), &update);
+    let denied: RegistryResult<()> = registry.update(stranger(), "update_company", update.clone());
+    assert!(denied.unwrap_err().contains("Unauthorized"));
+    registry.update::<RegistryResult<()>>(owner(), "update_company", update).unwrap();
+
+    // A pending challenge and the profile both live in stable memory
+    let challenge = registry.create_challenge(&company_id, "Ethereum", &eth_address(&signing_key(1))).unwrap();
+    registry
+        .pic
+        .upgrade_canister(registry.canister, registry.wasm.clone(), encode_text(None, "(null)"), Some(controller()))
+        .unwrap();
+
+    let company = registry.get_company(&company_id).unwrap();
+    assert_eq!(company.basic_info.description, "Updated through update_company");
+    assert_eq!(registry.query::<u64>("get_company_count", encode_args(()).unwrap()), 1);
+    let challenges: Vec<CrossChainChallenge> =
+        registry.query("get_crosschain_challenges_for_company", encode_one(&company_id).unwrap());
+    assert_eq!(challenges.len(), 1);
+    assert_eq!(challenges[0].nonce, challenge.nonce);
+
+    let denied: RegistryResult<Reserved> = registry.update(stranger(), "delete_company", encode_one(&company_id).unwrap());
+    denied.unwrap_err();
+    registry.update::<RegistryResult<Reserved>>(owner(), "delete_company", encode_one(&company_id).unwrap()).unwrap();
+    assert!(registry.get_company(&company_id).unwrap_err().contains("not found"));
+    assert_eq!(registry.query::<u64>("get_company_count", encode_args(()).unwrap()), 0);
+}
+
+#[test]
+#[ignore = "needs the simulation wasm and a PocketIC server; run test/run_integration.sh"]
+fn crosschain_challenges_carry_a_nonce_and_expire() {
+    let registry = Registry::new();
+    let company_id = registry.create_company("Challenge Labs", &[]);
+    let key = signing_key(2);
+    let address = eth_address(&key);
+
+    let challenge = registry.create_challenge(&company_id, "Ethereum", &address).unwrap();
+    let nonce = challenge.nonce.clone().expect("registry-issued nonce");
+    assert!(nonce.starts_with("ccr-"));
+    assert!(challenge.challenge_message.contains(&nonce));
+    assert!(challenge.challenge_message.contains(&company_id));
+    assert!(!challenge.challenge_message.contains("caller text"));
+
+    let other = eth_address(&signing_key(3));
+    let missing = registry.verify(
+        "verify_ethereum_contract",
+        encode_args((&company_id, &other, None::<String>, Some("0x00".to_string()))).unwrap(),
+    );
+    assert_eq!(missing.unwrap_err(), "Challenge not found");
+
+    registry.pic.advance_time(Duration::from_secs(49 * 60 * 60));
+    let signature = eth_personal_sign(&key, &challenge.challenge_message);
+    let expired = registry.verify(
+        "verify_ethereum_contract",
+        encode_args((&company_id, &address, None::<String>, Some(signature))).unwrap(),
+    );
+    assert!(expired.unwrap_err().contains("expired"));
+}
+
+#[test]
+#[ignore = "needs the simulation wasm and a PocketIC server; run test/run_integration.sh"]
+fn github_verification_reads_the_mocked_api() {
+    let registry = Registry::new();
+    let company_id = registry.create_company("GitHub Labs", &[]);
+
+    registry.fixture("https://api.github.com/orgs/ghost-labs", None, 404, serde_json::json!({ "message": "Not Found" }));
+    let missing = registry
+        .verify("verify_github_organization", encode_args((&company_id, "ghost-labs")).unwrap())
+        .unwrap();
+    assert!(!missing.success);
+    assert!(missing.message.contains("not found"));
+
+    registry.fixture(
+        "https://api.github.com/orgs/integration-labs",
+        None,
+        200,
+        serde_json::json!({
+            "login": "integration-labs", "id": 42, "name": "Integration Labs",
+            "blog": "https://integration-labs.example.org", "location": null, "email": null,
+            "public_repos": 3, "followers": 10, "created_at": "2021-01-01T00:00:00Z",
+        }),
+    );
+    let verified = registry
+        .verify("verify_github_organization", encode_args((&company_id, "integration-labs")).unwrap())
+        .unwrap();
+    assert!(verified.success, "{}", verified.message);
+    let company = registry.get_company(&company_id).unwrap();
+    assert_eq!(company.web3_identity.github_org.as_deref(), Some("integration-labs"));
+}
+
+#[test]
+#[ignore = "needs the simulation wasm and a PocketIC server; run test/run_integration.sh"]
+fn domain_verification_reads_the_mocked_resolvers() {
+    let registry = Registry::new();
+    let company_id = registry.create_company("Domain Labs", &[]);
+    let challenge: DomainVerificationChallenge = registry
+        .update::<RegistryResult<DomainVerificationChallenge>>(
+            owner(),
+            "create_domain_verification_challenge",
+            encode_one(&company_id).unwrap(),
+        )
+        .unwrap();
+    assert_eq!(challenge.domain, "integration-labs.example.org");
+
+    let answer = serde_json::json!({
+        "Status": 0,
+        "Answer": [{ "name": challenge.domain, "type": 16, "data": format!("\"{}\"", challenge.challenge_token) }],
+    });
+    registry.fixture("https://dns.google/", None, 200, answer.clone());
+    registry.fixture("https://cloudflare-dns.com/", None, 200, answer);
+
+    let verified = registry.verify("verify_domain_ownership", encode_one(&company_id).unwrap()).unwrap();
+    assert!(verified.success, "{}", verified.message);
+    assert!(registry.get_company(&company_id).unwrap().web3_identity.domain_verified);
+}
+
+#[test]
+#[ignore = "needs the simulation wasm and a PocketIC server; run test/run_integration.sh"]
+fn ethereum_accepts_a_signature_or_the_exact_nonce_transaction() {
+    let registry = Registry::new();
+    let company_id = registry.create_company("Ethereum Labs", &[]);
+
+    // personal_sign over the registry-built message
+    let key = signing_key(4);
+    let address = eth_address(&key);
+    let challenge = registry.create_challenge(&company_id, "Ethereum", &address).unwrap();
+    let signature = eth_personal_sign(&key, &challenge.challenge_message);
+    let verified = registry
+        .verify("verify_ethereum_contract", encode_args((&company_id, &address, None::<String>, Some(signature))).unwrap())
+        .unwrap();
+    assert!(verified.success, "{}", verified.message);
+
+    // A transaction proof, answered by both RPC endpoints so the quorum agrees
+    let address = eth_address(&signing_key(5));
+    let challenge = registry.create_challenge(&company_id, "Ethereum", &address).unwrap();
+    let nonce_hex = format!("0x{}", hex::encode(challenge.nonce.unwrap()));
+    let message_hex = format!("0x{}", hex::encode(&challenge.challenge_message));
+    let transaction = |hash: &str, from: &str, input: &str| {
+        registry.fixture(
+            "https://",
+            Some(hash),
+            200,
+            serde_json::json!({
+                "jsonrpc": "2.0", "id": 1,
+                "result": { "hash": hash, "from": from, "to": address, "input": input, "blockNumber": "0x10" },
+            }),
+        );
+    };
+    registry.fixture("https://", Some("eth_call"), 200, serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": "0x" }));
+
+    let whole_message = format!("0x{}", "a".repeat(64));
+    transaction(&whole_message, &address, &message_hex);
+    let rejected = registry
+        .verify("verify_ethereum_contract", encode_args((&company_id, &address, Some(&whole_message), None::<String>)).unwrap())
+        .unwrap();
+    assert!(!rejected.success);
+    assert!(rejected.message.contains("not the challenge nonce"));
+
+    let other_sender = format!("0x{}", "b".repeat(64));
+    transaction(&other_sender, &eth_address(&signing_key(6)), &nonce_hex);
+    let rejected = registry
+        .verify("verify_ethereum_contract", encode_args((&company_id, &address, Some(&other_sender), None::<String>)).unwrap())
+        .unwrap();
+    assert!(!rejected.success);
+    assert!(rejected.message.contains("neither"));
+
+    let exact = format!("0x{}", "c".repeat(64));
+    transaction(&exact, &address, &nonce_hex);
+    let verified = registry
+        .verify("verify_ethereum_contract", encode_args((&company_id, &address, Some(&exact), None::<String>)).unwrap())
+        .unwrap();
+    assert!(verified.success, "{}", verified.message);
+    assert_eq!(registry.get_company(&company_id).unwrap().cross_chain_presence.ethereum_contracts.len(), 2);
+}
+
+#[test]
+#[ignore = "needs the simulation wasm and a PocketIC server; run test/run_integration.sh"]
+fn bitcoin_signature_verification_feeds_treasury_monitoring() {
+    let registry = Registry::new();
+    let key = signing_key(7);
+    let address = btc_p2pkh_address(&key);
+    let company_id = registry.create_company("Bitcoin Labs", &[("bitcoin", &address)]);
+
+    let stats = serde_json::json!({
+        "funded_txo_count": 2, "spent_txo_count": 1, "tx_count": 3,
+        "funded_txo_sum": 150_000, "spent_txo_sum": 50_000,
+    });
+    let mempool = serde_json::json!({
+        "funded_txo_count": 0, "spent_txo_count": 0, "tx_count": 0, "funded_txo_sum": 0, "spent_txo_sum": 0,
+    });
+    registry.fixture(
+        "https://blockchain.info/rawaddr/",
+        None,
+        200,
+        serde_json::json!({
+            "address": address, "hash160": "00", "n_tx": 3, "n_unredeemed": 1,
+            "total_received": 150_000, "total_sent": 50_000, "final_balance": 100_000,
+        }),
+    );
+    for explorer in ["https://blockstream.info/api/address/", "https://mempool.space/api/address/"] {
+        registry.fixture(
+            explorer,
+            None,
+            200,
+            serde_json::json!({ "address": address, "chain_stats": stats, "mempool_stats": mempool }),
+        );
+    }
+
+    let challenge = registry.create_challenge(&company_id, "Bitcoin", &address).unwrap();
+    let unsigned = registry
+        .verify("verify_bitcoin_address", encode_args((&company_id, &address, Some(btc_sign_message(&key, "hello")))).unwrap())
+        .unwrap();
+    assert!(!unsigned.success);
+
+    let signature = btc_sign_message(&key, &challenge.challenge_message);
+    let verified = registry
+        .verify("verify_bitcoin_address", encode_args((&company_id, &address, Some(signature))).unwrap())
+        .unwrap();
+    assert!(verified.success, "{}", verified.message);
+    let company = registry.get_company(&company_id).unwrap();
+    assert!(company.cross_chain_presence.treasury_wallets.iter().any(|w| w.address == address && w.verified));
+
+    let alerts = registry.update::<RegistryResult<u32>>(controller(), "run_treasury_monitoring", encode_args(()).unwrap());
+    assert_eq!(alerts.unwrap(), 0);
+    let snapshots: Vec<TreasurySnapshot> = registry.query("get_treasury_snapshots", encode_one(&company_id).unwrap());
+    assert_eq!(snapshots.len(), 1);
+    assert_eq!(snapshots[0].address, address);
+    assert_eq!(snapshots[0].last_error, None);
+    assert_eq!(snapshots[0].samples[0].balance, Nat::from(100_000u64));
+}
+
+#[test]
+#[ignore = "needs the simulation wasm and a PocketIC server; run test/run_integration.sh"]
+fn solana_requires_the_exact_nonce_memo_signed_by_the_address() {
+    let registry = Registry::new();
+    let company_id = registry.create_company("Solana Labs", &[]);
+    let address = "7EcDhSYGxXyscszYEp35KHN8vvw3svAuLKTzXwCFLtV";
+    let challenge = registry.create_challenge(&company_id, "Solana", address).unwrap();
+    let nonce = challenge.nonce.unwrap();
+
+    let signatures = |memo: String| {
+        registry.fixture(
+            "https://",
+            Some("getSignaturesForAddress"),
+            200,
+            serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": [{ "signature": "sig-1", "memo": memo, "err": null }] }),
+        );
+    };
+    registry.fixture(
+        "https://",
+        Some("getTransaction"),
+        200,
+        serde_json::json!({
+            "jsonrpc": "2.0", "id": 1,
+            "result": { "transaction": { "message": {
+                "header": { "numRequiredSignatures": 1 },
+                "accountKeys": [address, "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"],
+            } } },
+        }),
+    );
+
+    // A memo that merely contains the nonce is not a proof
+    signatures(format!("[{}] {}", challenge.challenge_message.len(), challenge.challenge_message));
+    let rejected = registry.verify("verify_solana_address", encode_args((&company_id, address)).unwrap()).unwrap();
+    assert!(!rejected.success);
+
+    signatures(format!("[{}] {}", nonce.len(), nonce));
+    let verified = registry.verify("verify_solana_address", encode_args((&company_id, address)).unwrap()).unwrap();
+    assert!(verified.success, "{}", verified.message);
+    let company = registry.get_company(&company_id).unwrap();
+    assert_eq!(company.cross_chain_presence.solana_addresses, vec![address.to_string()]);
+}
+
+#[test]
+#[ignore = "needs the simulation wasm and a PocketIC server; run test/run_integration.sh"]
+fn ton_requires_the_exact_nonce_comment() {
+    let registry = Registry::new();
+    let company_id = registry.create_company("TON Labs", &[]);
+    let address = format!("EQ{}", "A".repeat(46));
+    let challenge = registry.create_challenge(&company_id, "TON", &address).unwrap();
+    let nonce = challenge.nonce.unwrap();
+
+    let transactions = |comment: String| {
+        registry.fixture(
+            "https://toncenter.com/",
+            None,
+            200,
+            serde_json::json!({ "ok": true, "result": [{ "out_msgs": [{ "message": comment }] }] }),
+        );
+    };
+
+    transactions(format!("registry {}", nonce));
+    let rejected = registry.verify("verify_ton_address", encode_args((&company_id, &address)).unwrap()).unwrap();
+    assert!(!rejected.success);
+
+    transactions(nonce);
+    let verified = registry.verify("verify_ton_address", encode_args((&company_id, &address)).unwrap()).unwrap();
+    assert!(verified.success, "{}", verified.message);
+    assert_eq!(registry.get_company(&company_id).unwrap().cross_chain_presence.ton_addresses, vec![address]);
+}
+
+#[test]
+#[ignore = "needs the simulation wasm and a PocketIC server; run test/run_integration.sh"]
+fn icp_canister_verification_feeds_canister_monitoring() {
+    let registry = Registry::new();
+    let company_id = registry.create_company("ICP Labs", &[]);
+    let canister = registry.pic.create_canister_with_settings(Some(owner()), None);
+    let canister_id = canister.to_text();
+
+    registry.create_challenge(&company_id, "ICP", &canister_id).unwrap();
+    let verified = registry.verify("verify_icp_canister", encode_args((&company_id, &canister_id)).unwrap()).unwrap();
+    assert!(verified.success, "{}", verified.message);
+    assert_eq!(registry.get_company(&company_id).unwrap().cross_chain_presence.icp_canisters, vec![canister_id.clone()]);
+
+    let alerts = registry.update::<RegistryResult<u32>>(controller(), "run_canister_monitoring", encode_args(()).unwrap());
+    assert_eq!(alerts.unwrap(), 0);
+    let snapshots: Vec<CanisterSnapshot> = registry.query("get_canister_snapshots", encode_one(&company_id).unwrap());
+    assert_eq!(snapshots.len(), 1);
+    assert_eq!(snapshots[0].canister_id, canister_id);
+    assert!(snapshots[0].controllers.contains(&owner()));
+
+    // A new controller set means the canister may have changed hands
+    registry.pic.set_controllers(canister, Some(owner()), vec![stranger()]).unwrap();
+    let alerts = registry.update::<RegistryResult<u32>>(controller(), "run_canister_monitoring", encode_args(()).unwrap());
+    assert_eq!(alerts.unwrap(), 1);
+    assert!(registry.get_company(&company_id).unwrap().cross_chain_presence.icp_canisters.is_empty());
+}
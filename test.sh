@@ -44,6 +44,7 @@ show_help() {
     echo "  verification    Verification system tests"
     echo "  errors          Error handling tests"
     echo "  advanced        Advanced feature tests"
+    echo "  integration     Challenge lifecycle and upgrade persistence tests"
     echo "  performance     Performance tests"
     echo
     echo "Test Options (for 'test' command):"
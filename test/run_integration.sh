@@ -0,0 +1,19 @@
+#!/bin/bash
+
+# PocketIC integration suite (src/CrossChainRegistry_backend/tests/integration.rs).
+# Builds the simulation wasm, whose HTTPS outcalls are answered from fixtures, and runs
+# the suite against a PocketIC server. Set POCKET_IC_BIN to a downloaded server binary
+# (https://github.com/dfinity/pocketic/releases); without it pocket-ic fetches one.
+set -e
+
+ROOT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")/.." && pwd)"
+cd "$ROOT_DIR"
+
+echo "🔨 Building the simulation wasm"
+rustup target add wasm32-unknown-unknown >/dev/null
+cargo build --target wasm32-unknown-unknown --release --features simulation -p CrossChainRegistry_backend
+
+export REGISTRY_WASM="$ROOT_DIR/target/wasm32-unknown-unknown/release/cross_chain_registry_backend.wasm"
+
+echo "🧪 Running the PocketIC integration suite"
+cargo test -p CrossChainRegistry_backend --test integration -- --ignored "$@"
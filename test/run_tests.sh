@@ -156,7 +156,7 @@ generate_test_report() {
 EOF
 
     # Add test results for each test type
-    local test_types=("crud" "community" "verification" "errors" "advanced" "performance")
+    local test_types=("crud" "community" "verification" "errors" "advanced" "integration" "performance")
     local overall_status="success"
     
     for test_type in "${test_types[@]}"; do
@@ -317,7 +317,7 @@ run_all_tests() {
     fi
     
     # Run test suites
-    local test_suites=("crud" "community" "verification" "errors" "advanced")
+    local test_suites=("crud" "community" "verification" "errors" "advanced" "integration")
     
     for test_suite in "${test_suites[@]}"; do
         if ! run_test_suite "$test_suite"; then
@@ -368,6 +368,7 @@ show_help() {
     echo "  verification      Run verification system tests only"
     echo "  errors            Run error handling tests only"
     echo "  advanced          Run advanced feature tests only"
+    echo "  integration       Run challenge lifecycle and upgrade persistence tests only"
     echo "  performance       Run performance tests only"
     echo ""
     echo "Examples:"
@@ -401,7 +402,7 @@ while [[ $# -gt 0 ]]; do
             show_help
             exit 0
             ;;
-        crud|community|verification|errors|advanced|performance|all)
+        crud|community|verification|errors|advanced|integration|performance|all)
             TEST_TYPE="$1"
             shift
             ;;